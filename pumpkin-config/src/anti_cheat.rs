@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AntiCheatConfig {
+    /// Whether player movement and interaction reach are validated.
+    pub enabled: bool,
+    /// The squared distance a player may move in a single movement packet, on top of their velocity.
+    pub max_move_squared: f64,
+    /// The same as `max_move_squared`, but while gliding with an elytra.
+    pub max_elytra_move_squared: f64,
+    /// Whether players in survival and adventure mode may fly.
+    pub allow_flight: bool,
+    /// How many ticks a player may float in the air without falling before it counts as flying.
+    pub max_floating_ticks: u32,
+    /// How many extra blocks of reach players get on top of their interaction range, to make up for lag.
+    pub reach_tolerance: f64,
+    /// How many violations get a player kicked. 0 never kicks, violations are only logged then.
+    pub kick_violations: u32,
+}
+
+impl Default for AntiCheatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_move_squared: 100.0,
+            max_elytra_move_squared: 300.0,
+            allow_flight: false,
+            max_floating_ticks: 80,
+            reach_tolerance: 1.0,
+            kick_violations: 0,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AntiXrayConfig {
+    /// Whether hidden blocks are replaced in the chunks sent to players.
+    pub enabled: bool,
+    /// Blocks which are only sent to players when they are exposed to a non full block, like air.
+    pub hidden_blocks: Vec<String>,
+    /// The block sent instead of hidden blocks in the overworld.
+    pub filler_block: String,
+    /// The block sent instead of hidden blocks below y = 0 in the overworld.
+    pub deepslate_filler_block: String,
+    /// The block sent instead of hidden blocks in the nether.
+    pub nether_filler_block: String,
+    /// The block sent instead of hidden blocks in the end.
+    pub end_filler_block: String,
+}
+
+impl Default for AntiXrayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hidden_blocks: [
+                "coal_ore",
+                "deepslate_coal_ore",
+                "iron_ore",
+                "deepslate_iron_ore",
+                "copper_ore",
+                "deepslate_copper_ore",
+                "gold_ore",
+                "deepslate_gold_ore",
+                "redstone_ore",
+                "deepslate_redstone_ore",
+                "emerald_ore",
+                "deepslate_emerald_ore",
+                "lapis_ore",
+                "deepslate_lapis_ore",
+                "diamond_ore",
+                "deepslate_diamond_ore",
+                "nether_gold_ore",
+                "nether_quartz_ore",
+                "ancient_debris",
+            ]
+            .map(|block| format!("minecraft:{block}"))
+            .into(),
+            filler_block: "minecraft:stone".to_string(),
+            deepslate_filler_block: "minecraft:deepslate".to_string(),
+            nether_filler_block: "minecraft:netherrack".to_string(),
+            end_filler_block: "minecraft:end_stone".to_string(),
+        }
+    }
+}
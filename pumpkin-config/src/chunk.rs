@@ -2,12 +2,32 @@ use std::str;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Default, Clone)]
+#[derive(Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub struct ChunkConfig {
     pub compression: ChunkCompression,
     pub format: ChunkFormat,
     pub write_in_place: bool,
+    /// How many ticks a chunk stays loaded after the last player stopped watching it
+    pub unload_delay_ticks: u32,
+    /// How many chunks are sent to a player per tick until their client reports how many it can handle
+    pub initial_chunks_per_tick: usize,
+    /// The most chunks sent to a player per tick, even if their client asks for more.
+    /// Keeps large view distances from saturating slow connections
+    pub max_chunks_per_tick: usize,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            compression: ChunkCompression::default(),
+            format: ChunkFormat::default(),
+            write_in_place: false,
+            unload_delay_ticks: 300,
+            initial_chunks_per_tick: 9,
+            max_chunks_per_tick: 32,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -19,7 +39,7 @@ pub struct ChunkCompression {
 impl Default for ChunkCompression {
     fn default() -> Self {
         Self {
-            algorithm: Compression::LZ4,
+            algorithm: Compression::Zstd,
             level: 6,
         }
     }
@@ -33,6 +53,9 @@ pub enum Compression {
     ZLib,
     /// LZ4 Compression (since 24w04a)
     LZ4,
+    /// Zstd Compression, the default for new chunks. Chunks written with the other algorithms
+    /// stay readable, as each chunk stores its own compression
+    Zstd,
     /// Custom compression algorithm (since 24w05a)
     Custom,
 }
@@ -12,6 +12,10 @@ pub struct CommandsConfig {
     pub log_console: bool, // TODO: commands...
     /// The `op` permission level of everyone that is not in the `ops` file.
     pub default_op_level: PermissionLvl,
+    /// File the console input history is kept in between restarts, empty to disable.
+    pub history_file: String,
+    /// How many lines of console input history are kept.
+    pub max_history_entries: usize,
 }
 
 impl Default for CommandsConfig {
@@ -21,6 +25,8 @@ impl Default for CommandsConfig {
             log_console: true,
             use_tty: true,
             default_op_level: PermissionLvl::Zero,
+            history_file: ".console_history".to_string(),
+            max_history_entries: 500,
         }
     }
 }
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityConfig {
+    /// The amount of ticks a dropped item stays in the world before despawning.
+    pub item_despawn_ticks: u32,
+    /// Whether dropped items of the same type near each other merge into one stack.
+    pub merge_items: bool,
+    /// Whether mobs far away from players despawn.
+    pub mob_despawning: bool,
+}
+
+impl Default for EntityConfig {
+    fn default() -> Self {
+        Self {
+            item_despawn_ticks: 6000,
+            merge_items: true,
+            mob_despawning: true,
+        }
+    }
+}
@@ -19,24 +19,35 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use anti_cheat::AntiCheatConfig;
+pub use anti_xray::AntiXrayConfig;
 pub use chat::ChatConfig;
 pub use commands::CommandsConfig;
+pub use entity::EntityConfig;
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
 pub use networking::rcon::RCONConfig;
+pub use networking::status::StatusConfig;
+pub use performance::{PerformanceConfig, WatchdogConfig};
 pub use pvp::PVPConfig;
 pub use server_links::ServerLinksConfig;
+pub use world::{GeneratorType, WorldConfig};
 
+mod anti_cheat;
+mod anti_xray;
 mod commands;
 
 mod chat;
 pub mod chunk;
+mod entity;
 pub mod op;
+mod performance;
 mod player_data;
 mod pvp;
 mod server_links;
 pub mod whitelist;
+mod world;
 
 use networking::NetworkingConfig;
 use player_data::PlayerDataConfig;
@@ -103,6 +114,11 @@ pub struct AdvancedConfiguration {
     pub server_links: ServerLinksConfig,
     pub player_data: PlayerDataConfig,
     pub fun: FunConfig,
+    pub entity: EntityConfig,
+    pub world: WorldConfig,
+    pub anti_xray: AntiXrayConfig,
+    pub anti_cheat: AntiCheatConfig,
+    pub performance: PerformanceConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -148,8 +164,9 @@ pub struct BasicConfiguration {
     pub scrub_ips: bool,
     /// Whether to use a server favicon
     pub use_favicon: bool,
-    /// Path to server favicon
-    pub favicon_path: String,
+    /// Path to the server icon, a 64x64 PNG image
+    #[serde(alias = "favicon_path")]
+    pub server_icon_path: String,
     /// The default level name
     pub default_level_name: String,
     /// Whether chat messages should be signed or not
@@ -158,6 +175,8 @@ pub struct BasicConfiguration {
     pub white_list: bool,
     /// Whether to enforce the whitelist
     pub enforce_whitelist: bool,
+    /// Whether operators can join even if they are not on the whitelist
+    pub whitelist_exempt_ops: bool,
 }
 
 impl Default for BasicConfiguration {
@@ -183,11 +202,12 @@ impl Default for BasicConfiguration {
             force_gamemode: false,
             scrub_ips: true,
             use_favicon: true,
-            favicon_path: "icon.png".to_string(),
+            server_icon_path: "server-icon.png".to_string(),
             default_level_name: "world".to_string(),
             allow_chat_reports: false,
             white_list: false,
             enforce_whitelist: false,
+            whitelist_exempt_ops: true,
         }
     }
 }
@@ -196,6 +216,34 @@ impl BasicConfiguration {
     pub fn get_world_path(&self) -> PathBuf {
         format!("./{}", self.default_level_name).parse().unwrap()
     }
+
+    /// Reads the configuration from disk again, see [`AdvancedConfiguration::reload`].
+    pub fn reload(exec_dir: &Path) -> Result<Self, String> {
+        <Self as LoadConfiguration>::reload(exec_dir)
+    }
+}
+
+impl AdvancedConfiguration {
+    /// Reads the configuration from disk again.
+    ///
+    /// Unlike the initial load this never writes a default file and returns an error instead of
+    /// panicking, so a broken config can't take down a running server.
+    pub fn reload(exec_dir: &Path) -> Result<Self, String> {
+        <Self as LoadConfiguration>::reload(exec_dir)
+    }
+}
+
+/// Returns the names of all top-level settings which differ between two configurations.
+pub fn changed_settings<T: Serialize>(old: &T, new: &T) -> Vec<String> {
+    let (Ok(toml::Value::Table(old)), Ok(toml::Value::Table(new))) =
+        (toml::Value::try_from(old), toml::Value::try_from(new))
+    else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
 }
 
 trait LoadConfiguration {
@@ -238,6 +286,20 @@ trait LoadConfiguration {
         config
     }
 
+    fn reload(exec_dir: &Path) -> Result<Self, String>
+    where
+        Self: Sized + Default + Serialize + DeserializeOwned,
+    {
+        let path = exec_dir.join(CONFIG_ROOT_FOLDER).join(Self::get_path());
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file_content = fs::read_to_string(&path)
+            .map_err(|err| format!("Couldn't read configuration file at {path:?}: {err}"))?;
+        toml::from_str(&file_content)
+            .map_err(|err| format!("Couldn't parse config at {path:?}: {}", err.message()))
+    }
+
     fn get_path() -> &'static Path;
 
     fn validate(&self);
@@ -249,7 +311,8 @@ impl LoadConfiguration for AdvancedConfiguration {
     }
 
     fn validate(&self) {
-        self.resource_pack.validate()
+        self.resource_pack.validate();
+        self.networking.packet_compression.validate();
     }
 }
 
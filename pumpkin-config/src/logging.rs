@@ -1,23 +1,62 @@
+use std::str::FromStr;
+
+use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 pub struct LoggingConfig {
     pub enabled: bool,
+    /// The most verbose level logged: `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    /// `RUST_LOG` takes precedence on startup, `/reload` applies this level.
+    pub level: String,
     pub threads: bool,
     pub color: bool,
     pub timestamp: bool,
     pub file: String,
+    /// The latest log is compressed and a new one started once it grows past this size,
+    /// `0` only rotates once a day.
+    pub max_file_size_mb: f64,
+    /// The latest log is also rotated once it is older than this many hours, `0` only rotates by day and size.
+    pub max_file_age_hours: u64,
+    /// How many compressed logs to keep before deleting the oldest ones, `0` keeps all of them.
+    pub max_log_files: usize,
+    /// Writes every log record as a JSON object per line, for log aggregation pipelines.
+    pub json_logging: bool,
+    /// Log lines are buffered up to this size before being written to the file, `0` writes every line directly.
+    /// The buffer is written out when the server stops or panics, but lines still buffered when the process
+    /// gets killed are lost.
+    pub file_buffer_kb: usize,
+    /// Compresses rotated logs on a dedicated thread instead of while logging.
+    pub compress_in_background: bool,
+}
+
+impl LoggingConfig {
+    /// The configured level, `info` if it is unknown.
+    #[must_use]
+    pub fn level_filter(&self) -> LevelFilter {
+        LevelFilter::from_str(&self.level).unwrap_or_else(|_| {
+            log::warn!("Unknown logging level '{}', using info", self.level);
+            LevelFilter::Info
+        })
+    }
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            level: "info".to_string(),
             threads: true,
             color: true,
             timestamp: true,
             file: "latest.log".to_string(),
+            max_file_size_mb: 10.0,
+            max_file_age_hours: 0,
+            max_log_files: 0,
+            json_logging: false,
+            file_buffer_kb: 0,
+            compress_in_background: true,
         }
     }
 }
@@ -9,6 +9,10 @@ pub struct AuthenticationConfig {
     pub url: Option<String>,
     pub connect_timeout: u32,
     pub read_timeout: u32,
+    /// How many seconds a request to the authentication servers may take.
+    pub auth_timeout_secs: u64,
+    /// Lets players join in offline mode when the authentication servers time out or stay down.
+    pub fallback_to_offline_on_timeout: bool,
     pub prevent_proxy_connections: bool,
     pub prevent_proxy_connection_auth_url: Option<String>,
     /// Drasl and Mojang both call their public keys endpoint the "services url"
@@ -31,6 +35,8 @@ impl Default for AuthenticationConfig {
             services_url: None,
             connect_timeout: 5000,
             read_timeout: 5000,
+            auth_timeout_secs: 5,
+            fallback_to_offline_on_timeout: false,
         }
     }
 }
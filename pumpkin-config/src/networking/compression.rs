@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// The highest level zlib supports.
+pub const MAX_COMPRESSION_LEVEL: u32 = 9;
+
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 /// Packet compression
@@ -10,6 +13,21 @@ pub struct CompressionConfig {
     pub info: CompressionInfo,
 }
 
+impl CompressionConfig {
+    pub fn validate(&self) {
+        if !self.enabled {
+            return;
+        }
+
+        // Vanilla clients can only read zlib, so only its level can be tuned
+        let level = self.info.level;
+        assert!(
+            level <= MAX_COMPRESSION_LEVEL,
+            "Packet compression level must be between 0 and {MAX_COMPRESSION_LEVEL} (is {level})"
+        );
+    }
+}
+
 impl Default for CompressionConfig {
     fn default() -> Self {
         Self {
@@ -25,9 +43,11 @@ impl Default for CompressionConfig {
 pub struct CompressionInfo {
     /// The compression threshold used when compression is enabled.
     pub threshold: u32,
-    /// A value between `0..9`.
+    /// A value between `0..=9`.
+    /// `0` = Store the data without compressing it.
     /// `1` = Optimize for the best speed of encoding.
     /// `9` = Optimize for the size of data being encoded.
+    /// Raise it on bandwidth-constrained servers, lower it for LAN setups.
     pub level: u32,
 }
 
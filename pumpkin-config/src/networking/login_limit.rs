@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+/// Limits connections which did not finish logging in yet, to mitigate bot swarms
+/// opening many half-open connections.
+pub struct LoginLimitConfig {
+    /// Whether logins are limited.
+    pub enabled: bool,
+    /// How many connections from the same IP may be logging in at the same time.
+    pub max_logins_per_ip: u32,
+    /// How many seconds a connection has to get from the handshake into the play state.
+    pub login_timeout: u64,
+}
+
+impl Default for LoginLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_logins_per_ip: 3,
+            login_timeout: 30,
+        }
+    }
+}
@@ -1,17 +1,23 @@
 use auth::AuthenticationConfig;
+use login_limit::LoginLimitConfig;
+use protocol::ProtocolConfig;
 use proxy::ProxyConfig;
 use query::QueryConfig;
 use rcon::RCONConfig;
 use serde::{Deserialize, Serialize};
+use status::StatusConfig;
 
 use crate::{CompressionConfig, LANBroadcastConfig};
 
 pub mod auth;
 pub mod compression;
 pub mod lan_broadcast;
+pub mod login_limit;
+pub mod protocol;
 pub mod proxy;
 pub mod query;
 pub mod rcon;
+pub mod status;
 
 #[derive(Deserialize, Serialize, Default)]
 pub struct NetworkingConfig {
@@ -21,4 +27,7 @@ pub struct NetworkingConfig {
     pub proxy: ProxyConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub login_limit: LoginLimitConfig,
+    pub status: StatusConfig,
+    pub protocol: ProtocolConfig,
 }
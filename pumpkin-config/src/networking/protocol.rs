@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+/// The protocol versions clients may join with. Only widen the range if a proxy or plugin
+/// translates between the versions, as the server itself only speaks its own protocol.
+pub struct ProtocolConfig {
+    /// The oldest accepted protocol version, the server's own if unset.
+    pub min_protocol: Option<u32>,
+    /// The newest accepted protocol version, the server's own if unset.
+    pub max_protocol: Option<u32>,
+}
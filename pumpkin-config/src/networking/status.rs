@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct StatusConfig {
+    /// The lines of the MOTD, used instead of the `motd` of the basic config when not empty.
+    /// `&` followed by a formatting code, like `&6`, is turned into a color code.
+    pub motd: Vec<String>,
+    /// Overrides the version name shown to clients which don't support the server's protocol.
+    pub version_name: Option<String>,
+    /// Overrides the protocol version sent to clients, they show the server as incompatible
+    /// when it doesn't match their own.
+    pub protocol: Option<u32>,
+    /// Hides the player count and sample, clients show `???` instead.
+    pub hide_players: bool,
+    /// Whether online players which allow server listing are shown in the player sample.
+    pub show_online_players: bool,
+    /// Extra lines shown in the player sample after the online players, supporting color codes.
+    pub sample: Vec<String>,
+    /// Fake players added to the online player count.
+    pub extra_online_players: u32,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            motd: Vec::new(),
+            version_name: None,
+            protocol: None,
+            hide_players: false,
+            show_online_players: true,
+            sample: Vec::new(),
+            extra_online_players: 0,
+        }
+    }
+}
@@ -2,11 +2,17 @@ use pumpkin_util::permission::PermissionLvl;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// An entry of `ops.json`, using the same format as vanilla.
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Op {
     pub uuid: Uuid,
     pub name: String,
     pub level: PermissionLvl,
+    #[serde(
+        default,
+        rename = "bypassesPlayerLimit",
+        alias = "bypasses_player_limit"
+    )]
     pub bypasses_player_limit: bool,
 }
 
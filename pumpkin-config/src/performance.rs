@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    /// Whether the tick rate is lowered automatically while the server can't keep up.
+    pub adaptive_tick_rate: bool,
+    /// The lowest tick rate the adaptive tick rate goes down to.
+    pub min_tick_rate: u32,
+    pub watchdog: WatchdogConfig,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            adaptive_tick_rate: false,
+            min_tick_rate: 10,
+            watchdog: WatchdogConfig::default(),
+        }
+    }
+}
+
+/// Detects ticks which hang, e.g. because of a deadlock.
+///
+/// This is separate from the adaptive tick rate, which only reacts to ticks being a bit too slow.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    /// A tick running longer than this many milliseconds is reported as stalled.
+    pub stall_threshold_ms: u64,
+    /// Whether to log the state of the async runtime when a tick stalls.
+    /// Build with the `console-subscriber` feature to inspect single tasks with `tokio-console`.
+    pub dump_runtime_state: bool,
+    /// Saves and stops the server once the ticks have stalled this many times in a row, `0` never stops.
+    pub stop_after_stalls: u32,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stall_threshold_ms: 10_000,
+            dump_runtime_state: false,
+            stop_after_stalls: 0,
+        }
+    }
+}
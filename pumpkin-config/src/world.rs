@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    /// Data packs which get enabled in the `level.dat`, e.g. `trade_rebalance` for experimental features
+    pub extra_data_packs: Vec<String>,
+    /// How many blocks around the destination to look for an existing nether portal before building a new one.
+    /// In the nether this is divided by 8, like its coordinates.
+    pub portal_search_radius: u32,
+    /// How many ticks pass between autosaves of the worlds. `0` disables autosaving.
+    pub autosave_interval: u32,
+    /// The generator for new chunks of the overworld, `debug` builds vanilla's debug world with all block states.
+    pub generator: GeneratorType,
+    /// How far away from a player, in blocks, particles of a burst (e.g. an explosion) are still sent to them.
+    pub particle_burst_distance: f64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeneratorType {
+    #[default]
+    Default,
+    Debug,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            extra_data_packs: Vec::new(),
+            portal_search_radius: 128,
+            autosave_interval: 6000,
+            generator: GeneratorType::Default,
+            particle_burst_distance: 64.0,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::{fmt, fs};
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use serde::{
+    Deserialize, Deserializer,
+    de::{MapAccess, Visitor},
+};
+use syn::LitInt;
+
+#[derive(Deserialize)]
+struct AttributeRange {
+    default_value: f64,
+    min_value: f64,
+    max_value: f64,
+}
+
+/// The attributes in registry order, the order of `attributes.json` is their network id.
+struct AttributeDefaults(Vec<(String, AttributeRange)>);
+
+impl<'de> Deserialize<'de> for AttributeDefaults {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor;
+
+        impl<'de> Visitor<'de> for OrderedVisitor {
+            type Value = AttributeDefaults;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of attribute names to their default value and range")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut attributes = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    attributes.push(entry);
+                }
+                Ok(AttributeDefaults(attributes))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedVisitor)
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/attributes.json");
+
+    let attributes: AttributeDefaults =
+        serde_json::from_str(&fs::read_to_string("../assets/attributes.json").unwrap())
+            .expect("Failed to parse attributes.json");
+
+    let mut constants = TokenStream::new();
+    let mut type_from_name = TokenStream::new();
+
+    for (id, (name, range)) in attributes.0.iter().enumerate() {
+        let const_ident = format_ident!("{}", name.to_shouty_snake_case());
+        let id_lit = LitInt::new(&id.to_string(), proc_macro2::Span::call_site());
+        let AttributeRange {
+            default_value,
+            min_value,
+            max_value,
+        } = range;
+
+        constants.extend(quote! {
+            pub const #const_ident: Self = Self {
+                id: #id_lit,
+                name: #name,
+                default_value: #default_value,
+                min_value: #min_value,
+                max_value: #max_value,
+            };
+        });
+        type_from_name.extend(quote! {
+            #name => Some(Self::#const_ident),
+        });
+    }
+
+    quote! {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct Attributes {
+            pub id: u8,
+            pub name: &'static str,
+            pub default_value: f64,
+            #[doc = r" The values of the attribute are clamped between the min and max value."]
+            pub min_value: f64,
+            pub max_value: f64,
+        }
+
+        impl Attributes {
+            #constants
+
+            #[doc = r" Gets an attribute by its name without namespace, like `max_health`."]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+        }
+    }
+}
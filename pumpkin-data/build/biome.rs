@@ -12,13 +12,30 @@ struct Biome {
     temperature: f32,
     downfall: f32,
     temperature_modifier: Option<TemperatureModifier>,
-    //carvers: Vec<String>,
+    carvers: Carvers,
     features: Vec<Vec<String>>,
     creature_spawn_probability: Option<f32>,
     spawners: SpawnGroups,
     id: u8,
 }
 
+/// A biome's carvers, a single one is not wrapped in a list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Carvers {
+    List(Vec<String>),
+    Single(String),
+}
+
+impl Carvers {
+    fn names(&self) -> &[String] {
+        match self {
+            Carvers::List(carvers) => carvers,
+            Carvers::Single(carver) => std::slice::from_ref(carver),
+        }
+    }
+}
+
 #[derive(Deserialize, PartialEq, Eq, Hash)]
 struct SpawnGroups {
     monster: Vec<Spawner>,
@@ -151,7 +168,7 @@ pub(crate) fn build() -> TokenStream {
         let has_precipitation = biome.has_precipitation;
         let temperature = biome.temperature;
         let downfall = biome.downfall;
-        //  let carvers = &biome.carvers;
+        let carvers = biome.carvers.names();
         let features = &biome.features;
         let creature_spawn_probability = &biome.creature_spawn_probability.unwrap_or(0.1);
 
@@ -227,12 +244,14 @@ pub(crate) fn build() -> TokenStream {
             pub const #format_name: Biome = Biome {
                id: #index,
                registry_id: #name,
+               resource_location: concat!("minecraft:", #name),
                weather: Weather::new(
                     #has_precipitation,
                     #temperature,
                     #temperature_modifier,
                     #downfall
                ),
+               carvers: &[#(#carvers),*],
                features: &[#(&[#(#features),*]),*],
                creature_spawn_probability: #creature_spawn_probability,
                spawners: #spawners,
@@ -256,8 +275,10 @@ pub(crate) fn build() -> TokenStream {
             pub struct Biome {
                 pub id: u8,
                 pub registry_id: &'static str,
+                #[doc = r" The namespaced id of the biome, like `minecraft:plains`."]
+                pub resource_location: &'static str,
                 pub weather: Weather,
-                // carvers: &'static [&str],
+                pub carvers: &'static [&'static str],
                 pub features: &'static [&'static [&'static str]],
                 pub creature_spawn_probability: f32,
                 pub spawners: SpawnGroups,
@@ -838,12 +838,13 @@ pub(crate) fn build() -> TokenStream {
 
     quote! {
         use crate::{BlockState, Block, CollisionShape, blocks::Flammable};
-        use crate::block_state::PistonBehavior;
+        use crate::block_state::{GlobalPaletteCache, PistonBehavior};
         use pumpkin_util::math::int_provider::{UniformIntProvider, IntProvider, NormalIntProvider};
         use pumpkin_util::loot_table::*;
         use pumpkin_util::math::experience::Experience;
         use pumpkin_util::math::vector3::Vector3;
         use std::collections::HashMap;
+        use std::sync::LazyLock;
         use phf;
 
 
@@ -901,13 +902,24 @@ pub(crate) fn build() -> TokenStream {
            Block::from_registry_key(key)
         }
 
+        /// The registry keys of all blocks, without the `minecraft:` namespace.
+        pub fn get_block_registry_keys() -> impl Iterator<Item = &'static str> {
+            Block::BLOCK_FROM_NAME_MAP.keys().copied()
+        }
+
         pub fn get_block_by_id(id: u16) -> &'static Block {
             Block::from_id(id)
         }
 
+        pub static GLOBAL_PALETTE_CACHE: LazyLock<GlobalPaletteCache> = LazyLock::new(|| {
+            GlobalPaletteCache::new(Block::RAW_ID_FROM_STATE_ID.iter().enumerate().map(|(state_id, &raw_id)| {
+                let block = Block::from_id(raw_id);
+                (block, &block.states[state_id - block.states[0].id as usize])
+            }))
+        });
+
         pub fn get_state_by_state_id(id: u16) -> &'static BlockState {
-            let state: &BlockState = Block::from_state_id(id).states.iter().find(|state| state.id == id).unwrap();
-            state
+            GLOBAL_PALETTE_CACHE.get(id).1
         }
 
         pub fn get_block_by_state_id(id: u16) -> &'static Block {
@@ -915,9 +927,7 @@ pub(crate) fn build() -> TokenStream {
         }
 
         pub fn get_block_and_state_by_state_id(id: u16) -> (&'static Block, &'static BlockState) {
-            let block = Block::from_state_id(id);
-            let state: &BlockState = block.states.iter().find(|state| state.id == id).unwrap();
-            (block, state)
+            *GLOBAL_PALETTE_CACHE.get(id)
         }
 
         pub fn get_block_by_item(item_id: u16) -> Option<&'static Block> {
@@ -4,11 +4,13 @@ use quote::{format_ident, quote};
 use rayon::prelude::*;
 use std::{fs, io::Write, path::Path, process::Command};
 
+mod attributes;
 mod biome;
 mod block;
 mod chunk_status;
 mod composter_increase_chance;
 mod damage_type;
+mod data_component;
 mod enchantments;
 mod entity_pose;
 mod entity_status;
@@ -25,6 +27,7 @@ mod noise_parameter;
 mod noise_router;
 mod packet;
 mod particle;
+mod potion;
 mod recipes;
 mod scoreboard_slot;
 mod screen;
@@ -79,6 +82,9 @@ pub fn main() {
         (recipes::build, "recipes.rs"),
         (enchantments::build, "enchantment.rs"),
         (fuels::build, "fuels.rs"),
+        (attributes::build, "attributes.rs"),
+        (data_component::build, "data_component.rs"),
+        (potion::build, "potion.rs"),
     ];
 
     build_functions.par_iter().for_each(|(build_fn, file)| {
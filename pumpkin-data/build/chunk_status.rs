@@ -23,7 +23,8 @@ pub(crate) fn build() -> TokenStream {
     quote! {
         use serde::{Deserialize, Serialize};
 
-        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        /// The generation stages in the order a chunk goes through them.
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
         pub enum ChunkStatus {
             #variants
         }
@@ -99,11 +99,14 @@ pub(crate) fn build() -> TokenStream {
                 message_id: #message_id,
                 scaling: #scaling,
                 id: #id_lit,
+                registry_key: #resource_name,
             };
         });
     }
 
     quote! {
+        use crate::tag::{RegistryKey, Tagable};
+
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct DamageType {
             pub death_message_type: Option<DeathMessageType>,
@@ -112,6 +115,7 @@ pub(crate) fn build() -> TokenStream {
             pub message_id: &'static str,
             pub scaling: DamageScaling,
             pub id: u8,
+            pub registry_key: &'static str,
         }
 
         #[derive(Clone, Copy, Debug, PartialEq)]
@@ -150,5 +154,17 @@ pub(crate) fn build() -> TokenStream {
             }
 
         }
+
+        impl Tagable for DamageType {
+            #[inline]
+            fn tag_key() -> RegistryKey {
+                RegistryKey::DamageType
+            }
+
+            #[inline]
+            fn registry_key(&self) -> &str {
+                self.registry_key
+            }
+        }
     }
 }
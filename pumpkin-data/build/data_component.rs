@@ -0,0 +1,65 @@
+use std::fs;
+
+use heck::ToPascalCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::array_to_tokenstream;
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/data_components.json");
+
+    let components: Vec<String> =
+        serde_json::from_str(&fs::read_to_string("../assets/data_components.json").unwrap())
+            .expect("Failed to parse data_components.json");
+    let variants = array_to_tokenstream(&components);
+    let type_from_id = &components
+        .iter()
+        .enumerate()
+        .map(|(id, component)| {
+            let id = id as i32;
+            let name = format_ident!("{}", component.to_pascal_case());
+
+            quote! {
+                #id => Some(Self::#name),
+            }
+        })
+        .collect::<TokenStream>();
+    let type_to_name = &components
+        .iter()
+        .map(|component| {
+            let id = format!("minecraft:{component}");
+            let name = format_ident!("{}", component.to_pascal_case());
+
+            quote! {
+                Self::#name => #id,
+            }
+        })
+        .collect::<TokenStream>();
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum DataComponent {
+            #variants
+        }
+
+        impl DataComponent {
+            #[doc = r" The protocol id of the component type."]
+            pub const fn id(&self) -> i32 {
+                *self as i32
+            }
+
+            pub const fn from_id(id: i32) -> Option<Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+        }
+    }
+}
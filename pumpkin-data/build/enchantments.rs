@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::{collections::BTreeMap, fs};
 
 use heck::ToShoutySnakeCase;
 use proc_macro2::TokenStream;
@@ -50,14 +50,17 @@ impl AttributeModifierSlot {
 pub(crate) fn build() -> TokenStream {
     println!("cargo:rerun-if-changed=../assets/enchantments.json");
 
-    let enchantments: HashMap<String, Enchantment> =
+    let enchantments: BTreeMap<String, Enchantment> =
         serde_json::from_str(&fs::read_to_string("../assets/enchantments.json").unwrap())
-            .expect("Failed to parse biome.json");
+            .expect("Failed to parse enchantments.json");
 
     let mut variants = TokenStream::new();
     let mut name_to_type = TokenStream::new();
+    let mut id_to_type = TokenStream::new();
 
-    for (name, enchantment) in enchantments.iter() {
+    // The registry ids are the alphabetical order, like vanilla loads them from the data pack
+    for (id, (name, enchantment)) in enchantments.iter().enumerate() {
+        let id = id as u8;
         let raw_name = name.strip_prefix("minecraft:").unwrap();
         let format_name = format_ident!("{}", raw_name.to_shouty_snake_case());
         let anvil_cost = enchantment.anvil_cost;
@@ -68,6 +71,7 @@ pub(crate) fn build() -> TokenStream {
 
         variants.extend([quote! {
             pub const #format_name: Enchantment = Enchantment {
+               id: #id,
                name: #name,
                anvil_cost: #anvil_cost,
                supported_items: #supported_items,
@@ -76,12 +80,14 @@ pub(crate) fn build() -> TokenStream {
             };
         }]);
 
-        name_to_type.extend(quote! { #name => Some(Self::#format_name), });
+        name_to_type.extend(quote! { #name => Some(&Self::#format_name), });
+        id_to_type.extend(quote! { #id => Some(&Self::#format_name), });
     }
 
     quote! {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq)]
         pub struct Enchantment {
+            pub id: u8,
             pub name: &'static str,
             pub anvil_cost: u32,
             pub supported_items: &'static str,
@@ -90,7 +96,7 @@ pub(crate) fn build() -> TokenStream {
             // TODO: add more
         }
 
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq)]
         pub enum AttributeModifierSlot {
             Any,
             MainHand,
@@ -108,12 +114,19 @@ pub(crate) fn build() -> TokenStream {
         impl Enchantment {
             #variants
 
-            pub fn from_name(name: &str) -> Option<Self> {
+            pub fn from_name(name: &str) -> Option<&'static Self> {
                 match name {
                     #name_to_type
                     _ => None
                 }
             }
+
+            pub fn from_id(id: u8) -> Option<&'static Self> {
+                match id {
+                    #id_to_type
+                    _ => None
+                }
+            }
         }
     }
 }
@@ -1,5 +1,9 @@
-use std::{collections::HashMap, fs};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
 
+use heck::ToShoutySnakeCase;
 use proc_macro2::TokenStream;
 use pumpkin_util::HeightMap;
 use quote::{ToTokens, format_ident, quote};
@@ -36,7 +40,11 @@ pub enum SpawnLocation {
     Unrestricted,
 }
 
-pub struct NamedEntityType<'a>(&'a str, &'a EntityType);
+/// The base attribute values of an entity type which differ from the attribute defaults, as
+/// extracted into `entity_attributes.json`. Max health is in `max_health` of the entity type.
+type EntityAttributes = BTreeMap<String, f64>;
+
+pub struct NamedEntityType<'a>(&'a str, &'a EntityType, Option<&'a EntityAttributes>);
 
 impl ToTokens for NamedEntityType<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -79,6 +87,11 @@ impl ToTokens for NamedEntityType<'_> {
         let fire_immune = entity.fire_immune;
         let eye_height = entity.eye_height;
 
+        let attributes = self.2.into_iter().flatten().map(|(attribute, value)| {
+            let attribute = format_ident!("{}", attribute.to_shouty_snake_case());
+            quote! { (Attributes::#attribute, #value) }
+        });
+
         let dimension0 = entity.dimension[0];
         let dimension1 = entity.dimension[1];
 
@@ -100,6 +113,7 @@ impl ToTokens for NamedEntityType<'_> {
                 loot_table: #loot_table,
                 dimension: [#dimension0, #dimension1], // Correctly construct the array
                 eye_height: #eye_height,
+                attributes: &[#(#attributes),*],
                 spawn_restriction: #spawn_restriction,
                 resource_name: #name,
             }
@@ -109,10 +123,14 @@ impl ToTokens for NamedEntityType<'_> {
 
 pub(crate) fn build() -> TokenStream {
     println!("cargo:rerun-if-changed=../assets/entities.json");
+    println!("cargo:rerun-if-changed=../assets/entity_attributes.json");
 
     let json: HashMap<String, EntityType> =
         serde_json::from_str(&fs::read_to_string("../assets/entities.json").unwrap())
             .expect("Failed to parse entities.json");
+    let attributes: HashMap<String, EntityAttributes> =
+        serde_json::from_str(&fs::read_to_string("../assets/entity_attributes.json").unwrap())
+            .expect("Failed to parse entity_attributes.json");
 
     let mut consts = TokenStream::new();
     let mut type_from_raw_id_arms = TokenStream::new();
@@ -123,7 +141,13 @@ pub(crate) fn build() -> TokenStream {
         let id_lit = LitInt::new(&id.to_string(), proc_macro2::Span::call_site());
         let upper_name = format_ident!("{}", name.to_uppercase());
 
-        let entity_tokens = NamedEntityType(name, entity).to_token_stream();
+        let entity_attributes = attributes.get(name);
+        // Every living entity has attributes, even if all of them are the defaults
+        assert!(
+            entity.max_health.is_none() || entity_attributes.is_some(),
+            "The living entity {name} has no attributes in entity_attributes.json"
+        );
+        let entity_tokens = NamedEntityType(name, entity, entity_attributes).to_token_stream();
 
         consts.extend(quote! {
             pub const #upper_name: EntityType = #entity_tokens;
@@ -140,6 +164,7 @@ pub(crate) fn build() -> TokenStream {
     quote! {
         use pumpkin_util::loot_table::*;
         use pumpkin_util::HeightMap;
+        use crate::attributes::Attributes;
 
         #[derive(Clone, Copy, Debug, PartialEq)]
         pub struct EntityType {
@@ -151,6 +176,8 @@ pub(crate) fn build() -> TokenStream {
             pub loot_table: Option<LootTable>,
             pub dimension: [f32; 2],
             pub eye_height: f32,
+            #[doc = r" The base attribute values of the entity which differ from the attribute defaults."]
+            pub attributes: &'static [(Attributes, f64)],
             pub spawn_restriction: SpawnRestriction,
             pub resource_name: &'static str,
         }
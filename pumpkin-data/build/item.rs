@@ -31,6 +31,14 @@ pub struct ItemComponents {
     pub tool: Option<ToolComponent>,
     #[serde(rename = "minecraft:food")]
     pub food: Option<FoodComponent>,
+    #[serde(rename = "minecraft:consumable")]
+    pub consumable: Option<ConsumableComponent>,
+    #[serde(rename = "minecraft:use_cooldown")]
+    pub use_cooldown: Option<UseCooldownComponent>,
+    #[serde(rename = "minecraft:use_remainder")]
+    pub use_remainder: Option<UseRemainder>,
+    #[serde(rename = "minecraft:equippable")]
+    pub equippable: Option<EquippableComponent>,
 }
 
 impl ToTokens for ItemComponents {
@@ -168,6 +176,90 @@ impl ToTokens for ItemComponents {
             None => quote! { None },
         };
 
+        let consumable = match &self.consumable {
+            Some(consumable) => {
+                let consume_seconds = consumable.consume_seconds;
+                let effects = consumable
+                    .on_consume_effects
+                    .iter()
+                    .map(|effect| match effect {
+                        ConsumeEffect::ApplyEffects {
+                            effects,
+                            probability,
+                        } => {
+                            let effects = effects.iter().map(|effect| {
+                                let id = LitStr::new(&effect.id, Span::call_site());
+                                let amplifier = effect.amplifier;
+                                let duration = effect.duration;
+                                quote! {
+                                    ConsumeStatusEffect {
+                                        id: #id,
+                                        amplifier: #amplifier,
+                                        duration: #duration,
+                                    }
+                                }
+                            });
+                            quote! {
+                                ConsumeEffect::ApplyEffects {
+                                    effects: &[#(#effects),*],
+                                    probability: #probability,
+                                }
+                            }
+                        }
+                        ConsumeEffect::RemoveEffects { effects } => {
+                            let effects = effects
+                                .get_values()
+                                .into_iter()
+                                .map(|effect| effect.serialize());
+                            quote! { ConsumeEffect::RemoveEffects { effects: &[#(#effects),*] } }
+                        }
+                        ConsumeEffect::ClearAllEffects => quote! { ConsumeEffect::ClearAllEffects },
+                        ConsumeEffect::Other => quote! { ConsumeEffect::Other },
+                    });
+                quote! { Some(ConsumableComponent {
+                    consume_seconds: #consume_seconds,
+                    on_consume_effects: &[#(#effects),*],
+                }) }
+            }
+            None => quote! { None },
+        };
+
+        let use_cooldown = match &self.use_cooldown {
+            Some(cooldown) => {
+                let seconds = cooldown.seconds;
+                let cooldown_group = match &cooldown.cooldown_group {
+                    Some(group) => quote! { Some(#group) },
+                    None => quote! { None },
+                };
+                quote! { Some(UseCooldownComponent {
+                    seconds: #seconds,
+                    cooldown_group: #cooldown_group,
+                }) }
+            }
+            None => quote! { None },
+        };
+
+        let use_remainder = match &self.use_remainder {
+            Some(remainder) => {
+                let id = LitStr::new(&remainder.id, Span::call_site());
+                let count = remainder.count;
+                quote! { Some(UseRemainder { id: #id, count: #count }) }
+            }
+            None => quote! { None },
+        };
+
+        let equippable = match &self.equippable {
+            Some(equippable) => {
+                let slot = LitStr::new(&equippable.slot, Span::call_site());
+                let damage_on_hurt = equippable.damage_on_hurt;
+                quote! { Some(EquippableComponent {
+                    slot: #slot,
+                    damage_on_hurt: #damage_on_hurt,
+                }) }
+            }
+            None => quote! { None },
+        };
+
         tokens.extend(quote! {
             ItemComponents {
                 item_name: #item_name,
@@ -177,7 +269,11 @@ impl ToTokens for ItemComponents {
                 max_damage: #max_damage,
                 attribute_modifiers: #attribute_modifiers,
                 tool: #tool,
-                food: #food
+                food: #food,
+                consumable: #consumable,
+                use_cooldown: #use_cooldown,
+                use_remainder: #use_remainder,
+                equippable: #equippable
             }
         });
     }
@@ -189,6 +285,17 @@ pub struct ToolComponent {
     damage_per_block: Option<u32>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct EquippableComponent {
+    slot: String,
+    #[serde(default = "default_damage_on_hurt")]
+    damage_on_hurt: bool,
+}
+
+fn default_damage_on_hurt() -> bool {
+    true
+}
+
 #[derive(Deserialize, Copy, Clone, Debug)]
 pub struct FoodComponent {
     nutrition: u8,
@@ -196,6 +303,60 @@ pub struct FoodComponent {
     can_always_eat: Option<bool>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct ConsumableComponent {
+    #[serde(default = "default_consume_seconds")]
+    consume_seconds: f32,
+    #[serde(default)]
+    on_consume_effects: Vec<ConsumeEffect>,
+}
+
+fn default_consume_seconds() -> f32 {
+    1.6
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum ConsumeEffect {
+    #[serde(rename = "minecraft:apply_effects")]
+    ApplyEffects {
+        effects: Vec<ConsumeStatusEffect>,
+        #[serde(default = "default_probability")]
+        probability: f32,
+    },
+    #[serde(rename = "minecraft:remove_effects")]
+    RemoveEffects { effects: RegistryEntryList },
+    #[serde(rename = "minecraft:clear_all_effects")]
+    ClearAllEffects,
+    #[serde(other)]
+    Other,
+}
+
+fn default_probability() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ConsumeStatusEffect {
+    id: String,
+    #[serde(default)]
+    amplifier: u8,
+    #[serde(default)]
+    duration: i32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct UseCooldownComponent {
+    seconds: f32,
+    cooldown_group: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct UseRemainder {
+    id: String,
+    count: u8,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct ToolRule {
     blocks: RegistryEntryList,
@@ -294,7 +455,11 @@ pub(crate) fn build() -> TokenStream {
             pub max_damage: Option<u16>,
             pub attribute_modifiers: Option<&'static [Modifier]>,
             pub tool: Option<ToolComponent>,
-            pub food: Option<FoodComponent>
+            pub food: Option<FoodComponent>,
+            pub consumable: Option<ConsumableComponent>,
+            pub use_cooldown: Option<UseCooldownComponent>,
+            pub use_remainder: Option<UseRemainder>,
+            pub equippable: Option<EquippableComponent>
         }
 
         #[derive(Clone, Copy, Debug)]
@@ -335,6 +500,52 @@ pub(crate) fn build() -> TokenStream {
             pub can_always_eat: Option<bool>,
         }
 
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct ConsumableComponent {
+            pub consume_seconds: f32,
+            pub on_consume_effects: &'static [ConsumeEffect],
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum ConsumeEffect {
+            ApplyEffects {
+                effects: &'static [ConsumeStatusEffect],
+                probability: f32,
+            },
+            RemoveEffects {
+                effects: &'static [&'static str],
+            },
+            ClearAllEffects,
+            #[doc = "Effects we do not support yet, like teleporting randomly or playing a sound."]
+            Other,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct ConsumeStatusEffect {
+            pub id: &'static str,
+            pub amplifier: u8,
+            pub duration: i32,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct UseCooldownComponent {
+            pub seconds: f32,
+            pub cooldown_group: Option<&'static str>,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct UseRemainder {
+            pub id: &'static str,
+            pub count: u8,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct EquippableComponent {
+            pub slot: &'static str,
+            #[doc = "Whether the item loses durability when its wearer is hurt, false e.g. for elytras."]
+            pub damage_on_hurt: bool,
+        }
+
         impl Item {
             #constants
 
@@ -44,6 +44,8 @@ pub struct LootPoolStruct {
     entries: Vec<LootPoolEntryStruct>,
     rolls: LootNumberProviderTypes, // TODO
     bonus_rolls: f32,
+    conditions: Option<Vec<LootConditionStruct>>,
+    functions: Option<Vec<LootFunctionStruct>>,
 }
 
 impl ToTokens for LootPoolStruct {
@@ -55,12 +57,28 @@ impl ToTokens for LootPoolStruct {
             .collect();
         let rolls = &self.rolls;
         let bonus_rolls = &self.bonus_rolls;
+        let conditions_tokens = match &self.conditions {
+            Some(conds) => {
+                let cond_tokens: Vec<_> = conds.iter().map(|c| c.to_token_stream()).collect();
+                quote! { Some(&[#(#cond_tokens),*]) }
+            }
+            None => quote! { None },
+        };
+        let functions_tokens = match &self.functions {
+            Some(fns) => {
+                let fn_tokens: Vec<_> = fns.iter().map(|f| f.to_token_stream()).collect();
+                quote! { Some(&[#(#fn_tokens),*]) }
+            }
+            None => quote! { None },
+        };
 
         tokens.extend(quote! {
             LootPool {
                 entries: &[#(#entries_tokens),*],
                 rolls: #rolls,
                 bonus_rolls: #bonus_rolls,
+                conditions: #conditions_tokens,
+                functions: #functions_tokens,
             }
         });
     }
@@ -156,11 +174,11 @@ impl ToTokens for LootPoolEntryTypesStruct {
 #[serde(tag = "condition")]
 pub enum LootConditionStruct {
     #[serde(rename = "minecraft:inverted")]
-    Inverted,
+    Inverted { term: Box<LootConditionStruct> },
     #[serde(rename = "minecraft:any_of")]
-    AnyOf,
+    AnyOf { terms: Vec<LootConditionStruct> },
     #[serde(rename = "minecraft:all_of")]
-    AllOf,
+    AllOf { terms: Vec<LootConditionStruct> },
     #[serde(rename = "minecraft:random_chance")]
     RandomChance,
     #[serde(rename = "minecraft:random_chance_with_enchanted_bonus")]
@@ -177,9 +195,12 @@ pub enum LootConditionStruct {
         properties: HashMap<String, String>,
     },
     #[serde(rename = "minecraft:match_tool")]
-    MatchTool,
+    MatchTool { predicate: ItemPredicateStruct },
     #[serde(rename = "minecraft:table_bonus")]
-    TableBonus,
+    TableBonus {
+        enchantment: String,
+        chances: Vec<f32>,
+    },
     #[serde(rename = "minecraft:survives_explosion")]
     SurvivesExplosion,
     #[serde(rename = "minecraft:damage_source_properties")]
@@ -201,9 +222,15 @@ pub enum LootConditionStruct {
 impl ToTokens for LootConditionStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = match self {
-            LootConditionStruct::Inverted => quote! { LootCondition::Inverted },
-            LootConditionStruct::AnyOf => quote! { LootCondition::AnyOf },
-            LootConditionStruct::AllOf => quote! { LootCondition::AllOf },
+            LootConditionStruct::Inverted { term } => {
+                quote! { LootCondition::Inverted { term: &#term } }
+            }
+            LootConditionStruct::AnyOf { terms } => {
+                quote! { LootCondition::AnyOf { terms: &[#(#terms),*] } }
+            }
+            LootConditionStruct::AllOf { terms } => {
+                quote! { LootCondition::AllOf { terms: &[#(#terms),*] } }
+            }
             LootConditionStruct::RandomChance => quote! { LootCondition::RandomChance },
             LootConditionStruct::RandomChanceWithEnchantedBonus => {
                 quote! { LootCondition::RandomChanceWithEnchantedBonus }
@@ -218,8 +245,25 @@ impl ToTokens for LootConditionStruct {
                     .collect();
                 quote! { LootCondition::BlockStateProperty { block: #block, properties: &[#(#properties),*] } }
             }
-            LootConditionStruct::MatchTool => quote! { LootCondition::MatchTool },
-            LootConditionStruct::TableBonus => quote! { LootCondition::TableBonus },
+            LootConditionStruct::MatchTool { predicate } => {
+                let items = match &predicate.items {
+                    Some(ItemsStruct::One(item)) => quote! { Some(&[#item]) },
+                    Some(ItemsStruct::Many(items)) => quote! { Some(&[#(#items),*]) },
+                    None => quote! { None },
+                };
+                let enchantments = predicate
+                    .predicates
+                    .as_ref()
+                    .and_then(|predicates| predicates.enchantments.as_deref())
+                    .unwrap_or_default();
+                quote! { LootCondition::MatchTool { items: #items, enchantments: &[#(#enchantments),*] } }
+            }
+            LootConditionStruct::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                quote! { LootCondition::TableBonus { enchantment: #enchantment, chances: &[#(#chances),*] } }
+            }
             LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
             LootConditionStruct::DamageSourceProperties => {
                 quote! { LootCondition::DamageSourceProperties }
@@ -238,6 +282,65 @@ impl ToTokens for LootConditionStruct {
     }
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemPredicateStruct {
+    items: Option<ItemsStruct>,
+    predicates: Option<ItemSubPredicatesStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ItemsStruct {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ItemSubPredicatesStruct {
+    #[serde(rename = "minecraft:enchantments")]
+    enchantments: Option<Vec<EnchantmentPredicateStruct>>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicateStruct {
+    enchantments: String,
+    levels: Option<LevelsStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum LevelsStruct {
+    Exact(i32),
+    Range { min: Option<i32>, max: Option<i32> },
+}
+
+impl ToTokens for EnchantmentPredicateStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let enchantment = &self.enchantments;
+        let (min, max) = match self.levels {
+            Some(LevelsStruct::Exact(level)) => (Some(level), Some(level)),
+            Some(LevelsStruct::Range { min, max }) => (min, max),
+            None => (None, None),
+        };
+        let min_level = match min {
+            Some(min) => quote! { Some(#min) },
+            None => quote! { None },
+        };
+        let max_level = match max {
+            Some(max) => quote! { Some(#max) },
+            None => quote! { None },
+        };
+
+        tokens.extend(quote! {
+            EnchantmentPredicate {
+                enchantment: #enchantment,
+                min_level: #min_level,
+                max_level: #max_level,
+            }
+        });
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct LootFunctionStruct {
     #[serde(flatten)]
@@ -0,0 +1,102 @@
+use std::fs;
+
+use heck::{ToPascalCase, ToShoutySnakeCase};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use serde::Deserialize;
+use syn::LitInt;
+
+#[derive(Deserialize)]
+struct Potion {
+    name: String,
+    effects: Vec<PotionEffect>,
+}
+
+#[derive(Deserialize)]
+struct PotionEffect {
+    id: String,
+    duration: i32,
+    amplifier: u8,
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/potion.json");
+
+    // In registry order, the index of a potion is its network id
+    let potions: Vec<Potion> =
+        serde_json::from_str(&fs::read_to_string("../assets/potion.json").unwrap())
+            .expect("Failed to parse potion.json");
+
+    let mut constants = TokenStream::new();
+    let mut type_from_name = TokenStream::new();
+    let mut type_from_id = TokenStream::new();
+
+    for (id, potion) in potions.iter().enumerate() {
+        let const_ident = format_ident!("{}", potion.name.to_shouty_snake_case());
+        let id_lit = LitInt::new(&id.to_string(), proc_macro2::Span::call_site());
+        let name = &potion.name;
+        let effects = potion.effects.iter().map(|effect| {
+            let effect_type = format_ident!("{}", effect.id.to_pascal_case());
+            let duration = effect.duration;
+            let amplifier = effect.amplifier;
+            quote! {
+                PotionEffect {
+                    effect_type: EffectType::#effect_type,
+                    duration: #duration,
+                    amplifier: #amplifier,
+                }
+            }
+        });
+
+        constants.extend(quote! {
+            pub const #const_ident: Self = Self {
+                id: #id_lit,
+                name: #name,
+                effects: &[#(#effects),*],
+            };
+        });
+        type_from_name.extend(quote! {
+            #name => Some(&Self::#const_ident),
+        });
+        type_from_id.extend(quote! {
+            #id_lit => Some(&Self::#const_ident),
+        });
+    }
+
+    quote! {
+        use crate::entity::EffectType;
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct PotionEffect {
+            pub effect_type: EffectType,
+            pub duration: i32,
+            pub amplifier: u8,
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct Potion {
+            pub id: u8,
+            pub name: &'static str,
+            pub effects: &'static [PotionEffect],
+        }
+
+        impl Potion {
+            #constants
+
+            #[doc = r" Gets a potion by its name without namespace, like `long_swiftness`."]
+            pub fn from_name(name: &str) -> Option<&'static Self> {
+                match name {
+                    #type_from_name
+                    _ => None
+                }
+            }
+
+            pub const fn from_id(id: u8) -> Option<&'static Self> {
+                match id {
+                    #type_from_id
+                    _ => None
+                }
+            }
+        }
+    }
+}
@@ -15,6 +15,7 @@ pub(crate) fn build() -> TokenStream {
     let variants = array_to_tokenstream(&sound_categories);
 
     quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum ScoreboardDisplaySlot {
             #variants
         }
@@ -1,6 +1,8 @@
 use pumpkin_util::math::vector3::Vector3;
 
-use crate::block_properties::{COLLISION_SHAPES, Instrument, get_block_by_state_id};
+use crate::block_properties::{
+    COLLISION_SHAPES, GLOBAL_PALETTE_CACHE, Instrument, get_block_by_state_id,
+};
 use crate::{Block, BlockDirection, CollisionShape};
 
 #[derive(Debug)]
@@ -135,6 +137,50 @@ pub struct BlockStateRef {
     pub state_idx: u16,
 }
 
+impl BlockStateRef {
+    /// Returns the block and the state this reference points to.
+    pub fn as_tuple(&self) -> &'static (&'static Block, &'static BlockState) {
+        GLOBAL_PALETTE_CACHE.get(self.id)
+    }
+}
+
+/// Maps every block state id directly to its block and state.
+///
+/// Looking a state up through its block requires searching the block's states, which adds up
+/// for hot paths like collision checks, block updates and chunk encoding.
+pub struct GlobalPaletteCache {
+    entries: Box<[(&'static Block, &'static BlockState)]>,
+}
+
+impl GlobalPaletteCache {
+    /// Builds the cache, `entries` must be ordered by state id.
+    pub(crate) fn new(
+        entries: impl Iterator<Item = (&'static Block, &'static BlockState)>,
+    ) -> Self {
+        let entries: Box<[_]> = entries.collect();
+        debug_assert!(
+            entries
+                .iter()
+                .enumerate()
+                .all(|(id, (_, state))| state.id as usize == id)
+        );
+        Self { entries }
+    }
+
+    #[inline]
+    pub fn get(&self, state_id: u16) -> &(&'static Block, &'static BlockState) {
+        &self.entries[state_id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
 //This is the Layout of state_props in the right order
 const IS_AIR: u8 = 0b00000001;
 const BURNABLE: u8 = 0b00000010;
@@ -113,6 +113,10 @@ pub mod scoreboard;
 #[path = "generated/damage_type.rs"]
 pub mod damage;
 
+#[rustfmt::skip]
+#[path = "generated/data_component.rs"]
+pub mod data_component;
+
 #[rustfmt::skip]
 #[path = "generated/fluid.rs"]
 pub mod fluid;
@@ -141,6 +145,14 @@ pub mod flower_pot_transformations;
 #[path = "generated/fuels.rs"]
 pub mod fuels;
 
+#[rustfmt::skip]
+#[path = "generated/attributes.rs"]
+pub mod attributes;
+
+#[rustfmt::skip]
+#[path = "generated/potion.rs"]
+pub mod potion;
+
 mod block_direction;
 pub mod block_state;
 mod blocks;
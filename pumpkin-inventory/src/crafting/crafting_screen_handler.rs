@@ -2,7 +2,9 @@ use std::any::Any;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU8;
 
-use super::recipes::{RecipeFinderScreenHandler, RecipeInputInventory};
+use super::recipes::{
+    CraftingInput, RecipeFinderScreenHandler, RecipeInputInventory, RecipeRegistry, recipe_matches,
+};
 use crate::crafting::crafting_inventory::CraftingInventory;
 use crate::player::player_inventory::PlayerInventory;
 use crate::screen_handler::{
@@ -11,9 +13,8 @@ use crate::screen_handler::{
 use crate::slot::{NormalSlot, Slot};
 use async_trait::async_trait;
 use crossbeam_utils::atomic::AtomicCell;
-use pumpkin_data::recipes::{CraftingRecipeTypes, RECIPES_CRAFTING, RecipeResultStruct};
+use pumpkin_data::recipes::{CraftingRecipeTypes, RecipeResultStruct};
 use pumpkin_data::screen::WindowType;
-use pumpkin_data::tag::Tagable;
 use pumpkin_world::inventory::Inventory;
 use pumpkin_world::item::ItemStack;
 use tokio::sync::Mutex;
@@ -31,207 +32,6 @@ pub struct ResultSlot {
     recipe_cache: AtomicCell<Option<&'static CraftingRecipeTypes>>,
 }
 
-fn is_symmetrical_horizontally(pattern: &'static [&'static str]) -> bool {
-    let width = pattern.first().map_or(0, |s| s.len());
-    for row in pattern {
-        if row.len() != width {
-            return false; // All rows must have the same length
-        }
-        for j in 0..width / 2 {
-            if row.chars().nth(j) != row.chars().nth(width - j - 1) {
-                return false; // Characters must match symmetrically
-            }
-        }
-    }
-    true
-}
-
-async fn recipe_matches<'a>(
-    recipe: &'static CraftingRecipeTypes,
-    input_height: usize,
-    input_width: usize,
-    top_x: usize,
-    top_y: usize,
-    count: usize,
-    inventory: &'a dyn RecipeInputInventory,
-) -> Option<&'a RecipeResultStruct> {
-    match recipe {
-        CraftingRecipeTypes::CraftingShaped {
-            key,
-            pattern,
-            result,
-            ..
-        } => {
-            if pattern.len() != input_height || pattern.first().unwrap().len() != input_width {
-                return None;
-            }
-
-            if count
-                != pattern
-                    .iter()
-                    .map(|l| l.chars().filter(|c| *c != ' ').count())
-                    .sum::<usize>()
-            {
-                return None;
-            }
-
-            let x_offset = top_x;
-            let y_offset = top_y;
-
-            let mut matched = true;
-            'outer: for y in 0..pattern.len() {
-                for x in 0..pattern[y].len() {
-                    let current_key = pattern[y].chars().nth(x).unwrap();
-                    let slot = inventory
-                        .get_stack((y + y_offset) * inventory.get_height() + (x + x_offset))
-                        .await;
-                    if current_key == ' ' {
-                        if !slot.lock().await.is_empty() {
-                            matched = false;
-                            break 'outer;
-                        }
-                        continue;
-                    }
-
-                    let ingredient = key
-                        .iter()
-                        .find_map(|(k, v)| (*k == current_key).then_some(v))
-                        .expect("Crafting recipe used invalid key");
-
-                    let slot = slot.lock().await;
-
-                    if !ingredient.match_item(slot.item) {
-                        matched = false;
-                        break 'outer;
-                    }
-                }
-            }
-
-            // Check for asymmetrical recipes
-            if !matched && !is_symmetrical_horizontally(pattern) {
-                matched = true;
-                'outer: for y in 0..pattern.len() {
-                    for x in 0..pattern[y].len() {
-                        let current_key = pattern[y].chars().nth(x).unwrap();
-
-                        let slot = inventory
-                            .get_stack(
-                                (y + y_offset) * inventory.get_height()
-                                    + (x_offset + input_width - 1 - x),
-                            )
-                            .await;
-                        if current_key == ' ' {
-                            if !slot.lock().await.is_empty() {
-                                matched = false;
-                                break 'outer;
-                            }
-                            continue;
-                        }
-
-                        let ingredient = key
-                            .iter()
-                            .find_map(|(k, v)| (*k == current_key).then_some(v))
-                            .expect("Crafting recipe used invalid key");
-
-                        let slot = slot.lock().await;
-
-                        if !ingredient.match_item(slot.item) {
-                            matched = false;
-                            break 'outer;
-                        }
-                    }
-                }
-            }
-
-            // TODO: Apply components
-            if matched { Some(result) } else { None }
-        }
-        CraftingRecipeTypes::CraftingShapeless {
-            ingredients,
-            result,
-            ..
-        } => {
-            if count != ingredients.len() {
-                return None;
-            }
-
-            let mut ingredient_used = vec![false; ingredients.len()];
-            'next_slot: for i in 0..inventory.size() {
-                let slot = inventory.get_stack(i).await;
-                let slot = slot.lock().await;
-
-                if slot.is_empty() {
-                    continue 'next_slot;
-                }
-
-                for i in 0..ingredients.len() {
-                    if !ingredient_used[i] && ingredients[i].match_item(slot.item) {
-                        ingredient_used[i] = true;
-                        continue 'next_slot;
-                    }
-                }
-
-                return None;
-            }
-
-            // TODO: Apply components
-            Some(result)
-        }
-        CraftingRecipeTypes::CraftingTransmute {
-            input,
-            material,
-            result,
-            ..
-        } => {
-            if count != 2 {
-                return None;
-            }
-
-            'item_stack: for i in 0..inventory.size() {
-                let slot = inventory.get_stack(i).await;
-                let slot = slot.lock().await;
-
-                if slot.is_empty() {
-                    continue 'item_stack;
-                }
-
-                if !material.match_item(slot.item) && !input.match_item(slot.item) {
-                    return None;
-                }
-            }
-
-            // TODO: Copy components
-            Some(result)
-        }
-        CraftingRecipeTypes::CraftingDecoratedPot { .. } => {
-            if count != 4 || inventory.get_width() != 3 || inventory.get_height() != 3 {
-                return None;
-            }
-
-            for position in (1..=7).step_by(2) {
-                let slot = inventory.get_stack(position).await;
-                let slot = slot.lock().await;
-
-                if slot.is_empty()
-                    || !slot
-                        .item
-                        .is_tagged_with("#minecraft:decorated_pot_ingredients")
-                        .unwrap()
-                {
-                    return None;
-                }
-            }
-
-            // TODO: Handle side textures
-            Some(&RecipeResultStruct {
-                id: "minecraft:decorated_pot",
-                count: 1,
-            })
-        }
-        CraftingRecipeTypes::CraftingSpecial => None,
-    }
-}
-
 impl ResultSlot {
     fn stat_crafted(&self, _crafted_amount: u8, _player: &dyn InventoryPlayer) {}
 
@@ -247,68 +47,23 @@ impl ResultSlot {
     /// Matches the recipe in the crafting inventory and returns the result.
     ///
     /// If no recipe matches, returns `None`.
-    async fn match_recipe(&self) -> Option<(&RecipeResultStruct, &'static CraftingRecipeTypes)> {
-        let mut count: usize = 0;
-        let inventory_width = self.inventory.get_width();
-        let mut top_x = 9;
-        let mut top_y = 9;
-        let mut bottom_x = 0;
-        let mut bottom_y = 0;
-        for i in 0..self.inventory.size() {
-            let x = i % inventory_width;
-            let y = i / inventory_width;
-
-            let slot = self.inventory.get_stack(i).await;
-            let slot = slot.lock().await;
-            if !slot.is_empty() {
-                top_x = top_x.min(x);
-                top_y = top_y.min(y);
-                bottom_x = bottom_x.max(x);
-                bottom_y = bottom_y.max(y);
-                count += 1;
-            }
-        }
-
-        if count == 0 {
+    async fn match_recipe(
+        &self,
+    ) -> Option<(&'static RecipeResultStruct, &'static CraftingRecipeTypes)> {
+        let input = CraftingInput::from_inventory(&*self.inventory).await;
+        if input.is_empty() {
             return None;
         }
-        let input_width = bottom_x + 1 - top_x;
-        let input_height = bottom_y + 1 - top_y;
 
         if let Some(cached_recipe) = self.recipe_cache.load() {
-            if let Some(result) = recipe_matches(
-                cached_recipe,
-                input_height,
-                input_width,
-                top_x,
-                top_y,
-                count,
-                &*self.inventory,
-            )
-            .await
-            {
+            if let Some(result) = recipe_matches(cached_recipe, &input) {
                 return Some((result, cached_recipe));
             }
         }
 
-        for recipe in RECIPES_CRAFTING {
-            if let Some(result) = recipe_matches(
-                recipe,
-                input_height,
-                input_width,
-                top_x,
-                top_y,
-                count,
-                &*self.inventory,
-            )
-            .await
-            {
-                self.recipe_cache.store(Some(recipe));
-                return Some((result, recipe));
-            }
-        }
-
-        None
+        let (recipe, result) = RecipeRegistry::find_crafting_recipe(&input)?;
+        self.recipe_cache.store(Some(recipe));
+        Some((result, recipe))
     }
 
     async fn refill_output(&self) -> ItemStack {
@@ -317,7 +72,7 @@ impl ResultSlot {
             .await
             .map(|x| ItemStack::from(x.0))
             .unwrap_or(ItemStack::EMPTY);
-        *self.result.lock().await = result;
+        *self.result.lock().await = result.clone();
         result
     }
 }
@@ -343,6 +98,11 @@ impl Slot for ResultSlot {
     }
 
     async fn on_take_item(&self, player: &dyn InventoryPlayer, stack: &ItemStack) {
+        // The grid may have changed since the result was computed, never consume
+        // ingredients which do not form a recipe
+        if self.match_recipe().await.is_none() {
+            return;
+        }
         for i in 0..self.inventory.size() {
             let slot = self.inventory.get_stack(i).await;
             let mut stack = slot.lock().await;
@@ -364,7 +124,7 @@ impl Slot for ResultSlot {
     }
 
     async fn get_cloned_stack(&self) -> ItemStack {
-        *self.result.lock().await
+        self.result.lock().await.clone()
     }
 
     async fn has_stack(&self) -> bool {
@@ -396,14 +156,11 @@ impl Slot for ResultSlot {
     }
 
     async fn take_stack(&self, _amount: u8) -> ItemStack {
-        if self.has_stack().await {
-            let stack = self.result.lock().await;
-            // Vanilla: net.minecraft.world.inventory.ResultContainer#removeItem
-            // Regardless of the amount, we always return the full stack
-            *stack
-        } else {
-            ItemStack::EMPTY
-        }
+        // Validate the grid again instead of handing out the last result,
+        // the client is corrected by the next sync if they differ
+        // Vanilla: net.minecraft.world.inventory.ResultContainer#removeItem
+        // Regardless of the amount, we always return the full stack
+        self.refill_output().await
     }
 }
 
@@ -486,9 +243,12 @@ impl RecipeFinderScreenHandler for CraftingTableScreenHandler {}
 impl ScreenHandler for CraftingTableScreenHandler {
     async fn on_closed(&mut self, player: &dyn InventoryPlayer) {
         self.default_on_closed(player).await;
-        //TODO: this.craftingResultInventory.clear();
         self.drop_inventory(player, self.crafting_inventory.clone())
             .await;
+        // Recomputes the result from the now empty grid
+        self.get_behaviour().slots[0]
+            .set_stack(ItemStack::EMPTY)
+            .await;
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -509,7 +269,7 @@ impl ScreenHandler for CraftingTableScreenHandler {
         if slot.has_stack().await {
             let slot_stack = slot.get_stack().await;
             let mut slot_stack = slot_stack.lock().await;
-            let stack_prev = *slot_stack;
+            let stack_prev = slot_stack.clone();
 
             if slot_index == 0 {
                 // From crafting result slot - move to player inventory (slots 10-46)
@@ -544,11 +304,12 @@ impl ScreenHandler for CraftingTableScreenHandler {
                 }
             }
 
-            let stack = *slot_stack;
+            let stack = slot_stack.clone();
             drop(slot_stack); // release the lock before calling other methods
 
             if stack.is_empty() {
-                slot.set_stack_prev(ItemStack::EMPTY, stack_prev).await;
+                slot.set_stack_prev(ItemStack::EMPTY, stack_prev.clone())
+                    .await;
             } else {
                 slot.mark_dirty().await;
             }
@@ -561,7 +322,8 @@ impl ScreenHandler for CraftingTableScreenHandler {
             slot.on_take_item(player, &stack).await;
 
             if slot_index == 0 {
-                slot.on_quick_move_crafted(stack, stack_prev).await;
+                slot.on_quick_move_crafted(stack.clone(), stack_prev.clone())
+                    .await;
                 // For crafting result slot, drop any remaining items
                 if !stack.is_empty() {
                     player.drop_item(stack, false).await;
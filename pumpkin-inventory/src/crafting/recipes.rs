@@ -1,4 +1,10 @@
+use pumpkin_data::item::Item;
+use pumpkin_data::recipes::{
+    CraftingRecipeTypes, RECIPES_CRAFTING, RecipeIngredientTypes, RecipeResultStruct,
+};
+use pumpkin_data::tag::Tagable;
 use pumpkin_world::inventory::Inventory;
+use pumpkin_world::item::ItemStack;
 
 // RecipeMatcher.java
 pub struct RecipeMatcher {}
@@ -16,3 +22,313 @@ pub trait RecipeInputInventory: Inventory {
     // createRecipeInput
     // createPositionedRecipeInput
 }
+
+/// The items of a crafting grid, trimmed to the smallest rectangle containing all of them.
+///
+/// Vanilla: net.minecraft.world.item.crafting.CraftingInput
+pub struct CraftingInput {
+    width: usize,
+    height: usize,
+    items: Vec<Option<&'static Item>>,
+}
+
+impl CraftingInput {
+    /// Creates the input from the items of a grid with the given width, row by row.
+    #[must_use]
+    pub fn new(grid: &[Option<&'static Item>], width: usize) -> Self {
+        let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+        for (i, _) in grid.iter().enumerate().filter(|(_, item)| item.is_some()) {
+            let (x, y) = (i % width, i / width);
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+        if left == usize::MAX {
+            return Self {
+                width: 0,
+                height: 0,
+                items: Vec::new(),
+            };
+        }
+
+        let items = (top..=bottom)
+            .flat_map(|y| (left..=right).map(move |x| grid[y * width + x]))
+            .collect();
+        Self {
+            width: right + 1 - left,
+            height: bottom + 1 - top,
+            items,
+        }
+    }
+
+    pub async fn from_inventory(inventory: &dyn RecipeInputInventory) -> Self {
+        let mut grid = Vec::with_capacity(inventory.size());
+        for i in 0..inventory.size() {
+            let stack = inventory.get_stack(i).await;
+            let stack = stack.lock().await;
+            grid.push((!stack.is_empty()).then_some(stack.item));
+        }
+        Self::new(&grid, inventory.get_width())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&'static Item> {
+        self.items[y * self.width + x]
+    }
+
+    fn stacks(&self) -> impl Iterator<Item = &'static Item> + '_ {
+        self.items.iter().flatten().copied()
+    }
+}
+
+fn shaped_matches(
+    key: &[(char, RecipeIngredientTypes)],
+    pattern: &[&str],
+    input: &CraftingInput,
+    mirrored: bool,
+) -> bool {
+    if pattern.len() != input.height || pattern.iter().any(|row| row.len() != input.width) {
+        return false;
+    }
+    pattern.iter().enumerate().all(|(y, row)| {
+        row.chars().enumerate().all(|(x, symbol)| {
+            let x = if mirrored { input.width - 1 - x } else { x };
+            match (symbol, input.get(x, y)) {
+                (' ', item) => item.is_none(),
+                (_, None) => false,
+                (symbol, Some(item)) => key
+                    .iter()
+                    .find_map(|(k, v)| (*k == symbol).then_some(v))
+                    .expect("Crafting recipe used invalid key")
+                    .match_item(item),
+            }
+        })
+    })
+}
+
+/// Whether every item can be given a different ingredient it matches.
+///
+/// Taking the first matching ingredient isn't enough, it may be the only one another item
+/// matches, so this searches for augmenting paths like a bipartite matching.
+fn match_ingredients(items: &[&'static Item], ingredients: &[RecipeIngredientTypes]) -> bool {
+    fn assign(
+        item: usize,
+        items: &[&'static Item],
+        ingredients: &[RecipeIngredientTypes],
+        owners: &mut [Option<usize>],
+        visited: &mut [bool],
+    ) -> bool {
+        for (ingredient, candidate) in ingredients.iter().enumerate() {
+            if visited[ingredient] || !candidate.match_item(items[item]) {
+                continue;
+            }
+            visited[ingredient] = true;
+            // Take a free ingredient, or move the item holding it to another one
+            let is_free = match owners[ingredient] {
+                None => true,
+                Some(other) => assign(other, items, ingredients, owners, visited),
+            };
+            if is_free {
+                owners[ingredient] = Some(item);
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut owners = vec![None; ingredients.len()];
+    (0..items.len()).all(|item| {
+        let mut visited = vec![false; ingredients.len()];
+        assign(item, items, ingredients, &mut owners, &mut visited)
+    })
+}
+
+/// Returns the result of the recipe if the input matches it.
+///
+/// Shaped recipes must match their pattern exactly (or mirrored), shapeless recipes
+/// must contain each ingredient exactly once, in any order.
+#[must_use]
+pub fn recipe_matches(
+    recipe: &'static CraftingRecipeTypes,
+    input: &CraftingInput,
+) -> Option<&'static RecipeResultStruct> {
+    if input.is_empty() {
+        return None;
+    }
+    match recipe {
+        CraftingRecipeTypes::CraftingShaped {
+            key,
+            pattern,
+            result,
+            ..
+        } => {
+            // TODO: Apply components
+            (shaped_matches(key, pattern, input, false)
+                || shaped_matches(key, pattern, input, true))
+            .then_some(result)
+        }
+        CraftingRecipeTypes::CraftingShapeless {
+            ingredients,
+            result,
+            ..
+        } => {
+            let items: Vec<_> = input.stacks().collect();
+            // TODO: Apply components
+            (items.len() == ingredients.len() && match_ingredients(&items, ingredients))
+                .then_some(result)
+        }
+        CraftingRecipeTypes::CraftingTransmute {
+            input: transmuted,
+            material,
+            result,
+            ..
+        } => {
+            let mut stacks = input.stacks();
+            let (Some(first), Some(second), None) = (stacks.next(), stacks.next(), stacks.next())
+            else {
+                return None;
+            };
+            let matches = |input: &Item, other: &Item| {
+                transmuted.match_item(input) && material.match_item(other)
+            };
+
+            // TODO: Copy components
+            (matches(first, second) || matches(second, first)).then_some(result)
+        }
+        CraftingRecipeTypes::CraftingDecoratedPot { .. } => {
+            if input.width != 3 || input.height != 3 || input.stacks().count() != 4 {
+                return None;
+            }
+
+            for (x, y) in [(1, 0), (0, 1), (2, 1), (1, 2)] {
+                let is_sherd = input.get(x, y).is_some_and(|item| {
+                    item.is_tagged_with("#minecraft:decorated_pot_ingredients")
+                        .unwrap()
+                });
+                if !is_sherd {
+                    return None;
+                }
+            }
+
+            // TODO: Handle side textures
+            Some(&RecipeResultStruct {
+                id: "minecraft:decorated_pot",
+                count: 1,
+            })
+        }
+        CraftingRecipeTypes::CraftingSpecial => None,
+    }
+}
+
+pub type Recipe = CraftingRecipeTypes;
+
+/// The vanilla crafting recipes, generated from the recipe data at build time.
+///
+/// Crafting grids are always matched on the server, the results a client
+/// claims to have crafted are never trusted.
+pub struct RecipeRegistry;
+
+impl RecipeRegistry {
+    #[must_use]
+    pub fn recipes() -> &'static [Recipe] {
+        RECIPES_CRAFTING
+    }
+
+    /// Finds the recipe crafted by the items in a 3x3 grid.
+    ///
+    /// Shaped recipes may be placed anywhere in the grid and may be mirrored,
+    /// shapeless recipes must contain each ingredient exactly once.
+    #[must_use]
+    pub fn find_recipe(grid: &[[Option<ItemStack>; 3]; 3]) -> Option<&'static Recipe> {
+        let items: Vec<_> = grid
+            .iter()
+            .flatten()
+            .map(|stack| {
+                stack
+                    .as_ref()
+                    .filter(|stack| !stack.is_empty())
+                    .map(|stack| stack.item)
+            })
+            .collect();
+        Self::find_crafting_recipe(&CraftingInput::new(&items, 3)).map(|(recipe, _)| recipe)
+    }
+
+    /// Finds the first crafting recipe the input matches, together with its result.
+    #[must_use]
+    pub fn find_crafting_recipe(
+        input: &CraftingInput,
+    ) -> Option<(&'static Recipe, &'static RecipeResultStruct)> {
+        Self::recipes()
+            .iter()
+            .find_map(|recipe| recipe_matches(recipe, input).map(|result| (recipe, result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::item::Item;
+    use pumpkin_data::recipes::{CraftingRecipeTypes, RecipeIngredientTypes};
+    use pumpkin_world::item::ItemStack;
+
+    use super::{Recipe, RecipeRegistry, match_ingredients};
+
+    fn result_id(recipe: &Recipe) -> &'static str {
+        match recipe {
+            CraftingRecipeTypes::CraftingShaped { result, .. }
+            | CraftingRecipeTypes::CraftingShapeless { result, .. } => result.id,
+            _ => "",
+        }
+    }
+
+    #[test]
+    fn shapeless_ingredients_are_matched_as_a_whole() {
+        let ingredients = [
+            RecipeIngredientTypes::OneOf(&["minecraft:oak_planks", "minecraft:stone"]),
+            RecipeIngredientTypes::Simple("minecraft:oak_planks"),
+        ];
+        // The planks fit the first ingredient too, but the stone only fits that one
+        assert!(match_ingredients(
+            &[&Item::OAK_PLANKS, &Item::STONE],
+            &ingredients
+        ));
+        assert!(!match_ingredients(
+            &[&Item::STONE, &Item::STONE],
+            &ingredients
+        ));
+    }
+
+    #[test]
+    fn shaped_recipe_matches_anywhere_in_grid() {
+        let planks = || Some(ItemStack::new(1, &Item::OAK_PLANKS));
+        let grid = [
+            [None, None, planks()],
+            [None, None, planks()],
+            [None, None, None],
+        ];
+        let recipe = RecipeRegistry::find_recipe(&grid).expect("sticks should match");
+        assert_eq!(result_id(recipe), "minecraft:stick");
+
+        let grid = [
+            [planks(), None, None],
+            [None, planks(), None],
+            [None, None, None],
+        ];
+        assert!(RecipeRegistry::find_recipe(&grid).is_none());
+    }
+
+    #[test]
+    fn shapeless_recipe_matches_in_any_slot() {
+        let grid = [
+            [None, None, None],
+            [None, None, None],
+            [None, Some(ItemStack::new(1, &Item::OAK_LOG)), None],
+        ];
+        let recipe = RecipeRegistry::find_recipe(&grid).expect("planks should match");
+        assert_eq!(result_id(recipe), "minecraft:oak_planks");
+    }
+}
@@ -25,6 +25,21 @@ pub fn create_generic_9x3(
     )
 }
 
+pub fn create_generic_9x6(
+    sync_id: u8,
+    player_inventory: &Arc<PlayerInventory>,
+    inventory: Arc<dyn Inventory>,
+) -> GenericContainerScreenHandler {
+    GenericContainerScreenHandler::new(
+        WindowType::Generic9x6,
+        sync_id,
+        player_inventory,
+        inventory,
+        6,
+        9,
+    )
+}
+
 pub fn create_generic_3x3(
     sync_id: u8,
     player_inventory: &Arc<PlayerInventory>,
@@ -108,7 +123,7 @@ impl ScreenHandler for GenericContainerScreenHandler {
 
         if slot.has_stack().await {
             let slot_stack = slot.get_stack().await;
-            stack_left = *slot_stack.lock().await;
+            stack_left = slot_stack.lock().await.clone();
 
             if slot_index < (self.rows * 9) as i32 {
                 if !self
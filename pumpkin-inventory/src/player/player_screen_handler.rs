@@ -145,7 +145,7 @@ impl ScreenHandler for PlayerScreenHandler {
         if slot.has_stack().await {
             let slot_stack = slot.get_stack().await;
             let mut slot_stack = slot_stack.lock().await;
-            let stack_prev = *slot_stack;
+            let stack_prev = slot_stack.clone();
 
             #[allow(clippy::if_same_then_else)]
             if slot_index == 0 {
@@ -174,10 +174,11 @@ impl ScreenHandler for PlayerScreenHandler {
                 return ItemStack::EMPTY;
             }
 
-            let stack = *slot_stack;
+            let stack = slot_stack.clone();
             drop(slot_stack); // release the lock before calling other methods
             if stack.is_empty() {
-                slot.set_stack_prev(ItemStack::EMPTY, stack_prev).await;
+                slot.set_stack_prev(ItemStack::EMPTY, stack_prev.clone())
+                    .await;
             } else {
                 slot.mark_dirty().await;
             }
@@ -191,7 +192,8 @@ impl ScreenHandler for PlayerScreenHandler {
             if slot_index == 0 {
                 // From crafting result slot
                 // Notify the result slot to refill
-                slot.on_quick_move_crafted(stack, stack_prev).await;
+                slot.on_quick_move_crafted(stack.clone(), stack_prev.clone())
+                    .await;
                 // For crafting result slot, drop any remaining items
                 if !stack.is_empty() {
                     player.drop_item(stack, false).await;
@@ -54,7 +54,7 @@ pub trait Slot: Send + Sync + Debug {
             .await
             .expect("Timed out while trying to acquire lock");
 
-        *lock
+        lock.clone()
     }
 
     async fn has_stack(&self) -> bool {
@@ -135,7 +135,7 @@ pub trait Slot: Send + Sync + Debug {
             None
         } else {
             if self.get_cloned_stack().await.is_empty() {
-                self.set_stack_prev(ItemStack::EMPTY, stack).await;
+                self.set_stack_prev(ItemStack::EMPTY, stack.clone()).await;
             }
 
             Some(stack)
@@ -178,7 +178,7 @@ pub trait Slot: Send + Sync + Debug {
                 } else if stack.are_items_and_components_equal(&stack_self) {
                     stack.decrement(min_count);
                     stack_self.increment(min_count);
-                    let cloned_stack = *stack_self;
+                    let cloned_stack = stack_self.clone();
                     drop(stack_self);
                     self.set_stack(cloned_stack).await;
                 }
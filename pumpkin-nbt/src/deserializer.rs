@@ -1,6 +1,9 @@
 use crate::*;
 use io::Read;
-use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
 use serde::{Deserialize, forward_to_deserialize_any};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -98,14 +101,76 @@ where
     T::deserialize(&mut deserializer)
 }
 
+impl<R: Read> Deserializer<R> {
+    /// Visits a list or an array as a sequence of its values.
+    fn deserialize_list<'de, V>(&mut self, tag: u8, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let list_type = match tag {
+            LIST_ID => self.input.get_u8_be()?,
+            INT_ARRAY_ID => INT_ID,
+            LONG_ARRAY_ID => LONG_ID,
+            BYTE_ARRAY_ID => BYTE_ID,
+            _ => {
+                return Err(Error::SerdeError(format!(
+                    "Trying to deserialize a sequence from a non list tag ({tag})"
+                )));
+            }
+        };
+
+        let remaining_values = self.input.get_i32_be()?;
+        if remaining_values < 0 {
+            return Err(Error::NegativeLength(remaining_values));
+        }
+
+        visitor.visit_seq(ListAccess {
+            de: self,
+            list_type,
+            remaining_values: remaining_values as usize,
+        })
+    }
+}
+
 impl<'de, R: Read> de::Deserializer<'de> for &mut Deserializer<R> {
     type Error = Error;
 
     forward_to_deserialize_any! {
-        i8 i16 i32 i64 f32 f64 char str string unit unit_struct seq tuple tuple_struct
+        i8 i16 i32 i64 f32 f64 char str string unit unit_struct
         bytes newtype_struct byte_buf
     }
 
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let Some(tag) = self.tag_to_deserialize_stack.pop() else {
+            return Err(Error::SerdeError(
+                "The top level must be a component (e.g. a struct)".to_string(),
+            ));
+        };
+        self.deserialize_list(tag, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
@@ -132,27 +197,13 @@ impl<'de, R: Read> de::Deserializer<'de> for &mut Deserializer<R> {
             END_ID => Err(Error::SerdeError(
                 "Trying to deserialize an END tag!".to_string(),
             )),
-            LIST_ID | INT_ARRAY_ID | LONG_ARRAY_ID | BYTE_ARRAY_ID => {
-                let list_type = match tag_to_deserialize {
-                    LIST_ID => self.input.get_u8_be()?,
-                    INT_ARRAY_ID => INT_ID,
-                    LONG_ARRAY_ID => LONG_ID,
-                    BYTE_ARRAY_ID => BYTE_ID,
-                    _ => unreachable!(),
-                };
-
-                let remaining_values = self.input.get_i32_be()?;
-                if remaining_values < 0 {
-                    return Err(Error::NegativeLength(remaining_values));
-                }
-
-                let result = visitor.visit_seq(ListAccess {
-                    de: self,
-                    list_type,
-                    remaining_values: remaining_values as usize,
-                })?;
-                Ok(result)
-            }
+            LIST_ID => self.deserialize_list(tag_to_deserialize, visitor),
+            // Self describing types have to tell arrays apart from lists, so they get them as
+            // the variants `nbt_int_array` and friends serialize them as
+            INT_ARRAY_ID | LONG_ARRAY_ID | BYTE_ARRAY_ID => visitor.visit_enum(ArrayAccess {
+                de: self,
+                tag: tag_to_deserialize,
+            }),
             COMPOUND_ID => visitor.visit_map(CompoundAccess { de: self }),
             _ => {
                 let result = match NbtTag::deserialize_data(&mut self.input, tag_to_deserialize)? {
@@ -396,3 +447,60 @@ impl<'de, R: Read> SeqAccess<'de> for ListAccess<'_, R> {
         result
     }
 }
+
+struct ArrayAccess<'a, R: Read> {
+    de: &'a mut Deserializer<R>,
+    tag: u8,
+}
+
+impl<'de, R: Read> EnumAccess<'de> for ArrayAccess<'_, R> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let array_type = match self.tag {
+            INT_ARRAY_ID => NBT_INT_ARRAY_TAG,
+            LONG_ARRAY_ID => NBT_LONG_ARRAY_TAG,
+            _ => NBT_BYTE_ARRAY_TAG,
+        };
+        let variant = seed.deserialize(array_type.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, R: Read> VariantAccess<'de> for ArrayAccess<'_, R> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Err(Error::SerdeError(
+            "Arrays are not unit variants".to_string(),
+        ))
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.de.tag_to_deserialize_stack.push(self.tag);
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_list(self.tag, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], _visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::SerdeError(
+            "Arrays are not struct variants".to_string(),
+        ))
+    }
+}
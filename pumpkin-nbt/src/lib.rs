@@ -213,12 +213,15 @@ impl_array!(nbt_byte_array, NBT_BYTE_ARRAY_TAG);
 mod test {
 
     use crate::Error;
+    use crate::INT_ARRAY_ID;
+    use crate::compound::NbtCompound;
     use crate::deserializer::from_bytes;
     use crate::nbt_byte_array;
     use crate::nbt_int_array;
     use crate::nbt_long_array;
     use crate::serializer::to_bytes;
     use crate::serializer::to_bytes_named;
+    use crate::tag::NbtTag;
     use crate::{deserializer::from_bytes_unnamed, serializer::to_bytes_unnamed};
     use serde::{Deserialize, Serialize};
 
@@ -522,5 +525,33 @@ mod test {
         assert_eq!(value, reconstructed);
     }
 
+    #[test]
+    fn test_nbt_tag_arrays() {
+        let mut compound = NbtCompound::new();
+        compound.put("UUID", NbtTag::IntArray(vec![-1, 0, i32::MIN, i32::MAX]));
+        compound.put("longs", NbtTag::LongArray(vec![i64::MIN, 2]));
+        compound.put("bytes", NbtTag::ByteArray(vec![0, 255].into_boxed_slice()));
+        compound.put("ints", NbtTag::List(vec![NbtTag::Int(1), NbtTag::Int(2)]));
+        compound.put(
+            "arrays",
+            NbtTag::List(vec![
+                NbtTag::IntArray(vec![1, 2]),
+                NbtTag::IntArray(vec![3]),
+            ]),
+        );
+
+        let mut bytes = Vec::new();
+        to_bytes(&compound, &mut bytes).unwrap();
+        // The first tag is written as an int array, not as a list
+        assert_eq!(bytes[3], INT_ARRAY_ID);
+
+        let reconstructed: NbtCompound = from_bytes(&bytes[..]).unwrap();
+        assert_eq!(reconstructed, compound);
+        assert_eq!(
+            reconstructed.get_int_array("UUID"),
+            Some(&[-1, 0, i32::MIN, i32::MAX][..])
+        );
+    }
+
     // TODO: More robust tests
 }
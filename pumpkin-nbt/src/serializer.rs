@@ -82,19 +82,38 @@ enum State {
     },
     ListElement,
     CheckedListElement,
+    // Arrays in lists have no name.
     Array {
-        name: String,
+        name: Option<String>,
         array_type: &'static str,
     },
 }
 
+/// The tag id of an array marked with `nbt_byte_array`, `nbt_int_array` or `nbt_long_array`.
+fn array_id(array_type: &str) -> Result<u8> {
+    match array_type {
+        NBT_BYTE_ARRAY_TAG => Ok(BYTE_ARRAY_ID),
+        NBT_INT_ARRAY_TAG => Ok(INT_ARRAY_ID),
+        NBT_LONG_ARRAY_TAG => Ok(LONG_ARRAY_ID),
+        _ => Err(Error::SerdeError(
+            "Array supports only `byte`, `int`, and `long`".to_string(),
+        )),
+    }
+}
+
 impl<W: Write> Serializer<W> {
     fn parse_state(&mut self, tag: u8) -> Result<()> {
         match &mut self.state {
-            State::Named(name) | State::Array { name, .. } => {
+            State::Named(name)
+            | State::Array {
+                name: Some(name), ..
+            } => {
                 self.output.write_u8_be(tag)?;
                 NbtTag::String(name.clone()).serialize_data(&mut self.output)?;
             }
+            State::Array { name: None, .. } => {
+                // The list element was already written by `serialize_newtype_variant`
+            }
             State::FirstListElement { len } => {
                 self.output.write_u8_be(tag)?;
                 self.output.write_i32_be(*len)?;
@@ -331,7 +350,11 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
     {
         if name == NBT_ARRAY_TAG {
             let name = match self.state {
-                State::Named(ref name) => name.clone(),
+                State::Named(ref name) => Some(name.clone()),
+                State::FirstListElement { .. } | State::ListElement | State::CheckedListElement => {
+                    self.parse_state(array_id(variant)?)?;
+                    None
+                }
                 _ => return Err(Error::SerdeError("Invalid `Serializer` state!".to_string())),
             };
 
@@ -359,15 +382,11 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
 
         match &mut self.state {
             State::Array { array_type, .. } => {
-                let (id, expected_tag) = match *array_type {
-                    NBT_BYTE_ARRAY_TAG => (BYTE_ARRAY_ID, BYTE_ID),
-                    NBT_INT_ARRAY_TAG => (INT_ARRAY_ID, INT_ID),
-                    NBT_LONG_ARRAY_TAG => (LONG_ARRAY_ID, LONG_ID),
-                    _ => {
-                        return Err(Error::SerdeError(
-                            "Array supports only `byte`, `int`, and `long`".to_string(),
-                        ));
-                    }
+                let id = array_id(array_type)?;
+                let expected_tag = match id {
+                    BYTE_ARRAY_ID => BYTE_ID,
+                    INT_ARRAY_ID => INT_ID,
+                    _ => LONG_ID,
                 };
 
                 self.parse_state(id)?;
@@ -405,14 +405,7 @@ impl Serialize for NbtTag {
             NbtTag::Long(v) => serializer.serialize_i64(*v),
             NbtTag::Float(v) => serializer.serialize_f32(*v),
             NbtTag::Double(v) => serializer.serialize_f64(*v),
-            NbtTag::ByteArray(v) => {
-                use serde::ser::SerializeSeq;
-                let mut seq = serializer.serialize_seq(Some(v.len()))?;
-                for byte in v.iter() {
-                    seq.serialize_element(byte)?;
-                }
-                seq.end()
-            }
+            NbtTag::ByteArray(v) => nbt_byte_array(v, serializer),
             NbtTag::String(v) => serializer.serialize_str(v),
             NbtTag::List(v) => {
                 use serde::ser::SerializeSeq;
@@ -423,22 +416,8 @@ impl Serialize for NbtTag {
                 seq.end()
             }
             NbtTag::Compound(v) => v.serialize(serializer),
-            NbtTag::IntArray(v) => {
-                use serde::ser::SerializeSeq;
-                let mut seq = serializer.serialize_seq(Some(v.len()))?;
-                for int in v.iter() {
-                    seq.serialize_element(int)?;
-                }
-                seq.end()
-            }
-            NbtTag::LongArray(v) => {
-                use serde::ser::SerializeSeq;
-                let mut seq = serializer.serialize_seq(Some(v.len()))?;
-                for long in v.iter() {
-                    seq.serialize_element(long)?;
-                }
-                seq.end()
-            }
+            NbtTag::IntArray(v) => nbt_int_array(v, serializer),
+            NbtTag::LongArray(v) => nbt_long_array(v, serializer),
         }
     }
 }
@@ -503,6 +482,27 @@ impl<'de> Deserialize<'de> for NbtTag {
                 Ok(NbtTag::List(vec))
             }
 
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::EnumAccess<'de>,
+            {
+                use serde::de::VariantAccess;
+
+                // Arrays come in as the variants `nbt_byte_array` and friends serialize them as
+                let (array_type, array) = data.variant::<String>()?;
+                match array_type.as_str() {
+                    NBT_BYTE_ARRAY_TAG => Ok(NbtTag::ByteArray(
+                        array.newtype_variant::<Vec<u8>>()?.into_boxed_slice(),
+                    )),
+                    NBT_INT_ARRAY_TAG => Ok(NbtTag::IntArray(array.newtype_variant()?)),
+                    NBT_LONG_ARRAY_TAG => Ok(NbtTag::LongArray(array.newtype_variant()?)),
+                    other => Err(serde::de::Error::unknown_variant(
+                        other,
+                        &[NBT_BYTE_ARRAY_TAG, NBT_INT_ARRAY_TAG, NBT_LONG_ARRAY_TAG],
+                    )),
+                }
+            }
+
             fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
             where
                 A: serde::de::MapAccess<'de>,
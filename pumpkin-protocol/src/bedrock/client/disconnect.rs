@@ -0,0 +1,25 @@
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+use crate::codec::var_int::VarInt;
+
+#[derive(Serialize, Deserialize)]
+#[packet(0x05)]
+pub struct CDisconnect {
+    reason: VarInt,
+    skip_message: bool,
+    message: String,
+    filtered_message: String,
+}
+
+impl CDisconnect {
+    pub fn new(message: String) -> Self {
+        Self {
+            // Unknown
+            reason: VarInt(0),
+            skip_message: false,
+            filtered_message: message.clone(),
+            message,
+        }
+    }
+}
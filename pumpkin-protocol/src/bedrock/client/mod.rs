@@ -1,3 +1,4 @@
+pub mod disconnect;
 pub mod gamerules_changed;
 pub mod handshake;
 pub mod network_settings;
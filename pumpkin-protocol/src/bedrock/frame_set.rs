@@ -8,6 +8,9 @@ use crate::codec::u24::U24;
 use crate::ser::{NetworkReadExt, NetworkWriteExt, ReadingError, WritingError};
 use crate::{ClientPacket, ServerPacket};
 
+/// The size of a frame set without its frames: the id and the sequence number.
+pub const FRAME_SET_HEADER_SIZE: usize = 4;
+
 #[packet[0x80]]
 pub struct FrameSet {
     pub sequence: U24,
@@ -92,6 +95,52 @@ impl Frame {
         Ok(frames)
     }
 
+    /// The size of the frame without its payload.
+    #[must_use]
+    pub fn header_size(&self) -> usize {
+        // Flags and the payload length
+        let mut size = 3;
+        if self.reliability.is_reliable() {
+            size += 3;
+        }
+        if self.reliability.is_sequenced() {
+            size += 3;
+        }
+        if self.reliability.is_ordered() {
+            size += 4;
+        }
+        if self.split_size > 0 {
+            size += 10;
+        }
+        size
+    }
+
+    /// Splits the frame into fragments with payloads of at most `max_payload` bytes.
+    ///
+    /// The fragments share the split id and the order index, the reliable number of each
+    /// fragment has to be set by the sender.
+    #[must_use]
+    pub fn split(self, max_payload: usize, split_id: u16) -> Vec<Self> {
+        let split_size = self.payload.len().div_ceil(max_payload) as u32;
+        (0..split_size)
+            .map(|split_index| {
+                let start = split_index as usize * max_payload;
+                let end = (start + max_payload).min(self.payload.len());
+                Self {
+                    reliability: self.reliability,
+                    payload: self.payload.slice(start..end),
+                    reliable_number: self.reliable_number,
+                    sequence_index: self.sequence_index,
+                    order_index: self.order_index,
+                    order_channel: self.order_channel,
+                    split_size,
+                    split_id,
+                    split_index,
+                }
+            })
+            .collect()
+    }
+
     pub fn write(&self, mut write: impl Write) -> Result<(), WritingError> {
         let is_split = self.split_size > 0;
         let mut flags = self.reliability.to_id() << 5;
@@ -122,3 +171,50 @@ impl Frame {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::Frame;
+    use crate::bedrock::RakReliability;
+
+    #[test]
+    fn split_keeps_payload_order() {
+        let payload: Vec<u8> = (0..=255).collect();
+        let frame = Frame {
+            payload: Bytes::from(payload.clone()),
+            reliability: RakReliability::ReliableOrdered,
+            order_index: 7,
+            ..Default::default()
+        };
+
+        let fragments = frame.split(100, 3);
+        assert_eq!(fragments.len(), 3);
+        assert_eq!(fragments[2].payload.len(), 56);
+        for (index, fragment) in fragments.iter().enumerate() {
+            assert_eq!(fragment.split_index, index as u32);
+            assert_eq!(fragment.split_size, 3);
+            assert_eq!(fragment.split_id, 3);
+            assert_eq!(fragment.order_index, 7);
+        }
+        let merged: Vec<u8> = fragments
+            .iter()
+            .flat_map(|fragment| fragment.payload.iter().copied())
+            .collect();
+        assert_eq!(merged, payload);
+    }
+
+    #[test]
+    fn header_size_matches_written_header() {
+        let frame = Frame {
+            payload: Bytes::from_static(&[1, 2, 3]),
+            reliability: RakReliability::ReliableOrdered,
+            split_size: 2,
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        frame.write(&mut buf).unwrap();
+        assert_eq!(buf.len(), frame.header_size() + 3);
+    }
+}
@@ -1,17 +1,41 @@
+use std::io::Read;
+
 use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
-use crate::codec::socket_address::SocketAddress;
+use crate::{
+    ServerPacket,
+    bedrock::UDP_HEADER_SIZE,
+    codec::socket_address::SocketAddress,
+    ser::{NetworkReadExt, ReadingError},
+};
 
-#[derive(Serialize, Deserialize)]
 #[packet(0x05)]
 /// The client sends this when attempting to join the server
 pub struct SOpenConnectionRequest1 {
     pub magic: [u8; 16],
     pub protocol_version: u8,
+    /// The size of the datagram including the UDP header. The client pads the packet to the
+    /// MTU it wants to try, so it only arrives when the MTU fits.
     pub mtu: u16,
 }
 
+impl ServerPacket for SOpenConnectionRequest1 {
+    fn read(mut read: impl Read) -> Result<Self, ReadingError> {
+        let mut magic = [0; 16];
+        magic.copy_from_slice(&read.read_boxed_slice(16)?);
+        let protocol_version = read.get_u8()?;
+        let padding = read.read_remaining_to_boxed_slice(u16::MAX as usize)?;
+        // Packet id, magic and protocol version come before the padding
+        let mtu = (1 + 16 + 1 + padding.len()) as u16 + UDP_HEADER_SIZE;
+        Ok(Self {
+            magic,
+            protocol_version,
+            mtu,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[packet(0x07)]
 pub struct SOpenConnectionRequest2 {
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// A rotation in steps of 1/256 of a full turn, as sent for entity yaw and pitch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Angle(pub u8);
+
+impl Angle {
+    /// Wraps the degrees into a full turn, so negative rotations work as well.
+    #[must_use]
+    pub fn from_degrees(degrees: f32) -> Self {
+        Self((degrees * 256.0 / 360.0).rem_euclid(256.0) as u8)
+    }
+
+    /// The rotation in degrees, between `0` and `360`.
+    #[must_use]
+    pub fn to_degrees(self) -> f32 {
+        f32::from(self.0) * 360.0 / 256.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Angle;
+
+    #[test]
+    fn round_trips_within_one_step() {
+        // Every degree in steps of 0.01, which includes both sides of every step boundary
+        for i in 0..36_000 {
+            let degrees = i as f32 / 100.0;
+            let round_trip = Angle::from_degrees(degrees).to_degrees();
+            assert!(
+                (degrees - round_trip).abs() <= 1.5,
+                "{degrees} became {round_trip}"
+            );
+        }
+    }
+
+    #[test]
+    fn wraps_negative_and_large_degrees() {
+        assert_eq!(Angle::from_degrees(-90.0), Angle(192));
+        assert_eq!(Angle::from_degrees(450.0), Angle(64));
+        assert_eq!(Angle::from_degrees(360.0), Angle(0));
+    }
+}
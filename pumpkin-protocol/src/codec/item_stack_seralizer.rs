@@ -1,13 +1,22 @@
 use std::borrow::Cow;
 
 use crate::VarInt;
+use pumpkin_data::Enchantment;
+use pumpkin_data::data_component::DataComponent;
 use pumpkin_data::item::Item;
+use pumpkin_data::potion::Potion;
 use pumpkin_world::item::ItemStack;
 use serde::{
     Deserialize, Serialize, Serializer,
     de::{self, SeqAccess},
+    ser::SerializeTuple,
 };
 
+const DAMAGE_COMPONENT_ID: i32 = DataComponent::Damage.id();
+const ENCHANTMENTS_COMPONENT_ID: i32 = DataComponent::Enchantments.id();
+const MAP_ID_COMPONENT_ID: i32 = DataComponent::MapId.id();
+const POTION_CONTENTS_COMPONENT_ID: i32 = DataComponent::PotionContents.id();
+
 #[derive(Debug, Clone)]
 pub struct ItemStackSerializer<'a>(pub Cow<'a, ItemStack>);
 
@@ -33,7 +42,7 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                     .ok_or(de::Error::custom("Failed to decode VarInt"))?;
 
                 let slot = if item_count.0 == 0 {
-                    ItemStackSerializer(Cow::Borrowed(&ItemStack::EMPTY))
+                    ItemStackSerializer(Cow::Owned(ItemStack::EMPTY))
                 } else {
                     let item_id = seq
                         .next_element::<VarInt>()?
@@ -46,7 +55,7 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                         .next_element::<VarInt>()?
                         .ok_or(de::Error::custom("No component remove length VarInt!"))?;
 
-                    if num_components_to_add.0 != 0 || num_components_to_remove.0 != 0 {
+                    if num_components_to_remove.0 != 0 {
                         return Err(de::Error::custom(
                             "Slot components are currently unsupported",
                         ));
@@ -57,10 +66,86 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                         .try_into()
                         .map_err(|_| de::Error::custom("Invalid item id!"))?;
 
-                    ItemStackSerializer(Cow::Owned(ItemStack::new(
+                    let mut stack = ItemStack::new(
                         item_count.0 as u8,
                         Item::from_id(item_id).unwrap_or(&Item::AIR),
-                    )))
+                    );
+
+                    for _ in 0..num_components_to_add.0 {
+                        let component_id = seq
+                            .next_element::<VarInt>()?
+                            .ok_or(de::Error::custom("No component id VarInt!"))?;
+                        match component_id.0 {
+                            DAMAGE_COMPONENT_ID => {
+                                let damage = seq
+                                    .next_element::<VarInt>()?
+                                    .ok_or(de::Error::custom("No damage VarInt!"))?;
+                                stack.damage = damage.0;
+                            }
+                            ENCHANTMENTS_COMPONENT_ID => {
+                                let enchantment_count = seq
+                                    .next_element::<VarInt>()?
+                                    .ok_or(de::Error::custom("No enchantment count VarInt!"))?;
+                                for _ in 0..enchantment_count.0 {
+                                    let enchantment_id = seq
+                                        .next_element::<VarInt>()?
+                                        .ok_or(de::Error::custom("No enchantment id VarInt!"))?;
+                                    let level = seq
+                                        .next_element::<VarInt>()?
+                                        .ok_or(de::Error::custom("No enchantment level VarInt!"))?;
+                                    let enchantment = u8::try_from(enchantment_id.0)
+                                        .ok()
+                                        .and_then(Enchantment::from_id)
+                                        .ok_or(de::Error::custom("Invalid enchantment id!"))?;
+                                    stack.add_enchantment(enchantment, level.0);
+                                }
+                            }
+                            MAP_ID_COMPONENT_ID => {
+                                let map_id = seq
+                                    .next_element::<VarInt>()?
+                                    .ok_or(de::Error::custom("No map id VarInt!"))?;
+                                stack.map_id = Some(map_id.0);
+                            }
+                            POTION_CONTENTS_COMPONENT_ID => {
+                                let potion = seq
+                                    .next_element::<Option<VarInt>>()?
+                                    .ok_or(de::Error::custom("No potion Option!"))?;
+                                let custom_color = seq
+                                    .next_element::<Option<i32>>()?
+                                    .ok_or(de::Error::custom("No potion color Option!"))?;
+                                let custom_effect_count = seq
+                                    .next_element::<VarInt>()?
+                                    .ok_or(de::Error::custom("No custom effect count VarInt!"))?;
+                                let custom_name = seq
+                                    .next_element::<Option<String>>()?
+                                    .ok_or(de::Error::custom("No potion name Option!"))?;
+                                if custom_color.is_some()
+                                    || custom_effect_count.0 != 0
+                                    || custom_name.is_some()
+                                {
+                                    return Err(de::Error::custom(
+                                        "Custom potion contents are currently unsupported",
+                                    ));
+                                }
+                                stack.potion_contents = potion
+                                    .map(|id| {
+                                        u8::try_from(id.0)
+                                            .ok()
+                                            .and_then(Potion::from_id)
+                                            .ok_or(de::Error::custom("Invalid potion id!"))
+                                    })
+                                    .transpose()?;
+                            }
+                            // TODO: Other components
+                            _ => {
+                                return Err(de::Error::custom(
+                                    "Slot components are currently unsupported",
+                                ));
+                            }
+                        }
+                    }
+
+                    ItemStackSerializer(Cow::Owned(stack))
                 };
 
                 Ok(slot)
@@ -79,23 +164,46 @@ impl Serialize for ItemStackSerializer<'_> {
         if self.0.is_empty() {
             VarInt(0).serialize(serializer)
         } else {
-            // TODO: Components
-
-            #[derive(Serialize)]
-            struct NetworkRepr {
-                item_count: VarInt,
-                item_id: VarInt,
-                components_to_add: VarInt,
-                components_to_remove: VarInt,
+            // TODO: Other components
+            let has_damage = self.0.damage > 0;
+            let has_enchantments = !self.0.enchantments.is_empty();
+            let component_count = i32::from(has_damage)
+                + i32::from(has_enchantments)
+                + i32::from(self.0.map_id.is_some())
+                + i32::from(self.0.potion_contents.is_some());
+
+            let mut tuple = serializer.serialize_tuple(4)?;
+            tuple.serialize_element(&VarInt::from(self.0.item_count))?;
+            tuple.serialize_element(&VarInt::from(self.0.item.id))?;
+            tuple.serialize_element(&VarInt(component_count))?;
+            tuple.serialize_element(&VarInt(0))?;
+            if has_damage {
+                tuple.serialize_element(&VarInt(DAMAGE_COMPONENT_ID))?;
+                tuple.serialize_element(&VarInt(self.0.damage))?;
             }
-
-            NetworkRepr {
-                item_count: self.0.item_count.into(),
-                item_id: self.0.item.id.into(),
-                components_to_add: 0.into(),
-                components_to_remove: 0.into(),
+            if has_enchantments {
+                let enchantments: Vec<(VarInt, VarInt)> = self
+                    .0
+                    .enchantments
+                    .iter()
+                    .map(|(enchantment, level)| (enchantment.id.into(), (*level).into()))
+                    .collect();
+                tuple.serialize_element(&VarInt(ENCHANTMENTS_COMPONENT_ID))?;
+                tuple.serialize_element(&enchantments)?;
             }
-            .serialize(serializer)
+            if let Some(map_id) = self.0.map_id {
+                tuple.serialize_element(&VarInt(MAP_ID_COMPONENT_ID))?;
+                tuple.serialize_element(&VarInt(map_id))?;
+            }
+            if let Some(potion) = self.0.potion_contents {
+                // Without a custom color, custom effects or a custom name
+                tuple.serialize_element(&VarInt(POTION_CONTENTS_COMPONENT_ID))?;
+                tuple.serialize_element(&Some(VarInt::from(potion.id)))?;
+                tuple.serialize_element(&None::<i32>)?;
+                tuple.serialize_element(&VarInt(0))?;
+                tuple.serialize_element(&None::<String>)?;
+            }
+            tuple.end()
         }
     }
 }
@@ -116,7 +224,7 @@ impl From<Option<ItemStack>> for ItemStackSerializer<'_> {
     fn from(item: Option<ItemStack>) -> Self {
         match item {
             Some(item) => ItemStackSerializer::from(item),
-            None => ItemStackSerializer(Cow::Borrowed(&ItemStack::EMPTY)),
+            None => ItemStackSerializer(Cow::Owned(ItemStack::EMPTY)),
         }
     }
 }
@@ -247,3 +355,72 @@ impl<'de> Deserialize<'de> for ItemComponentHash {
         deserializer.deserialize_seq(Visitor)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Enchantment, data_component::DataComponent, item::Item, potion::Potion};
+    use pumpkin_world::item::ItemStack;
+    use serde::{Deserialize, Serialize};
+
+    use crate::ser::{deserializer::Deserializer, serializer::Serializer};
+
+    use super::ItemStackSerializer;
+
+    fn round_trip(stack: ItemStack) -> ItemStack {
+        let mut buf = Vec::new();
+        ItemStackSerializer::from(stack)
+            .serialize(&mut Serializer::new(&mut buf))
+            .unwrap();
+        let mut read = buf.as_slice();
+        let stack = ItemStackSerializer::deserialize(&mut Deserializer::new(&mut read))
+            .unwrap()
+            .to_stack();
+        assert!(read.is_empty());
+        stack
+    }
+
+    #[test]
+    fn damage_and_enchantments_round_trip() {
+        let mut stack = ItemStack::new(1, &Item::DIAMOND_PICKAXE);
+        stack.damage = 42;
+        stack.add_enchantment(&Enchantment::UNBREAKING, 3);
+
+        let decoded = round_trip(stack.clone());
+        assert!(decoded.are_equal(&stack));
+    }
+
+    #[test]
+    fn map_id_round_trips() {
+        let mut stack = ItemStack::new(1, &Item::FILLED_MAP);
+        stack.map_id = Some(7);
+        assert_eq!(round_trip(stack).map_id, Some(7));
+    }
+
+    #[test]
+    fn potion_contents_round_trip() {
+        let mut stack = ItemStack::new(1, &Item::POTION);
+        stack.potion_contents = Some(&Potion::STRONG_HEALING);
+        assert_eq!(
+            round_trip(stack).potion_contents,
+            Some(&Potion::STRONG_HEALING)
+        );
+    }
+
+    #[test]
+    fn undamaged_stack_round_trips() {
+        let stack = ItemStack::new(16, &Item::STONE);
+        let decoded = round_trip(stack.clone());
+        assert!(decoded.are_equal(&stack));
+        assert_eq!(decoded.damage, 0);
+    }
+
+    #[test]
+    fn component_ids_match_the_protocol() {
+        assert_eq!(DataComponent::Damage.id(), 3);
+        assert_eq!(DataComponent::Enchantments.id(), 10);
+        assert_eq!(
+            DataComponent::from_id(10).map(|component| component.to_name()),
+            Some("minecraft:enchantments")
+        );
+    }
+}
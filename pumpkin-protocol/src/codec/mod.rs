@@ -1,3 +1,4 @@
+pub mod angle;
 pub mod ascii_string;
 pub mod bedrock_block_pos;
 pub mod bit_set;
@@ -1,15 +1,16 @@
 use pumpkin_data::packet::clientbound::CONFIG_DISCONNECT;
 use pumpkin_macros::packet;
-use serde::Deserialize;
+use pumpkin_util::text::TextComponent;
+use serde::Serialize;
 
-#[derive(serde::Serialize, Deserialize)]
+#[derive(Serialize)]
 #[packet(CONFIG_DISCONNECT)]
 pub struct CConfigDisconnect<'a> {
-    pub reason: &'a str,
+    pub reason: &'a TextComponent,
 }
 
 impl<'a> CConfigDisconnect<'a> {
-    pub fn new(reason: &'a str) -> Self {
+    pub fn new(reason: &'a TextComponent) -> Self {
         Self { reason }
     }
 }
@@ -38,6 +38,13 @@ impl RegistryEntry {
             data: Some(data_buf.into_boxed_slice()),
         }
     }
+    /// An entry without data, which the client takes from the vanilla data pack it knows
+    pub fn from_known_pack(name: &str) -> Self {
+        RegistryEntry {
+            entry_id: ResourceLocation::vanilla(name),
+            data: None,
+        }
+    }
     pub fn from_nbt_custom(name: &str, nbt: &impl Serialize) -> Self {
         let mut data_buf = Vec::new();
         pumpkin_nbt::serializer::to_bytes_unnamed(nbt, &mut data_buf).unwrap();
@@ -1,16 +1,19 @@
 use pumpkin_data::packet::clientbound::LOGIN_LOGIN_DISCONNECT;
 use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
 #[packet(LOGIN_LOGIN_DISCONNECT)]
-pub struct CLoginDisconnect<'a> {
-    pub json_reason: &'a str,
+pub struct CLoginDisconnect {
+    /// Unlike in the later phases, the reason is sent as JSON.
+    pub json_reason: String,
 }
 
-impl<'a> CLoginDisconnect<'a> {
-    // input json!
-    pub fn new(json_reason: &'a str) -> Self {
-        Self { json_reason }
+impl CLoginDisconnect {
+    pub fn new(reason: &TextComponent) -> Self {
+        Self {
+            json_reason: reason.to_json(),
+        }
     }
 }
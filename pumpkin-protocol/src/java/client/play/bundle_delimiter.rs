@@ -0,0 +1,9 @@
+use pumpkin_data::packet::clientbound::PLAY_BUNDLE_DELIMITER;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Marks the start or the end of a bundle. The client handles all packets between two
+/// delimiters in the same tick.
+#[derive(Serialize)]
+#[packet(PLAY_BUNDLE_DELIMITER)]
+pub struct CBundleDelimiter;
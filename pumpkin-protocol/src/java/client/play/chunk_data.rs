@@ -6,22 +6,46 @@ use pumpkin_macros::packet;
 use pumpkin_nbt::END_ID;
 use pumpkin_util::math::position::get_local_cord;
 use pumpkin_world::chunk::format::LightContainer;
-use pumpkin_world::chunk::{ChunkData, palette::NetworkPalette};
+use pumpkin_world::chunk::{
+    ChunkData,
+    palette::{BlockPalette, NetworkPalette},
+};
 use std::io::Write;
 
 #[packet(PLAY_LEVEL_CHUNK_WITH_LIGHT)]
-pub struct CChunkData<'a>(pub &'a ChunkData);
+pub struct CChunkData<'a> {
+    chunk: &'a ChunkData,
+    block_states: Option<&'a [BlockPalette]>,
+}
+
+impl<'a> CChunkData<'a> {
+    pub fn new(chunk: &'a ChunkData) -> Self {
+        Self {
+            chunk,
+            block_states: None,
+        }
+    }
+
+    /// Sends `block_states` instead of the block states of the chunk sections, e.g. to hide ores.
+    pub fn with_block_states(chunk: &'a ChunkData, block_states: &'a [BlockPalette]) -> Self {
+        debug_assert_eq!(chunk.section.sections.len(), block_states.len());
+        Self {
+            chunk,
+            block_states: Some(block_states),
+        }
+    }
+}
 
 impl ClientPacket for CChunkData<'_> {
     fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
         let mut write = write;
 
         // Chunk X
-        write.write_i32_be(self.0.position.x)?;
+        write.write_i32_be(self.chunk.position.x)?;
         // Chunk Z
-        write.write_i32_be(self.0.position.y)?;
+        write.write_i32_be(self.chunk.position.y)?;
 
-        let heightmaps = &self.0.heightmap;
+        let heightmaps = &self.chunk.heightmap;
         // the heighmap is a map, we put 3 values in so the size is 3
         write.write_var_int(&VarInt(3))?;
 
@@ -49,13 +73,16 @@ impl ClientPacket for CChunkData<'_> {
 
         {
             let mut blocks_and_biomes_buf = Vec::new();
-            for section in &self.0.section.sections {
+            for (index, section) in self.chunk.section.sections.iter().enumerate() {
+                let block_states = self
+                    .block_states
+                    .map_or(&section.block_states, |block_states| &block_states[index]);
                 // Block count
-                let non_empty_block_count = section.block_states.non_air_block_count() as i16;
+                let non_empty_block_count = block_states.non_air_block_count() as i16;
                 blocks_and_biomes_buf.write_i16_be(non_empty_block_count)?;
 
                 // This is a bit messy, but we dont have access to VarInt in pumpkin-world
-                let network_repr = section.block_states.convert_network();
+                let network_repr = block_states.convert_network();
                 blocks_and_biomes_buf.write_u8(network_repr.bits_per_entry)?;
                 match network_repr.palette {
                     NetworkPalette::Single(registry_id) => {
@@ -119,8 +146,8 @@ impl ClientPacket for CChunkData<'_> {
         }
 
         // TODO: block entities
-        write.write_var_int(&VarInt(self.0.block_entities.len() as i32))?;
-        for block_entity in self.0.block_entities.values() {
+        write.write_var_int(&VarInt(self.chunk.block_entities.len() as i32))?;
+        for block_entity in self.chunk.block_entities.values() {
             let block_entity = &block_entity;
             let chunk_data_nbt = block_entity.chunk_data_nbt();
             let pos = block_entity.get_position();
@@ -143,14 +170,14 @@ impl ClientPacket for CChunkData<'_> {
             let mut block_light_empty_mask = 0;
             let mut sky_light_mask = 0;
             let mut block_light_mask = 0;
-            for light_index in 0..self.0.light_engine.sky_light.len() {
-                if let LightContainer::Full(_) = &self.0.light_engine.sky_light[light_index] {
+            for light_index in 0..self.chunk.light_engine.sky_light.len() {
+                if let LightContainer::Full(_) = &self.chunk.light_engine.sky_light[light_index] {
                     sky_light_mask |= 1 << light_index;
                 } else {
                     sky_light_empty_mask |= 1 << light_index;
                 }
 
-                if let LightContainer::Full(_) = &self.0.light_engine.block_light[light_index] {
+                if let LightContainer::Full(_) = &self.chunk.light_engine.block_light[light_index] {
                     block_light_mask |= 1 << light_index;
                 } else {
                     block_light_empty_mask |= 1 << light_index;
@@ -172,8 +199,9 @@ impl ClientPacket for CChunkData<'_> {
             let light_data_size: VarInt = LightContainer::ARRAY_SIZE.try_into().unwrap();
             // Sky light
             write.write_var_int(&VarInt(sky_light_mask.count_ones() as i32))?;
-            for light_index in 0..self.0.light_engine.sky_light.len() {
-                if let LightContainer::Full(data) = &self.0.light_engine.sky_light[light_index] {
+            for light_index in 0..self.chunk.light_engine.sky_light.len() {
+                if let LightContainer::Full(data) = &self.chunk.light_engine.sky_light[light_index]
+                {
                     write.write_var_int(&light_data_size)?;
                     write.write_slice(data)?;
                 }
@@ -181,8 +209,10 @@ impl ClientPacket for CChunkData<'_> {
 
             // Block Light
             write.write_var_int(&VarInt(block_light_mask.count_ones() as i32))?;
-            for light_index in 0..self.0.light_engine.block_light.len() {
-                if let LightContainer::Full(data) = &self.0.light_engine.block_light[light_index] {
+            for light_index in 0..self.chunk.light_engine.block_light.len() {
+                if let LightContainer::Full(data) =
+                    &self.chunk.light_engine.block_light[light_index]
+                {
                     write.write_var_int(&light_data_size)?;
                     write.write_slice(data)?;
                 }
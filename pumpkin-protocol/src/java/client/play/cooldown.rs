@@ -0,0 +1,24 @@
+use pumpkin_data::packet::clientbound::PLAY_COOLDOWN;
+use pumpkin_macros::packet;
+use pumpkin_util::resource_location::ResourceLocation;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Puts every item in the cooldown group on cooldown, greying it out in the hotbar.
+/// A cooldown of 0 ticks removes the cooldown.
+#[derive(Serialize)]
+#[packet(PLAY_COOLDOWN)]
+pub struct CCooldown<'a> {
+    cooldown_group: &'a ResourceLocation,
+    cooldown_ticks: VarInt,
+}
+
+impl<'a> CCooldown<'a> {
+    pub fn new(cooldown_group: &'a ResourceLocation, cooldown_ticks: VarInt) -> Self {
+        Self {
+            cooldown_group,
+            cooldown_ticks,
+        }
+    }
+}
@@ -3,16 +3,17 @@ use pumpkin_macros::packet;
 use serde::{Deserialize, Serialize};
 
 use crate::VarInt;
+use crate::codec::angle::Angle;
 
 #[derive(Serialize, Deserialize)]
 #[packet(PLAY_ROTATE_HEAD)]
 pub struct CHeadRot {
     entity_id: VarInt,
-    head_yaw: u8,
+    head_yaw: Angle,
 }
 
 impl CHeadRot {
-    pub fn new(entity_id: VarInt, head_yaw: u8) -> Self {
+    pub fn new(entity_id: VarInt, head_yaw: Angle) -> Self {
         Self {
             entity_id,
             head_yaw,
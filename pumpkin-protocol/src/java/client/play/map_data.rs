@@ -0,0 +1,91 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_MAP_ITEM_DATA;
+use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
+
+use crate::ser::NetworkWriteExt;
+use crate::{ClientPacket, VarInt, WritingError};
+
+#[packet(PLAY_MAP_ITEM_DATA)]
+pub struct CMapData<'a> {
+    pub map_id: VarInt,
+    pub scale: i8,
+    pub locked: bool,
+    /// `None` keeps the decorations the client already has
+    pub decorations: Option<&'a [MapDecoration]>,
+    /// `None` keeps the colors the client already has
+    pub color_patch: Option<MapColorPatch<'a>>,
+}
+
+impl<'a> CMapData<'a> {
+    pub fn new(
+        map_id: VarInt,
+        scale: i8,
+        locked: bool,
+        decorations: Option<&'a [MapDecoration]>,
+        color_patch: Option<MapColorPatch<'a>>,
+    ) -> Self {
+        Self {
+            map_id,
+            scale,
+            locked,
+            decorations,
+            color_patch,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MapDecoration {
+    /// Id in the `minecraft:map_decoration_type` registry
+    pub decoration_type: VarInt,
+    pub x: i8,
+    pub z: i8,
+    /// Rotation in 1/16 of a full turn
+    pub rotation: i8,
+    pub display_name: Option<TextComponent>,
+}
+
+/// A rectangle of map colors, starting at `start_x`/`start_z`.
+pub struct MapColorPatch<'a> {
+    pub width: u8,
+    pub height: u8,
+    pub start_x: u8,
+    pub start_z: u8,
+    /// Row-major colors of the rectangle
+    pub colors: &'a [u8],
+}
+
+impl ClientPacket for CMapData<'_> {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_var_int(&self.map_id)?;
+        write.write_i8(self.scale)?;
+        write.write_bool(self.locked)?;
+        write.write_option(&self.decorations, |write, decorations| {
+            write.write_list(decorations, |write, decoration| {
+                write.write_var_int(&decoration.decoration_type)?;
+                write.write_i8(decoration.x)?;
+                write.write_i8(decoration.z)?;
+                write.write_i8(decoration.rotation)?;
+                write.write_option(&decoration.display_name, |write, name| {
+                    write.write_slice(&name.encode())
+                })
+            })
+        })?;
+        // A width of 0 means there is no patch
+        match &self.color_patch {
+            Some(patch) => {
+                write.write_u8(patch.width)?;
+                write.write_u8(patch.height)?;
+                write.write_u8(patch.start_x)?;
+                write.write_u8(patch.start_z)?;
+                write.write_var_int(&VarInt(patch.colors.len() as i32))?;
+                write.write_slice(patch.colors)
+            }
+            None => write.write_u8(0),
+        }
+    }
+}
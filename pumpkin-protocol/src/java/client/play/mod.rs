@@ -6,6 +6,7 @@ mod block_event;
 mod block_update;
 mod boss_event;
 mod bossevent_action;
+mod bundle_delimiter;
 mod center_chunk;
 mod change_difficulty;
 mod chunk_batch_end;
@@ -17,6 +18,7 @@ mod combat_death;
 mod command_suggestions;
 mod commands;
 mod cookie_request;
+mod cooldown;
 mod damage_event;
 mod disconnect;
 mod disguised_chat_message;
@@ -35,6 +37,7 @@ mod initialize_world_border;
 mod keep_alive;
 mod level_event;
 mod login;
+mod map_data;
 mod multi_block_update;
 mod open_screen;
 mod open_sign_editor;
@@ -57,10 +60,12 @@ mod set_border_lerp_size;
 mod set_border_size;
 mod set_border_warning_delay;
 mod set_border_warning_distance;
+mod set_chunk_cache_radius;
 mod set_container_content;
 mod set_container_property;
 mod set_container_slot;
 mod set_cursor_slot;
+mod set_entity_link;
 mod set_equipment;
 mod set_experience;
 mod set_health;
@@ -68,6 +73,7 @@ mod set_held_item;
 mod set_player_inventory;
 mod set_time;
 mod set_title;
+mod set_title_animation;
 mod sound_effect;
 mod spawn_entity;
 mod stop_sound;
@@ -80,12 +86,15 @@ mod ticking_state;
 mod ticking_step;
 mod transfer;
 mod unload_chunk;
+mod update_attributes;
 mod update_entity_pos;
 mod update_entity_pos_rot;
 mod update_entity_rot;
+mod update_light;
 mod update_mob_effect;
 mod update_objectives;
 mod update_score;
+mod update_teams;
 mod worldevent;
 
 pub use acknowledge_block::*;
@@ -96,6 +105,7 @@ pub use block_event::*;
 pub use block_update::*;
 pub use boss_event::*;
 pub use bossevent_action::*;
+pub use bundle_delimiter::*;
 pub use center_chunk::*;
 pub use change_difficulty::*;
 pub use chunk_batch_end::*;
@@ -107,6 +117,7 @@ pub use combat_death::*;
 pub use command_suggestions::*;
 pub use commands::*;
 pub use cookie_request::*;
+pub use cooldown::*;
 pub use damage_event::*;
 pub use disconnect::*;
 pub use disguised_chat_message::*;
@@ -125,6 +136,7 @@ pub use initialize_world_border::*;
 pub use keep_alive::*;
 pub use level_event::*;
 pub use login::*;
+pub use map_data::*;
 pub use multi_block_update::*;
 pub use open_screen::*;
 pub use open_sign_editor::*;
@@ -147,10 +159,12 @@ pub use set_border_lerp_size::*;
 pub use set_border_size::*;
 pub use set_border_warning_delay::*;
 pub use set_border_warning_distance::*;
+pub use set_chunk_cache_radius::*;
 pub use set_container_content::*;
 pub use set_container_property::*;
 pub use set_container_slot::*;
 pub use set_cursor_slot::*;
+pub use set_entity_link::*;
 pub use set_equipment::*;
 pub use set_experience::*;
 pub use set_health::*;
@@ -158,6 +172,7 @@ pub use set_held_item::*;
 pub use set_player_inventory::*;
 pub use set_time::*;
 pub use set_title::*;
+pub use set_title_animation::*;
 pub use sound_effect::*;
 pub use spawn_entity::*;
 pub use stop_sound::*;
@@ -170,10 +185,13 @@ pub use ticking_state::*;
 pub use ticking_step::*;
 pub use transfer::*;
 pub use unload_chunk::*;
+pub use update_attributes::*;
 pub use update_entity_pos::*;
 pub use update_entity_pos_rot::*;
 pub use update_entity_rot::*;
+pub use update_light::*;
 pub use update_mob_effect::*;
 pub use update_objectives::*;
 pub use update_score::*;
+pub use update_teams::*;
 pub use worldevent::*;
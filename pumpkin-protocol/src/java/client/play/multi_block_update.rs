@@ -16,8 +16,13 @@ pub struct CMultiBlockUpdate {
 }
 
 impl CMultiBlockUpdate {
-    pub fn new(positions_to_state_ids: Vec<(BlockPos, u16)>) -> Self {
-        let chunk_section = chunk_section_from_pos(&positions_to_state_ids[0].0);
+    /// All positions must be inside `chunk_section`, the client places them relative to it.
+    pub fn new(chunk_section: Vector3<i32>, positions_to_state_ids: Vec<(BlockPos, u16)>) -> Self {
+        debug_assert!(
+            positions_to_state_ids
+                .iter()
+                .all(|(position, _)| chunk_section_from_pos(position) == chunk_section)
+        );
         Self {
             chunk_section,
             positions_to_state_ids: positions_to_state_ids
@@ -54,3 +59,98 @@ impl Serialize for CMultiBlockUpdate {
         tuple.end()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{
+        position::{BlockPos, chunk_section_from_pos},
+        vector3::Vector3,
+    };
+    use serde::Serialize;
+
+    use crate::ser::{NetworkReadExt, serializer::Serializer};
+
+    use super::CMultiBlockUpdate;
+
+    /// Serializes the update and places its blocks back into world coordinates like the client does.
+    fn decode(packet: &CMultiBlockUpdate) -> Vec<(BlockPos, u16)> {
+        let mut buf = Vec::new();
+        packet.serialize(&mut Serializer::new(&mut buf)).unwrap();
+        let mut read = buf.as_slice();
+
+        let section = read.get_i64_be().unwrap();
+        let section_x = (section >> 42) as i32;
+        let section_y = (section << 44 >> 44) as i32;
+        let section_z = (section << 22 >> 42) as i32;
+
+        let count = read.get_var_int().unwrap().0;
+        let blocks = (0..count)
+            .map(|_| {
+                let long = read.get_var_long().unwrap().0;
+                let x = (long >> 8 & 0xF) as i32;
+                let z = (long >> 4 & 0xF) as i32;
+                let y = (long & 0xF) as i32;
+                let position = BlockPos(Vector3::new(
+                    (section_x << 4) + x,
+                    (section_y << 4) + y,
+                    (section_z << 4) + z,
+                ));
+                (position, (long >> 12) as u16)
+            })
+            .collect();
+        assert!(read.is_empty());
+        blocks
+    }
+
+    #[test]
+    fn test_section_borders() {
+        let sections = [
+            // The corners of the sections on both sides of 0
+            vec![
+                BlockPos(Vector3::new(-1, -1, -1)),
+                BlockPos(Vector3::new(-16, -16, -16)),
+                BlockPos(Vector3::new(-1, -16, -16)),
+                BlockPos(Vector3::new(-16, -1, -1)),
+            ],
+            vec![
+                BlockPos(Vector3::new(0, 0, 0)),
+                BlockPos(Vector3::new(15, 15, 15)),
+                BlockPos(Vector3::new(15, 0, 0)),
+                BlockPos(Vector3::new(0, 15, 15)),
+            ],
+            // Just past the border of the negative section
+            vec![
+                BlockPos(Vector3::new(-17, -17, -17)),
+                BlockPos(Vector3::new(-32, -32, -32)),
+            ],
+            // Far from the origin, mixing signs
+            vec![
+                BlockPos(Vector3::new(-29_999_985, 319, 29_999_999)),
+                BlockPos(Vector3::new(-29_999_999, 304, 29_999_984)),
+            ],
+        ];
+
+        for (index, positions) in sections.into_iter().enumerate() {
+            let chunk_section = chunk_section_from_pos(&positions[0]);
+            let blocks: Vec<_> = positions
+                .into_iter()
+                .zip(1u16..)
+                .map(|(position, state_id)| (position, state_id + index as u16 * 100))
+                .collect();
+            let packet = CMultiBlockUpdate::new(chunk_section, blocks.clone());
+            assert_eq!(decode(&packet), blocks);
+        }
+    }
+
+    #[test]
+    fn test_section_of_negative_coordinates() {
+        assert_eq!(
+            chunk_section_from_pos(&BlockPos(Vector3::new(-1, -1, -1))),
+            Vector3::new(-1, -1, -1)
+        );
+        assert_eq!(
+            chunk_section_from_pos(&BlockPos(Vector3::new(-16, -17, 16))),
+            Vector3::new(-1, -2, 1)
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_CHUNK_CACHE_RADIUS;
+use pumpkin_macros::packet;
+
+use crate::VarInt;
+
+/// Tells the client how many chunks around its center it should keep loaded.
+#[derive(serde::Serialize)]
+#[packet(PLAY_SET_CHUNK_CACHE_RADIUS)]
+pub struct CSetChunkCacheRadius {
+    pub view_distance: VarInt,
+}
@@ -0,0 +1,21 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_ENTITY_LINK;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Attaches a leash from one entity to another, or removes it.
+#[derive(Serialize)]
+#[packet(PLAY_SET_ENTITY_LINK)]
+pub struct CSetEntityLink {
+    attached_entity_id: i32,
+    holding_entity_id: i32,
+}
+
+impl CSetEntityLink {
+    /// `None` as the holder detaches the leash.
+    pub fn new(attached_entity_id: i32, holding_entity_id: Option<i32>) -> Self {
+        Self {
+            attached_entity_id,
+            holding_entity_id: holding_entity_id.unwrap_or(-1),
+        }
+    }
+}
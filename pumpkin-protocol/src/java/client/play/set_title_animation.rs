@@ -0,0 +1,23 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_TITLES_ANIMATION;
+
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Sets how long the next titles fade in, stay and fade out, in ticks.
+#[derive(Serialize)]
+#[packet(PLAY_SET_TITLES_ANIMATION)]
+pub struct CSetTitleAnimationTimes {
+    fade_in: i32,
+    stay: i32,
+    fade_out: i32,
+}
+
+impl CSetTitleAnimationTimes {
+    pub fn new(fade_in: i32, stay: i32, fade_out: i32) -> Self {
+        Self {
+            fade_in,
+            stay,
+            fade_out,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use pumpkin_util::math::vector3::Vector3;
 use serde::Serialize;
 
 use crate::VarInt;
+use crate::codec::angle::Angle;
 
 #[derive(Serialize)]
 #[packet(PLAY_ADD_ENTITY)]
@@ -13,9 +14,9 @@ pub struct CSpawnEntity {
     entity_uuid: uuid::Uuid,
     r#type: VarInt,
     position: Vector3<f64>,
-    pitch: u8,    // angle
-    yaw: u8,      // angle
-    head_yaw: u8, // angle
+    pitch: Angle,
+    yaw: Angle,
+    head_yaw: Angle,
     data: VarInt,
     velocity: Vector3<i16>,
 }
@@ -38,9 +39,9 @@ impl CSpawnEntity {
             entity_uuid,
             r#type,
             position,
-            pitch: (pitch * 256.0 / 360.0).floor() as u8,
-            yaw: (yaw * 256.0 / 360.0).floor() as u8,
-            head_yaw: (head_yaw * 256.0 / 360.0).floor() as u8,
+            pitch: Angle::from_degrees(pitch),
+            yaw: Angle::from_degrees(yaw),
+            head_yaw: Angle::from_degrees(head_yaw),
             data,
             velocity: Vector3::new(
                 (velocity.x.clamp(-3.9, 3.9) * 8000.0) as i16,
@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_ATTRIBUTES;
+use pumpkin_macros::packet;
+
+use crate::{ClientPacket, VarInt, WritingError, ser::NetworkWriteExt};
+
+#[packet(PLAY_UPDATE_ATTRIBUTES)]
+pub struct CUpdateAttributes {
+    entity_id: VarInt,
+    properties: Vec<AttributeProperty>,
+}
+
+impl CUpdateAttributes {
+    pub fn new(entity_id: VarInt, properties: Vec<AttributeProperty>) -> Self {
+        Self {
+            entity_id,
+            properties,
+        }
+    }
+}
+
+/// The base value of an attribute together with its modifiers, the client computes the final value itself.
+pub struct AttributeProperty {
+    /// The id of the attribute in the attribute registry
+    pub id: VarInt,
+    pub base: f64,
+    pub modifiers: Vec<AttributeModifier>,
+}
+
+pub struct AttributeModifier {
+    pub id: String,
+    pub amount: f64,
+    pub operation: u8,
+}
+
+impl ClientPacket for CUpdateAttributes {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_var_int(&self.entity_id)?;
+        write.write_list(&self.properties, |p, property| {
+            p.write_var_int(&property.id)?;
+            p.write_f64_be(property.base)?;
+            p.write_list(&property.modifiers, |p, modifier| {
+                p.write_string(&modifier.id)?;
+                p.write_f64_be(modifier.amount)?;
+                p.write_u8(modifier.operation)
+            })
+        })
+    }
+}
@@ -4,14 +4,15 @@ use pumpkin_util::math::vector3::Vector3;
 use serde::Serialize;
 
 use crate::VarInt;
+use crate::codec::angle::Angle;
 
 #[derive(Serialize)]
 #[packet(PLAY_MOVE_ENTITY_POS_ROT)]
 pub struct CUpdateEntityPosRot {
     entity_id: VarInt,
     delta: Vector3<i16>,
-    yaw: u8,
-    pitch: u8,
+    yaw: Angle,
+    pitch: Angle,
     on_ground: bool,
 }
 
@@ -19,8 +20,8 @@ impl CUpdateEntityPosRot {
     pub fn new(
         entity_id: VarInt,
         delta: Vector3<i16>,
-        yaw: u8,
-        pitch: u8,
+        yaw: Angle,
+        pitch: Angle,
         on_ground: bool,
     ) -> Self {
         Self {
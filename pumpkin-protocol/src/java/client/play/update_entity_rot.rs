@@ -3,18 +3,19 @@ use pumpkin_macros::packet;
 use serde::Serialize;
 
 use crate::VarInt;
+use crate::codec::angle::Angle;
 
 #[derive(Serialize)]
 #[packet(PLAY_MOVE_ENTITY_ROT)]
 pub struct CUpdateEntityRot {
     entity_id: VarInt,
-    yaw: u8,
-    pitch: u8,
+    yaw: Angle,
+    pitch: Angle,
     on_ground: bool,
 }
 
 impl CUpdateEntityRot {
-    pub fn new(entity_id: VarInt, yaw: u8, pitch: u8, on_ground: bool) -> Self {
+    pub fn new(entity_id: VarInt, yaw: Angle, pitch: Angle, on_ground: bool) -> Self {
         Self {
             entity_id,
             yaw,
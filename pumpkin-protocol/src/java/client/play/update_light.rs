@@ -0,0 +1,66 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_LIGHT_UPDATE;
+use pumpkin_macros::packet;
+use pumpkin_world::chunk::format::LightContainer;
+
+use crate::{ClientPacket, VarInt, WritingError, codec::bit_set::BitSet, ser::NetworkWriteExt};
+
+/// Updates the block light of some sections of a chunk.
+#[packet(PLAY_LIGHT_UPDATE)]
+pub struct CUpdateLight {
+    chunk_x: VarInt,
+    chunk_z: VarInt,
+    /// The changed block light sections by their index, starting at the section below the world
+    block_light: Vec<(usize, LightContainer)>,
+}
+
+impl CUpdateLight {
+    pub fn new(
+        chunk_x: VarInt,
+        chunk_z: VarInt,
+        block_light: Vec<(usize, LightContainer)>,
+    ) -> Self {
+        Self {
+            chunk_x,
+            chunk_z,
+            block_light,
+        }
+    }
+}
+
+impl ClientPacket for CUpdateLight {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_var_int(&self.chunk_x)?;
+        write.write_var_int(&self.chunk_z)?;
+
+        let mut block_light_mask = 0;
+        let mut block_light_empty_mask = 0;
+        for (index, light) in &self.block_light {
+            if light.is_empty() {
+                block_light_empty_mask |= 1 << index;
+            } else {
+                block_light_mask |= 1 << index;
+            }
+        }
+
+        // Sky light is left as is
+        write.write_bitset(&BitSet(Box::new([0])))?;
+        write.write_bitset(&BitSet(Box::new([block_light_mask])))?;
+        write.write_bitset(&BitSet(Box::new([0])))?;
+        write.write_bitset(&BitSet(Box::new([block_light_empty_mask])))?;
+
+        write.write_var_int(&VarInt(0))?;
+        let light_data_size: VarInt = LightContainer::ARRAY_SIZE.try_into().unwrap();
+        write.write_var_int(&VarInt(block_light_mask.count_ones() as i32))?;
+        for (_, light) in &self.block_light {
+            if let LightContainer::Full(data) = light {
+                write.write_var_int(&light_data_size)?;
+                write.write_slice(data)?;
+            }
+        }
+        Ok(())
+    }
+}
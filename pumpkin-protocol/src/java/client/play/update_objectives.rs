@@ -63,12 +63,14 @@ impl ClientPacket for CUpdateObjectives {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
     Add,
     Remove,
     Update,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum RenderType {
     Integer,
     Hearts,
@@ -0,0 +1,110 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_SET_PLAYER_TEAM;
+use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
+
+use crate::{ClientPacket, VarInt, WritingError, ser::NetworkWriteExt};
+
+#[packet(PLAY_SET_PLAYER_TEAM)]
+pub struct CUpdateTeams<'a> {
+    team_name: &'a str,
+    method: TeamMethod<'a>,
+}
+
+impl<'a> CUpdateTeams<'a> {
+    pub fn new(team_name: &'a str, method: TeamMethod<'a>) -> Self {
+        Self { team_name, method }
+    }
+}
+
+pub enum TeamMethod<'a> {
+    Add(TeamParameters<'a>, &'a [String]),
+    Remove,
+    Update(TeamParameters<'a>),
+    AddEntities(&'a [String]),
+    RemoveEntities(&'a [String]),
+}
+
+pub struct TeamParameters<'a> {
+    pub display_name: &'a TextComponent,
+    pub allow_friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: NameTagVisibility,
+    pub collision_rule: CollisionRule,
+    /// The id of the named team color, or 21 to reset it
+    pub color: i32,
+    pub prefix: &'a TextComponent,
+    pub suffix: &'a TextComponent,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NameTagVisibility {
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CollisionRule {
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+impl TeamMethod<'_> {
+    const fn id(&self) -> u8 {
+        match self {
+            Self::Add(..) => 0,
+            Self::Remove => 1,
+            Self::Update(_) => 2,
+            Self::AddEntities(_) => 3,
+            Self::RemoveEntities(_) => 4,
+        }
+    }
+}
+
+impl TeamParameters<'_> {
+    fn write(&self, write: &mut impl Write) -> Result<(), WritingError> {
+        write.write_slice(&self.display_name.encode())?;
+        let mut flags = 0;
+        if self.allow_friendly_fire {
+            flags |= 0x01;
+        }
+        if self.see_friendly_invisibles {
+            flags |= 0x02;
+        }
+        write.write_u8(flags)?;
+        write.write_var_int(&VarInt(self.name_tag_visibility as i32))?;
+        write.write_var_int(&VarInt(self.collision_rule as i32))?;
+        write.write_var_int(&VarInt(self.color))?;
+        write.write_slice(&self.prefix.encode())?;
+        write.write_slice(&self.suffix.encode())
+    }
+}
+
+fn write_entities(write: &mut impl Write, entities: &[String]) -> Result<(), WritingError> {
+    write.write_list(entities, |p, v| p.write_string(v))
+}
+
+impl ClientPacket for CUpdateTeams<'_> {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_string(self.team_name)?;
+        write.write_u8(self.method.id())?;
+        match &self.method {
+            TeamMethod::Add(parameters, entities) => {
+                parameters.write(&mut write)?;
+                write_entities(&mut write, entities)
+            }
+            TeamMethod::Remove => Ok(()),
+            TeamMethod::Update(parameters) => parameters.write(&mut write),
+            TeamMethod::AddEntities(entities) | TeamMethod::RemoveEntities(entities) => {
+                write_entities(&mut write, entities)
+            }
+        }
+    }
+}
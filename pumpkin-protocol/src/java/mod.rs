@@ -2,3 +2,4 @@ pub mod client;
 pub mod packet_decoder;
 pub mod packet_encoder;
 pub mod server;
+pub mod statistics;
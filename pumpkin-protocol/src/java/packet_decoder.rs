@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use aes::cipher::KeyIvInit;
 use async_compression::tokio::bufread::ZlibDecoder;
 use tokio::io::{AsyncRead, AsyncReadExt, BufReader};
 
 use crate::{
     Aes128Cfb8Dec, CompressionThreshold, MAX_PACKET_DATA_SIZE, MAX_PACKET_SIZE, PacketDecodeError,
-    RawPacket, ReadingError, StreamDecryptor, VarInt,
+    RawPacket, ReadingError, StreamDecryptor, VarInt, java::statistics::PacketStatistics,
 };
 
 // decrypt -> decompress -> raw
@@ -73,6 +75,7 @@ impl<R: AsyncRead + Unpin> AsyncRead for DecryptionReader<R> {
 pub struct TCPNetworkDecoder<R: AsyncRead + Unpin> {
     reader: DecryptionReader<R>,
     compression: Option<CompressionThreshold>,
+    statistics: Arc<PacketStatistics>,
 }
 
 impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
@@ -80,9 +83,17 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
         Self {
             reader: DecryptionReader::None(reader),
             compression: None,
+            statistics: Arc::default(),
         }
     }
 
+    /// Counts the read packets into `statistics` instead of a counter of its own.
+    #[must_use]
+    pub fn with_statistics(mut self, statistics: Arc<PacketStatistics>) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
     pub fn set_compression(&mut self, threshold: CompressionThreshold) {
         self.compression = Some(threshold);
     }
@@ -104,6 +115,7 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
                 err => PacketDecodeError::MalformedLength(err.to_string()),
             })?;
 
+        let length_size = packet_len.written_size();
         let packet_len = packet_len.0 as u64;
 
         if !(0..=MAX_PACKET_SIZE).contains(&packet_len) {
@@ -149,6 +161,8 @@ impl<R: AsyncRead + Unpin> TCPNetworkDecoder<R> {
             .await
             .map_err(|err| PacketDecodeError::FailedDecompression(err.to_string()))?;
 
+        self.statistics
+            .record_received(length_size + packet_len as usize);
         Ok(RawPacket {
             id: packet_id,
             payload: payload.into(),
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use aes::cipher::KeyIvInit;
 use async_compression::{Level, tokio::write::ZlibEncoder};
 use bytes::Bytes;
@@ -6,7 +8,7 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     Aes128Cfb8Enc, CompressionLevel, CompressionThreshold, MAX_PACKET_DATA_SIZE, MAX_PACKET_SIZE,
-    PacketEncodeError, StreamEncryptor, VarInt,
+    PacketEncodeError, StreamEncryptor, VarInt, java::statistics::PacketStatistics,
 };
 
 // raw -> compress -> encrypt
@@ -83,6 +85,7 @@ pub struct TCPNetworkEncoder<W: AsyncWrite + Unpin> {
     writer: EncryptionWriter<W>,
     // compression and compression threshold
     compression: Option<(CompressionThreshold, CompressionLevel)>,
+    statistics: Arc<PacketStatistics>,
 }
 
 impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
@@ -90,11 +93,29 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
         Self {
             writer: EncryptionWriter::None(writer),
             compression: None,
+            statistics: Arc::default(),
         }
     }
 
-    pub fn set_compression(&mut self, compression_info: (CompressionThreshold, CompressionLevel)) {
+    /// Counts the written packets into `statistics` instead of a counter of its own.
+    #[must_use]
+    pub fn with_statistics(mut self, statistics: Arc<PacketStatistics>) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Enables zlib compression, the only algorithm vanilla clients understand.
+    ///
+    /// Fails if the level is outside of zlib's `0..=9` range.
+    pub fn set_compression(
+        &mut self,
+        compression_info: (CompressionThreshold, CompressionLevel),
+    ) -> Result<(), CompressionLevelError> {
+        if compression_info.1 > 9 {
+            return Err(CompressionLevelError);
+        }
         self.compression = Some(compression_info);
+        Ok(())
     }
 
     /// NOTE: Encryption can only be set; a minecraft stream cannot go back to being unencrypted
@@ -202,6 +223,7 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
                     .write_all(&compressed_buf)
                     .await
                     .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
+                self.statistics.record_sent(complete_serialization_length);
             } else {
                 // Pushed before data:
                 // Length of (Data Length) + length of compressed (Packet ID + Data)
@@ -234,6 +256,7 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
                     .write_all(&packet_data)
                     .await
                     .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
+                self.statistics.record_sent(complete_serialization_length);
             }
         } else {
             // Pushed before data:
@@ -255,6 +278,7 @@ impl<W: AsyncWrite + Unpin> TCPNetworkEncoder<W> {
                 .write_all(&packet_data)
                 .await
                 .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
+            self.statistics.record_sent(complete_serialization_length);
         }
 
         self.writer
@@ -329,7 +353,7 @@ mod tests {
         let mut buf = Vec::new();
         let mut encoder = TCPNetworkEncoder::new(&mut buf);
         if let Some(compression_info) = compression_info {
-            encoder.set_compression(compression_info);
+            encoder.set_compression(compression_info).unwrap();
         }
 
         if let Some(key) = key {
@@ -642,4 +666,52 @@ mod tests {
 
         assert_eq!(buffer, expected_payload);
     }
+
+    #[test]
+    fn test_set_compression_rejects_invalid_level() {
+        let mut buf = Vec::new();
+        let mut encoder = TCPNetworkEncoder::new(&mut buf);
+
+        assert!(encoder.set_compression((256, 10)).is_err());
+        assert!(encoder.compression.is_none());
+        assert!(encoder.set_compression((256, 9)).is_ok());
+    }
+
+    /// Test that written and read packets are counted with their full wire size
+    #[tokio::test]
+    async fn test_packet_statistics() {
+        use crate::java::packet_decoder::TCPNetworkDecoder;
+        use std::sync::atomic::Ordering;
+
+        let statistics = Arc::new(PacketStatistics::default());
+        let mut buf = Vec::new();
+        let mut encoder = TCPNetworkEncoder::new(&mut buf).with_statistics(statistics.clone());
+        for size in [4, 64] {
+            let mut packet_buf = Vec::new();
+            packet_buf.write_var_int(&VarInt(0)).unwrap();
+            packet_buf.extend(vec![0xAB; size]);
+            encoder.write_packet(packet_buf.into()).await.unwrap();
+        }
+
+        assert_eq!(statistics.packets_sent.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            statistics.bytes_sent.load(Ordering::Relaxed),
+            buf.len() as u64
+        );
+
+        let mut decoder =
+            TCPNetworkDecoder::new(buf.as_slice()).with_statistics(statistics.clone());
+        decoder.get_raw_packet().await.unwrap();
+        decoder.get_raw_packet().await.unwrap();
+
+        assert_eq!(statistics.packets_received.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            statistics.bytes_received.load(Ordering::Relaxed),
+            buf.len() as u64
+        );
+
+        statistics.reset();
+        assert_eq!(statistics.bytes_sent.load(Ordering::Relaxed), 0);
+        assert_eq!(statistics.packets_received.load(Ordering::Relaxed), 0);
+    }
 }
@@ -0,0 +1,12 @@
+use pumpkin_data::packet::serverbound::PLAY_CHAT_ACK;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Sent by the client to acknowledge chat messages it received without sending one itself
+#[derive(serde::Deserialize, Serialize)]
+#[packet(PLAY_CHAT_ACK)]
+pub struct SChatAck {
+    pub offset: VarInt,
+}
@@ -1,4 +1,5 @@
 mod change_game_mode;
+mod chat_ack;
 mod chat_command;
 mod chat_message;
 mod chunk_batch;
@@ -33,6 +34,7 @@ mod use_item;
 mod use_item_on;
 
 pub use change_game_mode::*;
+pub use chat_ack::*;
 pub use chat_command::*;
 pub use chat_message::*;
 pub use chunk_batch::*;
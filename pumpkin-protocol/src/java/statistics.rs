@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Traffic counters of a single connection, counting packets as they are framed on the wire,
+/// after compression but before encryption (which doesn't change the size).
+#[derive(Debug, Default)]
+pub struct PacketStatistics {
+    pub bytes_sent: AtomicU64,
+    pub bytes_received: AtomicU64,
+    pub packets_sent: AtomicU64,
+    pub packets_received: AtomicU64,
+}
+
+impl PacketStatistics {
+    pub fn record_sent(&self, bytes: usize) {
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_received(&self, bytes: usize) {
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn reset(&self) {
+        self.bytes_sent.store(0, Ordering::Relaxed);
+        self.bytes_received.store(0, Ordering::Relaxed);
+        self.packets_sent.store(0, Ordering::Relaxed);
+        self.packets_received.store(0, Ordering::Relaxed);
+    }
+
+    /// Adds the counters of `other` onto these, used to build server wide totals.
+    pub fn add(&self, other: &Self) {
+        self.bytes_sent
+            .fetch_add(other.bytes_sent.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.bytes_received.fetch_add(
+            other.bytes_received.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        self.packets_sent.fetch_add(
+            other.packets_sent.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        self.packets_received.fetch_add(
+            other.packets_received.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+    }
+}
@@ -3,7 +3,10 @@ use std::io::{Read, Write};
 
 use crate::{
     FixedBitSet,
-    codec::{bit_set::BitSet, u24::U24, var_int::VarInt, var_long::VarLong, var_uint::VarUInt},
+    codec::{
+        angle::Angle, bit_set::BitSet, u24::U24, var_int::VarInt, var_long::VarLong,
+        var_uint::VarUInt,
+    },
 };
 
 pub mod deserializer;
@@ -65,6 +68,9 @@ pub trait NetworkReadExt {
 
     fn get_bool(&mut self) -> Result<bool, ReadingError>;
     fn get_u24(&mut self) -> Result<U24, ReadingError>;
+    fn get_angle(&mut self) -> Result<Angle, ReadingError> {
+        Ok(Angle(self.get_u8()?))
+    }
     fn get_var_int(&mut self) -> Result<VarInt, ReadingError>;
     fn get_var_uint(&mut self) -> Result<VarUInt, ReadingError>;
     fn get_var_long(&mut self) -> Result<VarLong, ReadingError>;
@@ -245,6 +251,9 @@ pub trait NetworkWriteExt {
     fn write_i16_be(&mut self, data: i16) -> Result<(), WritingError>;
     fn write_u16_be(&mut self, data: u16) -> Result<(), WritingError>;
     fn write_u24_be(&mut self, data: U24) -> Result<(), WritingError>;
+    fn write_angle(&mut self, data: Angle) -> Result<(), WritingError> {
+        self.write_u8(data.0)
+    }
     fn write_i32_be(&mut self, data: i32) -> Result<(), WritingError>;
     fn write_u32_be(&mut self, data: u32) -> Result<(), WritingError>;
     fn write_i64_be(&mut self, data: i64) -> Result<(), WritingError>;
@@ -23,6 +23,12 @@ pub struct Dimension {
     ultrawarm: bool,
 }
 
+impl Dimension {
+    pub fn logical_height(&self) -> i32 {
+        self.logical_height
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum DimensionEffects {
     #[serde(rename = "minecraft:overworld")]
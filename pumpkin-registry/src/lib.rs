@@ -107,6 +107,12 @@ impl VanillaDimensionType {
             _ => None,
         }
     }
+
+    /// The height above the bottom of the world that portals can be placed in, e.g. below the
+    /// bedrock roof of the nether.
+    pub fn logical_height(&self) -> i32 {
+        SYNCED_REGISTRIES.dimension_type[self.resource_location().path.as_str()].logical_height()
+    }
 }
 
 impl Registry {
@@ -254,6 +260,18 @@ impl Registry {
             registry_entries,
         };
 
+        // Enchantments aren't modeled yet, so the client uses its own copy of the vanilla ones.
+        // Both are sorted by name, so the ids match the ones of `pumpkin_data::Enchantment`.
+        let registry_entries = SYNCED_REGISTRIES
+            .enchantment
+            .keys()
+            .map(|name| RegistryEntry::from_known_pack(name))
+            .collect();
+        let enchantment = Registry {
+            registry_id: ResourceLocation::vanilla("enchantment"),
+            registry_entries,
+        };
+
         let registry_entries = SYNCED_REGISTRIES
             .jukebox_song
             .iter()
@@ -280,7 +298,7 @@ impl Registry {
             dimension_type,
             damage_type,
             banner_pattern,
-            // enchantment,
+            enchantment,
             jukebox_song,
             // instrument,
         ]
@@ -16,6 +16,8 @@ pub struct LootPool {
     pub entries: &'static [LootPoolEntry],
     pub rolls: LootNumberProviderTypes,
     pub bonus_rolls: f32,
+    pub conditions: Option<&'static [LootCondition]>,
+    pub functions: Option<&'static [LootFunction]>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -42,9 +44,15 @@ pub enum LootPoolEntryTypes {
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum LootCondition {
-    Inverted,
-    AnyOf,
-    AllOf,
+    Inverted {
+        term: &'static LootCondition,
+    },
+    AnyOf {
+        terms: &'static [LootCondition],
+    },
+    AllOf {
+        terms: &'static [LootCondition],
+    },
     RandomChance,
     RandomChanceWithEnchantedBonus,
     EntityProperties,
@@ -54,8 +62,16 @@ pub enum LootCondition {
         block: &'static str,
         properties: &'static [(&'static str, &'static str)],
     },
-    MatchTool,
-    TableBonus,
+    MatchTool {
+        /// The items or `#`-prefixed item tags the tool has to be one of, any tool if `None`
+        items: Option<&'static [&'static str]>,
+        enchantments: &'static [EnchantmentPredicate],
+    },
+    TableBonus {
+        enchantment: &'static str,
+        /// The chance for each enchantment level, the last one is used for higher levels
+        chances: &'static [f32],
+    },
     SurvivesExplosion,
     DamageSourceProperties,
     LocationCheck,
@@ -66,6 +82,14 @@ pub enum LootCondition {
     EnchantmentActiveCheck,
 }
 
+/// Matches if the tool has the enchantment at a level within the bounds.
+#[derive(Clone, PartialEq, Debug)]
+pub struct EnchantmentPredicate {
+    pub enchantment: &'static str,
+    pub min_level: Option<i32>,
+    pub max_level: Option<i32>,
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct LootFunction {
     pub content: LootFunctionTypes,
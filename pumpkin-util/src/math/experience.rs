@@ -31,7 +31,7 @@ pub fn points_in_level(level: i32) -> i32 {
 /// Calculate the total number of points to reach a level.
 pub fn points_to_level(level: i32) -> i32 {
     match level {
-        0..=15 => (level * level + 6 * level) / 2,
+        0..=15 => level * level + 6 * level,
         16..=30 => ((2.5 * f64::from(level * level)) - (40.5 * f64::from(level)) + 360.0) as i32,
         _ => ((4.5 * f64::from(level * level)) - (162.5 * f64::from(level)) + 2220.0) as i32,
     }
@@ -39,11 +39,20 @@ pub fn points_to_level(level: i32) -> i32 {
 
 /// Calculate level and points from total points.
 pub fn total_to_level_and_points(total_points: i32) -> (i32, i32) {
-    let level = match total_points {
-        0..=352 => ((total_points as f64 + 9.0).sqrt() - 3.0) as i32,
-        353..=1507 => (81.0 + (total_points as f64 - 7839.0) / 40.0).sqrt() as i32,
-        _ => (325.0 + (total_points as f64 - 54215.0) / 72.0).sqrt() as i32,
+    let total = f64::from(total_points.max(0));
+    // Inverse of `points_to_level` for each of its ranges
+    let mut level = match total_points {
+        i32::MIN..=352 => ((total + 9.0).sqrt() - 3.0) as i32,
+        353..=1507 => ((40.5 + (10.0 * total - 1959.75).sqrt()) / 5.0) as i32,
+        _ => ((162.5 + (18.0 * total - 13553.75).sqrt()) / 9.0) as i32,
     };
+    // Correct floating point rounding errors at the level boundaries
+    while level > 0 && points_to_level(level) > total_points {
+        level -= 1;
+    }
+    while points_to_level(level + 1) <= total_points {
+        level += 1;
+    }
 
     let level_start = points_to_level(level);
     let points_into_level = total_points - level_start;
@@ -58,3 +67,31 @@ pub fn progress_in_level(points: i32, level: i32) -> f32 {
     let progress = (points as f32) / (max_points as f32);
     progress.clamp(0.0, 1.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_to_level_matches_vanilla() {
+        assert_eq!(points_to_level(0), 0);
+        assert_eq!(points_to_level(1), 7);
+        assert_eq!(points_to_level(16), 352);
+        assert_eq!(points_to_level(30), 1395);
+        assert_eq!(points_to_level(31), 1507);
+        assert_eq!(points_to_level(32), 1628);
+    }
+
+    #[test]
+    fn total_round_trip() {
+        for level in 0..100 {
+            let start = points_to_level(level);
+            assert_eq!(total_to_level_and_points(start), (level, 0));
+            let last = start + points_in_level(level) - 1;
+            assert_eq!(
+                total_to_level_and_points(last),
+                (level, points_in_level(level) - 1)
+            );
+        }
+    }
+}
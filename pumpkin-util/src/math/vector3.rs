@@ -448,8 +448,8 @@ pub const fn packed_chunk_pos(vec: &Vector3<i32>) -> i64 {
 
 #[inline]
 pub const fn packed_local(vec: &Vector3<i32>) -> i16 {
-    let x = vec.x as i16;
-    let y = vec.y as i16;
-    let z = vec.z as i16;
+    let x = (vec.x & 0xF) as i16;
+    let y = (vec.y & 0xF) as i16;
+    let z = (vec.z & 0xF) as i16;
     (x << 8) | (z << 4) | y
 }
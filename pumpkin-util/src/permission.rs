@@ -70,6 +70,13 @@ impl PermissionRegistry {
         Ok(())
     }
 
+    /// Unregister all permissions of a namespace (e.g. when a plugin gets unloaded)
+    pub fn unregister_namespace(&mut self, namespace: &str) {
+        let prefix = format!("{namespace}:");
+        self.permissions
+            .retain(|node, _| !node.starts_with(&prefix));
+    }
+
     /// Get a registered permission by node
     pub fn get_permission(&self, node: &str) -> Option<&Permission> {
         self.permissions.get(node)
@@ -79,6 +86,17 @@ impl PermissionRegistry {
     pub fn has_permission(&self, node: &str) -> bool {
         self.permissions.contains_key(node)
     }
+
+    /// Get the legacy op level a permission node maps to, derived from its default.
+    ///
+    /// `Allow` maps to level zero, `Deny` has no level that grants it.
+    pub fn legacy_level(&self, node: &str) -> Option<PermissionLvl> {
+        match self.permissions.get(node)?.default {
+            PermissionDefault::Allow => Some(PermissionLvl::Zero),
+            PermissionDefault::Deny => None,
+            PermissionDefault::Op(lvl) => Some(lvl),
+        }
+    }
 }
 
 /// Storage for player permissions
@@ -267,6 +285,7 @@ impl<'de> Deserialize<'de> for PermissionLvl {
         let value = u8::deserialize(deserializer)?;
         match value {
             0 => Ok(PermissionLvl::Zero),
+            1 => Ok(PermissionLvl::One),
             2 => Ok(PermissionLvl::Two),
             3 => Ok(PermissionLvl::Three),
             4 => Ok(PermissionLvl::Four),
@@ -78,9 +78,9 @@ impl Color {
 
 #[derive(Debug, Deserialize, Clone, Copy, Eq, Hash, PartialEq)]
 pub struct RGBColor {
-    red: u8,
-    green: u8,
-    blue: u8,
+    pub(crate) red: u8,
+    pub(crate) green: u8,
+    pub(crate) blue: u8,
 }
 
 impl RGBColor {
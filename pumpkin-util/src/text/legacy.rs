@@ -0,0 +1,376 @@
+use std::borrow::Cow;
+
+use super::{
+    TextComponent, TextComponentBase, TextContent,
+    color::{Color, NamedColor, RGBColor},
+    style::Style,
+};
+use crate::translation::get_translation_en_us;
+
+/// The character starting a legacy formatting code
+const SECTION_SIGN: char = '§';
+
+const NAMED_COLORS: [NamedColor; 16] = [
+    NamedColor::Black,
+    NamedColor::DarkBlue,
+    NamedColor::DarkGreen,
+    NamedColor::DarkAqua,
+    NamedColor::DarkRed,
+    NamedColor::DarkPurple,
+    NamedColor::Gold,
+    NamedColor::Gray,
+    NamedColor::DarkGray,
+    NamedColor::Blue,
+    NamedColor::Green,
+    NamedColor::Aqua,
+    NamedColor::Red,
+    NamedColor::LightPurple,
+    NamedColor::Yellow,
+    NamedColor::White,
+];
+
+/// The styling legacy codes can express
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct LegacyStyle {
+    color: Option<Color>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool,
+    obfuscated: bool,
+}
+
+impl LegacyStyle {
+    fn from_style(style: &Style, parent: Self) -> Self {
+        Self {
+            color: match style.color {
+                Some(Color::Reset) => None,
+                Some(color) => Some(color),
+                None => parent.color,
+            },
+            bold: style.bold.unwrap_or(parent.bold),
+            italic: style.italic.unwrap_or(parent.italic),
+            underlined: style.underlined.unwrap_or(parent.underlined),
+            strikethrough: style.strikethrough.unwrap_or(parent.strikethrough),
+            obfuscated: style.obfuscated.unwrap_or(parent.obfuscated),
+        }
+    }
+
+    fn to_style(self) -> Style {
+        let flag = |enabled: bool| enabled.then_some(true);
+        Style {
+            color: self.color,
+            bold: flag(self.bold),
+            italic: flag(self.italic),
+            underlined: flag(self.underlined),
+            strikethrough: flag(self.strikethrough),
+            obfuscated: flag(self.obfuscated),
+            ..Default::default()
+        }
+    }
+
+    /// The codes of the formats, in the order `k`, `l`, `m`, `n`, `o`
+    fn format_codes(self) -> impl Iterator<Item = char> {
+        [
+            (self.obfuscated, 'k'),
+            (self.bold, 'l'),
+            (self.strikethrough, 'm'),
+            (self.underlined, 'n'),
+            (self.italic, 'o'),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, code)| enabled.then_some(code))
+    }
+
+    /// Whether this style only adds formats to `other`, so we can switch to it without a reset
+    fn extends(self, other: Self) -> bool {
+        self.color == other.color
+            && (self.bold || !other.bold)
+            && (self.italic || !other.italic)
+            && (self.underlined || !other.underlined)
+            && (self.strikethrough || !other.strikethrough)
+            && (self.obfuscated || !other.obfuscated)
+    }
+}
+
+impl TextComponent {
+    /// Parses a string with legacy `§` formatting codes, like `§aGreen §lbold`.
+    ///
+    /// The text before the first code becomes the root, every color or format change after it
+    /// starts a new child. As in vanilla, a color code also resets the formats, and `§r` resets
+    /// everything. Codes we do not know are kept as text.
+    pub fn from_legacy_format(s: &str) -> Self {
+        let mut root = None;
+        let mut style = LegacyStyle::default();
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != SECTION_SIGN {
+                text.push(c);
+                continue;
+            }
+            let Some(&code) = chars.peek() else {
+                text.push(c);
+                break;
+            };
+            let mut new_style = style;
+            match code.to_ascii_lowercase() {
+                'x' => {
+                    // `§x§R§R§G§G§B§B`, as used by BungeeCord and Spigot
+                    let mut lookahead = chars.clone();
+                    lookahead.next();
+                    let Some(color) = parse_hex_color(&mut lookahead) else {
+                        text.push(c);
+                        continue;
+                    };
+                    chars = lookahead;
+                    new_style = LegacyStyle {
+                        color: Some(Color::Rgb(color)),
+                        ..Default::default()
+                    };
+                }
+                'r' => {
+                    chars.next();
+                    new_style = LegacyStyle::default();
+                }
+                'k' => {
+                    chars.next();
+                    new_style.obfuscated = true;
+                }
+                'l' => {
+                    chars.next();
+                    new_style.bold = true;
+                }
+                'm' => {
+                    chars.next();
+                    new_style.strikethrough = true;
+                }
+                'n' => {
+                    chars.next();
+                    new_style.underlined = true;
+                }
+                'o' => {
+                    chars.next();
+                    new_style.italic = true;
+                }
+                code => {
+                    let Some(index) = code.to_digit(16) else {
+                        text.push(c);
+                        continue;
+                    };
+                    chars.next();
+                    new_style = LegacyStyle {
+                        color: Some(Color::Named(NAMED_COLORS[index as usize])),
+                        ..Default::default()
+                    };
+                }
+            }
+            if new_style != style {
+                flush(&mut root, &mut text, style);
+                style = new_style;
+            }
+        }
+        flush(&mut root, &mut text, style);
+
+        root.unwrap_or_else(|| Self::text(""))
+    }
+
+    /// Writes the text with legacy `§` formatting codes, the reverse of [`Self::from_legacy_format`].
+    ///
+    /// Styling that has no code, like click events or fonts, is dropped.
+    pub fn to_legacy_format(&self) -> String {
+        let mut out = String::new();
+        let mut written = LegacyStyle::default();
+        write_legacy(&self.0, LegacyStyle::default(), &mut written, &mut out);
+        out
+    }
+}
+
+/// Adds the text collected so far, with the style it was written in
+fn flush(root: &mut Option<TextComponent>, text: &mut String, style: LegacyStyle) {
+    if text.is_empty() {
+        return;
+    }
+    let text = std::mem::take(text);
+    match root {
+        None if style == LegacyStyle::default() => *root = Some(TextComponent::text(text)),
+        None => *root = Some(TextComponent::text("").add_child(styled(text, style))),
+        Some(root) => root.0.extra.push(styled(text, style).0),
+    }
+}
+
+fn styled(text: String, style: LegacyStyle) -> TextComponent {
+    TextComponent(TextComponentBase {
+        content: TextContent::Text {
+            text: Cow::Owned(text),
+        },
+        style: style.to_style(),
+        extra: vec![],
+    })
+}
+
+/// Reads the six `§`-prefixed hex digits following `§x`
+fn parse_hex_color(chars: &mut impl Iterator<Item = char>) -> Option<RGBColor> {
+    let mut rgb = 0;
+    for _ in 0..6 {
+        if chars.next()? != SECTION_SIGN {
+            return None;
+        }
+        rgb = (rgb << 4) | chars.next()?.to_digit(16)?;
+    }
+    let [_, red, green, blue] = rgb.to_be_bytes();
+    Some(RGBColor::new(red, green, blue))
+}
+
+fn write_legacy(
+    component: &TextComponentBase,
+    parent: LegacyStyle,
+    written: &mut LegacyStyle,
+    out: &mut String,
+) {
+    let style = LegacyStyle::from_style(&component.style, parent);
+    let text = match &component.content {
+        TextContent::Text { text } => text.to_string(),
+        TextContent::Translate { translate, with } => {
+            get_translation_en_us(translate, with.clone()).unwrap_or(translate.to_string())
+        }
+        TextContent::EntityNames { selector, .. } => selector.to_string(),
+        TextContent::Keybind { keybind } => keybind.to_string(),
+    };
+    if !text.is_empty() {
+        if style != *written {
+            write_style_change(*written, style, out);
+            *written = style;
+        }
+        out.push_str(&text);
+    }
+    for child in &component.extra {
+        write_legacy(child, style, written, out);
+    }
+}
+
+fn write_style_change(from: LegacyStyle, to: LegacyStyle, out: &mut String) {
+    let new_formats = if to.extends(from) {
+        LegacyStyle {
+            color: to.color,
+            bold: to.bold && !from.bold,
+            italic: to.italic && !from.italic,
+            underlined: to.underlined && !from.underlined,
+            strikethrough: to.strikethrough && !from.strikethrough,
+            obfuscated: to.obfuscated && !from.obfuscated,
+        }
+    } else {
+        // A color code resets the formats too, so we only need `§r` to get rid of the color
+        match to.color {
+            Some(color) => write_color(color, out),
+            None => {
+                out.push(SECTION_SIGN);
+                out.push('r');
+            }
+        }
+        to
+    };
+    for code in new_formats.format_codes() {
+        out.push(SECTION_SIGN);
+        out.push(code);
+    }
+}
+
+fn write_color(color: Color, out: &mut String) {
+    match color {
+        Color::Named(color) => {
+            out.push(SECTION_SIGN);
+            out.push(char::from_digit(color as u32, 16).unwrap());
+        }
+        Color::Rgb(color) => {
+            out.push(SECTION_SIGN);
+            out.push('x');
+            for digit in format!("{:02x}{:02x}{:02x}", color.red, color.green, color.blue).chars() {
+                out.push(SECTION_SIGN);
+                out.push(digit);
+            }
+        }
+        Color::Reset => {
+            out.push(SECTION_SIGN);
+            out.push('r');
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::text::{
+        TextComponent,
+        color::{NamedColor, RGBColor},
+    };
+
+    #[test]
+    fn bare_text() {
+        let component = TextComponent::from_legacy_format("Hello world");
+        assert_eq!(component, TextComponent::text("Hello world"));
+        assert_eq!(component.to_legacy_format(), "Hello world");
+    }
+
+    #[test]
+    fn color_codes() {
+        let component = TextComponent::from_legacy_format("Hi §aGreen§cRed");
+        assert_eq!(
+            component,
+            TextComponent::text("Hi ")
+                .add_child(TextComponent::text("Green").color_named(NamedColor::Green))
+                .add_child(TextComponent::text("Red").color_named(NamedColor::Red))
+        );
+        assert_eq!(component.to_legacy_format(), "Hi §aGreen§cRed");
+    }
+
+    #[test]
+    fn style_codes() {
+        let component = TextComponent::from_legacy_format("§lBold§oBoth§rPlain");
+        assert_eq!(
+            component,
+            TextComponent::text("")
+                .add_child(TextComponent::text("Bold").bold())
+                .add_child(TextComponent::text("Both").bold().italic())
+                .add_child(TextComponent::text("Plain"))
+        );
+        assert_eq!(component.to_legacy_format(), "§lBold§oBoth§rPlain");
+    }
+
+    #[test]
+    fn mixed_codes() {
+        // A color resets the formats before it, but not the ones after it
+        let component = TextComponent::from_legacy_format("§l§6Gold§nunder §x§f§f§0§0§8§0pink");
+        assert_eq!(
+            component,
+            TextComponent::text("")
+                .add_child(TextComponent::text("Gold").color_named(NamedColor::Gold))
+                .add_child(
+                    TextComponent::text("under ")
+                        .color_named(NamedColor::Gold)
+                        .underlined()
+                )
+                .add_child(TextComponent::text("pink").color_rgb(RGBColor::new(255, 0, 128)))
+        );
+        assert_eq!(
+            component.to_legacy_format(),
+            "§6Gold§nunder §x§f§f§0§0§8§0pink"
+        );
+    }
+
+    #[test]
+    fn nested_components_to_legacy() {
+        let component = TextComponent::text("A")
+            .color_named(NamedColor::Red)
+            .bold()
+            .add_child(TextComponent::text("B").italic())
+            .add_child(TextComponent::text("C").color_named(NamedColor::Blue));
+        assert_eq!(component.to_legacy_format(), "§c§lA§oB§9§lC");
+    }
+
+    #[test]
+    fn unknown_codes_are_kept() {
+        let component = TextComponent::from_legacy_format("§zoops§");
+        assert_eq!(component, TextComponent::text("§zoops§"));
+    }
+}
@@ -13,6 +13,7 @@ use style::Style;
 pub mod click;
 pub mod color;
 pub mod hover;
+mod legacy;
 pub mod style;
 
 /// Represents a text component
@@ -231,6 +232,11 @@ impl TextComponent {
         buf.into_boxed_slice()
     }
 
+    /// Serializes the component as JSON, which the login phase uses instead of NBT.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).expect("Failed to serialize text component JSON")
+    }
+
     pub fn color(mut self, color: Color) -> Self {
         self.0.style.color = Some(color);
         self
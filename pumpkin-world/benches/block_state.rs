@@ -0,0 +1,31 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin_data::{
+    Block,
+    block_properties::{GLOBAL_PALETTE_CACHE, get_block_and_state_by_state_id},
+};
+
+fn bench_block_state_lookup(c: &mut Criterion) {
+    let state_count = GLOBAL_PALETTE_CACHE.len() as u16;
+
+    c.bench_function("block state lookup (search)", |b| {
+        b.iter(|| {
+            for id in 0..state_count {
+                let block = Block::from_state_id(id);
+                black_box(block.states.iter().find(|state| state.id == id));
+            }
+        });
+    });
+
+    c.bench_function("block state lookup (cache)", |b| {
+        b.iter(|| {
+            for id in 0..state_count {
+                black_box(get_block_and_state_by_state_id(id));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, bench_block_state_lookup);
+criterion_main!(benches);
@@ -3,7 +3,7 @@ use pumpkin_data::noise_router::OVERWORLD_BASE_NOISE_ROUTER;
 use pumpkin_world::{
     GENERATION_SETTINGS, GeneratorSetting, GlobalRandomConfig, ProtoNoiseRouters,
     bench_create_and_populate_biome, bench_create_and_populate_noise,
-    bench_create_and_populate_noise_with_surface,
+    bench_create_and_populate_noise_with_carvers, bench_create_and_populate_noise_with_surface,
 };
 
 fn bench_terrain_gen(c: &mut Criterion) {
@@ -31,6 +31,16 @@ fn bench_terrain_gen(c: &mut Criterion) {
             )
         });
     });
+
+    c.bench_function("overworld carvers", |b| {
+        b.iter(|| {
+            bench_create_and_populate_noise_with_carvers(
+                &base_router,
+                &random_config,
+                surface_config,
+            )
+        });
+    });
 }
 
 criterion_group!(benches, bench_terrain_gen);
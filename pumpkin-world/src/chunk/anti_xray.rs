@@ -0,0 +1,263 @@
+use std::collections::HashSet;
+
+use pumpkin_data::{Block, block_properties::get_state_by_state_id};
+
+use crate::BlockStateId;
+
+use super::{CHUNK_WIDTH, ChunkSections, palette::BlockPalette};
+
+/// The opacity of blocks which let no light through, glass and leaves have less
+const MAX_OPACITY: u8 = 15;
+
+/// The loaded chunks next to the one being obfuscated, used to tell if blocks at its border are
+/// exposed. Borders to chunks which aren't loaded are treated as exposed, so blocks there are never
+/// hidden by mistake.
+#[derive(Default, Clone, Copy)]
+pub struct ChunkNeighbors<'a> {
+    /// The chunk at -x
+    pub west: Option<&'a ChunkSections>,
+    /// The chunk at +x
+    pub east: Option<&'a ChunkSections>,
+    /// The chunk at -z
+    pub north: Option<&'a ChunkSections>,
+    /// The chunk at +z
+    pub south: Option<&'a ChunkSections>,
+}
+
+/// Hides blocks like ores from clients by sending a filler block in their place, unless they are
+/// exposed to a block which isn't a full, opaque cube, like air, glass or leaves. Only the sent data is changed, the chunk itself stays untouched.
+pub struct ChunkObfuscator {
+    hidden_states: HashSet<BlockStateId>,
+    filler: BlockStateId,
+    /// The filler below y = 0, so hidden blocks blend in with deepslate
+    deep_filler: BlockStateId,
+}
+
+impl ChunkObfuscator {
+    #[must_use]
+    pub fn new<'a>(
+        hidden_blocks: impl IntoIterator<Item = &'a Block>,
+        filler: &Block,
+        deep_filler: &Block,
+    ) -> Self {
+        let hidden_states = hidden_blocks
+            .into_iter()
+            .flat_map(|block| block.states.iter().map(|state| state.id))
+            .collect();
+        Self {
+            hidden_states,
+            filler: filler.default_state.id,
+            deep_filler: deep_filler.default_state.id,
+        }
+    }
+
+    #[must_use]
+    pub fn is_hidden(&self, state_id: BlockStateId) -> bool {
+        self.hidden_states.contains(&state_id)
+    }
+
+    /// Whether blocks next to this one are exposed, because it isn't a full, opaque cube.
+    #[must_use]
+    pub fn is_open(state_id: BlockStateId) -> bool {
+        let state = get_state_by_state_id(state_id);
+        !state.is_full_cube() || state.opacity != MAX_OPACITY
+    }
+
+    /// Returns the block states to send for each section, or `None` if nothing has to be hidden.
+    ///
+    /// Blocks at the horizontal chunk borders are checked against the neighbouring chunks.
+    #[must_use]
+    pub fn obfuscate(
+        &self,
+        sections: &ChunkSections,
+        neighbors: ChunkNeighbors<'_>,
+    ) -> Option<Box<[BlockPalette]>> {
+        let has_hidden =
+            |palette: &BlockPalette| palette.any_value(|state_id| self.is_hidden(state_id));
+        if !sections
+            .sections
+            .iter()
+            .any(|section| has_hidden(&section.block_states))
+        {
+            return None;
+        }
+
+        let obfuscated = sections
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                let mut palette = section.block_states.clone();
+                if !has_hidden(&section.block_states) {
+                    return palette;
+                }
+                let min_y = sections.min_y + (index * CHUNK_WIDTH) as i32;
+                for y in 0..CHUNK_WIDTH {
+                    let filler = if min_y + (y as i32) < 0 {
+                        self.deep_filler
+                    } else {
+                        self.filler
+                    };
+                    for z in 0..CHUNK_WIDTH {
+                        for x in 0..CHUNK_WIDTH {
+                            let state_id = section.block_states.get(x, y, z);
+                            if self.is_hidden(state_id)
+                                && !Self::is_exposed(sections, neighbors, index, x, y, z)
+                            {
+                                palette.set(x, y, z, filler);
+                            }
+                        }
+                    }
+                }
+                palette
+            })
+            .collect();
+        Some(obfuscated)
+    }
+
+    fn is_exposed(
+        sections: &ChunkSections,
+        neighbors: ChunkNeighbors<'_>,
+        index: usize,
+        x: usize,
+        y: usize,
+        z: usize,
+    ) -> bool {
+        let block_states = &sections.sections[index].block_states;
+        // The block at the other side of the border, `None` if that chunk isn't loaded
+        let across = |neighbor: Option<&ChunkSections>, x, z| {
+            neighbor
+                .and_then(|neighbor| neighbor.sections.get(index))
+                .map(|section| section.block_states.get(x, y, z))
+        };
+
+        let last = CHUNK_WIDTH - 1;
+        let horizontal = [
+            if x > 0 {
+                Some(block_states.get(x - 1, y, z))
+            } else {
+                across(neighbors.west, last, z)
+            },
+            if x < last {
+                Some(block_states.get(x + 1, y, z))
+            } else {
+                across(neighbors.east, 0, z)
+            },
+            if z > 0 {
+                Some(block_states.get(x, y, z - 1))
+            } else {
+                across(neighbors.north, x, last)
+            },
+            if z < last {
+                Some(block_states.get(x, y, z + 1))
+            } else {
+                across(neighbors.south, x, 0)
+            },
+        ];
+        if horizontal
+            .into_iter()
+            .any(|state_id| state_id.is_none_or(Self::is_open))
+        {
+            return true;
+        }
+
+        let below = if y > 0 {
+            Some(block_states.get(x, y - 1, z))
+        } else {
+            index.checked_sub(1).map(|below| {
+                sections.sections[below]
+                    .block_states
+                    .get(x, CHUNK_WIDTH - 1, z)
+            })
+        };
+        let above = if y + 1 < CHUNK_WIDTH {
+            Some(block_states.get(x, y + 1, z))
+        } else {
+            sections
+                .sections
+                .get(index + 1)
+                .map(|above| above.block_states.get(x, 0, z))
+        };
+        below.into_iter().chain(above).any(Self::is_open)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+
+    use super::{ChunkNeighbors, ChunkObfuscator};
+    use crate::chunk::{ChunkSections, SubChunk, palette::BlockPalette};
+
+    fn single_section(block_states: BlockPalette) -> ChunkSections {
+        ChunkSections::new(
+            vec![SubChunk {
+                block_states,
+                biomes: Default::default(),
+            }]
+            .into_boxed_slice(),
+            0,
+        )
+    }
+
+    #[test]
+    fn hides_only_covered_blocks() {
+        let mut block_states = BlockPalette::Homogeneous(Block::STONE.default_state.id);
+        let ore = Block::DIAMOND_ORE.default_state.id;
+        // Covered on every side
+        block_states.set(5, 5, 5, ore);
+        // Next to a cave
+        block_states.set(8, 8, 8, ore);
+        block_states.set(8, 9, 8, Block::AIR.default_state.id);
+        let sections = single_section(block_states);
+
+        let obfuscator =
+            ChunkObfuscator::new([&Block::DIAMOND_ORE], &Block::STONE, &Block::DEEPSLATE);
+        let obfuscated = obfuscator
+            .obfuscate(&sections, ChunkNeighbors::default())
+            .unwrap();
+        assert_eq!(obfuscated[0].get(5, 5, 5), Block::STONE.default_state.id);
+        assert_eq!(obfuscated[0].get(8, 8, 8), ore);
+        // The chunk itself keeps the real blocks
+        assert_eq!(sections.sections[0].block_states.get(5, 5, 5), ore);
+    }
+
+    #[test]
+    fn checks_neighboring_chunks_at_the_border() {
+        let stone = Block::STONE.default_state.id;
+        let ore = Block::DIAMOND_ORE.default_state.id;
+        let mut block_states = BlockPalette::Homogeneous(stone);
+        // At the west border, next to a cave in the neighbouring chunk
+        block_states.set(0, 4, 7, ore);
+        // At the south border, next to solid stone in the neighbouring chunk
+        block_states.set(3, 4, 15, ore);
+        // At the east border, whose neighbour isn't loaded
+        block_states.set(15, 4, 2, ore);
+        // Next to glass, which can be seen through
+        block_states.set(8, 4, 8, ore);
+        block_states.set(8, 5, 8, Block::GLASS.default_state.id);
+        let sections = single_section(block_states);
+
+        let mut west_states = BlockPalette::Homogeneous(stone);
+        west_states.set(15, 4, 7, Block::AIR.default_state.id);
+        let west = single_section(west_states);
+        let south = single_section(BlockPalette::Homogeneous(stone));
+
+        let obfuscator =
+            ChunkObfuscator::new([&Block::DIAMOND_ORE], &Block::STONE, &Block::DEEPSLATE);
+        let obfuscated = obfuscator
+            .obfuscate(
+                &sections,
+                ChunkNeighbors {
+                    west: Some(&west),
+                    south: Some(&south),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(obfuscated[0].get(0, 4, 7), ore);
+        assert_eq!(obfuscated[0].get(3, 4, 15), stone);
+        assert_eq!(obfuscated[0].get(15, 4, 2), ore);
+        assert_eq!(obfuscated[0].get(8, 4, 8), ore);
+    }
+}
@@ -5,6 +5,8 @@ use itertools::Itertools;
 use lz4_java_wrc::Context;
 use pumpkin_config::advanced_config;
 use pumpkin_util::math::vector2::Vector2;
+use ruzstd::decoding::StreamingDecoder;
+use ruzstd::encoding::{CompressionLevel, compress_to_vec};
 use std::{
     collections::HashSet,
     io::{Read, SeekFrom, Write},
@@ -52,6 +54,8 @@ pub enum Compression {
     ZLib = Self::ZLIB_ID,
     /// LZ4 Compression (since 24w04a)
     LZ4 = Self::LZ4_ID,
+    /// Zstd Compression
+    Zstd = Self::ZSTD_ID,
     /// Custom compression algorithm (since 24w05a)
     Custom = Self::CUSTOM_ID,
 }
@@ -60,6 +64,7 @@ pub enum CompressionRead<R: Read> {
     GZip(GzDecoder<R>),
     ZLib(ZlibDecoder<R>),
     LZ4(lz4_java_wrc::Lz4BlockInput<R>),
+    Zstd(Box<StreamingDecoder<R, ruzstd::decoding::FrameDecoder>>),
 }
 
 impl<R: Read> Read for CompressionRead<R> {
@@ -68,6 +73,7 @@ impl<R: Read> Read for CompressionRead<R> {
             Self::GZip(gzip) => gzip.read(buf),
             Self::ZLib(zlib) => zlib.read(buf),
             Self::LZ4(lz4) => lz4.read(buf),
+            Self::Zstd(zstd) => zstd.read(buf),
         }
     }
 }
@@ -124,6 +130,7 @@ impl Compression {
     const ZLIB_ID: u8 = 2;
     const NO_COMPRESSION_ID: u8 = 3;
     const LZ4_ID: u8 = 4;
+    const ZSTD_ID: u8 = 5;
     const CUSTOM_ID: u8 = 127;
 
     fn decompress_data(&self, compressed_data: &[u8]) -> Result<Box<[u8]>, CompressionError> {
@@ -152,6 +159,19 @@ impl Compression {
                     .map_err(CompressionError::LZ4Error)?;
                 Ok(decompressed_data.into_boxed_slice())
             }
+            Compression::Zstd => {
+                let mut decoder = StreamingDecoder::new(compressed_data).map_err(|err| {
+                    CompressionError::ZstdError(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        err,
+                    ))
+                })?;
+                let mut decompressed_data = Vec::new();
+                decoder
+                    .read_to_end(&mut decompressed_data)
+                    .map_err(CompressionError::ZstdError)?;
+                Ok(decompressed_data.into_boxed_slice())
+            }
             Compression::Custom => todo!(),
         }
     }
@@ -200,6 +220,11 @@ impl Compression {
                 drop(encoder);
                 Ok(compressed_data)
             }
+            // TODO: Currently ruzstd only supports fastest
+            Compression::Zstd => Ok(compress_to_vec(
+                uncompressed_data,
+                CompressionLevel::Fastest,
+            )),
             Compression::Custom => todo!(),
         }
     }
@@ -213,6 +238,7 @@ impl Compression {
             // Uncompressed (since a version before 1.15.1)
             Self::NO_COMPRESSION_ID => Ok(None),
             Self::LZ4_ID => Ok(Some(Self::LZ4)),
+            Self::ZSTD_ID => Ok(Some(Self::Zstd)),
             Self::CUSTOM_ID => Ok(Some(Self::Custom)),
             // Unknown format
             _ => Err(()),
@@ -227,6 +253,7 @@ impl From<pumpkin_config::chunk::Compression> for Compression {
             pumpkin_config::chunk::Compression::GZip => Self::GZip,
             pumpkin_config::chunk::Compression::ZLib => Self::ZLib,
             pumpkin_config::chunk::Compression::LZ4 => Self::LZ4,
+            pumpkin_config::chunk::Compression::Zstd => Self::Zstd,
             pumpkin_config::chunk::Compression::Custom => Self::Custom,
         }
     }
@@ -822,6 +849,7 @@ impl<S: SingleChunkDataSerializer> ChunkSerializer for AnvilChunkFile<S> {
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
+    use pumpkin_config::chunk::ChunkCompression;
     use pumpkin_config::{AdvancedConfiguration, advanced_config, override_config_for_testing};
     use pumpkin_data::BlockDirection;
     use pumpkin_util::math::position::BlockPos;
@@ -833,7 +861,7 @@ mod tests {
     use tokio::sync::RwLock;
 
     use crate::chunk::ChunkData;
-    use crate::chunk::format::anvil::{AnvilChunkFile, SingleChunkDataSerializer};
+    use crate::chunk::format::anvil::{AnvilChunkFile, Compression, SingleChunkDataSerializer};
     use crate::chunk::io::file_manager::{ChunkFileManager, PathFromLevelFolder};
     use crate::chunk::io::{FileIO, LoadedData};
     use crate::dimension::Dimension;
@@ -891,6 +919,33 @@ mod tests {
         read_chunks.into_boxed_slice()
     }
 
+    #[test]
+    fn compression_round_trip() {
+        let data: Vec<u8> = (0..16384u32).map(|i| (i % 251) as u8).collect();
+        for compression in [
+            Compression::GZip,
+            Compression::ZLib,
+            Compression::LZ4,
+            Compression::Zstd,
+        ] {
+            let compressed = compression.compress_data(&data, 6).unwrap();
+            let decompressed = compression.decompress_data(&compressed).unwrap();
+            assert_eq!(&*decompressed, data.as_slice());
+            assert_eq!(
+                Compression::from_byte(compression as u8),
+                Ok(Some(compression))
+            );
+        }
+    }
+
+    #[test]
+    fn new_chunks_default_to_zstd() {
+        assert_eq!(
+            Compression::from(ChunkCompression::default().algorithm),
+            Compression::Zstd
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn not_existing() {
         let region_path = PathBuf::from("not_existing");
@@ -4,13 +4,13 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use futures::future::join_all;
 use pumpkin_data::{Block, chunk::ChunkStatus};
-use pumpkin_nbt::{compound::NbtCompound, from_bytes, nbt_long_array};
+use pumpkin_nbt::{compound::NbtCompound, from_bytes, nbt_int_array, nbt_long_array, tag::NbtTag};
 use uuid::Uuid;
 
 use crate::{
     block::entities::block_entity_from_nbt,
     chunk::{
-        ChunkEntityData, ChunkReadingError, ChunkSerializingError,
+        ChunkEntityData, ChunkReadingError, ChunkSerializingError, can_complete_generation,
         format::anvil::{SingleChunkDataSerializer, WORLD_DATA_VERSION},
         io::{Dirtiable, file_manager::PathFromLevelFolder},
     },
@@ -80,12 +80,11 @@ impl ChunkData {
         chunk_data: &[u8],
         position: Vector2<i32>,
     ) -> Result<Self, ChunkParsingError> {
-        // TODO: Implement chunk stages?
-        if from_bytes::<ChunkStatusWrapper>(chunk_data)
+        let status = from_bytes::<ChunkStatusWrapper>(chunk_data)
             .map_err(ChunkParsingError::FailedReadStatus)?
-            .status
-            != ChunkStatus::Full
-        {
+            .status;
+        // Chunks stopped at an early stage are loaded too, the world generator runs the rest
+        if status != ChunkStatus::Full && !can_complete_generation(status) {
             return Err(ChunkParsingError::ChunkNotGenerated);
         }
 
@@ -144,8 +143,7 @@ impl ChunkData {
                             section.y as i32 == index as i32 + chunk_data.min_y_section - 1
                         })
                         .and_then(|section| section.block_light.clone())
-                        .map(LightContainer::new)
-                        .unwrap_or_default()
+                        .map_or_else(|| LightContainer::new_empty(0), LightContainer::new)
                 })
                 .collect(),
             sky_light: (0..chunk_data.sections.len() + 2)
@@ -229,6 +227,8 @@ impl ChunkData {
                 block_entities
             },
             light_engine,
+            structures: chunk_data.structures,
+            status,
         })
     }
 
@@ -265,7 +265,7 @@ impl ChunkData {
             x_pos: self.position.x,
             z_pos: self.position.y,
             min_y_section: section_coords::block_to_section(self.section.min_y),
-            status: ChunkStatus::Full,
+            status: self.status,
             heightmaps: self.heightmap.clone(),
             sections,
             block_ticks: {
@@ -308,6 +308,7 @@ impl ChunkData {
             .await,
             // we have not implemented light engine
             light_correct: false,
+            structures: self.structures.clone(),
         };
 
         let mut result = Vec::new();
@@ -375,21 +376,14 @@ impl ChunkEntityData {
         }
         let mut map = HashMap::new();
         for entity_nbt in chunk_entity_data.entities {
-            // TODO: This is wrong, we should use an int array, but our NBT lib for some reason does not work with int arrays and
-            // Just gives me a list when putting in a int array
-            let uuid = match entity_nbt.get_list("UUID") {
-                Some(uuid) => uuid,
-                None => {
-                    log::warn!("TODO: use int arrays for UUID");
-                    continue;
-                }
+            let Some(uuid) = Self::entity_uuid(&entity_nbt) else {
+                log::warn!(
+                    "Skipping an entity without a valid UUID in chunk {},{}",
+                    position.x,
+                    position.y
+                );
+                continue;
             };
-            let uuid = Uuid::from_u128(
-                (uuid.first().unwrap().extract_int().unwrap() as u128) << 96
-                    | (uuid.get(1).unwrap().extract_int().unwrap() as u128) << 64
-                    | (uuid.get(2).unwrap().extract_int().unwrap() as u128) << 32
-                    | (uuid.get(3).unwrap().extract_int().unwrap() as u128),
-            );
             map.insert(uuid, entity_nbt);
         }
 
@@ -400,6 +394,28 @@ impl ChunkEntityData {
         })
     }
 
+    /// Reads the UUID of an entity, which is saved as an array of four ints.
+    fn entity_uuid(entity_nbt: &NbtCompound) -> Option<Uuid> {
+        let ints = match entity_nbt.get("UUID")? {
+            NbtTag::IntArray(ints) => ints.clone(),
+            // Older versions of Pumpkin saved it as a list of ints
+            NbtTag::List(list) => list
+                .iter()
+                .map(NbtTag::extract_int)
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+        let &[a, b, c, d] = ints.as_slice() else {
+            return None;
+        };
+        Some(Uuid::from_u128(
+            (u128::from(a as u32) << 96)
+                | (u128::from(b as u32) << 64)
+                | (u128::from(c as u32) << 32)
+                | u128::from(d as u32),
+        ))
+    }
+
     fn internal_to_bytes(&self) -> Result<Bytes, ChunkSerializingError> {
         let nbt = EntityNbt {
             data_version: WORLD_DATA_VERSION,
@@ -568,12 +584,121 @@ struct ChunkNbt {
     block_entities: Vec<NbtCompound>,
     #[serde(rename = "isLightOn")]
     light_correct: bool,
+    #[serde(
+        rename = "structures",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    structures: Option<NbtCompound>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct EntityNbt {
     data_version: i32,
+    #[serde(serialize_with = "nbt_int_array")]
     position: [i32; 2],
     entities: Vec<NbtCompound>,
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_data::chunk::ChunkStatus;
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+    use pumpkin_util::math::vector2::Vector2;
+    use uuid::Uuid;
+
+    use super::LightContainer;
+    use crate::chunk::{
+        ChunkData, ChunkEntityData, ChunkLight, ChunkParsingError, ChunkSections, SubChunk,
+    };
+
+    fn chunk_at_status(position: Vector2<i32>, status: ChunkStatus) -> ChunkData {
+        ChunkData {
+            section: ChunkSections::new((0..24).map(|_| SubChunk::default()).collect(), -64),
+            heightmap: Default::default(),
+            position,
+            dirty: true,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLight {
+                sky_light: (0..26).map(|_| LightContainer::new_filled(15)).collect(),
+                block_light: (0..26).map(|_| LightContainer::new_empty(0)).collect(),
+            },
+            structures: None,
+            status,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_partially_generated_chunks() {
+        let position = Vector2::new(2, -5);
+        let bytes = chunk_at_status(position, ChunkStatus::Surface)
+            .internal_to_bytes()
+            .await
+            .unwrap();
+        let read = ChunkData::internal_from_bytes(&bytes, position).unwrap();
+        assert_eq!(read.status, ChunkStatus::Surface);
+
+        // Carvers and features already reached into the neighbours, so it is generated again
+        let bytes = chunk_at_status(position, ChunkStatus::Features)
+            .internal_to_bytes()
+            .await
+            .unwrap();
+        assert!(matches!(
+            ChunkData::internal_from_bytes(&bytes, position),
+            Err(ChunkParsingError::ChunkNotGenerated)
+        ));
+    }
+
+    #[test]
+    fn test_entity_uuid_round_trip() {
+        // The high bits are set, so the ints are negative
+        let uuid = Uuid::from_u128(0xF1E2_D3C4_B5A6_9788_8796_A5B4_C3D2_E1F0);
+        let value = uuid.as_u128();
+        let mut entity = NbtCompound::new();
+        entity.put_string("id", "minecraft:zombie".to_string());
+        entity.put(
+            "UUID",
+            NbtTag::IntArray(vec![
+                (value >> 96) as i32,
+                (value >> 64) as i32,
+                (value >> 32) as i32,
+                value as i32,
+            ]),
+        );
+
+        let position = Vector2::new(-3, 7);
+        let chunk = ChunkEntityData {
+            chunk_position: position,
+            data: HashMap::from([(uuid, entity.clone())]),
+            dirty: true,
+        };
+        let bytes = chunk.internal_to_bytes().unwrap();
+        let read = ChunkEntityData::internal_from_bytes(&bytes, position).unwrap();
+
+        assert_eq!(read.data.len(), 1);
+        let read_entity = read.data.get(&uuid).unwrap();
+        assert_eq!(read_entity, &entity);
+        assert!(read_entity.get_int_array("UUID").is_some());
+    }
+
+    #[test]
+    fn test_entity_uuid_from_list() {
+        let uuid = Uuid::from_u128(0x8000_0000_0000_0001_FFFF_FFFF_0000_0002);
+        let mut entity = NbtCompound::new();
+        entity.put(
+            "UUID",
+            NbtTag::List(vec![
+                NbtTag::Int(i32::MIN),
+                NbtTag::Int(1),
+                NbtTag::Int(-1),
+                NbtTag::Int(2),
+            ]),
+        );
+        assert_eq!(ChunkEntityData::entity_uuid(&entity), Some(uuid));
+    }
+}
@@ -377,6 +377,32 @@ where
         Ok(())
     }
 
+    async fn flush(&self) -> Result<(), ChunkWritingError> {
+        let serializers = self
+            .file_locks
+            .read()
+            .await
+            .iter()
+            .filter_map(|(path, loader)| {
+                loader
+                    .internal
+                    .get()
+                    .map(|serializer| (path.clone(), serializer.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        for (path, serializer) in serializers {
+            log::debug!("Flushing file for {path:?}");
+            serializer
+                .read()
+                .await
+                .write(path)
+                .await
+                .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+        }
+        Ok(())
+    }
+
     async fn clean_up_log(&self) {
         let locks = self.file_locks.read().await;
         log::debug!("{} File locks remain in cache", locks.len());
@@ -80,6 +80,10 @@ where
     /// Tells the `ChunkIO` that no more chunks are loaded in memory
     async fn clear_watched_chunks(&self);
 
+    /// Writes the files of chunks which are still loaded to disk. `save_chunks` only writes
+    /// files once none of their chunks are loaded anymore.
+    async fn flush(&self) -> Result<(), ChunkWritingError>;
+
     async fn clean_up_log(&self);
 
     /// Ensure that all ongoing operations are finished
@@ -0,0 +1,377 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use pumpkin_data::block_properties::get_state_by_state_id;
+
+use super::{ChunkData, format::LightContainer};
+
+const SECTION_SIZE: usize = LightContainer::DIM;
+const MAX_LIGHT_LEVEL: u8 = 15;
+
+type LightPos = (usize, usize, usize);
+
+/// A cube of light levels the BFS runs in.
+trait LightGrid {
+    /// Exclusive upper bounds of the coordinates
+    fn bounds(&self) -> LightPos;
+    fn get_level(&self, pos: LightPos) -> u8;
+    fn set_level(&mut self, pos: LightPos, level: u8);
+}
+
+impl LightGrid for LightContainer {
+    fn bounds(&self) -> LightPos {
+        (SECTION_SIZE, SECTION_SIZE, SECTION_SIZE)
+    }
+
+    fn get_level(&self, (x, y, z): LightPos) -> u8 {
+        self.get(x, y, z)
+    }
+
+    fn set_level(&mut self, (x, y, z): LightPos, level: u8) {
+        self.set(x, y, z, level);
+    }
+}
+
+/// The chunks on each side of the changed chunk. Light loses at least one level per block, so
+/// it never spreads further than this.
+const AREA_CHUNKS: usize = 3;
+const AREA_SIZE: usize = AREA_CHUNKS * SECTION_SIZE;
+
+/// The block light of a 3x3 chunk area around a changed block, including the ghost sections below
+/// and above. Chunks which aren't loaded are dark and light can't enter them.
+struct AreaLight<'a> {
+    /// Indexed by `z * AREA_CHUNKS + x` in chunks
+    chunks: [Option<&'a mut [LightContainer]>; AREA_CHUNKS * AREA_CHUNKS],
+    height: usize,
+    changed_sections: [BTreeSet<usize>; AREA_CHUNKS * AREA_CHUNKS],
+}
+
+fn area_chunk_index(x: usize, z: usize) -> usize {
+    (z / SECTION_SIZE) * AREA_CHUNKS + x / SECTION_SIZE
+}
+
+impl LightGrid for AreaLight<'_> {
+    fn bounds(&self) -> LightPos {
+        (AREA_SIZE, self.height, AREA_SIZE)
+    }
+
+    fn get_level(&self, (x, y, z): LightPos) -> u8 {
+        self.chunks[area_chunk_index(x, z)]
+            .as_ref()
+            .and_then(|sections| sections.get(y / SECTION_SIZE))
+            .map_or(0, |section| {
+                section.get(x % SECTION_SIZE, y % SECTION_SIZE, z % SECTION_SIZE)
+            })
+    }
+
+    fn set_level(&mut self, (x, y, z): LightPos, level: u8) {
+        let chunk = area_chunk_index(x, z);
+        let index = y / SECTION_SIZE;
+        let Some(section) = self.chunks[chunk]
+            .as_mut()
+            .and_then(|sections| sections.get_mut(index))
+        else {
+            return;
+        };
+        section.set(x % SECTION_SIZE, y % SECTION_SIZE, z % SECTION_SIZE, level);
+        self.changed_sections[chunk].insert(index);
+    }
+}
+
+fn neighbors(
+    (x, y, z): LightPos,
+    (max_x, max_y, max_z): LightPos,
+) -> impl Iterator<Item = LightPos> {
+    [
+        x.checked_sub(1).map(|x| (x, y, z)),
+        (x + 1 < max_x).then_some((x + 1, y, z)),
+        y.checked_sub(1).map(|y| (x, y, z)),
+        (y + 1 < max_y).then_some((x, y + 1, z)),
+        z.checked_sub(1).map(|z| (x, y, z)),
+        (z + 1 < max_z).then_some((x, y, z + 1)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// A position to spread light from, like vanilla's light engine queue entries.
+struct Increase {
+    pos: LightPos,
+    level: u8,
+    /// Light sources set their own level, other entries are skipped once their level changed
+    from_emission: bool,
+}
+
+/// Spreads light from the queued positions. Every step loses at least one level, or the opacity
+/// of the block entered. `opacity` returns `None` where light can't go.
+fn increase_light(
+    grid: &mut impl LightGrid,
+    mut queue: VecDeque<Increase>,
+    opacity: &impl Fn(LightPos) -> Option<u8>,
+) {
+    let bounds = grid.bounds();
+    while let Some(Increase {
+        pos,
+        level,
+        from_emission,
+    }) = queue.pop_front()
+    {
+        let mut current = grid.get_level(pos);
+        if from_emission && current < level {
+            grid.set_level(pos, level);
+            current = level;
+        }
+        // Another source took over or the light was removed after this got queued
+        if current != level {
+            continue;
+        }
+        for neighbor in neighbors(pos, bounds) {
+            let Some(opacity) = opacity(neighbor) else {
+                continue;
+            };
+            let new_level = level.saturating_sub(opacity.max(1));
+            if new_level > grid.get_level(neighbor) {
+                grid.set_level(neighbor, new_level);
+                queue.push_back(Increase {
+                    pos: neighbor,
+                    level: new_level,
+                    from_emission: false,
+                });
+            }
+        }
+    }
+}
+
+/// Removes the light which came from `pos`. Returns the positions which have to spread their light
+/// again: the light sources found on the way and the lit border of the removed area.
+fn decrease_light(
+    grid: &mut impl LightGrid,
+    pos: LightPos,
+    emission: &impl Fn(LightPos) -> u8,
+) -> VecDeque<Increase> {
+    let bounds = grid.bounds();
+    let mut relight = VecDeque::new();
+    let mut queue = VecDeque::from([(pos, grid.get_level(pos))]);
+    grid.set_level(pos, 0);
+    while let Some((pos, level)) = queue.pop_front() {
+        for neighbor in neighbors(pos, bounds) {
+            let neighbor_level = grid.get_level(neighbor);
+            if neighbor_level == 0 {
+                continue;
+            }
+            if neighbor_level < level {
+                // The light may have come from here, remove it and bring back what the block emits
+                grid.set_level(neighbor, 0);
+                queue.push_back((neighbor, neighbor_level));
+                let emitted = emission(neighbor);
+                if emitted > 0 {
+                    relight.push_back(Increase {
+                        pos: neighbor,
+                        level: emitted,
+                        from_emission: true,
+                    });
+                }
+            } else {
+                relight.push_back(Increase {
+                    pos: neighbor,
+                    level: neighbor_level,
+                    from_emission: false,
+                });
+            }
+        }
+    }
+    relight
+}
+
+impl LightContainer {
+    /// Lights `pos` with `initial_level` and spreads it through the section with a BFS.
+    ///
+    /// `neighbors` returns the opacity of the block at a position, or `None` where light can't go.
+    pub fn propagate_from(
+        &mut self,
+        pos: (usize, usize, usize),
+        initial_level: u8,
+        neighbors: impl Fn(usize, usize, usize) -> Option<u8>,
+    ) {
+        let initial_level = initial_level.min(MAX_LIGHT_LEVEL);
+        increase_light(
+            self,
+            VecDeque::from([Increase {
+                pos,
+                level: initial_level,
+                from_emission: true,
+            }]),
+            &|(x, y, z)| neighbors(x, y, z),
+        );
+    }
+}
+
+/// Recalculates the block light around a block which changed its light emission or opacity.
+///
+/// `chunks` are the 3x3 chunks around the changed block, indexed by `z * 3 + x` with the block's
+/// chunk in the middle, or `None` where a chunk isn't loaded. Light spreads across all of them.
+/// Returns the indices of the changed light sections for each chunk.
+pub fn recalculate_block_light(
+    chunks: [Option<&mut ChunkData>; AREA_CHUNKS * AREA_CHUNKS],
+    relative_x: usize,
+    y: i32,
+    relative_z: usize,
+) -> [BTreeSet<usize>; AREA_CHUNKS * AREA_CHUNKS] {
+    let mut blocks = [const { None }; AREA_CHUNKS * AREA_CHUNKS];
+    let mut light = [const { None }; AREA_CHUNKS * AREA_CHUNKS];
+    let mut min_y = None;
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let Some(chunk) = chunk else {
+            continue;
+        };
+        min_y.get_or_insert(chunk.section.min_y);
+        blocks[index] = Some(&chunk.section);
+        light[index] = Some(&mut chunk.light_engine.block_light[..]);
+    }
+    let center = area_chunk_index(SECTION_SIZE, SECTION_SIZE);
+    let (Some(min_y), Some(height)) = (
+        min_y,
+        light[center]
+            .as_ref()
+            .map(|sections| sections.len() * SECTION_SIZE),
+    ) else {
+        return Default::default();
+    };
+    let Ok(light_y) = usize::try_from(y - min_y + SECTION_SIZE as i32) else {
+        return Default::default();
+    };
+
+    // The ghost sections contain no blocks
+    let block_state = |(x, y, z): LightPos| -> Option<u16> {
+        let sections = blocks[area_chunk_index(x, z)]?;
+        let Some(y) = y.checked_sub(SECTION_SIZE) else {
+            return Some(0);
+        };
+        Some(
+            sections
+                .get_relative_block(x % SECTION_SIZE, y, z % SECTION_SIZE)
+                .unwrap_or(0),
+        )
+    };
+    let opacity = |pos: LightPos| {
+        let state_id = block_state(pos)?;
+        // u8::MAX is used for blocks without opacity
+        let opacity = match get_state_by_state_id(state_id).opacity {
+            u8::MAX => 0,
+            opacity => opacity,
+        };
+        (opacity < MAX_LIGHT_LEVEL).then_some(opacity)
+    };
+    let emission = |pos: LightPos| {
+        block_state(pos).map_or(0, |state_id| {
+            get_state_by_state_id(state_id)
+                .luminance
+                .min(MAX_LIGHT_LEVEL)
+        })
+    };
+
+    let mut area = AreaLight {
+        chunks: light,
+        height,
+        changed_sections: Default::default(),
+    };
+    let pos = (
+        SECTION_SIZE + relative_x,
+        light_y,
+        SECTION_SIZE + relative_z,
+    );
+    let mut queue = decrease_light(&mut area, pos, &emission);
+    let emitted = emission(pos);
+    if emitted > 0 {
+        queue.push_back(Increase {
+            pos,
+            level: emitted,
+            from_emission: true,
+        });
+    }
+    increase_light(&mut area, queue, &opacity);
+
+    area.changed_sections
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::recalculate_block_light;
+    use crate::chunk::{
+        ChunkData, ChunkLight, ChunkSections, ChunkStatus, SubChunk, format::LightContainer,
+    };
+
+    fn empty_chunk(x: i32, z: i32) -> ChunkData {
+        ChunkData {
+            section: ChunkSections::new((0..4).map(|_| SubChunk::default()).collect(), 0),
+            heightmap: Default::default(),
+            position: Vector2::new(x, z),
+            dirty: false,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLight {
+                sky_light: (0..6).map(|_| LightContainer::new_filled(15)).collect(),
+                block_light: (0..6).map(|_| LightContainer::new_empty(0)).collect(),
+            },
+            structures: None,
+            status: ChunkStatus::Full,
+        }
+    }
+
+    /// Sets a block in the middle chunk of `chunks` and recalculates the light around it
+    fn set_block(chunks: &mut [ChunkData], x: usize, y: i32, z: usize, state_id: u16) {
+        chunks[4].set_block_absolute_y(x, y, z, state_id);
+        let chunks: Vec<_> = chunks.iter_mut().map(Some).collect();
+        recalculate_block_light(chunks.try_into().ok().unwrap(), x, y, z);
+    }
+
+    /// The block light at a position of the middle chunk, the y counted from the world bottom
+    fn block_light(chunk: &ChunkData, x: usize, y: usize, z: usize) -> u8 {
+        chunk.light_engine.block_light[y / 16 + 1].get(x, y % 16, z)
+    }
+
+    #[test]
+    fn removing_light_keeps_other_sources() {
+        let mut chunks: Vec<_> = (0..9).map(|i| empty_chunk(i % 3, i / 3)).collect();
+        let glowstone = Block::GLOWSTONE.default_state.id;
+        set_block(&mut chunks, 4, 8, 8, glowstone);
+        set_block(&mut chunks, 10, 8, 8, glowstone);
+        assert_eq!(block_light(&chunks[4], 7, 8, 8), 12);
+
+        // The light of the first source goes away, the second one still lights its surroundings
+        set_block(&mut chunks, 4, 8, 8, Block::AIR.default_state.id);
+        assert_eq!(block_light(&chunks[4], 4, 8, 8), 9);
+        assert_eq!(block_light(&chunks[4], 7, 8, 8), 12);
+        assert_eq!(block_light(&chunks[4], 10, 8, 8), 15);
+    }
+
+    #[test]
+    fn light_spreads_into_neighbour_chunks() {
+        let mut chunks: Vec<_> = (0..9).map(|i| empty_chunk(i % 3, i / 3)).collect();
+        set_block(&mut chunks, 0, 8, 8, Block::GLOWSTONE.default_state.id);
+        // x = 15 of the chunk to the west is right next to the source
+        assert_eq!(block_light(&chunks[3], 15, 8, 8), 14);
+        assert_eq!(block_light(&chunks[3], 10, 8, 8), 9);
+
+        set_block(&mut chunks, 0, 8, 8, Block::AIR.default_state.id);
+        assert_eq!(block_light(&chunks[3], 15, 8, 8), 0);
+    }
+
+    #[test]
+    fn propagate_from_decreases_per_step() {
+        let mut light = LightContainer::new_empty(0);
+        light.propagate_from((8, 8, 8), 15, |_, _, _| Some(0));
+
+        assert_eq!(light.get(8, 8, 8), 15);
+        assert_eq!(light.get(9, 8, 8), 14);
+        assert_eq!(light.get(8, 5, 8), 12);
+        assert_eq!(light.get(0, 8, 8), 7);
+        // Blocked by an opaque wall at x = 10
+        let mut light = LightContainer::new_empty(0);
+        light.propagate_from((8, 8, 8), 15, |x, _, _| (x != 10).then_some(0));
+        assert_eq!(light.get(10, 8, 8), 0);
+    }
+}
@@ -1,7 +1,14 @@
 use crate::block::entities::BlockEntity;
 use palette::{BiomePalette, BlockPalette};
+use pumpkin_data::BlockState;
+use pumpkin_data::block_properties::{
+    blocks_movement, get_block_by_state_id, get_state_by_state_id,
+};
+use pumpkin_data::chunk::ChunkStatus;
+use pumpkin_data::tag::Tagable;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::nbt_long_array;
+use pumpkin_util::HeightMap;
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -11,9 +18,12 @@ use thiserror::Error;
 use crate::BlockStateId;
 use crate::chunk::format::LightContainer;
 
+pub mod anti_xray;
 pub mod format;
 pub mod io;
+pub mod light;
 pub mod palette;
+pub mod structure;
 
 // TODO
 pub const CHUNK_WIDTH: usize = BlockPalette::SIZE;
@@ -110,6 +120,17 @@ pub struct ScheduledTick {
     pub target_block_id: u16,
 }
 
+/// Whether a chunk saved at this status, e.g. by vanilla, is completed by the world generator
+/// instead of being generated again. Up to the surface stage a chunk only depends on itself,
+/// afterwards carvers and features reach into its neighbours.
+#[must_use]
+pub const fn can_complete_generation(status: ChunkStatus) -> bool {
+    matches!(
+        status,
+        ChunkStatus::Biomes | ChunkStatus::Noise | ChunkStatus::Surface
+    )
+}
+
 // Clone here cause we want to clone a snapshot of the chunk so we don't block writing for too long
 pub struct ChunkData {
     pub section: ChunkSections,
@@ -120,12 +141,25 @@ pub struct ChunkData {
     pub fluid_ticks: Vec<ScheduledTick>,
     pub block_entities: HashMap<BlockPos, Arc<dyn BlockEntity>>,
     pub light_engine: ChunkLight,
+    /// The structure starts and references of the chunk, kept as NBT until someone needs them.
+    /// See [`structure::StructureStart::from_nbt`].
+    pub structures: Option<NbtCompound>,
+    /// How far the chunk got through generation. Partially generated chunks are completed by the
+    /// world generator before anyone gets to see them, see [`can_complete_generation`].
+    pub status: ChunkStatus,
 
     pub dirty: bool,
 }
 
 impl ChunkData {
     pub fn get_and_tick_block_ticks(&mut self) -> VecDeque<ScheduledTick> {
+        // Counting down the delays changes the chunk too, otherwise chunks with pending ticks
+        // would be unloaded without saving and their ticks would be lost. Chunks without
+        // scheduled ticks stay clean
+        if self.block_ticks.is_empty() {
+            return VecDeque::new();
+        }
+        self.dirty = true;
         let mut ticks = VecDeque::new();
         let mut remaining_ticks = Vec::new();
         for mut tick in self.block_ticks.drain(..) {
@@ -142,6 +176,11 @@ impl ChunkData {
     }
 
     pub fn get_and_tick_fluid_ticks(&mut self) -> Vec<ScheduledTick> {
+        // See `get_and_tick_block_ticks`
+        if self.fluid_ticks.is_empty() {
+            return Vec::new();
+        }
+        self.dirty = true;
         let mut ticks = Vec::new();
         self.fluid_ticks.retain_mut(|tick| {
             tick.delay = tick.delay.saturating_sub(1);
@@ -282,6 +321,87 @@ impl Default for ChunkHeightmaps {
     }
 }
 
+impl ChunkHeightmaps {
+    /// Reads the height of a column from the packed heightmap: how many blocks above the bottom of
+    /// the world the first free block over its highest block is, or 0 if the column is empty.
+    ///
+    /// Returns `None` if this kind of heightmap is not stored, or if the heightmap is all zeroes,
+    /// which is the case for chunks that were generated without one.
+    pub fn get_height(
+        &self,
+        heightmap: HeightMap,
+        relative_x: usize,
+        relative_z: usize,
+        world_height: u16,
+    ) -> Option<u16> {
+        let data = match heightmap {
+            HeightMap::WorldSurface | HeightMap::WorldSurfaceWg => &self.world_surface,
+            HeightMap::MotionBlocking => &self.motion_blocking,
+            HeightMap::MotionBlockingNoLeaves => &self.motion_blocking_no_leaves,
+            HeightMap::OceanFloor | HeightMap::OceanFloorWg => return None,
+        };
+        if data.iter().all(|&packed| packed == 0) {
+            return None;
+        }
+
+        let (bits, entries_per_long) = Self::packing(world_height);
+        let index = relative_z * CHUNK_WIDTH + relative_x;
+        let packed = *data.get(index / entries_per_long)? as u64;
+        let height = (packed >> (index % entries_per_long * bits)) & ((1 << bits) - 1);
+        Some(height as u16)
+    }
+
+    /// Writes the height of a column into the packed heightmap, see [`Self::get_height`].
+    pub fn set_height(
+        &mut self,
+        heightmap: HeightMap,
+        relative_x: usize,
+        relative_z: usize,
+        world_height: u16,
+        height: u16,
+    ) {
+        let data = match heightmap {
+            HeightMap::WorldSurface | HeightMap::WorldSurfaceWg => &mut self.world_surface,
+            HeightMap::MotionBlocking => &mut self.motion_blocking,
+            HeightMap::MotionBlockingNoLeaves => &mut self.motion_blocking_no_leaves,
+            HeightMap::OceanFloor | HeightMap::OceanFloorWg => return,
+        };
+        let (bits, entries_per_long) = Self::packing(world_height);
+        let index = relative_z * CHUNK_WIDTH + relative_x;
+        let Some(packed) = data.get_mut(index / entries_per_long) else {
+            return;
+        };
+        let shift = index % entries_per_long * bits;
+        let mask = ((1u64 << bits) - 1) << shift;
+        *packed = ((*packed as u64 & !mask) | ((u64::from(height) << shift) & mask)) as i64;
+    }
+
+    /// How many bits each height uses and how many heights fit into a long. Enough bits to store
+    /// every height from 0 to the world height, 9 for vanilla worlds. Entries do not span across
+    /// longs.
+    fn packing(world_height: u16) -> (usize, usize) {
+        let bits = (u32::from(world_height) + 1)
+            .next_power_of_two()
+            .trailing_zeros() as usize;
+        (bits, 64 / bits)
+    }
+
+    /// Whether a block counts as the top of a column for the heightmap.
+    fn is_opaque(heightmap: HeightMap, state: &BlockState) -> bool {
+        match heightmap {
+            HeightMap::WorldSurface | HeightMap::WorldSurfaceWg => !state.is_air(),
+            HeightMap::OceanFloor | HeightMap::OceanFloorWg => blocks_movement(state),
+            HeightMap::MotionBlocking => blocks_movement(state) || state.is_liquid(),
+            HeightMap::MotionBlockingNoLeaves => {
+                (blocks_movement(state) || state.is_liquid())
+                    && !get_block_by_state_id(state.id)
+                        .is_tagged_with("minecraft:leaves")
+                        .unwrap_or(false)
+            }
+        }
+    }
+}
+
 impl ChunkSections {
     pub fn new(sections: Box<[SubChunk]>, min_y: i32) -> Self {
         Self { sections, min_y }
@@ -316,6 +436,22 @@ impl ChunkSections {
         self.set_relative_block(relative_x, relative_y, relative_z, block_state);
     }
 
+    /// Gets the biome id of the 4x4x4 biome cell containing the given block
+    pub fn get_biome_absolute_y(&self, relative_x: usize, y: i32, relative_z: usize) -> Option<u8> {
+        let y = y - self.min_y;
+        if y < 0 {
+            return None;
+        }
+        let biome_y = y as usize / 4;
+        let section_index = biome_y / BiomePalette::SIZE;
+        let relative_y = biome_y % BiomePalette::SIZE;
+        self.sections.get(section_index).map(|section| {
+            section
+                .biomes
+                .get(relative_x / 4, relative_y, relative_z / 4)
+        })
+    }
+
     /// Gets the given block in the chunk
     fn get_relative_block(
         &self,
@@ -411,9 +547,75 @@ impl ChunkData {
         relative_z: usize,
         block_state_id: BlockStateId,
     ) {
-        // TODO @LUK_ESC? update the heightmap
         self.section
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
+        self.update_heightmaps(relative_x, relative_y, relative_z, block_state_id);
+    }
+
+    /// Sets the given block in the chunk at an absolute Y and updates the heightmaps
+    pub fn set_block_absolute_y(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) {
+        let relative_y = y - self.section.min_y;
+        debug_assert!(relative_y >= 0);
+        self.set_relative_block(relative_x, relative_y as usize, relative_z, block_state_id);
+    }
+
+    /// Raises a column of the heightmaps if the block was placed above its top, or lowers it to the
+    /// next matching block below if its top block was replaced, like vanilla.
+    fn update_heightmaps(
+        &mut self,
+        relative_x: usize,
+        relative_y: usize,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) {
+        let world_height = (self.section.sections.len() * BlockPalette::SIZE) as u16;
+        let state = get_state_by_state_id(block_state_id);
+        for heightmap in [
+            HeightMap::WorldSurface,
+            HeightMap::MotionBlocking,
+            HeightMap::MotionBlockingNoLeaves,
+        ] {
+            let Some(height) =
+                self.heightmap
+                    .get_height(heightmap, relative_x, relative_z, world_height)
+            else {
+                // Without a heightmap there is nothing to keep up to date
+                continue;
+            };
+            let height = usize::from(height);
+            let new_height = if ChunkHeightmaps::is_opaque(heightmap, state) {
+                if relative_y < height {
+                    continue;
+                }
+                relative_y + 1
+            } else if relative_y + 1 == height {
+                (0..relative_y)
+                    .rev()
+                    .find(|&y| {
+                        self.section
+                            .get_relative_block(relative_x, y, relative_z)
+                            .is_some_and(|id| {
+                                ChunkHeightmaps::is_opaque(heightmap, get_state_by_state_id(id))
+                            })
+                    })
+                    .map_or(0, |y| y + 1)
+            } else {
+                continue;
+            };
+            self.heightmap.set_height(
+                heightmap,
+                relative_x,
+                relative_z,
+                world_height,
+                new_height as u16,
+            );
+        }
     }
 
     /// Sets the given block in the chunk, returning the old block
@@ -433,6 +635,21 @@ impl ChunkData {
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
     }
 
+    /// Gets the Y of the first free block above the highest block of the column in the heightmap,
+    /// or `None` if the chunk does not have that heightmap.
+    pub fn get_top_y(
+        &self,
+        heightmap: HeightMap,
+        relative_x: usize,
+        relative_z: usize,
+    ) -> Option<i32> {
+        let world_height = (self.section.sections.len() * BlockPalette::SIZE) as u16;
+        let height = self
+            .heightmap
+            .get_height(heightmap, relative_x, relative_z, world_height)?;
+        Some(self.section.min_y + i32::from(height))
+    }
+
     #[expect(dead_code)]
     fn calculate_heightmap(&self) -> ChunkHeightmaps {
         // figure out how LongArray is formatted
@@ -456,3 +673,156 @@ pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::chunk::{Biome, ChunkStatus};
+    use pumpkin_util::HeightMap;
+
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{
+        BiomePalette, BlockPalette, ChunkData, ChunkHeightmaps, ChunkLight, ChunkSections,
+        SubChunk, can_complete_generation,
+    };
+
+    /// Packs 9 bit heights, 7 per long, like vanilla does
+    fn pack(heights: &[u16]) -> Box<[i64]> {
+        let mut data = vec![0i64; 37];
+        for (index, &height) in heights.iter().enumerate() {
+            data[index / 7] |= i64::from(height) << (index % 7 * 9);
+        }
+        data.into_boxed_slice()
+    }
+
+    #[test]
+    fn decode_heightmap() {
+        let mut heights = vec![0u16; 256];
+        heights[0] = 64;
+        heights[6] = 384;
+        // x = 1, z = 1, starts a new long
+        heights[17] = 1;
+        heights[255] = 200;
+        let heightmaps = ChunkHeightmaps {
+            motion_blocking: pack(&heights),
+            ..Default::default()
+        };
+
+        let height = |x, z| heightmaps.get_height(HeightMap::MotionBlocking, x, z, 384);
+        assert_eq!(height(0, 0), Some(64));
+        assert_eq!(height(6, 0), Some(384));
+        assert_eq!(height(1, 1), Some(1));
+        assert_eq!(height(15, 15), Some(200));
+        assert_eq!(height(3, 3), Some(0));
+        // Not stored
+        assert_eq!(
+            heightmaps.get_height(HeightMap::WorldSurface, 0, 0, 384),
+            None
+        );
+        assert_eq!(
+            heightmaps.get_height(HeightMap::OceanFloor, 0, 0, 384),
+            None
+        );
+    }
+
+    #[test]
+    fn top_y_is_offset_by_min_y() {
+        let mut heights = vec![0u16; 256];
+        // Highest block at y = -55
+        heights[0] = 10;
+        heights[1] = 100;
+        let chunk = ChunkData {
+            section: ChunkSections::new((0..24).map(|_| SubChunk::default()).collect(), -64),
+            heightmap: ChunkHeightmaps {
+                world_surface: pack(&heights),
+                ..Default::default()
+            },
+            position: Vector2::new(0, 0),
+            dirty: false,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLight::default(),
+            structures: None,
+            status: ChunkStatus::Full,
+        };
+
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 0, 0), Some(-54));
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 1, 0), Some(36));
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 2, 0), Some(-64));
+        assert_eq!(chunk.get_top_y(HeightMap::MotionBlocking, 0, 0), None);
+    }
+
+    #[test]
+    fn setting_blocks_updates_heightmaps() {
+        let mut heights = vec![0u16; 256];
+        heights[0] = 1;
+        let mut chunk = ChunkData {
+            section: ChunkSections::new((0..24).map(|_| SubChunk::default()).collect(), -64),
+            heightmap: ChunkHeightmaps {
+                world_surface: pack(&heights),
+                motion_blocking: pack(&heights),
+                motion_blocking_no_leaves: pack(&heights),
+            },
+            position: Vector2::new(0, 0),
+            dirty: false,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLight::default(),
+            structures: None,
+            status: ChunkStatus::Full,
+        };
+        chunk.set_block_absolute_y(0, -64, 0, Block::STONE.default_state.id);
+        chunk.set_block_absolute_y(0, -60, 0, Block::STONE.default_state.id);
+        chunk.set_block_absolute_y(0, 10, 0, Block::OAK_LEAVES.default_state.id);
+
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 0, 0), Some(11));
+        assert_eq!(chunk.get_top_y(HeightMap::MotionBlocking, 0, 0), Some(11));
+        assert_eq!(
+            chunk.get_top_y(HeightMap::MotionBlockingNoLeaves, 0, 0),
+            Some(-59)
+        );
+
+        // Breaking the top block lowers the column to the next block below
+        chunk.set_block_absolute_y(0, 10, 0, Block::AIR.default_state.id);
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 0, 0), Some(-59));
+        chunk.set_block_absolute_y(0, -60, 0, Block::AIR.default_state.id);
+        assert_eq!(chunk.get_top_y(HeightMap::MotionBlocking, 0, 0), Some(-63));
+        // Other columns are untouched
+        assert_eq!(chunk.get_top_y(HeightMap::WorldSurface, 1, 0), Some(-64));
+    }
+
+    #[test]
+    fn biome_at_block() {
+        let plains = || SubChunk {
+            block_states: BlockPalette::default(),
+            biomes: BiomePalette::Homogeneous(Biome::PLAINS.id),
+        };
+        let mut sections = ChunkSections::new((0..24).map(|_| plains()).collect(), -64);
+        // Biome cell (1, 17, 3) covers the blocks x 4..8, y 4..8 and z 12..16
+        sections.set_relative_biome(1, 17, 3, Biome::DESERT.id);
+
+        let biome = |x, y, z| {
+            let id = sections.get_biome_absolute_y(x, y, z).unwrap();
+            Biome::from_id(id).unwrap().resource_location
+        };
+        assert_eq!(biome(4, 4, 12), "minecraft:desert");
+        assert_eq!(biome(7, 7, 15), "minecraft:desert");
+        assert_eq!(biome(3, 4, 12), "minecraft:plains");
+        assert_eq!(biome(4, 8, 12), "minecraft:plains");
+        assert_eq!(sections.get_biome_absolute_y(0, -65, 0), None);
+        assert_eq!(sections.get_biome_absolute_y(0, 320, 0), None);
+    }
+
+    #[test]
+    fn only_self_contained_stages_are_completed() {
+        assert!(can_complete_generation(ChunkStatus::Biomes));
+        assert!(can_complete_generation(ChunkStatus::Surface));
+        assert!(!can_complete_generation(ChunkStatus::Empty));
+        assert!(!can_complete_generation(ChunkStatus::Features));
+        assert!(!can_complete_generation(ChunkStatus::Full));
+        assert!(ChunkStatus::Noise < ChunkStatus::Surface);
+    }
+}
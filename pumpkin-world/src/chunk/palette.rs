@@ -213,6 +213,17 @@ impl<V: Hash + Eq + Copy + Default, const DIM: usize> PalettedContainer<V, DIM>
         }
     }
 
+    /// Whether any of the distinct values in the container matches the predicate.
+    pub fn any_value<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(V) -> bool,
+    {
+        match self {
+            Self::Homogeneous(value) => f(*value),
+            Self::Heterogeneous(data) => data.counts.keys().any(|value| f(*value)),
+        }
+    }
+
     pub fn for_each<F>(&self, mut f: F)
     where
         F: FnMut(V),
@@ -0,0 +1,113 @@
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+
+/// The id vanilla saves for structures which were placed into a chunk but failed to generate
+const INVALID_ID: &str = "INVALID";
+
+/// A structure, like a village or a stronghold, which starts in a chunk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructureStart {
+    /// The structure type, e.g. `minecraft:village_plains`
+    pub id: String,
+    pub chunk: Vector2<i32>,
+}
+
+impl StructureStart {
+    /// Reads the structure starts from the `structures` compound of the chunk at `chunk`.
+    pub fn from_nbt(structures: &NbtCompound, chunk: Vector2<i32>) -> Vec<Self> {
+        let Some(starts) = structures.get_compound("starts") else {
+            return Vec::new();
+        };
+        starts
+            .child_tags
+            .iter()
+            .filter_map(|(key, start)| {
+                let start = start.extract_compound()?;
+                let id = start.get_string("id").map_or(key.as_str(), String::as_str);
+                if id == INVALID_ID {
+                    return None;
+                }
+                Some(Self {
+                    id: id.to_string(),
+                    chunk: Vector2::new(
+                        start.get_int("ChunkX").unwrap_or(chunk.x),
+                        start.get_int("ChunkZ").unwrap_or(chunk.y),
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// Writes the starts into a `structures` compound like vanilla saves it.
+    pub fn to_nbt(starts: &[Self]) -> NbtCompound {
+        let mut starts_nbt = NbtCompound::new();
+        for start in starts {
+            let mut start_nbt = NbtCompound::new();
+            start_nbt.put_string("id", start.id.clone());
+            start_nbt.put_int("ChunkX", start.chunk.x);
+            start_nbt.put_int("ChunkZ", start.chunk.y);
+            start_nbt.put_list("Children", Vec::new());
+            starts_nbt.put_component(&start.id, start_nbt);
+        }
+        let mut structures = NbtCompound::new();
+        structures.put_component("starts", starts_nbt);
+        structures.put_component("References", NbtCompound::new());
+        structures
+    }
+
+    /// The position `/locate` reports, the corner of the start chunk like vanilla.
+    pub fn locate_pos(&self) -> BlockPos {
+        BlockPos::new(self.chunk.x << 4, 0, self.chunk.y << 4)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::StructureStart;
+
+    #[test]
+    fn skips_invalid_starts() {
+        let mut village = NbtCompound::new();
+        village.put_string("id", "minecraft:village_plains".to_string());
+        village.put_int("ChunkX", 3);
+        village.put_int("ChunkZ", -2);
+        let mut mineshaft = NbtCompound::new();
+        mineshaft.put_string("id", "INVALID".to_string());
+
+        let mut starts = NbtCompound::new();
+        starts.put_component("minecraft:village_plains", village);
+        starts.put_component("minecraft:mineshaft", mineshaft);
+        let mut structures = NbtCompound::new();
+        structures.put_component("starts", starts);
+
+        let starts = StructureStart::from_nbt(&structures, Vector2::new(3, -2));
+        assert_eq!(
+            starts,
+            vec![StructureStart {
+                id: "minecraft:village_plains".to_string(),
+                chunk: Vector2::new(3, -2),
+            }]
+        );
+        assert_eq!(starts[0].locate_pos().0.x, 48);
+        assert_eq!(starts[0].locate_pos().0.z, -32);
+    }
+
+    #[test]
+    fn starts_round_trip() {
+        let starts = vec![StructureStart {
+            id: "minecraft:desert_pyramid".to_string(),
+            chunk: Vector2::new(-7, 12),
+        }];
+        let structures = StructureStart::to_nbt(&starts);
+        assert_eq!(
+            StructureStart::from_nbt(&structures, Vector2::new(-7, 12)),
+            starts
+        );
+        assert!(
+            StructureStart::from_nbt(&StructureStart::to_nbt(&[]), Vector2::new(0, 0)).is_empty()
+        );
+    }
+}
@@ -1,166 +1,334 @@
-// use pumpkin_macros::block_state;
-// use pumpkin_util::{
-//     math::{float_provider::FloatProvider, vector2::Vector2, vector3::Vector3},
-//     random::RandomGenerator,
-// };
-// use serde::Deserialize;
-
-// use crate::{
-//     ProtoChunk,
-//     block::{BlockState, registry::get_block},
-//     generation::{
-//         aquifer_sampler::{AquiferSamplerImpl, WorldAquiferSampler},
-//         height_limit::HeightLimitView,
-//         height_provider::HeightProvider,
-//         positions::chunk_pos,
-//         section_coords,
-//         y_offset::YOffset,
-//     },
-// };
-
-// use super::mask::CarvingMask;
-
-// #[derive(Deserialize)]
-// pub struct CaveCraver {
-//     vertical_radius_multiplier: FloatProvider,
-//     horizontal_radius_multiplier: FloatProvider,
-//     floor_level: FloatProvider,
-//     y: HeightProvider,
-//     #[serde(rename = "yScale")]
-//     y_scale: FloatProvider,
-//     lava_level: YOffset,
-//     probability: f32,
-// }
-// const BRANCH_FACTOR: i32 = 4;
-// const MAX_CAVE_COUNT: i32 = 15;
-
-// impl CaveCraver {
-//     pub fn should_carve(&self, random: &mut RandomGenerator) -> bool {
-//         random.next_f32() <= self.probability
-//     }
-
-//     pub fn carve(
-//         &self,
-//         random: &mut RandomGenerator,
-//         chunk_pos: &Vector2<i32>,
-//         min_y: i8,
-//         height: u16,
-//     ) {
-//         todo!();
-//         let block_coord = section_coords::section_to_block(BRANCH_FACTOR * 2 - 1);
-//         let first_rnd = random.next_bounded_i32(MAX_CAVE_COUNT);
-//         let sec_rnd = random.next_bounded_i32(first_rnd + 1);
-//         let third_rnd = random.next_bounded_i32(sec_rnd + 1);
-//         let range = third_rnd;
-//         for _ in 0..range {
-//             let x = chunk_pos::start_block_x(chunk_pos) + random.next_bounded_i32(16); // offset
-//             let y = self.y.get(random, min_y, height);
-//             let z = chunk_pos::start_block_z(chunk_pos) + random.next_bounded_i32(16); // offset
-//             let vertical = self.vertical_radius_multiplier.get();
-//             let horizontal = self.horizontal_radius_multiplier.get();
-//             let floor = self.floor_level.get();
-//             let mut pitch;
-//             let mut tries = 0;
-//             if random.next_bounded_i32(4) == 0 {
-//                 let scale = self.y_scale.get();
-//                 pitch = 1.0 + random.next_f32() * 6.0;
-//                 tries += random.next_bounded_i32(4);
-//             }
-//         }
-//     }
-
-//     fn carve_cave(&self, chunk_pos: &Vector2<i32>, width: f64, height: f64) {
-//         todo!();
-//       //  let width = 1.5 + 1.5707964f64.sin() * width;
-//      //   let height = width * height;
-//      //   Self::carve_region(chunk_pos, width, height)
-//     }
-
-//     fn carve_region(
-//         min_y: i8,
-//         chunk_height: u16,
-//         chunk_pos: &Vector2<i32>,
-//         x: f64,
-//         y: f64,
-//         z: f64,
-//         width: f64,
-//         height: f64,
-//         mask: &mut CarvingMask,
-//         floor_level: f64,
-//     ) {
-//         let start_x = chunk_pos::start_block_x(chunk_pos);
-//         let start_z = chunk_pos::start_block_z(chunk_pos);
-
-//         let chunk_center_x = (start_x + 8) as f64;
-//         let chunk_center_z = (start_z + 8) as f64;
-
-//         let max_width = 16.0 + width * 2.0;
-//         if (x - chunk_center_x).abs() > max_width || (z - chunk_center_z).abs() > max_width {
-//             return;
-//         }
-//         let x_start = (x - width).floor() as i32 - start_x - 1.max(0);
-//         let max_x = (x - width).floor() as i32 - start_x - 1.min(15);
-
-//         let z_start = (z - width).floor() as i32 - start_z - 1.max(0);
-//         let max_z = (z - width).floor() as i32 - start_z - 1.min(15);
-
-//         let init_height =
-//             (y + height).floor() as i32 + 1.min(min_y as i32 + chunk_height as i32 - 1 - 7);
-
-//         let end_height = (y - height).floor() as i32 - 1.max(min_y as i32 + 1);
-
-//         for current_x in max_x..x_start {
-//             let x_offset = chunk_pos::start_block_x(chunk_pos) + current_x;
-//             let x_offwidth = (x_offset as f64 + 0.5 - x) / width;
-//             for current_z in max_z..z_start {
-//                 let z_offset = chunk_pos::start_block_z(chunk_pos) + current_z;
-//                 let z_offwidth = (z_offset as f64 + 0.5 - z) / width;
-
-//                 if x_offwidth * x_offwidth + z_offwidth * z_offwidth >= 1.0 {
-//                     continue;
-//                 }
-//                 for current_y in init_height..end_height {
-//                     let y_offwidth = (current_y as f64 - 0.5 - y) / height;
-//                     if Self::is_pos_excluded(
-//                         Vector3::new(x_offwidth, y_offwidth, z_offwidth),
-//                         floor_level,
-//                     ) {
-//                         continue;
-//                     }
-//                     mask.set(current_x, current_y, current_z);
-//                 }
-//             }
-//         }
-//     }
-
-//     fn carve_at_point(&self, chunk: &ProtoChunk, pos: Vector3<i32>, min_y: i8, height: u16) {
-//         let state = chunk.get_block_state(&pos);
-
-//         // if state.block_id == block_state!("grass_block").block_id || state.block_id == block_state!("mycelium").block_id {
-//         // TODO
-//         // }
-//         let state = self.get_state(pos, min_y, height);
-//     }
-
-//     fn get_state(&self, pos: Vector3<i32>, min_y: i8, height: u16) -> Option<BlockState> {
-//         if pos.y <= self.lava_level.get_y(min_y, height) as i32 {
-//             return Some(block_state!("lava"));
-//         }
-//         None
-//     }
-
-//     fn is_pos_excluded(scaled: Vector3<f64>, floor_y: f64) -> bool {
-//         if scaled.y <= floor_y {
-//             return true;
-//         }
-//         scaled.x * scaled.x + scaled.y * scaled.y + scaled.z * scaled.z >= 1.0
-//     }
-
-//     fn get_tunnel_width(random: &mut RandomGenerator) -> f32 {
-//         let mut width = random.next_f32() * 2.0 + random.next_f32();
-//         if random.next_bounded_i32(10) == 0 {
-//             width *= random.next_f32() * random.next_f32() * 3.0 + 1.0;
-//         }
-//         width
-//     }
-// }
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use pumpkin_data::{Block, tag::Tagable};
+use pumpkin_util::{
+    math::{float_provider::FloatProvider, vector2::Vector2, vector3::Vector3},
+    random::{RandomGenerator, RandomImpl, legacy_rand::LegacyRand},
+};
+use serde::Deserialize;
+
+use crate::{
+    ProtoChunk,
+    generation::{
+        height_limit::HeightLimitView, height_provider::HeightProvider, positions::chunk_pos,
+        section_coords, y_offset::YOffset,
+    },
+};
+
+use super::mask::CarvingMask;
+
+const BRANCH_FACTOR: i32 = 4;
+const MAX_CAVE_COUNT: i32 = 15;
+/// Carving stops this many blocks below the top of the world.
+const TOP_MARGIN: i32 = 7;
+
+/// The vanilla cave carver, which digs winding tunnels and the occasional round room through the
+/// terrain. The large "cheese" and "spaghetti" caves aren't carved, they are part of the noise.
+#[derive(Deserialize)]
+pub struct CaveCarver {
+    probability: f32,
+    y: HeightProvider,
+    #[serde(rename = "yScale")]
+    y_scale: FloatProvider,
+    lava_level: YOffset,
+    /// The block tag of the blocks this carver may remove.
+    replaceable: String,
+    horizontal_radius_multiplier: FloatProvider,
+    vertical_radius_multiplier: FloatProvider,
+    floor_level: FloatProvider,
+}
+
+/// The scale of all tunnels and rooms of a cave system.
+#[derive(Clone, Copy)]
+struct CaveShape {
+    horizontal_scale: f64,
+    vertical_scale: f64,
+    /// Blocks below this relative height of a tunnel are left as its floor.
+    floor_level: f64,
+}
+
+#[derive(Clone, Copy)]
+struct Tunnel {
+    pos: Vector3<f64>,
+    yaw: f32,
+    pitch: f32,
+    width: f32,
+    branch_index: i32,
+    branch_count: i32,
+    height_width_ratio: f64,
+}
+
+impl CaveCarver {
+    pub fn should_carve(&self, random: &mut RandomGenerator) -> bool {
+        random.next_f32() <= self.probability
+    }
+
+    pub fn carve(
+        &self,
+        chunk: &mut ProtoChunk,
+        mask: &mut CarvingMask,
+        random: &mut RandomGenerator,
+        origin: &Vector2<i32>,
+    ) {
+        let max_branch_count = section_coords::section_to_block(BRANCH_FACTOR * 2 - 1);
+        let cave_count = random.next_bounded_i32(MAX_CAVE_COUNT) + 1;
+        let cave_count = random.next_bounded_i32(cave_count) + 1;
+        let cave_count = random.next_bounded_i32(cave_count);
+        for _ in 0..cave_count {
+            let pos = Vector3::new(
+                f64::from(chunk_pos::start_block_x(origin) + random.next_bounded_i32(16)),
+                f64::from(self.y.get(random, chunk.bottom_y(), chunk.height())),
+                f64::from(chunk_pos::start_block_z(origin) + random.next_bounded_i32(16)),
+            );
+            let shape = CaveShape {
+                horizontal_scale: f64::from(self.horizontal_radius_multiplier.get(random)),
+                vertical_scale: f64::from(self.vertical_radius_multiplier.get(random)),
+                floor_level: f64::from(self.floor_level.get(random)),
+            };
+
+            let mut tunnel_count = 1;
+            if random.next_bounded_i32(4) == 0 {
+                let y_scale = f64::from(self.y_scale.get(random));
+                let width = 1.0 + random.next_f32() * 6.0;
+                self.carve_room(chunk, mask, &shape, pos, width, y_scale);
+                tunnel_count += random.next_bounded_i32(4);
+            }
+
+            for _ in 0..tunnel_count {
+                let yaw = random.next_f32() * TAU;
+                let pitch = (random.next_f32() - 0.5) / 4.0;
+                let width = Self::tunnel_width(random);
+                let branch_count = max_branch_count - random.next_bounded_i32(max_branch_count / 4);
+                let seed = random.next_i64();
+                self.carve_tunnel(
+                    chunk,
+                    mask,
+                    &shape,
+                    seed,
+                    Tunnel {
+                        pos,
+                        yaw,
+                        pitch,
+                        width,
+                        branch_index: 0,
+                        branch_count,
+                        height_width_ratio: 1.0,
+                    },
+                );
+            }
+        }
+    }
+
+    fn tunnel_width(random: &mut RandomGenerator) -> f32 {
+        let mut width = random.next_f32() * 2.0 + random.next_f32();
+        if random.next_bounded_i32(10) == 0 {
+            width *= random.next_f32() * random.next_f32() * 3.0 + 1.0;
+        }
+        width
+    }
+
+    fn carve_room(
+        &self,
+        chunk: &mut ProtoChunk,
+        mask: &mut CarvingMask,
+        shape: &CaveShape,
+        pos: Vector3<f64>,
+        width: f32,
+        y_scale: f64,
+    ) {
+        let width = 1.5 + f64::from(FRAC_PI_2.sin() * width);
+        let height = width * y_scale;
+        let center = Vector3::new(pos.x + 1.0, pos.y, pos.z);
+        self.carve_region(chunk, mask, shape, center, width, height);
+    }
+
+    fn carve_tunnel(
+        &self,
+        chunk: &mut ProtoChunk,
+        mask: &mut CarvingMask,
+        shape: &CaveShape,
+        seed: i64,
+        mut tunnel: Tunnel,
+    ) {
+        let mut random = RandomGenerator::Legacy(LegacyRand::from_seed(seed as u64));
+        let split_index =
+            random.next_bounded_i32(tunnel.branch_count / 2) + tunnel.branch_count / 4;
+        let steep = random.next_bounded_i32(6) == 0;
+        let mut yaw_change = 0.0f32;
+        let mut pitch_change = 0.0f32;
+
+        for index in tunnel.branch_index..tunnel.branch_count {
+            let horizontal_radius = 1.5
+                + f64::from((PI * index as f32 / tunnel.branch_count as f32).sin() * tunnel.width);
+            let vertical_radius = horizontal_radius * tunnel.height_width_ratio;
+
+            let pitch_cos = tunnel.pitch.cos();
+            tunnel.pos.x += f64::from(tunnel.yaw.cos() * pitch_cos);
+            tunnel.pos.y += f64::from(tunnel.pitch.sin());
+            tunnel.pos.z += f64::from(tunnel.yaw.sin() * pitch_cos);
+            tunnel.pitch *= if steep { 0.92 } else { 0.7 };
+            tunnel.pitch += pitch_change * 0.1;
+            tunnel.yaw += yaw_change * 0.1;
+            pitch_change *= 0.9;
+            yaw_change *= 0.75;
+            pitch_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 2.0;
+            yaw_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 4.0;
+
+            if index == split_index && tunnel.width > 1.0 {
+                // The tunnel forks into two thinner ones going left and right
+                for turn in [-FRAC_PI_2, FRAC_PI_2] {
+                    let seed = random.next_i64();
+                    let width = random.next_f32() * 0.5 + 0.5;
+                    self.carve_tunnel(
+                        chunk,
+                        mask,
+                        shape,
+                        seed,
+                        Tunnel {
+                            yaw: tunnel.yaw + turn,
+                            pitch: tunnel.pitch / 3.0,
+                            width,
+                            branch_index: index,
+                            height_width_ratio: 1.0,
+                            ..tunnel
+                        },
+                    );
+                }
+                return;
+            }
+
+            if random.next_bounded_i32(4) == 0 {
+                continue;
+            }
+            if !Self::can_reach_chunk(&chunk.chunk_pos, &tunnel, index) {
+                return;
+            }
+            self.carve_region(
+                chunk,
+                mask,
+                shape,
+                tunnel.pos,
+                horizontal_radius * shape.horizontal_scale,
+                vertical_radius * shape.vertical_scale,
+            );
+        }
+    }
+
+    /// Whether the rest of the tunnel can still reach the chunk.
+    fn can_reach_chunk(chunk_pos: &Vector2<i32>, tunnel: &Tunnel, branch_index: i32) -> bool {
+        let distance_x = tunnel.pos.x - f64::from(chunk_pos::start_block_x(chunk_pos) + 8);
+        let distance_z = tunnel.pos.z - f64::from(chunk_pos::start_block_z(chunk_pos) + 8);
+        let remaining = f64::from(tunnel.branch_count - branch_index);
+        let max_distance = f64::from(tunnel.width + 2.0 + 16.0);
+        distance_x * distance_x + distance_z * distance_z - remaining * remaining
+            <= max_distance * max_distance
+    }
+
+    /// Carves the ellipsoid around `center` with the given radii out of the chunk.
+    fn carve_region(
+        &self,
+        chunk: &mut ProtoChunk,
+        mask: &mut CarvingMask,
+        shape: &CaveShape,
+        center: Vector3<f64>,
+        width: f64,
+        height: f64,
+    ) {
+        let start_x = chunk_pos::start_block_x(&chunk.chunk_pos);
+        let start_z = chunk_pos::start_block_z(&chunk.chunk_pos);
+        let max_distance = 16.0 + width * 2.0;
+        if (center.x - f64::from(start_x + 8)).abs() > max_distance
+            || (center.z - f64::from(start_z + 8)).abs() > max_distance
+        {
+            return;
+        }
+
+        let bottom_y = i32::from(chunk.bottom_y());
+        let min_x = ((center.x - width).floor() as i32 - start_x - 1).max(0);
+        let max_x = ((center.x + width).floor() as i32 - start_x).min(15);
+        let min_y = ((center.y - height).floor() as i32 - 1).max(bottom_y + 1);
+        let max_y = ((center.y + height).floor() as i32 + 1)
+            .min(bottom_y + i32::from(chunk.height()) - 1 - TOP_MARGIN);
+        let min_z = ((center.z - width).floor() as i32 - start_z - 1).max(0);
+        let max_z = ((center.z + width).floor() as i32 - start_z).min(15);
+
+        for local_x in min_x..=max_x {
+            let x = start_x + local_x;
+            let relative_x = (f64::from(x) + 0.5 - center.x) / width;
+            for local_z in min_z..=max_z {
+                let z = start_z + local_z;
+                let relative_z = (f64::from(z) + 0.5 - center.z) / width;
+                if relative_x * relative_x + relative_z * relative_z >= 1.0 {
+                    continue;
+                }
+
+                let mut removed_top_block = None;
+                for y in (min_y + 1..=max_y).rev() {
+                    let relative_y = (f64::from(y) - 0.5 - center.y) / height;
+                    if relative_y <= shape.floor_level
+                        || relative_x * relative_x
+                            + relative_y * relative_y
+                            + relative_z * relative_z
+                            >= 1.0
+                        || mask.get(local_x, y, local_z)
+                    {
+                        continue;
+                    }
+                    mask.set(local_x, y, local_z);
+                    self.carve_at(chunk, &Vector3::new(x, y, z), &mut removed_top_block);
+                }
+            }
+        }
+    }
+
+    fn carve_at(
+        &self,
+        chunk: &mut ProtoChunk,
+        pos: &Vector3<i32>,
+        removed_top_block: &mut Option<&'static Block>,
+    ) {
+        let block = chunk.get_block_state(pos).to_block();
+        if block == &Block::GRASS_BLOCK || block == &Block::MYCELIUM {
+            *removed_top_block = Some(block);
+        }
+        let replaceable = self
+            .replaceable
+            .strip_prefix('#')
+            .unwrap_or(&self.replaceable);
+        if !block.is_tagged_with(replaceable).unwrap_or(false) {
+            return;
+        }
+
+        let state = if pos.y <= i32::from(self.lava_level.get_y(chunk.bottom_y(), chunk.height())) {
+            Block::LAVA.default_state
+        } else {
+            // TODO: Ask the aquifer like vanilla does, until then keep the walls of any water or
+            // lava next to the cave so it doesn't drain into it
+            if Self::touches_fluid(chunk, pos) {
+                return;
+            }
+            Block::CAVE_AIR.default_state
+        };
+        chunk.set_block_state(pos, state);
+
+        // Cut off grass would leave dirt at the surface, so grow it back on the block below
+        if let Some(top_block) = removed_top_block {
+            let below = Vector3::new(pos.x, pos.y - 1, pos.z);
+            if chunk.get_block_state(&below).to_block() == &Block::DIRT {
+                chunk.set_block_state(&below, top_block.default_state);
+            }
+        }
+    }
+
+    fn touches_fluid(chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+        [
+            Vector3::new(pos.x, pos.y + 1, pos.z),
+            Vector3::new(pos.x - 1, pos.y, pos.z),
+            Vector3::new(pos.x + 1, pos.y, pos.z),
+            Vector3::new(pos.x, pos.y, pos.z - 1),
+            Vector3::new(pos.x, pos.y, pos.z + 1),
+        ]
+        .iter()
+        .any(|neighbor| chunk.get_block_state(neighbor).to_state().is_liquid())
+    }
+}
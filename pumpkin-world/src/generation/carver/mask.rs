@@ -1,29 +1,28 @@
-// use pumpkin_util::math::vector3::Vector3;
+/// Which blocks of a chunk were carved already, so overlapping caves don't carve a block twice.
+pub struct CarvingMask {
+    min_y: i32,
+    mask: Box<[bool]>,
+}
 
-// pub struct CarvingMask {
-//     min_y: i8,
-//     mask: Vec<Vector3<i32>>,
-// }
+impl CarvingMask {
+    pub fn new(min_y: i32, height: usize) -> Self {
+        Self {
+            min_y,
+            mask: vec![false; 256 * height].into_boxed_slice(),
+        }
+    }
 
-// impl CarvingMask {
-//     pub fn new(height: i16, min_y: i8) -> Self {
-//         Self {
-//             min_y,
-//             mask: vec![Vector3::new(0, 0, 0); 256 * height as usize],
-//         }
-//     }
+    fn get_index(&self, local_x: i32, y: i32, local_z: i32) -> usize {
+        (local_x & 0xF | (local_z & 0xF) << 4 | (y - self.min_y) << 8) as usize
+    }
 
-//     fn get_index(&self, offset_x: i32, y: i32, offset_z: i32) -> usize {
-//         (offset_x & 0xF | (offset_z & 0xF) << 4 | (y - self.min_y as i32) << 8) as usize
-//     }
+    pub fn set(&mut self, local_x: i32, y: i32, local_z: i32) {
+        let index = self.get_index(local_x, y, local_z);
+        self.mask[index] = true;
+    }
 
-//     pub fn set(&mut self, offset_x: i32, y: i32, offset_z: i32) {
-//         let index = self.get_index(offset_x, y, offset_z);
-//         self.mask[index] = Vector3::new(offset_x, y, offset_z)
-//     }
-
-//     pub fn get(&mut self, offset_x: i32, y: i32, offset_z: i32) -> bool {
-//         let index = self.get_index(offset_x, y, offset_z);
-//         self.mask.get(index).is_some()
-//     }
-// }
+    pub fn get(&self, local_x: i32, y: i32, local_z: i32) -> bool {
+        let index = self.get_index(local_x, y, local_z);
+        self.mask[index]
+    }
+}
@@ -1,2 +1,68 @@
-mod cave;
-mod mask;
+use std::{collections::HashMap, sync::LazyLock};
+
+use pumpkin_util::{
+    math::vector2::Vector2,
+    random::{RandomGenerator, RandomImpl, legacy_rand::LegacyRand},
+};
+use serde::{Deserialize, de::IgnoredAny};
+
+use crate::ProtoChunk;
+
+use cave::CaveCarver;
+use mask::CarvingMask;
+
+pub mod cave;
+pub mod mask;
+
+/// How many chunks away from a chunk the carvers that can reach into it start.
+pub const CARVER_RANGE: i32 = 8;
+
+pub static CONFIGURED_CARVERS: LazyLock<HashMap<String, ConfiguredCarver>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../../assets/carver.json"))
+        .expect("Could not parse carver.json registry.")
+});
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum ConfiguredCarver {
+    #[serde(rename = "minecraft:cave")]
+    Cave(CaveCarver),
+    // TODO: Carve these
+    #[serde(rename = "minecraft:nether_cave")]
+    NetherCave(IgnoredAny),
+    #[serde(rename = "minecraft:canyon")]
+    Canyon(IgnoredAny),
+}
+
+impl ConfiguredCarver {
+    /// Carves the parts of the caves starting in the chunk at `origin` that reach into `chunk`.
+    pub fn carve(
+        &self,
+        chunk: &mut ProtoChunk,
+        mask: &mut CarvingMask,
+        random: &mut RandomGenerator,
+        origin: &Vector2<i32>,
+    ) {
+        match self {
+            ConfiguredCarver::Cave(carver) => {
+                if carver.should_carve(random) {
+                    carver.carve(chunk, mask, random, origin);
+                }
+            }
+            ConfiguredCarver::NetherCave(_) | ConfiguredCarver::Canyon(_) => {}
+        }
+    }
+}
+
+/// The random of a carver starting in the chunk at `origin`, vanilla's `setCarverSeed`.
+///
+/// `seed` is the world seed plus the index of the carver in its biome.
+pub fn carver_random(seed: i64, origin: &Vector2<i32>) -> RandomGenerator {
+    let mut random = LegacyRand::from_seed(seed as u64);
+    let x_factor = random.next_i64();
+    let z_factor = random.next_i64();
+    let chunk_seed = i64::from(origin.x).wrapping_mul(x_factor)
+        ^ i64::from(origin.y).wrapping_mul(z_factor)
+        ^ seed;
+    RandomGenerator::Legacy(LegacyRand::from_seed(chunk_seed as u64))
+}
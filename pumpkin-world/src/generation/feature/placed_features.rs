@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use pumpkin_data::{Block, BlockDirection};
+use pumpkin_data::{Block, BlockDirection, chunk::Biome};
 use pumpkin_util::HeightMap;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -29,6 +29,26 @@ pub static PLACED_FEATURES: LazyLock<HashMap<String, PlacedFeature>> = LazyLock:
         .expect("Could not parse placed_feature.json registry.")
 });
 
+/// The placed features of all biomes for each generation step, without the namespace. A
+/// feature's index in its step seeds its random, like vanilla's feature sorter does.
+pub static FEATURE_ORDER: LazyLock<Vec<Vec<&'static str>>> = LazyLock::new(|| {
+    let mut order: Vec<Vec<&'static str>> = Vec::new();
+    for biome in (0..=u8::MAX).filter_map(Biome::from_id) {
+        for (step, features) in biome.features.iter().enumerate() {
+            if order.len() <= step {
+                order.resize_with(step + 1, Vec::new);
+            }
+            for feature in *features {
+                let feature = feature.strip_prefix("minecraft:").unwrap_or(feature);
+                if !order[step].contains(&feature) {
+                    order[step].push(feature);
+                }
+            }
+        }
+    }
+    order
+});
+
 #[derive(Deserialize)]
 #[serde(untagged)]
 pub enum PlacedFeatureWrapper {
@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use dashmap::DashMap;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+use serde::{Deserialize, Serialize};
+
+use crate::BlockStateId;
+
+/// The file in the level folder the queued blocks are saved to
+const FEATURE_QUEUE_FILE_NAME: &str = "pending_features.dat";
+
+/// The blocks queued for one chunk by the chunk whose features placed them. They are applied
+/// sorted by that chunk, so overlapping features end up the same no matter which chunk generated
+/// first.
+type QueuedBlocks = BTreeMap<(i32, i32), Vec<(BlockPos, BlockStateId)>>;
+
+/// Blocks which features of generated chunks placed into neighbouring chunks, waiting for those
+/// chunks to be loaded.
+///
+/// A chunk next to the explored area may not be loaded for a long time, so the queue is saved
+/// with the level instead of being dropped.
+#[derive(Default)]
+pub struct FeatureBlockQueue {
+    chunks: DashMap<Vector2<i32>, QueuedBlocks>,
+    /// Whether the queue changed since it was last written
+    dirty: AtomicBool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct SavedQueue {
+    chunks: Vec<SavedChunk>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedChunk {
+    x: i32,
+    z: i32,
+    sources: Vec<SavedSource>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedSource {
+    x: i32,
+    z: i32,
+    positions: Vec<BlockPos>,
+    states: Vec<i32>,
+}
+
+impl FeatureBlockQueue {
+    /// Reads the queue saved in the level folder, or starts an empty one if there is none.
+    pub fn read(level_folder: &Path) -> Self {
+        let path = level_folder.join(FEATURE_QUEUE_FILE_NAME);
+        if !path.exists() {
+            return Self::default();
+        }
+        let saved = OpenOptions::new()
+            .read(true)
+            .open(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|file| {
+                pumpkin_nbt::from_bytes::<SavedQueue>(GzDecoder::new(file))
+                    .map_err(|err| err.to_string())
+            });
+        match saved {
+            Ok(saved) => Self::from_saved(saved),
+            Err(err) => {
+                log::error!("Failed to read the queued feature blocks from {path:?}: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    fn from_saved(saved: SavedQueue) -> Self {
+        let chunks = saved
+            .chunks
+            .into_iter()
+            .map(|chunk| {
+                let blocks = chunk
+                    .sources
+                    .into_iter()
+                    .map(|source| {
+                        let blocks = source
+                            .positions
+                            .into_iter()
+                            .zip(source.states)
+                            .map(|(pos, state)| (pos, state as BlockStateId))
+                            .collect();
+                        ((source.x, source.z), blocks)
+                    })
+                    .collect();
+                (Vector2::new(chunk.x, chunk.z), blocks)
+            })
+            .collect();
+        Self {
+            chunks,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    fn to_saved(&self) -> SavedQueue {
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|entry| SavedChunk {
+                x: entry.key().x,
+                z: entry.key().y,
+                sources: entry
+                    .value()
+                    .iter()
+                    .map(|(&(x, z), blocks)| SavedSource {
+                        x,
+                        z,
+                        positions: blocks.iter().map(|(pos, _)| *pos).collect(),
+                        states: blocks.iter().map(|(_, state)| i32::from(*state)).collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        SavedQueue { chunks }
+    }
+
+    /// Writes the queue to the level folder if it changed since the last write.
+    pub fn write(&self, level_folder: &Path) -> io::Result<()> {
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let file = OpenOptions::new()
+            .truncate(true)
+            .create(true)
+            .write(true)
+            .open(level_folder.join(FEATURE_QUEUE_FILE_NAME))?;
+        let result = pumpkin_nbt::to_bytes(
+            &self.to_saved(),
+            GzEncoder::new(file, Compression::default()),
+        )
+        .map_err(|err| io::Error::other(err.to_string()));
+        if result.is_err() {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Queues blocks which the features of the `source` chunk placed outside of it.
+    pub fn queue(&self, source: Vector2<i32>, blocks: Vec<(BlockPos, BlockStateId)>) {
+        if blocks.is_empty() {
+            return;
+        }
+        for (pos, state_id) in blocks {
+            let (chunk, _) = pos.chunk_and_chunk_relative_position();
+            self.chunks
+                .entry(chunk)
+                .or_default()
+                .entry((source.x, source.y))
+                .or_default()
+                .push((pos, state_id));
+        }
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn contains(&self, chunk: &Vector2<i32>) -> bool {
+        self.chunks.contains_key(chunk)
+    }
+
+    /// Removes the blocks queued for a chunk, in the order they have to be set.
+    pub fn take(&self, chunk: &Vector2<i32>) -> Option<Vec<(BlockPos, BlockStateId)>> {
+        let (_, blocks) = self.chunks.remove(chunk)?;
+        self.dirty.store(true, Ordering::Relaxed);
+        Some(blocks.into_values().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+    use temp_dir::TempDir;
+
+    use super::FeatureBlockQueue;
+
+    #[test]
+    fn queued_blocks_survive_a_restart() {
+        let folder = TempDir::new().unwrap();
+        let queue = FeatureBlockQueue::default();
+        // Blocks of the chunk to the east are set after those of the chunk to the west
+        queue.queue(Vector2::new(1, 0), vec![(BlockPos::new(-1, 70, 3), 2)]);
+        queue.queue(Vector2::new(-1, 0), vec![(BlockPos::new(-15, 64, 0), 1)]);
+        queue.write(folder.path()).unwrap();
+
+        let queue = FeatureBlockQueue::read(folder.path());
+        assert!(queue.contains(&Vector2::new(-1, 0)));
+        assert_eq!(
+            queue.take(&Vector2::new(-1, 0)),
+            Some(vec![
+                (BlockPos::new(-15, 64, 0), 1),
+                (BlockPos::new(-1, 70, 3), 2)
+            ])
+        );
+        assert!(!queue.contains(&Vector2::new(-1, 0)));
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::{Arc, LazyLock};
+
+use pumpkin_data::{
+    Block,
+    block_properties::{GLOBAL_PALETTE_CACHE, get_state_by_state_id},
+    chunk::{Biome, ChunkStatus},
+};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+
+use super::{GeneratorInit, WorldGenerator};
+use crate::{
+    BlockStateId,
+    chunk::{
+        ChunkData, ChunkLight, ChunkSections, SubChunk,
+        format::LightContainer,
+        palette::{BiomePalette, BlockPalette},
+    },
+    dimension::Dimension,
+    generation::{
+        Seed,
+        positions::chunk_pos::{start_block_x, start_block_z},
+        settings::{GenerationSettings, gen_settings_from_dimension},
+    },
+    level::Level,
+    world::BlockRegistryExt,
+};
+
+/// The height the block states are placed at.
+pub const STATES_HEIGHT: i32 = 70;
+/// The height of the barrier floor below the block states.
+pub const BARRIER_HEIGHT: i32 = 60;
+
+/// How many block states there are in each row of the grid.
+static GRID_WIDTH: LazyLock<i32> =
+    LazyLock::new(|| (GLOBAL_PALETTE_CACHE.len() as f32).sqrt().ceil() as i32);
+/// How many rows the grid has.
+static GRID_HEIGHT: LazyLock<i32> =
+    LazyLock::new(|| (GLOBAL_PALETTE_CACHE.len() as f32 / *GRID_WIDTH as f32).ceil() as i32);
+
+/// Generates vanilla's debug world: every block state on a grid at y 70, one block apart, above
+/// a barrier floor at y 60.
+pub struct DebugChunkGenerator {
+    generation_settings: &'static GenerationSettings,
+}
+
+impl GeneratorInit for DebugChunkGenerator {
+    fn new(_seed: Seed, dimension: Dimension) -> Self {
+        Self::with_settings(gen_settings_from_dimension(&dimension))
+    }
+}
+
+impl DebugChunkGenerator {
+    /// Creates a generator that builds chunks with the height of the generation settings.
+    pub fn with_settings(generation_settings: &'static GenerationSettings) -> Self {
+        Self {
+            generation_settings,
+        }
+    }
+
+    /// The block state placed at the block column `x`, `z` of the grid, air between the states.
+    pub fn block_state_for(x: i32, z: i32) -> BlockStateId {
+        if x <= 0 || z <= 0 || x % 2 == 0 || z % 2 == 0 {
+            return Block::AIR.default_state.id;
+        }
+        let (x, z) = (x / 2, z / 2);
+        if x > *GRID_WIDTH || z > *GRID_HEIGHT {
+            return Block::AIR.default_state.id;
+        }
+        let index = (x * *GRID_WIDTH + z).unsigned_abs() as usize;
+        if index < GLOBAL_PALETTE_CACHE.len() {
+            get_state_by_state_id(index as BlockStateId).id
+        } else {
+            Block::AIR.default_state.id
+        }
+    }
+}
+
+impl WorldGenerator for DebugChunkGenerator {
+    fn generate_chunk(
+        &self,
+        _level: &Arc<Level>,
+        _block_registry: &dyn BlockRegistryExt,
+        at: &Vector2<i32>,
+    ) -> ChunkData {
+        let shape = &self.generation_settings.shape;
+        let min_y = i32::from(shape.min_y);
+        let sub_chunks = shape.height as usize / BlockPalette::SIZE;
+        let sections = (0..sub_chunks)
+            .map(|_| SubChunk {
+                block_states: BlockPalette::default(),
+                biomes: BiomePalette::Homogeneous(Biome::PLAINS.id),
+            })
+            .collect();
+        let mut sections = ChunkSections::new(sections, min_y);
+
+        let start_x = start_block_x(at);
+        let start_z = start_block_z(at);
+        for z in 0..BlockPalette::SIZE {
+            for x in 0..BlockPalette::SIZE {
+                sections.set_relative_block(
+                    x,
+                    (BARRIER_HEIGHT - min_y) as usize,
+                    z,
+                    Block::BARRIER.default_state.id,
+                );
+                let state = Self::block_state_for(start_x + x as i32, start_z + z as i32);
+                sections.set_relative_block(x, (STATES_HEIGHT - min_y) as usize, z, state);
+            }
+        }
+
+        ChunkData {
+            light_engine: ChunkLight {
+                sky_light: (0..sections.sections.len() + 2)
+                    .map(|_| LightContainer::new_filled(15))
+                    .collect(),
+                block_light: (0..sections.sections.len() + 2)
+                    .map(|_| LightContainer::new_empty(0))
+                    .collect(),
+            },
+            section: sections,
+            heightmap: Default::default(),
+            position: *at,
+            dirty: true,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            structures: None,
+            status: ChunkStatus::Full,
+        }
+    }
+
+    fn place_feature(
+        &self,
+        _level: &Arc<Level>,
+        _block_registry: &dyn BlockRegistryExt,
+        _chunk: &ChunkData,
+        _feature_name: &str,
+        _pos: BlockPos,
+        _seed: u64,
+    ) -> Option<Vec<(BlockPos, BlockStateId)>> {
+        // The debug world has no features
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, block_properties::GLOBAL_PALETTE_CACHE};
+
+    use super::{DebugChunkGenerator, GRID_WIDTH};
+
+    #[test]
+    fn test_block_state_grid() {
+        let air = Block::AIR.default_state.id;
+        // Only odd, positive columns hold block states
+        assert_eq!(DebugChunkGenerator::block_state_for(2, 3), air);
+        assert_eq!(DebugChunkGenerator::block_state_for(-1, 3), air);
+        assert_eq!(DebugChunkGenerator::block_state_for(3, 0), air);
+
+        // The states are laid out in rows of the grid's width along z
+        assert_eq!(DebugChunkGenerator::block_state_for(1, 1), 0);
+        assert_eq!(DebugChunkGenerator::block_state_for(1, 3), 1);
+        assert_eq!(
+            DebugChunkGenerator::block_state_for(3, 1),
+            *GRID_WIDTH as u16
+        );
+        assert_eq!(
+            DebugChunkGenerator::block_state_for(3, 5),
+            *GRID_WIDTH as u16 + 2
+        );
+
+        // Every state is placed somewhere
+        let mut placed = vec![false; GLOBAL_PALETTE_CACHE.len()];
+        for x in (1..=*GRID_WIDTH * 2 + 1).step_by(2) {
+            for z in (1..=*GRID_WIDTH * 2 + 1).step_by(2) {
+                placed[DebugChunkGenerator::block_state_for(x, z) as usize] = true;
+            }
+        }
+        assert!(placed.into_iter().all(|placed| placed));
+    }
+}
@@ -1,15 +1,24 @@
+pub mod debug;
+
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use pumpkin_data::chunk::ChunkStatus;
 use pumpkin_data::noise_router::{
     END_BASE_NOISE_ROUTER, NETHER_BASE_NOISE_ROUTER, OVERWORLD_BASE_NOISE_ROUTER,
 };
-use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_util::random::{RandomGenerator, xoroshiro128::Xoroshiro};
 
 use super::{
-    biome_coords, noise_router::proto_noise_router::ProtoNoiseRouters,
-    settings::gen_settings_from_dimension,
+    biome_coords,
+    feature::configured_features::CONFIGURED_FEATURES,
+    noise_router::proto_noise_router::ProtoNoiseRouters,
+    positions::chunk_pos::{start_block_x, start_block_z},
+    settings::{GenerationSettings, gen_settings_from_dimension},
 };
+use crate::BlockStateId;
+use crate::block::RawBlockState;
 use crate::chunk::format::LightContainer;
 use crate::level::Level;
 use crate::world::BlockRegistryExt;
@@ -28,22 +37,66 @@ pub trait GeneratorInit {
 
 #[async_trait]
 pub trait WorldGenerator: Sync + Send {
+    /// Generates the chunk. Blocks its features place in neighbouring chunks are queued on the
+    /// level, see [`Level::queue_feature_blocks`].
     fn generate_chunk(
         &self,
         level: &Arc<Level>,
         block_registry: &dyn BlockRegistryExt,
         at: &Vector2<i32>,
     ) -> ChunkData;
+
+    /// Runs the generation stages a partially generated chunk, e.g. one from a vanilla world,
+    /// is still missing. Only called for chunks whose status passes
+    /// [`crate::chunk::can_complete_generation`].
+    ///
+    /// By default the chunk is generated again.
+    fn complete_chunk(
+        &self,
+        level: &Arc<Level>,
+        block_registry: &dyn BlockRegistryExt,
+        chunk: &ChunkData,
+    ) -> ChunkData {
+        self.generate_chunk(level, block_registry, &chunk.position)
+    }
+
+    /// Places a single configured feature, like a tree or an ore vein, into an already
+    /// generated chunk.
+    ///
+    /// Returns the blocks changed by the feature, or `None` if there is no feature with
+    /// that name or it could not be placed at the position.
+    fn place_feature(
+        &self,
+        level: &Arc<Level>,
+        block_registry: &dyn BlockRegistryExt,
+        chunk: &ChunkData,
+        feature_name: &str,
+        pos: BlockPos,
+        seed: u64,
+    ) -> Option<Vec<(BlockPos, BlockStateId)>>;
 }
 
 pub struct VanillaGenerator {
     random_config: GlobalRandomConfig,
     base_router: ProtoNoiseRouters,
     dimension: Dimension,
+    generation_settings: &'static GenerationSettings,
 }
 
 impl GeneratorInit for VanillaGenerator {
     fn new(seed: Seed, dimension: Dimension) -> Self {
+        Self::with_settings(seed, dimension, gen_settings_from_dimension(&dimension))
+    }
+}
+
+impl VanillaGenerator {
+    /// Creates a generator for the dimension's terrain and biomes, but with custom generation
+    /// settings, e.g. a different height or sea level.
+    pub fn with_settings(
+        seed: Seed,
+        dimension: Dimension,
+        generation_settings: &'static GenerationSettings,
+    ) -> Self {
         let random_config = GlobalRandomConfig::new(seed.0, false);
         // TODO: The generation settings contains (part of?) the noise routers too; do we keep the separate or
         // use only the generation settings?
@@ -57,19 +110,39 @@ impl GeneratorInit for VanillaGenerator {
             random_config,
             base_router,
             dimension,
+            generation_settings,
         }
     }
-}
 
-#[async_trait]
-impl WorldGenerator for VanillaGenerator {
-    fn generate_chunk(
+    /// Copies the blocks of a chunk into the proto chunk, which also fills its heightmaps.
+    fn copy_blocks(&self, chunk: &ChunkData, proto_chunk: &mut ProtoChunk) {
+        let start_x = start_block_x(&chunk.position);
+        let start_z = start_block_z(&chunk.position);
+        let min_y = self.generation_settings.shape.min_y as i32;
+        let height = self.generation_settings.shape.height as usize;
+        for y in 0..height {
+            for z in 0..BlockPalette::SIZE {
+                for x in 0..BlockPalette::SIZE {
+                    let block = chunk.get_relative_block(x, y, z).unwrap_or_default();
+                    proto_chunk.set_block_state(
+                        &Vector3::new(start_x + x as i32, min_y + y as i32, start_z + z as i32),
+                        RawBlockState(block).to_state(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Runs the generation stages after `status` and turns the proto chunk into a full chunk.
+    fn finish_chunk(
         &self,
         level: &Arc<Level>,
         block_registry: &dyn BlockRegistryExt,
-        at: &Vector2<i32>,
+        mut proto_chunk: ProtoChunk,
+        status: ChunkStatus,
     ) -> ChunkData {
-        let generation_settings = gen_settings_from_dimension(&self.dimension);
+        let generation_settings = self.generation_settings;
+        let at = proto_chunk.chunk_pos;
 
         let height: usize = match self.dimension {
             Dimension::Overworld => 384,
@@ -79,16 +152,20 @@ impl WorldGenerator for VanillaGenerator {
         let sections = (0..sub_chunks).map(|_| SubChunk::default()).collect();
         let mut sections = ChunkSections::new(sections, generation_settings.shape.min_y as i32);
 
-        let mut proto_chunk = ProtoChunk::new(
-            *at,
-            &self.base_router,
-            &self.random_config,
-            generation_settings,
-        );
+        // Biomes only depend on the seed, so they are sampled again even if the chunk had them
         proto_chunk.populate_biomes(self.dimension);
-        proto_chunk.populate_noise();
-        proto_chunk.build_surface();
+        if status < ChunkStatus::Noise {
+            proto_chunk.populate_noise();
+        }
+        if status < ChunkStatus::Surface {
+            proto_chunk.build_surface();
+        }
+        proto_chunk.carve();
         proto_chunk.generate_features(level, block_registry);
+        level.queue_feature_blocks(at, proto_chunk.take_outside_blocks());
+
+        let start_x = start_block_x(&at);
+        let start_z = start_block_z(&at);
 
         for y in 0..biome_coords::from_block(generation_settings.shape.height) {
             for z in 0..BiomePalette::SIZE {
@@ -106,8 +183,11 @@ impl WorldGenerator for VanillaGenerator {
             for z in 0..BlockPalette::SIZE {
                 for x in 0..BlockPalette::SIZE {
                     let absolute_y = generation_settings.shape.min_y as i32 + y as i32;
-                    let block =
-                        proto_chunk.get_block_state(&Vector3::new(x as i32, absolute_y, z as i32));
+                    let block = proto_chunk.get_block_state(&Vector3::new(
+                        start_x + x as i32,
+                        absolute_y,
+                        start_z + z as i32,
+                    ));
                     sections.set_relative_block(x, y as usize, z, block.0);
                 }
             }
@@ -118,16 +198,126 @@ impl WorldGenerator for VanillaGenerator {
                     .map(|_| LightContainer::new_filled(15))
                     .collect(),
                 block_light: (0..sections.sections.len() + 2)
-                    .map(|_| LightContainer::new_empty(15))
+                    .map(|_| LightContainer::new_empty(0))
                     .collect(),
             },
             section: sections,
             heightmap: Default::default(),
-            position: *at,
+            position: at,
             dirty: true,
             block_ticks: Default::default(),
             fluid_ticks: Default::default(),
             block_entities: Default::default(),
+            structures: Some(proto_chunk.structures_nbt()),
+            status: ChunkStatus::Full,
+        }
+    }
+}
+
+#[async_trait]
+impl WorldGenerator for VanillaGenerator {
+    fn generate_chunk(
+        &self,
+        level: &Arc<Level>,
+        block_registry: &dyn BlockRegistryExt,
+        at: &Vector2<i32>,
+    ) -> ChunkData {
+        let proto_chunk = ProtoChunk::new(
+            *at,
+            &self.base_router,
+            &self.random_config,
+            self.generation_settings,
+        );
+        self.finish_chunk(level, block_registry, proto_chunk, ChunkStatus::Empty)
+    }
+
+    fn complete_chunk(
+        &self,
+        level: &Arc<Level>,
+        block_registry: &dyn BlockRegistryExt,
+        chunk: &ChunkData,
+    ) -> ChunkData {
+        let mut proto_chunk = ProtoChunk::new(
+            chunk.position,
+            &self.base_router,
+            &self.random_config,
+            self.generation_settings,
+        );
+        // The noise stage placed the terrain, anything saved before that has none to keep
+        if chunk.status >= ChunkStatus::Noise {
+            self.copy_blocks(chunk, &mut proto_chunk);
+        }
+        self.finish_chunk(level, block_registry, proto_chunk, chunk.status)
+    }
+
+    fn place_feature(
+        &self,
+        level: &Arc<Level>,
+        block_registry: &dyn BlockRegistryExt,
+        chunk: &ChunkData,
+        feature_name: &str,
+        pos: BlockPos,
+        seed: u64,
+    ) -> Option<Vec<(BlockPos, BlockStateId)>> {
+        let feature = CONFIGURED_FEATURES.get(
+            feature_name
+                .strip_prefix("minecraft:")
+                .unwrap_or(feature_name),
+        )?;
+        let generation_settings = self.generation_settings;
+
+        // Features read biomes and blocks from a proto chunk, so rebuild one from the chunk
+        let mut proto_chunk = ProtoChunk::new(
+            chunk.position,
+            &self.base_router,
+            &self.random_config,
+            generation_settings,
+        );
+        proto_chunk.populate_biomes(self.dimension);
+        self.copy_blocks(chunk, &mut proto_chunk);
+
+        let start_x = start_block_x(&chunk.position);
+        let start_z = start_block_z(&chunk.position);
+        let min_y = generation_settings.shape.min_y as i32;
+        let height = generation_settings.shape.height as usize;
+
+        let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(seed));
+        let (bottom_y, noise_height) = (
+            proto_chunk.noise_sampler.min_y(),
+            proto_chunk.noise_sampler.height(),
+        );
+        let placed = feature.generate(
+            &mut proto_chunk,
+            level,
+            block_registry,
+            bottom_y,
+            noise_height,
+            feature_name,
+            &mut random,
+            pos,
+        );
+        if !placed {
+            return None;
+        }
+
+        let mut changes = proto_chunk.take_outside_blocks();
+        for y in 0..height {
+            for z in 0..BlockPalette::SIZE {
+                for x in 0..BlockPalette::SIZE {
+                    let (x, y, z) = (x as i32, min_y + y as i32, z as i32);
+                    let old = chunk
+                        .section
+                        .get_block_absolute_y(x as usize, y, z as usize)
+                        .unwrap_or_default();
+                    let new = proto_chunk
+                        .get_block_state(&Vector3::new(start_x + x, y, start_z + z))
+                        .0;
+                    if old != new {
+                        changes.push((BlockPos::new(start_x + x, y, start_z + z), new));
+                    }
+                }
+            }
         }
+        Some(changes)
     }
 }
@@ -8,6 +8,7 @@ mod block_state_provider;
 pub mod carver;
 pub mod chunk_noise;
 mod feature;
+pub mod feature_queue;
 pub mod height_limit;
 pub mod height_provider;
 pub mod implementation;
@@ -31,12 +32,21 @@ use pumpkin_util::random::{
 pub use seed::Seed;
 
 use crate::dimension::Dimension;
+use settings::GenerationSettings;
 
 pub fn get_world_gen(seed: Seed, dimension: Dimension) -> Box<dyn WorldGenerator> {
     // TODO decide which WorldGenerator to pick based on config.
     Box::new(VanillaGenerator::new(seed, dimension))
 }
 
+pub fn get_world_gen_with_settings(
+    seed: Seed,
+    dimension: Dimension,
+    settings: &'static GenerationSettings,
+) -> Box<dyn WorldGenerator> {
+    Box::new(VanillaGenerator::with_settings(seed, dimension, settings))
+}
+
 #[derive(Getters)]
 pub struct GlobalRandomConfig {
     seed: u64,
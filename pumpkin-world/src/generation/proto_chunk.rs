@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use pumpkin_data::{
@@ -7,6 +10,7 @@ use pumpkin_data::{
     chunk::Biome,
     tag::Tagable,
 };
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::{
     HeightMap,
     math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
@@ -17,7 +21,7 @@ use crate::{
     BlockStateId,
     biome::{BiomeSupplier, MultiNoiseBiomeSupplier, end::TheEndBiomeSupplier, hash_seed},
     block::RawBlockState,
-    chunk::CHUNK_AREA,
+    chunk::{CHUNK_AREA, structure::StructureStart},
     dimension::Dimension,
     generation::{biome, positions::chunk_pos},
     level::Level,
@@ -28,8 +32,9 @@ use super::{
     GlobalRandomConfig,
     aquifer_sampler::{FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl},
     biome_coords,
+    carver::{CARVER_RANGE, CONFIGURED_CARVERS, carver_random, mask::CarvingMask},
     chunk_noise::{CHUNK_DIM, ChunkNoiseGenerator, LAVA_BLOCK, WATER_BLOCK},
-    feature::placed_features::PLACED_FEATURES,
+    feature::placed_features::{FEATURE_ORDER, PLACED_FEATURES},
     height_limit::HeightLimitView,
     noise_router::{
         multi_noise_sampler::{MultiNoiseSampler, MultiNoiseSamplerBuilderOptions},
@@ -121,6 +126,11 @@ pub struct ProtoChunk<'a> {
     flat_ocean_floor_height_map: Box<[i64]>,
     pub flat_motion_blocking_height_map: Box<[i64]>,
     pub flat_motion_blocking_no_leaves_height_map: Box<[i64]>,
+    /// Blocks placed by features which reach past the chunk border, like the leaves of a tree
+    /// growing next to it. These are handed to the neighbouring chunks once generation is done.
+    outside_blocks: HashMap<BlockPos, BlockStateId>,
+    /// The structures starting in this chunk, saved with it so `/locate` can find them
+    structure_starts: Vec<StructureStart>,
     // may want to use chunk status
 }
 
@@ -209,6 +219,8 @@ impl<'a> ProtoChunk<'a> {
             flat_ocean_floor_height_map: default_heightmap.clone(),
             flat_motion_blocking_height_map: default_heightmap.clone(),
             flat_motion_blocking_no_leaves_height_map: default_heightmap,
+            outside_blocks: HashMap::new(),
+            structure_starts: Vec::new(),
         }
     }
 
@@ -348,13 +360,20 @@ impl<'a> ProtoChunk<'a> {
         state.is_air()
     }
 
+    fn is_inside(&self, pos: &Vector3<i32>) -> bool {
+        pos.x >> 4 == self.chunk_pos.x && pos.z >> 4 == self.chunk_pos.y
+    }
+
     #[inline]
-    pub fn get_block_state(&self, local_pos: &Vector3<i32>) -> RawBlockState {
-        let local_pos = Vector3::new(
-            local_pos.x & 15,
-            local_pos.y - self.bottom_y() as i32,
-            local_pos.z & 15,
-        );
+    pub fn get_block_state(&self, pos: &Vector3<i32>) -> RawBlockState {
+        if !self.is_inside(pos) {
+            // We don't know the neighbouring chunks, so only features placed by this one are there
+            return self
+                .outside_blocks
+                .get(&BlockPos(*pos))
+                .map_or(RawBlockState::AIR, |id| RawBlockState(*id));
+        }
+        let local_pos = Vector3::new(pos.x & 15, pos.y - self.bottom_y() as i32, pos.z & 15);
         if local_pos.y < 0 || local_pos.y >= self.height() as i32 {
             return RawBlockState::AIR;
         }
@@ -367,6 +386,10 @@ impl<'a> ProtoChunk<'a> {
         if local_pos.y < 0 || local_pos.y >= self.height() as i32 {
             return;
         }
+        if !self.is_inside(pos) {
+            self.outside_blocks.insert(BlockPos(*pos), block_state.id);
+            return;
+        }
         if !block_state.is_air() {
             self.maybe_update_surface_height_map(pos);
         }
@@ -389,6 +412,27 @@ impl<'a> ProtoChunk<'a> {
         self.flat_block_map[index] = block_state.id;
     }
 
+    /// Takes the blocks placed outside of this chunk, which belong to its neighbours.
+    pub fn take_outside_blocks(&mut self) -> Vec<(BlockPos, BlockStateId)> {
+        let mut blocks: Vec<_> = self.outside_blocks.drain().collect();
+        // Map order is random, hand them out in the same order every time
+        blocks.sort_unstable_by_key(|(pos, _)| (pos.0.x, pos.0.z, pos.0.y));
+        blocks
+    }
+
+    /// Marks a structure as starting in this chunk.
+    pub fn add_structure_start(&mut self, id: String) {
+        self.structure_starts.push(StructureStart {
+            id,
+            chunk: self.chunk_pos,
+        });
+    }
+
+    /// The `structures` compound the chunk is saved with.
+    pub fn structures_nbt(&self) -> NbtCompound {
+        StructureStart::to_nbt(&self.structure_starts)
+    }
+
     #[inline]
     pub fn get_biome(&self, global_biome_pos: &Vector3<i32>) -> &'static Biome {
         let local_pos = Vector3::new(
@@ -645,7 +689,7 @@ impl<'a> ProtoChunk<'a> {
                             }
 
                             let state = self
-                                .get_block_state(&Vector3::new(local_x, search_y, local_z))
+                                .get_block_state(&Vector3::new(x, search_y, z))
                                 .to_block();
 
                             // TODO: Is there a better way to check that its not a fluid?
@@ -695,6 +739,95 @@ impl<'a> ProtoChunk<'a> {
         }
     }
 
+    /// Carves caves into the terrain. Carvers starting in chunks up to [`CARVER_RANGE`] chunks
+    /// away can reach into this one, so the caves of all of them are traced.
+    pub fn carve(&mut self) {
+        let biome = self.get_biome(&Vector3::new(
+            biome_coords::from_block(self.start_block_x()),
+            0,
+            biome_coords::from_block(self.start_block_z()),
+        ));
+        // Every biome of a dimension has the same carvers, so this chunk's stand in for the
+        // ones of its neighbours
+        if biome.carvers.is_empty() {
+            return;
+        }
+
+        let seed = self.random_config.seed as i64;
+        let mut mask = CarvingMask::new(self.bottom_y() as i32, self.height() as usize);
+        for offset_x in -CARVER_RANGE..=CARVER_RANGE {
+            for offset_z in -CARVER_RANGE..=CARVER_RANGE {
+                let origin = Vector2::new(self.chunk_pos.x + offset_x, self.chunk_pos.y + offset_z);
+                for (index, name) in biome.carvers.iter().enumerate() {
+                    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+                    let Some(carver) = CONFIGURED_CARVERS.get(name) else {
+                        continue;
+                    };
+                    let mut random = carver_random(seed.wrapping_add(index as i64), &origin);
+                    carver.carve(self, &mut mask, &mut random, &origin);
+                }
+            }
+        }
+
+        for local_x in 0..CHUNK_DIM as i32 {
+            for local_z in 0..CHUNK_DIM as i32 {
+                self.lower_height_maps(local_x, local_z);
+            }
+        }
+    }
+
+    /// Moves the height maps of a column down to its highest matching blocks, as setting a
+    /// block only ever raises them.
+    fn lower_height_maps(&mut self, local_x: i32, local_z: i32) {
+        let index = Self::local_position_to_height_map_index(local_x as usize, local_z as usize);
+        let x = self.start_block_x() + local_x;
+        let z = self.start_block_z() + local_z;
+
+        self.flat_surface_height_map[index] =
+            self.highest_block(x, z, self.flat_surface_height_map[index], |state| {
+                !state.is_air()
+            });
+        self.flat_ocean_floor_height_map[index] =
+            self.highest_block(x, z, self.flat_ocean_floor_height_map[index], |state| {
+                blocks_movement(state)
+            });
+        self.flat_motion_blocking_height_map[index] =
+            self.highest_block(x, z, self.flat_motion_blocking_height_map[index], |state| {
+                blocks_movement(state) || state.is_liquid()
+            });
+        self.flat_motion_blocking_no_leaves_height_map[index] = self.highest_block(
+            x,
+            z,
+            self.flat_motion_blocking_no_leaves_height_map[index],
+            |state| {
+                (blocks_movement(state) || state.is_liquid())
+                    && !get_block_by_state_id(state.id)
+                        .is_tagged_with("minecraft:leaves")
+                        .unwrap()
+            },
+        );
+    }
+
+    fn highest_block(
+        &self,
+        x: i32,
+        z: i32,
+        from: i64,
+        predicate: impl Fn(&BlockState) -> bool,
+    ) -> i64 {
+        let mut y = from;
+        while y >= self.bottom_y() as i64 {
+            if predicate(
+                self.get_block_state(&Vector3::new(x, y as i32, z))
+                    .to_state(),
+            ) {
+                return y;
+            }
+            y -= 1;
+        }
+        i64::MIN
+    }
+
     /// This generates "Features," also known as decorations, which include things like trees, grass, ores, and more.
     /// Essentially, it encompasses everything above the surface or underground. It's crucial that this step is executed after biomes are generated,
     /// as the decoration directly depends on the biome. Similarly, running this after the surface is built is logical, as it often involves checking block types.
@@ -705,36 +838,52 @@ impl<'a> ProtoChunk<'a> {
     /// 1. First, we determine **whether** to generate a feature and **at which block positions** to place it.
     /// 2. Then, using the second file, we determine **how** to generate the feature.
     pub fn generate_features(&mut self, level: &Arc<Level>, block_registry: &dyn BlockRegistryExt) {
-        let chunk_pos = self.chunk_pos;
         let min_y = self.noise_sampler.min_y();
         let height = self.noise_sampler.height();
 
-        let bottom_section = section_coords::block_to_section(min_y) as i32;
         let block_pos = BlockPos(Vector3::new(
-            section_coords::section_to_block(chunk_pos.x),
-            bottom_section,
-            section_coords::section_to_block(chunk_pos.y),
+            self.start_block_x(),
+            min_y as i32,
+            self.start_block_z(),
         ));
 
         let population_seed =
             Xoroshiro::get_population_seed(self.random_config.seed, block_pos.0.x, block_pos.0.z);
 
-        // TODO: This needs to be different depending on what biomes are in the chunk -> affects the
-        // random
-        for (name, feature) in PLACED_FEATURES.iter() {
-            // TODO: Properly set index and step
-            let decorator_seed = get_decorator_seed(population_seed, 0, 0);
-            let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(decorator_seed));
-            feature.generate(
-                self,
-                level,
-                block_registry,
-                min_y,
-                height,
-                name,
-                &mut random,
-                block_pos,
-            );
+        let biomes: BTreeSet<u8> = self.flat_biome_map.iter().map(|biome| biome.id).collect();
+        let biomes: Vec<&'static Biome> = biomes.into_iter().filter_map(Biome::from_id).collect();
+
+        // The index of a feature in the global order seeds its random, so the result only depends
+        // on the seed and the chunk, not on which features the neighbours place
+        for (step, features) in FEATURE_ORDER.iter().enumerate() {
+            for (index, name) in features.iter().enumerate() {
+                let in_chunk = biomes.iter().any(|biome| {
+                    biome.features.get(step).is_some_and(|step_features| {
+                        step_features
+                            .iter()
+                            .any(|feature| feature.strip_prefix("minecraft:") == Some(name))
+                    })
+                });
+                if !in_chunk {
+                    continue;
+                }
+                let Some(feature) = PLACED_FEATURES.get(*name) else {
+                    continue;
+                };
+
+                let decorator_seed = get_decorator_seed(population_seed, index, step);
+                let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(decorator_seed));
+                feature.generate(
+                    self,
+                    level,
+                    block_registry,
+                    min_y,
+                    height,
+                    name,
+                    &mut random,
+                    block_pos,
+                );
+            }
         }
     }
 
@@ -781,8 +930,11 @@ impl BlockAccessor for ProtoChunk<'_> {
 mod test {
     use std::sync::LazyLock;
 
-    use pumpkin_data::noise_router::{OVERWORLD_BASE_NOISE_ROUTER, WrapperType};
-    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_data::{
+        Block,
+        noise_router::{OVERWORLD_BASE_NOISE_ROUTER, WrapperType},
+    };
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
 
     use crate::{
         dimension::Dimension,
@@ -1415,4 +1567,66 @@ mod test {
                 }
             });
     }
+
+    #[test]
+    fn test_blocks_outside_chunk() {
+        let surface_config = GENERATION_SETTINGS
+            .get(&GeneratorSetting::Overworld)
+            .unwrap();
+        let mut chunk = ProtoChunk::new(
+            Vector2::new(1, -1),
+            &BASE_NOISE_ROUTER,
+            &RANDOM_CONFIG,
+            surface_config,
+        );
+
+        let stone = Block::STONE.default_state;
+        let inside = Vector3::new(20, 64, -5);
+        let outside = Vector3::new(32, 64, -5);
+        chunk.set_block_state(&inside, stone);
+        chunk.set_block_state(&outside, stone);
+
+        assert_eq!(chunk.get_block_state(&inside).0, stone.id);
+        assert_eq!(chunk.get_block_state(&outside).0, stone.id);
+        // The block outside must not wrap around into this chunk
+        assert!(chunk.is_air(&Vector3::new(16, 64, -5)));
+        assert_eq!(
+            chunk.take_outside_blocks(),
+            vec![(BlockPos(outside), stone.id)]
+        );
+    }
+
+    #[test]
+    fn test_carve() {
+        let surface_config = GENERATION_SETTINGS
+            .get(&GeneratorSetting::Overworld)
+            .unwrap();
+        let mut chunk = ProtoChunk::new(
+            Vector2::new(0, 0),
+            &BASE_NOISE_ROUTER,
+            &RANDOM_CONFIG,
+            surface_config,
+        );
+        chunk.populate_biomes(Dimension::Overworld);
+        chunk.populate_noise();
+        chunk.build_surface();
+        let surface = chunk.flat_block_map.clone();
+
+        chunk.carve();
+
+        let cave_air = Block::CAVE_AIR.default_state.id;
+        let lava = Block::LAVA.default_state.id;
+        let mut carved = 0;
+        for (before, after) in surface.iter().zip(&chunk.flat_block_map) {
+            if before != after {
+                assert!(
+                    [cave_air, lava].contains(after)
+                        || Block::from_state_id(*after) == &Block::GRASS_BLOCK,
+                    "carved into {after}"
+                );
+                carved += 1;
+            }
+        }
+        assert!(carved > 0, "no caves were carved");
+    }
 }
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::item::ItemStack;
+
+use super::{Clearable, Inventory};
+
+// DoubleInventory.java
+/// Two inventories shown as one, like the halves of a double chest. The slots of `first` come
+/// before the ones of `second`.
+#[derive(Debug)]
+pub struct DoubleInventory {
+    first: Arc<dyn Inventory>,
+    second: Arc<dyn Inventory>,
+}
+
+impl DoubleInventory {
+    pub fn new(first: Arc<dyn Inventory>, second: Arc<dyn Inventory>) -> Self {
+        Self { first, second }
+    }
+
+    fn half(&self, slot: usize) -> (&Arc<dyn Inventory>, usize) {
+        let first_size = self.first.size();
+        if slot >= first_size {
+            (&self.second, slot - first_size)
+        } else {
+            (&self.first, slot)
+        }
+    }
+}
+
+#[async_trait]
+impl Inventory for DoubleInventory {
+    fn size(&self) -> usize {
+        self.first.size() + self.second.size()
+    }
+
+    async fn is_empty(&self) -> bool {
+        self.first.is_empty().await && self.second.is_empty().await
+    }
+
+    async fn get_stack(&self, slot: usize) -> Arc<Mutex<ItemStack>> {
+        let (inventory, slot) = self.half(slot);
+        inventory.get_stack(slot).await
+    }
+
+    async fn remove_stack(&self, slot: usize) -> ItemStack {
+        let (inventory, slot) = self.half(slot);
+        inventory.remove_stack(slot).await
+    }
+
+    async fn remove_stack_specific(&self, slot: usize, amount: u8) -> ItemStack {
+        let (inventory, slot) = self.half(slot);
+        inventory.remove_stack_specific(slot, amount).await
+    }
+
+    fn get_max_count_per_stack(&self) -> u8 {
+        self.first.get_max_count_per_stack()
+    }
+
+    async fn set_stack(&self, slot: usize, stack: ItemStack) {
+        let (inventory, slot) = self.half(slot);
+        inventory.set_stack(slot, stack).await;
+    }
+
+    fn mark_dirty(&self) {
+        self.first.mark_dirty();
+        self.second.mark_dirty();
+    }
+
+    fn is_valid_slot_for(&self, slot: usize, stack: &ItemStack) -> bool {
+        let (inventory, slot) = self.half(slot);
+        inventory.is_valid_slot_for(slot, stack)
+    }
+}
+
+#[async_trait]
+impl Clearable for DoubleInventory {
+    async fn clear(&self) {
+        self.first.clear().await;
+        self.second.clear().await;
+    }
+}
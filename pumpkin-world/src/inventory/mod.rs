@@ -4,9 +4,11 @@ use tokio::sync::Mutex;
 
 use crate::item::ItemStack;
 
+mod double_inventory;
 #[allow(clippy::module_inception)]
 mod inventory;
 
+pub use double_inventory::DoubleInventory;
 pub use inventory::*;
 
 // These are some utility functions found in Inventories.java
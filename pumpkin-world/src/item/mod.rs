@@ -1,4 +1,6 @@
+use pumpkin_data::Enchantment;
 use pumpkin_data::item::Item;
+use pumpkin_data::potion::Potion;
 use pumpkin_data::recipes::RecipeResultStruct;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_nbt::compound::NbtCompound;
@@ -17,10 +19,18 @@ pub enum Rarity {
     Epic,
 }
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug)]
 pub struct ItemStack {
     pub item_count: u8,
     pub item: &'static Item,
+    /// The enchantments of this stack and their levels
+    pub enchantments: Vec<(&'static Enchantment, i32)>,
+    /// The durability used up, only meaningful for items with a max damage
+    pub damage: i32,
+    /// The map a filled map shows
+    pub map_id: Option<i32>,
+    /// The potion of the `potion_contents` component, held by potions and tipped arrows
+    pub potion_contents: Option<&'static Potion>,
 }
 
 impl Hash for ItemStack {
@@ -41,10 +51,21 @@ impl ItemStack {
     pub const EMPTY: ItemStack = ItemStack {
         item_count: 0,
         item: &Item::AIR,
+        enchantments: Vec::new(),
+        damage: 0,
+        map_id: None,
+        potion_contents: None,
     };
 
     pub fn new(item_count: u8, item: &'static Item) -> Self {
-        Self { item_count, item }
+        Self {
+            item_count,
+            item,
+            enchantments: Vec::new(),
+            damage: 0,
+            map_id: None,
+            potion_contents: None,
+        }
     }
 
     pub fn get_max_stack_size(&self) -> u8 {
@@ -60,7 +81,34 @@ impl ItemStack {
     }
 
     pub fn is_stackable(&self) -> bool {
-        self.get_max_stack_size() > 1 // TODO: && (!this.isDamageable() || !this.isDamaged());
+        self.get_max_stack_size() > 1 && (!self.is_damageable() || !self.is_damaged())
+    }
+
+    /// Returns the durability of the item, `0` if it can't be damaged.
+    pub fn get_max_damage(&self) -> i32 {
+        self.item.components.max_damage.map_or(0, i32::from)
+    }
+
+    pub fn is_damageable(&self) -> bool {
+        !self.is_empty() && self.get_max_damage() > 0
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.is_damageable() && self.damage > 0
+    }
+
+    /// Uses up `amount` durability, returns `true` if the item broke and was removed.
+    pub fn damage_item(&mut self, amount: i32) -> bool {
+        if !self.is_damageable() || amount <= 0 {
+            return false;
+        }
+        self.damage += amount;
+        if self.damage >= self.get_max_damage() {
+            self.decrement(1);
+            self.damage = 0;
+            return true;
+        }
+        false
     }
 
     pub fn is_empty(&self) -> bool {
@@ -84,7 +132,7 @@ impl ItemStack {
     }
 
     pub fn copy_with_count(&self, count: u8) -> Self {
-        let mut stack = *self;
+        let mut stack = self.clone();
         stack.item_count = count;
         stack
     }
@@ -102,7 +150,32 @@ impl ItemStack {
     }
 
     pub fn are_items_and_components_equal(&self, other: &Self) -> bool {
-        self.item == other.item //TODO: && self.item.components == other.item.components
+        //TODO: Compare the other components
+        self.item == other.item
+            && self.enchantments == other.enchantments
+            && self.damage == other.damage
+            && self.map_id == other.map_id
+            && self.potion_contents == other.potion_contents
+    }
+
+    /// Returns the level of the enchantment, `0` if the stack doesn't have it.
+    pub fn get_enchantment_level(&self, enchantment: &Enchantment) -> i32 {
+        self.enchantments
+            .iter()
+            .find(|(other, _)| other.id == enchantment.id)
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Adds the enchantment, an existing enchantment is only changed if the new level is higher.
+    pub fn add_enchantment(&mut self, enchantment: &'static Enchantment, level: i32) {
+        match self
+            .enchantments
+            .iter_mut()
+            .find(|(other, _)| other.id == enchantment.id)
+        {
+            Some((_, current)) => *current = (*current).max(level),
+            None => self.enchantments.push((enchantment, level)),
+        }
     }
 
     pub fn are_equal(&self, other: &Self) -> bool {
@@ -185,11 +258,31 @@ impl ItemStack {
         compound.put_int("count", self.item_count as i32);
 
         // Create a tag compound for additional data
-        let tag = NbtCompound::new();
+        let mut tag = NbtCompound::new();
+
+        if !self.enchantments.is_empty() {
+            let mut enchantments = NbtCompound::new();
+            for (enchantment, level) in &self.enchantments {
+                enchantments.put_int(enchantment.name, *level);
+            }
+            tag.put_component("minecraft:enchantments", enchantments);
+        }
+
+        if self.damage > 0 {
+            tag.put_int("minecraft:damage", self.damage);
+        }
 
-        // TODO: Store custom data like enchantments, display name, etc. would go here
+        if let Some(map_id) = self.map_id {
+            tag.put_int("minecraft:map_id", map_id);
+        }
+
+        if let Some(potion) = self.potion_contents {
+            let mut potion_contents = NbtCompound::new();
+            potion_contents.put_string("potion", format!("minecraft:{}", potion.name));
+            tag.put_component("minecraft:potion_contents", potion_contents);
+        }
 
-        // Store custom data like enchantments, display name, etc. would go here
+        // TODO: Store custom data like display name, etc. would go here
         compound.put_component("components", tag);
     }
 
@@ -206,11 +299,33 @@ impl ItemStack {
         let count = compound.get_int("count")? as u8;
 
         // Create the item stack
-        let item_stack = Self::new(count, item);
+        let mut item_stack = Self::new(count, item);
 
         // Process any additional data in the components compound
-        if let Some(_tag) = compound.get_compound("components") {
-            // TODO: Process additional components like damage, enchantments, etc.
+        if let Some(tag) = compound.get_compound("components") {
+            let enchantments = tag
+                .get_compound("minecraft:enchantments")
+                .map_or(&[][..], |enchantments| &enchantments.child_tags);
+            for (name, level) in enchantments {
+                if let (Some(enchantment), Some(level)) =
+                    (Enchantment::from_name(name), level.extract_int())
+                {
+                    item_stack.add_enchantment(enchantment, level);
+                }
+            }
+            if let Some(damage) = tag.get_int("minecraft:damage") {
+                item_stack.damage = damage;
+            }
+            item_stack.map_id = tag.get_int("minecraft:map_id");
+            // Vanilla also accepts just the name of the potion instead of the compound
+            let potion = tag
+                .get_compound("minecraft:potion_contents")
+                .and_then(|potion_contents| potion_contents.get_string("potion"))
+                .or_else(|| tag.get_string("minecraft:potion_contents"));
+            item_stack.potion_contents = potion.and_then(|name| {
+                Potion::from_name(name.strip_prefix("minecraft:").unwrap_or(name))
+            });
+            // TODO: Process additional components
         }
 
         Some(item_stack)
@@ -219,10 +334,10 @@ impl ItemStack {
 
 impl From<&RecipeResultStruct> for ItemStack {
     fn from(value: &RecipeResultStruct) -> Self {
-        Self {
-            item_count: value.count,
-            item: Item::from_registry_key(value.id.strip_prefix("minecraft:").unwrap_or(value.id))
+        Self::new(
+            value.count,
+            Item::from_registry_key(value.id.strip_prefix("minecraft:").unwrap_or(value.id))
                 .expect("Crafting recipe gives invalid item"),
-        }
+        )
     }
 }
@@ -1,13 +1,18 @@
 use dashmap::{DashMap, Entry};
 use log::trace;
 use num_traits::Zero;
-use pumpkin_config::{advanced_config, chunk::ChunkFormat};
-use pumpkin_data::{Block, block_properties::has_random_ticks};
+use pumpkin_config::{GeneratorType, advanced_config, chunk::ChunkFormat};
+use pumpkin_data::{
+    Block,
+    block_properties::has_random_ticks,
+    chunk::{Biome, ChunkStatus},
+};
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use rand::{Rng, SeedableRng, rngs::SmallRng};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::{
     collections::HashMap,
+    panic::{self, AssertUnwindSafe},
     path::PathBuf,
     sync::{
         Arc,
@@ -19,6 +24,7 @@ use tokio::{
     sync::{
         Mutex, Notify, RwLock,
         mpsc::{self, UnboundedReceiver},
+        oneshot,
     },
     task::JoinHandle,
 };
@@ -34,7 +40,13 @@ use crate::{
         io::{Dirtiable, FileIO, LoadedData, file_manager::ChunkFileManager},
     },
     dimension::Dimension,
-    generation::{Seed, get_world_gen, implementation::WorldGenerator},
+    generation::{
+        Seed,
+        feature_queue::FeatureBlockQueue,
+        get_world_gen_with_settings,
+        implementation::{WorldGenerator, debug::DebugChunkGenerator},
+        settings::{GenerationSettings, gen_settings_from_dimension},
+    },
     world::BlockRegistryExt,
 };
 
@@ -64,11 +76,26 @@ pub struct Level {
     loaded_entity_chunks: Arc<DashMap<Vector2<i32>, SyncEntityChunk>>,
 
     chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    /// Loaded chunks without watchers and the server tick at which they lost their last watcher.
+    /// They get unloaded once the unload delay has passed.
+    unwatched_chunks: DashMap<Vector2<i32>, i64>,
 
     chunk_saver: Arc<dyn FileIO<Data = SyncChunk>>,
     entity_saver: Arc<dyn FileIO<Data = SyncEntityChunk>>,
 
     world_gen: Arc<dyn WorldGenerator>,
+    /// Whether new chunks are generated as the debug world
+    debug: bool,
+    /// Held while chunks are written to disk, so saves don't overlap
+    save_lock: Mutex<()>,
+    /// Blocks which features of generated chunks placed into neighbouring chunks, waiting for
+    /// those chunks to be loaded
+    pending_feature_blocks: FeatureBlockQueue,
+    /// Loaded chunks which got queued feature blocks set after they may have been sent to
+    /// players, see [`Self::take_changed_chunks`]
+    changed_chunks: Mutex<Vec<Vector2<i32>>>,
+    /// The settings this level is generated with
+    generation_settings: &'static GenerationSettings,
 
     /// Semaphore to limit concurrent chunk generation tasks
     //chunk_generation_semaphore: Arc<Semaphore>,
@@ -103,6 +130,24 @@ impl Level {
         block_registry: Arc<dyn BlockRegistryExt>,
         seed: i64,
         dimension: Dimension,
+    ) -> Self {
+        Self::with_generation_settings(
+            root_folder,
+            block_registry,
+            seed,
+            dimension,
+            gen_settings_from_dimension(&dimension),
+        )
+    }
+
+    /// Like [`Self::from_root_folder`], but generates new chunks with custom settings instead of
+    /// the dimension's vanilla ones.
+    pub fn with_generation_settings(
+        root_folder: PathBuf,
+        block_registry: Arc<dyn BlockRegistryExt>,
+        seed: i64,
+        dimension: Dimension,
+        generation_settings: &'static GenerationSettings,
     ) -> Self {
         // If we are using an already existing world we want to read the seed from the level.dat, If not we want to check if there is a seed in the config, if not lets create a random one
         let region_folder = root_folder.join("region");
@@ -122,7 +167,14 @@ impl Level {
         // TODO: Load info correctly based on world format type
 
         let seed = Seed(seed as u64);
-        let world_gen = get_world_gen(seed, dimension).into();
+        // Like in vanilla, the nether and end of a debug world are generated normally
+        let debug = advanced_config().world.generator == GeneratorType::Debug
+            && dimension == Dimension::Overworld;
+        let world_gen: Arc<dyn WorldGenerator> = if debug {
+            Arc::new(DebugChunkGenerator::with_settings(generation_settings))
+        } else {
+            get_world_gen_with_settings(seed, dimension, generation_settings).into()
+        };
 
         let chunk_saver: Arc<dyn FileIO<Data = SyncChunk>> = match advanced_config().chunk.format {
             ChunkFormat::Linear => Arc::new(ChunkFileManager::<LinearFile<ChunkData>>::default()),
@@ -144,6 +196,11 @@ impl Level {
             seed,
             block_registry,
             world_gen,
+            debug,
+            save_lock: Mutex::new(()),
+            pending_feature_blocks: FeatureBlockQueue::read(&level_folder.root_folder),
+            changed_chunks: Mutex::new(Vec::new()),
+            generation_settings,
             level_folder,
             chunk_saver,
             entity_saver,
@@ -151,6 +208,7 @@ impl Level {
             loaded_chunks: Arc::new(DashMap::new()),
             loaded_entity_chunks: Arc::new(DashMap::new()),
             chunk_watchers: Arc::new(DashMap::new()),
+            unwatched_chunks: DashMap::new(),
             tasks: TaskTracker::new(),
             shutdown_notifier: Notify::new(),
             // Limits concurrent chunk generation tasks to 2x the number of CPUs
@@ -165,6 +223,22 @@ impl Level {
         }
     }
 
+    #[must_use]
+    pub fn level_folder(&self) -> &LevelFolder {
+        &self.level_folder
+    }
+
+    #[must_use]
+    pub fn generation_settings(&self) -> &'static GenerationSettings {
+        self.generation_settings
+    }
+
+    /// Whether this level is vanilla's debug world, filled with all block states.
+    #[must_use]
+    pub fn is_debug(&self) -> bool {
+        self.debug
+    }
+
     /// Spawns a task associated with this world. All tasks spawned with this method are awaited
     /// when the client. This means tasks should complete in a reasonable (no looping) amount of time.
     pub fn spawn_task<F>(&self, task: F) -> JoinHandle<F::Output>
@@ -184,6 +258,9 @@ impl Level {
         self.tasks.wait().await;
         log::debug!("Done awaiting level chunk tasks");
 
+        // wait for an autosave to finish
+        let _save_lock = self.save_lock.lock().await;
+
         // wait for chunks currently saving in other threads
         self.chunk_saver.block_and_await_ongoing_tasks().await;
 
@@ -215,6 +292,58 @@ impl Level {
         // TODO: I think the chunk_saver should be at the server level
         self.entity_saver.clear_watched_chunks().await;
         self.write_entity_chunks(chunks_to_write).await;
+
+        self.write_pending_feature_blocks();
+    }
+
+    /// Writes the dirty chunks to disk while keeping them loaded.
+    ///
+    /// Unless `flush` is set, the chunks are written in batches with a break in between, so the
+    /// chunk locks don't stall the world tick.
+    pub async fn save(&self, flush: bool) {
+        const BATCH_SIZE: usize = 64;
+
+        let _save_lock = self.save_lock.lock().await;
+        log::debug!("Saving level {:?}", self.level_folder.root_folder);
+
+        let chunks = self
+            .loaded_chunks
+            .iter()
+            .filter(|chunk| may_be_dirty(chunk.value()))
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect::<Vec<_>>();
+        for batch in chunks.chunks(BATCH_SIZE) {
+            self.write_chunks(batch.to_vec()).await;
+            if !flush {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        let entity_chunks = self
+            .loaded_entity_chunks
+            .iter()
+            .filter(|chunk| may_be_dirty(chunk.value()))
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect::<Vec<_>>();
+        for batch in entity_chunks.chunks(BATCH_SIZE) {
+            self.write_entity_chunks(batch.to_vec()).await;
+            if !flush {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        if let Err(error) = self.chunk_saver.flush().await {
+            log::error!("Failed writing chunks to disk: {error}");
+        }
+        if let Err(error) = self.entity_saver.flush().await {
+            log::error!("Failed writing entity chunks to disk: {error}");
+        }
+        self.write_pending_feature_blocks();
+        log::debug!(
+            "Saved {} chunks and {} entity chunks",
+            chunks.len(),
+            entity_chunks.len()
+        );
     }
 
     pub fn loaded_chunk_count(&self) -> usize {
@@ -238,6 +367,7 @@ impl Level {
     pub async fn mark_chunks_as_newly_watched(&self, chunks: &[Vector2<i32>]) {
         for chunk in chunks {
             log::trace!("{chunk:?} marked as newly watched");
+            self.unwatched_chunks.remove(chunk);
             match self.chunk_watchers.entry(*chunk) {
                 Entry::Occupied(mut occupied) => {
                     let value = occupied.get_mut();
@@ -396,7 +526,11 @@ impl Level {
     }
 
     // Gets random ticks, block ticks and fluid ticks
-    pub async fn get_tick_data(&self) -> TickData {
+    /// Collects the ticks of all loaded chunks for which `is_simulated` returns true.
+    ///
+    /// The scheduled ticks of other chunks are kept as they are and continue once the chunk gets
+    /// simulated again.
+    pub async fn get_tick_data(&self, is_simulated: impl Fn(&Vector2<i32>) -> bool) -> TickData {
         let mut ticks = TickData {
             block_ticks: Vec::new(),
             fluid_ticks: Vec::new(),
@@ -405,6 +539,9 @@ impl Level {
         };
         let mut rng = SmallRng::from_os_rng();
         for chunk in self.loaded_chunks.iter() {
+            if !is_simulated(chunk.key()) {
+                continue;
+            }
             let mut chunk = chunk.write().await;
             ticks.block_ticks.extend(chunk.get_and_tick_block_ticks());
             ticks.fluid_ticks.extend(chunk.get_and_tick_fluid_ticks());
@@ -474,12 +611,48 @@ impl Level {
         self.chunk_watchers.get(chunk).is_some()
     }
 
+    /// Saves and unloads the chunks which were not watched by any player for at least
+    /// `unload_delay_ticks`. Spawn chunks stay loaded.
+    ///
+    /// `tick` is the server's tick count, which unlike the world age keeps counting while the game
+    /// is frozen. Block and entity chunks are always unloaded together.
+    pub async fn unload_unwatched_chunks(self: &Arc<Self>, tick: i64) {
+        // Chunks can also be loaded without being watched, e.g. by a block change far away
+        let loaded = self.loaded_chunks.iter().map(|chunk| *chunk.key());
+        let loaded_entities = self.loaded_entity_chunks.iter().map(|chunk| *chunk.key());
+        for pos in loaded.chain(loaded_entities) {
+            if !self.is_chunk_watched(&pos) {
+                self.unwatched_chunks.entry(pos).or_insert(tick);
+            }
+        }
+
+        let unload_delay = i64::from(advanced_config().chunk.unload_delay_ticks);
+        let mut chunks_to_unload = Vec::new();
+        self.unwatched_chunks.retain(|pos, unwatched_since| {
+            if self.is_chunk_watched(pos) {
+                return false;
+            }
+            if tick - *unwatched_since < unload_delay {
+                return true;
+            }
+            if !self.spawn_chunks.contains_key(pos) {
+                chunks_to_unload.push(*pos);
+            }
+            false
+        });
+
+        if chunks_to_unload.is_empty() {
+            return;
+        }
+        log::trace!("Unloading {} unwatched chunks", chunks_to_unload.len());
+        self.clean_chunks(&chunks_to_unload).await;
+        self.clean_entity_chunks(&chunks_to_unload).await;
+    }
+
+    /// Removes left over watcher entries. Unwatched chunks are unloaded by
+    /// [`Level::unload_unwatched_chunks`], as they need to be saved first.
     pub fn clean_memory(&self) {
         self.chunk_watchers.retain(|_, watcher| !watcher.is_zero());
-        self.loaded_chunks
-            .retain(|at, _| self.chunk_watchers.get(at).is_some());
-        self.loaded_entity_chunks
-            .retain(|at, _| self.chunk_watchers.get(at).is_some());
 
         // if the difference is too big, we can shrink the loaded chunks
         // (1024 chunks is the equivalent to a 32x32 chunks area)
@@ -588,6 +761,135 @@ impl Level {
             .expect("Channel closed for unknown reason")
     }
 
+    /// Places a configured feature into the chunk at the position.
+    ///
+    /// Returns the changed blocks, which still have to be set in the world.
+    pub async fn place_feature(
+        self: &Arc<Self>,
+        feature_name: &str,
+        pos: BlockPos,
+        seed: u64,
+    ) -> Option<Vec<(BlockPos, BlockStateId)>> {
+        let (chunk_coordinate, _) = pos.chunk_and_chunk_relative_position();
+        let chunk = self.get_chunk(chunk_coordinate).await;
+        let chunk = chunk.read().await;
+        self.world_gen.place_feature(
+            self,
+            self.block_registry.as_ref(),
+            &chunk,
+            feature_name,
+            pos,
+            seed,
+        )
+    }
+
+    /// Queues blocks which the features of the `source` chunk placed outside of it. They are set
+    /// once their chunk is loaded, so a feature is never placed twice.
+    pub fn queue_feature_blocks(
+        &self,
+        source: Vector2<i32>,
+        blocks: Vec<(BlockPos, BlockStateId)>,
+    ) {
+        self.pending_feature_blocks.queue(source, blocks);
+    }
+
+    /// Takes the loaded chunks whose blocks changed because queued feature blocks were set after
+    /// they got loaded. Players which already received them need them sent again.
+    pub async fn take_changed_chunks(&self) -> Vec<Vector2<i32>> {
+        std::mem::take(&mut *self.changed_chunks.lock().await)
+    }
+
+    fn write_pending_feature_blocks(&self) {
+        if let Err(error) = self
+            .pending_feature_blocks
+            .write(&self.level_folder.root_folder)
+        {
+            log::error!("Failed writing the queued feature blocks to disk: {error}");
+        }
+    }
+
+    /// Runs the missing generation stages of a partially generated chunk on the world
+    /// generation pool, e.g. of a chunk imported from a vanilla world.
+    ///
+    /// Returns `false` if the generator failed, the chunk is left as it was then.
+    async fn complete_chunk(self: &Arc<Self>, chunk: &SyncChunk) -> bool {
+        let (send, recv) = oneshot::channel();
+        let level = self.clone();
+        let partial = chunk.clone();
+        self.world_gen_pool.spawn(move || {
+            // A panic would abort the whole pool, so catch it and report the failure instead
+            let completed = panic::catch_unwind(AssertUnwindSafe(|| {
+                level.world_gen.complete_chunk(
+                    &level,
+                    level.block_registry.as_ref(),
+                    &partial.blocking_read(),
+                )
+            }));
+            if let Ok(completed) = completed {
+                let _ = send.send(completed);
+            }
+        });
+        // The sender is dropped without sending if the generator failed
+        let Ok(completed) = recv.await else {
+            return false;
+        };
+        *chunk.write().await = completed;
+        true
+    }
+
+    /// Applies the queued feature blocks to a chunk read from disk and makes it the loaded chunk
+    /// at its position, unless another one got loaded there in the meantime.
+    async fn insert_loaded_chunk(&self, chunk: SyncChunk) -> SyncChunk {
+        let position = {
+            let mut chunk = chunk.write().await;
+            self.apply_feature_blocks(&mut chunk);
+            chunk.position
+        };
+        self.loaded_chunks
+            .entry(position)
+            .or_insert(chunk)
+            .value()
+            .clone()
+    }
+
+    /// Sets the queued feature blocks of the chunk, if there are any.
+    fn apply_feature_blocks(&self, chunk: &mut ChunkData) {
+        let Some(pending) = self.pending_feature_blocks.take(&chunk.position) else {
+            return;
+        };
+        for (pos, state_id) in pending {
+            let (_, relative) = pos.chunk_and_chunk_relative_position();
+            let (x, z) = (relative.x as usize, relative.z as usize);
+            let Some(old) = chunk.section.get_block_absolute_y(x, relative.y, z) else {
+                continue;
+            };
+            // The chunk generated on its own, so only fill in where there is room
+            if RawBlockState(old).to_state().replaceable() {
+                chunk
+                    .section
+                    .set_block_absolute_y(x, relative.y, z, state_id);
+            }
+        }
+        chunk.mark_dirty(true);
+    }
+
+    /// Sets the queued feature blocks of the chunk at the position if it is loaded, and marks it
+    /// to be sent again.
+    async fn apply_loaded_feature_blocks(&self, pos: Vector2<i32>) {
+        if !self.pending_feature_blocks.contains(&pos) {
+            return;
+        }
+        let Some(chunk) = self
+            .loaded_chunks
+            .get(&pos)
+            .map(|chunk| chunk.value().clone())
+        else {
+            return;
+        };
+        self.apply_feature_blocks(&mut *chunk.write().await);
+        self.changed_chunks.lock().await.push(pos);
+    }
+
     pub async fn get_block_state(self: &Arc<Self>, position: &BlockPos) -> RawBlockState {
         let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
         let chunk = self.get_chunk(chunk_coordinate).await;
@@ -604,6 +906,19 @@ impl Level {
         RawBlockState(id)
     }
 
+    /// Gets the biome of the 4x4x4 biome cell the given block lies in.
+    pub async fn get_biome(self: &Arc<Self>, position: &BlockPos) -> &'static Biome {
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.get_chunk(chunk_coordinate).await;
+
+        let chunk = chunk.read().await;
+        chunk
+            .section
+            .get_biome_absolute_y(relative.x as usize, relative.y, relative.z as usize)
+            .and_then(Biome::from_id)
+            .unwrap_or(&Biome::PLAINS)
+    }
+
     pub async fn set_block_state(
         self: &Arc<Self>,
         position: &BlockPos,
@@ -683,6 +998,41 @@ impl Level {
         log::debug!("Read {} chunks as spawn chunks", chunks.len());
     }
 
+    /// Reads the chunks which are loaded or saved to disk. Unlike [`Self::fetch_chunks`], chunks
+    /// which were never generated are skipped, and chunks read from disk are not kept loaded.
+    pub async fn read_saved_chunks(&self, chunks: &[Vector2<i32>]) -> Vec<SyncChunk> {
+        let mut read_chunks = Vec::with_capacity(chunks.len());
+        let mut remaining_chunks = Vec::new();
+        for chunk in chunks {
+            if let Some(loaded) = self.loaded_chunks.get(chunk) {
+                read_chunks.push(loaded.value().clone());
+            } else {
+                remaining_chunks.push(*chunk);
+            }
+        }
+        if remaining_chunks.is_empty() {
+            return read_chunks;
+        }
+
+        let (send, mut recv) = mpsc::channel(16);
+        let fetcher = self
+            .chunk_saver
+            .fetch_chunks(&self.level_folder, &remaining_chunks, send);
+        let handler = async {
+            while let Some(data) = recv.recv().await {
+                match data {
+                    LoadedData::Loaded(chunk) => read_chunks.push(chunk),
+                    LoadedData::Missing(_) => {}
+                    LoadedData::Error((pos, error)) => {
+                        log::debug!("Failed to read chunk at {pos:?}: {error}");
+                    }
+                }
+            }
+        };
+        tokio::join!(fetcher, handler);
+        read_chunks
+    }
+
     /// Reads/Generates many chunks in a world
     /// Note: The order of the output chunks will almost never be in the same order as the order of input chunks
     pub async fn fetch_chunks(
@@ -734,19 +1084,35 @@ impl Level {
         let (generate_bridge_send, mut generate_bridge_recv) = tokio::sync::mpsc::channel(16);
 
         let load_channel = channel.clone();
-        let loaded_chunks = self.loaded_chunks.clone();
+        let level = self.clone();
         let handle_load = async move {
             while let Some(data) = load_bridge_recv.recv().await {
                 let is_ok = match data {
                     LoadedData::Loaded(chunk) => {
-                        let position = chunk.read().await.position;
-
-                        let value = loaded_chunks
-                            .entry(position)
-                            .or_insert(chunk)
-                            .value()
-                            .clone();
-                        send_chunk(false, value, &load_channel)
+                        if chunk.read().await.status == ChunkStatus::Full {
+                            let value = level.insert_loaded_chunk(chunk).await;
+                            send_chunk(false, value, &load_channel)
+                        } else {
+                            // Completing takes as long as generating, so the other chunks don't
+                            // wait for it
+                            let level_clone = level.clone();
+                            let channel = load_channel.clone();
+                            let generate_bridge_send = generate_bridge_send.clone();
+                            level.spawn_task(async move {
+                                if level_clone.complete_chunk(&chunk).await {
+                                    let value = level_clone.insert_loaded_chunk(chunk).await;
+                                    send_chunk(false, value, &channel);
+                                } else {
+                                    // Never hand out the partial chunk, generate it from scratch
+                                    let position = chunk.read().await.position;
+                                    log::error!(
+                                        "Failed to complete chunk at {position:?} (regenerating)"
+                                    );
+                                    let _ = generate_bridge_send.send(position).await;
+                                }
+                            });
+                            true
+                        }
                     }
                     LoadedData::Missing(pos) => generate_bridge_send.send(pos).await.is_ok(),
                     LoadedData::Error((pos, error)) => {
@@ -840,11 +1206,12 @@ impl Level {
                             // Deduplicate chunk generation using chunk_generation_locks
 
                             // We are responsible for generating the chunk
-                            let generated_chunk = world_gen.generate_chunk(
+                            let mut generated_chunk = world_gen.generate_chunk(
                                 &self_clone,
                                 block_registry.as_ref(),
                                 &pos,
                             );
+                            self_clone.apply_feature_blocks(&mut generated_chunk);
                             let arc_chunk = Arc::new(RwLock::new(generated_chunk));
                             loaded_chunks.insert(pos, arc_chunk.clone());
 
@@ -862,6 +1229,20 @@ impl Level {
                                 if let Some(notify) = locks.remove(&pos) {
                                     notify.notify_waiters();
                                 }
+                                drop(locks);
+
+                                // Features of this chunk may have reached into loaded neighbours,
+                                // and neighbours may have queued blocks since we took ours
+                                for x in -1..=1 {
+                                    for z in -1..=1 {
+                                        self_clone
+                                            .apply_loaded_feature_blocks(Vector2::new(
+                                                pos.x + x,
+                                                pos.y + z,
+                                            ))
+                                            .await;
+                                    }
+                                }
                             });
                         }
 
@@ -1049,3 +1430,8 @@ impl Level {
         self.loaded_entity_chunks.try_get(&coordinates).try_unwrap()
     }
 }
+
+/// Chunks which are locked right now are probably being changed, so assume they're dirty.
+fn may_be_dirty<T: Dirtiable>(chunk: &RwLock<T>) -> bool {
+    chunk.try_read().map_or(true, |chunk| chunk.is_dirty())
+}
@@ -77,3 +77,15 @@ pub fn bench_create_and_populate_noise_with_surface(
     chunk.populate_noise();
     chunk.build_surface();
 }
+
+pub fn bench_create_and_populate_noise_with_carvers(
+    base_router: &ProtoNoiseRouters,
+    random_config: &GlobalRandomConfig,
+    settings: &GenerationSettings,
+) {
+    let mut chunk = ProtoChunk::new(Vector2::new(0, 0), base_router, random_config, settings);
+    chunk.populate_biomes(Dimension::Overworld);
+    chunk.populate_noise();
+    chunk.build_surface();
+    chunk.carve();
+}
@@ -231,10 +231,14 @@ mod test {
             world_gen_settings: WorldGenSettings { seed: 1 },
             last_played: 1733847709327,
             level_name: "New World".to_string(),
+            raining: false,
+            rain_time: 80942,
             spawn_x: 160,
             spawn_y: 70,
             spawn_z: 160,
             spawn_angle: 0.0,
+            thundering: false,
+            thunder_time: 74299,
             nbt_version: 19133,
             version: WorldVersion {
                 name: "1.21.4".to_string(),
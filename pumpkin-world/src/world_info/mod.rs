@@ -74,6 +74,12 @@ pub struct LevelData {
     pub last_played: i64,
     // The name of the level.
     pub level_name: String,
+    // true if the level is currently experiencing rain, snow, and cloud cover.
+    #[serde(rename = "raining", default)]
+    pub raining: bool,
+    // The number of ticks before "raining" is toggled and this value gets set to another random value.
+    #[serde(rename = "rainTime", default)]
+    pub rain_time: i32,
     // The X coordinate of the world spawn.
     pub spawn_x: i32,
     // The Y coordinate of the world spawn.
@@ -82,6 +88,12 @@ pub struct LevelData {
     pub spawn_z: i32,
     // The Yaw rotation of the world spawn.
     pub spawn_angle: f32,
+    // true if the rain/snow/cloud cover is a lightning storm and dark enough for mobs to spawn under the sky.
+    #[serde(rename = "thundering", default)]
+    pub thundering: bool,
+    // The number of ticks before "thundering" is toggled and this value gets set to another random value.
+    #[serde(rename = "thunderTime", default)]
+    pub thunder_time: i32,
     #[serde(rename = "version")]
     // The NBT version of the level
     pub nbt_version: i32,
@@ -105,6 +117,39 @@ pub struct DataPacks {
     pub enabled: Vec<String>,
 }
 
+/// The data pack containing the vanilla data (known to the client as `minecraft:core`), it is always enabled.
+pub const CORE_DATA_PACK: &str = "vanilla";
+/// The data packs shipped with the game, mostly experimental features.
+pub const BUILTIN_DATA_PACKS: [&str; 5] = [
+    CORE_DATA_PACK,
+    "bundle",
+    "trade_rebalance",
+    "redstone_experiments",
+    "minecart_improvements",
+];
+
+impl DataPacks {
+    /// Enables the given data packs in addition to the already enabled ones.
+    ///
+    /// Unknown data packs are skipped with a warning. The core data pack is always kept enabled.
+    pub fn enable(&mut self, packs: &[String]) {
+        for pack in packs {
+            if !BUILTIN_DATA_PACKS.contains(&pack.as_str()) {
+                log::warn!("Unknown data pack '{pack}', skipping it");
+                continue;
+            }
+            self.disabled.retain(|disabled| disabled != pack);
+            if !self.enabled.contains(pack) {
+                self.enabled.push(pack.clone());
+            }
+        }
+        self.disabled.retain(|disabled| disabled != CORE_DATA_PACK);
+        if !self.enabled.iter().any(|pack| pack == CORE_DATA_PACK) {
+            self.enabled.insert(0, CORE_DATA_PACK.to_string());
+        }
+    }
+}
+
 fn get_or_create_seed() -> Seed {
     // TODO: if there is a seed in the config (!= "") use it. Otherwise make a random one
     Seed::from(BASIC_CONFIG.seed.as_str())
@@ -158,7 +203,7 @@ impl Default for LevelData {
             clear_weather_time: -1,
             data_packs: DataPacks {
                 disabled: vec![],
-                enabled: vec!["vanilla".to_string()],
+                enabled: vec![CORE_DATA_PACK.to_string()],
             },
             data_version: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
             day_time: 0,
@@ -168,10 +213,14 @@ impl Default for LevelData {
             world_gen_settings: Default::default(),
             last_played: -1,
             level_name: "world".to_string(),
+            raining: false,
+            rain_time: 0,
             spawn_x: 0,
             spawn_y: 200,
             spawn_z: 0,
             spawn_angle: 0.0,
+            thundering: false,
+            thunder_time: 0,
             nbt_version: -1,
             version: Default::default(),
         }
@@ -1,4 +1,8 @@
-use crate::block::pumpkin_block::{BlockMetadata, OnPlaceArgs, PumpkinBlock};
+use crate::block::blocks::falling::{schedule_fall, try_fall};
+use crate::block::pumpkin_block::{
+    BlockMetadata, GetStateForNeighborUpdateArgs, OnPlaceArgs, OnScheduledTickArgs, PlacedArgs,
+    PumpkinBlock,
+};
 use async_trait::async_trait;
 use pumpkin_data::block_properties::{BlockProperties, WallTorchLikeProperties};
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
@@ -31,4 +35,20 @@ impl PumpkinBlock for AnvilBlock {
         props.facing = dir;
         props.to_state_id(args.block)
     }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        schedule_fall(args.world, args.block, args.position).await;
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        schedule_fall(args.world, args.block, args.position).await;
+        args.state_id
+    }
+
+    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
+        try_fall(args.world, args.position).await;
+    }
 }
@@ -7,13 +7,20 @@ use pumpkin_data::block_properties::{
 use pumpkin_data::entity::EntityPose;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_data::{Block, BlockDirection};
+use pumpkin_inventory::generic_container_screen_handler::{create_generic_9x3, create_generic_9x6};
+use pumpkin_inventory::player::player_inventory::PlayerInventory;
+use pumpkin_inventory::screen_handler::{InventoryPlayer, ScreenHandler, ScreenHandlerFactory};
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
+use pumpkin_world::block::entities::BlockEntity;
 use pumpkin_world::block::entities::chest::ChestBlockEntity;
+use pumpkin_world::inventory::{DoubleInventory, Inventory};
 use pumpkin_world::world::BlockFlags;
+use tokio::sync::Mutex;
 
 use crate::block::pumpkin_block::{
-    BlockMetadata, BrokenArgs, OnPlaceArgs, OnStateReplacedArgs, PlacedArgs, UseWithItemArgs,
+    BlockMetadata, BrokenArgs, NormalUseArgs, OnPlaceArgs, OnStateReplacedArgs, PlacedArgs,
 };
 use crate::entity::EntityBase;
 use crate::world::World;
@@ -22,6 +29,35 @@ use crate::{
     entity::player::Player,
 };
 
+struct ChestScreenFactory(Arc<dyn Inventory>);
+
+#[async_trait]
+impl ScreenHandlerFactory for ChestScreenFactory {
+    async fn create_screen_handler(
+        &self,
+        sync_id: u8,
+        player_inventory: &Arc<PlayerInventory>,
+        _player: &dyn InventoryPlayer,
+    ) -> Option<Arc<Mutex<dyn ScreenHandler>>> {
+        let handler = if self.0.size() > SINGLE_CHEST_SIZE {
+            create_generic_9x6(sync_id, player_inventory, self.0.clone())
+        } else {
+            create_generic_9x3(sync_id, player_inventory, self.0.clone())
+        };
+        Some(Arc::new(Mutex::new(handler)))
+    }
+
+    fn get_display_name(&self) -> TextComponent {
+        if self.0.size() > SINGLE_CHEST_SIZE {
+            TextComponent::translate("container.chestDouble", &[])
+        } else {
+            TextComponent::translate("container.chest", &[])
+        }
+    }
+}
+
+const SINGLE_CHEST_SIZE: usize = 27;
+
 pub struct ChestBlock;
 
 impl BlockMetadata for ChestBlock {
@@ -89,11 +125,32 @@ impl PumpkinBlock for ChestBlock {
     }
 
     async fn on_state_replaced(&self, args: OnStateReplacedArgs<'_>) {
+        if !args.moved {
+            if let Some(inventory) = args
+                .world
+                .get_block_entity(args.position)
+                .await
+                .and_then(BlockEntity::get_inventory)
+            {
+                for slot in 0..inventory.size() {
+                    let stack = inventory.remove_stack(slot).await;
+                    if !stack.is_empty() {
+                        args.world.drop_stack(args.position, stack).await;
+                    }
+                }
+            }
+        }
         args.world.remove_block_entity(args.position).await;
     }
 
-    async fn use_with_item(&self, _args: UseWithItemArgs<'_>) -> BlockActionResult {
-        BlockActionResult::Consume
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        if let Some(inventory) = get_chest_inventory(args.world, args.block, args.position).await {
+            args.player
+                .open_handled_screen(&ChestScreenFactory(inventory))
+                .await;
+        }
+
+        BlockActionResult::Success
     }
 
     async fn broken(&self, args: BrokenArgs<'_>) {
@@ -192,6 +249,40 @@ async fn compute_chest_props(
     }
 }
 
+/// The inventory of the chest, joined with the other half if it is a double chest.
+async fn get_chest_inventory(
+    world: &World,
+    block: &Block,
+    block_pos: &BlockPos,
+) -> Option<Arc<dyn Inventory>> {
+    let inventory = world
+        .get_block_entity(block_pos)
+        .await
+        .and_then(BlockEntity::get_inventory)?;
+
+    let state = world.get_block_state(block_pos).await;
+    let chest_props = ChestLikeProperties::from_state_id(state.id, block);
+    let connected_towards = match chest_props.r#type {
+        ChestType::Single => return Some(inventory),
+        ChestType::Left => chest_props.facing.rotate_clockwise(),
+        ChestType::Right => chest_props.facing.rotate_counter_clockwise(),
+    };
+    let Some(neighbor) = world
+        .get_block_entity(&block_pos.offset(connected_towards.to_offset()))
+        .await
+        .and_then(BlockEntity::get_inventory)
+    else {
+        return Some(inventory);
+    };
+
+    // The right half is always shown on top, like in vanilla
+    Some(Arc::new(if chest_props.r#type == ChestType::Right {
+        DoubleInventory::new(inventory, neighbor)
+    } else {
+        DoubleInventory::new(neighbor, inventory)
+    }))
+}
+
 async fn get_chest_properties_if_can_connect(
     world: &World,
     block: &Block,
@@ -15,14 +15,13 @@ pub struct EndPortalBlock;
 #[async_trait]
 impl PumpkinBlock for EndPortalBlock {
     async fn on_entity_collision(&self, args: OnEntityCollisionArgs<'_>) {
-        let world = if args.world.dimension_type == VanillaDimensionType::TheEnd {
-            args.server
-                .get_world_from_dimension(VanillaDimensionType::Overworld)
-                .await
+        let target = if args.world.dimension_type == VanillaDimensionType::TheEnd {
+            VanillaDimensionType::Overworld
         } else {
-            args.server
-                .get_world_from_dimension(VanillaDimensionType::TheEnd)
-                .await
+            VanillaDimensionType::TheEnd
+        };
+        let Some(world) = args.server.get_world_from_dimension(target).await else {
+            return;
         };
         args.entity
             .get_entity()
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use crate::block::pumpkin_block::{
+    BlockMetadata, GetStateForNeighborUpdateArgs, OnPlaceArgs, OnScheduledTickArgs, PlacedArgs,
+    PumpkinBlock,
+};
+use crate::entity::falling_block::FallingBlockEntity;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_data::fluid::Fluid;
+use pumpkin_data::tag::{RegistryKey, Tagable, get_tag_values};
+use pumpkin_data::{Block, BlockDirection, BlockState};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::chunk::TickPriority;
+
+/// Ticks between a gravity affected block losing its support and it starting to fall.
+const FALL_DELAY: u16 = 2;
+
+pub struct FallingBlock;
+
+impl BlockMetadata for FallingBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn ids(&self) -> &'static [&'static str] {
+        &[
+            "sand",
+            "red_sand",
+            "suspicious_sand",
+            "gravel",
+            "suspicious_gravel",
+        ]
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for FallingBlock {
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        schedule_fall(args.world, args.block, args.position).await;
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        schedule_fall(args.world, args.block, args.position).await;
+        args.state_id
+    }
+
+    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
+        try_fall(args.world, args.position).await;
+    }
+}
+
+pub struct ConcretePowderBlock;
+
+impl ConcretePowderBlock {
+    #[must_use]
+    pub fn is_concrete_powder(block: &Block) -> bool {
+        block
+            .is_tagged_with("minecraft:concrete_powder")
+            .unwrap_or(false)
+    }
+
+    /// The default state of the concrete this powder turns into.
+    #[must_use]
+    pub fn hardened_state(block: &Block) -> Option<BlockStateId> {
+        let name = block.name.strip_suffix("_powder")?;
+        Block::from_registry_key(name).map(|concrete| concrete.default_state.id)
+    }
+
+    /// Concrete powder hardens when it is in water or water touches any side but its bottom.
+    pub async fn should_harden(world: &World, position: &BlockPos) -> bool {
+        if is_water(world.get_fluid(position).await) {
+            return true;
+        }
+        for direction in BlockDirection::all() {
+            if direction == BlockDirection::Down {
+                continue;
+            }
+            if is_water(
+                world
+                    .get_fluid(&position.offset(direction.to_offset()))
+                    .await,
+            ) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl BlockMetadata for ConcretePowderBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn ids(&self) -> &'static [&'static str] {
+        get_tag_values(RegistryKey::Block, "minecraft:concrete_powder").unwrap()
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ConcretePowderBlock {
+    async fn on_place(&self, args: OnPlaceArgs<'_>) -> BlockStateId {
+        if Self::should_harden(args.world, args.position).await {
+            if let Some(state_id) = Self::hardened_state(args.block) {
+                return state_id;
+            }
+        }
+        args.block.default_state.id
+    }
+
+    async fn placed(&self, args: PlacedArgs<'_>) {
+        schedule_fall(args.world, args.block, args.position).await;
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        args: GetStateForNeighborUpdateArgs<'_>,
+    ) -> BlockStateId {
+        if Self::should_harden(args.world, args.position).await {
+            if let Some(state_id) = Self::hardened_state(args.block) {
+                return state_id;
+            }
+        }
+        schedule_fall(args.world, args.block, args.position).await;
+        args.state_id
+    }
+
+    async fn on_scheduled_tick(&self, args: OnScheduledTickArgs<'_>) {
+        try_fall(args.world, args.position).await;
+    }
+}
+
+fn is_water(fluid: &Fluid) -> bool {
+    fluid == &Fluid::WATER || fluid == &Fluid::FLOWING_WATER
+}
+
+/// Whether a falling block can move into a block of this state.
+#[must_use]
+pub fn can_fall_through(state: &BlockState) -> bool {
+    let block = state.block();
+    state.is_air()
+        || state.is_liquid()
+        || state.replaceable()
+        || block == &Block::FIRE
+        || block == &Block::SOUL_FIRE
+}
+
+pub async fn schedule_fall(world: &World, block: &Block, position: &BlockPos) {
+    world
+        .schedule_block_tick(block, *position, FALL_DELAY, TickPriority::Normal)
+        .await;
+}
+
+/// Turns the block at `position` into a falling block if nothing supports it.
+pub async fn try_fall(world: &Arc<World>, position: &BlockPos) {
+    let below = position.down();
+    if below.0.y < i32::from(world.generation_settings().shape.min_y)
+        || !can_fall_through(world.get_block_state(&below).await)
+    {
+        return;
+    }
+    let state_id = world.get_block_state_id(position).await;
+    FallingBlockEntity::spawn(world, position, state_id).await;
+}
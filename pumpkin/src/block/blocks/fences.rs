@@ -1,5 +1,8 @@
 use crate::block::pumpkin_block::GetStateForNeighborUpdateArgs;
+use crate::block::pumpkin_block::NormalUseArgs;
 use crate::block::pumpkin_block::OnPlaceArgs;
+use crate::block::registry::BlockActionResult;
+use crate::entity::leash::tie_to_fence;
 use async_trait::async_trait;
 use pumpkin_data::Block;
 use pumpkin_data::BlockDirection;
@@ -44,6 +47,14 @@ impl PumpkinBlock for FenceBlock {
         let fence_props = FenceProperties::from_state_id(args.state_id, args.block);
         compute_fence_state(fence_props, args.world, args.block, args.position).await
     }
+
+    async fn normal_use(&self, args: NormalUseArgs<'_>) -> BlockActionResult {
+        if tie_to_fence(args.player, args.world, *args.position).await {
+            BlockActionResult::Success
+        } else {
+            BlockActionResult::Continue
+        }
+    }
 }
 
 pub async fn compute_fence_state(
@@ -1,7 +1,8 @@
+use std::sync::LazyLock;
+
 use async_trait::async_trait;
+use pumpkin_data::Block;
 use pumpkin_data::block_properties::BlockProperties;
-use pumpkin_data::tag::RegistryKey;
-use pumpkin_data::tag::get_tag_values;
 use pumpkin_world::BlockStateId;
 
 use crate::block::pumpkin_block::OnPlaceArgs;
@@ -9,6 +10,15 @@ use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
 
 type LogProperties = pumpkin_data::block_properties::PaleOakWoodLikeProperties;
 
+/// Every block which only has an axis, e.g. logs, basalt, hay bales and quartz pillars.
+static PILLAR_BLOCKS: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    (0..=u16::MAX)
+        .filter(|id| LogProperties::handles_block_id(*id))
+        .map(|id| Block::from_id(id).name)
+        .collect()
+});
+
+/// Logs and the other pillar blocks, which are placed along the axis of the clicked face.
 pub struct LogBlock;
 impl BlockMetadata for LogBlock {
     fn namespace(&self) -> &'static str {
@@ -16,7 +26,7 @@ impl BlockMetadata for LogBlock {
     }
 
     fn ids(&self) -> &'static [&'static str] {
-        get_tag_values(RegistryKey::Block, "minecraft:logs").unwrap()
+        &PILLAR_BLOCKS
     }
 }
 
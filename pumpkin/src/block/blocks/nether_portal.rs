@@ -56,14 +56,13 @@ impl PumpkinBlock for NetherPortalBlock {
     }
 
     async fn on_entity_collision(&self, args: OnEntityCollisionArgs<'_>) {
-        let target_world = if args.world.dimension_type == VanillaDimensionType::TheNether {
-            args.server
-                .get_world_from_dimension(VanillaDimensionType::Overworld)
-                .await
+        let target = if args.world.dimension_type == VanillaDimensionType::TheNether {
+            VanillaDimensionType::Overworld
         } else {
-            args.server
-                .get_world_from_dimension(VanillaDimensionType::TheNether)
-                .await
+            VanillaDimensionType::TheNether
+        };
+        let Some(target_world) = args.server.get_world_from_dimension(target).await else {
+            return;
         };
 
         let portal_delay = Self::get_portal_time(args.world, args.entity).await;
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
-use pumpkin_data::{Block, BlockState, block_properties::get_block_by_state_id, item::Item};
+use pumpkin_data::{
+    Block, BlockState, Enchantment, block_properties::get_block_by_state_id, item::Item,
+    tag::Tagable,
+};
 use pumpkin_util::{
     loot_table::{
-        LootCondition, LootFunctionNumberProvider, LootFunctionTypes, LootPoolEntry,
-        LootPoolEntryTypes, LootTable,
+        LootCondition, LootFunction, LootFunctionBonusParameter, LootFunctionNumberProvider,
+        LootFunctionTypes, LootPoolEntry, LootPoolEntryTypes, LootTable,
     },
     random::{RandomGenerator, xoroshiro128::Xoroshiro},
 };
@@ -15,6 +18,18 @@ use rand::Rng;
 pub struct LootContextParameters {
     pub explosion_radius: Option<f32>,
     pub block_state: Option<&'static BlockState>,
+    /// The item the block was broken with
+    pub tool: Option<ItemStack>,
+}
+
+impl LootContextParameters {
+    /// The level of the enchantment on the tool, `0` without a tool.
+    fn enchantment_level(&self, enchantment: &str) -> i32 {
+        match (&self.tool, Enchantment::from_name(enchantment)) {
+            (Some(tool), Some(enchantment)) => tool.get_enchantment_level(enchantment),
+            _ => 0,
+        }
+    }
 }
 
 pub trait LootTableExt {
@@ -27,6 +42,10 @@ impl LootTableExt for LootTable {
 
         if let Some(pools) = self.pools {
             for pool in pools {
+                if !conditions_fulfilled(pool.conditions, &params) {
+                    continue;
+                }
+                let mut pool_stacks = Vec::new();
                 // TODO
                 let rolls = pool
                     .rolls
@@ -37,13 +56,19 @@ impl LootTableExt for LootTable {
                 for _ in 0..(rolls as i32) {
                     for entry in pool.entries {
                         if let Some(loot) = entry.get_loot(&params) {
-                            stacks.extend(loot);
+                            pool_stacks.extend(loot);
                         }
                     }
                 }
+                if let Some(functions) = pool.functions {
+                    apply_functions(functions, &mut pool_stacks, &params);
+                }
+                stacks.extend(pool_stacks);
             }
         }
 
+        // Explosion decay can leave nothing of a stack
+        stacks.retain(|stack| stack.item_count > 0);
         stacks
     }
 }
@@ -54,74 +79,134 @@ trait LootPoolEntryExt {
 
 impl LootPoolEntryExt for LootPoolEntry {
     fn get_loot(&self, params: &LootContextParameters) -> Option<Vec<ItemStack>> {
-        if let Some(conditions) = self.conditions {
-            if !conditions.iter().all(|cond| cond.is_fulfilled(params)) {
-                return None;
-            }
+        if !conditions_fulfilled(self.conditions, params) {
+            return None;
         }
 
         let mut stacks = self.content.get_stacks(params);
 
         if let Some(functions) = self.functions {
-            for function in functions {
-                if let Some(conditions) = function.conditions {
-                    if !conditions.iter().all(|cond| cond.is_fulfilled(params)) {
-                        continue;
+            apply_functions(functions, &mut stacks, params);
+        }
+
+        Some(stacks)
+    }
+}
+
+fn conditions_fulfilled(
+    conditions: Option<&[LootCondition]>,
+    params: &LootContextParameters,
+) -> bool {
+    conditions.is_none_or(|conditions| conditions.iter().all(|cond| cond.is_fulfilled(params)))
+}
+
+fn apply_functions(
+    functions: &[LootFunction],
+    stacks: &mut [ItemStack],
+    params: &LootContextParameters,
+) {
+    for function in functions {
+        if !conditions_fulfilled(function.conditions, params) {
+            continue;
+        }
+
+        match &function.content {
+            LootFunctionTypes::SetCount { count, add } => {
+                for stack in stacks.iter_mut() {
+                    if *add {
+                        stack.item_count += count.generate().round() as u8;
+                    } else {
+                        stack.item_count = count.generate().round() as u8;
                     }
                 }
-
-                match &function.content {
-                    LootFunctionTypes::SetCount { count, add } => {
-                        for stack in &mut stacks {
-                            if *add {
-                                stack.item_count += count.generate().round() as u8;
-                            } else {
-                                stack.item_count = count.generate().round() as u8;
-                            }
+            }
+            LootFunctionTypes::LimitCount { min, max } => {
+                if let Some(min) = min.map(|min| min.round() as u8) {
+                    for stack in stacks.iter_mut() {
+                        if stack.item_count < min {
+                            stack.item_count = min;
                         }
                     }
-                    LootFunctionTypes::LimitCount { min, max } => {
-                        if let Some(min) = min.map(|min| min.round() as u8) {
-                            for stack in &mut stacks {
-                                if stack.item_count < min {
-                                    stack.item_count = min;
-                                }
-                            }
-                        }
+                }
 
-                        if let Some(max) = max.map(|max| max.round() as u8) {
-                            for stack in &mut stacks {
-                                if stack.item_count > max {
-                                    stack.item_count = max;
-                                }
-                            }
+                if let Some(max) = max.map(|max| max.round() as u8) {
+                    for stack in stacks.iter_mut() {
+                        if stack.item_count > max {
+                            stack.item_count = max;
                         }
                     }
-                    LootFunctionTypes::ApplyBonus {
-                        enchantment: _,
-                        formula: _,
-                        parameters: _,
-                    }
-                    | LootFunctionTypes::CopyComponents {
-                        source: _,
-                        include: _,
-                    }
-                    | LootFunctionTypes::CopyState {
-                        block: _,
-                        properties: _,
-                    }
-                    | LootFunctionTypes::EnchantedCountIncrease
-                    | LootFunctionTypes::SetOminousBottleAmplifier
-                    | LootFunctionTypes::SetPotion
-                    | LootFunctionTypes::FurnaceSmelt
-                    | LootFunctionTypes::ExplosionDecay => {
-                        // TODO: shouldnt crash here but needs to be implemented someday
+                }
+            }
+            LootFunctionTypes::ApplyBonus {
+                enchantment,
+                formula,
+                parameters,
+            } => {
+                let level = params.enchantment_level(enchantment);
+                for stack in stacks.iter_mut() {
+                    stack.item_count =
+                        apply_bonus(formula, parameters.as_ref(), stack.item_count, level);
+                }
+            }
+            LootFunctionTypes::ExplosionDecay => {
+                if let Some(radius) = params.explosion_radius {
+                    // Every item survives on its own
+                    let chance = 1.0 / radius;
+                    for stack in stacks.iter_mut() {
+                        stack.item_count = (0..stack.item_count)
+                            .filter(|_| rand::rng().random::<f32>() <= chance)
+                            .count() as u8;
                     }
                 }
             }
+            LootFunctionTypes::CopyComponents {
+                source: _,
+                include: _,
+            }
+            | LootFunctionTypes::CopyState {
+                block: _,
+                properties: _,
+            }
+            | LootFunctionTypes::EnchantedCountIncrease
+            | LootFunctionTypes::SetOminousBottleAmplifier
+            | LootFunctionTypes::SetPotion
+            | LootFunctionTypes::FurnaceSmelt => {
+                // TODO: shouldnt crash here but needs to be implemented someday
+            }
         }
+    }
+}
 
-        Some(stacks)
+/// Adds the bonus of the `formula` for the enchantment `level` to `count`, like vanilla's `ApplyBonusCount`.
+fn apply_bonus(
+    formula: &str,
+    parameters: Option<&LootFunctionBonusParameter>,
+    count: u8,
+    level: i32,
+) -> u8 {
+    let mut random = rand::rng();
+    match (formula, parameters) {
+        ("minecraft:ore_drops", _) => {
+            if level <= 0 {
+                return count;
+            }
+            let multiplier = random.random_range(0..level + 2).max(1);
+            count.saturating_mul(multiplier as u8)
+        }
+        (
+            "minecraft:uniform_bonus_count",
+            Some(LootFunctionBonusParameter::Multiplier { bonus_multiplier }),
+        ) => count.saturating_add(random.random_range(0..=bonus_multiplier * level) as u8),
+        (
+            "minecraft:binomial_with_bonus_count",
+            Some(LootFunctionBonusParameter::Probability { extra, probability }),
+        ) => {
+            let successes = (0..level + extra)
+                .filter(|_| random.random::<f32>() < *probability)
+                .count();
+            count.saturating_add(successes as u8)
+        }
+        _ => count,
     }
 }
 
@@ -140,12 +225,12 @@ impl LootPoolEntryTypesExt for LootPoolEntryTypes {
             Self::LootTable => todo!(),
             Self::Dynamic => todo!(),
             Self::Tag => todo!(),
+            // Only the first child whose conditions are fulfilled drops
             Self::Alternatives(alternative_entry) => alternative_entry
                 .children
                 .iter()
-                .filter_map(|entry| entry.get_loot(params))
-                .flatten()
-                .collect(),
+                .find_map(|entry| entry.get_loot(params))
+                .unwrap_or_default(),
             Self::Sequence => todo!(),
             Self::Group => todo!(),
         }
@@ -187,6 +272,37 @@ impl LootConditionExt for LootCondition {
                 }
                 false
             }
+            Self::Inverted { term } => !term.is_fulfilled(params),
+            Self::AnyOf { terms } => terms.iter().any(|term| term.is_fulfilled(params)),
+            Self::AllOf { terms } => terms.iter().all(|term| term.is_fulfilled(params)),
+            Self::MatchTool {
+                items,
+                enchantments,
+            } => params.tool.as_ref().is_some_and(|tool| {
+                items.is_none_or(|items| {
+                    items.iter().any(|item| {
+                        if item.starts_with('#') {
+                            tool.item.is_tagged_with(item).unwrap_or(false)
+                        } else {
+                            item.strip_prefix("minecraft:").unwrap_or(item)
+                                == tool.item.registry_key
+                        }
+                    })
+                }) && enchantments.iter().all(|predicate| {
+                    let level = params.enchantment_level(predicate.enchantment);
+                    level > 0
+                        && predicate.min_level.is_none_or(|min| level >= min)
+                        && predicate.max_level.is_none_or(|max| level <= max)
+                })
+            }),
+            Self::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                let level = params.enchantment_level(enchantment) as usize;
+                let chance = chances.get(level).or(chances.last()).copied();
+                chance.is_some_and(|chance| rand::rng().random::<f32>() < chance)
+            }
             _ => false,
         }
     }
@@ -211,3 +327,67 @@ impl LootFunctionNumberProviderExt for LootFunctionNumberProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, Enchantment, item::Item};
+    use pumpkin_world::item::ItemStack;
+
+    use super::{LootContextParameters, LootTableExt};
+
+    fn pickaxe(enchantment: Option<(&'static Enchantment, i32)>) -> LootContextParameters {
+        let mut tool = ItemStack::new(1, &Item::DIAMOND_PICKAXE);
+        if let Some((enchantment, level)) = enchantment {
+            tool.add_enchantment(enchantment, level);
+        }
+        LootContextParameters {
+            tool: Some(tool),
+            ..Default::default()
+        }
+    }
+
+    fn drops(block: &Block, params: LootContextParameters) -> Vec<(&'static str, u8)> {
+        block
+            .loot_table
+            .unwrap()
+            .get_loot(params)
+            .into_iter()
+            .map(|stack| (stack.item.registry_key, stack.item_count))
+            .collect()
+    }
+
+    #[test]
+    fn silk_touch_drops_the_ore() {
+        assert_eq!(
+            drops(
+                &Block::DIAMOND_ORE,
+                pickaxe(Some((&Enchantment::SILK_TOUCH, 1)))
+            ),
+            [("diamond_ore", 1)]
+        );
+        assert_eq!(drops(&Block::DIAMOND_ORE, pickaxe(None)), [("diamond", 1)]);
+    }
+
+    #[test]
+    fn pool_conditions_need_silk_touch() {
+        assert!(drops(&Block::GLASS, pickaxe(None)).is_empty());
+        assert_eq!(
+            drops(&Block::GLASS, pickaxe(Some((&Enchantment::SILK_TOUCH, 1)))),
+            [("glass", 1)]
+        );
+    }
+
+    #[test]
+    fn fortune_multiplies_ore_drops() {
+        for _ in 0..100 {
+            let drops = drops(
+                &Block::DIAMOND_ORE,
+                pickaxe(Some((&Enchantment::FORTUNE, 3))),
+            );
+            let [("diamond", count)] = drops[..] else {
+                panic!("unexpected drops {drops:?}");
+            };
+            assert!((1..=4).contains(&count));
+        }
+    }
+}
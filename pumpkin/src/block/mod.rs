@@ -10,6 +10,7 @@ use blocks::dirt_path::DirtPathBlock;
 use blocks::doors::DoorBlock;
 use blocks::end_portal::EndPortalBlock;
 use blocks::end_portal_frame::EndPortalFrameBlock;
+use blocks::falling::{ConcretePowderBlock, FallingBlock};
 use blocks::farmland::FarmLandBlock;
 use blocks::fence_gates::FenceGateBlock;
 use blocks::fences::FenceBlock;
@@ -68,7 +69,7 @@ use fluid::lava::FlowingLava;
 use fluid::water::FlowingWater;
 use loot::LootTableExt;
 use pumpkin_data::block_properties::Integer0To15;
-use pumpkin_data::{Block, BlockState};
+use pumpkin_data::{Block, BlockState, Enchantment};
 
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::random::{RandomGenerator, get_seed, xoroshiro128::Xoroshiro};
@@ -114,6 +115,7 @@ pub mod pumpkin_fluid;
 pub mod registry;
 
 #[must_use]
+#[allow(clippy::too_many_lines)]
 pub fn default_registry() -> Arc<BlockRegistry> {
     let mut manager = BlockRegistry::default();
 
@@ -123,6 +125,8 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(SaplingBlock);
     manager.register(CactusBlock);
     manager.register(CarpetBlock);
+    manager.register(ConcretePowderBlock);
+    manager.register(FallingBlock);
     manager.register(CampfireBlock);
     manager.register(MossCarpetBlock);
     manager.register(PaleMossCarpetBlock);
@@ -243,17 +247,22 @@ pub async fn drop_loot(
     experience: bool,
     params: LootContextParameters,
 ) {
+    // Silk touch keeps the experience in the block
+    let silk_touch = params
+        .tool
+        .as_ref()
+        .is_some_and(|tool| tool.get_enchantment_level(&Enchantment::SILK_TOUCH) > 0);
+
     if let Some(loot_table) = &block.loot_table {
         for stack in loot_table.get_loot(params) {
             world.drop_stack(pos, stack).await;
         }
     }
 
-    if experience {
+    if experience && !silk_touch {
         if let Some(experience) = &block.experience {
             let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(get_seed()));
             let amount = experience.experience.get(&mut random);
-            // TODO: Silk touch gives no exp
             if amount > 0 {
                 ExperienceOrbEntity::spawn(world, pos.to_f64(), amount as u32).await;
             }
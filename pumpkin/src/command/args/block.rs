@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 use pumpkin_data::tag::{RegistryKey, get_tag_values};
-use pumpkin_data::{Block, block_properties::get_block};
+use pumpkin_data::{
+    Block,
+    block_properties::{get_block, get_block_registry_keys},
+};
 use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 use pumpkin_util::text::TextComponent;
 
@@ -12,6 +15,7 @@ use super::{
         args::{ArgumentConsumer, RawArgs},
     },
     Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+    SplitSingleWhitespaceIncludingEmptyParts,
 };
 
 pub struct BlockArgumentConsumer;
@@ -22,7 +26,7 @@ impl GetClientSideArgParser for BlockArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -42,9 +46,18 @@ impl ArgumentConsumer for BlockArgumentConsumer {
         &'a self,
         _sender: &CommandSender,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        let Some(input) = input.split_single_whitespace_including_empty_parts().last() else {
+            return Ok(None);
+        };
+
+        let suggestions = get_block_registry_keys()
+            .map(|key| format!("minecraft:{key}"))
+            .filter(|id| id.starts_with(input) || id["minecraft:".len()..].starts_with(input))
+            .map(|id| CommandSuggestion::new(id, None))
+            .collect();
+        Ok(Some(suggestions))
     }
 }
 
@@ -1,5 +1,9 @@
 use std::str::FromStr;
 
+use pumpkin_protocol::java::client::play::CommandSuggestion;
+
+use super::SplitSingleWhitespaceIncludingEmptyParts;
+
 pub enum MaybeRelativeCoordinate<const IS_Y: bool> {
     Absolute(f64),
     Relative(f64),
@@ -68,3 +72,29 @@ impl<const IS_Y: bool> MaybeRelativeBlockCoordinate<IS_Y> {
         Some(abs)
     }
 }
+
+/// Suggests the relative coordinates still missing from a position with `dimensions`
+/// coordinates, e.g. `~ ~` once the x coordinate of a block position was typed.
+pub(crate) fn suggest_relative_coordinates(
+    input: &str,
+    dimensions: usize,
+) -> Vec<CommandSuggestion> {
+    let mut words: Vec<&str> = input
+        .split_single_whitespace_including_empty_parts()
+        .collect();
+    let Some(current) = words.pop() else {
+        return Vec::new();
+    };
+    let typed = words
+        .iter()
+        .rev()
+        .take_while(|word| MaybeRelativeCoordinate::<true>::try_from(**word).is_ok())
+        .count();
+    let missing = dimensions - typed % dimensions;
+
+    (1..=missing)
+        .map(|count| vec!["~"; count].join(" "))
+        .filter(|suggestion| suggestion.starts_with(current))
+        .map(|suggestion| CommandSuggestion::new(suggestion, None))
+        .collect()
+}
@@ -6,14 +6,15 @@ use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, Sugg
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
-use crate::entity::player::Player;
+use crate::entity::EntityBase;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
 use super::players::PlayersArgumentConsumer;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// todo: implement (currently just calls [`super::arg_player::PlayerArgumentConsumer`])
+/// todo: implement the remaining selectors (currently only @e selects non-player entities, everything
+/// else calls [`super::players::PlayersArgumentConsumer`])
 ///
 /// For selecting zero, one or multiple entities, eg. using @s, a player name, @a or @e
 pub struct EntitiesArgumentConsumer;
@@ -37,9 +38,17 @@ impl ArgumentConsumer for EntitiesArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        // todo
+        if args.last() == Some(&"@e") {
+            args.pop();
+            return Some(Arg::Entities(server.get_all_entities().await));
+        }
         match PlayersArgumentConsumer.consume(src, server, args).await {
-            Some(Arg::Players(p)) => Some(Arg::Entities(p)),
+            Some(Arg::Players(players)) => Some(Arg::Entities(
+                players
+                    .into_iter()
+                    .map(|player| player as Arc<dyn EntityBase>)
+                    .collect(),
+            )),
             _ => None,
         }
     }
@@ -61,7 +70,7 @@ impl DefaultNameArgConsumer for EntitiesArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EntitiesArgumentConsumer {
-    type Data = &'a [Arc<Player>];
+    type Data = &'a [Arc<dyn EntityBase>];
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
@@ -21,7 +21,11 @@ use super::{
     tree::{CommandTree, RawArgs},
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
-use crate::{entity::player::Player, server::Server};
+use crate::world::particle::ParticleData;
+use crate::{
+    entity::{EntityBase, player::Player},
+    server::Server,
+};
 
 pub mod block;
 pub mod bool;
@@ -42,6 +46,7 @@ pub mod position_block;
 pub mod resource;
 pub mod resource_location;
 pub mod rotation;
+pub mod score_holder;
 pub mod simple;
 pub mod sound;
 pub mod sound_category;
@@ -83,9 +88,10 @@ pub trait DefaultNameArgConsumer: ArgumentConsumer {
 
 #[derive(Clone)]
 pub enum Arg<'a> {
-    Entities(Vec<Arc<Player>>),
+    Entities(Vec<Arc<dyn EntityBase>>),
     Entity(Arc<Player>),
     Players(Vec<Arc<Player>>),
+    ScoreHolders(Vec<String>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
     Pos2D(Vector2<f64>),
@@ -99,7 +105,7 @@ pub enum Arg<'a> {
     BlockPredicate(&'a str),
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
-    Particle(Particle),
+    Particle(Particle, ParticleData),
     Msg(String),
     TextComponent(TextComponent),
     Time(i32),
@@ -110,7 +116,7 @@ pub enum Arg<'a> {
     SoundCategory(SoundCategory),
     DamageType(DamageType),
     Effect(EffectType),
-    Enchantment(Enchantment),
+    Enchantment(&'static Enchantment),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
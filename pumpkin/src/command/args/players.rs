@@ -10,7 +10,10 @@ use crate::entity::player::Player;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+use super::{
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+    SplitSingleWhitespaceIncludingEmptyParts,
+};
 
 /// Select zero, one or multiple players
 pub struct PlayersArgumentConsumer;
@@ -24,7 +27,7 @@ impl GetClientSideArgParser for PlayersArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -63,10 +66,28 @@ impl ArgumentConsumer for PlayersArgumentConsumer {
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender,
-        _server: &'a Server,
-        _input: &'a str,
+        server: &'a Server,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        let Some(input) = input.split_single_whitespace_including_empty_parts().last() else {
+            return Ok(None);
+        };
+        let input = input.to_lowercase();
+
+        let names = server
+            .get_all_players()
+            .await
+            .iter()
+            .map(|player| player.gameprofile.name.clone())
+            .collect::<Vec<_>>();
+        let suggestions = ["@a", "@e", "@p", "@r", "@s"]
+            .into_iter()
+            .map(str::to_string)
+            .chain(names)
+            .filter(|suggestion| suggestion.to_lowercase().starts_with(&input))
+            .map(|suggestion| CommandSuggestion::new(suggestion, None))
+            .collect();
+        Ok(Some(suggestions))
     }
 }
 
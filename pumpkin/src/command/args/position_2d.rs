@@ -9,7 +9,7 @@ use crate::command::tree::RawArgs;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::coordinate::MaybeRelativeCoordinate;
+use super::coordinate::{MaybeRelativeCoordinate, suggest_relative_coordinates};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x and z coordinates only
@@ -23,7 +23,7 @@ impl GetClientSideArgParser for Position2DArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -46,9 +46,9 @@ impl ArgumentConsumer for Position2DArgumentConsumer {
         &'a self,
         _sender: &CommandSender,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_relative_coordinates(input, 2)))
     }
 }
 
@@ -9,7 +9,7 @@ use crate::command::tree::RawArgs;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::coordinate::MaybeRelativeBlockCoordinate;
+use super::coordinate::{MaybeRelativeBlockCoordinate, suggest_relative_coordinates};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x, y and z coordinates
@@ -21,7 +21,7 @@ impl GetClientSideArgParser for BlockPosArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -44,9 +44,9 @@ impl ArgumentConsumer for BlockPosArgumentConsumer {
         &'a self,
         _sender: &CommandSender,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_relative_coordinates(input, 3)))
     }
 }
 
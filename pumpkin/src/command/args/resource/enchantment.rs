@@ -36,10 +36,11 @@ impl ArgumentConsumer for EnchantmentArgumentConsumer {
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
         let name = args.pop()?;
-
-        // Create a static damage type first
-        let enchantment = Enchantment::from_name(name)?;
-        // Find matching static damage type from values array
+        let enchantment = if name.contains(':') {
+            Enchantment::from_name(name)
+        } else {
+            Enchantment::from_name(&format!("minecraft:{name}"))
+        }?;
         Some(Arg::Enchantment(enchantment))
     }
 
@@ -60,7 +61,7 @@ impl DefaultNameArgConsumer for EnchantmentArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EnchantmentArgumentConsumer {
-    type Data = &'a Enchantment;
+    type Data = &'static Enchantment;
 
     fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
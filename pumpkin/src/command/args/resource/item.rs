@@ -7,7 +7,7 @@ use crate::command::{
     CommandSender,
     args::{
         Arg, ArgumentConsumer, ConsumedArgs, DefaultNameArgConsumer, FindArg,
-        GetClientSideArgParser,
+        GetClientSideArgParser, SplitSingleWhitespaceIncludingEmptyParts,
     },
     dispatcher::CommandError,
     tree::RawArgs,
@@ -22,7 +22,7 @@ impl GetClientSideArgParser for ItemArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        None
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -42,9 +42,20 @@ impl ArgumentConsumer for ItemArgumentConsumer {
         &'a self,
         _sender: &CommandSender,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        let Some(input) = input.split_single_whitespace_including_empty_parts().last() else {
+            return Ok(None);
+        };
+        let input = input.strip_prefix("minecraft:").unwrap_or(input);
+
+        // Item ids are contiguous, so walk them until the first unknown id
+        let suggestions = (0..)
+            .map_while(Item::from_id)
+            .filter(|item| item.id != Item::AIR.id && item.registry_key.starts_with(input))
+            .map(|item| CommandSuggestion::new(format!("minecraft:{}", item.registry_key), None))
+            .collect();
+        Ok(Some(suggestions))
     }
 }
 
@@ -12,14 +12,13 @@ use crate::command::{
     tree::RawArgs,
 };
 use crate::server::Server;
+use crate::world::particle::ParticleData;
 
 pub struct ParticleArgumentConsumer;
 
 impl GetClientSideArgParser for ParticleArgumentConsumer {
     fn get_client_side_parser(&self) -> ArgumentType {
-        ArgumentType::Resource {
-            identifier: "particle_type",
-        }
+        ArgumentType::Particle
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
@@ -35,12 +34,10 @@ impl ArgumentConsumer for ParticleArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let name = args.pop()?;
-
-        // Create a static damage type first
+        let (name, options) = pop_particle(args)?;
         let particle = Particle::from_name(name.strip_prefix("minecraft:").unwrap_or(name))?;
-        // Find matching static damage type from values array
-        Some(Arg::Particle(particle))
+        let data = ParticleData::parse(particle, options.as_deref())?;
+        Some(Arg::Particle(particle, data))
     }
 
     async fn suggest<'a>(
@@ -53,6 +50,25 @@ impl ArgumentConsumer for ParticleArgumentConsumer {
     }
 }
 
+/// Takes the particle name and its options, which follow the name directly, e.g.
+/// `dust{color:[1.0, 0.0, 0.0],scale:1.0}`. The options may contain spaces, so words are taken
+/// until their braces are balanced.
+fn pop_particle<'a>(args: &mut RawArgs<'a>) -> Option<(&'a str, Option<String>)> {
+    let input = args.pop()?;
+    let Some(start) = input.find('{') else {
+        return Some((input, None));
+    };
+    let mut options = input[start..].to_string();
+    let depth = |options: &str| {
+        options.matches('{').count() as isize - options.matches('}').count() as isize
+    };
+    while depth(&options) > 0 {
+        options.push(' ');
+        options.push_str(args.pop()?);
+    }
+    Some((&input[..start], Some(options)))
+}
+
 impl DefaultNameArgConsumer for ParticleArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "particle_type"
@@ -60,12 +76,37 @@ impl DefaultNameArgConsumer for ParticleArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for ParticleArgumentConsumer {
-    type Data = &'a Particle;
+    type Data = (Particle, ParticleData);
 
     fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Particle(data)) => Ok(data),
+            Some(Arg::Particle(particle, data)) => Ok((*particle, *data)),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::pop_particle;
+
+    #[test]
+    fn takes_options_with_spaces() {
+        // Raw args are stored in reverse, the next argument is popped from the end
+        let mut args = vec!["~", "scale:1.0}", "0.0],", "0.0,", "dust{color:[1.0,"];
+        let (name, options) = pop_particle(&mut args).unwrap();
+        assert_eq!(name, "dust");
+        assert_eq!(
+            options.as_deref(),
+            Some("{color:[1.0, 0.0, 0.0], scale:1.0}")
+        );
+        assert_eq!(args, ["~"]);
+
+        let mut args = vec!["~", "minecraft:flame"];
+        assert_eq!(pop_particle(&mut args), Some(("minecraft:flame", None)));
+
+        // Unclosed options
+        let mut args = vec!["dust{color:[1.0,"];
+        assert_eq!(pop_particle(&mut args), None);
+    }
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use pumpkin_protocol::java::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::CommandSender;
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::server::Server;
+
+use super::super::args::ArgumentConsumer;
+use super::players::PlayersArgumentConsumer;
+use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Select zero, one or multiple score holders by their name.
+///
+/// Selectors resolve to the names of the selected players, while any other name is used as is,
+/// so offline players and fake players like `#global` can hold scores too.
+/// todo: support `*` for every holder of the scoreboard
+pub struct ScoreHoldersArgumentConsumer;
+
+impl GetClientSideArgParser for ScoreHoldersArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::ScoreHolder { flags: 1 }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        Some(SuggestionProviders::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ScoreHoldersArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let name = *args.last()?;
+        if !name.starts_with('@') {
+            args.pop();
+            return Some(Arg::ScoreHolders(vec![name.to_string()]));
+        }
+        match PlayersArgumentConsumer.consume(src, server, args).await {
+            Some(Arg::Players(players)) => Some(Arg::ScoreHolders(
+                players
+                    .iter()
+                    .map(|player| player.gameprofile.name.clone())
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        sender: &CommandSender,
+        server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        PlayersArgumentConsumer.suggest(sender, server, input).await
+    }
+}
+
+impl DefaultNameArgConsumer for ScoreHoldersArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "targets"
+    }
+}
+
+impl<'a> FindArg<'a> for ScoreHoldersArgumentConsumer {
+    type Data = &'a [String];
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ScoreHolders(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
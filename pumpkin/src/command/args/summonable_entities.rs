@@ -11,6 +11,7 @@ use super::{
         args::{ArgumentConsumer, RawArgs},
     },
     Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+    SplitSingleWhitespaceIncludingEmptyParts,
 };
 
 pub struct SummonableEntitiesArgumentConsumer;
@@ -21,7 +22,8 @@ impl GetClientSideArgParser for SummonableEntitiesArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
-        Some(SuggestionProviders::SummonableEntities)
+        // Only the server knows which entities it can summon
+        Some(SuggestionProviders::AskServer)
     }
 }
 
@@ -41,12 +43,26 @@ impl ArgumentConsumer for SummonableEntitiesArgumentConsumer {
         &'a self,
         _sender: &CommandSender,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
-        Ok(None)
+        let Some(input) = input.split_single_whitespace_including_empty_parts().last() else {
+            return Ok(None);
+        };
+        Ok(Some(summonable_entity_suggestions(input)))
     }
 }
 
+/// The ids of the summonable entities starting with `input`, with or without the namespace.
+fn summonable_entity_suggestions(input: &str) -> Vec<CommandSuggestion> {
+    (0..)
+        .map_while(EntityType::from_raw)
+        .filter(|entity_type| entity_type.summonable)
+        .map(|entity_type| format!("minecraft:{}", entity_type.resource_name))
+        .filter(|id| id.starts_with(input) || id["minecraft:".len()..].starts_with(input))
+        .map(|id| CommandSuggestion::new(id, None))
+        .collect()
+}
+
 impl DefaultNameArgConsumer for SummonableEntitiesArgumentConsumer {
     fn default_name(&self) -> &'static str {
         "summonable_entities"
@@ -72,3 +88,25 @@ impl<'a> FindArg<'a> for SummonableEntitiesArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::summonable_entity_suggestions;
+
+    fn suggestions(input: &str) -> Vec<String> {
+        summonable_entity_suggestions(input)
+            .into_iter()
+            .map(|suggestion| suggestion.suggestion)
+            .collect()
+    }
+
+    #[test]
+    fn suggests_summonable_entities_by_prefix() {
+        let zombies = suggestions("zombie");
+        assert!(zombies.contains(&"minecraft:zombie".to_string()));
+        assert!(zombies.iter().all(|id| id.starts_with("minecraft:zombie")));
+        assert_eq!(suggestions("minecraft:cre"), suggestions("cre"));
+        // Players can't be summoned
+        assert!(!suggestions("").contains(&"minecraft:player".to_string()));
+    }
+}
@@ -9,10 +9,12 @@ use crate::{
         banned_player_data::BANNED_PLAYER_LIST,
     },
     entity::player::Player,
+    plugin::player::player_kick::KickCause,
 };
 use CommandError::InvalidConsumption;
 use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
+use std::sync::Arc;
 
 const NAMES: [&str; 1] = ["ban"];
 const DESCRIPTION: &str = "bans a player";
@@ -34,7 +36,9 @@ impl CommandExecutor for NoReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
-        ban_player(sender, &targets[0], None).await;
+        for target in targets {
+            ban_player(sender, target, None).await;
+        }
         Ok(())
     }
 }
@@ -57,12 +61,14 @@ impl CommandExecutor for ReasonExecutor {
             return Err(InvalidConsumption(Some(ARG_REASON.into())));
         };
 
-        ban_player(sender, &targets[0], Some(reason.to_string())).await;
+        for target in targets {
+            ban_player(sender, target, Some(reason.clone())).await;
+        }
         Ok(())
     }
 }
 
-async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<String>) {
+async fn ban_player(sender: &CommandSender, player: &Arc<Player>, reason: Option<String>) {
     let mut banned_players = BANNED_PLAYER_LIST.write().await;
 
     let reason = reason.unwrap_or_else(|| "Banned by an operator.".to_string());
@@ -91,16 +97,19 @@ async fn ban_player(sender: &CommandSender, player: &Player, reason: Option<Stri
             "commands.ban.success",
             [
                 TextComponent::text(player.gameprofile.name.clone()),
-                TextComponent::text(reason),
+                TextComponent::text(reason.clone()),
             ],
         ))
         .await;
 
     player
-        .kick(TextComponent::translate(
-            "multiplayer.disconnect.banned",
-            [],
-        ))
+        .kick(
+            KickCause::Admin,
+            TextComponent::translate(
+                "multiplayer.disconnect.banned.reason",
+                [TextComponent::text(reason)],
+            ),
+        )
         .await;
 }
 
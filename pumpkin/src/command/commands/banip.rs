@@ -10,6 +10,7 @@ use crate::{
     data::{
         SaveJSONConfiguration, banlist_serializer::BannedIpEntry, banned_ip_data::BANNED_IP_LIST,
     },
+    plugin::player::player_kick::KickCause,
     server::Server,
 };
 use CommandError::InvalidConsumption;
@@ -119,7 +120,7 @@ async fn ban_ip(sender: &CommandSender, server: &Server, target: &str, reason: O
             "commands.banip.success",
             [
                 TextComponent::text(target_ip.to_string()),
-                TextComponent::text(reason),
+                TextComponent::text(reason.clone()),
             ],
         ))
         .await;
@@ -136,10 +137,13 @@ async fn ban_ip(sender: &CommandSender, server: &Server, target: &str, reason: O
 
     for target in affected {
         target
-            .kick(TextComponent::translate(
-                "multiplayer.disconnect.ip_banned",
-                [],
-            ))
+            .kick(
+                KickCause::Admin,
+                TextComponent::translate(
+                    "multiplayer.disconnect.banned_ip.reason",
+                    [TextComponent::text(reason.clone())],
+                ),
+            )
             .await;
     }
 }
@@ -17,6 +17,8 @@ use crate::command::{
         builder::{argument, literal},
     },
 };
+use crate::entity::EntityBase;
+use crate::entity::damage::DamageSource;
 
 const NAMES: [&str; 1] = ["damage"];
 const DESCRIPTION: &str = "Deals damage to entities";
@@ -91,8 +93,7 @@ impl CommandExecutor for LocationExecutor {
         let location = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
         let success = target
-            .living_entity
-            .damage_with_context(amount, damage_type, Some(location), None, None)
+            .damage_with_source(amount, DamageSource::at(damage_type, location))
             .await;
 
         send_damage_result(sender, success, amount, target.gameprofile.name.clone()).await;
@@ -128,23 +129,17 @@ impl CommandExecutor for EntityExecutor {
                 _ => DamageType::GENERIC,
             });
 
-        let source = EntityArgumentConsumer::find_arg(args, ARG_ENTITY).ok();
-        let cause = if self.0 {
-            EntityArgumentConsumer::find_arg(args, ARG_CAUSE).ok()
-        } else {
-            None
+        // `by` is the entity dealing the damage and `from` the one responsible for it
+        let source = match EntityArgumentConsumer::find_arg(args, ARG_ENTITY) {
+            Ok(entity) if self.0 => {
+                let cause = EntityArgumentConsumer::find_arg(args, ARG_CAUSE)?;
+                DamageSource::indirect(damage_type, entity, Some(cause))
+            }
+            Ok(entity) => DamageSource::entity(damage_type, entity),
+            Err(_) => DamageSource::new(damage_type),
         };
 
-        let success = target
-            .living_entity
-            .damage_with_context(
-                amount,
-                damage_type,
-                None,
-                source.as_ref().map(|e| &e.living_entity.entity),
-                cause.as_ref().map(|e| &e.living_entity.entity),
-            )
-            .await;
+        let success = target.damage_with_source(amount, source).await;
 
         send_damage_result(sender, success, amount, target.gameprofile.name.clone()).await;
 
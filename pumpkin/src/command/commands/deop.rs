@@ -32,13 +32,17 @@ impl CommandExecutor for Executor {
         };
 
         for player in targets {
-            if let Some(op_index) = config
+            let Some(op_index) = config
                 .ops
                 .iter()
                 .position(|o| o.uuid == player.gameprofile.id)
-            {
-                config.ops.remove(op_index);
-            }
+            else {
+                sender
+                    .send_message(TextComponent::translate("commands.deop.failed", []))
+                    .await;
+                continue;
+            };
+            config.ops.remove(op_index);
             config.save();
 
             {
@@ -9,6 +9,7 @@ use crate::command::dispatcher::CommandError::InvalidConsumption;
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, literal};
 use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::effect::{Effect, INFINITE_DURATION};
 use crate::server::Server;
 use async_trait::async_trait;
 use pumpkin_util::text::color::{Color, NamedColor};
@@ -65,7 +66,7 @@ impl CommandExecutor for GiveExecutor {
                     .find_arg_default_name(args)??
                     * 20
             }
-            Time::Infinite => -1,
+            Time::Infinite => INFINITE_DURATION,
         };
 
         let amplifier: i32 = match self.1 {
@@ -77,41 +78,33 @@ impl CommandExecutor for GiveExecutor {
                 .find_arg_default_name(args)??,
         };
 
-        let mut hide_particles = self.2;
         //if false -> parameter is referred
-        if !hide_particles {
+        let hide_particles = if self.2 {
+            false
+        } else {
             let Some(Arg::Bool(hide_particle)) = args.get(ARG_HIDE_PARTICLE) else {
                 return Err(InvalidConsumption(Some(ARG_HIDE_PARTICLE.into())));
             };
-
-            hide_particles = *hide_particle;
-        }
+            *hide_particle
+        };
 
         let mut failed = 0;
 
         for target in targets {
-            if target.living_entity.has_effect(*effect).await
-                && target
-                    .living_entity
-                    .get_effect(*effect)
-                    .await
-                    .unwrap()
-                    .amplifier
-                    > amplifier as u8
-            {
+            // Fails if the target already has a stronger or longer effect
+            let changed = target
+                .add_effect(Effect {
+                    r#type: *effect,
+                    duration: second,
+                    amplifier: amplifier as u8,
+                    ambient: false, //this is not a beacon effect
+                    show_particles: !hide_particles,
+                    show_icon: true,
+                    blend: true, //Currently only used in the DARKNESS effect to apply extra void fog and adjust the gamma value for lighting.
+                })
+                .await;
+            if !changed {
                 failed += 1;
-            } else {
-                target
-                    .add_effect(crate::entity::effect::Effect {
-                        r#type: *effect,
-                        duration: second,
-                        amplifier: amplifier as u8,
-                        ambient: false, //this is not a beacon effect
-                        show_particles: hide_particles,
-                        show_icon: true,
-                        blend: true, //Currently only used in the DARKNESS effect to apply extra void fog and adjust the gamma value for lighting.
-                    })
-                    .await;
             }
         }
 
@@ -190,7 +183,7 @@ impl CommandExecutor for ClearExecutor {
                 sender
                     .send_message(TextComponent::translate(
                         "commands.effect.clear.everything.success.single",
-                        [TextComponent::text(targets[0].gameprofile.name.to_string())],
+                        [TextComponent::text(targets[0].gameprofile.name.clone())],
                     ))
                     .await;
             } else {
@@ -207,50 +200,42 @@ impl CommandExecutor for ClearExecutor {
             };
 
             effect = *effect_type;
-            let mut has_effect = vec![];
-
+            let mut removed = 0;
             for target in targets {
-                if !target.living_entity.has_effect(effect).await {
-                    target.remove_effect(effect).await;
+                if target.remove_effect(effect).await {
+                    removed += 1;
                 }
-                has_effect.push(target.living_entity.has_effect(effect).await);
             }
 
-            if has_effect.contains(&false) {
-                //contain false for 1 player == don't have
-                if targets.len() == 1 || !has_effect.contains(&true) {
-                    sender
-                        .send_message(
-                            TextComponent::translate("commands.effect.clear.specific.failed", [])
-                                .color(Color::Named(NamedColor::Red)),
-                        )
-                        .await;
-                }
+            let translation_name =
+                TextComponent::translate(format!("effect.minecraft.{}", effect.to_name()), []);
+            if removed == 0 {
+                sender
+                    .send_message(
+                        TextComponent::translate("commands.effect.clear.specific.failed", [])
+                            .color(Color::Named(NamedColor::Red)),
+                    )
+                    .await;
+            } else if targets.len() == 1 {
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.effect.clear.specific.success.single",
+                        [
+                            translation_name,
+                            TextComponent::text(targets[0].gameprofile.name.clone()),
+                        ],
+                    ))
+                    .await;
             } else {
-                //true for 1 player = have the effect
-                if targets.len() == 1 {
-                    sender
-                        .send_message(TextComponent::translate(
-                            "commands.effect.clear.specific.success.single",
-                            [
-                                TextComponent::text(effect.to_name()),
-                                TextComponent::text(targets[0].gameprofile.name.to_string()),
-                            ],
-                        ))
-                        .await;
-                }
-                //contain true for everyplayer = at least 1 player have the effect
-                else {
-                    sender
-                        .send_message(TextComponent::translate(
-                            "commands.effect.clear.specific.success.multiple",
-                            [
-                                TextComponent::text(effect.to_name()),
-                                TextComponent::text(targets.len().to_string()),
-                            ],
-                        ))
-                        .await;
-                }
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.effect.clear.specific.success.multiple",
+                        [
+                            translation_name,
+                            TextComponent::text(targets.len().to_string()),
+                        ],
+                    ))
+                    .await;
             }
         }
 
@@ -282,7 +267,7 @@ pub fn init_command_tree() -> CommandTree {
                                 ARG_SECOND,
                                 BoundedNumArgumentConsumer::new()
                                     .name("seconds")
-                                    .min(0)
+                                    .min(1)
                                     .max(1_000_000),
                             )
                             .execute(GiveExecutor(Time::Specified, Amplifier::Base, true))
@@ -291,7 +276,7 @@ pub fn init_command_tree() -> CommandTree {
                                     ARG_AMPLIFIER,
                                     BoundedNumArgumentConsumer::new()
                                         .name("amplifier")
-                                        .min(1)
+                                        .min(0)
                                         .max(255),
                                 )
                                 .execute(GiveExecutor(Time::Specified, Amplifier::Specified, true))
@@ -310,7 +295,7 @@ pub fn init_command_tree() -> CommandTree {
                                         ARG_AMPLIFIER,
                                         BoundedNumArgumentConsumer::new()
                                             .name("amplifier")
-                                            .min(1)
+                                            .min(0)
                                             .max(255),
                                     )
                                     .execute(GiveExecutor(
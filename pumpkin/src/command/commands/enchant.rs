@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::resource::enchantment::EnchantmentArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, argument_default_name};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::item::enchantment::{MAX_ENCHANTMENT_LEVEL, can_enchant, get_full_name};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["enchant"];
+
+const DESCRIPTION: &str = "Adds an enchantment to the held item of players.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_ENCHANTMENT: &str = "enchantment";
+const ARG_LEVEL: &str = "level";
+
+fn level_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_LEVEL)
+        .min(1)
+        .max(MAX_ENCHANTMENT_LEVEL)
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let enchantment = EnchantmentArgumentConsumer::find_arg(args, ARG_ENCHANTMENT)?;
+        let level = match level_consumer().find_arg_default_name(args) {
+            Err(_) => 1,
+            Ok(Ok(level)) => level,
+            Ok(Err(_)) => {
+                return Err(CommandError::InvalidConsumption(Some(ARG_LEVEL.into())));
+            }
+        };
+
+        // Operators with the highest permission level may go beyond the vanilla maximum
+        if level > enchantment.max_level
+            && !sender
+                .has_permission("pumpkin:command.enchant.beyond_max_level")
+                .await
+        {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate(
+                    "commands.enchant.failed.level",
+                    [
+                        TextComponent::text(level.to_string()),
+                        TextComponent::text(enchantment.max_level.to_string()),
+                    ],
+                )
+                .color_named(NamedColor::Red),
+            )));
+        }
+
+        let mut enchanted = 0;
+        for target in targets {
+            let held_item = target.inventory().held_item();
+            let mut stack = held_item.lock().await;
+            if stack.is_empty() {
+                if targets.len() == 1 {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            "commands.enchant.failed.itemless",
+                            [TextComponent::text(target.gameprofile.name.clone())],
+                        )
+                        .color_named(NamedColor::Red),
+                    )));
+                }
+                continue;
+            }
+            if !can_enchant(enchantment, stack.item) {
+                if targets.len() == 1 {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            "commands.enchant.failed.incompatible",
+                            [stack.item.translated_name()],
+                        )
+                        .color_named(NamedColor::Red),
+                    )));
+                }
+                continue;
+            }
+            // The changed slot is sent to the player with the next inventory sync
+            stack.add_enchantment(enchantment, level);
+            enchanted += 1;
+        }
+
+        let msg = match targets {
+            _ if enchanted == 0 => {
+                return Err(CommandError::CommandFailed(Box::new(
+                    TextComponent::translate("commands.enchant.failed", [])
+                        .color_named(NamedColor::Red),
+                )));
+            }
+            [target] => TextComponent::translate(
+                "commands.enchant.success.single",
+                [
+                    get_full_name(enchantment, level),
+                    TextComponent::text(target.gameprofile.name.clone()),
+                ],
+            ),
+            _ => TextComponent::translate(
+                "commands.enchant.success.multiple",
+                [
+                    get_full_name(enchantment, level),
+                    TextComponent::text(enchanted.to_string()),
+                ],
+            ),
+        };
+        sender.send_message(msg).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGETS, PlayersArgumentConsumer).then(
+            argument(ARG_ENCHANTMENT, EnchantmentArgumentConsumer)
+                .execute(Executor)
+                .then(argument_default_name(level_consumer()).execute(Executor)),
+        ),
+    )
+}
@@ -38,8 +38,7 @@ impl CommandExecutor for TargetSelfExecutor {
         };
 
         if let Player(target) = sender {
-            if target.gamemode.load() != gamemode {
-                target.set_gamemode(gamemode).await;
+            if target.set_gamemode(gamemode).await {
                 let gamemode_string = format!("{gamemode:?}").to_lowercase();
                 let gamemode_string = format!("gameMode.{gamemode_string}");
                 target
@@ -76,8 +75,7 @@ impl CommandExecutor for TargetPlayerExecutor {
         let target_count = targets.len();
 
         for target in targets {
-            if target.gamemode.load() != gamemode {
-                target.set_gamemode(gamemode).await;
+            if target.set_gamemode(gamemode).await {
                 let gamemode_string = format!("{gamemode:?}").to_lowercase();
                 let gamemode_string = format!("gameMode.{gamemode_string}");
                 target
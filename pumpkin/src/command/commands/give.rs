@@ -69,7 +69,12 @@ impl CommandExecutor for Executor {
                 let mut stack = ItemStack::new(take as u8, item);
                 target.inventory().insert_stack_anywhere(&mut stack).await;
                 if !stack.is_empty() {
-                    target.drop_item(stack).await;
+                    // Inventory is full, drop the rest at the player's feet
+                    target
+                        .world()
+                        .await
+                        .drop_stack(&target.living_entity.entity.block_pos.load(), stack)
+                        .await;
                 }
                 remaining -= take;
             }
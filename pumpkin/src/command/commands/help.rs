@@ -9,8 +9,8 @@ use crate::command::args::command::CommandTreeArgumentConsumer;
 use crate::command::args::{Arg, ConsumedArgs, FindArgDefaultName};
 use crate::command::dispatcher::CommandError;
 use crate::command::dispatcher::CommandError::InvalidConsumption;
+use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, argument_default_name};
-use crate::command::tree::{Command, CommandTree};
 use crate::command::{CommandExecutor, CommandSender};
 use crate::server::Server;
 
@@ -20,7 +20,7 @@ const DESCRIPTION: &str = "Print a help message.";
 
 const ARG_COMMAND: &str = "command";
 
-const COMMANDS_PER_PAGE: i32 = 7;
+const COMMANDS_PER_PAGE: i32 = 10;
 
 fn page_number_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new().name("page").min(1)
@@ -125,28 +125,24 @@ impl CommandExecutor for BaseHelpExecutor {
         };
 
         let dispatcher = server.command_dispatcher.read().await;
-        let commands: Vec<&CommandTree> = dispatcher
-            .commands
-            .values()
-            .filter_map(|cmd| match cmd {
-                Command::Tree(tree) => Some(tree),
-                Command::Alias(_) => None,
-            })
-            .collect();
-
-        let mut commands: Vec<&CommandTree> = futures::stream::iter(commands.iter())
-            .filter(|tree| async {
-                if let Some(perm) = dispatcher.permissions.get(&tree.names[0]) {
-                    return sender.has_permission(perm.as_str()).await;
+        // Only list the commands the sender may run
+        let permitted: &CommandSender = sender;
+        let commands: Vec<&CommandTree> = futures::stream::iter(dispatcher.get_all_commands())
+            .filter(|&(name, _)| {
+                let permission = dispatcher.get_permission(name);
+                async move {
+                    match permission {
+                        Some(permission) => permitted.has_permission(permission).await,
+                        None => false,
+                    }
                 }
-                false
             })
+            .map(|(_, tree)| tree)
             .collect()
             .await;
 
-        commands.sort_by(|a, b| a.names[0].cmp(&b.names[0]));
-
-        let total_pages = (commands.len() as i32 + COMMANDS_PER_PAGE - 1) / COMMANDS_PER_PAGE;
+        let total_pages =
+            ((commands.len() as i32 + COMMANDS_PER_PAGE - 1) / COMMANDS_PER_PAGE).max(1);
         let page = page_number.min(total_pages);
 
         let start = (page - 1) * COMMANDS_PER_PAGE;
@@ -1,5 +1,8 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
-use pumpkin_data::entity;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::EntityType;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::hover::HoverEvent;
@@ -9,6 +12,8 @@ use crate::command::args::{Arg, ConsumedArgs};
 use crate::command::tree::CommandTree;
 use crate::command::tree::builder::{argument, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::EntityBase;
+use crate::server::Server;
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kill"];
@@ -16,6 +21,43 @@ const DESCRIPTION: &str = "Kills all target entities.";
 
 const ARG_TARGET: &str = "target";
 
+/// Kills the entity with lethal damage, so players go through their normal death and see the
+/// death screen. Entities which can not take damage, like items, are removed instead.
+async fn kill_entity(target: &Arc<dyn EntityBase>) {
+    if !target.damage(f32::MAX, DamageType::GENERIC_KILL).await {
+        let entity = target.get_entity();
+        if entity.entity_type != EntityType::PLAYER {
+            entity.remove().await;
+        }
+    }
+}
+
+async fn entity_display(server: &Server, target: &Arc<dyn EntityBase>) -> TextComponent {
+    let entity = target.get_entity();
+    let player = server.get_player_by_uuid(entity.entity_uuid).await;
+    let name = player.as_ref().map_or_else(
+        || {
+            TextComponent::translate(
+                format!("entity.minecraft.{}", entity.entity_type.resource_name),
+                [],
+            )
+        },
+        |player| TextComponent::text(player.gameprofile.name.clone()),
+    );
+
+    let mut entity_display = name.clone().hover_event(HoverEvent::show_entity(
+        entity.entity_uuid.to_string(),
+        entity.entity_type.resource_name.into(),
+        Some(name),
+    ));
+    if let Some(player) = player {
+        entity_display = entity_display.click_event(ClickEvent::SuggestCommand {
+            command: format!("/tell {} ", player.gameprofile.name).into(),
+        });
+    }
+    entity_display
+}
+
 struct Executor;
 
 #[async_trait]
@@ -23,40 +65,26 @@ impl CommandExecutor for Executor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let Some(Arg::Entities(targets)) = args.get(&ARG_TARGET) else {
             return Err(InvalidConsumption(Some(ARG_TARGET.into())));
         };
 
-        let target_count = targets.len();
-        let mut name = String::new();
         for target in targets {
-            target.kill().await;
-            name.clone_from(&target.gameprofile.name);
+            kill_entity(target).await;
         }
 
-        let msg = if target_count == 1 {
-            let entity = &targets[0].living_entity.entity;
-            let mut entity_display =
-                TextComponent::text(name.clone()).hover_event(HoverEvent::show_entity(
-                    entity.entity_uuid.to_string(),
-                    entity.entity_type.resource_name.into(),
-                    Some(TextComponent::text(name.clone())),
-                ));
-
-            if entity.entity_type == entity::EntityType::PLAYER {
-                entity_display = entity_display.click_event(ClickEvent::SuggestCommand {
-                    command: format!("/tell {} ", name.clone()).into(),
-                });
-            }
-
-            TextComponent::translate("commands.kill.success.single", [entity_display])
+        let msg = if let [target] = targets.as_slice() {
+            TextComponent::translate(
+                "commands.kill.success.single",
+                [entity_display(server, target).await],
+            )
         } else {
             TextComponent::translate(
                 "commands.kill.success.multiple",
-                [TextComponent::text(target_count.to_string())],
+                [TextComponent::text(targets.len().to_string())],
             )
         };
 
@@ -73,27 +101,18 @@ impl CommandExecutor for SelfExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender,
-        _server: &crate::server::Server,
+        server: &Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
-        let name = target.gameprofile.name.clone();
-        let entity = &target.living_entity.entity;
+        let target: Arc<dyn EntityBase> =
+            sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-        target.kill().await;
+        kill_entity(&target).await;
 
         sender
             .send_message(TextComponent::translate(
                 "commands.kill.success.single",
-                [TextComponent::text(name.clone())
-                    .hover_event(HoverEvent::show_entity(
-                        entity.entity_uuid.to_string(),
-                        entity.entity_type.resource_name.into(),
-                        Some(TextComponent::text(name.clone())),
-                    ))
-                    .click_event(ClickEvent::SuggestCommand {
-                        command: format!("/tell {} ", name.clone()).into(),
-                    })],
+                [entity_display(server, &target).await],
             ))
             .await;
 
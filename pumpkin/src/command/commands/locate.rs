@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::resource_location::ResourceLocation;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::click::ClickEvent;
+use pumpkin_util::text::color::NamedColor;
+use pumpkin_util::text::hover::HoverEvent;
+
+use crate::command::args::resource_location::ResourceLocationArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["locate"];
+
+const DESCRIPTION: &str = "Finds the nearest structure of a type.";
+
+const ARG_STRUCTURE: &str = "structure";
+
+/// How many chunks around the sender are searched, like vanilla
+const SEARCH_RADIUS: i32 = 100;
+
+struct StructureExecutor;
+
+#[async_trait]
+impl CommandExecutor for StructureExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let structure = ResourceLocationArgumentConsumer::find_arg(args, ARG_STRUCTURE)?;
+        let structure = match structure.split_once(':') {
+            Some((namespace, path)) => ResourceLocation {
+                namespace: namespace.to_string(),
+                path: path.to_string(),
+            },
+            None => ResourceLocation::vanilla(structure),
+        };
+        let world = match sender.world().await {
+            Some(world) => world,
+            None => server
+                .worlds
+                .read()
+                .await
+                .first()
+                .cloned()
+                .ok_or(CommandError::InvalidRequirement)?,
+        };
+        let center = if let Some(position) = sender.position() {
+            position.to_block_pos()
+        } else {
+            let level_info = world.level_info.read().await;
+            BlockPos::new(level_info.spawn_x, level_info.spawn_y, level_info.spawn_z)
+        };
+
+        let Some(pos) = world
+            .find_nearest_structure(center, &structure, SEARCH_RADIUS)
+            .await
+        else {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate(
+                    "commands.locate.structure.not_found",
+                    [TextComponent::text(structure.to_string())],
+                ),
+            )));
+        };
+
+        let dx = f64::from(pos.0.x - center.0.x);
+        let dz = f64::from(pos.0.z - center.0.z);
+        let distance = dx.hypot(dz).floor() as i32;
+        let coordinates = TextComponent::translate(
+            "chat.square_brackets",
+            [TextComponent::translate(
+                "chat.coordinates",
+                [
+                    TextComponent::text(pos.0.x.to_string()),
+                    TextComponent::text("~"),
+                    TextComponent::text(pos.0.z.to_string()),
+                ],
+            )],
+        )
+        .color_named(NamedColor::Green)
+        .click_event(ClickEvent::SuggestCommand {
+            command: Cow::from(format!("/tp @s {} ~ {}", pos.0.x, pos.0.z)),
+        })
+        .hover_event(HoverEvent::show_text(TextComponent::translate(
+            "chat.coordinates.tooltip",
+            [],
+        )));
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.locate.structure.success",
+                [
+                    TextComponent::text(structure.to_string()),
+                    coordinates,
+                    TextComponent::text(distance.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("structure").then(
+            argument(ARG_STRUCTURE, ResourceLocationArgumentConsumer::new(false))
+                .execute(StructureExecutor),
+        ),
+    )
+}
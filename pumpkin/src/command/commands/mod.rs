@@ -17,6 +17,7 @@ pub mod defaultgamemode;
 mod deop;
 mod difficulty;
 mod effect;
+mod enchant;
 mod experience;
 mod fill;
 mod gamemode;
@@ -26,27 +27,35 @@ mod help;
 mod kick;
 mod kill;
 mod list;
+mod locate;
 mod me;
 mod msg;
+mod network;
 mod op;
 mod pardon;
 mod pardonip;
 mod particle;
+mod place;
 mod playsound;
 mod plugin;
 mod plugins;
 mod pumpkin;
+mod reload;
+mod save_all;
 mod say;
+mod scoreboard;
 mod seed;
 mod setblock;
 mod stop;
 mod stopsound;
 mod summon;
+mod team;
 mod teleport;
 mod tellraw;
 mod tick;
 mod time;
 mod title;
+mod tps;
 mod transfer;
 mod weather;
 mod whitelist;
@@ -57,9 +66,14 @@ mod profile;
 
 #[must_use]
 pub async fn default_dispatcher() -> CommandDispatcher {
-    let mut dispatcher = CommandDispatcher::default();
-
     register_permissions().await;
+    builtin_dispatcher()
+}
+
+/// Creates a dispatcher containing only the builtin commands, without registering their permissions.
+#[must_use]
+pub fn builtin_dispatcher() -> CommandDispatcher {
+    let mut dispatcher = CommandDispatcher::default();
 
     // Zero
     dispatcher.register(pumpkin::init_command_tree(), "pumpkin:command.pumpkin");
@@ -74,6 +88,7 @@ pub async fn default_dispatcher() -> CommandDispatcher {
         "minecraft:command.worldborder",
     );
     dispatcher.register(effect::init_command_tree(), "minecraft:command.effect");
+    dispatcher.register(enchant::init_command_tree(), "minecraft:command.enchant");
     dispatcher.register(teleport::init_command_tree(), "minecraft:command.teleport");
     dispatcher.register(time::init_command_tree(), "minecraft:command.time");
     dispatcher.register(tick::init_command_tree(), "minecraft:command.tick");
@@ -95,8 +110,15 @@ pub async fn default_dispatcher() -> CommandDispatcher {
     );
     dispatcher.register(weather::init_command_tree(), "minecraft:command.weather");
     dispatcher.register(particle::init_command_tree(), "minecraft:command.particle");
+    dispatcher.register(place::init_command_tree(), "minecraft:command.place");
+    dispatcher.register(locate::init_command_tree(), "minecraft:command.locate");
     dispatcher.register(damage::init_command_tree(), "minecraft:command.damage");
     dispatcher.register(bossbar::init_command_tree(), "minecraft:command.bossbar");
+    dispatcher.register(
+        scoreboard::init_command_tree(),
+        "minecraft:command.scoreboard",
+    );
+    dispatcher.register(team::init_command_tree(), "minecraft:command.team");
     dispatcher.register(say::init_command_tree(), "minecraft:command.say");
     dispatcher.register(gamemode::init_command_tree(), "minecraft:command.gamemode");
     dispatcher.register(gamerule::init_command_tree(), "minecraft:command.gamerule");
@@ -116,8 +138,10 @@ pub async fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(op::init_command_tree(), "minecraft:command.op");
     dispatcher.register(deop::init_command_tree(), "minecraft:command.deop");
     dispatcher.register(kick::init_command_tree(), "minecraft:command.kick");
+    dispatcher.register(network::init_command_tree(), "pumpkin:command.network");
     dispatcher.register(plugin::init_command_tree(), "pumpkin:command.plugin");
     dispatcher.register(plugins::init_command_tree(), "pumpkin:command.plugins");
+    dispatcher.register(tps::init_command_tree(), "pumpkin:command.tps");
     dispatcher.register(ban::init_command_tree(), "minecraft:command.ban");
     dispatcher.register(banip::init_command_tree(), "minecraft:command.banip");
     dispatcher.register(banlist::init_command_tree(), "minecraft:command.banlist");
@@ -128,8 +152,10 @@ pub async fn default_dispatcher() -> CommandDispatcher {
         "minecraft:command.whitelist",
     );
     dispatcher.register(transfer::init_command_tree(), "minecraft:command.transfer");
+    dispatcher.register(reload::init_command_tree(), "minecraft:command.reload");
     // Four
     dispatcher.register(stop::init_command_tree(), "minecraft:command.stop");
+    dispatcher.register(save_all::init_command_tree(), "minecraft:command.save-all");
 
     #[cfg(feature = "dhat-heap")]
     dispatcher.register(profile::init_command_tree(), "pumpkin:command.profile");
@@ -140,6 +166,8 @@ pub async fn default_dispatcher() -> CommandDispatcher {
 async fn register_permissions() {
     let mut registry = PERMISSION_REGISTRY.write().await;
 
+    register_op_level_permissions(&mut registry);
+
     // Register level 0 permissions (allowed by default)
     register_level_0_permissions(&mut registry);
 
@@ -153,6 +181,33 @@ async fn register_permissions() {
     register_level_4_permissions(&mut registry);
 }
 
+/// One node per op level, granted to operators of that level. Numeric level checks go through
+/// these, see [`crate::command::CommandSender::op_level`].
+pub const OP_LEVEL_PERMISSIONS: [&str; 4] = [
+    "pumpkin:op_level.1",
+    "pumpkin:op_level.2",
+    "pumpkin:op_level.3",
+    "pumpkin:op_level.4",
+];
+
+fn register_op_level_permissions(registry: &mut PermissionRegistry) {
+    let levels = [
+        PermissionLvl::One,
+        PermissionLvl::Two,
+        PermissionLvl::Three,
+        PermissionLvl::Four,
+    ];
+    for (node, level) in OP_LEVEL_PERMISSIONS.into_iter().zip(levels) {
+        registry
+            .register_permission(Permission::new(
+                node,
+                "Grants everything an operator of this level may do",
+                PermissionDefault::Op(level),
+            ))
+            .unwrap();
+    }
+}
+
 fn register_level_0_permissions(registry: &mut PermissionRegistry) {
     // Register permissions for builtin commands that are allowed for everyone
     registry
@@ -202,6 +257,20 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.scoreboard",
+            "Manages scoreboard objectives and players",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.team",
+            "Controls teams",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.worldborder",
@@ -216,6 +285,13 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.enchant",
+            "Adds an enchantment to a player's selected item",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.teleport",
@@ -314,6 +390,20 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.place",
+            "Places a feature in the world",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.locate",
+            "Finds the nearest structure",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.damage",
@@ -321,6 +411,13 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Two),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:notify.tick_rate",
+            "Receives a message when the tick rate is adapted to the server's performance",
+            PermissionDefault::Op(PermissionLvl::Two),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.bossbar",
@@ -372,6 +469,7 @@ fn register_level_2_permissions(registry: &mut PermissionRegistry) {
         .unwrap();
 }
 
+#[allow(clippy::too_many_lines)]
 fn register_level_3_permissions(registry: &mut PermissionRegistry) {
     // Register permissions for commands with PermissionLvl::Three
     registry
@@ -395,6 +493,13 @@ fn register_level_3_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Three),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.network",
+            "Shows the network traffic of players",
+            PermissionDefault::Op(PermissionLvl::Three),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "pumpkin:command.plugin",
@@ -409,6 +514,13 @@ fn register_level_3_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Three),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.tps",
+            "Shows the ticks per second of the server",
+            PermissionDefault::Op(PermissionLvl::Three),
+        ))
+        .unwrap();
     registry
         .register_permission(Permission::new(
             "minecraft:command.ban",
@@ -465,6 +577,13 @@ fn register_level_3_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Three),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.reload",
+            "Reloads the configuration and plugins",
+            PermissionDefault::Op(PermissionLvl::Three),
+        ))
+        .unwrap();
 }
 
 fn register_level_4_permissions(registry: &mut PermissionRegistry) {
@@ -476,6 +595,20 @@ fn register_level_4_permissions(registry: &mut PermissionRegistry) {
             PermissionDefault::Op(PermissionLvl::Four),
         ))
         .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "minecraft:command.save-all",
+            "Saves the server to disk",
+            PermissionDefault::Op(PermissionLvl::Four),
+        ))
+        .unwrap();
+    registry
+        .register_permission(Permission::new(
+            "pumpkin:command.enchant.beyond_max_level",
+            "Enchants items beyond the maximum level of the enchantment",
+            PermissionDefault::Op(PermissionLvl::Four),
+        ))
+        .unwrap();
 
     #[cfg(feature = "dhat-heap")]
     registry
@@ -0,0 +1,91 @@
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_protocol::java::statistics::PacketStatistics;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::net::ClientPlatform;
+use CommandError::InvalidConsumption;
+
+const NAMES: [&str; 1] = ["network"];
+const DESCRIPTION: &str = "Shows the network traffic of players.";
+
+const ARG_TARGETS: &str = "targets";
+
+fn statistics_text(name: &str, statistics: &PacketStatistics) -> TextComponent {
+    TextComponent::text(format!(
+        "{name}: sent {} bytes in {} packets, received {} bytes in {} packets",
+        statistics.bytes_sent.load(Ordering::Relaxed),
+        statistics.packets_sent.load(Ordering::Relaxed),
+        statistics.bytes_received.load(Ordering::Relaxed),
+        statistics.packets_received.load(Ordering::Relaxed),
+    ))
+}
+
+struct TotalExecutor;
+
+#[async_trait]
+impl CommandExecutor for TotalExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let total = PacketStatistics::default();
+        let mut players = 0;
+        for player in server.get_all_players().await {
+            if let ClientPlatform::Java(client) = &player.client {
+                total.add(&client.statistics);
+                players += 1;
+            }
+        }
+
+        sender
+            .send_message(statistics_text(&format!("{players} players"), &total))
+            .await;
+        Ok(())
+    }
+}
+
+struct PlayersExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+
+        for target in targets {
+            let name = &target.gameprofile.name;
+            // Bedrock connections don't go through the Java encoder, so they aren't counted
+            let message = match &target.client {
+                ClientPlatform::Java(client) => statistics_text(name, &client.statistics),
+                ClientPlatform::Bedrock(_) => {
+                    TextComponent::text(format!("{name}: no statistics for Bedrock players"))
+                }
+            };
+            sender.send_message(message).await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("stats")
+            .execute(TotalExecutor)
+            .then(argument(ARG_TARGETS, PlayersArgumentConsumer).execute(PlayersExecutor)),
+    )
+}
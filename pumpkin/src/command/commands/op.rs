@@ -32,10 +32,10 @@ impl CommandExecutor for Executor {
             return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
         };
 
+        // Senders can't grant a higher level than their own
+        let sender_level = sender.op_level().await;
         for player in targets {
-            let new_level = BASIC_CONFIG
-                .op_permission_level
-                .min(sender.permission_lvl());
+            let new_level = BASIC_CONFIG.op_permission_level.min(sender_level);
 
             if player.permission_lvl.load() == new_level {
                 sender
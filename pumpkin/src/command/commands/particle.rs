@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
 
@@ -5,9 +7,13 @@ use crate::command::{
     CommandError, CommandExecutor, CommandSender,
     args::{
         ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
-        position_3d::Position3DArgumentConsumer, resource::particle::ParticleArgumentConsumer,
+        players::PlayersArgumentConsumer, position_3d::Position3DArgumentConsumer,
+        resource::particle::ParticleArgumentConsumer,
+    },
+    tree::{
+        CommandTree,
+        builder::{NonLeafNodeBuilder, argument, literal},
     },
-    tree::{CommandTree, builder::argument},
 };
 const NAMES: [&str; 1] = ["particle"];
 
@@ -19,8 +25,12 @@ const ARG_POS: &str = "pos";
 const ARG_DELTA: &str = "delta";
 const ARG_SPEED: &str = "speed";
 const ARG_COUNT: &str = "count";
+const ARG_VIEWERS: &str = "viewers";
 
-struct Executor;
+struct Executor {
+    /// Whether players further away or with particles turned down see it too
+    force: bool,
+}
 
 #[async_trait]
 impl CommandExecutor for Executor {
@@ -30,7 +40,7 @@ impl CommandExecutor for Executor {
         server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let particle = ParticleArgumentConsumer::find_arg(args, ARG_NAME)?;
+        let (particle, data) = ParticleArgumentConsumer::find_arg(args, ARG_NAME)?;
         let pos = Position3DArgumentConsumer::find_arg(args, ARG_POS);
         let delta = Position3DArgumentConsumer::find_arg(args, ARG_DELTA);
         let speed = BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_SPEED);
@@ -67,9 +77,24 @@ impl CommandExecutor for Executor {
             }
         };
 
-        world
-            .spawn_particle(pos, delta, speed, count, *particle)
-            .await;
+        let viewers = match PlayersArgumentConsumer::find_arg(args, ARG_VIEWERS) {
+            Ok(viewers) => viewers.to_vec(),
+            Err(_) => world.players.read().await.values().cloned().collect(),
+        };
+        let mut sent = false;
+        for viewer in viewers {
+            if !Arc::ptr_eq(&viewer.world().await, &world) {
+                continue;
+            }
+            sent |= viewer
+                .spawn_particle(pos, delta, speed, count, particle, data, self.force)
+                .await;
+        }
+        if !sent {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.particle.failed", []),
+            )));
+        }
 
         sender
             .send_message(TextComponent::translate(
@@ -82,28 +107,36 @@ impl CommandExecutor for Executor {
     }
 }
 
+fn display_mode(name: &'static str, force: bool) -> NonLeafNodeBuilder {
+    literal(name)
+        .execute(Executor { force })
+        .then(argument(ARG_VIEWERS, PlayersArgumentConsumer).execute(Executor { force }))
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_NAME, ParticleArgumentConsumer)
-            .execute(Executor)
+            .execute(Executor { force: false })
             .then(
                 argument(ARG_POS, Position3DArgumentConsumer)
-                    .execute(Executor)
+                    .execute(Executor { force: false })
                     .then(
                         argument(ARG_DELTA, Position3DArgumentConsumer)
-                            .execute(Executor)
+                            .execute(Executor { force: false })
                             .then(
                                 argument(
                                     ARG_SPEED,
                                     BoundedNumArgumentConsumer::<f32>::new().min(0.0),
                                 )
-                                .execute(Executor)
+                                .execute(Executor { force: false })
                                 .then(
                                     argument(
                                         ARG_COUNT,
                                         BoundedNumArgumentConsumer::<i32>::new().min(0),
                                     )
-                                    .execute(Executor),
+                                    .execute(Executor { force: false })
+                                    .then(display_mode("force", true))
+                                    .then(display_mode("normal", false)),
                                 ),
                             ),
                     ),
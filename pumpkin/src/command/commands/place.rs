@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use pumpkin_util::resource_location::ResourceLocation;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::resource_location::ResourceLocationArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["place"];
+
+const DESCRIPTION: &str = "Places a configured feature at a location.";
+
+const ARG_FEATURE: &str = "feature";
+const ARG_POS: &str = "pos";
+
+struct FeatureExecutor;
+
+#[async_trait]
+impl CommandExecutor for FeatureExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let feature = ResourceLocationArgumentConsumer::find_arg(args, ARG_FEATURE)?;
+        let feature = match feature.split_once(':') {
+            Some((namespace, path)) => ResourceLocation {
+                namespace: namespace.to_string(),
+                path: path.to_string(),
+            },
+            None => ResourceLocation::vanilla(feature),
+        };
+        let pos = match BlockPosArgumentConsumer::find_arg(args, ARG_POS) {
+            Ok(pos) => pos,
+            Err(_) => sender
+                .position()
+                .ok_or(CommandError::InvalidRequirement)?
+                .to_block_pos(),
+        };
+        let world = match sender.world().await {
+            Some(world) => world,
+            None => server
+                .worlds
+                .read()
+                .await
+                .first()
+                .cloned()
+                .ok_or(CommandError::InvalidRequirement)?,
+        };
+
+        // The thread local generator can not be held across an await
+        let mut rng = StdRng::from_rng(&mut rand::rng());
+        if !world.place_feature(feature, pos, &mut rng).await {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.place.feature.failed", [])
+                    .color_named(NamedColor::Red),
+            )));
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.place.feature.success",
+                [
+                    TextComponent::text(pos.0.x.to_string()),
+                    TextComponent::text(pos.0.y.to_string()),
+                    TextComponent::text(pos.0.z.to_string()),
+                ],
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("feature").then(
+            argument(ARG_FEATURE, ResourceLocationArgumentConsumer::new(false))
+                .execute(FeatureExecutor)
+                .then(argument(ARG_POS, BlockPosArgumentConsumer).execute(FeatureExecutor)),
+        ),
+    )
+}
@@ -1,10 +1,7 @@
 use std::path::Path;
 
 use async_trait::async_trait;
-use pumpkin_util::{
-    PermissionLvl,
-    text::{TextComponent, color::NamedColor, hover::HoverEvent},
-};
+use pumpkin_util::text::{TextComponent, color::NamedColor, hover::HoverEvent};
 
 use crate::{
     PLUGIN_MANAGER,
@@ -13,7 +10,7 @@ use crate::{
         args::{Arg, ConsumedArgs, simple::SimpleArgConsumer},
         tree::{
             CommandTree,
-            builder::{argument, literal, require},
+            builder::{argument, literal},
         },
     },
 };
@@ -172,16 +169,11 @@ impl CommandExecutor for UnloadExecutor {
 }
 
 pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION).then(
-        require(|sender| sender.has_permission_lvl(PermissionLvl::Three))
-            .then(
-                literal("load")
-                    .then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(LoadExecutor)),
-            )
-            .then(
-                literal("unload")
-                    .then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(UnloadExecutor)),
-            )
-            .then(literal("list").execute(ListExecutor)),
-    )
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("load").then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(LoadExecutor)))
+        .then(
+            literal("unload")
+                .then(argument(PLUGIN_NAME, SimpleArgConsumer).execute(UnloadExecutor)),
+        )
+        .then(literal("list").execute(ListExecutor))
 }
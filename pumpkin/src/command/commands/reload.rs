@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::literal;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["reload"];
+
+const DESCRIPTION: &str = "Reloads the configuration and plugins.";
+
+struct Executor;
+
+struct IconExecutor;
+
+#[async_trait]
+impl CommandExecutor for IconExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        server.reload_icon().await;
+        sender
+            .send_message(TextComponent::text("Reloaded the server icon"))
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // The reply is sent once the reload is done
+        server.request_reload(sender.clone()).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("icon").execute(IconExecutor))
+        .execute(Executor)
+}
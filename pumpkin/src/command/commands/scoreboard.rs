@@ -0,0 +1,613 @@
+use async_trait::async_trait;
+use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::RenderType;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::hover::HoverEvent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::score_holder::ScoreHoldersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::textcomponent::TextComponentArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, argument_default_name, literal};
+use crate::command::{CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::scoreboard::{
+    DISPLAY_SLOTS, Scoreboard, ScoreboardObjective, ScoreboardScore, display_slot_name,
+};
+
+const NAMES: [&str; 1] = ["scoreboard"];
+const DESCRIPTION: &str = "Manages scoreboard objectives and players.";
+
+const ARG_OBJECTIVE: &str = "objective";
+const ARG_CRITERIA: &str = "criteria";
+const ARG_DISPLAY_NAME: &str = "displayName";
+
+fn score_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("score")
+}
+
+/// The objective's display name in brackets, showing its name on hover like vanilla.
+fn objective_display(scoreboard: &Scoreboard, name: &str) -> TextComponent {
+    let display_name = scoreboard
+        .objective_display_name(name)
+        .cloned()
+        .unwrap_or_else(|| TextComponent::text(name.to_string()));
+    TextComponent::text("[")
+        .add_child(display_name)
+        .add_child(TextComponent::text("]"))
+        .hover_event(HoverEvent::show_text(TextComponent::text(name.to_string())))
+}
+
+fn find_objective<'a>(
+    scoreboard: &Scoreboard,
+    args: &'a ConsumedArgs<'a>,
+) -> Result<&'a str, CommandError> {
+    let name = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+    if !scoreboard.has_objective(name) {
+        return Err(CommandError::CommandFailed(Box::new(
+            TextComponent::translate(
+                "arguments.objective.notFound",
+                [TextComponent::text(name.to_string())],
+            ),
+        )));
+    }
+    Ok(name)
+}
+
+struct ObjectivesListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ObjectivesListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+
+        let mut names: Vec<&String> = scoreboard.objectives().map(|(name, _)| name).collect();
+        if names.is_empty() {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.objectives.list.empty",
+                    [],
+                ))
+                .await;
+            return Ok(());
+        }
+        names.sort();
+
+        let mut list = TextComponent::text("");
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                list = list.add_child(TextComponent::text(", "));
+            }
+            list = list.add_child(objective_display(&scoreboard, name));
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.list.success",
+                [TextComponent::text(names.len().to_string()), list],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct ObjectivesAddExecutor;
+
+#[async_trait]
+impl CommandExecutor for ObjectivesAddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let criteria = SimpleArgConsumer::find_arg(args, ARG_CRITERIA)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if scoreboard.has_objective(name) {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.scoreboard.objectives.add.duplicate", []),
+            )));
+        }
+        scoreboard
+            .add_objective(
+                server,
+                ScoreboardObjective::new(name, display_name, criteria, RenderType::Integer, None),
+            )
+            .await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.add.success",
+                [objective_display(&scoreboard, name)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct ObjectivesRemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for ObjectivesRemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_objective(&scoreboard, args)?;
+        let display = objective_display(&scoreboard, name);
+        scoreboard.remove_objective(server, name).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.remove.success",
+                [display],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct ObjectivesSetDisplayExecutor(ScoreboardDisplaySlot);
+
+#[async_trait]
+impl CommandExecutor for ObjectivesSetDisplayExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let slot = self.0;
+        let slot_name = TextComponent::text(display_slot_name(slot));
+        let mut scoreboard = server.scoreboard.lock().await;
+
+        // Without an objective the slot gets cleared
+        if SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE).is_err() {
+            if scoreboard.display_slot(slot).is_none() {
+                return Err(CommandError::CommandFailed(Box::new(
+                    TextComponent::translate(
+                        "commands.scoreboard.objectives.display.alreadyEmpty",
+                        [],
+                    ),
+                )));
+            }
+            scoreboard.set_display_slot(server, slot, None).await;
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.objectives.display.cleared",
+                    [slot_name],
+                ))
+                .await;
+            return Ok(());
+        }
+
+        let name = find_objective(&scoreboard, args)?;
+        if scoreboard
+            .display_slot(slot)
+            .is_some_and(|shown| shown == name)
+        {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.scoreboard.objectives.display.alreadySet", []),
+            )));
+        }
+        scoreboard.set_display_slot(server, slot, Some(name)).await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.display.set",
+                [slot_name, objective_display(&scoreboard, name)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+enum ObjectiveModification {
+    DisplayName,
+    RenderType(RenderType),
+}
+
+struct ObjectivesModifyExecutor(ObjectiveModification);
+
+#[async_trait]
+impl CommandExecutor for ObjectivesModifyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_objective(&scoreboard, args)?;
+
+        let message = match self.0 {
+            ObjectiveModification::DisplayName => {
+                let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)?;
+                scoreboard
+                    .update_objective(server, name, Some(display_name.clone()), None)
+                    .await;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.displayname",
+                    [TextComponent::text(name.to_string()), display_name],
+                )
+            }
+            ObjectiveModification::RenderType(render_type) => {
+                scoreboard
+                    .update_objective(server, name, None, Some(render_type))
+                    .await;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.rendertype",
+                    [objective_display(&scoreboard, name)],
+                )
+            }
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersListExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+
+        // Without a target all holders get listed
+        let Ok(targets) = ScoreHoldersArgumentConsumer.find_arg_default_name(args) else {
+            let mut holders: Vec<&String> = scoreboard.holders().collect();
+            if holders.is_empty() {
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.scoreboard.players.list.empty",
+                        [],
+                    ))
+                    .await;
+                return Ok(());
+            }
+            holders.sort();
+            let list = holders
+                .iter()
+                .map(|holder| holder.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.success",
+                    [
+                        TextComponent::text(holders.len().to_string()),
+                        TextComponent::text(list),
+                    ],
+                ))
+                .await;
+            return Ok(());
+        };
+
+        for target in targets {
+            let holder = TextComponent::text(target.clone());
+            let Some(scores) = scoreboard
+                .holder_scores(target)
+                .filter(|scores| !scores.is_empty())
+            else {
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.scoreboard.players.list.entity.empty",
+                        [holder],
+                    ))
+                    .await;
+                continue;
+            };
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.entity.success",
+                    [holder, TextComponent::text(scores.len().to_string())],
+                ))
+                .await;
+            let mut scores: Vec<(&String, &i32)> = scores.iter().collect();
+            scores.sort();
+            for (objective_name, value) in scores {
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.scoreboard.players.list.entity.entry",
+                        [
+                            objective_display(&scoreboard, objective_name),
+                            TextComponent::text(value.to_string()),
+                        ],
+                    ))
+                    .await;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct PlayersGetExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersGetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHoldersArgumentConsumer.find_arg_default_name(args)?;
+        let scoreboard = server.scoreboard.lock().await;
+        let name = find_objective(&scoreboard, args)?;
+
+        let [target] = targets else {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("argument.scoreHolder.tooMany", []),
+            )));
+        };
+        let Some(value) = scoreboard.get_score(target, name) else {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate(
+                    "commands.scoreboard.players.get.null",
+                    [
+                        TextComponent::text(name.to_string()),
+                        TextComponent::text(target.clone()),
+                    ],
+                ),
+            )));
+        };
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.players.get.success",
+                [
+                    TextComponent::text(target.clone()),
+                    TextComponent::text(value.to_string()),
+                    objective_display(&scoreboard, name),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ScoreChange {
+    Set,
+    Add,
+    Remove,
+}
+
+struct PlayersChangeExecutor(ScoreChange);
+
+#[async_trait]
+impl CommandExecutor for PlayersChangeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHoldersArgumentConsumer.find_arg_default_name(args)?;
+        let amount = score_consumer().find_arg_default_name(args)??;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_objective(&scoreboard, args)?;
+
+        let mut last_value = 0;
+        for target in targets {
+            let current = scoreboard.get_score(target, name).unwrap_or(0);
+            last_value = match self.0 {
+                ScoreChange::Set => amount,
+                ScoreChange::Add => current.wrapping_add(amount),
+                ScoreChange::Remove => current.wrapping_sub(amount),
+            };
+            scoreboard
+                .update_score(
+                    server,
+                    ScoreboardScore::new(target, name, VarInt(last_value), None, None),
+                )
+                .await;
+        }
+
+        let key = match self.0 {
+            ScoreChange::Set => "set",
+            ScoreChange::Add => "add",
+            ScoreChange::Remove => "remove",
+        };
+        let objective = objective_display(&scoreboard, name);
+        let amount = TextComponent::text(amount.to_string());
+        let message = match (self.0, targets) {
+            (ScoreChange::Set, [target]) => TextComponent::translate(
+                "commands.scoreboard.players.set.success.single",
+                [objective, TextComponent::text(target.clone()), amount],
+            ),
+            (ScoreChange::Set, _) => TextComponent::translate(
+                "commands.scoreboard.players.set.success.multiple",
+                [
+                    objective,
+                    TextComponent::text(targets.len().to_string()),
+                    amount,
+                ],
+            ),
+            (_, [target]) => TextComponent::translate(
+                format!("commands.scoreboard.players.{key}.success.single"),
+                [
+                    amount,
+                    objective,
+                    TextComponent::text(target.clone()),
+                    TextComponent::text(last_value.to_string()),
+                ],
+            ),
+            (_, _) => TextComponent::translate(
+                format!("commands.scoreboard.players.{key}.success.multiple"),
+                [
+                    amount,
+                    objective,
+                    TextComponent::text(targets.len().to_string()),
+                ],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct PlayersResetExecutor;
+
+#[async_trait]
+impl CommandExecutor for PlayersResetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHoldersArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let objective = if SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE).is_ok() {
+            Some(find_objective(&scoreboard, args)?)
+        } else {
+            None
+        };
+
+        for target in targets {
+            scoreboard.reset_score(server, target, objective).await;
+        }
+
+        let count = TextComponent::text(targets.len().to_string());
+        let message = match (objective, targets) {
+            (None, [target]) => TextComponent::translate(
+                "commands.scoreboard.players.reset.all.single",
+                [TextComponent::text(target.clone())],
+            ),
+            (None, _) => {
+                TextComponent::translate("commands.scoreboard.players.reset.all.multiple", [count])
+            }
+            (Some(objective), [target]) => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.single",
+                [
+                    objective_display(&scoreboard, objective),
+                    TextComponent::text(target.clone()),
+                ],
+            ),
+            (Some(objective), _) => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.multiple",
+                [objective_display(&scoreboard, objective), count],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+fn objectives_tree() -> NonLeafNodeBuilder {
+    let mut set_display = literal("setdisplay");
+    for slot in DISPLAY_SLOTS {
+        set_display = set_display.then(
+            literal(display_slot_name(slot))
+                .then(
+                    argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                        .execute(ObjectivesSetDisplayExecutor(slot)),
+                )
+                .execute(ObjectivesSetDisplayExecutor(slot)),
+        );
+    }
+
+    literal("objectives")
+        .then(literal("list").execute(ObjectivesListExecutor))
+        .then(
+            literal("add").then(
+                argument(ARG_OBJECTIVE, SimpleArgConsumer).then(
+                    argument(ARG_CRITERIA, SimpleArgConsumer)
+                        .then(
+                            argument(ARG_DISPLAY_NAME, TextComponentArgConsumer)
+                                .execute(ObjectivesAddExecutor),
+                        )
+                        .execute(ObjectivesAddExecutor),
+                ),
+            ),
+        )
+        .then(
+            literal("remove")
+                .then(argument(ARG_OBJECTIVE, SimpleArgConsumer).execute(ObjectivesRemoveExecutor)),
+        )
+        .then(set_display)
+        .then(
+            literal("modify").then(
+                argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                    .then(
+                        literal("displayname").then(
+                            argument(ARG_DISPLAY_NAME, TextComponentArgConsumer).execute(
+                                ObjectivesModifyExecutor(ObjectiveModification::DisplayName),
+                            ),
+                        ),
+                    )
+                    .then(
+                        literal("rendertype")
+                            .then(literal("hearts").execute(ObjectivesModifyExecutor(
+                                ObjectiveModification::RenderType(RenderType::Hearts),
+                            )))
+                            .then(literal("integer").execute(ObjectivesModifyExecutor(
+                                ObjectiveModification::RenderType(RenderType::Integer),
+                            ))),
+                    ),
+            ),
+        )
+}
+
+fn change_tree(name: &str, change: ScoreChange) -> NonLeafNodeBuilder {
+    literal(name).then(
+        argument_default_name(ScoreHoldersArgumentConsumer).then(
+            argument(ARG_OBJECTIVE, SimpleArgConsumer).then(
+                argument_default_name(score_consumer()).execute(PlayersChangeExecutor(change)),
+            ),
+        ),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(objectives_tree())
+        .then(
+            literal("players")
+                .then(
+                    literal("list")
+                        .then(
+                            argument_default_name(ScoreHoldersArgumentConsumer)
+                                .execute(PlayersListExecutor),
+                        )
+                        .execute(PlayersListExecutor),
+                )
+                .then(literal("get").then(
+                    argument_default_name(ScoreHoldersArgumentConsumer).then(
+                        argument(ARG_OBJECTIVE, SimpleArgConsumer).execute(PlayersGetExecutor),
+                    ),
+                ))
+                .then(change_tree("set", ScoreChange::Set))
+                .then(change_tree("add", ScoreChange::Add))
+                .then(change_tree("remove", ScoreChange::Remove))
+                .then(
+                    literal("reset").then(
+                        argument_default_name(ScoreHoldersArgumentConsumer)
+                            .then(
+                                argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                                    .execute(PlayersResetExecutor),
+                            )
+                            .execute(PlayersResetExecutor),
+                    ),
+                ),
+        )
+}
@@ -0,0 +1,570 @@
+use async_trait::async_trait;
+use pumpkin_protocol::java::client::play::{CollisionRule, NameTagVisibility};
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+use pumpkin_util::text::hover::HoverEvent;
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::score_holder::ScoreHoldersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::textcomponent::TextComponentArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, argument_default_name, literal};
+use crate::command::{CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::scoreboard::{
+    NAMED_COLORS, Scoreboard, Team, collision_rule_name, color_name, name_tag_visibility_name,
+};
+
+const NAMES: [&str; 1] = ["team"];
+const DESCRIPTION: &str = "Controls teams.";
+
+const ARG_TEAM: &str = "team";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_VALUE: &str = "value";
+
+/// The team's display name in brackets, showing its name on hover like vanilla.
+fn team_display(scoreboard: &Scoreboard, name: &str) -> TextComponent {
+    let display_name = scoreboard.team(name).map_or_else(
+        || TextComponent::text(name.to_string()),
+        |team| team.display_name.clone(),
+    );
+    TextComponent::text("[")
+        .add_child(display_name)
+        .add_child(TextComponent::text("]"))
+        .hover_event(HoverEvent::show_text(TextComponent::text(name.to_string())))
+}
+
+fn find_team<'a>(
+    scoreboard: &Scoreboard,
+    args: &'a ConsumedArgs<'a>,
+) -> Result<&'a str, CommandError> {
+    let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+    if scoreboard.team(name).is_none() {
+        return Err(CommandError::CommandFailed(Box::new(
+            TextComponent::translate("team.notFound", [TextComponent::text(name.to_string())]),
+        )));
+    }
+    Ok(name)
+}
+
+struct AddExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+
+        let mut scoreboard = server.scoreboard.lock().await;
+        if scoreboard.team(name).is_some() {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.team.add.duplicate", []),
+            )));
+        }
+        scoreboard
+            .add_team(server, name, Team::new(display_name))
+            .await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.add.success",
+                [team_display(&scoreboard, name)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_team(&scoreboard, args)?;
+        let display = team_display(&scoreboard, name);
+        scoreboard.remove_team(server, name).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.remove.success",
+                [display],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct EmptyExecutor;
+
+#[async_trait]
+impl CommandExecutor for EmptyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_team(&scoreboard, args)?;
+        let members: Vec<String> = scoreboard
+            .team(name)
+            .map(|team| team.members().cloned().collect())
+            .unwrap_or_default();
+        if members.is_empty() {
+            return Err(CommandError::CommandFailed(Box::new(
+                TextComponent::translate("commands.team.empty.unchanged", []),
+            )));
+        }
+        scoreboard.leave_team(server, &members).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.empty.success",
+                [
+                    TextComponent::text(members.len().to_string()),
+                    team_display(&scoreboard, name),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct JoinExecutor;
+
+#[async_trait]
+impl CommandExecutor for JoinExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // Without members the sender joins the team
+        let members = match ScoreHoldersArgumentConsumer.find_arg_default_name(args) {
+            Ok(members) => members.to_vec(),
+            Err(_) => vec![
+                sender
+                    .as_player()
+                    .ok_or(CommandError::InvalidRequirement)?
+                    .gameprofile
+                    .name
+                    .clone(),
+            ],
+        };
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_team(&scoreboard, args)?;
+        scoreboard.join_team(server, name, &members).await;
+
+        let message = if let [member] = members.as_slice() {
+            TextComponent::translate(
+                "commands.team.join.success.single",
+                [
+                    TextComponent::text(member.clone()),
+                    team_display(&scoreboard, name),
+                ],
+            )
+        } else {
+            TextComponent::translate(
+                "commands.team.join.success.multiple",
+                [
+                    TextComponent::text(members.len().to_string()),
+                    team_display(&scoreboard, name),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct LeaveExecutor;
+
+#[async_trait]
+impl CommandExecutor for LeaveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let members = ScoreHoldersArgumentConsumer.find_arg_default_name(args)?;
+        server
+            .scoreboard
+            .lock()
+            .await
+            .leave_team(server, members)
+            .await;
+
+        let message = if let [member] = members {
+            TextComponent::translate(
+                "commands.team.leave.success.single",
+                [TextComponent::text(member.clone())],
+            )
+        } else {
+            TextComponent::translate(
+                "commands.team.leave.success.multiple",
+                [TextComponent::text(members.len().to_string())],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+
+        // Without a team all teams get listed
+        if SimpleArgConsumer::find_arg(args, ARG_TEAM).is_err() {
+            let mut names: Vec<&String> = scoreboard.teams().map(|(name, _)| name).collect();
+            if names.is_empty() {
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.team.list.teams.empty",
+                        [],
+                    ))
+                    .await;
+                return Ok(());
+            }
+            names.sort();
+            let mut list = TextComponent::text("");
+            for (i, name) in names.iter().enumerate() {
+                if i > 0 {
+                    list = list.add_child(TextComponent::text(", "));
+                }
+                list = list.add_child(team_display(&scoreboard, name));
+            }
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.team.list.teams.success",
+                    [TextComponent::text(names.len().to_string()), list],
+                ))
+                .await;
+            return Ok(());
+        }
+
+        let name = find_team(&scoreboard, args)?;
+        let members: Vec<&String> = scoreboard
+            .team(name)
+            .map(|team| team.members().collect())
+            .unwrap_or_default();
+        let message = if members.is_empty() {
+            TextComponent::translate(
+                "commands.team.list.members.empty",
+                [team_display(&scoreboard, name)],
+            )
+        } else {
+            let list = members
+                .iter()
+                .map(|member| member.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            TextComponent::translate(
+                "commands.team.list.members.success",
+                [
+                    team_display(&scoreboard, name),
+                    TextComponent::text(members.len().to_string()),
+                    TextComponent::text(list),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+enum TeamOption {
+    DisplayName,
+    Color(Option<NamedColor>),
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+    NameTagVisibility(NameTagVisibility),
+    CollisionRule(CollisionRule),
+    Prefix,
+    Suffix,
+}
+
+struct ModifyExecutor(TeamOption);
+
+#[async_trait]
+impl CommandExecutor for ModifyExecutor {
+    #[allow(clippy::too_many_lines)]
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut scoreboard = server.scoreboard.lock().await;
+        let name = find_team(&scoreboard, args)?;
+        let team_name = team_display(&scoreboard, name);
+
+        let message = match self.0 {
+            TeamOption::DisplayName => {
+                let display_name = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                scoreboard
+                    .update_team(server, name, |team| team.display_name = display_name)
+                    .await;
+                TextComponent::translate("commands.team.option.name.success", [team_name])
+            }
+            TeamOption::Color(color) => {
+                if scoreboard
+                    .team(name)
+                    .is_some_and(|team| team.color == color)
+                {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate("commands.team.option.color.unchanged", []),
+                    )));
+                }
+                scoreboard
+                    .update_team(server, name, |team| team.color = color)
+                    .await;
+                TextComponent::translate(
+                    "commands.team.option.color.success",
+                    [
+                        team_name,
+                        TextComponent::text(color.map_or("reset", color_name)),
+                    ],
+                )
+            }
+            TeamOption::FriendlyFire => {
+                let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                let (changed, unchanged) = if value {
+                    ("enabled", "alreadyEnabled")
+                } else {
+                    ("disabled", "alreadyDisabled")
+                };
+                if scoreboard
+                    .team(name)
+                    .is_some_and(|team| team.allow_friendly_fire == value)
+                {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            format!("commands.team.option.friendlyfire.{unchanged}"),
+                            [],
+                        ),
+                    )));
+                }
+                scoreboard
+                    .update_team(server, name, |team| team.allow_friendly_fire = value)
+                    .await;
+                TextComponent::translate(
+                    format!("commands.team.option.friendlyfire.{changed}"),
+                    [team_name],
+                )
+            }
+            TeamOption::SeeFriendlyInvisibles => {
+                let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                let (changed, unchanged) = if value {
+                    ("enabled", "alreadyEnabled")
+                } else {
+                    ("disabled", "alreadyDisabled")
+                };
+                if scoreboard
+                    .team(name)
+                    .is_some_and(|team| team.see_friendly_invisibles == value)
+                {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            format!("commands.team.option.seeFriendlyInvisibles.{unchanged}"),
+                            [],
+                        ),
+                    )));
+                }
+                scoreboard
+                    .update_team(server, name, |team| team.see_friendly_invisibles = value)
+                    .await;
+                TextComponent::translate(
+                    format!("commands.team.option.seeFriendlyInvisibles.{changed}"),
+                    [team_name],
+                )
+            }
+            TeamOption::NameTagVisibility(visibility) => {
+                if scoreboard
+                    .team(name)
+                    .is_some_and(|team| team.name_tag_visibility == visibility)
+                {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            "commands.team.option.nametagVisibility.unchanged",
+                            [],
+                        ),
+                    )));
+                }
+                scoreboard
+                    .update_team(server, name, |team| team.name_tag_visibility = visibility)
+                    .await;
+                TextComponent::translate(
+                    "commands.team.option.nametagVisibility.success",
+                    [
+                        team_name,
+                        TextComponent::text(name_tag_visibility_name(visibility)),
+                    ],
+                )
+            }
+            TeamOption::CollisionRule(rule) => {
+                if scoreboard
+                    .team(name)
+                    .is_some_and(|team| team.collision_rule == rule)
+                {
+                    return Err(CommandError::CommandFailed(Box::new(
+                        TextComponent::translate(
+                            "commands.team.option.collisionRule.unchanged",
+                            [],
+                        ),
+                    )));
+                }
+                scoreboard
+                    .update_team(server, name, |team| team.collision_rule = rule)
+                    .await;
+                TextComponent::translate(
+                    "commands.team.option.collisionRule.success",
+                    [team_name, TextComponent::text(collision_rule_name(rule))],
+                )
+            }
+            TeamOption::Prefix => {
+                let prefix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                scoreboard
+                    .update_team(server, name, |team| team.prefix = prefix.clone())
+                    .await;
+                TextComponent::translate("commands.team.option.prefix.success", [prefix])
+            }
+            TeamOption::Suffix => {
+                let suffix = TextComponentArgConsumer::find_arg(args, ARG_VALUE)?;
+                scoreboard
+                    .update_team(server, name, |team| team.suffix = suffix.clone())
+                    .await;
+                TextComponent::translate("commands.team.option.suffix.success", [suffix])
+            }
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut color =
+        literal("color").then(literal("reset").execute(ModifyExecutor(TeamOption::Color(None))));
+    for named_color in NAMED_COLORS {
+        color = color.then(
+            literal(color_name(named_color))
+                .execute(ModifyExecutor(TeamOption::Color(Some(named_color)))),
+        );
+    }
+
+    let mut name_tag_visibility = literal("nametagVisibility");
+    for visibility in [
+        NameTagVisibility::Always,
+        NameTagVisibility::Never,
+        NameTagVisibility::HideForOtherTeams,
+        NameTagVisibility::HideForOwnTeam,
+    ] {
+        name_tag_visibility = name_tag_visibility.then(
+            literal(name_tag_visibility_name(visibility))
+                .execute(ModifyExecutor(TeamOption::NameTagVisibility(visibility))),
+        );
+    }
+
+    let mut collision_rule = literal("collisionRule");
+    for rule in [
+        CollisionRule::Always,
+        CollisionRule::Never,
+        CollisionRule::PushOtherTeams,
+        CollisionRule::PushOwnTeam,
+    ] {
+        collision_rule = collision_rule.then(
+            literal(collision_rule_name(rule))
+                .execute(ModifyExecutor(TeamOption::CollisionRule(rule))),
+        );
+    }
+
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("list")
+                .then(argument(ARG_TEAM, SimpleArgConsumer).execute(ListExecutor))
+                .execute(ListExecutor),
+        )
+        .then(
+            literal("add").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(argument(ARG_DISPLAY_NAME, TextComponentArgConsumer).execute(AddExecutor))
+                    .execute(AddExecutor),
+            ),
+        )
+        .then(literal("remove").then(argument(ARG_TEAM, SimpleArgConsumer).execute(RemoveExecutor)))
+        .then(literal("empty").then(argument(ARG_TEAM, SimpleArgConsumer).execute(EmptyExecutor)))
+        .then(
+            literal("join").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(argument_default_name(ScoreHoldersArgumentConsumer).execute(JoinExecutor))
+                    .execute(JoinExecutor),
+            ),
+        )
+        .then(
+            literal("leave")
+                .then(argument_default_name(ScoreHoldersArgumentConsumer).execute(LeaveExecutor)),
+        )
+        .then(
+            literal("modify").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .then(
+                        literal("displayName").then(
+                            argument(ARG_VALUE, TextComponentArgConsumer)
+                                .execute(ModifyExecutor(TeamOption::DisplayName)),
+                        ),
+                    )
+                    .then(color)
+                    .then(
+                        literal("friendlyFire").then(
+                            argument(ARG_VALUE, BoolArgConsumer)
+                                .execute(ModifyExecutor(TeamOption::FriendlyFire)),
+                        ),
+                    )
+                    .then(
+                        literal("seeFriendlyInvisibles").then(
+                            argument(ARG_VALUE, BoolArgConsumer)
+                                .execute(ModifyExecutor(TeamOption::SeeFriendlyInvisibles)),
+                        ),
+                    )
+                    .then(name_tag_visibility)
+                    .then(collision_rule)
+                    .then(
+                        literal("prefix").then(
+                            argument(ARG_VALUE, TextComponentArgConsumer)
+                                .execute(ModifyExecutor(TeamOption::Prefix)),
+                        ),
+                    )
+                    .then(
+                        literal("suffix").then(
+                            argument(ARG_VALUE, TextComponentArgConsumer)
+                                .execute(ModifyExecutor(TeamOption::Suffix)),
+                        ),
+                    ),
+            ),
+        )
+}
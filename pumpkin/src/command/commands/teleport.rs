@@ -70,9 +70,8 @@ impl CommandExecutor for EntitiesToEntityExecutor {
             )));
         }
         for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.teleport(pos, yaw, pitch).await;
+            let world = target.get_entity().world.read().await.clone();
+            target.clone().teleport(Some(pos), None, None, world).await;
         }
 
         Ok(())
@@ -101,7 +100,11 @@ impl CommandExecutor for EntitiesToPosFacingPosExecutor {
         let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
 
         for target in targets {
-            target.teleport(pos, yaw, pitch).await;
+            let world = target.get_entity().world.read().await.clone();
+            target
+                .clone()
+                .teleport(Some(pos), Some(yaw), Some(pitch), world)
+                .await;
         }
 
         Ok(())
@@ -132,7 +135,11 @@ impl CommandExecutor for EntitiesToPosFacingEntityExecutor {
         let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.pos.load());
 
         for target in targets {
-            target.teleport(pos, yaw, pitch).await;
+            let world = target.get_entity().world.read().await.clone();
+            target
+                .clone()
+                .teleport(Some(pos), Some(yaw), Some(pitch), world)
+                .await;
         }
 
         Ok(())
@@ -160,7 +167,11 @@ impl CommandExecutor for EntitiesToPosWithRotationExecutor {
         let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
 
         for target in targets {
-            target.teleport(pos, yaw, pitch).await;
+            let world = target.get_entity().world.read().await.clone();
+            target
+                .clone()
+                .teleport(Some(pos), Some(yaw), Some(pitch), world)
+                .await;
         }
 
         Ok(())
@@ -186,9 +197,8 @@ impl CommandExecutor for EntitiesToPosExecutor {
             )));
         }
         for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.teleport(pos, yaw, pitch).await;
+            let world = target.get_entity().world.read().await.clone();
+            target.clone().teleport(Some(pos), None, None, world).await;
         }
 
         Ok(())
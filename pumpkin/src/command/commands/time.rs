@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::color::{Color, NamedColor};
@@ -7,9 +9,10 @@ use crate::command::tree::builder::{argument, literal};
 use crate::command::{
     CommandError, CommandExecutor, CommandSender, ConsumedArgs, tree::CommandTree,
 };
+use crate::world::World;
 
 const NAMES: [&str; 1] = ["time"];
-const DESCRIPTION: &str = "Query the world time.";
+const DESCRIPTION: &str = "Changes or queries the world's game time.";
 const ARG_TIME: &str = "time";
 
 #[derive(Clone, Copy)]
@@ -44,6 +47,20 @@ enum QueryMode {
     Day,
 }
 
+/// The world of the player running the command, or the overworld.
+async fn source_world(sender: &CommandSender, server: &crate::server::Server) -> Arc<World> {
+    match sender.world().await {
+        Some(world) => world,
+        None => server
+            .worlds
+            .read()
+            .await
+            .first()
+            .expect("There should always be at least one world")
+            .clone(),
+    }
+}
+
 struct QueryExecutor(QueryMode);
 
 #[async_trait]
@@ -55,11 +72,7 @@ impl CommandExecutor for QueryExecutor {
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let mode = self.0;
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = source_world(sender, server).await;
         let level_time = world.level_time.lock().await;
 
         let msg = match mode {
@@ -115,35 +128,22 @@ impl CommandExecutor for ChangeExecutor {
             return Ok(());
         };
 
-        let mode = self.0;
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
-        let mut level_time = world.level_time.lock().await;
-
-        let msg = match mode {
-            Mode::Add => {
-                // add
-                level_time.add_time(time_count.into());
-                level_time.send_time(world).await;
-                let curr_time = level_time.query_daytime();
-                TextComponent::translate(
-                    "commands.time.set",
-                    [TextComponent::text(curr_time.to_string())],
-                )
-            }
-            Mode::Set(_) => {
-                // set
-                level_time.set_time(time_count.into());
-                level_time.send_time(world).await;
-                TextComponent::translate(
-                    "commands.time.set",
-                    [TextComponent::text(time_count.to_string())],
-                )
+        // Like vanilla, the time changes in every world
+        for world in server.worlds.read().await.iter() {
+            let mut level_time = world.level_time.lock().await;
+            match self.0 {
+                Mode::Add => level_time.add_time(time_count.into()),
+                Mode::Set(_) => level_time.set_time(time_count.into()),
             }
-        };
+            level_time.send_time(world).await;
+        }
+
+        let world = source_world(sender, server).await;
+        let curr_time = world.level_time.lock().await.query_daytime();
+        let msg = TextComponent::translate(
+            "commands.time.set",
+            [TextComponent::text(curr_time.to_string())],
+        );
 
         sender.send_message(msg).await;
         Ok(())
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree,
+};
+
+const NAMES: [&str; 1] = ["tps"];
+
+const DESCRIPTION: &str = "Shows the ticks per second over the last 1, 5 and 15 minutes.";
+
+struct Executor;
+
+/// Colors the TPS by how far it is behind the tick rate.
+fn tps_component(tps: f32, tick_rate: f32) -> TextComponent {
+    let color = if tps >= tick_rate * 0.9 {
+        NamedColor::Green
+    } else if tps >= tick_rate * 0.75 {
+        NamedColor::Yellow
+    } else {
+        NamedColor::Red
+    };
+    TextComponent::text(format!("{tps:.1}")).color_named(color)
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let (one, five, fifteen) = server.get_tps();
+        let tick_rate = server.tick_rate_manager.tickrate();
+
+        sender
+            .send_message(
+                TextComponent::text("TPS: ")
+                    .add_child(tps_component(one, tick_rate))
+                    .add_child(TextComponent::text("/"))
+                    .add_child(tps_component(five, tick_rate))
+                    .add_child(TextComponent::text("/"))
+                    .add_child(tps_component(fifteen, tick_rate)),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
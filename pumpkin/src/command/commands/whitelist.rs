@@ -18,6 +18,7 @@ use crate::{
         LoadJSONConfiguration, SaveJSONConfiguration,
         whitelist_data::{WHITELIST_CONFIG, WhitelistConfig},
     },
+    plugin::player::player_kick::KickCause,
     server::Server,
 };
 
@@ -29,14 +30,14 @@ async fn kick_non_whitelisted_players(server: &Server) {
     let whitelist = WHITELIST_CONFIG.read().await;
     if BASIC_CONFIG.enforce_whitelist && server.white_list.load(Ordering::Relaxed) {
         for player in server.get_all_players().await {
-            if whitelist.is_whitelisted(&player.gameprofile) {
+            if whitelist.is_allowed(&player.gameprofile).await {
                 continue;
             }
             player
-                .kick(TextComponent::translate(
-                    "multiplayer.disconnect.not_whitelisted",
-                    &[],
-                ))
+                .kick(
+                    KickCause::Admin,
+                    TextComponent::translate("multiplayer.disconnect.not_whitelisted", &[]),
+                )
                 .await;
         }
     }
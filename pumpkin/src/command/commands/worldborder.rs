@@ -65,7 +65,7 @@ impl CommandExecutor for GetExecutor {
             .expect("There should always be at least one world");
         let border = world.worldborder.lock().await;
 
-        let diameter = border.new_diameter.round() as i32;
+        let diameter = border.diameter().round() as i32;
         sender
             .send_message(TextComponent::translate(
                 "commands.worldborder.get",
@@ -106,7 +106,7 @@ impl CommandExecutor for SetExecutor {
             return Ok(());
         };
 
-        if (distance - border.new_diameter).abs() < f64::EPSILON {
+        if (distance - border.diameter()).abs() < f64::EPSILON {
             sender
                 .send_message(
                     TextComponent::translate(NOTHING_CHANGED_EXCEPTION, [])
@@ -170,7 +170,7 @@ impl CommandExecutor for SetTimeExecutor {
             return Ok(());
         };
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&border.diameter()) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
@@ -253,7 +253,7 @@ impl CommandExecutor for AddExecutor {
             return Ok(());
         }
 
-        let distance = border.new_diameter + distance;
+        let distance = border.diameter() + distance;
 
         let dist = format!("{distance:.1}");
         sender
@@ -309,9 +309,10 @@ impl CommandExecutor for AddTimeExecutor {
             return Ok(());
         };
 
-        let distance = distance + border.new_diameter;
+        let diameter = border.diameter();
+        let distance = distance + diameter;
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&diameter) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
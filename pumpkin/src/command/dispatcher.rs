@@ -5,11 +5,14 @@ use super::args::ConsumedArgs;
 
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError::{
-    CommandFailed, InvalidConsumption, InvalidRequirement, PermissionDenied,
+    CommandFailed, InvalidConsumption, InvalidRequirement, OnCooldown, PermissionDenied,
 };
 use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
 use crate::server::Server;
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub enum CommandError {
@@ -22,6 +25,8 @@ pub enum CommandError {
     /// The command could not be executed due to insufficient permissions.
     /// The user attempting to run the command lacks the necessary authorization.
     PermissionDenied,
+    /// The player used the command too recently and has to wait for the remaining duration.
+    OnCooldown(Duration),
     /// A general error occurred during command execution that doesn't fit into
     /// more specific `CommandError` variants.
     CommandFailed(Box<TextComponent>),
@@ -49,6 +54,10 @@ impl CommandError {
                     "I'm sorry, but you do not have permission to perform this command. Please contact the server administrator if you believe this is an error.",
                 )
             }
+            OnCooldown(remaining) => TextComponent::text(format!(
+                "You have to wait {} more seconds before using this command again.",
+                remaining.as_secs() + 1
+            )),
             CommandFailed(s) => *s,
         }
     }
@@ -58,6 +67,8 @@ impl CommandError {
 pub struct CommandDispatcher {
     pub commands: HashMap<String, Command>,
     pub permissions: HashMap<String, String>,
+    /// When each player may use a command with a cooldown again, by player and primary name.
+    cooldowns: Mutex<HashMap<(Uuid, String), Instant>>,
 }
 
 /// Stores registered [`CommandTree`]s and dispatches commands to them.
@@ -121,7 +132,7 @@ impl CommandDispatcher {
                     log::trace!("Permission denied for command \"{cmd}\"");
                     return Vec::new();
                 }
-                Err(CommandFailed(_)) => {
+                Err(OnCooldown(_) | CommandFailed(_)) => {
                     return Vec::new();
                 }
                 Ok(Some(new_suggestions)) => {
@@ -266,9 +277,23 @@ impl CommandDispatcher {
 
         let tree = self.get_tree(key)?;
 
+        // The console and RCON bypass cooldowns like they bypass permissions
+        let cooldown = match src {
+            CommandSender::Player(player) => tree
+                .cooldown
+                .map(|cooldown| (player.gameprofile.id, cooldown)),
+            _ => None,
+        };
+        if let Some((player, _)) = cooldown {
+            self.check_cooldown(player, &tree.names[0])?;
+        }
+
         // try paths until fitting path is found
         for path in tree.iter_paths() {
             if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone()).await? {
+                if let Some((player, cooldown)) = cooldown {
+                    self.start_cooldown(player, &tree.names[0], cooldown);
+                }
                 return Ok(());
             }
         }
@@ -306,6 +331,40 @@ impl CommandDispatcher {
         self.permissions.get(key)
     }
 
+    /// All registered commands by their primary name, sorted by name. Aliases are left out.
+    #[must_use]
+    pub fn get_all_commands(&self) -> Vec<(&str, &CommandTree)> {
+        let mut commands: Vec<_> = self
+            .commands
+            .iter()
+            .filter_map(|(name, command)| match command {
+                Command::Tree(tree) => Some((name.as_str(), tree)),
+                Command::Alias(_) => None,
+            })
+            .collect();
+        commands.sort_unstable_by_key(|(name, _)| *name);
+        commands
+    }
+
+    /// Fails with [`CommandError::OnCooldown`] if the player has to wait before using the command again.
+    fn check_cooldown(&self, player: Uuid, name: &str) -> Result<(), CommandError> {
+        let cooldowns = self.cooldowns.lock().unwrap();
+        match cooldowns.get(&(player, name.to_string())) {
+            Some(until) if *until > Instant::now() => {
+                Err(OnCooldown(until.duration_since(Instant::now())))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn start_cooldown(&self, player: Uuid, name: &str, cooldown: Duration) {
+        let now = Instant::now();
+        let mut cooldowns = self.cooldowns.lock().unwrap();
+        // Forget about cooldowns that ran out so the map doesn't keep growing
+        cooldowns.retain(|_, until| *until > now);
+        cooldowns.insert((player, name.to_string()), now + cooldown);
+    }
+
     async fn try_is_fitting_path<'a>(
         src: &mut CommandSender,
         server: &'a Server,
@@ -383,6 +442,10 @@ impl CommandDispatcher {
                 }
                 NodeType::Argument { consumer, name } => {
                     match consumer.consume(src, server, raw_args).await {
+                        // The last word is still being typed, so it may be completed
+                        Some(_) if raw_args.is_empty() && !input.ends_with(char::is_whitespace) => {
+                            return consumer.suggest(src, server, input).await;
+                        }
                         Some(consumed) => {
                             parsed_args.insert(name, consumed);
                         }
@@ -427,8 +490,10 @@ impl CommandDispatcher {
             .insert(primary_name.to_string(), Command::Tree(tree));
     }
 
-    /// Remove a command from the dispatcher by its primary name.
-    pub fn unregister(&mut self, name: &str) {
+    /// Remove a command and its aliases from the dispatcher by its primary name.
+    ///
+    /// Returns whether the command was registered.
+    pub fn unregister_command(&mut self, name: &str) -> bool {
         let mut to_remove = Vec::new();
         for (key, value) in &self.commands {
             if key == name {
@@ -440,15 +505,26 @@ impl CommandDispatcher {
             }
         }
 
+        let removed = !to_remove.is_empty();
         for key in to_remove {
             self.commands.remove(&key);
             self.permissions.remove(&key);
         }
+        self.cooldowns
+            .lock()
+            .unwrap()
+            .retain(|(_, command), _| command != name);
+        removed
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::{CommandDispatcher, CommandError};
     use crate::command::{commands::default_dispatcher, tree::CommandTree};
     #[tokio::test]
     async fn test_dynamic_command() {
@@ -456,4 +532,50 @@ mod test {
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, "minecraft:test");
     }
+
+    #[tokio::test]
+    async fn test_unregister_command() {
+        // The default dispatcher registers permissions globally, which fails if done twice
+        let mut dispatcher = CommandDispatcher::default();
+        let tree = CommandTree::new(["test", "t"], "test_desc");
+        dispatcher.register(tree, "minecraft:test");
+
+        assert!(dispatcher.unregister_command("test"));
+        assert!(dispatcher.get_tree("test").is_err());
+        assert!(dispatcher.get_tree("t").is_err());
+        assert!(dispatcher.get_permission("t").is_none());
+        assert!(!dispatcher.unregister_command("test"));
+    }
+
+    #[test]
+    fn test_get_all_commands() {
+        let mut dispatcher = CommandDispatcher::default();
+        dispatcher.register(CommandTree::new(["b", "alias"], "b_desc"), "minecraft:b");
+        dispatcher.register(CommandTree::new(["a"], "a_desc"), "minecraft:a");
+
+        let commands = dispatcher.get_all_commands();
+        let names: Vec<_> = commands.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(commands[1].1.description, "b_desc");
+    }
+
+    #[tokio::test]
+    async fn test_command_cooldown() {
+        let mut dispatcher = CommandDispatcher::default();
+        let tree = CommandTree::new(["test"], "test_desc").with_cooldown(Duration::from_secs(60));
+        dispatcher.register(tree, "minecraft:test");
+
+        let player = Uuid::new_v4();
+        assert!(dispatcher.check_cooldown(player, "test").is_ok());
+        dispatcher.start_cooldown(player, "test", Duration::from_secs(60));
+        assert!(matches!(
+            dispatcher.check_cooldown(player, "test"),
+            Err(CommandError::OnCooldown(_))
+        ));
+        // Cooldowns are per player
+        assert!(dispatcher.check_cooldown(Uuid::new_v4(), "test").is_ok());
+
+        dispatcher.start_cooldown(player, "other", Duration::ZERO);
+        assert!(dispatcher.check_cooldown(player, "other").is_ok());
+    }
 }
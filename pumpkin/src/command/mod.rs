@@ -1,6 +1,8 @@
 use std::fmt;
 use std::sync::Arc;
 
+use crate::PERMISSION_REGISTRY;
+use crate::command::commands::OP_LEVEL_PERMISSIONS;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::World;
@@ -18,6 +20,7 @@ pub mod commands;
 pub mod dispatcher;
 pub mod tree;
 
+#[derive(Clone)]
 pub enum CommandSender {
     Rcon(Arc<tokio::sync::Mutex<Vec<String>>>),
     Console,
@@ -89,6 +92,28 @@ impl CommandSender {
         }
     }
 
+    /// Returns the legacy op level required for a permission node, derived from its default value.
+    /// Nodes which are unregistered or denied by default require [`PermissionLvl::Four`].
+    pub async fn legacy_permission_lvl(node: &str) -> PermissionLvl {
+        PERMISSION_REGISTRY
+            .read()
+            .await
+            .legacy_level(node)
+            .unwrap_or(PermissionLvl::Four)
+    }
+
+    /// The highest op level the sender holds, derived from the op level permission nodes, so
+    /// permissions assigned to players count too.
+    pub async fn op_level(&self) -> PermissionLvl {
+        let mut level = PermissionLvl::Zero;
+        for node in OP_LEVEL_PERMISSIONS {
+            if self.has_permission(node).await {
+                level = level.max(Self::legacy_permission_lvl(node).await);
+            }
+        }
+        level
+    }
+
     #[must_use]
     pub fn position(&self) -> Option<Vector3<f64>> {
         match self {
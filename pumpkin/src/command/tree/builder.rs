@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use super::CommandExecutor;
 use crate::command::CommandSender;
@@ -28,9 +28,18 @@ impl CommandTree {
             children: Vec::new(),
             names: names_vec,
             description: description.into(),
+            cooldown: None,
         }
     }
 
+    /// Makes players wait `cooldown` between two uses of this command.
+    /// The console and RCON are never on cooldown.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+
     /// Executes if a command terminates at this [Node], i.e. without any arguments.
     ///
     /// [`ConsumedArgs`] maps the names of all
@@ -1,6 +1,6 @@
 use super::{CommandExecutor, args::ArgumentConsumer};
 use crate::command::CommandSender;
-use std::{collections::VecDeque, fmt::Debug, sync::Arc};
+use std::{collections::VecDeque, fmt::Debug, sync::Arc, time::Duration};
 
 pub mod builder;
 pub mod format;
@@ -60,6 +60,8 @@ pub struct CommandTree {
     pub children: Vec<usize>,
     pub names: Vec<String>,
     pub description: String,
+    /// How long a player has to wait between two uses of this command.
+    pub cooldown: Option<Duration>,
 }
 
 impl CommandTree {
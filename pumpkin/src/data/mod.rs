@@ -1,10 +1,17 @@
-use std::{env, fs, path::Path};
+use std::{
+    env, fs,
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use serde::{Deserialize, Serialize};
 
 const DATA_FOLDER: &str = "data/";
+/// Numbers the temporary files of saves, so saves running at the same time never share one
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 pub mod op_data;
+pub mod permission_data;
 
 pub mod banlist_serializer;
 pub mod banned_ip_data;
@@ -18,6 +25,10 @@ pub trait LoadJSONConfiguration {
     where
         Self: Sized + Default + Serialize + for<'de> Deserialize<'de>,
     {
+        // Tests run in the crate directory, they must not read or create the server's data files
+        if cfg!(test) {
+            return Self::default();
+        }
         let exe_dir = env::current_dir().unwrap();
         let data_dir = exe_dir.join(DATA_FOLDER);
         if !data_dir.exists() {
@@ -63,6 +74,9 @@ pub trait SaveJSONConfiguration: LoadJSONConfiguration {
     where
         Self: Sized + Default + Serialize + for<'de> Deserialize<'de>,
     {
+        if cfg!(test) {
+            return;
+        }
         let exe_dir = env::current_dir().unwrap();
         let data_dir = exe_dir.join(DATA_FOLDER);
         if !data_dir.exists() {
@@ -75,16 +89,26 @@ pub trait SaveJSONConfiguration: LoadJSONConfiguration {
             Ok(content) => content,
             Err(err) => {
                 log::warn!(
-                    "Couldn't serialize operator data config to {}. Reason: {err}",
+                    "Couldn't serialize data config to {}. Reason: {err}",
                     path.display()
                 );
                 return;
             }
         };
 
-        if let Err(err) = std::fs::write(&path, content) {
+        // Write to a temporary file first, so a crash mid-write never leaves a truncated file behind
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temp_path = path.with_file_name(temp_name);
+        if let Err(err) =
+            std::fs::write(&temp_path, content).and_then(|()| std::fs::rename(&temp_path, &path))
+        {
             log::warn!(
-                "Couldn't write operator config to {}. Reason: {err}",
+                "Couldn't write data config to {}. Reason: {err}",
                 path.display()
             );
         }
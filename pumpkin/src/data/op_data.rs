@@ -32,3 +32,20 @@ impl LoadJSONConfiguration for OperatorConfig {
 }
 
 impl SaveJSONConfiguration for OperatorConfig {}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::PermissionLvl;
+
+    use super::OperatorConfig;
+
+    #[test]
+    fn reads_vanilla_ops() {
+        let json = r#"[{"uuid":"069a79f4-44e9-4726-a5be-fca90e38aaf5","name":"Notch","level":1,"bypassesPlayerLimit":true}]"#;
+        let config: OperatorConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.ops[0].name, "Notch");
+        assert_eq!(config.ops[0].level, PermissionLvl::One);
+        assert!(config.ops[0].bypasses_player_limit);
+        assert_eq!(serde_json::to_string(&config).unwrap(), json);
+    }
+}
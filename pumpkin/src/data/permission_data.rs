@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, LazyLock},
+};
+
+use pumpkin_util::permission::{PermissionAttachment, PermissionManager};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static PERMISSION_CONFIG: LazyLock<RwLock<PermissionConfig>> =
+    LazyLock::new(|| RwLock::new(PermissionConfig::load()));
+
+/// The permission nodes directly assigned to each player.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct PermissionConfig {
+    pub players: HashMap<Uuid, PermissionAttachment>,
+}
+
+impl PermissionConfig {
+    /// Creates the attachments of every stored player.
+    pub fn load_into(&self, manager: &mut PermissionManager) {
+        for (uuid, attachment) in &self.players {
+            manager
+                .attachments
+                .insert(*uuid, Arc::new(RwLock::new(attachment.clone())));
+        }
+    }
+
+    /// Replaces the stored players with the current attachments of the manager.
+    ///
+    /// Players without any assigned permission are left out.
+    pub async fn update_from(&mut self, manager: &PermissionManager) {
+        self.players.clear();
+        for (uuid, attachment) in &manager.attachments {
+            let attachment = attachment.read().await;
+            if !attachment.get_permissions().is_empty() {
+                self.players.insert(*uuid, attachment.clone());
+            }
+        }
+    }
+}
+
+impl LoadJSONConfiguration for PermissionConfig {
+    fn get_path() -> &'static Path {
+        Path::new("permissions.json")
+    }
+    fn validate(&self) {}
+}
+
+impl SaveJSONConfiguration for PermissionConfig {}
+
+/// Restores the permissions assigned to players before the last restart.
+pub async fn load_permissions(manager: &RwLock<PermissionManager>) {
+    PERMISSION_CONFIG
+        .read()
+        .await
+        .load_into(&mut *manager.write().await);
+}
+
+/// Saves the permissions of all players. The manager is only locked while copying the
+/// permissions, the file is written afterwards. The config stays locked, so saves never
+/// interleave.
+pub async fn save_permissions(manager: &RwLock<PermissionManager>) {
+    let mut config = PERMISSION_CONFIG.write().await;
+    config.update_from(&*manager.read().await).await;
+    config.save();
+}
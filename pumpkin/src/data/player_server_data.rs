@@ -178,11 +178,19 @@ impl ServerPlayerData {
 #[cfg(test)]
 mod test {
     use crate::data::player_server_data::ServerPlayerData;
+    use crate::entity::NBTStorage;
+    use pumpkin_data::item::Item;
+    use pumpkin_inventory::entity_equipment::EntityEquipment;
+    use pumpkin_inventory::player::player_inventory::PlayerInventory;
     use pumpkin_nbt::compound::NbtCompound;
     use pumpkin_world::data::player_data::PlayerDataStorage;
+    use pumpkin_world::inventory::Inventory;
+    use pumpkin_world::item::ItemStack;
+    use std::sync::Arc;
     use std::time::Duration;
     use std::time::Instant;
     use tempfile::tempdir;
+    use tokio::sync::Mutex;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -314,4 +322,30 @@ mod test {
         assert_eq!(loaded_data.get_string("name").unwrap(), "TestPlayer");
         assert_eq!(loaded_data.get_int("level").unwrap(), 42);
     }
+
+    #[tokio::test]
+    async fn test_player_inventory_round_trip() {
+        let inventory = PlayerInventory::new(Arc::new(Mutex::new(EntityEquipment::new())));
+        inventory.set_selected_slot(4);
+        inventory
+            .set_stack(4, ItemStack::new(16, &Item::DIAMOND))
+            .await;
+        inventory
+            .set_stack(20, ItemStack::new(1, &Item::BONE_MEAL))
+            .await;
+
+        let mut nbt = NbtCompound::new();
+        inventory.write_nbt(&mut nbt).await;
+
+        let loaded = PlayerInventory::new(Arc::new(Mutex::new(EntityEquipment::new())));
+        loaded.read_nbt_non_mut(&mut nbt).await;
+
+        assert_eq!(loaded.get_selected_slot(), 4);
+        let stack = loaded.get_stack(4).await;
+        let stack = stack.lock().await;
+        assert_eq!(stack.item.id, Item::DIAMOND.id);
+        assert_eq!(stack.item_count, 16);
+        let stack = loaded.get_stack(20).await;
+        assert_eq!(stack.lock().await.item.id, Item::BONE_MEAL.id);
+    }
 }
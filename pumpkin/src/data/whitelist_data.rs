@@ -1,11 +1,11 @@
 use std::{path::Path, sync::LazyLock};
 
-use pumpkin_config::whitelist::WhitelistEntry;
+use pumpkin_config::{BASIC_CONFIG, whitelist::WhitelistEntry};
 use serde::{Deserialize, Serialize};
 
 use crate::net::GameProfile;
 
-use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+use super::{LoadJSONConfiguration, SaveJSONConfiguration, op_data::OPERATOR_CONFIG};
 
 pub static WHITELIST_CONFIG: LazyLock<tokio::sync::RwLock<WhitelistConfig>> =
     LazyLock::new(|| tokio::sync::RwLock::new(WhitelistConfig::load()));
@@ -17,11 +17,24 @@ pub struct WhitelistConfig {
 }
 
 impl WhitelistConfig {
+    /// Only the UUID is checked, as names can change.
     #[must_use]
     pub fn is_whitelisted(&self, profile: &GameProfile) -> bool {
-        self.whitelist
-            .iter()
-            .any(|entry| entry.uuid == profile.id && entry.name == profile.name)
+        self.whitelist.iter().any(|entry| entry.uuid == profile.id)
+    }
+
+    /// Whether the player may join while the whitelist is on,
+    /// operators are exempt if `whitelist_exempt_ops` is enabled.
+    pub async fn is_allowed(&self, profile: &GameProfile) -> bool {
+        if self.is_whitelisted(profile) {
+            return true;
+        }
+        BASIC_CONFIG.whitelist_exempt_ops
+            && OPERATOR_CONFIG
+                .read()
+                .await
+                .get_entry(&profile.id)
+                .is_some()
     }
 }
 
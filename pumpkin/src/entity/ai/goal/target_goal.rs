@@ -7,19 +7,17 @@ use crate::entity::{ai::path::NavigatorGoal, mob::MobEntity, player::Player};
 
 use super::Goal;
 
+/// Targets the closest player within the mob's follow range.
+#[derive(Default)]
 pub struct TargetGoal {
     // TODO: make this an entity
     target: Mutex<Option<Arc<Player>>>,
-    range: f64,
 }
 
 impl TargetGoal {
     #[must_use]
-    pub fn new(range: f64) -> Self {
-        Self {
-            target: Mutex::new(None),
-            range,
-        }
+    pub fn new() -> Self {
+        Self::default()
     }
 }
 
@@ -28,6 +26,7 @@ impl Goal for TargetGoal {
     async fn can_start(&self, mob: &MobEntity) -> bool {
         // TODO: make this an entity
         let mut target = self.target.lock().await;
+        let range = mob.living_entity.get_follow_range().await;
 
         // gets the closest entity (currently player)
         *target = mob
@@ -36,7 +35,7 @@ impl Goal for TargetGoal {
             .world
             .read()
             .await
-            .get_closest_player(mob.living_entity.entity.pos.load(), self.range)
+            .get_closest_player(mob.living_entity.entity.pos.load(), range)
             .await;
         // we can't use filter because of async closures
         if let Some(player) = target.as_ref() {
@@ -52,21 +51,23 @@ impl Goal for TargetGoal {
         if let Some(target) = self.target.lock().await.as_ref() {
             let mob_pos = mob.living_entity.entity.pos.load();
             let target_pos = target.living_entity.entity.pos.load();
+            let range = mob.living_entity.get_follow_range().await;
             let abilities = target.abilities.lock().await;
             return !abilities.invulnerable
-                && mob_pos.squared_distance_to_vec(target_pos) <= (self.range * self.range);
+                && mob_pos.squared_distance_to_vec(target_pos) <= (range * range);
         }
         false
     }
     async fn tick(&self, mob: &MobEntity) {
         if let Some(target) = self.target.lock().await.as_ref() {
+            let speed = mob.living_entity.get_movement_speed().await;
             let mut navigator = mob.navigator.lock().await;
             let target_player = target.living_entity.entity.pos.load();
 
             navigator.set_progress(NavigatorGoal {
                 current_progress: mob.living_entity.entity.pos.load(),
                 destination: target_player,
-                speed: 0.1,
+                speed,
             });
         }
     }
@@ -0,0 +1,207 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use pumpkin_config::advanced_config;
+use pumpkin_data::entity::EffectType;
+use pumpkin_util::{
+    GameMode,
+    math::{position::BlockPos, vector3::Vector3},
+    text::TextComponent,
+};
+
+use super::{Entity, player::Player};
+use crate::plugin::player::player_kick::KickCause;
+
+/// Once a player sends more movement packets than this in one tick, we stop counting them
+/// towards the allowed distance, so spamming packets does not allow moving further. Vanilla: 5
+const MAX_MOVE_PACKETS_PER_TICK: u32 = 5;
+/// Moving down slower than this while in the air counts as floating. Vanilla: -0.03125
+const MIN_FALL_SPEED: f64 = -0.031_25;
+
+/// Validates the movement and interactions players report, to catch the most obvious cheats.
+#[derive(Default)]
+pub struct AntiCheat {
+    /// The movement packets received since the last tick
+    move_packets: AtomicU32,
+    /// Whether the last movement left the player floating in the air
+    floating: AtomicBool,
+    /// For how many ticks the player has been floating
+    floating_ticks: AtomicU32,
+    violations: AtomicU32,
+}
+
+impl AntiCheat {
+    pub async fn tick(&self, player: &Arc<Player>) {
+        self.move_packets.store(0, Ordering::Relaxed);
+
+        let config = &advanced_config().anti_cheat;
+        if !self.floating.load(Ordering::Relaxed) {
+            self.floating_ticks.store(0, Ordering::Relaxed);
+            return;
+        }
+        let floating_ticks = self.floating_ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        if floating_ticks <= config.max_floating_ticks {
+            return;
+        }
+        // Like other violations, flying is only logged while kicking is turned off
+        if config.kick_violations == 0 {
+            if floating_ticks == config.max_floating_ticks + 1 {
+                log::warn!("Player {} is flying", player.gameprofile.name);
+            }
+        } else {
+            log::warn!("Player {} was kicked for flying", player.gameprofile.name);
+            player
+                .kick(
+                    KickCause::AntiCheat,
+                    TextComponent::translate("multiplayer.disconnect.flying", []),
+                )
+                .await;
+        }
+    }
+
+    /// Checks a movement reported by the player. Returns `false` if the player moved further than
+    /// they possibly could, in which case they should be moved back.
+    pub async fn check_move(
+        &self,
+        player: &Arc<Player>,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        on_ground: bool,
+    ) -> bool {
+        let config = &advanced_config().anti_cheat;
+        let entity = &player.living_entity.entity;
+        let move_packets = self.move_packets.fetch_add(1, Ordering::Relaxed) + 1;
+        // The client keeps moving from where it was until it confirms a teleport
+        if !config.enabled
+            || player.gamemode.load() == GameMode::Spectator
+            || player.awaiting_teleport.lock().await.is_some()
+        {
+            self.floating.store(false, Ordering::Relaxed);
+            return true;
+        }
+
+        let abilities = player.abilities.lock().await;
+        let fall_flying = entity.fall_flying.load(Ordering::Relaxed);
+        let floating = !on_ground
+            && !abilities.allow_flying
+            && !config.allow_flight
+            && !fall_flying
+            && to.y - from.y >= MIN_FALL_SPEED
+            && !player
+                .living_entity
+                .has_effect(EffectType::Levitation)
+                .await
+            && !player
+                .living_entity
+                .has_effect(EffectType::SlowFalling)
+                .await
+            && !Self::touches_blocks(entity, to).await;
+        drop(abilities);
+        self.floating.store(floating, Ordering::Relaxed);
+
+        let mut max_move = if fall_flying {
+            config.max_elytra_move_squared
+        } else {
+            config.max_move_squared
+        };
+        // Packets sent together after lag may each cover a whole tick of movement
+        if move_packets <= MAX_MOVE_PACKETS_PER_TICK {
+            max_move *= f64::from(move_packets);
+        }
+        if let Some(speed) = player.living_entity.get_effect(EffectType::Speed).await {
+            let multiplier = 1.0 + 0.2 * (f64::from(speed.amplifier) + 1.0);
+            max_move *= multiplier * multiplier;
+        }
+
+        // Knockback and explosions launch players further than they could walk
+        let moved = to.sub(&from).length_squared();
+        let launched = entity.velocity.load().length_squared();
+        if moved - launched > max_move {
+            self.flag(
+                player,
+                &format!("moved too quickly ({:.2} blocks)", moved.sqrt()),
+            )
+            .await;
+            return false;
+        }
+        true
+    }
+
+    /// Checks whether the player can reach the block, flagging them if they can not.
+    pub async fn check_block_reach(&self, player: &Arc<Player>, position: &BlockPos) -> bool {
+        let config = &advanced_config().anti_cheat;
+        let tolerance = if config.enabled {
+            config.reach_tolerance
+        } else {
+            // Vanilla
+            1.0
+        };
+        if player.can_interact_with_block_at(position, tolerance) {
+            return true;
+        }
+        self.flag(
+            player,
+            &format!("tried to interact with block out of reach at {position}"),
+        )
+        .await;
+        false
+    }
+
+    /// Checks whether the player can reach the entity, flagging them if they can not.
+    pub async fn check_entity_reach(&self, player: &Arc<Player>, target: &Entity) -> bool {
+        let config = &advanced_config().anti_cheat;
+        if !config.enabled {
+            return true;
+        }
+        if player.can_interact_with_entity(target, config.reach_tolerance) {
+            return true;
+        }
+        self.flag(
+            player,
+            &format!(
+                "tried to interact with entity out of reach at {:?}",
+                target.pos.load()
+            ),
+        )
+        .await;
+        false
+    }
+
+    /// Logs the violation and, if validation is enabled, kicks the player once they have too many.
+    async fn flag(&self, player: &Arc<Player>, violation: &str) {
+        let config = &advanced_config().anti_cheat;
+        if !config.enabled {
+            log::warn!("Player {} {violation}", player.gameprofile.name);
+            return;
+        }
+        let violations = self.violations.fetch_add(1, Ordering::Relaxed) + 1;
+        log::warn!(
+            "Player {} {violation}, violation {violations}",
+            player.gameprofile.name
+        );
+        if config.kick_violations != 0 && violations >= config.kick_violations {
+            player
+                .kick(
+                    KickCause::AntiCheat,
+                    TextComponent::translate("multiplayer.disconnect.invalid_player_movement", []),
+                )
+                .await;
+        }
+    }
+
+    /// Whether there are any blocks around the player at the position, which they could be
+    /// climbing or standing on.
+    async fn touches_blocks(entity: &Entity, pos: Vector3<f64>) -> bool {
+        let world = entity.world.read().await;
+        let min = BlockPos::floored(pos.x - 0.5, pos.y - 0.5, pos.z - 0.5);
+        let max = BlockPos::floored(pos.x + 0.5, pos.y + 2.0, pos.z + 0.5);
+        for block_pos in BlockPos::iterate(min, max) {
+            if !world.get_block_state(&block_pos).await.is_air() {
+                return true;
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,333 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_inventory::equipment_slot::EquipmentSlot;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{MetaDataType, Metadata};
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
+
+use super::{Entity, EntityBase, living::LivingEntity};
+
+/// The equipment slots of an armor stand and their names in the `equipment` NBT compound.
+const EQUIPMENT_SLOTS: [(EquipmentSlot, &str); 6] = [
+    (EquipmentSlot::MAIN_HAND, "mainhand"),
+    (EquipmentSlot::OFF_HAND, "offhand"),
+    (EquipmentSlot::FEET, "feet"),
+    (EquipmentSlot::LEGS, "legs"),
+    (EquipmentSlot::CHEST, "chest"),
+    (EquipmentSlot::HEAD, "head"),
+];
+
+/// Metadata index of the armor stand flags, the body part rotations follow it.
+const FLAGS_INDEX: u8 = 15;
+
+const SMALL_FLAG: u8 = 0x01;
+const SHOW_ARMS_FLAG: u8 = 0x04;
+const NO_BASE_PLATE_FLAG: u8 = 0x08;
+const MARKER_FLAG: u8 = 0x10;
+
+/// The rotations of each body part in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArmorStandPose {
+    pub head: Vector3<f32>,
+    pub body: Vector3<f32>,
+    pub left_arm: Vector3<f32>,
+    pub right_arm: Vector3<f32>,
+    pub left_leg: Vector3<f32>,
+    pub right_leg: Vector3<f32>,
+}
+
+impl Default for ArmorStandPose {
+    fn default() -> Self {
+        Self {
+            head: Vector3::new(0.0, 0.0, 0.0),
+            body: Vector3::new(0.0, 0.0, 0.0),
+            left_arm: Vector3::new(-10.0, 0.0, -10.0),
+            right_arm: Vector3::new(-15.0, 0.0, 10.0),
+            left_leg: Vector3::new(-1.0, 0.0, -1.0),
+            right_leg: Vector3::new(1.0, 0.0, 1.0),
+        }
+    }
+}
+
+impl ArmorStandPose {
+    /// The body parts in metadata order with their names in the `Pose` NBT compound.
+    fn parts(&self) -> [(&'static str, Vector3<f32>); 6] {
+        [
+            ("Head", self.head),
+            ("Body", self.body),
+            ("LeftArm", self.left_arm),
+            ("RightArm", self.right_arm),
+            ("LeftLeg", self.left_leg),
+            ("RightLeg", self.right_leg),
+        ]
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        for (name, rotation) in self.parts() {
+            nbt.put(
+                name,
+                NbtTag::List(vec![
+                    rotation.x.into(),
+                    rotation.y.into(),
+                    rotation.z.into(),
+                ]),
+            );
+        }
+    }
+
+    fn read_nbt(nbt: &NbtCompound) -> Self {
+        let mut pose = Self::default();
+        let parts = [
+            ("Head", &mut pose.head),
+            ("Body", &mut pose.body),
+            ("LeftArm", &mut pose.left_arm),
+            ("RightArm", &mut pose.right_arm),
+            ("LeftLeg", &mut pose.left_leg),
+            ("RightLeg", &mut pose.right_leg),
+        ];
+        for (name, rotation) in parts {
+            if let Some([x, y, z]) = nbt.get_list(name) {
+                *rotation = Vector3::new(
+                    x.extract_float().unwrap_or(0.0),
+                    y.extract_float().unwrap_or(0.0),
+                    z.extract_float().unwrap_or(0.0),
+                );
+            }
+        }
+        pose
+    }
+}
+
+pub struct ArmorStandEntity {
+    living_entity: LivingEntity,
+    /// The small, show arms, no base plate and marker flags
+    flags: AtomicU8,
+    pose: AtomicCell<ArmorStandPose>,
+}
+
+impl ArmorStandEntity {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            living_entity: LivingEntity::new(entity),
+            flags: AtomicU8::new(0),
+            pose: AtomicCell::new(ArmorStandPose::default()),
+        }
+    }
+
+    fn has_flag(&self, flag: u8) -> bool {
+        self.flags.load(Ordering::Relaxed) & flag != 0
+    }
+
+    async fn set_flag(&self, flag: u8, value: bool) {
+        if value {
+            self.flags.fetch_or(flag, Ordering::Relaxed);
+        } else {
+            self.flags.fetch_and(!flag, Ordering::Relaxed);
+        }
+        self.send_flags().await;
+    }
+
+    pub fn is_small(&self) -> bool {
+        self.has_flag(SMALL_FLAG)
+    }
+
+    pub async fn set_small(&self, small: bool) {
+        self.set_flag(SMALL_FLAG, small).await;
+    }
+
+    pub fn shows_arms(&self) -> bool {
+        self.has_flag(SHOW_ARMS_FLAG)
+    }
+
+    pub async fn set_show_arms(&self, show_arms: bool) {
+        self.set_flag(SHOW_ARMS_FLAG, show_arms).await;
+    }
+
+    pub fn has_base_plate(&self) -> bool {
+        !self.has_flag(NO_BASE_PLATE_FLAG)
+    }
+
+    pub async fn set_base_plate(&self, base_plate: bool) {
+        self.set_flag(NO_BASE_PLATE_FLAG, !base_plate).await;
+    }
+
+    /// Markers have no hitbox and can't be interacted with.
+    pub fn is_marker(&self) -> bool {
+        self.has_flag(MARKER_FLAG)
+    }
+
+    pub async fn set_marker(&self, marker: bool) {
+        self.set_flag(MARKER_FLAG, marker).await;
+    }
+
+    pub fn get_pose(&self) -> ArmorStandPose {
+        self.pose.load()
+    }
+
+    pub async fn set_pose(&self, pose: ArmorStandPose) {
+        self.pose.store(pose);
+        self.send_pose().await;
+    }
+
+    /// Puts the stack into the slot and shows it to nearby players, returns the previous stack.
+    pub async fn set_equipment(&self, slot: &EquipmentSlot, stack: ItemStack) -> ItemStack {
+        let previous = self
+            .living_entity
+            .entity_equipment
+            .lock()
+            .await
+            .put(slot, stack.clone())
+            .await;
+        self.living_entity
+            .send_equipment_changes(&[(slot.clone(), stack)])
+            .await;
+        previous
+    }
+
+    async fn send_flags(&self) {
+        self.living_entity
+            .entity
+            .send_meta_data(&[Metadata::new(
+                FLAGS_INDEX,
+                MetaDataType::Byte,
+                self.flags.load(Ordering::Relaxed),
+            )])
+            .await;
+    }
+
+    async fn send_pose(&self) {
+        let metadata: Vec<_> = self
+            .pose
+            .load()
+            .parts()
+            .into_iter()
+            .zip(FLAGS_INDEX + 1..)
+            .map(|((_, rotation), index)| Metadata::new(index, MetaDataType::Rotation, rotation))
+            .collect();
+        self.living_entity.entity.send_meta_data(&metadata).await;
+    }
+
+    async fn send_equipment(&self) {
+        let mut equipment = Vec::new();
+        {
+            let entity_equipment = self.living_entity.entity_equipment.lock().await;
+            for (slot, _) in EQUIPMENT_SLOTS {
+                let stack = entity_equipment.get(&slot).lock().await.clone();
+                if !stack.is_empty() {
+                    equipment.push((slot, stack));
+                }
+            }
+        }
+        if !equipment.is_empty() {
+            self.living_entity.send_equipment_changes(&equipment).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for ArmorStandEntity {
+    async fn init_data_tracker(&self) {
+        self.send_flags().await;
+        self.send_pose().await;
+        self.send_equipment().await;
+    }
+
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        nbt.put_bool("Small", self.is_small());
+        nbt.put_bool("ShowArms", self.shows_arms());
+        nbt.put_bool("NoBasePlate", !self.has_base_plate());
+        nbt.put_bool("Marker", self.is_marker());
+
+        let mut pose = NbtCompound::new();
+        self.pose.load().write_nbt(&mut pose);
+        nbt.put_component("Pose", pose);
+
+        let mut equipment = NbtCompound::new();
+        {
+            let entity_equipment = self.living_entity.entity_equipment.lock().await;
+            for (slot, name) in EQUIPMENT_SLOTS {
+                let stack = entity_equipment.get(&slot).lock().await.clone();
+                if !stack.is_empty() {
+                    let mut item = NbtCompound::new();
+                    stack.write_item_stack(&mut item);
+                    equipment.put_component(name, item);
+                }
+            }
+        }
+        if !equipment.child_tags.is_empty() {
+            nbt.put_component("equipment", equipment);
+        }
+    }
+
+    async fn read_nbt(&self, nbt: &NbtCompound) {
+        self.living_entity.read_nbt(nbt).await;
+        let flags = [
+            ("Small", SMALL_FLAG),
+            ("ShowArms", SHOW_ARMS_FLAG),
+            ("NoBasePlate", NO_BASE_PLATE_FLAG),
+            ("Marker", MARKER_FLAG),
+        ]
+        .into_iter()
+        .filter(|(name, _)| nbt.get_bool(name).unwrap_or(false))
+        .fold(0, |flags, (_, flag)| flags | flag);
+        self.flags.store(flags, Ordering::Relaxed);
+
+        self.pose.store(
+            nbt.get_compound("Pose")
+                .map(ArmorStandPose::read_nbt)
+                .unwrap_or_default(),
+        );
+
+        if let Some(equipment) = nbt.get_compound("equipment") {
+            let mut entity_equipment = self.living_entity.entity_equipment.lock().await;
+            for (slot, name) in EQUIPMENT_SLOTS {
+                if let Some(stack) = equipment
+                    .get_compound(name)
+                    .and_then(ItemStack::read_item_stack)
+                {
+                    entity_equipment.put(&slot, stack).await;
+                }
+            }
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        Some(&self.living_entity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::ArmorStandPose;
+
+    #[test]
+    fn pose_nbt_round_trip() {
+        let pose = ArmorStandPose {
+            head: Vector3::new(10.0, 20.0, 30.0),
+            right_leg: Vector3::new(-5.0, 0.0, 5.0),
+            ..Default::default()
+        };
+        let mut nbt = NbtCompound::new();
+        pose.write_nbt(&mut nbt);
+        assert_eq!(ArmorStandPose::read_nbt(&nbt), pose);
+    }
+
+    #[test]
+    fn missing_parts_use_the_default_pose() {
+        assert_eq!(
+            ArmorStandPose::read_nbt(&NbtCompound::new()),
+            ArmorStandPose::default()
+        );
+    }
+}
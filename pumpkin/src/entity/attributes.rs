@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use crate::entity::NBTStorage;
+use async_trait::async_trait;
+use pumpkin_data::attributes::Attributes;
+use pumpkin_data::entity::{EffectType, EntityType};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_protocol::java::client::play::{AttributeModifier, AttributeProperty};
+use uuid::Uuid;
+
+pub const MAX_HEALTH: &str = "minecraft:generic.max_health";
+pub const MOVEMENT_SPEED: &str = "minecraft:generic.movement_speed";
+pub const ATTACK_DAMAGE: &str = "minecraft:generic.attack_damage";
+pub const ATTACK_SPEED: &str = "minecraft:generic.attack_speed";
+pub const KNOCKBACK_RESISTANCE: &str = "minecraft:generic.knockback_resistance";
+pub const FOLLOW_RANGE: &str = "minecraft:generic.follow_range";
+
+/// The movement speed boost of sprinting, it isn't saved
+pub const SPRINTING_MODIFIER: Uuid = Uuid::from_u128(0x662A_6B8D_DA3E_4C1C_8813_96EA_6097_278D);
+
+/// Adds the amount to the base value
+pub const OPERATION_ADD_VALUE: u8 = 0;
+/// Adds the amount multiplied by the base value
+pub const OPERATION_ADD_MULTIPLIED_BASE: u8 = 1;
+/// Multiplies the total value by `1 + amount`
+pub const OPERATION_ADD_MULTIPLIED_TOTAL: u8 = 2;
+
+/// Looks up an attribute in the registry.
+///
+/// Accepts both the current names like `minecraft:max_health` and the legacy ones
+/// with a category like `minecraft:generic.max_health`.
+#[must_use]
+pub fn registry_attribute(name: &str) -> Option<Attributes> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let name = name.split_once('.').map_or(name, |(_, name)| name);
+    Attributes::from_name(name)
+}
+
+/// Clamps a value into the range of the attribute, like vanilla does for every computed value.
+#[must_use]
+pub fn sanitize_value(name: &str, value: f64) -> f64 {
+    registry_attribute(name).map_or(value, |attribute| {
+        value.clamp(attribute.min_value, attribute.max_value)
+    })
+}
+
+/// The value of an attribute when neither the entity nor its NBT defines one.
+#[must_use]
+pub fn default_value(name: &str) -> f64 {
+    registry_attribute(name).map_or(0.0, |attribute| attribute.default_value)
+}
+
+/// The modifier a status effect applies while it is active, scaled by its amplifier.
+#[must_use]
+pub fn effect_modifier(
+    effect: EffectType,
+    amplifier: u8,
+) -> Option<(&'static str, AttributeInstance)> {
+    let (name, id, amount, operation) = match effect {
+        EffectType::Speed => (
+            MOVEMENT_SPEED,
+            0x91AE_AA56_376B_4498_935B_2F7F_6807_0635,
+            0.2,
+            OPERATION_ADD_MULTIPLIED_TOTAL,
+        ),
+        EffectType::Slowness => (
+            MOVEMENT_SPEED,
+            0x7107_DE5E_7CE8_4030_940E_514C_1F16_0890,
+            -0.15,
+            OPERATION_ADD_MULTIPLIED_TOTAL,
+        ),
+        EffectType::Haste => (
+            ATTACK_SPEED,
+            0xAF8B_6E3F_3328_4C0A_AA36_5BA2_BB9D_BEF3,
+            0.1,
+            OPERATION_ADD_MULTIPLIED_TOTAL,
+        ),
+        EffectType::MiningFatigue => (
+            ATTACK_SPEED,
+            0x55FC_ED67_E92A_486E_9800_B47F_202C_4386,
+            -0.1,
+            OPERATION_ADD_MULTIPLIED_TOTAL,
+        ),
+        EffectType::Strength => (
+            ATTACK_DAMAGE,
+            0x648D_7064_6A60_4F59_8ABE_C2C2_3A6D_D7A9,
+            3.0,
+            OPERATION_ADD_VALUE,
+        ),
+        EffectType::Weakness => (
+            ATTACK_DAMAGE,
+            0x2265_3B89_116E_49DC_9B6B_9971_489B_5BE5,
+            -4.0,
+            OPERATION_ADD_VALUE,
+        ),
+        EffectType::HealthBoost => (
+            MAX_HEALTH,
+            0x5D6F_0BA2_1186_46AC_B896_C61C_5CEE_99CC,
+            4.0,
+            OPERATION_ADD_VALUE,
+        ),
+        _ => return None,
+    };
+    Some((
+        name,
+        AttributeInstance {
+            id: Uuid::from_u128(id),
+            amount: amount * (f64::from(amplifier) + 1.0),
+            operation,
+        },
+    ))
+}
+
+/// A single modifier applied on top of an attribute's base value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeInstance {
+    pub id: Uuid,
+    pub amount: f64,
+    pub operation: u8,
+}
+
+#[async_trait]
+impl NBTStorage for AttributeInstance {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let uuid = self.id.as_u128();
+        nbt.put(
+            "UUID",
+            NbtTag::IntArray(vec![
+                (uuid >> 96) as i32,
+                ((uuid >> 64) & 0xFFFF_FFFF) as i32,
+                ((uuid >> 32) & 0xFFFF_FFFF) as i32,
+                (uuid & 0xFFFF_FFFF) as i32,
+            ]),
+        );
+        nbt.put_double("Amount", self.amount);
+        nbt.put_int("Operation", i32::from(self.operation));
+    }
+
+    async fn create_from_nbt(nbt: &mut NbtCompound) -> Option<Self> {
+        let Some(&[a, b, c, d]) = nbt.get_int_array("UUID") else {
+            log::warn!("Unable to read attribute modifier. UUID is not present");
+            return None;
+        };
+        let id = Uuid::from_u128(
+            (u128::from(a as u32) << 96)
+                | (u128::from(b as u32) << 64)
+                | (u128::from(c as u32) << 32)
+                | u128::from(d as u32),
+        );
+        Some(Self {
+            id,
+            amount: nbt.get_double("Amount").unwrap_or(0.0),
+            operation: nbt.get_int("Operation").unwrap_or(0).clamp(0, 2) as u8,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Attribute {
+    base: f64,
+    modifiers: Vec<AttributeInstance>,
+}
+
+/// The attributes of a living entity, like its max health or movement speed.
+///
+/// Attributes which were never set use their default value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributeMap {
+    attributes: HashMap<String, Attribute>,
+}
+
+impl AttributeMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The base values every entity of the type spawns with.
+    #[must_use]
+    pub fn for_entity_type(entity_type: &EntityType) -> Self {
+        let mut map = Self::new();
+        if let Some(max_health) = entity_type.max_health {
+            map.set_base_value(MAX_HEALTH, f64::from(max_health));
+        }
+        for (attribute, base) in entity_type.attributes {
+            map.set_base_value(&format!("minecraft:generic.{}", attribute.name), *base);
+        }
+        map
+    }
+
+    #[must_use]
+    pub fn get_base_value(&self, name: &str) -> f64 {
+        self.attributes
+            .get(name)
+            .map_or_else(|| default_value(name), |attribute| attribute.base)
+    }
+
+    pub fn set_base_value(&mut self, name: &str, base: f64) {
+        self.attributes
+            .entry(name.to_string())
+            .or_insert_with(|| Attribute {
+                base,
+                modifiers: Vec::new(),
+            })
+            .base = base;
+    }
+
+    /// Adds a modifier, replacing an existing one with the same id.
+    pub fn add_modifier(&mut self, name: &str, modifier: AttributeInstance) {
+        let attribute = self
+            .attributes
+            .entry(name.to_string())
+            .or_insert_with(|| Attribute {
+                base: default_value(name),
+                modifiers: Vec::new(),
+            });
+        attribute.modifiers.retain(|m| m.id != modifier.id);
+        attribute.modifiers.push(modifier);
+    }
+
+    pub fn remove_modifier(&mut self, name: &str, id: Uuid) {
+        if let Some(attribute) = self.attributes.get_mut(name) {
+            attribute.modifiers.retain(|m| m.id != id);
+        }
+    }
+
+    /// The effective value after applying all modifiers.
+    ///
+    /// Additive modifiers are applied first, then the ones multiplying the base value
+    /// and at last the ones multiplying the total value. The result is clamped to the range of
+    /// the attribute, so for example strong Slowness stops an entity instead of reversing it.
+    #[must_use]
+    pub fn get_value(&self, name: &str) -> f64 {
+        let Some(attribute) = self.attributes.get(name) else {
+            return default_value(name);
+        };
+        let modifiers = |operation| {
+            attribute
+                .modifiers
+                .iter()
+                .filter(move |m| m.operation == operation)
+                .map(|m| m.amount)
+        };
+
+        let base = attribute.base + modifiers(OPERATION_ADD_VALUE).sum::<f64>();
+        let mut value = base;
+        for amount in modifiers(OPERATION_ADD_MULTIPLIED_BASE) {
+            value += base * amount;
+        }
+        for amount in modifiers(OPERATION_ADD_MULTIPLIED_TOTAL) {
+            value *= 1.0 + amount;
+        }
+        sanitize_value(name, value)
+    }
+
+    /// The attribute as sent to clients, `None` for attributes the client doesn't know.
+    #[must_use]
+    pub fn property(&self, name: &str) -> Option<AttributeProperty> {
+        let id = registry_attribute(name)?.id;
+        let (base, modifiers) = self.attributes.get(name).map_or_else(
+            || (default_value(name), Vec::new()),
+            |attribute| {
+                let modifiers = attribute
+                    .modifiers
+                    .iter()
+                    .map(|modifier| AttributeModifier {
+                        id: modifier.id.to_string(),
+                        amount: modifier.amount,
+                        operation: modifier.operation,
+                    })
+                    .collect();
+                (attribute.base, modifiers)
+            },
+        );
+        Some(AttributeProperty {
+            id: id.into(),
+            base,
+            modifiers,
+        })
+    }
+
+    /// All attributes which were set, as sent to clients.
+    #[must_use]
+    pub fn properties(&self) -> Vec<AttributeProperty> {
+        self.attributes
+            .keys()
+            .filter_map(|name| self.property(name))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl NBTStorage for AttributeMap {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut attributes = Vec::with_capacity(self.attributes.len());
+        for (name, attribute) in &self.attributes {
+            let mut modifiers = Vec::with_capacity(attribute.modifiers.len());
+            for modifier in &attribute.modifiers {
+                let mut modifier_nbt = NbtCompound::new();
+                modifier.write_nbt(&mut modifier_nbt).await;
+                modifiers.push(NbtTag::Compound(modifier_nbt));
+            }
+            let mut attribute_nbt = NbtCompound::new();
+            attribute_nbt.put_string("Name", name.clone());
+            attribute_nbt.put_double("Base", attribute.base);
+            if !modifiers.is_empty() {
+                attribute_nbt.put_list("Modifiers", modifiers);
+            }
+            attributes.push(NbtTag::Compound(attribute_nbt));
+        }
+        if !attributes.is_empty() {
+            nbt.put_list("Attributes", attributes);
+        }
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        let Some(attributes) = nbt.get_list("Attributes") else {
+            return;
+        };
+        for attribute in attributes {
+            let NbtTag::Compound(attribute_nbt) = attribute else {
+                continue;
+            };
+            let Some(name) = attribute_nbt.get_string("Name") else {
+                log::warn!("Unable to read attribute. Name is not present");
+                continue;
+            };
+            let mut modifiers = Vec::new();
+            for modifier in attribute_nbt.get_list("Modifiers").unwrap_or_default() {
+                if let NbtTag::Compound(modifier_nbt) = modifier {
+                    if let Some(modifier) =
+                        AttributeInstance::create_from_nbt(&mut modifier_nbt.clone()).await
+                    {
+                        modifiers.push(modifier);
+                    }
+                }
+            }
+            self.attributes.insert(
+                name.clone(),
+                Attribute {
+                    base: attribute_nbt
+                        .get_double("Base")
+                        .unwrap_or_else(|| default_value(name)),
+                    modifiers,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifier(operation: u8, amount: f64) -> AttributeInstance {
+        AttributeInstance {
+            id: Uuid::from_u128(u128::from(operation) + 1),
+            amount,
+            operation,
+        }
+    }
+
+    #[test]
+    fn value_applies_modifiers_in_order() {
+        let mut map = AttributeMap::new();
+        map.set_base_value(ATTACK_DAMAGE, 2.0);
+        map.add_modifier(ATTACK_DAMAGE, modifier(OPERATION_ADD_MULTIPLIED_TOTAL, 0.5));
+        map.add_modifier(ATTACK_DAMAGE, modifier(OPERATION_ADD_MULTIPLIED_BASE, 1.0));
+        map.add_modifier(ATTACK_DAMAGE, modifier(OPERATION_ADD_VALUE, 2.0));
+        // (2 + 2) + 4 * 1 = 8, then * 1.5
+        assert!((map.get_value(ATTACK_DAMAGE) - 12.0).abs() < f64::EPSILON);
+        assert!((map.get_value(MAX_HEALTH) - 20.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn registry_attribute_accepts_legacy_names() {
+        assert_eq!(
+            registry_attribute(FOLLOW_RANGE),
+            registry_attribute("minecraft:follow_range")
+        );
+        assert!((default_value(FOLLOW_RANGE) - 32.0).abs() < f64::EPSILON);
+        assert!(registry_attribute("minecraft:generic.unknown").is_none());
+    }
+
+    #[test]
+    fn effect_modifier_scales_with_amplifier() {
+        let (name, strength) = effect_modifier(EffectType::Strength, 1).unwrap();
+        let mut map = AttributeMap::new();
+        map.add_modifier(name, strength);
+        assert!((map.get_value(ATTACK_DAMAGE) - 8.0).abs() < f64::EPSILON);
+        assert!(effect_modifier(EffectType::Glowing, 0).is_none());
+    }
+
+    #[test]
+    fn slowness_vii_stops_instead_of_reversing() {
+        let mut zombie = AttributeMap::for_entity_type(&EntityType::ZOMBIE);
+        let (name, slowness) = effect_modifier(EffectType::Slowness, 6).unwrap();
+        zombie.add_modifier(name, slowness);
+        assert!(zombie.get_value(MOVEMENT_SPEED).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn weakness_never_makes_damage_negative() {
+        let mut player = AttributeMap::for_entity_type(&EntityType::PLAYER);
+        let (name, weakness) = effect_modifier(EffectType::Weakness, 0).unwrap();
+        player.add_modifier(name, weakness);
+        assert!(player.get_value(ATTACK_DAMAGE).abs() < f64::EPSILON);
+
+        // Strength still counts against the weakness before the value is clamped
+        let (name, strength) = effect_modifier(EffectType::Strength, 1).unwrap();
+        player.add_modifier(name, strength);
+        assert!((player.get_value(ATTACK_DAMAGE) - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn entity_type_defaults() {
+        let zombie = AttributeMap::for_entity_type(&EntityType::ZOMBIE);
+        assert!((zombie.get_value(MAX_HEALTH) - 20.0).abs() < f64::EPSILON);
+        assert!((zombie.get_value(MOVEMENT_SPEED) - 0.23).abs() < f64::EPSILON);
+        assert!((zombie.get_value(FOLLOW_RANGE) - 35.0).abs() < f64::EPSILON);
+
+        let enderman = AttributeMap::for_entity_type(&EntityType::ENDERMAN);
+        assert!((enderman.get_value(MAX_HEALTH) - 40.0).abs() < f64::EPSILON);
+        assert!((enderman.get_value(ATTACK_DAMAGE) - 7.0).abs() < f64::EPSILON);
+        // Not overridden by the type, so it keeps the attribute default
+        assert!((enderman.get_value(KNOCKBACK_RESISTANCE)).abs() < f64::EPSILON);
+
+        let ravager = AttributeMap::for_entity_type(&EntityType::RAVAGER);
+        assert!((ravager.get_value(MAX_HEALTH) - 100.0).abs() < f64::EPSILON);
+        assert!((ravager.get_value(KNOCKBACK_RESISTANCE) - 0.75).abs() < f64::EPSILON);
+        assert!((ravager.get_value(ATTACK_DAMAGE) - 12.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn nbt_round_trip() {
+        let mut map = AttributeMap::new();
+        map.set_base_value(MAX_HEALTH, 40.0);
+        map.add_modifier(
+            MOVEMENT_SPEED,
+            modifier(OPERATION_ADD_MULTIPLIED_BASE, -0.15),
+        );
+
+        let mut nbt = NbtCompound::new();
+        map.write_nbt(&mut nbt).await;
+        let mut read = AttributeMap::new();
+        read.read_nbt(&mut nbt).await;
+
+        assert_eq!(read, map);
+        assert!((read.get_value(MAX_HEALTH) - 40.0).abs() < f64::EPSILON);
+    }
+}
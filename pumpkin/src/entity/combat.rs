@@ -70,6 +70,18 @@ pub async fn handle_knockback(attacker: &Entity, world: &World, victim: &Entity,
     world.broadcast_packet_all(&packet).await;
 }
 
+/// Knocks the victim away from `origin`, the position the damage came from.
+pub async fn knockback_from(victim: &Entity, world: &World, origin: Vector3<f64>, strength: f64) {
+    let pos = victim.pos.load();
+
+    let saved_velo = victim.velocity.load();
+    victim.knockback(strength, origin.x - pos.x, origin.z - pos.z);
+
+    let packet = CEntityVelocity::new(VarInt(victim.entity_id), victim.velocity.load());
+    victim.velocity.store(saved_velo);
+    world.broadcast_packet_all(&packet).await;
+}
+
 pub async fn spawn_sweep_particle(attacker_entity: &Entity, world: &World, pos: &Vector3<f64>) {
     let yaw = attacker_entity.yaw.load();
     let d = -f64::from((yaw.to_radians()).sin());
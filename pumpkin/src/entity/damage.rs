@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use pumpkin_data::damage::{DamageType, DeathMessageType};
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::click::ClickEvent;
+use pumpkin_util::text::hover::HoverEvent;
+
+use super::EntityBase;
+
+/// What dealt damage to an entity: the damage type, the entities behind it and where it came from.
+#[derive(Clone)]
+pub struct DamageSource {
+    pub damage_type: DamageType,
+    /// The entity which directly dealt the damage, e.g. an arrow
+    pub direct_entity: Option<Arc<dyn EntityBase>>,
+    /// The entity responsible for the damage, e.g. the player who shot the arrow
+    pub attacker: Option<Arc<dyn EntityBase>>,
+    /// Where the damage came from if not from an entity, e.g. the center of an explosion
+    pub position: Option<Vector3<f64>>,
+}
+
+impl DamageSource {
+    /// Damage not caused by anything in particular, like starving or falling.
+    #[must_use]
+    pub const fn new(damage_type: DamageType) -> Self {
+        Self {
+            damage_type,
+            direct_entity: None,
+            attacker: None,
+            position: None,
+        }
+    }
+
+    /// Damage dealt directly by an entity, like a melee attack.
+    pub fn entity(damage_type: DamageType, attacker: Arc<dyn EntityBase>) -> Self {
+        Self {
+            damage_type,
+            direct_entity: Some(attacker.clone()),
+            attacker: Some(attacker),
+            position: None,
+        }
+    }
+
+    /// Damage dealt by one entity on behalf of another, like an arrow shot by a player.
+    pub fn indirect(
+        damage_type: DamageType,
+        direct_entity: Arc<dyn EntityBase>,
+        attacker: Option<Arc<dyn EntityBase>>,
+    ) -> Self {
+        Self {
+            damage_type,
+            direct_entity: Some(direct_entity),
+            attacker,
+            position: None,
+        }
+    }
+
+    /// Damage coming from a position, like an explosion.
+    #[must_use]
+    pub const fn at(damage_type: DamageType, position: Vector3<f64>) -> Self {
+        Self {
+            damage_type,
+            direct_entity: None,
+            attacker: None,
+            position: Some(position),
+        }
+    }
+
+    /// The position the damage came from, which the victim gets knocked away from.
+    #[must_use]
+    pub fn source_position(&self) -> Option<Vector3<f64>> {
+        self.position.or_else(|| {
+            self.direct_entity
+                .as_ref()
+                .map(|entity| entity.get_entity().pos.load())
+        })
+    }
+
+    /// The message shown when this damage kills the victim. Damage without an entity behind it
+    /// credits `kill_credit`, the entity which last attacked the victim, like vanilla does with
+    /// "was doomed to fall by".
+    pub fn death_message(
+        &self,
+        victim: &dyn EntityBase,
+        kill_credit: Option<&dyn EntityBase>,
+    ) -> TextComponent {
+        let key = format!("death.attack.{}", self.damage_type.message_id);
+        if self.damage_type.death_message_type == Some(DeathMessageType::IntentionalGameDesign) {
+            let link = TextComponent::translate(format!("{key}.link"), [])
+                .click_event(ClickEvent::OpenUrl {
+                    url: "https://bugs.mojang.com/browse/MCPE-28723".into(),
+                })
+                .hover_event(HoverEvent::show_text(TextComponent::text("MCPE-28723")));
+            return TextComponent::translate(
+                format!("{key}.message"),
+                [victim.get_display_name(), link],
+            );
+        }
+
+        let attacker = self.attacker.as_ref().or(self.direct_entity.as_ref());
+        if let Some(attacker) = attacker {
+            return TextComponent::translate(
+                key,
+                [victim.get_display_name(), attacker.get_display_name()],
+            );
+        }
+        match kill_credit {
+            Some(killer) => TextComponent::translate(
+                format!("{key}.player"),
+                [victim.get_display_name(), killer.get_display_name()],
+            ),
+            None => TextComponent::translate(key, [victim.get_display_name()]),
+        }
+    }
+}
+
+impl From<DamageType> for DamageSource {
+    fn from(damage_type: DamageType) -> Self {
+        Self::new(damage_type)
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::position::BlockPos;
+use uuid::Uuid;
+
+use crate::entity::{Entity, EntityBase, leash::LeashHolder, living::LivingEntity};
+use crate::server::Server;
+use crate::world::World;
+
+/// The knot a lead is tied to on a fence.
+pub struct LeashKnotEntity {
+    entity: Entity,
+}
+
+impl LeashKnotEntity {
+    pub fn new(entity: Entity) -> Self {
+        Self { entity }
+    }
+
+    /// Spawns a knot on the fence unless there already is one.
+    pub async fn get_or_spawn(world: &Arc<World>, fence: BlockPos) {
+        if world.leashes.lock().await.knot(fence).is_some() {
+            return;
+        }
+        let position = fence.to_f64().add_raw(0.5, 0.375, 0.5);
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            world.clone(),
+            position,
+            EntityType::LEASH_KNOT,
+            false,
+        );
+        world.spawn_entity(Arc::new(Self::new(entity))).await;
+    }
+
+    /// A knot only stays while its fence stands and something is tied to it.
+    async fn survives(&self, world: &World) -> bool {
+        let fence = self.entity.block_pos.load();
+        if !world
+            .get_block(&fence)
+            .await
+            .is_tagged_with("#minecraft:fences")
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        world
+            .leashes
+            .lock()
+            .await
+            .holds_any(LeashHolder::Fence(fence))
+    }
+}
+
+#[async_trait]
+impl EntityBase for LeashKnotEntity {
+    async fn tick(&self, _caller: Arc<dyn EntityBase>, _server: &Server) {
+        let world = self.entity.world.read().await.clone();
+        if !self.survives(&world).await {
+            self.entity.remove().await;
+        }
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
@@ -1 +1,2 @@
+pub mod leash_knot;
 pub mod painting;
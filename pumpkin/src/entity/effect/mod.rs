@@ -1,12 +1,17 @@
 use crate::entity::NBTStorage;
 use async_trait::async_trait;
 use pumpkin_data::entity::EffectType;
+use pumpkin_data::potion::PotionEffect;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::tag::NbtTag;
 
+/// The duration of effects which never run out
+pub const INFINITE_DURATION: i32 = -1;
+
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct Effect {
     pub r#type: EffectType,
+    /// The remaining duration in ticks or [`INFINITE_DURATION`]
     pub duration: i32,
     pub amplifier: u8,
     pub ambient: bool,
@@ -15,6 +20,96 @@ pub struct Effect {
     pub blend: bool,
 }
 
+impl Effect {
+    /// The effect a drunk potion applies. Instant health and damage have to be applied right
+    /// away instead.
+    #[must_use]
+    pub const fn from_potion(effect: &PotionEffect) -> Self {
+        Self {
+            r#type: effect.effect_type,
+            duration: effect.duration,
+            amplifier: effect.amplifier,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+            blend: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_infinite(&self) -> bool {
+        self.duration == INFINITE_DURATION
+    }
+
+    const fn is_shorter_than(&self, other: &Self) -> bool {
+        !self.is_infinite() && (self.duration < other.duration || other.is_infinite())
+    }
+
+    /// Merges a newly applied effect of the same type into this one, returns whether anything changed.
+    ///
+    /// A higher amplifier replaces the effect, the same amplifier only extends a shorter duration
+    /// and a lower amplifier is ignored.
+    pub fn update(&mut self, other: &Self) -> bool {
+        let mut changed = false;
+        if other.amplifier > self.amplifier {
+            self.amplifier = other.amplifier;
+            self.duration = other.duration;
+            changed = true;
+        } else if other.amplifier == self.amplifier && self.is_shorter_than(other) {
+            self.duration = other.duration;
+            changed = true;
+        }
+
+        if (!other.ambient && self.ambient) || (changed && other.ambient != self.ambient) {
+            self.ambient = other.ambient;
+            changed = true;
+        }
+        if other.show_particles != self.show_particles {
+            self.show_particles = other.show_particles;
+            changed = true;
+        }
+        if other.show_icon != self.show_icon {
+            self.show_icon = other.show_icon;
+            changed = true;
+        }
+        changed
+    }
+
+    /// Whether the effect does something in this tick, like healing or damaging.
+    ///
+    /// `ticks` is the remaining duration, or the age of the entity for infinite effects.
+    #[must_use]
+    pub fn should_apply_tick(&self, ticks: i32) -> bool {
+        let interval: i32 = match self.r#type {
+            EffectType::Regeneration => 50,
+            EffectType::Poison => 25,
+            EffectType::Wither => 40,
+            _ => return false,
+        };
+        let interval = interval.checked_shr(u32::from(self.amplifier)).unwrap_or(0);
+        interval == 0 || ticks % interval == 0
+    }
+
+    /// The flags of the `CUpdateMobEffect` packet
+    #[must_use]
+    pub fn flags(&self) -> i8 {
+        let mut flags = 0;
+        if self.ambient {
+            flags |= 1;
+        }
+        if self.show_particles {
+            flags |= 2;
+        }
+        if self.show_icon {
+            flags |= 4;
+        }
+        if self.blend {
+            flags |= 8;
+        }
+        flags
+    }
+}
+
 #[async_trait]
 impl NBTStorage for Effect {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
@@ -63,3 +158,55 @@ impl NBTStorage for Effect {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pumpkin_data::potion::Potion;
+
+    fn effect(amplifier: u8, duration: i32) -> Effect {
+        Effect {
+            r#type: EffectType::Speed,
+            duration,
+            amplifier,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+            blend: false,
+        }
+    }
+
+    #[test]
+    fn update_follows_amplifier_stacking() {
+        let mut active = effect(1, 100);
+        // A weaker effect does nothing, even if it lasts longer
+        assert!(!active.update(&effect(0, 1000)));
+        assert_eq!(active, effect(1, 100));
+        // The same amplifier extends the duration, but never shortens it
+        assert!(!active.update(&effect(1, 50)));
+        assert!(active.update(&effect(1, 200)));
+        assert_eq!(active.duration, 200);
+        // A stronger effect replaces the duration too
+        assert!(active.update(&effect(2, 20)));
+        assert_eq!(active, effect(2, 20));
+    }
+
+    #[test]
+    fn infinite_effects_are_never_shortened() {
+        let mut active = effect(0, INFINITE_DURATION);
+        assert!(!active.update(&effect(0, 1_000_000)));
+        assert!(active.is_infinite());
+
+        let mut active = effect(0, 100);
+        assert!(active.update(&effect(0, INFINITE_DURATION)));
+        assert!(active.is_infinite());
+    }
+
+    #[test]
+    fn potion_effects_keep_their_strength() {
+        let swiftness = Effect::from_potion(&Potion::STRONG_SWIFTNESS.effects[0]);
+        assert_eq!(swiftness.r#type, EffectType::Speed);
+        assert_eq!(swiftness.amplifier, 1);
+        assert_eq!(swiftness.duration, 1800);
+    }
+}
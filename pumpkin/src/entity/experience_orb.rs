@@ -1,28 +1,107 @@
-use std::sync::{Arc, atomic::AtomicU32};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
 
 use async_trait::async_trait;
-use pumpkin_data::{damage::DamageType, entity::EntityType};
+use pumpkin_data::{
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::java::client::play::CEntityPositionSync;
 use pumpkin_util::math::vector3::Vector3;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::{server::Server, world::World};
 
-use super::{Entity, EntityBase, living::LivingEntity, player::Player};
+use super::{Entity, EntityBase, damage::DamageSource, living::LivingEntity, player::Player};
 
 pub struct ExperienceOrbEntity {
     entity: Entity,
     amount: u32,
     orb_age: AtomicU32,
+    /// The player this orb is flying towards
+    target: Mutex<Option<Arc<Player>>>,
+    /// Set once a player collected the orb, so it can't be collected twice
+    picked_up: AtomicBool,
 }
 
 impl ExperienceOrbEntity {
+    /// Orbs are attracted by players within this distance
+    const FOLLOW_DISTANCE: f64 = 8.0;
+
     pub fn new(entity: Entity, amount: u32) -> Self {
         entity.yaw.store(rand::random::<f32>() * 360.0);
         Self {
             entity,
             amount,
             orb_age: AtomicU32::new(0),
+            target: Mutex::new(None),
+            picked_up: AtomicBool::new(false),
+        }
+    }
+
+    /// Looks for the closest player once a second, like vanilla.
+    async fn update_target(&self, age: u32) {
+        let mut target = self.target.lock().await;
+        let pos = self.entity.pos.load();
+        let out_of_range = target.as_ref().is_none_or(|player| {
+            player.living_entity.health.load() <= 0.0
+                || player
+                    .living_entity
+                    .entity
+                    .pos
+                    .load()
+                    .squared_distance_to_vec(pos)
+                    > Self::FOLLOW_DISTANCE * Self::FOLLOW_DISTANCE
+        });
+        if age % 20 == 1 || out_of_range {
+            let world = self.entity.world.read().await.clone();
+            *target = world
+                .get_closest_player(pos, Self::FOLLOW_DISTANCE)
+                .await
+                .filter(|player| player.living_entity.health.load() > 0.0);
+        }
+    }
+
+    /// Accelerates the orb towards its target, the closer the player the faster it gets.
+    async fn move_towards_target(&self) {
+        let Some(target) = self.target.lock().await.clone() else {
+            return;
+        };
+        let pos = self.entity.pos.load();
+        let target_entity = &target.living_entity.entity;
+        let target_pos = target_entity.pos.load();
+        let delta = Vector3::new(
+            target_pos.x - pos.x,
+            target_pos.y + f64::from(target_entity.standing_eye_height) / 2.0 - pos.y,
+            target_pos.z - pos.z,
+        );
+        let distance_squared = delta.length_squared();
+        if !(1.0E-7..Self::FOLLOW_DISTANCE * Self::FOLLOW_DISTANCE).contains(&distance_squared) {
+            return;
         }
+        let strength = 1.0 - distance_squared.sqrt() / Self::FOLLOW_DISTANCE;
+        let velocity =
+            (self.entity.velocity.load() + delta.normalize() * (strength * strength * 0.1)) * 0.98;
+        self.entity.velocity.store(velocity);
+
+        let new_pos = pos + velocity;
+        self.entity.set_pos(new_pos);
+        self.entity
+            .world
+            .read()
+            .await
+            .broadcast_packet_all(&CEntityPositionSync::new(
+                self.entity.entity_id.into(),
+                new_pos,
+                velocity,
+                self.entity.yaw.load(),
+                self.entity.pitch.load(),
+                self.entity.on_ground.load(Ordering::Relaxed),
+            ))
+            .await;
     }
 
     pub async fn spawn(world: &Arc<World>, position: Vector3<f64>, amount: u32) {
@@ -74,12 +153,14 @@ impl EntityBase for ExperienceOrbEntity {
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         self.entity.tick(caller, server).await;
 
-        let age = self
-            .orb_age
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let age = self.orb_age.fetch_add(1, Ordering::Relaxed);
         if age >= 6000 {
             self.entity.remove().await;
+            return;
         }
+
+        self.update_target(age).await;
+        self.move_towards_target().await;
     }
 
     fn get_entity(&self) -> &Entity {
@@ -89,17 +170,34 @@ impl EntityBase for ExperienceOrbEntity {
     async fn on_player_collision(&self, player: &Arc<Player>) {
         if player.living_entity.health.load() > 0.0 {
             let mut delay = player.experience_pick_up_delay.lock().await;
-            if *delay == 0 {
+            // Another player may have collected this orb in the meantime
+            if *delay == 0 && !self.picked_up.swap(true, Ordering::AcqRel) {
                 *delay = 2;
+                // The client plays the pickup sound when it receives the pickup animation
                 player.living_entity.pickup(&self.entity, 1).await;
+
+                let old_level = player.experience_level.load(Ordering::Relaxed);
                 player.add_experience_points(self.amount as i32).await;
+                let new_level = player.experience_level.load(Ordering::Relaxed);
+                if new_level > old_level && new_level % 5 == 0 {
+                    let world = player.world().await;
+                    world
+                        .play_sound_raw(
+                            Sound::EntityPlayerLevelup as u16,
+                            SoundCategory::Players,
+                            &player.living_entity.entity.pos.load(),
+                            0.75,
+                            1.0,
+                        )
+                        .await;
+                }
                 // TODO: pickingCount for merging
                 self.entity.remove().await;
             }
         }
     }
 
-    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
         false
     }
 
@@ -0,0 +1,207 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    Block, BlockState, block_properties::get_state_by_state_id, entity::EntityType, item::Item,
+};
+use pumpkin_protocol::java::client::play::CEntityPositionSync;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{BlockStateId, item::ItemStack, world::BlockFlags};
+use uuid::Uuid;
+
+use crate::{block::blocks::falling::ConcretePowderBlock, server::Server, world::World};
+
+use super::{Entity, EntityBase, damage::DamageSource, living::LivingEntity};
+
+/// A block affected by gravity, like sand or gravel, while it is falling.
+pub struct FallingBlockEntity {
+    entity: Entity,
+    block_state: BlockStateId,
+    time_falling: AtomicU32,
+}
+
+impl FallingBlockEntity {
+    const GRAVITY: f64 = 0.04;
+    const DRAG: f64 = 0.98;
+    /// Falling blocks that haven't landed after this many ticks drop as an item.
+    const MAX_FALL_TIME: u32 = 600;
+
+    pub fn new(entity: Entity, block_state: BlockStateId) -> Self {
+        // The client reads the falling block's state from the spawn packet data.
+        entity.data.store(i32::from(block_state), Ordering::Relaxed);
+        Self {
+            entity,
+            block_state,
+            time_falling: AtomicU32::new(0),
+        }
+    }
+
+    /// Replaces the block at `position` with a falling block entity of the same state.
+    pub async fn spawn(world: &Arc<World>, position: &BlockPos, block_state: BlockStateId) {
+        let entity = Entity::new(
+            Uuid::new_v4(),
+            world.clone(),
+            position.to_f64(),
+            EntityType::FALLING_BLOCK,
+            false,
+        );
+        let falling_block = Arc::new(Self::new(entity, block_state));
+        world
+            .set_block_state(position, 0, BlockFlags::NOTIFY_ALL)
+            .await;
+        world.spawn_entity(falling_block).await;
+    }
+
+    /// Looks through every block passed this tick for one that stops the fall, so fast falling
+    /// blocks can't skip over a floor. Returns the position the block lands in.
+    async fn find_landing(
+        &self,
+        world: &World,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+    ) -> Option<BlockPos> {
+        let block = Block::from_state_id(self.block_state);
+        let start = BlockPos::floored(from.x, from.y, from.z);
+        let end_y = to.y.floor() as i32;
+        for y in (end_y..start.0.y).rev() {
+            let pos = BlockPos(Vector3::new(start.0.x, y, start.0.z));
+            let state_id = world.get_block_state_id(&pos).await;
+            if Self::stops_in(block, state_id) {
+                return Some(pos);
+            }
+            // Torches, flowers and other blocks without collision don't stop a falling block
+            if get_state_by_state_id(state_id).is_solid() {
+                return Some(pos.up());
+            }
+        }
+        None
+    }
+
+    /// Whether the falling block stops inside a block instead of on top of it, like concrete
+    /// powder at the surface of water.
+    fn stops_in(block: &Block, state_id: BlockStateId) -> bool {
+        state_id == Block::WATER.default_state.id && ConcretePowderBlock::is_concrete_powder(block)
+    }
+
+    /// Whether the falling block can be placed where it landed. Otherwise it breaks and drops as
+    /// an item, e.g. when landing where a torch is.
+    fn can_place_in(state: &BlockState) -> bool {
+        state.is_air() || state.replaceable()
+    }
+
+    async fn land(&self, world: &Arc<World>, position: &BlockPos) {
+        let block = Block::from_state_id(self.block_state);
+        if !Self::can_place_in(world.get_block_state(position).await) {
+            self.drop_item(world, position).await;
+            return;
+        }
+
+        let state_id = if ConcretePowderBlock::is_concrete_powder(block)
+            && ConcretePowderBlock::should_harden(world, position).await
+        {
+            ConcretePowderBlock::hardened_state(block).unwrap_or(self.block_state)
+        } else {
+            self.block_state
+        };
+        world
+            .set_block_state(position, state_id, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+
+    async fn drop_item(&self, world: &Arc<World>, position: &BlockPos) {
+        let block = Block::from_state_id(self.block_state);
+        if let Some(item) = Item::from_id(block.item_id) {
+            world.drop_stack(position, ItemStack::new(1, item)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for FallingBlockEntity {
+    async fn tick(&self, _caller: Arc<dyn EntityBase>, _server: &Server) {
+        let entity = &self.entity;
+        let world = entity.world.read().await.clone();
+        let time_falling = self.time_falling.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let pos = entity.pos.load();
+        let mut velocity = entity.velocity.load();
+        velocity.y -= Self::GRAVITY;
+        let new_pos = pos + velocity;
+
+        if let Some(landing) = self.find_landing(&world, pos, new_pos).await {
+            entity.remove().await;
+            self.land(&world, &landing).await;
+            return;
+        }
+
+        let block_pos = BlockPos::floored(new_pos.x, new_pos.y, new_pos.z);
+        let below_world = block_pos.0.y <= i32::from(world.generation_settings().shape.min_y);
+        if time_falling > Self::MAX_FALL_TIME || (time_falling > 100 && below_world) {
+            entity.remove().await;
+            self.drop_item(&world, &block_pos).await;
+            return;
+        }
+
+        let velocity = velocity * Self::DRAG;
+        entity.velocity.store(velocity);
+        entity.set_pos(new_pos);
+        world
+            .broadcast_packet_all(&CEntityPositionSync::new(
+                entity.entity_id.into(),
+                new_pos,
+                velocity,
+                entity.yaw.load(),
+                entity.pitch.load(),
+                false,
+            ))
+            .await;
+    }
+
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
+        false
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, block_properties::get_state_by_state_id};
+
+    use super::FallingBlockEntity;
+
+    #[test]
+    fn drops_when_landing_in_non_replaceable_blocks() {
+        let can_place_in = |block: &Block| {
+            FallingBlockEntity::can_place_in(get_state_by_state_id(block.default_state.id))
+        };
+        assert!(can_place_in(&Block::AIR));
+        assert!(can_place_in(&Block::SHORT_GRASS));
+        assert!(can_place_in(&Block::WATER));
+        assert!(!can_place_in(&Block::TORCH));
+        assert!(!can_place_in(&Block::STONE));
+    }
+
+    #[test]
+    fn concrete_powder_stops_in_water() {
+        let water = Block::WATER.default_state.id;
+        assert!(FallingBlockEntity::stops_in(
+            &Block::WHITE_CONCRETE_POWDER,
+            water
+        ));
+        assert!(!FallingBlockEntity::stops_in(&Block::SAND, water));
+        assert!(!FallingBlockEntity::stops_in(
+            &Block::WHITE_CONCRETE_POWDER,
+            Block::AIR.default_state.id
+        ));
+    }
+}
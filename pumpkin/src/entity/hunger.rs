@@ -46,7 +46,7 @@ impl HungerManager {
 
         // Heal when hunger is full
         let natural_regen = true; // TODO: Get the actual value when this will be implemented.
-        if natural_regen && saturation > 0.0 && player.can_food_heal() && level >= 20 {
+        if natural_regen && saturation > 0.0 && player.can_food_heal().await && level >= 20 {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 10 {
                 let saturation = saturation.min(6.0);
@@ -54,7 +54,7 @@ impl HungerManager {
                 self.add_exhaustion(saturation);
                 self.tick_timer.store(0);
             }
-        } else if natural_regen && level >= 18 && player.can_food_heal() {
+        } else if natural_regen && level >= 18 && player.can_food_heal().await {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 80 {
                 player.heal(1.0).await;
@@ -82,6 +82,14 @@ impl HungerManager {
             .store((self.exhaustion.load() + exhaustion).min(40.0));
     }
 
+    /// Restores hunger and saturation after eating, with saturation capped at the hunger level.
+    pub fn eat(&self, nutrition: u8, saturation: f32) {
+        let level = self.level.load().saturating_add(nutrition).min(20);
+        self.level.store(level);
+        self.saturation
+            .store((self.saturation.load() + saturation).clamp(0.0, f32::from(level)));
+    }
+
     pub fn restart(&self) {
         self.level.store(20);
         self.saturation.store(5.0);
@@ -4,7 +4,7 @@ use std::sync::{
 };
 
 use async_trait::async_trait;
-use pumpkin_data::damage::DamageType;
+use pumpkin_config::advanced_config;
 use pumpkin_protocol::{
     codec::item_stack_seralizer::ItemStackSerializer,
     java::client::play::{CTakeItemEntity, MetaDataType, Metadata},
@@ -15,7 +15,12 @@ use tokio::sync::Mutex;
 
 use crate::server::Server;
 
-use super::{Entity, EntityBase, living::LivingEntity, player::Player};
+use super::{
+    Entity, EntityBase,
+    damage::DamageSource,
+    living::LivingEntity,
+    player::{Hand, Player},
+};
 
 pub struct ItemEntity {
     entity: Entity,
@@ -27,6 +32,9 @@ pub struct ItemEntity {
 }
 
 impl ItemEntity {
+    /// How far (horizontally) an item looks for other items to merge with
+    const MERGE_RADIUS: f64 = 0.5;
+
     pub async fn new(entity: Entity, item_stack: ItemStack) -> Self {
         entity
             .set_velocity(Vector3::new(
@@ -59,51 +67,107 @@ impl ItemEntity {
             pickup_delay: Mutex::new(pickup_delay), // Vanilla pickup delay is 10 ticks
         }
     }
-}
 
-#[async_trait]
-impl EntityBase for ItemEntity {
-    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
-        let entity = &self.entity;
-        entity.tick(caller, server).await;
-        {
-            let mut delay = self.pickup_delay.lock().await;
-            *delay = delay.saturating_sub(1);
-        };
+    /// Tries to merge nearby item entities of the same item into this one.
+    async fn try_merge(&self) {
+        let world = self.entity.world.read().await.clone();
+        let aabb =
+            self.entity
+                .bounding_box
+                .load()
+                .expand(Self::MERGE_RADIUS, 0.0, Self::MERGE_RADIUS);
 
-        let age = self.item_age.fetch_add(1, Relaxed);
-        if age >= 6000 {
-            entity.remove().await;
+        for other in world.get_entities_at_box(&aabb).await {
+            if other.get_entity().entity_id == self.entity.entity_id {
+                continue;
+            }
+            let Some(other) = other.get_item_entity() else {
+                continue;
+            };
+            if self.merge_with(other).await {
+                return;
+            }
         }
     }
 
-    async fn init_data_tracker(&self) {
-        self.entity
-            .send_meta_data(&[Metadata::new(
-                8,
-                MetaDataType::ItemStack,
-                &ItemStackSerializer::from(*self.item_stack.lock().await),
-            )])
-            .await;
-    }
+    /// Moves items from the smaller of the two stacks into the larger one, respecting the max
+    /// stack size. Returns true when this entity became empty and was removed.
+    async fn merge_with(&self, other: &Self) -> bool {
+        // Both stacks are locked in the order of the entity ids, so two items merging into each
+        // other at the same time can't deadlock
+        let (mut stack, mut other_stack) = if self.entity.entity_id < other.entity.entity_id {
+            let stack = self.item_stack.lock().await;
+            (stack, other.item_stack.lock().await)
+        } else {
+            let other_stack = other.item_stack.lock().await;
+            (self.item_stack.lock().await, other_stack)
+        };
 
-    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
-        false
+        if !stack.are_items_and_components_equal(&other_stack)
+            || stack.is_empty()
+            || other_stack.is_empty()
+            || !stack.is_stackable()
+        {
+            return false;
+        }
+        let max = stack.get_max_stack_size();
+        if stack.item_count >= max || other_stack.item_count >= max {
+            return false;
+        }
+
+        // Vanilla merges the smaller stack into the bigger one
+        let self_is_target = stack.item_count >= other_stack.item_count;
+        let (target, source) = if self_is_target {
+            (&mut *stack, &mut *other_stack)
+        } else {
+            (&mut *other_stack, &mut *stack)
+        };
+        let moved = (max - target.item_count).min(source.item_count);
+        target.increment(moved);
+        source.decrement(moved);
+        let source_empty = source.is_empty();
+        drop(stack);
+        drop(other_stack);
+
+        let (target, source) = if self_is_target {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        // Keep the older despawn timer and longer pickup delay, like vanilla
+        target
+            .item_age
+            .fetch_min(source.item_age.load(Relaxed), Relaxed);
+        let source_delay = *source.pickup_delay.lock().await;
+        {
+            let mut delay = target.pickup_delay.lock().await;
+            *delay = (*delay).max(source_delay);
+        };
+        target.init_data_tracker().await;
+
+        if source_empty {
+            source.entity.remove().await;
+        } else {
+            source.init_data_tracker().await;
+        }
+        source_empty && !self_is_target
     }
 
-    async fn on_player_collision(&self, player: &Arc<Player>) {
+    /// Moves as much of the item as fits into the player's inventory. Returns if anything was
+    /// picked up
+    async fn try_pickup(&self, player: &Arc<Player>) -> bool {
         let can_pickup = {
             let delay = self.pickup_delay.lock().await;
             *delay == 0
         };
 
-        if can_pickup
+        let picked_up = can_pickup
             && player.living_entity.health.load() > 0.0
             && player
                 .inventory
                 .insert_stack_anywhere(&mut *self.item_stack.lock().await)
-                .await
-        {
+                .await;
+        if picked_up {
             player
                 .client
                 .enqueue_packet(&CTakeItemEntity::new(
@@ -128,6 +192,55 @@ impl EntityBase for ItemEntity {
                 self.init_data_tracker().await;
             }
         }
+        picked_up
+    }
+}
+
+#[async_trait]
+impl EntityBase for ItemEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        let entity = &self.entity;
+        entity.tick(caller, server).await;
+        {
+            let mut delay = self.pickup_delay.lock().await;
+            *delay = delay.saturating_sub(1);
+        };
+
+        let config = &advanced_config().entity;
+        let age = self.item_age.fetch_add(1, Relaxed);
+        if age >= config.item_despawn_ticks {
+            entity.remove().await;
+            return;
+        }
+
+        // Vanilla checks for merges every 2 ticks while moving and every 40 ticks while resting
+        let moving = entity.velocity.load().length_squared() > 1.0E-5;
+        let merge_interval = if moving { 2 } else { 40 };
+        if config.merge_items && age.is_multiple_of(merge_interval) {
+            self.try_merge().await;
+        }
+    }
+
+    async fn init_data_tracker(&self) {
+        self.entity
+            .send_meta_data(&[Metadata::new(
+                8,
+                MetaDataType::ItemStack,
+                &ItemStackSerializer::from(self.item_stack.lock().await.clone()),
+            )])
+            .await;
+    }
+
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
+        false
+    }
+
+    async fn on_player_collision(&self, player: &Arc<Player>) {
+        self.try_pickup(player).await;
+    }
+
+    async fn on_interact(&self, player: &Arc<Player>, _hand: Hand) -> bool {
+        self.try_pickup(player).await
     }
 
     fn get_entity(&self) -> &Entity {
@@ -137,4 +250,8 @@ impl EntityBase for ItemEntity {
     fn get_living_entity(&self) -> Option<&LivingEntity> {
         None
     }
+
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        Some(self)
+    }
 }
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::{EffectType, EntityStatus, EntityType};
+use pumpkin_data::item::{ConsumableComponent, ConsumeEffect, Item};
+use pumpkin_data::potion::Potion;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{CCooldown, MetaDataType, Metadata};
+use pumpkin_util::GameMode;
+use pumpkin_util::resource_location::ResourceLocation;
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::effect::Effect;
+use super::player::{Hand, Player};
+use super::projectile::arrow::ArrowEntity;
+use super::{Entity, EntityBase};
+
+/// How long an item without a fixed use duration, like a bow, can be held in use. Vanilla: 72000
+const MAX_USE_TICKS: u32 = 72000;
+/// Bows released with less pull than this do not shoot. Vanilla: 0.1
+const MIN_BOW_PULL: f32 = 0.1;
+/// The speed of an arrow shot from a fully drawn bow. Vanilla: 3.0
+const ARROW_POWER: f32 = 3.0;
+
+/// The metadata index of the living entity flags, which tell other players which hand is in use
+const LIVING_ENTITY_FLAGS: u8 = 8;
+const USING_ITEM_FLAG: i8 = 0x01;
+const OFF_HAND_FLAG: i8 = 0x02;
+
+struct ActiveUse {
+    hand: Hand,
+    /// The stack as it was when the use started, using stops once the hand holds a different one
+    stack: ItemStack,
+    /// For how many ticks the item has been used
+    ticks: u32,
+}
+
+/// Tracks the item a player is using over several ticks, like food being eaten or a bow being drawn.
+#[derive(Default)]
+pub struct ItemUseManager {
+    active: Mutex<Option<ActiveUse>>,
+}
+
+impl ItemUseManager {
+    /// Starts using the item in the given hand if it is used over time. Returns `false` if the
+    /// item is used instantly instead, or can not be used right now.
+    pub async fn start(&self, player: &Player, hand: Hand, item: &'static Item) -> bool {
+        let creative = player.gamemode.load() == GameMode::Creative;
+        let can_use = if let Some(food) = item.components.food {
+            creative
+                || food.can_always_eat.unwrap_or(false)
+                || player.hunger_manager.level.load() < 20
+        } else if item.components.consumable.is_some() {
+            true
+        } else if item == &Item::BOW {
+            creative || find_arrow(player).await.is_some()
+        } else {
+            return false;
+        };
+        if can_use {
+            let stack = player.item_in_hand(hand).await.lock().await.clone();
+            *self.active.lock().await = Some(ActiveUse {
+                hand,
+                stack,
+                ticks: 0,
+            });
+            send_using_flags(player, Some(hand)).await;
+        }
+        true
+    }
+
+    pub async fn tick(&self, player: &Player) {
+        let mut active = self.active.lock().await;
+        let Some(active_use) = active.as_mut() else {
+            return;
+        };
+        // The player switched slots or the stack got moved away or replaced
+        if !player
+            .item_in_hand(active_use.hand)
+            .await
+            .lock()
+            .await
+            .are_items_and_components_equal(&active_use.stack)
+        {
+            *active = None;
+            drop(active);
+            send_using_flags(player, None).await;
+            return;
+        }
+
+        active_use.ticks += 1;
+        let finished = active_use
+            .stack
+            .item
+            .components
+            .consumable
+            .map_or(active_use.ticks >= MAX_USE_TICKS, |consumable| {
+                active_use.ticks >= consume_ticks(&consumable)
+            });
+        if !finished {
+            return;
+        }
+        let Some(active_use) = active.take() else {
+            return;
+        };
+        drop(active);
+        send_using_flags(player, None).await;
+        // Bows held for too long stop being drawn without shooting, they only shoot on release
+        if active_use.stack.item.components.consumable.is_some() {
+            finish_consuming(player, active_use.hand, &active_use.stack).await;
+        }
+    }
+
+    /// Called when the player lets go of the use button. Bows shoot here, while food that was not
+    /// eaten up yet is put away.
+    pub async fn release(&self, player: &Player) {
+        let Some(active_use) = self.active.lock().await.take() else {
+            return;
+        };
+        send_using_flags(player, None).await;
+        if active_use.stack.item == &Item::BOW {
+            shoot_bow(player, active_use.ticks).await;
+        }
+    }
+
+    /// Stops using the current item without finishing it, e.g. because the player took damage.
+    pub async fn cancel(&self, player: &Player) {
+        if self.active.lock().await.take().is_some() {
+            send_using_flags(player, None).await;
+        }
+    }
+}
+
+/// Tracks which items a player can not use yet, like ender pearls right after throwing one.
+#[derive(Default)]
+pub struct ItemCooldownManager {
+    /// The tick each cooldown group runs out at
+    cooldowns: Mutex<HashMap<ResourceLocation, i32>>,
+}
+
+impl ItemCooldownManager {
+    pub async fn is_on_cooldown(&self, player: &Player, item: &Item) -> bool {
+        let now = player.tick_counter.load(Ordering::Relaxed);
+        self.cooldowns
+            .lock()
+            .await
+            .get(&cooldown_group(item))
+            .is_some_and(|end| *end > now)
+    }
+
+    /// Puts the item's cooldown group on cooldown if the item has a use cooldown.
+    pub async fn apply(&self, player: &Player, item: &Item) {
+        let Some(use_cooldown) = item.components.use_cooldown else {
+            return;
+        };
+        let ticks = (use_cooldown.seconds * 20.0) as i32;
+        if ticks <= 0 {
+            return;
+        }
+        let group = cooldown_group(item);
+        let now = player.tick_counter.load(Ordering::Relaxed);
+        let mut cooldowns = self.cooldowns.lock().await;
+        cooldowns.retain(|_, end| *end > now);
+        cooldowns.insert(group.clone(), now + ticks);
+        drop(cooldowns);
+        player
+            .client
+            .enqueue_packet(&CCooldown::new(&group, VarInt(ticks)))
+            .await;
+    }
+}
+
+fn cooldown_group(item: &Item) -> ResourceLocation {
+    let group = item
+        .components
+        .use_cooldown
+        .and_then(|use_cooldown| use_cooldown.cooldown_group)
+        .and_then(|group| group.split_once(':'));
+    match group {
+        Some((namespace, path)) => ResourceLocation {
+            namespace: namespace.to_string(),
+            path: path.to_string(),
+        },
+        None => ResourceLocation::vanilla(item.registry_key),
+    }
+}
+
+fn consume_ticks(consumable: &ConsumableComponent) -> u32 {
+    (consumable.consume_seconds * 20.0) as u32
+}
+
+/// How far a bow is drawn after being used for the given ticks, from `0.0` to `1.0`.
+fn bow_pull_progress(ticks: u32) -> f32 {
+    let seconds = ticks as f32 / 20.0;
+    ((seconds * seconds + seconds * 2.0) / 3.0).min(1.0)
+}
+
+async fn send_using_flags(player: &Player, hand: Option<Hand>) {
+    let flags = match hand {
+        Some(Hand::Left) => USING_ITEM_FLAG,
+        Some(Hand::Right) => USING_ITEM_FLAG | OFF_HAND_FLAG,
+        None => 0,
+    };
+    player
+        .living_entity
+        .entity
+        .send_meta_data(&[Metadata::new(
+            LIVING_ENTITY_FLAGS,
+            MetaDataType::Byte,
+            flags,
+        )])
+        .await;
+}
+
+async fn finish_consuming(player: &Player, hand: Hand, consumed: &ItemStack) {
+    let item = consumed.item;
+    let Some(consumable) = item.components.consumable else {
+        return;
+    };
+    let world = player.world().await;
+    if let Some(food) = item.components.food {
+        player.hunger_manager.eat(food.nutrition, food.saturation);
+        player.send_health().await;
+        world
+            .play_sound(
+                Sound::EntityPlayerBurp,
+                SoundCategory::Players,
+                &player.position(),
+            )
+            .await;
+    }
+    if let Some(potion) = consumed.potion_contents {
+        drink_potion(player, potion).await;
+    }
+    for effect in consumable.on_consume_effects {
+        match effect {
+            ConsumeEffect::ApplyEffects {
+                effects,
+                probability,
+            } => {
+                if rand::random::<f32>() >= *probability {
+                    continue;
+                }
+                for effect in *effects {
+                    let Some(effect_type) = EffectType::from_minecraft_name(effect.id) else {
+                        continue;
+                    };
+                    player
+                        .add_effect(Effect {
+                            r#type: effect_type,
+                            duration: effect.duration,
+                            amplifier: effect.amplifier,
+                            ambient: false,
+                            show_particles: true,
+                            show_icon: true,
+                            blend: false,
+                        })
+                        .await;
+                }
+            }
+            ConsumeEffect::RemoveEffects { effects } => {
+                for effect in *effects {
+                    if let Some(effect_type) = EffectType::from_minecraft_name(effect) {
+                        if player.living_entity.has_effect(effect_type).await {
+                            player.remove_effect(effect_type).await;
+                        }
+                    }
+                }
+            }
+            ConsumeEffect::ClearAllEffects => {
+                player.remove_all_effect().await;
+            }
+            ConsumeEffect::Other => {}
+        }
+    }
+    world
+        .send_entity_status(&player.living_entity.entity, EntityStatus::ConsumeItem)
+        .await;
+    player.item_cooldowns.apply(player, item).await;
+
+    if player.gamemode.load() == GameMode::Creative {
+        return;
+    }
+    let binding = player.item_in_hand(hand).await;
+    let mut stack = binding.lock().await;
+    stack.decrement(1);
+    let Some(remainder) = item.components.use_remainder else {
+        return;
+    };
+    let Some(remainder_item) = Item::from_registry_key(
+        remainder
+            .id
+            .strip_prefix("minecraft:")
+            .unwrap_or(remainder.id),
+    ) else {
+        return;
+    };
+    let remainder = ItemStack::new(remainder.count, remainder_item);
+    if stack.is_empty() {
+        *stack = remainder;
+    } else {
+        drop(stack);
+        player
+            .inventory
+            .offer_or_drop_stack(remainder, player)
+            .await;
+    }
+}
+
+async fn drink_potion(player: &Player, potion: &Potion) {
+    for effect in potion.effects {
+        match effect.effect_type {
+            EffectType::InstantHealth => {
+                player.heal((4 << effect.amplifier) as f32).await;
+            }
+            EffectType::InstantDamage => {
+                player
+                    .damage((6 << effect.amplifier) as f32, DamageType::MAGIC)
+                    .await;
+            }
+            _ => {
+                player.add_effect(Effect::from_potion(effect)).await;
+            }
+        }
+    }
+}
+
+async fn shoot_bow(player: &Player, ticks: u32) {
+    let pull = bow_pull_progress(ticks);
+    if pull < MIN_BOW_PULL {
+        return;
+    }
+    let creative = player.gamemode.load() == GameMode::Creative;
+    let arrow = find_arrow(player).await;
+    if arrow.is_none() && !creative {
+        return;
+    }
+
+    // A single arrow of the stack, keeping components like the potion of a tipped arrow
+    let arrow_stack = match &arrow {
+        Some(arrow) => arrow.lock().await.copy_with_count(1),
+        None => ItemStack::new(1, &Item::ARROW),
+    };
+    let entity_type = if arrow_stack.item == &Item::SPECTRAL_ARROW {
+        EntityType::SPECTRAL_ARROW
+    } else {
+        EntityType::ARROW
+    };
+
+    let position = player.position();
+    let world = player.world().await;
+    let entity = Entity::new(Uuid::new_v4(), world.clone(), position, entity_type, false);
+    let owner = world
+        .get_player_by_uuid(player.gameprofile.id)
+        .await
+        .map(|owner| Arc::downgrade(&(owner as Arc<dyn EntityBase>)));
+    // Arrows shot in creative can't be picked up, as none were used up
+    let pickup_stack = (!creative).then_some(arrow_stack);
+    let projectile = ArrowEntity::new(entity, &player.living_entity.entity, owner, pickup_stack);
+    let yaw = player.living_entity.entity.yaw.load();
+    let pitch = player.living_entity.entity.pitch.load();
+    projectile.set_velocity_from(
+        &player.living_entity.entity,
+        pitch,
+        yaw,
+        0.0,
+        pull * ARROW_POWER,
+        1.0,
+    );
+    world.spawn_entity(Arc::new(projectile)).await;
+    world
+        .play_sound(Sound::EntityArrowShoot, SoundCategory::Players, &position)
+        .await;
+
+    // TODO: Damage the bow
+    if !creative {
+        if let Some(arrow) = arrow {
+            arrow.lock().await.decrement(1);
+        }
+    }
+}
+
+fn is_arrow(item: &Item) -> bool {
+    item == &Item::ARROW || item == &Item::SPECTRAL_ARROW || item == &Item::TIPPED_ARROW
+}
+
+/// Finds the arrows a bow would shoot, preferring the off hand over the rest of the inventory.
+async fn find_arrow(player: &Player) -> Option<Arc<Mutex<ItemStack>>> {
+    let off_hand = player.inventory.off_hand_item().await;
+    if is_arrow(off_hand.lock().await.item) {
+        return Some(off_hand);
+    }
+    for stack in &player.inventory.main_inventory {
+        let item = stack.lock().await;
+        if !item.is_empty() && is_arrow(item.item) {
+            drop(item);
+            return Some(stack.clone());
+        }
+    }
+    None
+}
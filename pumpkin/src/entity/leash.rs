@@ -0,0 +1,415 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use pumpkin_data::{
+    entity::EntityType,
+    item::Item,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::java::client::play::CSetEntityLink;
+use pumpkin_util::{
+    GameMode,
+    math::{position::BlockPos, vector3::Vector3},
+};
+use pumpkin_world::item::ItemStack;
+use uuid::Uuid;
+
+use crate::{
+    entity::{Entity, EntityBase, decoration::leash_knot::LeashKnotEntity, player::Player},
+    world::World,
+};
+
+/// Past this distance the holder starts pulling the leashed entity towards itself
+const ELASTIC_DISTANCE: f64 = 6.0;
+/// Past this distance the lead snaps
+const MAX_DISTANCE: f64 = 10.0;
+/// How far away a player's leashed mobs can be to get tied to a fence
+const FENCE_TIE_DISTANCE: f64 = 7.0;
+
+/// What a leashed entity is tied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeashHolder {
+    /// Held by another entity, usually a player
+    Entity(Uuid),
+    /// Tied to a fence through a leash knot
+    Fence(BlockPos),
+}
+
+impl LeashHolder {
+    /// Finds the entity id and position of the holder in the world.
+    async fn find(self, world: &World) -> Option<(i32, Vector3<f64>)> {
+        match self {
+            Self::Entity(uuid) => {
+                if let Some(player) = world.get_player_by_uuid(uuid).await {
+                    return Some((player.entity_id(), player.position()));
+                }
+                let entities = world.entities.read().await;
+                let entity = entities.get(&uuid)?.get_entity();
+                Some((entity.entity_id, entity.pos.load()))
+            }
+            Self::Fence(pos) => {
+                let knot = world.leashes.lock().await.knot(pos)?;
+                let entities = world.entities.read().await;
+                let knot = entities.get(&knot)?.get_entity();
+                Some((knot.entity_id, knot.pos.load()))
+            }
+        }
+    }
+
+    fn write_nbt(self, nbt: &mut NbtCompound) {
+        match self {
+            Self::Entity(uuid) => {
+                let uuid = uuid.as_u128();
+                let mut holder = NbtCompound::new();
+                holder.put(
+                    "UUID",
+                    NbtTag::IntArray(vec![
+                        (uuid >> 96) as i32,
+                        ((uuid >> 64) & 0xFFFF_FFFF) as i32,
+                        ((uuid >> 32) & 0xFFFF_FFFF) as i32,
+                        (uuid & 0xFFFF_FFFF) as i32,
+                    ]),
+                );
+                nbt.put_component("leash", holder);
+            }
+            Self::Fence(pos) => {
+                nbt.put("leash", NbtTag::IntArray(vec![pos.0.x, pos.0.y, pos.0.z]));
+            }
+        }
+    }
+
+    fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        if let Some(&[x, y, z]) = nbt.get_int_array("leash") {
+            return Some(Self::Fence(BlockPos::new(x, y, z)));
+        }
+        let &[a, b, c, d] = nbt.get_compound("leash")?.get_int_array("UUID")? else {
+            return None;
+        };
+        let uuid = (u128::from(a as u32) << 96)
+            | (u128::from(b as u32) << 64)
+            | (u128::from(c as u32) << 32)
+            | u128::from(d as u32);
+        Some(Self::Entity(Uuid::from_u128(uuid)))
+    }
+}
+
+/// Which entities are tied to which holder and where the leash knots are, so leads never have
+/// to look through all entities of a world.
+#[derive(Default)]
+pub struct LeashIndex {
+    holders: HashMap<Uuid, LeashHolder>,
+    held: HashMap<LeashHolder, HashSet<Uuid>>,
+    knots: HashMap<BlockPos, Uuid>,
+}
+
+impl LeashIndex {
+    /// Records the holder of an entity, `None` unties it.
+    pub fn set_holder(&mut self, entity: Uuid, holder: Option<LeashHolder>) {
+        self.untie(entity);
+        if let Some(holder) = holder {
+            self.holders.insert(entity, holder);
+            self.held.entry(holder).or_default().insert(entity);
+        }
+    }
+
+    fn untie(&mut self, entity: Uuid) {
+        let Some(old) = self.holders.remove(&entity) else {
+            return;
+        };
+        let Some(held) = self.held.get_mut(&old) else {
+            return;
+        };
+        held.remove(&entity);
+        if held.is_empty() {
+            self.held.remove(&old);
+        }
+    }
+
+    /// The entities tied to the holder.
+    pub fn held_by(&self, holder: LeashHolder) -> impl Iterator<Item = Uuid> + '_ {
+        self.held.get(&holder).into_iter().flatten().copied()
+    }
+
+    #[must_use]
+    pub fn holds_any(&self, holder: LeashHolder) -> bool {
+        self.held.contains_key(&holder)
+    }
+
+    /// The leash knot on the fence, if there is one.
+    #[must_use]
+    pub fn knot(&self, fence: BlockPos) -> Option<Uuid> {
+        self.knots.get(&fence).copied()
+    }
+
+    /// Records an entity added to the world, which may be leashed or a knot.
+    pub fn track(&mut self, entity: &Entity) {
+        if entity.entity_type == EntityType::LEASH_KNOT {
+            self.knots
+                .insert(entity.block_pos.load(), entity.entity_uuid);
+        } else if let Some(holder) = entity.leash_holder.load() {
+            self.set_holder(entity.entity_uuid, Some(holder));
+        }
+    }
+
+    /// Forgets an entity which left the world.
+    pub fn remove(&mut self, entity: Uuid) {
+        self.untie(entity);
+        self.knots.retain(|_, knot| *knot != entity);
+    }
+}
+
+impl Entity {
+    /// Ties the entity to a new holder, or unties it with `None`, and shows the lead to all players.
+    pub async fn set_leash_holder(&self, holder: Option<LeashHolder>) {
+        self.leash_holder.store(holder);
+        let world = self.world.read().await.clone();
+        world
+            .leashes
+            .lock()
+            .await
+            .set_holder(self.entity_uuid, holder);
+        let holder_id = match holder {
+            Some(holder) => holder.find(&world).await.map(|(id, _)| id),
+            None => None,
+        };
+        world
+            .broadcast_packet_all(&CSetEntityLink::new(self.entity_id, holder_id))
+            .await;
+    }
+
+    /// Leashed mobs never despawn.
+    #[must_use]
+    pub fn is_leashed(&self) -> bool {
+        self.leash_holder.load().is_some()
+    }
+
+    /// The packet showing the current lead, for players the entity was just spawned for.
+    pub async fn leash_link_packet(&self) -> Option<CSetEntityLink> {
+        let holder = self.leash_holder.load()?;
+        let world = self.world.read().await.clone();
+        let (holder_id, _) = holder.find(&world).await?;
+        Some(CSetEntityLink::new(self.entity_id, Some(holder_id)))
+    }
+
+    /// Unties the entity, dropping the lead as an item if `drop_item` is set.
+    pub async fn drop_leash(&self, drop_item: bool) {
+        if self.leash_holder.load().is_none() {
+            return;
+        }
+        self.set_leash_holder(None).await;
+        if drop_item {
+            let world = self.world.read().await.clone();
+            world
+                .drop_stack(&self.block_pos.load(), ItemStack::new(1, &Item::LEAD))
+                .await;
+        }
+    }
+
+    /// Pulls the entity towards its holder and snaps the lead when they get too far apart or a
+    /// block gets in between.
+    pub async fn tick_leash(&self) {
+        let Some(holder) = self.leash_holder.load() else {
+            return;
+        };
+        let world = self.world.read().await.clone();
+        // The holder left the world, or the knot is gone together with its fence
+        let Some((_, holder_pos)) = holder.find(&world).await else {
+            self.drop_leash(true).await;
+            return;
+        };
+
+        let pos = self.pos.load();
+        let distance = pos.squared_distance_to_vec(holder_pos).sqrt();
+        if distance > MAX_DISTANCE {
+            world
+                .play_sound(Sound::ItemLeadBreak, SoundCategory::Neutral, &pos)
+                .await;
+            self.drop_leash(true).await;
+            return;
+        }
+        if distance <= ELASTIC_DISTANCE {
+            return;
+        }
+
+        if matches!(holder, LeashHolder::Entity(_)) {
+            let eye_pos = pos.add_raw(0.0, f64::from(self.standing_eye_height), 0.0);
+            let obstructed = world
+                .raycast(eye_pos, holder_pos, async |pos, world| {
+                    !world.get_block_state(pos).await.collision_shapes.is_empty()
+                })
+                .await
+                .is_some();
+            if obstructed {
+                world
+                    .play_sound(Sound::ItemLeadBreak, SoundCategory::Neutral, &pos)
+                    .await;
+                self.drop_leash(true).await;
+                return;
+            }
+        }
+
+        // Vanilla: net.minecraft.world.entity.Leashable#legacyElasticRangeLeashBehaviour
+        let direction = holder_pos.sub(&pos) * (1.0 / distance);
+        let pull = |d: f64| (d * d * 0.4).copysign(d);
+        let velocity =
+            self.velocity
+                .load()
+                .add_raw(pull(direction.x), pull(direction.y), pull(direction.z));
+        self.set_velocity(velocity).await;
+    }
+
+    pub(super) fn write_leash_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(holder) = self.leash_holder.load() {
+            holder.write_nbt(nbt);
+        }
+    }
+
+    pub(super) fn read_leash_nbt(&self, nbt: &NbtCompound) {
+        self.leash_holder.store(LeashHolder::read_nbt(nbt));
+    }
+}
+
+/// Leashes the target to the player when they hold a lead, or unleashes it when the player
+/// already holds it.
+///
+/// Returns whether the interaction did anything.
+pub async fn interact_with_lead(
+    player: &Player,
+    target: &dyn EntityBase,
+    item_in_hand: &Arc<tokio::sync::Mutex<ItemStack>>,
+) -> bool {
+    let entity = target.get_entity();
+    if target.get_living_entity().is_none() || entity.entity_type == EntityType::LEASH_KNOT {
+        return false;
+    }
+    let world = player.world().await;
+    let creative = player.gamemode.load() == GameMode::Creative;
+    let player_holder = LeashHolder::Entity(player.gameprofile.id);
+
+    if entity.leash_holder.load() == Some(player_holder) {
+        entity.drop_leash(!creative).await;
+        world
+            .play_sound(
+                Sound::ItemLeadUntied,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+            )
+            .await;
+        return true;
+    }
+
+    let mut stack = item_in_hand.lock().await;
+    if stack.item != &Item::LEAD || entity.leash_holder.load().is_some() {
+        return false;
+    }
+    if !creative {
+        stack.decrement(1);
+    }
+    drop(stack);
+    entity.set_leash_holder(Some(player_holder)).await;
+    world
+        .play_sound(
+            Sound::ItemLeadTied,
+            SoundCategory::Neutral,
+            &entity.pos.load(),
+        )
+        .await;
+    true
+}
+
+/// Moves every mob the player is holding nearby over to the fence.
+///
+/// Returns whether any mob was tied to it.
+pub async fn tie_to_fence(player: &Player, world: &Arc<World>, fence: BlockPos) -> bool {
+    let player_pos = player.position();
+    let held: Vec<_> = world
+        .leashes
+        .lock()
+        .await
+        .held_by(LeashHolder::Entity(player.gameprofile.id))
+        .collect();
+    let entities = world.entities.read().await;
+    let leashed: Vec<_> = held
+        .iter()
+        .filter_map(|uuid| entities.get(uuid))
+        .filter(|entity| {
+            entity
+                .get_entity()
+                .pos
+                .load()
+                .squared_distance_to_vec(player_pos)
+                <= FENCE_TIE_DISTANCE * FENCE_TIE_DISTANCE
+        })
+        .cloned()
+        .collect();
+    drop(entities);
+    if leashed.is_empty() {
+        return false;
+    }
+
+    // Tie them first so the new knot is not removed for having nothing tied to it
+    let mut index = world.leashes.lock().await;
+    for entity in &leashed {
+        let entity = entity.get_entity();
+        entity.leash_holder.store(Some(LeashHolder::Fence(fence)));
+        index.set_holder(entity.entity_uuid, Some(LeashHolder::Fence(fence)));
+    }
+    drop(index);
+    LeashKnotEntity::get_or_spawn(world, fence).await;
+    for entity in leashed {
+        entity
+            .get_entity()
+            .set_leash_holder(Some(LeashHolder::Fence(fence)))
+            .await;
+    }
+    world
+        .play_sound(
+            Sound::ItemLeadTied,
+            SoundCategory::Blocks,
+            &fence.to_centered_f64(),
+        )
+        .await;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leash_holder_nbt_round_trip() {
+        for holder in [
+            LeashHolder::Entity(Uuid::new_v4()),
+            LeashHolder::Fence(BlockPos::new(12, -40, 7)),
+        ] {
+            let mut nbt = NbtCompound::new();
+            holder.write_nbt(&mut nbt);
+            assert_eq!(LeashHolder::read_nbt(&nbt), Some(holder));
+        }
+        assert_eq!(LeashHolder::read_nbt(&NbtCompound::new()), None);
+    }
+
+    #[test]
+    fn leash_index_follows_holders() {
+        let player = LeashHolder::Entity(Uuid::new_v4());
+        let fence = LeashHolder::Fence(BlockPos::new(0, 64, 0));
+        let (cow, sheep) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut index = LeashIndex::default();
+
+        index.set_holder(cow, Some(player));
+        index.set_holder(sheep, Some(player));
+        assert_eq!(index.held_by(player).count(), 2);
+
+        index.set_holder(cow, Some(fence));
+        assert_eq!(index.held_by(player).collect::<Vec<_>>(), [sheep]);
+        assert_eq!(index.held_by(fence).collect::<Vec<_>>(), [cow]);
+
+        index.remove(sheep);
+        index.set_holder(cow, None);
+        assert!(!index.holds_any(player));
+        assert!(!index.holds_any(fence));
+    }
+}
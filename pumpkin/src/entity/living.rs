@@ -1,22 +1,31 @@
-use std::sync::Arc;
+use std::collections::hash_map::Entry;
 use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
+use std::sync::{Arc, Weak};
 use std::{collections::HashMap, sync::atomic::AtomicI32};
 
 use super::EntityBase;
+use super::attributes::{self, AttributeInstance, AttributeMap};
+use super::combat;
+use super::damage::DamageSource;
 use super::{Entity, EntityId, NBTStorage, effect::Effect};
 use crate::block::loot::{LootContextParameters, LootTableExt};
+use crate::item::enchantment;
 use crate::server::Server;
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::advanced_config;
 use pumpkin_data::Block;
-use pumpkin_data::entity::{EffectType, EntityStatus};
-use pumpkin_data::{damage::DamageType, sound::Sound};
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::{EffectType, EntityStatus, EntityType};
+use pumpkin_data::sound::Sound;
+use pumpkin_data::tag::Tagable;
 use pumpkin_inventory::entity_equipment::EntityEquipment;
 use pumpkin_inventory::equipment_slot::EquipmentSlot;
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::java::client::play::{CHurtAnimation, CTakeItemEntity};
+use pumpkin_protocol::java::client::play::{
+    CHurtAnimation, CTakeItemEntity, CUpdateAttributes, CUpdateMobEffect,
+};
 use pumpkin_protocol::{
     codec::item_stack_seralizer::ItemStackSerializer,
     java::client::play::{CDamageEvent, CSetEquipment, MetaDataType, Metadata},
@@ -24,6 +33,12 @@ use pumpkin_protocol::{
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::item::ItemStack;
 use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// How long an attacker is credited for the death of an entity it attacked. Vanilla: 100
+const ATTACKER_CREDIT_TICKS: i32 = 100;
+/// How far entities get knocked away from where damage came from. Vanilla: 0.4
+const KNOCKBACK_STRENGTH: f64 = 0.4;
 
 /// Represents a living entity within the game world.
 ///
@@ -44,27 +59,46 @@ pub struct LivingEntity {
     pub fall_distance: AtomicCell<f32>,
     pub active_effects: Mutex<HashMap<EffectType, Effect>>,
     pub entity_equipment: Arc<Mutex<EntityEquipment>>,
+    /// The attributes like max health and movement speed, including their modifiers.
+    pub attributes: Mutex<AttributeMap>,
+    /// The entity which last attacked this one, credited if it dies soon after
+    pub last_attacker: Mutex<Option<Weak<dyn EntityBase>>>,
+    /// Ticks until the last attacker stops being credited
+    pub last_attacker_ticks: AtomicI32,
+    /// Ticks since the entity was spawned, infinite effects use it instead of their duration
+    pub age: AtomicI32,
 }
 impl LivingEntity {
     pub fn new(entity: Entity) -> Self {
         let pos = entity.pos.load();
+        let attributes = AttributeMap::for_entity_type(&entity.entity_type);
+        let max_health = attributes.get_value(attributes::MAX_HEALTH) as f32;
         Self {
             entity,
             last_pos: AtomicCell::new(pos),
             time_until_regen: AtomicI32::new(0),
             last_damage_taken: AtomicCell::new(0.0),
-            health: AtomicCell::new(20.0),
+            health: AtomicCell::new(max_health),
             fall_distance: AtomicCell::new(0.0),
             death_time: AtomicU8::new(0),
             active_effects: Mutex::new(HashMap::new()),
             entity_equipment: Arc::new(Mutex::new(EntityEquipment::new())),
+            attributes: Mutex::new(attributes),
+            last_attacker: Mutex::new(None),
+            last_attacker_ticks: AtomicI32::new(0),
+            age: AtomicI32::new(0),
         }
     }
 
     pub async fn send_equipment_changes(&self, equipment: &[(EquipmentSlot, ItemStack)]) {
         let equipment: Vec<(i8, ItemStackSerializer)> = equipment
             .iter()
-            .map(|(slot, stack)| (slot.discriminant(), ItemStackSerializer::from(*stack)))
+            .map(|(slot, stack)| {
+                (
+                    slot.discriminant(),
+                    ItemStackSerializer::from(stack.clone()),
+                )
+            })
             .collect();
         self.entity
             .world
@@ -99,7 +133,113 @@ impl LivingEntity {
 
     pub async fn heal(&self, additional_health: f32) {
         assert!(additional_health > 0.0);
-        self.set_health(self.health.load() + additional_health)
+        let max_health = self.get_max_health().await;
+        self.set_health((self.health.load() + additional_health).min(max_health))
+            .await;
+    }
+
+    pub async fn get_attribute_value(&self, name: &str) -> f64 {
+        self.attributes.lock().await.get_value(name)
+    }
+
+    pub async fn get_max_health(&self) -> f32 {
+        self.get_attribute_value(attributes::MAX_HEALTH).await as f32
+    }
+
+    pub async fn get_movement_speed(&self) -> f64 {
+        self.get_attribute_value(attributes::MOVEMENT_SPEED).await
+    }
+
+    pub async fn get_attack_damage(&self) -> f64 {
+        self.get_attribute_value(attributes::ATTACK_DAMAGE).await
+    }
+
+    pub async fn get_knockback_resistance(&self) -> f64 {
+        self.get_attribute_value(attributes::KNOCKBACK_RESISTANCE)
+            .await
+            .clamp(0.0, 1.0)
+    }
+
+    pub async fn get_follow_range(&self) -> f64 {
+        self.get_attribute_value(attributes::FOLLOW_RANGE).await
+    }
+
+    /// The knockback strength which is left after the knockback resistance.
+    pub async fn knockback_strength(&self, strength: f64) -> f64 {
+        strength * (1.0 - self.get_knockback_resistance().await)
+    }
+
+    pub async fn set_attribute_base_value(&self, name: &str, base: f64) {
+        self.attributes.lock().await.set_base_value(name, base);
+        self.on_attribute_changed(name).await;
+    }
+
+    /// Adds a modifier, replacing an existing one with the same id.
+    pub async fn add_attribute_modifier(&self, name: &str, modifier: AttributeInstance) {
+        self.attributes.lock().await.add_modifier(name, modifier);
+        self.on_attribute_changed(name).await;
+    }
+
+    pub async fn remove_attribute_modifier(&self, name: &str, id: Uuid) {
+        self.attributes.lock().await.remove_modifier(name, id);
+        self.on_attribute_changed(name).await;
+    }
+
+    /// Sets the sprinting flag together with the movement speed boost of sprinting.
+    pub async fn set_sprinting(&self, sprinting: bool) {
+        self.entity.set_sprinting(sprinting).await;
+        if sprinting {
+            self.add_attribute_modifier(
+                attributes::MOVEMENT_SPEED,
+                AttributeInstance {
+                    id: attributes::SPRINTING_MODIFIER,
+                    amount: 0.3,
+                    operation: attributes::OPERATION_ADD_MULTIPLIED_TOTAL,
+                },
+            )
+            .await;
+        } else {
+            self.remove_attribute_modifier(
+                attributes::MOVEMENT_SPEED,
+                attributes::SPRINTING_MODIFIER,
+            )
+            .await;
+        }
+    }
+
+    /// Sends all attributes which were set on this entity to the clients.
+    pub async fn send_attributes(&self) {
+        let properties = self.attributes.lock().await.properties();
+        if properties.is_empty() {
+            return;
+        }
+        self.entity
+            .world
+            .read()
+            .await
+            .broadcast_packet_all(&CUpdateAttributes::new(self.entity_id().into(), properties))
+            .await;
+    }
+
+    async fn on_attribute_changed(&self, name: &str) {
+        if name == attributes::MAX_HEALTH {
+            let max_health = self.get_max_health().await;
+            if self.health.load() > max_health {
+                self.set_health(max_health).await;
+            }
+        }
+
+        let Some(property) = self.attributes.lock().await.property(name) else {
+            return;
+        };
+        self.entity
+            .world
+            .read()
+            .await
+            .broadcast_packet_all(&CUpdateAttributes::new(
+                self.entity_id().into(),
+                vec![property],
+            ))
             .await;
     }
 
@@ -115,32 +255,53 @@ impl LivingEntity {
         self.entity.entity_id
     }
 
-    pub async fn damage_with_context(
-        &self,
-        amount: f32,
-        damage_type: DamageType,
-        position: Option<Vector3<f64>>,
-        source: Option<&Entity>,
-        cause: Option<&Entity>,
-    ) -> bool {
+    pub async fn damage_with_context(&self, amount: f32, source: &DamageSource) -> bool {
         // Check invulnerability before applying damage
-        if self.entity.is_invulnerable_to(&damage_type) {
+        if self.entity.is_invulnerable_to(&source.damage_type) {
             return false;
         }
 
-        self.entity
-            .world
-            .read()
-            .await
+        let world = self.entity.world.read().await.clone();
+        world
             .broadcast_packet_all(&CDamageEvent::new(
                 self.entity.entity_id.into(),
-                damage_type.id.into(),
-                source.map(|e| e.entity_id.into()),
-                cause.map(|e| e.entity_id.into()),
-                position,
+                source.damage_type.id.into(),
+                source
+                    .attacker
+                    .as_ref()
+                    .map(|e| e.get_entity().entity_id.into()),
+                source
+                    .direct_entity
+                    .as_ref()
+                    .map(|e| e.get_entity().entity_id.into()),
+                source.position,
             ))
             .await;
 
+        if let Some(attacker) = &source.attacker {
+            *self.last_attacker.lock().await = Some(Arc::downgrade(attacker));
+            self.last_attacker_ticks
+                .store(ATTACKER_CREDIT_TICKS, Relaxed);
+        }
+
+        if !source
+            .damage_type
+            .is_tagged_with("minecraft:no_knockback")
+            .unwrap_or(false)
+        {
+            if let Some(origin) = source.source_position() {
+                let attacked_by_player = source
+                    .attacker
+                    .as_ref()
+                    .is_some_and(|e| e.get_entity().entity_type == EntityType::PLAYER);
+                let strength = self.knockback_strength(KNOCKBACK_STRENGTH).await;
+                if strength > 0.0 && (!attacked_by_player || advanced_config().pvp.knockback) {
+                    combat::knockback_from(&self.entity, &world, origin, strength).await;
+                }
+            }
+        }
+
+        let amount = self.apply_enchantment_protection(amount, source).await;
         let new_health = (self.health.load() - amount).max(0.0);
 
         if new_health == 0.0 {
@@ -152,21 +313,95 @@ impl LivingEntity {
         true
     }
 
-    pub async fn add_effect(&self, effect: Effect) {
-        let mut effects = self.active_effects.lock().await;
-        effects.insert(effect.r#type, effect);
-        // TODO broadcast metadata
+    /// Reduces the damage by the protection enchantments of the worn armor.
+    async fn apply_enchantment_protection(&self, amount: f32, source: &DamageSource) -> f32 {
+        let equipment = self.entity_equipment.lock().await;
+        let mut protection_factor = 0;
+        for slot in [
+            EquipmentSlot::HEAD,
+            EquipmentSlot::CHEST,
+            EquipmentSlot::LEGS,
+            EquipmentSlot::FEET,
+        ] {
+            let armor = equipment.get(&slot);
+            protection_factor +=
+                enchantment::protection_factor(&*armor.lock().await, &source.damage_type);
+        }
+        enchantment::apply_protection(amount, protection_factor)
+    }
+
+    /// The entity credited for killing this one, which attacked it in the last few seconds
+    /// and has not been removed since.
+    pub async fn kill_credit(&self) -> Option<Arc<dyn EntityBase>> {
+        self.last_attacker
+            .lock()
+            .await
+            .as_ref()
+            .and_then(Weak::upgrade)
+    }
+
+    /// Adds the effect, or merges it into the active one of the same type.
+    ///
+    /// Returns whether anything changed, see [`Effect::update`].
+    pub async fn add_effect(&self, effect: Effect) -> bool {
+        let effect = match self.active_effects.lock().await.entry(effect.r#type) {
+            Entry::Occupied(mut active) => {
+                if !active.get_mut().update(&effect) {
+                    return false;
+                }
+                active.get().clone()
+            }
+            Entry::Vacant(vacant) => vacant.insert(effect).clone(),
+        };
+        if let Some((name, modifier)) = attributes::effect_modifier(effect.r#type, effect.amplifier)
+        {
+            self.add_attribute_modifier(name, modifier).await;
+        }
+        self.entity
+            .world
+            .read()
+            .await
+            .broadcast_packet_all(&CUpdateMobEffect::new(
+                self.entity_id().into(),
+                VarInt(effect.r#type as i32),
+                effect.amplifier.into(),
+                effect.duration.into(),
+                effect.flags(),
+            ))
+            .await;
+        true
     }
 
-    pub async fn remove_effect(&self, effect_type: EffectType) {
-        let mut effects = self.active_effects.lock().await;
-        effects.remove(&effect_type);
+    /// Removes the effect, returns whether the entity had it.
+    pub async fn remove_effect(&self, effect_type: EffectType) -> bool {
+        if self
+            .active_effects
+            .lock()
+            .await
+            .remove(&effect_type)
+            .is_none()
+        {
+            return false;
+        }
+        if let Some((name, modifier)) = attributes::effect_modifier(effect_type, 0) {
+            self.remove_attribute_modifier(name, modifier.id).await;
+        }
         self.entity
             .world
             .read()
             .await
             .send_remove_mob_effect(&self.entity, effect_type)
             .await;
+        true
+    }
+
+    /// Removes all effects, returns how many there were.
+    pub async fn remove_all_effects(&self) -> usize {
+        let effects: Vec<_> = self.active_effects.lock().await.keys().copied().collect();
+        for effect_type in &effects {
+            self.remove_effect(*effect_type).await;
+        }
+        effects.len()
     }
 
     pub async fn has_effect(&self, effect: EffectType) -> bool {
@@ -277,6 +512,7 @@ impl LivingEntity {
             )
             .await;
         self.drop_loot().await;
+        self.entity.drop_leash(true).await;
     }
 
     async fn drop_loot(&self) {
@@ -305,23 +541,53 @@ impl LivingEntity {
         Entity::check_block_collision(entity, server).await;
     }
 
-    async fn tick_effects(&self) {
+    async fn tick_effects(&self, caller: &dyn EntityBase) {
+        let age = self.age.fetch_add(1, Relaxed);
+        let mut effects_to_apply = Vec::new();
         let mut effects_to_remove = Vec::new();
 
         {
             let mut effects = self.active_effects.lock().await;
             for effect in effects.values_mut() {
-                if effect.duration == 0 {
-                    effects_to_remove.push(effect.r#type);
+                if effect.is_infinite() {
+                    if effect.should_apply_tick(age) {
+                        effects_to_apply.push(effect.r#type);
+                    }
+                    continue;
+                }
+                if effect.should_apply_tick(effect.duration) {
+                    effects_to_apply.push(effect.r#type);
                 }
                 effect.duration -= 1;
+                if effect.duration <= 0 {
+                    effects_to_remove.push(effect.r#type);
+                }
             }
         }
 
+        for effect_type in effects_to_apply {
+            self.apply_effect_tick(caller, effect_type).await;
+        }
         for effect_type in effects_to_remove {
             self.remove_effect(effect_type).await;
         }
     }
+
+    async fn apply_effect_tick(&self, caller: &dyn EntityBase, effect_type: EffectType) {
+        match effect_type {
+            EffectType::Regeneration if self.health.load() < self.get_max_health().await => {
+                self.heal(1.0).await;
+            }
+            // Poison can't kill
+            EffectType::Poison if self.health.load() > 1.0 => {
+                caller.damage(1.0, DamageType::MAGIC).await;
+            }
+            EffectType::Wither => {
+                caller.damage(1.0, DamageType::WITHER).await;
+            }
+            _ => {}
+        }
+    }
 }
 
 #[async_trait]
@@ -329,10 +595,15 @@ impl EntityBase for LivingEntity {
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         self.entity.tick(caller.clone(), server).await;
         self.tick_move(caller.as_ref(), server).await;
-        self.tick_effects().await;
+        self.tick_effects(caller.as_ref()).await;
         if self.time_until_regen.load(Relaxed) > 0 {
             self.time_until_regen.fetch_sub(1, Relaxed);
         }
+        if self.last_attacker_ticks.load(Relaxed) > 0
+            && self.last_attacker_ticks.fetch_sub(1, Relaxed) == 1
+        {
+            *self.last_attacker.lock().await = None;
+        }
         if self.health.load() <= 0.0 {
             let time = self
                 .death_time
@@ -349,17 +620,14 @@ impl EntityBase for LivingEntity {
             }
         }
     }
-    async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
+    async fn damage_with_source(&self, amount: f32, source: DamageSource) -> bool {
         let world = self.entity.world.read().await;
         if !self.check_damage(amount) {
             return false;
         }
         let config = &advanced_config().pvp;
 
-        if !self
-            .damage_with_context(amount, damage_type, None, None, None)
-            .await
-        {
+        if !self.damage_with_context(amount, &source).await {
             return false;
         }
 
@@ -396,6 +664,7 @@ impl EntityBase for LivingEntity {
                 nbt.put("active_effects", NbtTag::List(effects_list));
             }
         }
+        self.attributes.lock().await.write_nbt(nbt).await;
         //TODO: write equipment
         // todo more...
     }
@@ -423,6 +692,9 @@ impl EntityBase for LivingEntity {
                 }
             }
         }
+        let mut attributes = self.attributes.lock().await;
+        attributes.read_nbt(&mut nbt.clone()).await;
+        attributes.remove_modifier(attributes::MOVEMENT_SPEED, attributes::SPRINTING_MODIFIER);
         // todo more...
     }
 }
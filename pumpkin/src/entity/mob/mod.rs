@@ -1,6 +1,14 @@
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicI32, Ordering::Relaxed},
+};
 
 use async_trait::async_trait;
+use pumpkin_config::advanced_config;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::sound::Sound;
+use pumpkin_nbt::compound::NbtCompound;
+use rand::Rng;
 use tokio::sync::Mutex;
 
 use crate::server::Server;
@@ -17,12 +25,179 @@ pub struct MobEntity {
     pub living_entity: LivingEntity,
     pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
     pub navigator: Mutex<Navigator>,
+    /// Persistent mobs (e.g. named ones) never despawn
+    pub persistence_required: AtomicBool,
+    /// Ticks since a player was last close to this mob
+    pub despawn_counter: AtomicI32,
+    /// Grows every tick, the chance to play the ambient sound is this out of 1000
+    ambient_sound_chance: AtomicI32,
+}
+
+impl MobEntity {
+    /// Past this distance to the closest player mobs despawn immediately
+    const DESPAWN_DISTANCE: f64 = 128.0;
+    /// Past this distance to the closest player mobs have a random chance to despawn
+    const NO_DESPAWN_DISTANCE: f64 = 32.0;
+    /// The fewest ticks between two ambient sounds, vanilla
+    const MIN_AMBIENT_SOUND_DELAY: i32 = 80;
+    /// The hostile mobs which despawn when no player is close, like vanilla's
+    /// `removeWhenFarAway`. Passive mobs and entities like boats or item frames always stay, as do
+    /// bosses, elder guardians, piglin brutes, shulkers and wardens.
+    const DESPAWNING_TYPES: [&EntityType; 33] = [
+        &EntityType::BLAZE,
+        &EntityType::BOGGED,
+        &EntityType::BREEZE,
+        &EntityType::CAVE_SPIDER,
+        &EntityType::CREEPER,
+        &EntityType::DROWNED,
+        &EntityType::ENDERMAN,
+        &EntityType::ENDERMITE,
+        &EntityType::EVOKER,
+        &EntityType::GHAST,
+        &EntityType::GIANT,
+        &EntityType::GUARDIAN,
+        &EntityType::HOGLIN,
+        &EntityType::HUSK,
+        &EntityType::ILLUSIONER,
+        &EntityType::MAGMA_CUBE,
+        &EntityType::PHANTOM,
+        &EntityType::PIGLIN,
+        &EntityType::PILLAGER,
+        &EntityType::RAVAGER,
+        &EntityType::SILVERFISH,
+        &EntityType::SKELETON,
+        &EntityType::SLIME,
+        &EntityType::SPIDER,
+        &EntityType::STRAY,
+        &EntityType::VEX,
+        &EntityType::VINDICATOR,
+        &EntityType::WITCH,
+        &EntityType::WITHER_SKELETON,
+        &EntityType::ZOGLIN,
+        &EntityType::ZOMBIE,
+        &EntityType::ZOMBIE_VILLAGER,
+        &EntityType::ZOMBIFIED_PIGLIN,
+    ];
+
+    pub fn new(living_entity: LivingEntity, goals: Vec<(Arc<dyn Goal>, bool)>) -> Self {
+        Self {
+            living_entity,
+            goals: Mutex::new(goals),
+            navigator: Mutex::new(Navigator::default()),
+            persistence_required: AtomicBool::new(false),
+            despawn_counter: AtomicI32::new(0),
+            ambient_sound_chance: AtomicI32::new(0),
+        }
+    }
+
+    /// Plays the mob's ambient sound now and then, like vanilla at least
+    /// `MIN_AMBIENT_SOUND_DELAY` ticks apart
+    async fn tick_ambient_sound(&self) {
+        let chance = self.ambient_sound_chance.fetch_add(1, Relaxed);
+        if rand::rng().random_range(0..1000) < chance {
+            self.ambient_sound_chance
+                .store(-Self::MIN_AMBIENT_SOUND_DELAY, Relaxed);
+            self.play_ambient_sound().await;
+        }
+    }
+
+    /// Plays the mob's ambient sound, if it has one
+    async fn play_ambient_sound(&self) {
+        let entity = &self.living_entity.entity;
+        let id = entity.entity_type.id;
+        // Creepers don't have an ambient sound
+        let sound = if id == EntityType::ZOMBIE.id {
+            Sound::EntityZombieAmbient
+        } else if id == EntityType::SKELETON.id {
+            Sound::EntitySkeletonAmbient
+        } else {
+            return;
+        };
+        entity.play_sound_with_pitch_variation(sound).await;
+    }
+
+    /// The hostile mobs which never despawn because no player is close, see
+    /// [`Self::DESPAWNING_TYPES`].
+    const PERSISTENT_HOSTILE_TYPES: [&EntityType; 6] = [
+        &EntityType::ELDER_GUARDIAN,
+        &EntityType::ENDER_DRAGON,
+        &EntityType::PIGLIN_BRUTE,
+        &EntityType::SHULKER,
+        &EntityType::WARDEN,
+        &EntityType::WITHER,
+    ];
+
+    /// Whether mobs of the type are monsters, which play their sounds in the hostile category.
+    #[must_use]
+    pub fn is_hostile(entity_type: &EntityType) -> bool {
+        Self::despawns_far_away(entity_type)
+            || Self::PERSISTENT_HOSTILE_TYPES
+                .iter()
+                .any(|hostile| hostile.id == entity_type.id)
+    }
+
+    /// Whether mobs of the type despawn when no player is close.
+    fn despawns_far_away(entity_type: &EntityType) -> bool {
+        Self::DESPAWNING_TYPES
+            .iter()
+            .any(|despawning| despawning.id == entity_type.id)
+    }
+
+    /// Applies the vanilla despawn rules, returns true if the mob was removed
+    async fn check_despawn(&self) -> bool {
+        if !Self::despawns_far_away(&self.living_entity.entity.entity_type) {
+            return false;
+        }
+        if self.persistence_required.load(Relaxed)
+            || self.living_entity.entity.is_leashed()
+            || self.living_entity.entity.custom_name.lock().await.is_some()
+            || !advanced_config().entity.mob_despawning
+        {
+            self.despawn_counter.store(0, Relaxed);
+            return false;
+        }
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        let world = entity.world.read().await.clone();
+        // If no player is in the world we keep the mob, like vanilla
+        let Some(player) = world.get_closest_player(pos, f64::MAX).await else {
+            return false;
+        };
+        let distance_squared = player
+            .living_entity
+            .entity
+            .pos
+            .load()
+            .squared_distance_to_vec(pos);
+
+        if distance_squared > Self::DESPAWN_DISTANCE * Self::DESPAWN_DISTANCE {
+            entity.remove().await;
+            return true;
+        }
+        if distance_squared < Self::NO_DESPAWN_DISTANCE * Self::NO_DESPAWN_DISTANCE {
+            self.despawn_counter.store(0, Relaxed);
+            return false;
+        }
+        let counter = self.despawn_counter.fetch_add(1, Relaxed);
+        if counter > 600 && rand::rng().random_range(0..800) == 0 {
+            entity.remove().await;
+            return true;
+        }
+        false
+    }
 }
 
 #[async_trait]
 impl EntityBase for MobEntity {
     async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
         self.living_entity.tick(caller, server).await;
+        if self.check_despawn().await {
+            return;
+        }
+        self.living_entity.entity.tick_leash().await;
+        if self.living_entity.health.load() > 0.0 {
+            self.tick_ambient_sound().await;
+        }
         let mut goals = self.goals.lock().await;
         for (goal, running) in goals.iter_mut() {
             if *running {
@@ -39,6 +214,22 @@ impl EntityBase for MobEntity {
         navigator.tick(&self.living_entity).await;
     }
 
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        nbt.put_bool(
+            "PersistenceRequired",
+            self.persistence_required.load(Relaxed),
+        );
+    }
+
+    async fn read_nbt(&self, nbt: &NbtCompound) {
+        self.living_entity.read_nbt(nbt).await;
+        self.persistence_required.store(
+            nbt.get_bool("PersistenceRequired").unwrap_or(false),
+            Relaxed,
+        );
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.living_entity.entity
     }
@@ -47,3 +238,33 @@ impl EntityBase for MobEntity {
         Some(&self.living_entity)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::entity::EntityType;
+
+    use super::MobEntity;
+
+    #[test]
+    fn only_hostile_mobs_despawn() {
+        for entity_type in [
+            &EntityType::ZOMBIE,
+            &EntityType::CREEPER,
+            &EntityType::PIGLIN,
+        ] {
+            assert!(MobEntity::despawns_far_away(entity_type));
+        }
+        for entity_type in [
+            &EntityType::VILLAGER,
+            &EntityType::COW,
+            &EntityType::IRON_GOLEM,
+            &EntityType::OAK_BOAT,
+            &EntityType::MINECART,
+            &EntityType::ITEM_FRAME,
+            &EntityType::PIGLIN_BRUTE,
+            &EntityType::WARDEN,
+        ] {
+            assert!(!MobEntity::despawns_far_away(entity_type));
+        }
+    }
+}
@@ -1,13 +1,8 @@
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
-
 use crate::entity::{
     Entity,
-    ai::{
-        goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal},
-        path::Navigator,
-    },
+    ai::goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal},
     living::LivingEntity,
 };
 
@@ -17,13 +12,12 @@ pub struct Zombie;
 
 impl Zombie {
     pub fn make(entity: Entity) -> MobEntity {
-        MobEntity {
-            living_entity: LivingEntity::new(entity),
-            goals: Mutex::new(vec![
+        MobEntity::new(
+            LivingEntity::new(entity),
+            vec![
                 (Arc::new(LookAtEntityGoal::new(8.0)), false),
-                (Arc::new(TargetGoal::new(16.0)), false),
-            ]),
-            navigator: Mutex::new(Navigator::default()),
-        }
+                (Arc::new(TargetGoal::new()), false),
+            ],
+        )
     }
 }
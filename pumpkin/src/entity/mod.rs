@@ -1,20 +1,29 @@
-use crate::{server::Server, world::portal::PortalManager};
+use crate::{
+    server::Server,
+    world::portal::{PortalManager, nether::NetherPortal},
+};
 use async_trait::async_trait;
 use bytes::BufMut;
 use core::f32;
 use crossbeam::atomic::AtomicCell;
+use damage::DamageSource;
+use item::ItemEntity;
+use leash::LeashHolder;
 use living::LivingEntity;
-use player::Player;
+use mob::MobEntity;
+use player::{Hand, Player};
+use pumpkin_config::advanced_config;
 use pumpkin_data::block_properties::Integer0To15;
 use pumpkin_data::{
     block_properties::{Facing, HorizontalFacing},
     damage::DamageType,
     entity::{EntityPose, EntityType},
     sound::{Sound, SoundCategory},
+    tag::Tagable,
 };
 use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
-    codec::var_int::VarInt,
+    codec::{angle::Angle, var_int::VarInt},
     java::client::play::{
         CEntityPositionSync, CEntityVelocity, CHeadRot, CSetEntityMetadata, CSpawnEntity,
         CUpdateEntityRot, MetaDataType, Metadata,
@@ -30,6 +39,7 @@ use pumpkin_util::math::{
     vector3::Vector3,
     wrap_degrees,
 };
+use pumpkin_util::text::TextComponent;
 use serde::Serialize;
 use std::sync::{
     Arc,
@@ -43,11 +53,18 @@ use tokio::sync::{Mutex, RwLock};
 use crate::world::World;
 
 pub mod ai;
+pub mod anti_cheat;
+pub mod armor_stand;
+pub mod attributes;
+pub mod damage;
 pub mod decoration;
 pub mod effect;
 pub mod experience_orb;
+pub mod falling_block;
 pub mod hunger;
 pub mod item;
+pub mod item_use;
+pub mod leash;
 pub mod living;
 pub mod mob;
 pub mod player;
@@ -108,17 +125,42 @@ pub trait EntityBase: Send + Sync {
 
     /// Returns if damage was successful or not
     async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
+        self.damage_with_source(amount, DamageSource::new(damage_type))
+            .await
+    }
+
+    /// Like `damage`, but knowing who or what dealt the damage. Returns if damage was successful or not
+    async fn damage_with_source(&self, amount: f32, source: DamageSource) -> bool {
         if let Some(living) = self.get_living_entity() {
-            living.damage(amount, damage_type).await
+            living.damage_with_source(amount, source).await
         } else {
-            self.get_entity().damage(amount, damage_type).await
+            self.get_entity().damage_with_source(amount, source).await
         }
     }
 
+    /// The name of the entity as shown in chat, e.g. in death messages
+    fn get_display_name(&self) -> TextComponent {
+        TextComponent::translate(
+            format!(
+                "entity.minecraft.{}",
+                self.get_entity().entity_type.resource_name
+            ),
+            [],
+        )
+    }
+
     /// Called when a player collides with a entity
     async fn on_player_collision(&self, _player: &Arc<Player>) {}
+
+    /// Called when a player right-clicks the entity. Returns if the interaction was handled
+    async fn on_interact(&self, _player: &Arc<Player>, _hand: Hand) -> bool {
+        false
+    }
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
+    fn get_item_entity(&self) -> Option<&ItemEntity> {
+        None
+    }
 }
 
 static CURRENT_ID: AtomicI32 = AtomicI32::new(0);
@@ -165,6 +207,12 @@ pub struct Entity {
     pub bounding_box_size: AtomicCell<EntityDimensions>,
     /// Whether this entity is invulnerable to all damage
     pub invulnerable: AtomicBool,
+    /// Whether the entity passes through blocks and other entities, e.g. spectators
+    pub no_clip: AtomicBool,
+    /// What the entity is tied to with a lead
+    pub leash_holder: AtomicCell<Option<LeashHolder>>,
+    /// The name given to the entity, e.g. with a name tag
+    pub custom_name: Mutex<Option<TextComponent>>,
     /// List of damage types this entity is immune to
     pub damage_immunities: Vec<DamageType>,
     pub fire_ticks: AtomicI32,
@@ -224,6 +272,9 @@ impl Entity {
             )),
             bounding_box_size: AtomicCell::new(bounding_box_size),
             invulnerable: AtomicBool::new(invulnerable),
+            no_clip: AtomicBool::new(false),
+            leash_holder: AtomicCell::new(None),
+            custom_name: Mutex::new(None),
             damage_immunities: Vec::new(),
             data: AtomicI32::new(0),
             fire_ticks: AtomicI32::new(-1),
@@ -308,22 +359,22 @@ impl Entity {
 
         // Broadcast the update packet.
         // TODO: Do caching to only send the packet when needed.
-        let yaw = (yaw * 256.0 / 360.0).rem_euclid(256.0);
-        let pitch = (pitch * 256.0 / 360.0).rem_euclid(256.0);
+        let yaw = Angle::from_degrees(yaw);
+        let pitch = Angle::from_degrees(pitch);
         self.world
             .read()
             .await
             .broadcast_packet_all(&CUpdateEntityRot::new(
                 self.entity_id.into(),
-                yaw as u8,
-                pitch as u8,
+                yaw,
+                pitch,
                 self.on_ground.load(Relaxed),
             ))
             .await;
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), yaw as u8))
+            .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), yaw))
             .await;
     }
 
@@ -335,7 +386,7 @@ impl Entity {
         }
     }
 
-    async fn tick_portal(&self, caller: &Arc<dyn EntityBase>) {
+    async fn tick_portal(&self, caller: &Arc<dyn EntityBase>, server: &Server) {
         if self.portal_cooldown.load(Ordering::Relaxed) > 0 {
             self.portal_cooldown.fetch_sub(1, Ordering::Relaxed);
         }
@@ -348,34 +399,34 @@ impl Entity {
                 // reset cooldown
                 self.portal_cooldown
                     .store(self.default_portal_cooldown(), Ordering::Relaxed);
+                let destination = portal_manager.portal_world.clone();
                 let pos = self.pos.load();
-                // TODO: this is bad
-                let scale_factor_new = if portal_manager.portal_world.dimension_type
-                    == VanillaDimensionType::TheNether
-                {
-                    8.0
-                } else {
-                    1.0
-                };
-                // TODO: this is bad
-                let scale_factor_current =
-                    if self.world.read().await.dimension_type == VanillaDimensionType::TheNether {
-                        8.0
-                    } else {
-                        1.0
+                let current_dimension = self.world.read().await.dimension_type;
+                let caller = caller.clone();
+                // Finding or building the destination portal reads a lot of blocks, so it must not hold up the tick
+                server.spawn_task(async move {
+                    let target = match (current_dimension, destination.dimension_type) {
+                        (VanillaDimensionType::Overworld, VanillaDimensionType::TheNether)
+                        | (VanillaDimensionType::TheNether, VanillaDimensionType::Overworld) => {
+                            // One block in the nether is eight in the overworld, but only horizontally
+                            let radius = advanced_config().world.portal_search_radius as i32;
+                            let (scale, radius) =
+                                if destination.dimension_type == VanillaDimensionType::TheNether {
+                                    (1.0 / 8.0, radius / 8)
+                                } else {
+                                    (8.0, radius)
+                                };
+                            let pos = BlockPos::floored(pos.x * scale, pos.y, pos.z * scale);
+                            NetherPortal::find_or_create(&destination, pos, radius)
+                                .await
+                                .to_f64()
+                        }
+                        _ => BlockPos::floored(pos.x, pos.y, pos.z).to_f64(),
                     };
-                let scale_factor = scale_factor_current / scale_factor_new;
-                // TODO
-                let pos = BlockPos::floored(pos.x * scale_factor, pos.y, pos.z * scale_factor);
-                caller
-                    .clone()
-                    .teleport(
-                        Some(pos.0.to_f64()),
-                        None,
-                        None,
-                        portal_manager.portal_world.clone(),
-                    )
-                    .await;
+                    caller.teleport(Some(target), None, None, destination).await;
+                });
+                // Entering a portal again after the cooldown has to start over
+                should_remove = true;
             } else if portal_manager.ticks_in_portal == 0 {
                 should_remove = true;
             }
@@ -646,10 +697,6 @@ impl Entity {
         self.set_flag(Flag::Sprinting, sprinting).await;
     }
 
-    pub fn check_fall_flying(&self) -> bool {
-        !self.on_ground.load(Relaxed)
-    }
-
     pub async fn set_fall_flying(&self, fall_flying: bool) {
         assert!(self.fall_flying.load(Relaxed) != fall_flying);
         self.fall_flying.store(fall_flying, Relaxed);
@@ -677,6 +724,26 @@ impl Entity {
             .await;
     }
 
+    /// Plays sound from this entity's eyes with a slightly randomized pitch, used for mob sounds.
+    /// Like in vanilla, monsters play their sounds in the hostile category.
+    pub async fn play_sound_with_pitch_variation(&self, sound: Sound) {
+        let category = if MobEntity::is_hostile(&self.entity_type) {
+            SoundCategory::Hostile
+        } else {
+            SoundCategory::Neutral
+        };
+        // Like vanilla, between 0.8 and 1.2 and more likely close to 1.0. This already is the
+        // whole variation, so the sound skips the one `World::play_sound_at_entity` adds
+        let pitch = (rand::random::<f32>() - rand::random::<f32>()) * 0.2 + 1.0;
+        let mut position = self.pos.load();
+        position.y += f64::from(self.standing_eye_height);
+        self.world
+            .read()
+            .await
+            .play_sound_raw(sound as u16, category, &position, 1.0, pitch)
+            .await;
+    }
+
     pub async fn send_meta_data<T>(&self, meta: &[Metadata<T>])
     where
         T: Serialize,
@@ -704,7 +771,8 @@ impl Entity {
     }
 
     pub fn is_invulnerable_to(&self, damage_type: &DamageType) -> bool {
-        self.invulnerable.load(Relaxed) || self.damage_immunities.contains(damage_type)
+        (self.invulnerable.load(Relaxed) && !bypasses_invulnerability(damage_type))
+            || self.damage_immunities.contains(damage_type)
     }
 
     fn velocity_multiplier(_pos: Vector3<f64>) -> f32 {
@@ -727,6 +795,9 @@ impl Entity {
     }
 
     pub async fn check_block_collision(entity: &dyn EntityBase, server: &Server) {
+        if entity.get_entity().no_clip.load(Ordering::Relaxed) {
+            return;
+        }
         let aabb = entity.get_entity().bounding_box.load();
         let blockpos = BlockPos::new(
             (aabb.min.x + 0.001).floor() as i32,
@@ -799,15 +870,20 @@ impl Entity {
         _world: Arc<World>,
     ) {
         // TODO: handle world change
+        let position = position.unwrap_or_else(|| self.pos.load());
+        let yaw = yaw.unwrap_or_else(|| self.yaw.load());
+        let pitch = pitch.unwrap_or_else(|| self.pitch.load());
+        self.set_pos(position);
+        self.set_rotation(yaw, pitch);
         self.world
             .read()
             .await
             .broadcast_packet_all(&CEntityPositionSync::new(
                 self.entity_id.into(),
-                position.unwrap_or(Vector3::new(0.0, 0.0, 0.0)),
+                position,
                 Vector3::new(0.0, 0.0, 0.0),
-                yaw.unwrap_or(0.0),
-                pitch.unwrap_or(0.0),
+                yaw,
+                pitch,
                 self.on_ground.load(Ordering::SeqCst),
             ))
             .await;
@@ -816,12 +892,12 @@ impl Entity {
 
 #[async_trait]
 impl EntityBase for Entity {
-    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
         false
     }
 
-    async fn tick(&self, caller: Arc<dyn EntityBase>, _server: &Server) {
-        self.tick_portal(&caller).await;
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        self.tick_portal(&caller, server).await;
         let fire_ticks = self.fire_ticks.load(Ordering::Relaxed);
         if fire_ticks > 0 {
             if self.entity_type.fire_immune {
@@ -905,6 +981,16 @@ impl EntityBase for Entity {
         if self.has_visual_fire.load(Relaxed) {
             nbt.put_bool("HasVisualFire", true);
         }
+        let custom_name = self
+            .custom_name
+            .lock()
+            .await
+            .as_ref()
+            .map(serde_json::to_string);
+        if let Some(Ok(name)) = custom_name {
+            nbt.put_string("CustomName", name);
+        }
+        self.write_leash_nbt(nbt);
 
         // todo more...
     }
@@ -937,10 +1023,25 @@ impl EntityBase for Entity {
             .store(nbt.get_int("PortalCooldown").unwrap_or(0) as u32, Relaxed);
         self.has_visual_fire
             .store(nbt.get_bool("HasVisualFire").unwrap_or(false), Relaxed);
+        if let Some(name) = nbt.get_string("CustomName") {
+            let name =
+                serde_json::from_str(name).unwrap_or_else(|_| TextComponent::text(name.clone()));
+            *self.custom_name.lock().await = Some(name);
+        }
+        self.read_leash_nbt(nbt);
         // todo more...
     }
 }
 
+/// Whether the damage type hurts even invulnerable entities, like creative players. Used by `/kill`
+/// and the void.
+#[must_use]
+pub fn bypasses_invulnerability(damage_type: &DamageType) -> bool {
+    damage_type
+        .is_tagged_with("minecraft:bypasses_invulnerability")
+        .unwrap_or(false)
+}
+
 #[async_trait]
 pub trait NBTStorage: Send + Sync + Sized {
     async fn write_nbt(&self, _nbt: &mut NbtCompound) {}
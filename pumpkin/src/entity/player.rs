@@ -9,16 +9,19 @@ use std::time::{Duration, Instant};
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use log::warn;
-use pumpkin_world::chunk::{ChunkData, ChunkEntityData};
+use pumpkin_world::chunk::ChunkEntityData;
 use pumpkin_world::inventory::Inventory;
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer0To4, RespawnAnchorLikeProperties,
+};
 use pumpkin_data::damage::DamageType;
 use pumpkin_data::entity::{EffectType, EntityPose, EntityStatus, EntityType};
-use pumpkin_data::item::Operation;
+use pumpkin_data::item::{Item, Operation};
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::{Sound, SoundCategory};
 use pumpkin_data::tag::Tagable;
@@ -49,13 +52,14 @@ use pumpkin_protocol::java::client::play::{
 };
 use pumpkin_protocol::java::server::play::SClickSlot;
 use pumpkin_registry::VanillaDimensionType;
-use pumpkin_util::GameMode;
 use pumpkin_util::math::{
     boundingbox::BoundingBox, experience, position::BlockPos, vector2::Vector2, vector3::Vector3,
 };
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_util::text::TextComponent;
+use pumpkin_util::{GameMode, HeightMap};
+use pumpkin_world::BlockStateId;
 use pumpkin_world::biome;
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
 use pumpkin_world::entity::entity_data_flags::{
@@ -63,29 +67,41 @@ use pumpkin_world::entity::entity_data_flags::{
 };
 use pumpkin_world::item::ItemStack;
 use pumpkin_world::level::{SyncChunk, SyncEntityChunk};
+use pumpkin_world::world::BlockFlags;
 
 use crate::block::blocks::bed::BedBlock;
 use crate::command::client_suggestions;
 use crate::command::dispatcher::CommandDispatcher;
 use crate::data::op_data::OPERATOR_CONFIG;
+use crate::item::enchantment;
 use crate::net::PlayerConfig;
 use crate::net::{ClientPlatform, GameProfile};
 use crate::plugin::player::player_change_world::PlayerChangeWorldEvent;
 use crate::plugin::player::player_gamemode_change::PlayerGamemodeChangeEvent;
+use crate::plugin::player::player_kick::{KickCause, PlayerKickEvent};
 use crate::plugin::player::player_teleport::PlayerTeleportEvent;
-use crate::server::Server;
-use crate::world::World;
+use crate::server::{Server, WorldLookup};
+use crate::world::particle::ParticleData;
+use crate::world::{World, chunker};
 use crate::{PERMISSION_MANAGER, block};
 
+use super::anti_cheat::AntiCheat;
+use super::attributes;
 use super::combat::{self, AttackType, player_attack_sound};
+use super::damage::DamageSource;
 use super::effect::Effect;
 use super::hunger::HungerManager;
 use super::item::ItemEntity;
+use super::item_use::{ItemCooldownManager, ItemUseManager};
 use super::living::LivingEntity;
-use super::{Entity, EntityBase, EntityId, NBTStorage};
+use super::{Entity, EntityBase, EntityId, NBTStorage, bypasses_invulnerability};
 
 const MAX_CACHED_SIGNATURES: u8 = 128; // Vanilla: 128
 const MAX_PREVIOUS_MESSAGES: u8 = 20; // Vanilla: 20
+/// How far away players see particles, in blocks
+const PARTICLE_DISTANCE: f64 = 32.0; // Vanilla: 32
+/// How far away players see forced particles, in blocks
+const FORCED_PARTICLE_DISTANCE: f64 = 512.0; // Vanilla: 512
 
 enum BatchState {
     Initial,
@@ -95,6 +111,7 @@ enum BatchState {
 
 pub struct ChunkManager {
     chunks_per_tick: usize,
+    max_chunks_per_tick: usize,
     chunk_queue: VecDeque<(Vector2<i32>, SyncChunk)>,
     entity_chunk_queue: VecDeque<(Vector2<i32>, SyncEntityChunk)>,
     batches_sent_since_ack: BatchState,
@@ -104,18 +121,22 @@ impl ChunkManager {
     pub const NOTCHIAN_BATCHES_WITHOUT_ACK_UNTIL_PAUSE: u8 = 10;
 
     #[must_use]
-    pub fn new(chunks_per_tick: usize) -> Self {
+    pub fn new(chunks_per_tick: usize, max_chunks_per_tick: usize) -> Self {
+        let max_chunks_per_tick = max_chunks_per_tick.max(1);
         Self {
-            chunks_per_tick,
+            chunks_per_tick: chunks_per_tick.clamp(1, max_chunks_per_tick),
+            max_chunks_per_tick,
             chunk_queue: VecDeque::new(),
             entity_chunk_queue: VecDeque::new(),
             batches_sent_since_ack: BatchState::Initial,
         }
     }
 
+    /// The client measures how fast it processes chunks, also while on the loading terrain
+    /// screen, so the rate follows its connection up to the configured maximum.
     pub fn handle_acknowledge(&mut self, chunks_per_tick: f32) {
         self.batches_sent_since_ack = BatchState::Count(0);
-        self.chunks_per_tick = chunks_per_tick.ceil() as usize;
+        self.chunks_per_tick = (chunks_per_tick.ceil() as usize).clamp(1, self.max_chunks_per_tick);
     }
 
     pub fn push_chunk(&mut self, position: Vector2<i32>, chunk: SyncChunk) {
@@ -137,13 +158,19 @@ impl ChunkManager {
         state_available && !self.chunk_queue.is_empty()
     }
 
-    pub fn next_chunk(&mut self) -> Box<[SyncChunk]> {
+    /// Takes the next batch of chunks, closest to `center` first. Chunks arrive in the order they
+    /// finish loading, so the queue is sorted again before every batch.
+    pub fn next_chunk(&mut self, center: Vector2<i32>) -> Box<[(Vector2<i32>, SyncChunk)]> {
+        self.chunk_queue
+            .make_contiguous()
+            .sort_unstable_by_key(|(pos, _)| {
+                let rel_x = pos.x - center.x;
+                let rel_z = pos.y - center.y;
+                rel_x * rel_x + rel_z * rel_z
+            });
         let chunk_size = self.chunk_queue.len().min(self.chunks_per_tick);
-        let chunks: Vec<Arc<RwLock<ChunkData>>> = self
-            .chunk_queue
-            .drain(0..chunk_size)
-            .map(|(_, chunk)| chunk)
-            .collect();
+        let chunks: Vec<(Vector2<i32>, SyncChunk)> =
+            self.chunk_queue.drain(0..chunk_size).collect();
 
         match &mut self.batches_sent_since_ack {
             BatchState::Count(count) => {
@@ -205,8 +232,16 @@ pub struct Player {
     pub respawn_point: AtomicCell<Option<RespawnPoint>>,
     /// The player's sleep status
     pub sleeping_since: AtomicCell<Option<u8>>,
+    /// The hand the player is holding a shield up with
+    pub blocking_hand: AtomicCell<Option<Hand>>,
     /// Manages the player's hunger level.
     pub hunger_manager: HungerManager,
+    /// The item the player is using over time, like food being eaten or a bow being drawn.
+    pub item_use: ItemUseManager,
+    /// The item groups the player can not use yet.
+    pub item_cooldowns: ItemCooldownManager,
+    /// Validates the player's movement and reach.
+    pub anti_cheat: AntiCheat,
     /// The ID of the currently open container (if any).
     pub open_container: AtomicCell<Option<u64>>,
     /// The item currently being held by the player.
@@ -266,6 +301,8 @@ pub struct Player {
     pub screen_handler_sync_id: AtomicU8,
     pub screen_handler_listener: Arc<dyn ScreenHandlerListener>,
     pub screen_handler_sync_handler: Arc<SyncHandler>,
+    /// The server's lookup of which world each player is in, updated when changing worlds.
+    world_lookup: WorldLookup,
 }
 
 impl Player {
@@ -275,6 +312,7 @@ impl Player {
         config: PlayerConfig,
         world: Arc<World>,
         gamemode: GameMode,
+        world_lookup: WorldLookup,
     ) -> Self {
         struct ScreenListener;
 
@@ -314,6 +352,9 @@ impl Player {
             awaiting_teleport: Mutex::new(None),
             // TODO: Load this from previous instance
             hunger_manager: HungerManager::default(),
+            item_use: ItemUseManager::default(),
+            item_cooldowns: ItemCooldownManager::default(),
+            anti_cheat: AntiCheat::default(),
             current_block_destroy_stage: AtomicI32::new(-1),
             open_container: AtomicCell::new(None),
             tick_counter: AtomicI32::new(0),
@@ -330,6 +371,7 @@ impl Player {
             // TODO: Send the CPlayerSpawnPosition packet when the client connects with proper values
             respawn_point: AtomicCell::new(None),
             sleeping_since: AtomicCell::new(None),
+            blocking_hand: AtomicCell::new(None),
             // We want this to be an impossible watched section so that `player_chunker::update_position`
             // will mark chunks as watched for a new join rather than a respawn.
             // (We left shift by one so we can search around that chunk)
@@ -354,8 +396,11 @@ impl Player {
             experience_level: AtomicI32::new(0),
             experience_progress: AtomicCell::new(0.0),
             experience_points: AtomicI32::new(0),
-            // Default to sending 16 chunks per tick.
-            chunk_manager: Mutex::new(ChunkManager::new(16)),
+            // Starts at the configured rate and adapts to what the client acknowledges.
+            chunk_manager: Mutex::new(ChunkManager::new(
+                advanced_config().chunk.initial_chunks_per_tick,
+                advanced_config().chunk.max_chunks_per_tick,
+            )),
             last_sent_xp: AtomicI32::new(-1),
             last_sent_health: AtomicI32::new(-1),
             last_sent_food: AtomicU8::new(0),
@@ -368,6 +413,7 @@ impl Player {
             screen_handler_sync_id: AtomicU8::new(0),
             screen_handler_listener: Arc::new(ScreenListener {}),
             screen_handler_sync_handler: Arc::new(SyncHandler::new()),
+            world_lookup,
         }
     }
 
@@ -408,11 +454,9 @@ impl Player {
 
         let level = &world.level;
 
-        // Decrement the value of watched chunks
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks).await;
-        // Remove chunks with no watchers from the cache
-        level.clean_chunks(&chunks_to_clean).await;
-        level.clean_entity_chunks(&chunks_to_clean).await;
+        // Decrement the value of watched chunks, chunks with no watchers are unloaded by the
+        // world after a delay
+        level.mark_chunks_as_not_watched(&radial_chunks).await;
         // Remove left over entries from all possiblily loaded chunks
         level.clean_memory();
 
@@ -428,7 +472,7 @@ impl Player {
         //self.world().level.list_cached();
     }
 
-    pub async fn attack(&self, victim: Arc<dyn EntityBase>) {
+    pub async fn attack(self: &Arc<Self>, victim: Arc<dyn EntityBase>) {
         let world = self.world().await;
         let victim_entity = victim.get_entity();
         let attacker_entity = &self.living_entity.entity;
@@ -437,12 +481,17 @@ impl Player {
         let inventory = self.inventory();
         let item_stack = inventory.held_item();
 
-        let base_damage = 1.0;
-        let base_attack_speed = 4.0;
+        let base_damage = self.living_entity.get_attack_damage().await;
+        let base_attack_speed = self
+            .living_entity
+            .get_attribute_value(attributes::ATTACK_SPEED)
+            .await;
 
         let mut damage_multiplier = 1.0;
         let mut add_damage = 0.0;
         let mut add_speed = 0.0;
+        let enchantment_damage =
+            f64::from(enchantment::attack_damage_bonus(&*item_stack.lock().await));
 
         // Get the attack damage
         // TODO: this should be cached in memory, we shouldn't just use default here either
@@ -465,12 +514,13 @@ impl Player {
         self.last_attacked_ticks.store(0, Ordering::Relaxed);
 
         // Only reduce attack damage if in cooldown
-        // TODO: Enchantments are reduced in the same way, just without the square.
         if attack_cooldown_progress < 1.0 {
             damage_multiplier = 0.2 + attack_cooldown_progress.powi(2) * 0.8;
         }
         // Modify the added damage based on the multiplier.
         let mut damage = base_damage + add_damage * damage_multiplier;
+        // Enchantments are reduced in the same way, just without the square
+        damage += enchantment_damage * attack_cooldown_progress;
 
         let pos = victim_entity.pos.load();
 
@@ -480,10 +530,8 @@ impl Player {
             damage *= 1.5;
         }
 
-        if !victim
-            .damage(damage as f32, DamageType::PLAYER_ATTACK)
-            .await
-        {
+        let source = DamageSource::entity(DamageType::PLAYER_ATTACK, self.clone());
+        if !victim.damage_with_source(damage as f32, source).await {
             world
                 .play_sound(
                     Sound::EntityPlayerAttackNodamage,
@@ -494,25 +542,36 @@ impl Player {
             return;
         }
 
-        if victim.get_living_entity().is_some() {
-            let mut knockback_strength = 1.0;
+        // Swords are made for fighting, other tools wear out faster
+        let weapon_damage = {
+            let item = item_stack.lock().await.item;
+            if item.is_tagged_with("minecraft:swords").unwrap_or(false)
+                || item.components.tool.is_none()
+            {
+                1
+            } else {
+                2
+            }
+        };
+        self.damage_item(&EquipmentSlot::MAIN_HAND, weapon_damage)
+            .await;
+
+        if let Some(victim_living) = victim.get_living_entity() {
             player_attack_sound(&pos, &world, attack_type).await;
             match attack_type {
-                AttackType::Knockback => knockback_strength += 1.0,
+                // The base knockback is dealt by the damage itself, sprinting adds some more
+                AttackType::Knockback if config.knockback => {
+                    let strength = victim_living.knockback_strength(1.0).await;
+                    if strength > 0.0 {
+                        combat::handle_knockback(attacker_entity, &world, victim_entity, strength)
+                            .await;
+                    }
+                }
                 AttackType::Sweeping => {
                     combat::spawn_sweep_particle(attacker_entity, &world, &pos).await;
                 }
                 _ => {}
             }
-            if config.knockback {
-                combat::handle_knockback(
-                    attacker_entity,
-                    &world,
-                    victim_entity,
-                    knockback_strength,
-                )
-                .await;
-            }
         }
 
         if config.swing {}
@@ -543,33 +602,102 @@ impl Player {
         true
     }
 
-    pub async fn get_respawn_point(&self) -> Option<(Vector3<f64>, f32)> {
+    /// Finds where the player respawns at their bed or respawn anchor, which may be in another
+    /// world than the one they are in. Returns `None` if the block is gone or can't be used.
+    pub async fn get_respawn_point(
+        &self,
+        server: &Server,
+    ) -> Option<(Arc<World>, Vector3<f64>, f32)> {
         let respawn_point = self.respawn_point.load()?;
 
-        let (block, _block_state) = self
-            .world()
+        let respawn = match server
+            .get_world_from_dimension(respawn_point.dimension)
             .await
-            .get_block_and_block_state(&respawn_point.position)
-            .await;
-
-        if respawn_point.dimension == VanillaDimensionType::Overworld
-            && block.is_tagged_with("#minecraft:beds").unwrap()
-        {
-            // TODO: calculate respawn position
-            Some((respawn_point.position.to_f64(), respawn_point.yaw))
-        } else if respawn_point.dimension == VanillaDimensionType::TheNether
-            && block == &Block::RESPAWN_ANCHOR
         {
-            // TODO: calculate respawn position
-            // TODO: check if there is fuel for respawn
-            Some((respawn_point.position.to_f64(), respawn_point.yaw))
-        } else {
+            Some(world) => {
+                let (block, block_state) = world
+                    .get_block_and_block_state(&respawn_point.position)
+                    .await;
+                let position = if respawn_point.dimension == VanillaDimensionType::Overworld
+                    && block.is_tagged_with("#minecraft:beds").unwrap()
+                {
+                    // TODO: calculate respawn position
+                    Some(respawn_point.position.to_f64())
+                } else if respawn_point.dimension == VanillaDimensionType::TheNether
+                    && block == &Block::RESPAWN_ANCHOR
+                {
+                    Self::use_respawn_anchor(&world, &respawn_point.position, block_state.id).await
+                } else {
+                    None
+                };
+                position.map(|position| (world, position, respawn_point.yaw))
+            }
+            None => None,
+        };
+
+        if respawn.is_none() {
             self.client
                 .send_packet_now(&CGameEvent::new(GameEvent::NoRespawnBlockAvailable, 0.0))
                 .await;
+        }
+        respawn
+    }
 
-            None
+    /// Uses up one charge of the respawn anchor and returns where to stand next to it.
+    ///
+    /// Returns `None` if the anchor has no charges left or is surrounded by blocks.
+    async fn use_respawn_anchor(
+        world: &Arc<World>,
+        anchor_pos: &BlockPos,
+        state_id: BlockStateId,
+    ) -> Option<Vector3<f64>> {
+        let mut props =
+            RespawnAnchorLikeProperties::from_state_id(state_id, &Block::RESPAWN_ANCHOR);
+        let charges = props.charges.to_index();
+        if charges == 0 {
+            return None;
+        }
+        let position = Self::find_respawn_anchor_stand_position(world, anchor_pos).await?;
+
+        props.charges = Integer0To4::from_index(charges - 1);
+        world
+            .set_block_state(
+                anchor_pos,
+                props.to_state_id(&Block::RESPAWN_ANCHOR),
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        Some(position)
+    }
+
+    /// Vanilla: net.minecraft.world.level.block.RespawnAnchorBlock#findStandUpPosition
+    async fn find_respawn_anchor_stand_position(
+        world: &World,
+        anchor_pos: &BlockPos,
+    ) -> Option<Vector3<f64>> {
+        const OFFSETS: [(i32, i32); 8] = [
+            (0, -1),
+            (-1, 0),
+            (0, 1),
+            (1, 0),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ];
+        for y in [0, -1, 1] {
+            for (x, z) in OFFSETS {
+                let pos = anchor_pos.offset(Vector3::new(x, y, z));
+                let is_free = |state: &BlockState| !state.is_solid() && !state.is_liquid();
+                if world.get_block_state(&pos.down()).await.is_solid()
+                    && is_free(world.get_block_state(&pos).await)
+                    && is_free(world.get_block_state(&pos.up()).await)
+                {
+                    return Some(pos.to_f64().add_raw(0.5, 0.0, 0.5));
+                }
+            }
         }
+        None
     }
 
     pub async fn sleep(&self, bed_head_pos: BlockPos) {
@@ -632,6 +760,37 @@ impl Player {
         self.sleeping_since.store(None);
     }
 
+    /// Whether the player wears something to glide with, like an elytra.
+    pub async fn can_glide(&self) -> bool {
+        let chest = self
+            .living_entity
+            .entity_equipment
+            .lock()
+            .await
+            .get(&EquipmentSlot::CHEST);
+        chest.lock().await.item.id == Item::ELYTRA.id
+    }
+
+    /// Starts gliding if the player is in the air and can glide, returns whether it started.
+    pub async fn try_start_fall_flying(&self) -> bool {
+        let entity = &self.living_entity.entity;
+        if entity.on_ground.load(Ordering::Relaxed)
+            || entity.fall_flying.load(Ordering::Relaxed)
+            || !self.can_glide().await
+        {
+            return false;
+        }
+        entity.set_fall_flying(true).await;
+        true
+    }
+
+    pub async fn stop_fall_flying(&self) {
+        let entity = &self.living_entity.entity;
+        if entity.fall_flying.load(Ordering::Relaxed) {
+            entity.set_fall_flying(false).await;
+        }
+    }
+
     pub async fn show_title(&self, text: &TextComponent, mode: &TitleMode) {
         match mode {
             TitleMode::Title => self.client.enqueue_packet(&CTitleText::new(text)).await,
@@ -640,6 +799,11 @@ impl Player {
         }
     }
 
+    /// Sends the particle to this player if they are close enough to see it.
+    ///
+    /// Forced particles can be seen from further away and show up even when the player turned
+    /// particles down in their settings. Returns whether the particle was sent.
+    #[expect(clippy::too_many_arguments)]
     pub async fn spawn_particle(
         &self,
         position: Vector3<f64>,
@@ -647,19 +811,30 @@ impl Player {
         max_speed: f32,
         particle_count: i32,
         particle: Particle,
-    ) {
+        data: ParticleData,
+        force: bool,
+    ) -> bool {
+        let max_distance = if force {
+            FORCED_PARTICLE_DISTANCE
+        } else {
+            PARTICLE_DISTANCE
+        };
+        if self.position().squared_distance_to_vec(position) > max_distance * max_distance {
+            return false;
+        }
         self.client
             .enqueue_packet(&CParticle::new(
-                false,
+                force,
                 false,
                 position,
                 offset,
                 max_speed,
                 particle_count,
                 VarInt(particle as i32),
-                &[],
+                &data.to_bytes(),
             ))
             .await;
+        true
     }
 
     pub async fn play_sound(
@@ -699,6 +874,7 @@ impl Player {
             .await;
     }
 
+    #[expect(clippy::too_many_lines)]
     pub async fn tick(self: &Arc<Self>, server: &Server) {
         self.current_screen_handler
             .lock()
@@ -719,6 +895,10 @@ impl Player {
                 ))
                 .await;
         }
+        self.living_entity.entity.no_clip.store(
+            self.gamemode.load() == GameMode::Spectator,
+            Ordering::Relaxed,
+        );
         {
             let mut xp = self.experience_pick_up_delay.lock().await;
             if *xp > 0 {
@@ -730,17 +910,25 @@ impl Player {
             let mut chunk_manager = self.chunk_manager.lock().await;
             chunk_manager
                 .can_send_chunk()
-                .then(|| chunk_manager.next_chunk())
+                .then(|| chunk_manager.next_chunk(self.living_entity.entity.chunk_pos.load()))
         };
 
         if let Some(chunk_of_chunks) = chunk_of_chunks {
             let chunk_count = chunk_of_chunks.len();
+            let world = self.world().await;
             self.client.send_packet_now(&CChunkBatchStart).await;
-            for chunk in chunk_of_chunks {
-                let chunk = chunk.read().await;
+            for (position, chunk) in chunk_of_chunks {
                 // TODO: Can we check if we still need to send the chunk? Like if it's a fast moving
                 // player or something.
-                self.client.send_packet_now(&CChunkData(&chunk)).await;
+                world
+                    .with_chunk_to_send(position, &chunk, async |chunk, block_states| {
+                        let packet = block_states.map_or_else(
+                            || CChunkData::new(chunk),
+                            |block_states| CChunkData::with_block_states(chunk, block_states),
+                        );
+                        self.client.send_packet_now(&packet).await;
+                    })
+                    .await;
             }
             self.client
                 .send_packet_now(&CChunkBatchEnd::new(chunk_count as u16))
@@ -781,8 +969,18 @@ impl Player {
 
         self.last_attacked_ticks.fetch_add(1, Ordering::Relaxed);
 
+        let entity = &self.living_entity.entity;
+        if entity.fall_flying.load(Ordering::Relaxed)
+            && (entity.on_ground.load(Ordering::Relaxed) || !self.can_glide().await)
+        {
+            self.stop_fall_flying().await;
+        }
+
         self.living_entity.tick(self.clone(), server).await;
+        self.tick_world_border().await;
         self.hunger_manager.tick(self.as_ref()).await;
+        self.item_use.tick(self.as_ref()).await;
+        self.anti_cheat.tick(self).await;
 
         // experience handling
         self.tick_experience().await;
@@ -795,7 +993,8 @@ impl Player {
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
             // We never got a response from the last keep alive we sent.
             if self.wait_for_keep_alive.load(Ordering::Relaxed) {
-                self.kick(TextComponent::translate("disconnect.timeout", []))
+                self.client
+                    .kick(TextComponent::translate("disconnect.timeout", []))
                     .await;
                 return;
             }
@@ -882,6 +1081,11 @@ impl Player {
         self.living_entity.entity.pos.load()
     }
 
+    /// Spectators pass through blocks and entities and can't be targeted
+    pub fn is_spectator(&self) -> bool {
+        self.gamemode.load() == GameMode::Spectator
+    }
+
     pub fn eye_position(&self) -> Vector3<f64> {
         let eye_height = if self.living_entity.entity.pose.load() == EntityPose::Crouching {
             1.27
@@ -968,12 +1172,13 @@ impl Player {
 
     /// Sends the world time to only this player.
     pub async fn send_time(&self, world: &World) {
+        let daylight_cycle = world.level_info.read().await.game_rules.do_daylight_cycle;
         let l_world = world.level_time.lock().await;
         self.client
             .enqueue_packet(&CUpdateTime::new(
                 l_world.world_age,
                 l_world.time_of_day,
-                true,
+                daylight_cycle,
             ))
             .await;
     }
@@ -982,7 +1187,6 @@ impl Player {
         let radial_chunks = self.watched_section.load().all_chunks_within();
         let level = &world.level;
         let chunks_to_clean = level.mark_chunks_as_not_watched(&radial_chunks).await;
-        level.clean_chunks(&chunks_to_clean).await;
         for chunk in chunks_to_clean {
             self.client
                 .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
@@ -995,6 +1199,16 @@ impl Player {
         ));
     }
 
+    /// Changes the view distance of the player, the client is told about it and chunks which came
+    /// into or left its range are loaded or unloaded.
+    pub async fn update_chunk_cache_radius(self: &Arc<Self>, new_radius: u8) {
+        let Some(view_distance) = NonZeroU8::new(new_radius) else {
+            return;
+        };
+        self.config.write().await.view_distance = view_distance;
+        chunker::update_view_distance(self).await;
+    }
+
     /// Teleports the player to a different world or dimension with an optional position, yaw, and pitch.
     pub async fn teleport_world(
         self: &Arc<Self>,
@@ -1004,25 +1218,24 @@ impl Player {
         pitch: Option<f32>,
     ) {
         let current_world = self.living_entity.entity.world.read().await.clone();
-        let info = &new_world.level_info.read().await;
+        let (spawn_x, spawn_z, spawn_angle) = {
+            let info = new_world.level_info.read().await;
+            (info.spawn_x, info.spawn_z, info.spawn_angle)
+        };
         let position = if let Some(pos) = position {
             pos
         } else {
             Vector3::new(
-                f64::from(info.spawn_x),
+                f64::from(spawn_x),
                 f64::from(
                     new_world
-                        .get_top_block(Vector2::new(
-                            f64::from(info.spawn_x) as i32,
-                            f64::from(info.spawn_z) as i32,
-                        ))
-                        .await
-                        + 1,
+                        .get_top_y(Vector2::new(spawn_x, spawn_z), HeightMap::MotionBlocking)
+                        .await,
                 ),
-                f64::from(info.spawn_z),
+                f64::from(spawn_z),
             )
         };
-        let yaw = yaw.unwrap_or(info.spawn_angle);
+        let yaw = yaw.unwrap_or(spawn_angle);
         let pitch = pitch.unwrap_or(10.0);
 
         send_cancellable! {{
@@ -1042,12 +1255,14 @@ impl Player {
                 let pitch = event.pitch;
                 let new_world = event.new_world;
 
+                // A plugin may have sent the player back to the world they are in
+                if Arc::ptr_eq(&new_world, &current_world) {
+                    Self::teleport(self, position, yaw, pitch).await;
+                    return;
+                }
+
                 self.set_client_loaded(false);
-                let uuid = self.gameprofile.id;
-                current_world.remove_player(self, false).await;
-                *self.living_entity.entity.world.write().await = new_world.clone();
-                new_world.players.write().await.insert(uuid, self.clone());
-                self.unload_watched_chunks(&current_world).await;
+                self.move_to_world(&new_world).await;
 
                 let last_pos = self.living_entity.last_pos.load();
                 let death_dimension = self.world().await.dimension_type.resource_location();
@@ -1062,8 +1277,11 @@ impl Player {
                         new_world.dimension_type.resource_location(),
                         biome::hash_seed(new_world.level.seed.0), // seed
                         self.gamemode.load() as u8,
-                        self.gamemode.load() as i8,
-                        false,
+                        self
+                            .previous_gamemode
+                            .load()
+                            .map_or(-1, |gamemode| gamemode as i8),
+                        new_world.level.is_debug(),
                         false,
                         Some((death_dimension, death_location)),
                         VarInt(self.get_entity().portal_cooldown.load(Ordering::Relaxed) as i32),
@@ -1081,6 +1299,24 @@ impl Player {
         }}
     }
 
+    /// Moves the player from their world's player list into the one of `new_world`, without
+    /// telling the client. The caller has to send the respawn packet.
+    pub async fn move_to_world(self: &Arc<Self>, new_world: &Arc<World>) {
+        let current_world = self.world().await;
+        if Arc::ptr_eq(&current_world, new_world) {
+            return;
+        }
+        let uuid = self.gameprofile.id;
+        current_world.remove_player(self, false).await;
+        *self.living_entity.entity.world.write().await = new_world.clone();
+        new_world.players.write().await.insert(uuid, self.clone());
+        self.world_lookup
+            .write()
+            .await
+            .insert(uuid, new_world.clone());
+        self.unload_watched_chunks(&current_world).await;
+    }
+
     /// `yaw` and `pitch` are in degrees.
     /// Rarly used, for example when waking up the player from a bed or their first time spawn. Otherwise, the `teleport` method should be used.
     /// The player should respond with the `SConfirmTeleport` packet.
@@ -1172,13 +1408,51 @@ impl Player {
         }) < d * d
     }
 
-    pub async fn kick(&self, reason: TextComponent) {
-        self.client.kick(reason).await;
+    pub fn entity_interaction_range(&self) -> f64 {
+        if self.gamemode.load() == GameMode::Creative {
+            5.0
+        } else {
+            3.0
+        }
+    }
+
+    pub fn can_interact_with_entity(&self, entity: &Entity, additional_range: f64) -> bool {
+        let d = self.entity_interaction_range() + additional_range;
+        let entity_pos = self.living_entity.entity.pos.load();
+        let standing_eye_height = self.living_entity.entity.standing_eye_height;
+        entity.bounding_box.load().squared_magnitude(Vector3 {
+            x: entity_pos.x,
+            y: entity_pos.y + f64::from(standing_eye_height),
+            z: entity_pos.z,
+        }) < d * d
+    }
+
+    /// Kicks the player, after letting plugins change the reason or veto the kick.
+    pub async fn kick(self: &Arc<Self>, cause: KickCause, reason: TextComponent) {
+        // Don't wait on plugins while shutting down
+        if cause == KickCause::Shutdown {
+            self.client.kick(reason).await;
+            return;
+        }
+
+        send_cancellable! {{
+            PlayerKickEvent::new(self.clone(), reason, cause);
+
+            'after: {
+                self.client.kick(event.reason).await;
+            }
+
+            'cancelled: {
+                if !cause.is_cancellable() {
+                    self.client.kick(event.reason).await;
+                }
+            }
+        }}
     }
 
-    pub fn can_food_heal(&self) -> bool {
+    pub async fn can_food_heal(&self) -> bool {
         let health = self.living_entity.health.load();
-        let max_health = 20.0; // TODO
+        let max_health = self.living_entity.get_max_health().await;
         health > 0.0 && health < max_health
     }
 
@@ -1205,6 +1479,32 @@ impl Player {
             .await;
     }
 
+    /// Damages the player when they are outside of the world border and past its buffer,
+    /// pushing them back towards its center.
+    pub async fn tick_world_border(&self) {
+        if self.living_entity.health.load() <= 0.0 {
+            return;
+        }
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        let world = self.world().await;
+        let (damage, center) = {
+            let border = world.worldborder.lock().await;
+            (
+                border.damage_at(pos.x, pos.z),
+                Vector3::new(border.center_x, pos.y, border.center_z),
+            )
+        };
+        let Some(damage) = damage else {
+            return;
+        };
+        if self.damage(damage, DamageType::OUTSIDE_BORDER).await {
+            // Knock the player away from the point mirrored across them, which is towards the center
+            let origin = (pos * 2.0).sub(&center);
+            combat::knockback_from(entity, &world, origin, 0.4).await;
+        }
+    }
+
     pub async fn tick_health(&self) {
         let health = self.living_entity.health.load() as i32;
         let food = self.hunger_manager.level.load();
@@ -1238,26 +1538,36 @@ impl Player {
 
     pub async fn kill(&self) {
         self.living_entity.kill().await;
-        self.handle_killed().await;
+        self.handle_killed(&DamageSource::new(DamageType::GENERIC_KILL))
+            .await;
     }
 
-    async fn handle_killed(&self) {
+    async fn handle_killed(&self, source: &DamageSource) {
         self.set_client_loaded(false);
+        let kill_credit = self.living_entity.kill_credit().await;
+        let message = source.death_message(self, kill_credit.as_deref());
         self.client
-            .send_packet_now(&CCombatDeath::new(
-                self.entity_id().into(),
-                &TextComponent::text("noob"),
-            ))
+            .send_packet_now(&CCombatDeath::new(self.entity_id().into(), &message))
             .await;
+
+        let world = self.world().await;
+        if world.level_info.read().await.game_rules.show_death_messages {
+            world
+                .broadcast_packet_all(&CSystemChatMessage::new(&message, false))
+                .await;
+        }
+        *self.living_entity.last_attacker.lock().await = None;
     }
 
-    pub async fn set_gamemode(self: &Arc<Self>, gamemode: GameMode) {
+    /// Changes the game mode and syncs it with the client and the tab list.
+    ///
+    /// Returns whether the game mode changed, it doesn't when the player already is in it
+    /// or a plugin cancelled the [`PlayerGamemodeChangeEvent`].
+    pub async fn set_gamemode(self: &Arc<Self>, gamemode: GameMode) -> bool {
         // We could send the same gamemode without any problems. But why waste bandwidth?
-        assert_ne!(
-            self.gamemode.load(),
-            gamemode,
-            "Attempt to set the gamemode to the already current gamemode"
-        );
+        if self.gamemode.load() == gamemode {
+            return false;
+        }
         send_cancellable! {{
             PlayerGamemodeChangeEvent {
                 player: self.clone(),
@@ -1268,10 +1578,12 @@ impl Player {
 
             'after: {
                 let gamemode = event.new_gamemode;
-                self.gamemode.store(gamemode);
-                // TODO: Fix this when mojang fixes it
-                // This is intentional to keep the pure vanilla mojang experience
-                // self.previous_gamemode.store(self.previous_gamemode.load());
+                // Remembered so the client can toggle back, e.g. with F3 + N
+                self.previous_gamemode.store(Some(self.gamemode.swap(gamemode)));
+                self.living_entity
+                    .entity
+                    .no_clip
+                    .store(gamemode == GameMode::Spectator, Ordering::Relaxed);
                 {
                     // Use another scope so that we instantly unlock `abilities`.
                     let mut abilities = self.abilities.lock().await;
@@ -1302,8 +1614,12 @@ impl Player {
                         GameEvent::ChangeGameMode,
                         gamemode as i32 as f32,
                     )).await;
+                // Like vanilla, resend the permission level after the game mode changed
+                self.send_permission_lvl_update().await;
+                return true;
             }
         }}
+        false
     }
 
     /// Send the player's skin layers and used hand to all players.
@@ -1326,6 +1642,98 @@ impl Player {
             .await;
     }
 
+    /// Uses up durability of the item in the slot, breaking it once none is left.
+    ///
+    /// Creative players don't use up their items, unbreaking may prevent each point.
+    /// The changed slot is sent to the player with the next inventory sync.
+    pub async fn damage_item(&self, slot: &EquipmentSlot, amount: i32) {
+        if amount <= 0 || self.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let stack = match slot {
+            EquipmentSlot::MainHand(_) => self.inventory.held_item(),
+            _ => self.living_entity.entity_equipment.lock().await.get(slot),
+        };
+        let broke = {
+            let mut stack = stack.lock().await;
+            if !stack.is_damageable() {
+                return;
+            }
+            let amount = (0..amount)
+                .filter(|_| !enchantment::unbreaking_prevents_damage(&stack))
+                .count();
+            stack.damage_item(amount as i32)
+        };
+        if broke {
+            let status = match slot {
+                EquipmentSlot::MainHand(_) => EntityStatus::BreakMainhand,
+                EquipmentSlot::OffHand(_) => EntityStatus::BreakOffhand,
+                EquipmentSlot::Feet(_) => EntityStatus::BreakFeet,
+                EquipmentSlot::Legs(_) => EntityStatus::BreakLegs,
+                EquipmentSlot::Chest(_) => EntityStatus::BreakChest,
+                EquipmentSlot::Head(_) => EntityStatus::BreakHead,
+                EquipmentSlot::Body(_) => EntityStatus::BreakBody,
+                EquipmentSlot::Saddle(_) => EntityStatus::BreakSaddle,
+            };
+            // The client plays the break sound and spawns the item particles
+            self.world()
+                .await
+                .send_entity_status(&self.living_entity.entity, status)
+                .await;
+        }
+    }
+
+    /// Uses up durability of the held tool after mining a block, instantly broken blocks are free.
+    pub async fn damage_tool_for_block(&self, state: &BlockState) {
+        if state.hardness == 0.0 {
+            return;
+        }
+        let damage = {
+            let tool = self.inventory.held_item();
+            let tool = tool.lock().await;
+            tool.item
+                .components
+                .tool
+                .map_or(0, |tool| tool.damage_per_block.unwrap_or(1) as i32)
+        };
+        self.damage_item(&EquipmentSlot::MAIN_HAND, damage).await;
+    }
+
+    /// Uses up durability of the armor after taking `amount` damage.
+    pub async fn damage_armor(&self, amount: f32, damage_type: &DamageType) {
+        if amount <= 0.0
+            || damage_type
+                .is_tagged_with("minecraft:bypasses_armor")
+                .unwrap_or(false)
+        {
+            return;
+        }
+        let damage = ((amount / 4.0) as i32).max(1);
+        for slot in [
+            EquipmentSlot::HEAD,
+            EquipmentSlot::CHEST,
+            EquipmentSlot::LEGS,
+            EquipmentSlot::FEET,
+        ] {
+            let stack = self.living_entity.entity_equipment.lock().await.get(&slot);
+            // Only armor wears down, not e.g. an elytra, a carved pumpkin or a head
+            if Self::damaged_on_hurt(&*stack.lock().await) {
+                self.damage_item(&slot, damage).await;
+            }
+        }
+    }
+
+    /// Whether the worn stack loses durability when its wearer is hurt. Carved pumpkins and heads
+    /// are equippable too, but have no durability.
+    fn damaged_on_hurt(stack: &ItemStack) -> bool {
+        stack.is_damageable()
+            && stack
+                .item
+                .components
+                .equippable
+                .is_some_and(|equippable| equippable.damage_on_hurt)
+    }
+
     pub async fn can_harvest(&self, block: &BlockState, block_name: &str) -> bool {
         !block.tool_required()
             || self
@@ -1337,12 +1745,12 @@ impl Player {
     }
 
     pub async fn get_mining_speed(&self, block_name: &str) -> f32 {
-        let mut speed = self
-            .inventory
-            .held_item()
-            .lock()
-            .await
-            .get_speed(block_name);
+        let mut speed = {
+            let tool = self.inventory.held_item();
+            let tool = tool.lock().await;
+            let speed = tool.get_speed(block_name);
+            speed + enchantment::mining_speed_bonus(&tool, speed)
+        };
         // Haste
         if self.living_entity.has_effect(EffectType::Haste).await
             || self
@@ -1457,19 +1865,36 @@ impl Player {
                 .await;
 
             if let Some(slot_index) = slot_index {
-                screen_handler.set_received_stack(slot_index, *item_stack);
+                screen_handler.set_received_stack(slot_index, item_stack.clone());
             }
         }
     }
 
+    pub async fn item_in_hand(&self, hand: Hand) -> Arc<Mutex<ItemStack>> {
+        if hand == Hand::Left {
+            self.inventory.held_item()
+        } else {
+            self.inventory.off_hand_item().await
+        }
+    }
+
+    /// Whether the player is holding up a shield, which blocks explosions and attacks from the front.
+    pub async fn is_blocking_with_shield(&self) -> bool {
+        let Some(hand) = self.blocking_hand.load() else {
+            return false;
+        };
+        self.item_in_hand(hand).await.lock().await.item == &Item::SHIELD
+    }
+
     pub async fn swap_item(&self) {
+        self.blocking_hand.store(None);
         let (main_hand_item, off_hand_item) = self.inventory.swap_item().await;
         let equipment = &[
             (EquipmentSlot::MAIN_HAND, main_hand_item),
             (EquipmentSlot::OFF_HAND, off_hand_item),
         ];
         self.living_entity.send_equipment_changes(equipment).await;
-        // todo this.player.stopUsingItem();
+        self.item_use.cancel(self).await;
     }
 
     pub async fn send_system_message(&self, text: &TextComponent) {
@@ -1539,9 +1964,9 @@ impl Player {
         self.set_experience(new_level, progress, points).await;
     }
 
-    pub async fn add_effect(&self, effect: Effect) {
-        self.send_effect(effect.clone()).await;
-        self.living_entity.add_effect(effect).await;
+    /// Adds the effect, or merges it into the active one, returns whether anything changed.
+    pub async fn add_effect(&self, effect: Effect) -> bool {
+        self.living_entity.add_effect(effect).await
     }
 
     pub async fn send_active_effects(&self) {
@@ -1552,21 +1977,6 @@ impl Player {
     }
 
     pub async fn send_effect(&self, effect: Effect) {
-        let mut flag: i8 = 0;
-
-        if effect.ambient {
-            flag |= 1;
-        }
-        if effect.show_particles {
-            flag |= 2;
-        }
-        if effect.show_icon {
-            flag |= 4;
-        }
-        if effect.blend {
-            flag |= 8;
-        }
-
         let effect_id = VarInt(effect.r#type as i32);
         self.client
             .enqueue_packet(&CUpdateMobEffect::new(
@@ -1574,48 +1984,19 @@ impl Player {
                 effect_id,
                 effect.amplifier.into(),
                 effect.duration.into(),
-                flag,
+                effect.flags(),
             ))
             .await;
     }
 
-    pub async fn remove_effect(&self, effect_type: EffectType) {
-        let effect_id = VarInt(effect_type as i32);
-        self.client
-            .enqueue_packet(
-                &pumpkin_protocol::java::client::play::CRemoveMobEffect::new(
-                    self.entity_id().into(),
-                    effect_id,
-                ),
-            )
-            .await;
-        self.living_entity.remove_effect(effect_type).await;
-
-        // TODO broadcast metadata
+    /// Removes the effect, returns whether the player had it.
+    pub async fn remove_effect(&self, effect_type: EffectType) -> bool {
+        self.living_entity.remove_effect(effect_type).await
     }
 
-    pub async fn remove_all_effect(&self) -> u8 {
-        let mut count = 0;
-        let mut effect_list = vec![];
-        for effect in self.living_entity.active_effects.lock().await.keys() {
-            effect_list.push(*effect);
-            let effect_id = VarInt(*effect as i32);
-            self.client
-                .enqueue_packet(
-                    &pumpkin_protocol::java::client::play::CRemoveMobEffect::new(
-                        self.entity_id().into(),
-                        effect_id,
-                    ),
-                )
-                .await;
-            count += 1;
-        }
-        //Need to remove effect after because the player effect are lock in the for before
-        for effect in effect_list {
-            self.living_entity.remove_effect(effect).await;
-        }
-
-        count
+    /// Removes all effects, returns how many there were.
+    pub async fn remove_all_effect(&self) -> usize {
+        self.living_entity.remove_all_effects().await
     }
 
     /// Add experience levels to the player.
@@ -1859,6 +2240,19 @@ impl NBTStorage for Player {
         // Store food level, saturation, exhaustion, and tick timer
         self.hunger_manager.write_nbt(nbt).await;
 
+        // A bed in the overworld or a respawn anchor in the nether
+        if let Some(respawn_point) = self.respawn_point.load() {
+            nbt.put_int("SpawnX", respawn_point.position.0.x);
+            nbt.put_int("SpawnY", respawn_point.position.0.y);
+            nbt.put_int("SpawnZ", respawn_point.position.0.z);
+            nbt.put_float("SpawnAngle", respawn_point.yaw);
+            nbt.put_bool("SpawnForced", respawn_point.force);
+            nbt.put_string(
+                "SpawnDimension",
+                respawn_point.dimension.resource_location().to_string(),
+            );
+        }
+
         nbt.put_string(
             "Dimension",
             self.world()
@@ -1892,6 +2286,23 @@ impl NBTStorage for Player {
         // Load food level, saturation, exhaustion, and tick timer
         self.hunger_manager.read_nbt(nbt).await;
 
+        if let (Some(x), Some(y), Some(z)) = (
+            nbt.get_int("SpawnX"),
+            nbt.get_int("SpawnY"),
+            nbt.get_int("SpawnZ"),
+        ) {
+            let dimension = nbt
+                .get_string("SpawnDimension")
+                .and_then(|name| VanillaDimensionType::from_resource_location_string(name))
+                .unwrap_or(VanillaDimensionType::Overworld);
+            self.respawn_point.store(Some(RespawnPoint {
+                dimension,
+                position: BlockPos::new(x, y, z),
+                yaw: nbt.get_float("SpawnAngle").unwrap_or(0.0),
+                force: nbt.get_bool("SpawnForced").unwrap_or(false),
+            }));
+        }
+
         // Load from total XP
         let total_exp = nbt.get_int("XpTotal").unwrap_or(0);
         let (level, points) = experience::total_to_level_and_points(total_exp);
@@ -1957,8 +2368,10 @@ impl NBTStorage for PlayerInventory {
 
 #[async_trait]
 impl EntityBase for Player {
-    async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
-        if self.abilities.lock().await.invulnerable {
+    async fn damage_with_source(&self, amount: f32, source: DamageSource) -> bool {
+        if self.abilities.lock().await.invulnerable
+            && !bypasses_invulnerability(&source.damage_type)
+        {
             return false;
         }
         self.world()
@@ -1969,16 +2382,25 @@ impl EntityBase for Player {
                 &self.living_entity.entity.pos.load(),
             )
             .await;
-        let result = self.living_entity.damage(amount, damage_type).await;
+        let result = self
+            .living_entity
+            .damage_with_source(amount, source.clone())
+            .await;
         if result {
+            self.item_use.cancel(self).await;
+            self.damage_armor(amount, &source.damage_type).await;
             let health = self.living_entity.health.load();
             if health <= 0.0 {
-                self.handle_killed().await;
+                self.handle_killed(&source).await;
             }
         }
         result
     }
 
+    fn get_display_name(&self) -> TextComponent {
+        TextComponent::text(self.gameprofile.name.clone())
+    }
+
     async fn teleport(
         self: Arc<Self>,
         position: Option<Vector3<f64>>,
@@ -1986,6 +2408,20 @@ impl EntityBase for Player {
         pitch: Option<f32>,
         world: Arc<World>,
     ) {
+        // Staying in the same world does not need a respawn
+        if let Some(position) = position {
+            if Arc::ptr_eq(&world, &self.world().await) {
+                let (current_yaw, current_pitch) = self.rotation();
+                Self::teleport(
+                    &self,
+                    position,
+                    yaw.unwrap_or(current_yaw),
+                    pitch.unwrap_or(current_pitch),
+                )
+                .await;
+                return;
+            }
+        }
         self.teleport_world(world, position, yaw, pitch).await;
     }
 
@@ -2232,13 +2668,24 @@ impl LastSeen {
     }
 }
 
+/// A message in the last seen window that the client may acknowledge
+struct TrackedMessage {
+    signature: Box<[u8]>,
+    /// Whether the client has not acknowledged this message yet
+    pending: bool,
+}
+
 pub struct MessageCache {
     /// max 128 cached message signatures. Most recent FIRST.
     /// Server should (when possible) reference indexes in this (recipient's) cache instead of sending full signatures in last seen.
     /// Must be 1:1 with client's signature cache.
     full_cache: VecDeque<Box<[u8]>>,
-    /// max 20 last seen messages by the sender. Most Recent LAST
+    /// max 20 last seen messages acknowledged by the sender's latest chat message. Most Recent LAST
     pub last_seen: LastSeen,
+    /// The window the client acknowledges from, followed by messages it has not seen yet.
+    /// Mirrors vanilla's `LastSeenMessagesValidator`.
+    tracked: VecDeque<Option<TrackedMessage>>,
+    last_pending: Option<Box<[u8]>>,
 }
 
 impl Default for MessageCache {
@@ -2246,35 +2693,96 @@ impl Default for MessageCache {
         Self {
             full_cache: VecDeque::with_capacity(MAX_CACHED_SIGNATURES as usize),
             last_seen: LastSeen::default(),
+            tracked: std::iter::repeat_with(|| None)
+                .take(MAX_PREVIOUS_MESSAGES as usize)
+                .collect(),
+            last_pending: None,
         }
     }
 }
 
 impl MessageCache {
-    /// Not used for caching seen messages. Only for non-indexed signatures from senders.
-    pub fn cache_signatures(&mut self, signatures: &[Box<[u8]>]) {
-        for sig in signatures.iter().rev() {
-            if self.full_cache.contains(sig) {
-                continue;
-            }
-            // If the cache is maxed, and someone sends a signature older than the oldest in cache, ignore it
-            if self.full_cache.len() < MAX_CACHED_SIGNATURES as usize {
-                self.full_cache.push_back(sig.clone()); // Recipient never saw this message so it must be older than the oldest in cache
-            }
+    /// Adds a received message the same way the client does, so both caches stay 1:1.
+    ///
+    /// The message's signature becomes the most recent entry, followed by the sender's
+    /// `last_seen` signatures (newest first) and then the remaining older entries.
+    pub fn add_message(&mut self, sender_last_seen: &[Box<[u8]>], signature: &[u8]) {
+        let added: Vec<Box<[u8]>> = std::iter::once(signature.into())
+            .chain(sender_last_seen.iter().rev().cloned())
+            .collect();
+        self.full_cache.retain(|sig| !added.contains(sig));
+        for sig in added.into_iter().rev() {
+            self.full_cache.push_front(sig);
         }
+        self.full_cache.truncate(MAX_CACHED_SIGNATURES as usize);
+
+        if self.last_pending.as_deref() != Some(signature) {
+            self.tracked.push_back(Some(TrackedMessage {
+                signature: signature.into(),
+                pending: true,
+            }));
+            self.last_pending = Some(signature.into());
+        }
+    }
+
+    /// The number of messages the client has not moved its last seen window past yet
+    #[must_use]
+    pub fn tracked_messages(&self) -> usize {
+        self.tracked.len()
+    }
+
+    /// Moves the last seen window forward by `offset` messages.
+    ///
+    /// Returns false if the client advanced past messages it was never sent.
+    #[must_use]
+    pub fn apply_offset(&mut self, offset: i32) -> bool {
+        let max_offset = self.tracked.len() - MAX_PREVIOUS_MESSAGES as usize;
+        let Ok(offset) = usize::try_from(offset) else {
+            return false;
+        };
+        if offset > max_offset {
+            return false;
+        }
+        self.tracked.drain(..offset);
+        true
     }
 
-    /// Adds a seen signature to `last_seen` and `full_cache`.
-    pub fn add_seen_signature(&mut self, signature: &[u8]) {
-        if self.last_seen.0.len() >= MAX_PREVIOUS_MESSAGES as usize {
-            self.last_seen.0.remove(0);
+    /// Applies the last seen update of a chat message and returns the acknowledged signatures, oldest first.
+    ///
+    /// Returns None if the update doesn't match the tracked window, e.g. it acknowledges an unknown message
+    /// or drops one it acknowledged before.
+    pub fn apply_update(&mut self, offset: i32, acknowledged: &[u8]) -> Option<LastSeen> {
+        if !self.apply_offset(offset) {
+            return None;
+        }
+        let is_acknowledged = |bit: usize| {
+            acknowledged
+                .get(bit / 8)
+                .is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+        };
+        if (MAX_PREVIOUS_MESSAGES as usize..acknowledged.len() * 8).any(is_acknowledged) {
+            return None;
         }
-        self.last_seen.0.push(signature.into());
-        // This probably doesn't need to be a loop, but better safe than sorry
-        while self.full_cache.len() >= MAX_CACHED_SIGNATURES as usize {
-            self.full_cache.pop_back();
+
+        let mut last_seen = Vec::new();
+        for (bit, entry) in self
+            .tracked
+            .iter_mut()
+            .take(MAX_PREVIOUS_MESSAGES as usize)
+            .enumerate()
+        {
+            if is_acknowledged(bit) {
+                let message = entry.as_mut()?;
+                message.pending = false;
+                last_seen.push(message.signature.clone());
+            } else {
+                if entry.as_ref().is_some_and(|message| !message.pending) {
+                    return None;
+                }
+                *entry = None;
+            }
         }
-        self.full_cache.push_front(signature.into()); // Since recipient saw this message it will be most recent in cache
+        Some(LastSeen(last_seen))
     }
 }
 
@@ -2316,3 +2824,133 @@ impl InventoryPlayer for Player {
         self.client.enqueue_packet(packet).await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::item::Item;
+    use pumpkin_registry::VanillaDimensionType;
+    use pumpkin_util::GameMode;
+    use pumpkin_world::{
+        dimension::Dimension, item::ItemStack, level::Level, world_info::LevelData,
+    };
+    use tokio::net::{TcpListener, TcpStream};
+    use uuid::Uuid;
+
+    use super::{MessageCache, Player};
+    use crate::{
+        block::default_registry,
+        net::{ClientPlatform, GameProfile, PlayerConfig, java::JavaClientPlatform},
+        server::WorldLookup,
+        world::World,
+    };
+
+    fn signature(id: u8) -> Box<[u8]> {
+        vec![id; 256].into_boxed_slice()
+    }
+
+    #[test]
+    fn last_seen_window_follows_acknowledgements() {
+        let mut cache = MessageCache::default();
+        cache.add_message(&[], &signature(1));
+        cache.add_message(&[], &signature(2));
+
+        // The client moved its window by both messages, so they are its newest entries
+        let last_seen = cache.apply_update(2, &[0, 0, 0b1100]).unwrap();
+        assert_eq!(last_seen.as_ref(), &[signature(1), signature(2)]);
+
+        // Messages can't be dropped once acknowledged
+        assert!(cache.apply_update(0, &[0, 0, 0b0100]).is_none());
+    }
+
+    #[test]
+    fn last_seen_rejects_unknown_messages() {
+        let mut cache = MessageCache::default();
+        cache.add_message(&[], &signature(1));
+
+        // The window can't move past messages that were never sent
+        assert!(!cache.apply_offset(2));
+        // Nothing was acknowledged at this position yet
+        assert!(cache.apply_update(0, &[0b1, 0, 0]).is_none());
+    }
+
+    #[tokio::test]
+    async fn moving_between_dimensions_updates_the_world_lookup() {
+        let folder = tempfile::tempdir().unwrap();
+        let block_registry = default_registry();
+        let load_world = |name: &str, dimension, dimension_type| {
+            let level = Level::from_root_folder(
+                folder.path().join(name),
+                block_registry.clone(),
+                0,
+                dimension,
+            );
+            Arc::new(World::load(
+                level,
+                LevelData::default(),
+                dimension_type,
+                block_registry.clone(),
+            ))
+        };
+        let overworld = load_world(
+            "world",
+            Dimension::Overworld,
+            VanillaDimensionType::Overworld,
+        );
+        let nether = load_world("DIM-1", Dimension::Nether, VanillaDimensionType::TheNether);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let stream = TcpStream::connect(address).await.unwrap();
+        let client = ClientPlatform::Java(Arc::new(JavaClientPlatform::new(stream, address, 0)));
+        let profile = GameProfile {
+            id: Uuid::new_v4(),
+            name: "Traveller".to_string(),
+            properties: vec![],
+            profile_actions: None,
+        };
+        let uuid = profile.id;
+        let world_lookup = WorldLookup::default();
+        let player = Arc::new(
+            Player::new(
+                client,
+                profile,
+                PlayerConfig::default(),
+                overworld.clone(),
+                GameMode::Survival,
+                world_lookup.clone(),
+            )
+            .await,
+        );
+        overworld.players.write().await.insert(uuid, player.clone());
+        world_lookup.write().await.insert(uuid, overworld.clone());
+
+        player.move_to_world(&nether).await;
+
+        // `Server::world_for_player` reads the same lookup
+        assert!(Arc::ptr_eq(&world_lookup.read().await[&uuid], &nether));
+        assert!(Arc::ptr_eq(&player.world().await, &nether));
+        assert!(!overworld.players.read().await.contains_key(&uuid));
+        assert!(nether.players.read().await.contains_key(&uuid));
+    }
+
+    #[test]
+    fn only_armor_is_damaged_on_hurt() {
+        for item in [
+            &Item::DIAMOND_HELMET,
+            &Item::IRON_CHESTPLATE,
+            &Item::TURTLE_HELMET,
+        ] {
+            assert!(Player::damaged_on_hurt(&ItemStack::new(1, item)));
+        }
+        for item in [
+            &Item::ELYTRA,
+            &Item::CARVED_PUMPKIN,
+            &Item::PLAYER_HEAD,
+            &Item::DIAMOND_SWORD,
+        ] {
+            assert!(!Player::damaged_on_hurt(&ItemStack::new(1, item)));
+        }
+    }
+}
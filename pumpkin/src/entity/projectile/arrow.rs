@@ -0,0 +1,275 @@
+use std::sync::{
+    Arc, Weak,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::java::client::play::{CEntityPositionSync, CTakeItemEntity};
+use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+use pumpkin_world::item::ItemStack;
+
+use crate::{entity::player::Player, server::Server, world::World};
+
+use super::{
+    super::{Entity, EntityBase, damage::DamageSource, living::LivingEntity},
+    ThrownItemEntity,
+};
+
+/// An arrow shot from a bow. It flies until it hits an entity, which it damages, or a block,
+/// which it sticks in until it gets picked up or despawns.
+pub struct ArrowEntity {
+    projectile: ThrownItemEntity,
+    owner: Option<Weak<dyn EntityBase>>,
+    /// The arrow given back when it is picked up, with the components of the shot arrow like the
+    /// potion of a tipped arrow. `None` if it can't be picked up
+    pickup_stack: Option<ItemStack>,
+    in_ground: AtomicBool,
+    /// Ticks spent stuck in the ground
+    life: AtomicU32,
+    /// Ticks spent flying, the owner can't be hit right after shooting
+    flight_ticks: AtomicU32,
+}
+
+impl ArrowEntity {
+    const GRAVITY: f64 = 0.05;
+    const DRAG: f64 = 0.99;
+    /// The damage of an arrow at a speed of one block per tick. Vanilla: 2.0
+    const BASE_DAMAGE: f64 = 2.0;
+    /// Arrows stuck in the ground despawn after this many ticks. Vanilla: 1200
+    const DESPAWN_TICKS: u32 = 1200;
+    /// For how many ticks the arrow ignores the entity which shot it
+    const OWNER_IMMUNITY_TICKS: u32 = 5;
+
+    pub fn new(
+        entity: Entity,
+        owner: &Entity,
+        owner_entity: Option<Weak<dyn EntityBase>>,
+        pickup_stack: Option<ItemStack>,
+    ) -> Self {
+        Self {
+            projectile: ThrownItemEntity::new(entity, owner),
+            owner: owner_entity,
+            pickup_stack,
+            in_ground: AtomicBool::new(false),
+            life: AtomicU32::new(0),
+            flight_ticks: AtomicU32::new(0),
+        }
+    }
+
+    /// See [`ThrownItemEntity::set_velocity_from`].
+    pub fn set_velocity_from(
+        &self,
+        shooter: &Entity,
+        pitch: f32,
+        yaw: f32,
+        roll: f32,
+        speed: f32,
+        divergence: f32,
+    ) {
+        self.projectile
+            .set_velocity_from(shooter, pitch, yaw, roll, speed, divergence);
+    }
+
+    fn owner(&self) -> Option<Arc<dyn EntityBase>> {
+        self.owner.as_ref().and_then(Weak::upgrade)
+    }
+
+    /// Finds the closest living entity in the arrow's path this tick, closer than the given
+    /// squared distance.
+    async fn find_target(
+        &self,
+        world: &World,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        max_distance: f64,
+    ) -> Option<Arc<dyn EntityBase>> {
+        let entity = self.get_entity();
+        let search = BoundingBox::new(
+            Vector3::new(from.x.min(to.x), from.y.min(to.y), from.z.min(to.z)),
+            Vector3::new(from.x.max(to.x), from.y.max(to.y), from.z.max(to.z)),
+        )
+        .expand(1.0, 1.0, 1.0);
+        let owner_id = self.owner().map(|owner| owner.get_entity().entity_id);
+        let ignore_owner = self.flight_ticks.load(Ordering::Relaxed) < Self::OWNER_IMMUNITY_TICKS;
+
+        let mut candidates = world.get_entities_at_box(&search).await;
+        for player in world.get_players_at_box(&search).await {
+            candidates.push(player);
+        }
+        candidates
+            .into_iter()
+            .filter(|target| {
+                let target_entity = target.get_entity();
+                target_entity.entity_id != entity.entity_id
+                    && target.get_living_entity().is_some()
+                    && !(ignore_owner && Some(target_entity.entity_id) == owner_id)
+            })
+            .filter_map(|target| {
+                let bounding_box = target
+                    .get_entity()
+                    .bounding_box
+                    .load()
+                    .expand(0.3, 0.3, 0.3);
+                World::intersects_aabb_with_direction(
+                    from,
+                    to,
+                    bounding_box.min,
+                    bounding_box.max,
+                )?;
+                let distance = bounding_box.squared_magnitude(from);
+                (distance < max_distance).then_some((distance, target))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, target)| target)
+    }
+
+    /// The damage dealt by an arrow hitting an entity at the given speed in blocks per tick.
+    fn impact_damage(speed: f64) -> f32 {
+        (speed * Self::BASE_DAMAGE)
+            .ceil()
+            .clamp(0.0, f64::from(i32::MAX)) as f32
+    }
+
+    async fn hit_entity(&self, caller: Arc<dyn EntityBase>, target: &Arc<dyn EntityBase>) {
+        let entity = self.get_entity();
+        let damage = Self::impact_damage(entity.velocity.load().length());
+        let source = DamageSource::indirect(DamageType::ARROW, caller, self.owner());
+        target.damage_with_source(damage, source).await;
+        let world = entity.world.read().await.clone();
+        world
+            .play_sound(
+                Sound::EntityArrowHit,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+            )
+            .await;
+        entity.remove().await;
+    }
+
+    async fn stick_in_ground(&self, world: &World, position: Vector3<f64>) {
+        let entity = self.get_entity();
+        self.in_ground.store(true, Ordering::Relaxed);
+        entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        entity.set_pos(position);
+        Self::sync_position(world, entity).await;
+        world
+            .play_sound(Sound::EntityArrowHit, SoundCategory::Neutral, &position)
+            .await;
+    }
+
+    async fn sync_position(world: &World, entity: &Entity) {
+        world
+            .broadcast_packet_all(&CEntityPositionSync::new(
+                entity.entity_id.into(),
+                entity.pos.load(),
+                entity.velocity.load(),
+                entity.yaw.load(),
+                entity.pitch.load(),
+                false,
+            ))
+            .await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for ArrowEntity {
+    async fn tick(&self, caller: Arc<dyn EntityBase>, server: &Server) {
+        let entity = self.get_entity();
+        entity.tick(caller.clone(), server).await;
+        if self.in_ground.load(Ordering::Relaxed) {
+            if self.life.fetch_add(1, Ordering::Relaxed) + 1 >= Self::DESPAWN_TICKS {
+                entity.remove().await;
+            }
+            return;
+        }
+        self.flight_ticks.fetch_add(1, Ordering::Relaxed);
+
+        let world = entity.world.read().await.clone();
+        let from = entity.pos.load();
+        let velocity = entity.velocity.load();
+        let to = from + velocity;
+
+        let block_hit = world
+            .raycast(from, to, async |pos, world| {
+                !world.get_block_state(pos).await.collision_shapes.is_empty()
+            })
+            .await;
+        // Entities behind the block that was hit are out of reach
+        let max_distance = block_hit.map_or(f64::MAX, |(pos, _)| {
+            BoundingBox::from_block(&pos).squared_magnitude(from)
+        });
+        if let Some(target) = self.find_target(&world, from, to, max_distance).await {
+            self.hit_entity(caller, &target).await;
+            return;
+        }
+        if block_hit.is_some() {
+            // The arrow stays where it was before flying into the block
+            self.stick_in_ground(&world, from).await;
+            return;
+        }
+
+        let mut velocity = velocity * Self::DRAG;
+        velocity.y -= Self::GRAVITY;
+        entity.velocity.store(velocity);
+        entity.set_pos(to);
+        Self::sync_position(&world, entity).await;
+    }
+
+    async fn on_player_collision(&self, player: &Arc<Player>) {
+        if !self.in_ground.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(mut stack) = self.pickup_stack.clone() else {
+            return;
+        };
+        if !player.inventory.insert_stack_anywhere(&mut stack).await {
+            return;
+        }
+        let entity = self.get_entity();
+        player
+            .client
+            .enqueue_packet(&CTakeItemEntity::new(
+                entity.entity_id.into(),
+                player.entity_id().into(),
+                VarInt(1),
+            ))
+            .await;
+        entity.remove().await;
+    }
+
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
+        false
+    }
+
+    fn get_entity(&self) -> &Entity {
+        self.projectile.get_entity()
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrowEntity;
+
+    #[test]
+    fn impact_damage_scales_with_speed() {
+        let cases = [
+            // A fully drawn bow shoots at 3 blocks per tick
+            (3.0, 6.0),
+            // Partial damage rounds up
+            (1.2, 3.0),
+            (0.0, 0.0),
+        ];
+        for (speed, damage) in cases {
+            assert!((ArrowEntity::impact_damage(speed) - damage).abs() < 0.001);
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use std::f32::{self};
 
 use async_trait::async_trait;
-use pumpkin_data::damage::DamageType;
 use pumpkin_util::math::vector3::Vector3;
 
-use super::{Entity, EntityBase, living::LivingEntity};
+use super::{Entity, EntityBase, damage::DamageSource, living::LivingEntity};
+
+pub mod arrow;
 
 pub struct ThrownItemEntity {
     entity: Entity,
@@ -81,7 +82,7 @@ impl EntityBase for ThrownItemEntity {
         &self.entity
     }
 
-    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
         false
     }
 
@@ -1,6 +1,6 @@
 use crate::server::Server;
 use async_trait::async_trait;
-use pumpkin_data::{Block, damage::DamageType};
+use pumpkin_data::Block;
 use pumpkin_protocol::{
     codec::var_int::VarInt,
     java::client::play::{MetaDataType, Metadata},
@@ -14,7 +14,7 @@ use std::{
     },
 };
 
-use super::{Entity, EntityBase, living::LivingEntity};
+use super::{Entity, EntityBase, damage::DamageSource, living::LivingEntity};
 
 pub struct TNTEntity {
     entity: Entity,
@@ -71,7 +71,7 @@ impl EntityBase for TNTEntity {
             .await;
     }
 
-    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+    async fn damage_with_source(&self, _amount: f32, _source: DamageSource) -> bool {
         false
     }
 
@@ -2,14 +2,13 @@ use std::sync::Arc;
 
 use pumpkin_data::entity::EntityType;
 use pumpkin_util::math::vector3::Vector3;
-use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::{
     entity::{
         Entity, EntityBase,
-        ai::path::Navigator,
-        decoration::painting::PaintingEntity,
+        armor_stand::ArmorStandEntity,
+        decoration::{leash_knot::LeashKnotEntity, painting::PaintingEntity},
         living::LivingEntity,
         mob::{MobEntity, zombie::Zombie},
     },
@@ -27,12 +26,10 @@ pub fn from_type(
     let base: Arc<dyn EntityBase> = match entity_type {
         EntityType::ZOMBIE => Arc::new(Zombie::make(entity)),
         EntityType::PAINTING => Arc::new(PaintingEntity::new(entity)),
+        EntityType::LEASH_KNOT => Arc::new(LeashKnotEntity::new(entity)),
+        EntityType::ARMOR_STAND => Arc::new(ArmorStandEntity::new(entity)),
         // TODO
-        _ => Arc::new(MobEntity {
-            living_entity: LivingEntity::new(entity),
-            goals: Mutex::new(vec![]),
-            navigator: Mutex::new(Navigator::default()),
-        }),
+        _ => Arc::new(MobEntity::new(LivingEntity::new(entity), vec![])),
     };
     base
 }
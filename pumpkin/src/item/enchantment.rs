@@ -0,0 +1,174 @@
+use pumpkin_data::Enchantment;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+use pumpkin_world::item::ItemStack;
+use rand::Rng;
+
+/// The highest level an enchantment can have on an item
+pub const MAX_ENCHANTMENT_LEVEL: i32 = 255;
+
+/// Whether the enchantment can be applied to the item.
+///
+/// Each enchantment lists the tools, weapons and armor it supports as an item tag,
+/// e.g. `#minecraft:enchantable/sword` for looting.
+#[must_use]
+pub fn can_enchant(enchantment: &Enchantment, item: &Item) -> bool {
+    item.is_tagged_with(enchantment.supported_items)
+        .unwrap_or(false)
+}
+
+/// The translated name of an enchantment with its level, like "Sharpness V".
+#[must_use]
+pub fn get_full_name(enchantment: &Enchantment, level: i32) -> TextComponent {
+    let name = enchantment
+        .name
+        .strip_prefix("minecraft:")
+        .unwrap_or(enchantment.name);
+    let mut text = TextComponent::translate(format!("enchantment.minecraft.{name}"), []);
+    if level != 1 || enchantment.max_level != 1 {
+        text = text
+            .add_child(TextComponent::text(" "))
+            .add_child(TextComponent::translate(
+                format!("enchantment.level.{level}"),
+                [],
+            ));
+    }
+    if name.ends_with("_curse") {
+        text.color_named(NamedColor::Red)
+    } else {
+        text.color_named(NamedColor::Gray)
+    }
+}
+
+/// The extra attack damage of a weapon, from sharpness.
+#[must_use]
+pub fn attack_damage_bonus(weapon: &ItemStack) -> f32 {
+    let level = weapon.get_enchantment_level(&Enchantment::SHARPNESS);
+    if level > 0 {
+        0.5 * level as f32 + 0.5
+    } else {
+        0.0
+    }
+}
+
+/// The extra mining speed of a tool, from efficiency.
+///
+/// Only applies if the tool is meant for the block, i.e. its speed is above `1`.
+#[must_use]
+pub fn mining_speed_bonus(tool: &ItemStack, speed: f32) -> f32 {
+    let level = tool.get_enchantment_level(&Enchantment::EFFICIENCY);
+    if level > 0 && speed > 1.0 {
+        (level * level + 1) as f32
+    } else {
+        0.0
+    }
+}
+
+/// How much a piece of armor protects against the damage type, vanilla's "enchantment protection factor".
+#[must_use]
+pub fn protection_factor(armor: &ItemStack, damage_type: &DamageType) -> i32 {
+    if damage_type
+        .is_tagged_with("minecraft:bypasses_enchantments")
+        .unwrap_or(false)
+    {
+        return 0;
+    }
+    let is = |tag: &str| damage_type.is_tagged_with(tag).unwrap_or(false);
+
+    let mut factor = 0;
+    if !is("minecraft:bypasses_invulnerability") {
+        factor += armor.get_enchantment_level(&Enchantment::PROTECTION);
+    }
+    if is("minecraft:is_fire") {
+        factor += 2 * armor.get_enchantment_level(&Enchantment::FIRE_PROTECTION);
+    }
+    if is("minecraft:is_explosion") {
+        factor += 2 * armor.get_enchantment_level(&Enchantment::BLAST_PROTECTION);
+    }
+    if is("minecraft:is_projectile") {
+        factor += 2 * armor.get_enchantment_level(&Enchantment::PROJECTILE_PROTECTION);
+    }
+    if is("minecraft:is_fall") {
+        factor += 3 * armor.get_enchantment_level(&Enchantment::FEATHER_FALLING);
+    }
+    factor
+}
+
+/// Reduces the damage by the summed up protection factor of all worn armor, which is capped at `20`.
+#[must_use]
+pub fn apply_protection(amount: f32, protection_factor: i32) -> f32 {
+    amount * (1.0 - protection_factor.clamp(0, 20) as f32 / 25.0)
+}
+
+/// Whether unbreaking prevents the item from losing one point of durability.
+#[must_use]
+pub fn unbreaking_prevents_damage(stack: &ItemStack) -> bool {
+    let level = stack.get_enchantment_level(&Enchantment::UNBREAKING);
+    if level <= 0 {
+        return false;
+    }
+    // Armor only gets a part of the bonus
+    let chance_to_damage = if stack
+        .item
+        .is_tagged_with("minecraft:enchantable/armor")
+        .unwrap_or(false)
+    {
+        0.6 + 0.4 / (level + 1) as f32
+    } else {
+        1.0 / (level + 1) as f32
+    };
+    rand::rng().random::<f32>() >= chance_to_damage
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Enchantment, damage::DamageType, item::Item};
+    use pumpkin_world::item::ItemStack;
+
+    use super::{apply_protection, attack_damage_bonus, mining_speed_bonus, protection_factor};
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.001,
+            "{actual} is not {expected}"
+        );
+    }
+
+    fn enchanted(item: &'static Item, enchantment: &'static Enchantment, level: i32) -> ItemStack {
+        let mut stack = ItemStack::new(1, item);
+        stack.add_enchantment(enchantment, level);
+        stack
+    }
+
+    #[test]
+    fn sharpness_and_efficiency() {
+        let sword = enchanted(&Item::DIAMOND_SWORD, &Enchantment::SHARPNESS, 5);
+        assert_close(attack_damage_bonus(&sword), 3.0);
+        assert_close(
+            attack_damage_bonus(&ItemStack::new(1, &Item::DIAMOND_SWORD)),
+            0.0,
+        );
+
+        let pickaxe = enchanted(&Item::DIAMOND_PICKAXE, &Enchantment::EFFICIENCY, 5);
+        assert_close(mining_speed_bonus(&pickaxe, 8.0), 26.0);
+        // The wrong tool for the block isn't sped up
+        assert_close(mining_speed_bonus(&pickaxe, 1.0), 0.0);
+    }
+
+    #[test]
+    fn protection_depends_on_the_damage_type() {
+        let boots = enchanted(&Item::DIAMOND_BOOTS, &Enchantment::FEATHER_FALLING, 4);
+        assert_eq!(protection_factor(&boots, &DamageType::FALL), 12);
+        assert_eq!(protection_factor(&boots, &DamageType::MOB_ATTACK), 0);
+
+        let chestplate = enchanted(&Item::DIAMOND_CHESTPLATE, &Enchantment::PROTECTION, 4);
+        assert_eq!(protection_factor(&chestplate, &DamageType::MOB_ATTACK), 4);
+
+        assert_close(apply_protection(10.0, 5), 8.0);
+        // The protection is capped at 80%
+        assert_close(apply_protection(10.0, 30), apply_protection(10.0, 20));
+    }
+}
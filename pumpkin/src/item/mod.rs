@@ -1,3 +1,5 @@
+pub mod enchantment;
 pub mod items;
 pub mod pumpkin_item;
+pub mod recipe;
 pub mod registry;
@@ -0,0 +1,2 @@
+// The registry lives next to the crafting screen handler, which matches every grid through it
+pub use pumpkin_inventory::crafting::recipes::{Recipe, RecipeRegistry};
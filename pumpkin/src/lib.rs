@@ -1,7 +1,7 @@
 // Not warn event sending macros
 #![allow(unused_labels)]
 
-use crate::logging::{GzipRollingLogger, ReadlineLogWrapper};
+use crate::logging::{ConsoleHistoryStore, GzipRollingLogger, JsonLogWrapper, ReadlineLogWrapper};
 use crate::net::bedrock::BedrockClientPlatform;
 use crate::net::java::JavaClientPlatform;
 use crate::net::{lan_broadcast, query, rcon::RCONServer};
@@ -10,6 +10,7 @@ use bytes::Bytes;
 use log::{Level, LevelFilter};
 use net::authentication::fetch_mojang_public_keys;
 use plugin::PluginManager;
+use plugin::player::player_kick::KickCause;
 use plugin::server::server_command::ServerCommandEvent;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_macros::send_cancellable;
@@ -96,7 +97,10 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
             .as_deref()
             .map(LevelFilter::from_str)
             .and_then(Result::ok)
-            .unwrap_or(LevelFilter::Info);
+            .unwrap_or_else(|| advanced_config().logging.level_filter());
+        // The loggers let everything through, `log::set_max_level` filters the records so
+        // `/reload` can change the level
+        let logger_level = LevelFilter::Trace;
 
         let file_logger: Option<Box<dyn SharedLogger + 'static>> =
             if advanced_config().logging.file.is_empty() {
@@ -104,7 +108,7 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
             } else {
                 Some(
                     GzipRollingLogger::new(
-                        level,
+                        logger_level,
                         {
                             let mut config = config.clone();
                             for level in Level::iter() {
@@ -122,7 +126,11 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
         if advanced_config().commands.use_tty && stdin().is_terminal() {
             match Readline::new("$ ".to_owned()) {
                 Ok((rl, stdout)) => {
-                    let logger = simplelog::WriteLogger::new(level, config.build(), stdout);
+                    let logger: Box<dyn SharedLogger> = if advanced_config().logging.json_logging {
+                        JsonLogWrapper::new(logger_level, stdout)
+                    } else {
+                        simplelog::WriteLogger::new(logger_level, config.build(), stdout)
+                    };
                     Some((
                         ReadlineLogWrapper::new(logger, file_logger, Some(rl)),
                         level,
@@ -132,12 +140,12 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
                     log::warn!(
                         "Failed to initialize console input ({e}); falling back to simple logger"
                     );
-                    let logger = simplelog::SimpleLogger::new(level, config.build());
+                    let logger = stdout_logger(logger_level, config.build());
                     Some((ReadlineLogWrapper::new(logger, file_logger, None), level))
                 }
             }
         } else {
-            let logger = simplelog::SimpleLogger::new(level, config.build());
+            let logger = stdout_logger(logger_level, config.build());
             Some((ReadlineLogWrapper::new(logger, file_logger, None), level))
         }
     } else {
@@ -145,6 +153,15 @@ pub static LOGGER_IMPL: LazyLock<Option<(ReadlineLogWrapper, LevelFilter)>> = La
     }
 });
 
+/// Logs to stdout, as JSON if `json_logging` is enabled.
+fn stdout_logger(level: LevelFilter, config: simplelog::Config) -> Box<dyn SharedLogger> {
+    if advanced_config().logging.json_logging {
+        JsonLogWrapper::new(level, std::io::stdout())
+    } else {
+        simplelog::SimpleLogger::new(level, config)
+    }
+}
+
 #[macro_export]
 macro_rules! init_log {
     () => {
@@ -226,7 +243,7 @@ impl PumpkinServer {
         }
 
         if BASIC_CONFIG.allow_chat_reports {
-            let mojang_public_keys = fetch_mojang_public_keys().unwrap();
+            let mojang_public_keys = fetch_mojang_public_keys().await.unwrap();
             *server.mojang_public_keys.lock().await = mojang_public_keys;
         }
 
@@ -293,7 +310,7 @@ impl PumpkinServer {
 
         let kick_message = TextComponent::text("Server stopped");
         for player in self.server.get_all_players().await {
-            player.kick(kick_message.clone()).await;
+            player.kick(KickCause::Shutdown, kick_message.clone()).await;
         }
 
         log::info!("Ending player tasks");
@@ -470,6 +487,11 @@ fn setup_console(rl: Readline, server: Arc<Server>) {
     // This needs to be async, or it will hog a thread.
     server.clone().spawn_task(async move {
         let mut rl = rl;
+        // Loaded once the logger is set up, so a failure to load is logged
+        let history = ConsoleHistoryStore::from_config();
+        if let Some(history) = &history {
+            history.load_history(&mut rl);
+        }
         while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
             let t1 = rl.readline();
             let t2 = STOP_INTERRUPT.notified();
@@ -507,6 +529,9 @@ fn setup_console(rl: Readline, server: Arc<Server>) {
                 }
             }
         }
+        if let Some(history) = &history {
+            history.save_history(&rl);
+        }
         if let Some((wrapper, _)) = &*LOGGER_IMPL {
             wrapper.return_readline(rl);
         }
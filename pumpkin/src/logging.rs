@@ -1,10 +1,18 @@
 use flate2::write::GzEncoder;
+use log::kv::{self, VisitSource};
 use log::{LevelFilter, Log};
+use pumpkin_config::{advanced_config, logging::LoggingConfig};
 use rustyline_async::Readline;
 use simplelog::{CombinedLogger, Config, SharedLogger, WriteLogger};
 use std::fs::File;
-use std::io::BufWriter;
-use std::path::Path;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, mpsc};
+
+mod history;
+
+pub use history::ConsoleHistoryStore;
 
 /// A wrapper for our logger to hold the terminal input while no input is expected in order to
 /// properly flush logs to the output while they happen instead of batched
@@ -13,58 +21,335 @@ pub struct ReadlineLogWrapper {
     readline: std::sync::Mutex<Option<Readline>>,
 }
 
+/// Counts the bytes written to the latest log so we know when it has to be rotated.
+struct CountingWriter {
+    file: BufWriter<File>,
+    written: Arc<AtomicU64>,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.written.fetch_add(written as u64, Ordering::Relaxed);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writes each log record as a single line JSON object, for log aggregation pipelines like ELK or Loki.
+///
+/// Structured fields of the record, e.g. `log::info!(player = name; "joined")`, end up in `fields`.
+pub struct JsonLogWrapper<W: Write + Send> {
+    log_level: LevelFilter,
+    writer: std::sync::Mutex<W>,
+}
+
+impl<W: Write + Send + 'static> JsonLogWrapper<W> {
+    pub fn new(log_level: LevelFilter, writer: W) -> Box<Self> {
+        Box::new(Self {
+            log_level,
+            writer: std::sync::Mutex::new(writer),
+        })
+    }
+
+    fn to_json(record: &log::Record) -> serde_json::Value {
+        let timestamp = time::OffsetDateTime::now_utc()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default();
+        let mut fields = FieldCollector(serde_json::Map::new());
+        // Our collector never fails
+        let _ = record.key_values().visit(&mut fields);
+
+        serde_json::json!({
+            "timestamp": timestamp,
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "message": record.args().to_string(),
+            "fields": fields.0,
+        })
+    }
+}
+
+/// Collects the structured fields of a record into a JSON object.
+struct FieldCollector(serde_json::Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        let value = if let Some(value) = value.to_bool() {
+            serde_json::Value::Bool(value)
+        } else if let Some(value) = value.to_i64() {
+            value.into()
+        } else if let Some(value) = value.to_u64() {
+            value.into()
+        } else if let Some(value) = value.to_f64() {
+            value.into()
+        } else {
+            value.to_string().into()
+        };
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+impl<W: Write + Send + 'static> Log for JsonLogWrapper<W> {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.log_level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = Self::to_json(record).to_string();
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{line}");
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> SharedLogger for JsonLogWrapper<W> {
+    fn level(&self) -> LevelFilter {
+        self.log_level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        Box::new(*self)
+    }
+}
+
 struct GzipRollingLoggerData {
     pub current_day_of_month: u8,
     pub last_rotate_time: time::OffsetDateTime,
-    pub latest_logger: WriteLogger<File>,
+    pub latest_logger: Box<dyn SharedLogger>,
     latest_filename: String,
+    latest_size: Arc<AtomicU64>,
+}
+
+/// Messages handled by the compression thread, in the order they were sent.
+enum ArchiveMessage {
+    /// Compresses a rotated log into the given archive.
+    Compress(PathBuf, PathBuf),
+    /// Answers once every log sent before was compressed.
+    Flush(mpsc::Sender<()>),
+}
+
+/// Compresses rotated logs and deletes the oldest ones.
+struct LogArchiver {
+    directory: PathBuf,
+    /// How many rotated logs are kept, `0` keeps all of them
+    max_log_files: usize,
+}
+
+impl LogArchiver {
+    /// Compresses the rotated log into `archive` and deletes it afterwards.
+    ///
+    /// The archive is written under a temporary name first and renamed once complete,
+    /// so readers never see a partially written archive.
+    fn archive(&self, rotated: &Path, archive: &Path) -> io::Result<()> {
+        let partial = archive.with_extension("tmp");
+        GzipRollingLogger::compress(rotated, &partial)?;
+        std::fs::rename(&partial, archive)?;
+        std::fs::remove_file(rotated)?;
+        self.remove_old_logs()
+    }
+
+    /// Deletes the oldest rotated logs until at most `max_log_files` are left.
+    fn remove_old_logs(&self) -> io::Result<()> {
+        if self.max_log_files == 0 {
+            return Ok(());
+        }
+        let mut rotated = Vec::new();
+        for entry in std::fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().ends_with(".log.gz") {
+                rotated.push((entry.metadata()?.modified()?, entry.path()));
+            }
+        }
+        let excess = rotated.len().saturating_sub(self.max_log_files);
+        rotated.sort();
+        for (_, path) in rotated.into_iter().take(excess) {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Starts the thread compressing rotated logs, so rotating never blocks logging.
+    fn spawn(self: Arc<Self>) -> io::Result<mpsc::Sender<ArchiveMessage>> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("Log Compression".to_string())
+            .spawn(move || {
+                for message in receiver {
+                    match message {
+                        ArchiveMessage::Compress(rotated, archive) => {
+                            if let Err(e) = self.archive(&rotated, &archive) {
+                                eprintln!("Failed to compress log {}: {e}", rotated.display());
+                            }
+                        }
+                        ArchiveMessage::Flush(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            })?;
+        Ok(sender)
+    }
 }
 
 pub struct GzipRollingLogger {
     log_level: LevelFilter,
     data: std::sync::Mutex<GzipRollingLoggerData>,
     config: Config,
+    directory: PathBuf,
+    /// The latest log is rotated once it grows past this many bytes, `0` disables it
+    max_file_size: u64,
+    /// The latest log is rotated once it is this old, `None` only rotates daily
+    max_file_age: Option<time::Duration>,
+    /// How many bytes are buffered before they are written to the latest log
+    buffer_size: usize,
+    /// Writes the logs as JSON instead of text
+    json: bool,
+    archiver: Arc<LogArchiver>,
+    /// Sends rotated logs to the compression thread, `None` compresses them while rotating
+    compression_thread: Option<mpsc::Sender<ArchiveMessage>>,
 }
 
 impl GzipRollingLogger {
+    /// Creates the logger in the `logs` directory using the `logging` config.
     pub fn new(
         log_level: LevelFilter,
         config: Config,
         filename: String,
     ) -> Result<Box<Self>, Box<dyn std::error::Error>> {
+        Self::with_rotation(
+            "logs",
+            log_level,
+            config,
+            filename,
+            &advanced_config().logging,
+        )
+    }
+
+    pub fn with_rotation(
+        directory: impl Into<PathBuf>,
+        log_level: LevelFilter,
+        config: Config,
+        filename: String,
+        logging: &LoggingConfig,
+    ) -> Result<Box<Self>, Box<dyn std::error::Error>> {
+        let directory = directory.into();
         let now = time::OffsetDateTime::now_utc();
-        std::fs::create_dir_all("logs")?;
+        std::fs::create_dir_all(&directory)?;
+
+        let archiver = Arc::new(LogArchiver {
+            directory: directory.clone(),
+            max_log_files: logging.max_log_files,
+        });
+
+        // Logs rotated right before the last shutdown may not have been compressed yet
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "rotated")
+            {
+                archiver.archive(&path, &Self::new_filename(&directory, false))?;
+            }
+        }
 
         // If latest.log exists, we will gzip it
-        if Path::new(&format!("logs/{filename}")).exists() {
-            let new_filename = Self::new_filename(false);
-            let mut file = File::open(format!("logs/{filename}"))?;
-            let mut encoder = GzEncoder::new(
-                BufWriter::new(File::create(&new_filename)?),
-                flate2::Compression::best(),
-            );
-            println!("logs/{filename}");
-            std::io::copy(&mut file, &mut encoder)?;
-            encoder.finish()?;
+        let latest_path = directory.join(&filename);
+        if latest_path.exists() {
+            let (rotated, _) = Self::rotated_paths(&latest_path);
+            std::fs::rename(&latest_path, &rotated)?;
+            archiver.archive(&rotated, &Self::new_filename(&directory, false))?;
         }
+        archiver.remove_old_logs()?;
+
+        let buffer_size = logging.file_buffer_kb * 1024;
+        let latest_size = Arc::new(AtomicU64::new(0));
+        let latest_logger = Self::create_latest_logger(
+            log_level,
+            &config,
+            logging.json_logging,
+            CountingWriter {
+                file: BufWriter::with_capacity(buffer_size, File::create(&latest_path)?),
+                written: latest_size.clone(),
+            },
+        );
+        let compression_thread = if logging.compress_in_background {
+            Some(archiver.clone().spawn()?)
+        } else {
+            None
+        };
 
         Ok(Box::new(Self {
             log_level,
             data: std::sync::Mutex::new(GzipRollingLoggerData {
                 current_day_of_month: now.day(),
                 last_rotate_time: now,
-                latest_filename: filename.clone(),
-                latest_logger: *WriteLogger::new(
-                    log_level,
-                    config.clone(),
-                    File::create(format!("logs/{filename}")).unwrap(),
-                ),
+                latest_logger,
+                latest_filename: filename,
+                latest_size,
             }),
             config,
+            directory,
+            max_file_size: (logging.max_file_size_mb * 1024.0 * 1024.0) as u64,
+            max_file_age: (logging.max_file_age_hours > 0)
+                .then(|| time::Duration::hours(logging.max_file_age_hours as i64)),
+            buffer_size,
+            json: logging.json_logging,
+            archiver,
+            compression_thread,
         }))
     }
 
-    pub fn new_filename(yesterday: bool) -> String {
+    fn create_latest_logger(
+        log_level: LevelFilter,
+        config: &Config,
+        json: bool,
+        writer: CountingWriter,
+    ) -> Box<dyn SharedLogger> {
+        if json {
+            JsonLogWrapper::new(log_level, writer)
+        } else {
+            WriteLogger::new(log_level, config.clone(), writer)
+        }
+    }
+
+    /// Where the latest log is moved to until it is compressed, and the archive named
+    /// `<name>-<timestamp>.log.gz` it can be compressed into. Neither of them exists yet.
+    fn rotated_paths(latest_path: &Path) -> (PathBuf, PathBuf) {
+        let name = latest_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+        let mut timestamp = time::OffsetDateTime::now_utc().unix_timestamp_nanos();
+        loop {
+            let rotated = latest_path.with_extension(format!("{timestamp}.rotated"));
+            let archive = latest_path.with_file_name(format!("{name}-{timestamp}.log.gz"));
+            if !rotated.exists() && !archive.exists() {
+                return (rotated, archive);
+            }
+            timestamp += 1;
+        }
+    }
+
+    pub fn new_filename(directory: &Path, yesterday: bool) -> PathBuf {
         let mut now = time::OffsetDateTime::now_utc()
             .to_offset(time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC));
         if yesterday {
@@ -74,34 +359,70 @@ impl GzipRollingLogger {
 
         let mut id = 1;
         loop {
-            let filename = format!("logs/{base_filename}-{id}.log.gz");
-            if !Path::new(&filename).exists() {
+            let filename = directory.join(format!("{base_filename}-{id}.log.gz"));
+            if !filename.exists() {
                 return filename;
             }
             id += 1;
         }
     }
 
-    fn rotate_log(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let now = time::OffsetDateTime::now_utc();
-        let mut data = self.data.lock().unwrap();
-
-        let new_filename = Self::new_filename(true);
-        let mut file = File::open(format!("logs/{}", data.latest_filename))?;
+    fn compress(from: &Path, to: &Path) -> io::Result<()> {
+        let mut file = File::open(from)?;
         let mut encoder = GzEncoder::new(
-            BufWriter::new(File::create(format!("logs/{new_filename}"))?),
+            BufWriter::new(File::create(to)?),
             flate2::Compression::best(),
         );
         std::io::copy(&mut file, &mut encoder)?;
-        encoder.finish()?;
+        encoder.finish()?.flush()
+    }
+
+    /// Moves the latest log aside and starts a new one, returns the moved log and the archive
+    /// to compress it into.
+    ///
+    /// Takes the locked `data`, so no other writer can rotate the same log in the meantime.
+    /// `yesterday` names the archive after the previous day, for rotations at midnight.
+    fn rotate_log(
+        &self,
+        data: &mut GzipRollingLoggerData,
+        yesterday: bool,
+    ) -> Result<(PathBuf, PathBuf), Box<dyn std::error::Error>> {
+        let now = time::OffsetDateTime::now_utc();
+
+        data.latest_logger.flush();
+        let latest_path = self.directory.join(&data.latest_filename);
+        let (rotated, archive) = Self::rotated_paths(&latest_path);
+        std::fs::rename(&latest_path, &rotated)?;
+        let archive = if yesterday {
+            Self::new_filename(&self.directory, true)
+        } else {
+            archive
+        };
 
         data.current_day_of_month = now.day();
         data.last_rotate_time = now;
-        data.latest_logger = *WriteLogger::new(
+        data.latest_size.store(0, Ordering::Relaxed);
+        data.latest_logger = Self::create_latest_logger(
             self.log_level,
-            self.config.clone(),
-            File::create(format!("logs/{}", data.latest_filename)).unwrap(),
+            &self.config,
+            self.json,
+            CountingWriter {
+                file: BufWriter::with_capacity(self.buffer_size, File::create(&latest_path)?),
+                written: data.latest_size.clone(),
+            },
         );
+        Ok((rotated, archive))
+    }
+
+    fn compress_rotated(
+        &self,
+        rotated: PathBuf,
+        archive: PathBuf,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match &self.compression_thread {
+            Some(sender) => sender.send(ArchiveMessage::Compress(rotated, archive))?,
+            None => self.archiver.archive(&rotated, &archive)?,
+        }
         Ok(())
     }
 }
@@ -118,21 +439,39 @@ impl Log for GzipRollingLogger {
 
         let now = time::OffsetDateTime::now_utc();
 
-        if let Ok(data) = self.data.lock() {
+        if let Ok(mut data) = self.data.lock() {
             data.latest_logger.log(record);
-            if data.current_day_of_month != now.day() {
+            let new_day = data.current_day_of_month != now.day();
+            let too_large = self.max_file_size > 0
+                && data.latest_size.load(Ordering::Relaxed) > self.max_file_size;
+            let too_old = self
+                .max_file_age
+                .is_some_and(|max_age| now - data.last_rotate_time >= max_age);
+            if new_day || too_large || too_old {
+                // The lock is held until the new log is in place, so other writers can't rotate
+                // the same log again. Compressing it doesn't need the lock anymore
+                let rotated = self.rotate_log(&mut data, new_day);
                 drop(data);
-                if let Err(e) = self.rotate_log() {
+                if let Err(e) =
+                    rotated.and_then(|(rotated, archive)| self.compress_rotated(rotated, archive))
+                {
                     eprintln!("Failed to rotate log: {e}");
                 }
             }
         }
     }
 
+    /// Writes the buffered lines and waits until every rotated log is compressed.
     fn flush(&self) {
         if let Ok(data) = self.data.lock() {
             data.latest_logger.flush();
         }
+        if let Some(sender) = &self.compression_thread {
+            let (done, wait) = mpsc::channel();
+            if sender.send(ArchiveMessage::Flush(done)).is_ok() {
+                let _ = wait.recv();
+            }
+        }
     }
 }
 
@@ -203,3 +542,138 @@ impl Log for ReadlineLogWrapper {
         self.internal.enabled(metadata)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotates_when_the_latest_log_grows_too_large() {
+        let temp_dir = tempdir().unwrap();
+        let logger = GzipRollingLogger::with_rotation(
+            temp_dir.path(),
+            LevelFilter::Info,
+            Config::default(),
+            "latest.log".to_string(),
+            &LoggingConfig {
+                max_file_size_mb: 0.001,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let message = "a".repeat(1001);
+        for _ in 0..2 {
+            logger.log(
+                &log::Record::builder()
+                    .args(format_args!("{message}"))
+                    .level(Level::Info)
+                    .build(),
+            );
+        }
+        logger.flush();
+
+        let mut files: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1], "latest.log");
+        let timestamp = files[0]
+            .strip_prefix("latest-")
+            .and_then(|name| name.strip_suffix(".log.gz"))
+            .unwrap();
+        assert!(timestamp.parse::<i128>().is_ok());
+    }
+
+    #[test]
+    fn keeps_every_line_across_rotations() {
+        let temp_dir = tempdir().unwrap();
+        let logger = GzipRollingLogger::with_rotation(
+            temp_dir.path(),
+            LevelFilter::Info,
+            Config::default(),
+            "latest.log".to_string(),
+            &LoggingConfig {
+                max_file_size_mb: 0.001,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for i in 0..100 {
+            logger.log(
+                &log::Record::builder()
+                    .args(format_args!("line {i:03}"))
+                    .level(Level::Info)
+                    .build(),
+            );
+        }
+        logger.flush();
+
+        let mut archives: Vec<_> = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(".log.gz"))
+            .collect();
+        assert!(archives.len() > 1);
+        archives.sort_by_key(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let timestamp = name.trim_end_matches(".log.gz").rsplit('-').next().unwrap();
+            timestamp.parse::<i128>().unwrap()
+        });
+
+        let mut contents = String::new();
+        for archive in archives {
+            let file = File::open(archive).unwrap();
+            io::Read::read_to_string(&mut flate2::read::GzDecoder::new(file), &mut contents)
+                .unwrap();
+        }
+        contents += &std::fs::read_to_string(temp_dir.path().join("latest.log")).unwrap();
+
+        let lines: Vec<_> = contents
+            .lines()
+            .map(|line| line.rsplit("line ").next().unwrap())
+            .collect();
+        let expected: Vec<_> = (0..100).map(|i| format!("{i:03}")).collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn writes_records_as_json() {
+        let temp_dir = tempdir().unwrap();
+        let logger = GzipRollingLogger::with_rotation(
+            temp_dir.path(),
+            LevelFilter::Info,
+            Config::default(),
+            "latest.log".to_string(),
+            &LoggingConfig {
+                max_file_size_mb: 0.0,
+                json_logging: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let fields = [("player", "Steve")];
+        logger.log(
+            &log::Record::builder()
+                .args(format_args!("joined"))
+                .level(Level::Info)
+                .target("pumpkin::server")
+                .key_values(&fields)
+                .build(),
+        );
+        logger.flush();
+
+        let latest = std::fs::read_to_string(temp_dir.path().join("latest.log")).unwrap();
+        let record: serde_json::Value = serde_json::from_str(latest.trim()).unwrap();
+        assert_eq!(record["level"], "INFO");
+        assert_eq!(record["target"], "pumpkin::server");
+        assert_eq!(record["message"], "joined");
+        assert_eq!(record["fields"]["player"], "Steve");
+    }
+}
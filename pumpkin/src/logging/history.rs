@@ -0,0 +1,91 @@
+use std::{fs, io, path::PathBuf};
+
+use pumpkin_config::advanced_config;
+use rustyline_async::Readline;
+
+/// Keeps the console input history between restarts as a JSON list of lines.
+pub struct ConsoleHistoryStore {
+    path: PathBuf,
+    max_entries: usize,
+}
+
+impl ConsoleHistoryStore {
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, max_entries: usize) -> Self {
+        Self {
+            path: path.into(),
+            max_entries,
+        }
+    }
+
+    /// Creates the store from the `commands` config, or `None` if the history file is disabled.
+    #[must_use]
+    pub fn from_config() -> Option<Self> {
+        let config = &advanced_config().commands;
+        if config.history_file.is_empty() {
+            return None;
+        }
+        Some(Self::new(&config.history_file, config.max_history_entries))
+    }
+
+    /// Reads the newest `max_entries` lines, a missing file is an empty history.
+    pub fn load(&self) -> io::Result<Vec<String>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut entries: Vec<String> = serde_json::from_str(&content)?;
+        let excess = entries.len().saturating_sub(self.max_entries);
+        entries.drain(..excess);
+        Ok(entries)
+    }
+
+    /// Writes the newest `max_entries` lines.
+    pub fn save(&self, entries: &[String]) -> io::Result<()> {
+        let excess = entries.len().saturating_sub(self.max_entries);
+        let content = serde_json::to_string_pretty(&entries[excess..])?;
+        fs::write(&self.path, content)
+    }
+
+    /// Replaces the history of the line reader with the stored one.
+    pub fn load_history(&self, rl: &mut Readline) {
+        rl.set_max_history(self.max_entries);
+        match self.load() {
+            Ok(entries) => rl.set_history_entries(entries),
+            Err(err) => log::warn!(
+                "Failed to load console history from {}: {err}",
+                self.path.display()
+            ),
+        }
+    }
+
+    /// Stores the current history of the line reader.
+    pub fn save_history(&self, rl: &Readline) {
+        let entries: Vec<String> = rl.get_history_entries().iter().cloned().collect();
+        if let Err(err) = self.save(&entries) {
+            log::warn!(
+                "Failed to save console history to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConsoleHistoryStore;
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_and_load_keeps_newest_entries() {
+        let temp_dir = tempdir().unwrap();
+        let store = ConsoleHistoryStore::new(temp_dir.path().join(".console_history"), 2);
+
+        assert!(store.load().unwrap().is_empty());
+
+        let entries = ["list", "say hi", "stop"].map(String::from);
+        store.save(&entries).unwrap();
+        assert_eq!(store.load().unwrap(), ["say hi", "stop"]);
+    }
+}
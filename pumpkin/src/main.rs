@@ -118,6 +118,8 @@ async fn main() {
     let default_panic = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         default_panic(info);
+        // Buffered log lines would be lost when exiting, and they likely tell what went wrong
+        log::logger().flush();
         // TODO: Gracefully exit?
         // We need to abide by the panic rules here.
         std::process::exit(1);
@@ -176,6 +178,8 @@ async fn main() {
 
     pumpkin_server.start().await;
     log::info!("The server has stopped.");
+    // Make sure buffered log lines and rotated logs are written before exiting
+    log::logger().flush();
 }
 
 fn handle_interrupt() {
@@ -1,13 +1,18 @@
-use std::{collections::HashMap, net::IpAddr};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
 
 use base64::{Engine, engine::general_purpose};
 use pumpkin_config::{advanced_config, networking::auth::TextureConfig};
 use pumpkin_protocol::Property;
+use reqwest::{Client, StatusCode, Url};
 use rsa::RsaPublicKey;
 use rsa::pkcs8::DecodePublicKey;
 use serde::Deserialize;
 use thiserror::Error;
-use ureq::http::{StatusCode, Uri};
 use uuid::Uuid;
 
 use super::GameProfile;
@@ -47,6 +52,20 @@ const MOJANG_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/sessio
 const MOJANG_PREVENT_PROXY_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
 const MOJANG_SERVICES_URL: &str = "https://api.minecraftservices.com/";
 
+/// How often we ask the session server before giving up, if it keeps answering with a server error.
+const MAX_ATTEMPTS: u32 = 3;
+/// The wait before the first retry, which doubles with every further retry.
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    let config = &advanced_config().networking.authentication;
+    Client::builder()
+        .connect_timeout(Duration::from_millis(config.connect_timeout.into()))
+        .timeout(Duration::from_secs(config.auth_timeout_secs))
+        .build()
+        .expect("Failed to create the authentication HTTP client")
+});
+
 /// Sends a GET request to Mojang's authentication servers to verify a client's Minecraft account.
 ///
 /// **Purpose:**
@@ -60,7 +79,7 @@ const MOJANG_SERVICES_URL: &str = "https://api.minecraftservices.com/";
 /// 3. Now our server will send a Request to the Session servers and check if the Player has joined the Session Server .
 ///
 /// See <https://pumpkinmc.org/developer/networking/authentication>
-pub fn authenticate(
+pub async fn authenticate(
     username: &str,
     server_hash: &str,
     ip: &IpAddr,
@@ -94,19 +113,58 @@ pub fn authenticate(
             .replace("{server_hash}", server_hash)
     };
 
-    let mut response = ureq::get(address)
-        .call()
-        .map_err(|_| AuthError::FailedResponse)?;
-    match response.status() {
-        StatusCode::OK => {}
-        StatusCode::NO_CONTENT => Err(AuthError::UnverifiedUsername)?,
-        other => Err(AuthError::UnknownStatusCode(other))?,
+    fetch_profile(&CLIENT, &address).await
+}
+
+/// Asks the session server for the profile, retrying with exponential back-off when it answers
+/// with a server error.
+async fn fetch_profile(client: &Client, address: &str) -> Result<GameProfile, AuthError> {
+    let start = Instant::now();
+    let mut delay = RETRY_DELAY;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let response = client.get(address).send().await.map_err(|error| {
+            log::debug!("Authentication attempt {attempt} failed: {error}");
+            if error.is_timeout() {
+                AuthError::Timeout
+            } else {
+                AuthError::FailedResponse
+            }
+        })?;
+
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::NO_CONTENT => {
+                log::debug!("Authentication attempt {attempt}: unverified username");
+                Err(AuthError::UnverifiedUsername)?;
+            }
+            status if status.is_server_error() => {
+                log::debug!("Authentication attempt {attempt} failed with status {status}");
+                if attempt < MAX_ATTEMPTS {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                continue;
+            }
+            other => Err(AuthError::UnknownStatusCode(other))?,
+        }
+
+        let profile: GameProfile = response.json().await.map_err(|error| {
+            if error.is_timeout() {
+                AuthError::Timeout
+            } else {
+                AuthError::FailedParse
+            }
+        })?;
+        log::debug!(
+            "Authenticated {} after {attempt} attempt(s) in {:?}",
+            profile.name,
+            start.elapsed()
+        );
+        return Ok(profile);
     }
-    let profile: GameProfile = response
-        .body_mut()
-        .read_json()
-        .map_err(|_| AuthError::FailedParse)?;
-    Ok(profile)
+
+    log::debug!("Authentication failed after {MAX_ATTEMPTS} attempts");
+    Err(AuthError::FailedResponse)
 }
 
 pub fn validate_textures(property: &Property, config: &TextureConfig) -> Result<(), TextureError> {
@@ -126,27 +184,27 @@ pub fn validate_textures(property: &Property, config: &TextureConfig) -> Result<
     Ok(())
 }
 
-pub fn is_texture_url_valid(url: &Uri, config: &TextureConfig) -> Result<(), TextureError> {
-    let scheme = url.scheme().unwrap();
+pub fn is_texture_url_valid(url: &Url, config: &TextureConfig) -> Result<(), TextureError> {
+    let scheme = url.scheme();
     if !config
         .allowed_url_schemes
         .iter()
-        .any(|allowed_scheme| scheme.as_str().ends_with(allowed_scheme))
+        .any(|allowed_scheme| scheme.ends_with(allowed_scheme))
     {
         return Err(TextureError::DisallowedUrlScheme(scheme.to_string()));
     }
-    let domain = url.authority().unwrap();
+    let domain = url.host_str().ok_or(TextureError::InvalidURL)?;
     if !config
         .allowed_url_domains
         .iter()
-        .any(|allowed_domain| domain.as_str().ends_with(allowed_domain))
+        .any(|allowed_domain| domain.ends_with(allowed_domain))
     {
         return Err(TextureError::DisallowedUrlDomain(domain.to_string()));
     }
     Ok(())
 }
 
-pub fn fetch_mojang_public_keys() -> Result<Vec<RsaPublicKey>, AuthError> {
+pub async fn fetch_mojang_public_keys() -> Result<Vec<RsaPublicKey>, AuthError> {
     let services_url = advanced_config()
         .networking
         .authentication
@@ -156,8 +214,10 @@ pub fn fetch_mojang_public_keys() -> Result<Vec<RsaPublicKey>, AuthError> {
 
     let url = format!("{services_url}/publickeys");
 
-    let mut response = ureq::get(url)
-        .call()
+    let response = CLIENT
+        .get(url)
+        .send()
+        .await
         .map_err(|_| AuthError::FailedResponse)?;
 
     match response.status() {
@@ -166,10 +226,8 @@ pub fn fetch_mojang_public_keys() -> Result<Vec<RsaPublicKey>, AuthError> {
         other => Err(AuthError::UnknownStatusCode(other))?,
     }
 
-    let public_keys: MojangPublicKeys = response
-        .body_mut()
-        .read_json()
-        .map_err(|_| AuthError::FailedParse)?;
+    let public_keys: MojangPublicKeys =
+        response.json().await.map_err(|_| AuthError::FailedParse)?;
 
     let as_rsa_keys = public_keys
         .player_certificate_keys
@@ -189,6 +247,8 @@ pub fn fetch_mojang_public_keys() -> Result<Vec<RsaPublicKey>, AuthError> {
 pub enum AuthError {
     #[error("Authentication servers are down")]
     FailedResponse,
+    #[error("Authentication servers timed out")]
+    Timeout,
     #[error("Failed to verify username")]
     UnverifiedUsername,
     #[error("You are banned from Authentication servers")]
@@ -216,3 +276,38 @@ pub enum TextureError {
     #[error("Failed to parse JSON from player texture: {0}")]
     JSONError(String),
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::Client;
+
+    use super::fetch_profile;
+
+    #[tokio::test]
+    async fn retries_after_server_error() {
+        let mut server = mockito::Server::new_async().await;
+        let path = "/session/minecraft/hasJoined?username=Alex&serverId=hash";
+        let unavailable = server
+            .mock("GET", path)
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let joined = server
+            .mock("GET", path)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"id":"069a79f444e94726a5befca90e38aaf5","name":"Alex","properties":[]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let profile = fetch_profile(&Client::new(), &format!("{}{path}", server.url()))
+            .await
+            .expect("The player should be authenticated on the retry");
+        assert_eq!(profile.name, "Alex");
+
+        unavailable.assert_async().await;
+        joined.assert_async().await;
+    }
+}
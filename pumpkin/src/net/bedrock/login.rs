@@ -17,7 +17,7 @@ use pumpkin_protocol::{
 };
 
 use crate::{
-    net::{ClientPlatform, GameProfile, bedrock::BedrockClientPlatform},
+    net::{ClientPlatform, GameProfile, bedrock::BedrockClientPlatform, can_not_join},
     server::{CURRENT_BEDROCK_MC_VERSION, Server},
 };
 
@@ -87,6 +87,11 @@ impl BedrockClientPlatform {
             profile_actions: None,
         };
 
+        if let Some(reason) = can_not_join(&profile, &self.address, server).await {
+            self.kick(reason).await;
+            return;
+        }
+
         if let Some((player, world)) = server
             .add_player(
                 ClientPlatform::Bedrock(self.clone()),
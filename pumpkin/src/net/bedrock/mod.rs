@@ -3,7 +3,7 @@ use std::{
     io::{Cursor, Write},
     sync::{
         Arc,
-        atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicI32, AtomicU16, AtomicU32, Ordering},
     },
 };
 
@@ -13,8 +13,10 @@ use pumpkin_protocol::{
     ClientPacket, PacketDecodeError, PacketEncodeError, RawPacket, ServerPacket,
     bedrock::{
         RAKNET_ACK, RAKNET_GAME_PACKET, RAKNET_NACK, RAKNET_VALID, RakReliability, SubClient,
+        UDP_HEADER_SIZE,
         ack::Ack,
-        frame_set::{Frame, FrameSet},
+        client::disconnect::CDisconnect,
+        frame_set::{FRAME_SET_HEADER_SIZE, Frame, FrameSet},
         packet_decoder::UDPNetworkDecoder,
         packet_encoder::UDPNetworkEncoder,
         server::{
@@ -50,6 +52,11 @@ pub mod unconnected;
 
 use crate::{entity::player::Player, server::Server};
 
+/// The smallest MTU every client has to support
+pub const MIN_MTU: u16 = 576;
+/// The largest MTU we agree on, even if the client's path supports more
+pub const MAX_MTU: u16 = 1400;
+
 pub struct BedrockClientPlatform {
     socket: Arc<UdpSocket>,
     /// The client's IP address.
@@ -73,6 +80,10 @@ pub struct BedrockClientPlatform {
     output_reliable_number: AtomicU32,
     output_sequenced_index: AtomicU32,
     output_ordered_index: AtomicU32,
+    /// The id of the next packet split into fragments
+    output_split_id: AtomicU16,
+    /// The MTU agreed on in the open connection handshake, including the UDP header
+    mtu: AtomicU16,
 
     /// An notifier that is triggered when this client is closed.
     close_interrupt: Arc<Notify>,
@@ -104,6 +115,8 @@ impl BedrockClientPlatform {
             output_reliable_number: AtomicU32::new(0),
             output_sequenced_index: AtomicU32::new(0),
             output_ordered_index: AtomicU32::new(0),
+            output_split_id: AtomicU16::new(0),
+            mtu: AtomicU16::new(MIN_MTU),
             compounds: Arc::new(Mutex::new(HashMap::new())),
             closed: Arc::new(AtomicBool::new(false)),
             close_interrupt: Arc::new(Notify::new()),
@@ -184,9 +197,14 @@ impl BedrockClientPlatform {
             .set_compression((compression.threshold as usize, compression.level));
     }
 
-    #[allow(clippy::unused_async)]
-    pub async fn kick(&self, _reason: TextComponent) {
-        // TODO
+    pub async fn kick(&self, reason: TextComponent) {
+        // Sent in order after everything else, so the client sees the reason instead of a timeout
+        self.send_game_packet(
+            &CDisconnect::new(reason.get_text()),
+            RakReliability::ReliableOrdered,
+        )
+        .await;
+        self.close();
     }
 
     pub async fn enqueue_packet<P>(&self, packet: &P)
@@ -280,49 +298,64 @@ impl BedrockClientPlatform {
     }
 
     pub async fn send_framed_packet_data(&self, packet_buf: Vec<u8>, reliability: RakReliability) {
-        let mut frame_set = FrameSet {
-            sequence: U24(self.output_sequence_number.fetch_add(1, Ordering::Relaxed)),
-            frames: Vec::with_capacity(1),
-        };
         let mut frame = Frame {
             payload: packet_buf.into(),
             reliability,
             ..Default::default()
         };
 
-        if reliability.is_reliable() {
-            frame.reliable_number = self.output_reliable_number.fetch_add(1, Ordering::Relaxed);
-            if matches!(reliability, RakReliability::ReliableOrdered) {
-                //Todo! Check if Fragmenting is needed
-            }
+        let max_frame_size =
+            usize::from(self.mtu.load(Ordering::Relaxed) - UDP_HEADER_SIZE) - FRAME_SET_HEADER_SIZE;
+        let needs_split = frame.header_size() + frame.payload.len() > max_frame_size;
+        if needs_split && !reliability.is_reliable() {
+            // Losing a single fragment would drop the whole packet
+            frame.reliability = RakReliability::ReliableOrdered;
         }
 
-        if reliability.is_ordered() {
+        if frame.reliability.is_ordered() {
             frame.order_index = self.output_ordered_index.fetch_add(1, Ordering::Relaxed);
         }
 
-        if reliability.is_sequenced() {
+        if frame.reliability.is_sequenced() {
             frame.sequence_index = self.output_sequenced_index.fetch_add(1, Ordering::Relaxed);
         }
 
-        frame_set.frames.push(frame);
+        let frames = if needs_split {
+            // Makes the header size include the split fields
+            frame.split_size = 1;
+            let max_payload = max_frame_size - frame.header_size();
+            let split_id = self.output_split_id.fetch_add(1, Ordering::Relaxed);
+            frame.split(max_payload, split_id)
+        } else {
+            vec![frame]
+        };
 
-        let mut packet_buf = Vec::new();
-        frame_set.write_packet_data(&mut packet_buf).unwrap();
+        // Hold the writer while sending so the fragments aren't interleaved with other packets
+        let mut writer = self.network_writer.lock().await;
+        for mut frame in frames {
+            if frame.reliability.is_reliable() {
+                frame.reliable_number = self.output_reliable_number.fetch_add(1, Ordering::Relaxed);
+            }
+            let frame_set = FrameSet {
+                sequence: U24(self.output_sequence_number.fetch_add(1, Ordering::Relaxed)),
+                frames: vec![frame],
+            };
 
-        if let Err(err) = self
-            .network_writer
-            .lock()
-            .await
-            .write_packet(packet_buf.into(), self.address, &self.socket)
-            .await
-        {
-            // It is expected that the packet will fail if we are closed
-            if !self.closed.load(Ordering::Relaxed) {
-                log::warn!("Failed to send packet to client: {err}");
-                // We now need to close the connection to the client since the stream is in an
-                // unknown state
-                self.close();
+            let mut packet_buf = Vec::new();
+            frame_set.write_packet_data(&mut packet_buf).unwrap();
+
+            if let Err(err) = writer
+                .write_packet(packet_buf.into(), self.address, &self.socket)
+                .await
+            {
+                // It is expected that the packet will fail if we are closed
+                if !self.closed.load(Ordering::Relaxed) {
+                    log::warn!("Failed to send packet to client: {err}");
+                    // We now need to close the connection to the client since the stream is in an
+                    // unknown state
+                    self.close();
+                }
+                return;
             }
         }
     }
@@ -1,3 +1,5 @@
+use std::sync::atomic::Ordering;
+
 use pumpkin_protocol::{
     bedrock::{
         client::raknet::open_connection::{COpenConnectionReply1, COpenConnectionReply2},
@@ -6,22 +8,26 @@ use pumpkin_protocol::{
     codec::socket_address::SocketAddress,
 };
 
-use crate::{net::bedrock::BedrockClientPlatform, server::Server};
+use crate::{
+    net::bedrock::{BedrockClientPlatform, MAX_MTU, MIN_MTU},
+    server::Server,
+};
 
 impl BedrockClientPlatform {
-    pub async fn handle_open_connection_1(
-        &self,
-        server: &Server,
-        _packet: SOpenConnectionRequest1,
-    ) {
-        self.send_raknet_packet_now(&COpenConnectionReply1::new(server.server_guid, false, 1400))
+    pub async fn handle_open_connection_1(&self, server: &Server, packet: SOpenConnectionRequest1) {
+        // The request made it through, so its size fits the path's MTU
+        let mtu = packet.mtu.min(MAX_MTU);
+        self.send_raknet_packet_now(&COpenConnectionReply1::new(server.server_guid, false, mtu))
             .await;
     }
+
     pub async fn handle_open_connection_2(&self, server: &Server, packet: SOpenConnectionRequest2) {
+        let mtu = packet.mtu.clamp(MIN_MTU, MAX_MTU);
+        self.mtu.store(mtu, Ordering::Relaxed);
         self.send_raknet_packet_now(&COpenConnectionReply2::new(
             server.server_guid,
             SocketAddress(self.address),
-            packet.mtu,
+            mtu,
             false,
         ))
         .await;
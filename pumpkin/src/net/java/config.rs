@@ -162,6 +162,7 @@ impl JavaClientPlatform {
     pub async fn handle_config_acknowledged(self: &Arc<Self>, server: &Server) {
         log::debug!("Handling config acknowledgement");
         self.connection_state.store(ConnectionState::Play);
+        self.login_finished.notify_one();
 
         let profile = self.gameprofile.lock().await.clone();
         let profile = profile.unwrap();
@@ -1,37 +1,62 @@
+use std::{ops::RangeInclusive, sync::Arc, time::Duration};
+
+use pumpkin_config::advanced_config;
 use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
 use pumpkin_protocol::{ConnectionState, java::server::handshake::SHandShake};
-use pumpkin_util::text::TextComponent;
 
-use crate::{net::java::JavaClientPlatform, server::CURRENT_MC_VERSION};
+use crate::{net::java::JavaClientPlatform, server::Server};
+
+/// The protocol versions clients may join with, only the server's own unless configured otherwise.
+#[must_use]
+pub fn accepted_protocols() -> RangeInclusive<i32> {
+    let config = &advanced_config().networking.protocol;
+    let min = config.min_protocol.unwrap_or(CURRENT_MC_PROTOCOL);
+    let max = config.max_protocol.unwrap_or(CURRENT_MC_PROTOCOL);
+    min as i32..=max as i32
+}
 
 impl JavaClientPlatform {
-    pub async fn handle_handshake(&self, handshake: SHandShake) {
+    pub async fn handle_handshake(self: &Arc<Self>, server: &Server, handshake: SHandShake) {
         let version = handshake.protocol_version.0;
+        // Kept for the status response and the version check on login
         self.protocol_version
             .store(version, std::sync::atomic::Ordering::Relaxed);
         *self.server_address.lock().await = handshake.server_address;
 
         log::debug!("Handshake: next state is {:?}", &handshake.next_state);
         self.connection_state.store(handshake.next_state);
-        if self.connection_state.load() != ConnectionState::Status {
-            let protocol = version;
-            match protocol.cmp(&(CURRENT_MC_PROTOCOL as i32)) {
-                std::cmp::Ordering::Less => {
-                    self.kick(TextComponent::translate(
-                        "multiplayer.disconnect.outdated_client",
-                        [TextComponent::text(CURRENT_MC_VERSION.to_string())],
-                    ))
-                    .await;
-                }
-                std::cmp::Ordering::Equal => {}
-                std::cmp::Ordering::Greater => {
-                    self.kick(TextComponent::translate(
-                        "multiplayer.disconnect.incompatible",
-                        [TextComponent::text(CURRENT_MC_VERSION.to_string())],
-                    ))
-                    .await;
-                }
-            }
+        // Status pings don't log in, so they don't count towards the limit
+        if matches!(
+            handshake.next_state,
+            ConnectionState::Login | ConnectionState::Transfer
+        ) {
+            self.limit_login(server).await;
+        }
+    }
+
+    /// Counts the login towards the limit of its IP, closing the connection if there are too
+    /// many logins in progress from it already.
+    async fn limit_login(self: &Arc<Self>, server: &Server) {
+        let login_limit = &advanced_config().networking.login_limit;
+        if !login_limit.enabled {
+            return;
         }
+        let address = *self.address.lock().await;
+        let Some(permit) = server
+            .login_limiter
+            .try_acquire(address.ip(), login_limit.max_logins_per_ip)
+        else {
+            log::info!(
+                "Dropping connection {}: too many logins in progress from its IP",
+                self.id
+            );
+            self.close();
+            return;
+        };
+        let client = self.clone();
+        let timeout = Duration::from_secs(login_limit.login_timeout);
+        self.spawn_task(async move {
+            client.await_login(permit, timeout).await;
+        });
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::LazyLock;
+use std::sync::{LazyLock, atomic::Ordering};
 
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_protocol::{
@@ -19,11 +19,10 @@ use crate::{
         GameProfile,
         authentication::{self, AuthError},
         is_valid_player_name,
-        java::JavaClientPlatform,
-        offline_uuid,
+        java::{JavaClientPlatform, handshake::accepted_protocols},
         proxy::{bungeecord, velocity},
     },
-    server::Server,
+    server::{CURRENT_MC_VERSION, Server},
 };
 
 static LINKS: LazyLock<Vec<Link>> = LazyLock::new(|| {
@@ -90,6 +89,25 @@ impl JavaClientPlatform {
     pub async fn handle_login_start(&self, server: &Server, login_start: SLoginStart) {
         log::debug!("login start");
 
+        let protocol = self.protocol_version.load(Ordering::Relaxed);
+        let accepted = accepted_protocols();
+        if protocol < *accepted.start() {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.outdated_client",
+                [TextComponent::text(CURRENT_MC_VERSION.to_string())],
+            ))
+            .await;
+            return;
+        }
+        if protocol > *accepted.end() {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.incompatible",
+                [TextComponent::text(CURRENT_MC_VERSION.to_string())],
+            ))
+            .await;
+            return;
+        }
+
         // Don't allow new logons when the server is full.
         // If `max_players` is set to zero, then there is no max player count enforced.
         // TODO: If client is an operator or has otherwise suitable elevated permissions, allow the client to bypass this requirement.
@@ -123,8 +141,9 @@ impl JavaClientPlatform {
                 )
                 .await
                 {
-                    Ok((_ip, profile)) => {
-                        // self.address.lock() = ip;
+                    Ok((ip, profile)) => {
+                        // Use the forwarded address, otherwise bans would apply to the proxy
+                        self.address.lock().await.set_ip(ip);
                         self.finish_login(&profile).await;
                         *gameprofile = Some(profile);
                     }
@@ -135,7 +154,7 @@ impl JavaClientPlatform {
             let id = if BASIC_CONFIG.online_mode {
                 login_start.uuid
             } else {
-                offline_uuid(&login_start.name).expect("This is very not safe and bad")
+                GameProfile::offline_uuid(&login_start.name)
             };
 
             let profile = GameProfile {
@@ -191,9 +210,21 @@ impl JavaClientPlatform {
                 .await
             {
                 Ok(new_profile) => *profile = new_profile,
+                Err(AuthError::Timeout | AuthError::FailedResponse)
+                    if advanced_config()
+                        .networking
+                        .authentication
+                        .fallback_to_offline_on_timeout =>
+                {
+                    log::warn!(
+                        "Authentication servers are unavailable, letting {} join in offline mode",
+                        profile.name
+                    );
+                    profile.id = GameProfile::offline_uuid(&profile.name);
+                }
                 Err(error) => {
                     self.kick(match error {
-                        AuthError::FailedResponse => {
+                        AuthError::FailedResponse | AuthError::Timeout => {
                             TextComponent::translate("multiplayer.disconnect.authservers_down", [])
                         }
                         AuthError::UnverifiedUsername => TextComponent::translate(
@@ -267,7 +298,7 @@ impl JavaClientPlatform {
     ) -> Result<GameProfile, AuthError> {
         let hash = server.digest_secret(shared_secret);
         let ip = self.address.lock().await.ip();
-        let profile = authentication::authenticate(username, &hash, &ip)?;
+        let profile = authentication::authenticate(username, &hash, &ip).await?;
 
         // Check if the player should join
         if let Some(actions) = &profile.profile_actions {
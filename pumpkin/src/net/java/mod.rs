@@ -1,12 +1,13 @@
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, AtomicI32};
+use std::time::Duration;
 use std::{io::Write, sync::Arc};
 
 use bytes::Bytes;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::networking::compression::CompressionInfo;
 use pumpkin_protocol::java::server::play::{
-    SChangeGameMode, SChatCommand, SChatMessage, SChunkBatch, SClickSlot, SClientCommand,
+    SChangeGameMode, SChatAck, SChatCommand, SChatMessage, SChunkBatch, SClickSlot, SClientCommand,
     SClientInformationPlay, SClientTickEnd, SCloseContainer, SCommandSuggestion, SConfirmTeleport,
     SCookieResponse as SPCookieResponse, SInteract, SKeepAlive, SPickItemFromBlock,
     SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerLoaded,
@@ -32,6 +33,7 @@ use pumpkin_protocol::{
             },
             status::{SStatusPingRequest, SStatusRequest},
         },
+        statistics::PacketStatistics,
     },
     packet::Packet,
     ser::{NetworkWriteExt, ReadingError, WritingError},
@@ -59,7 +61,9 @@ pub mod play;
 pub mod status;
 
 use crate::entity::player::Player;
+use crate::net::login_limit::LoginPermit;
 use crate::net::{GameProfile, PlayerConfig};
+use crate::plugin::packet::{PACKET_HOOKS, PacketAction, PacketDirection};
 use crate::{error::PumpkinError, net::EncryptionError, server::Server};
 
 pub struct JavaClientPlatform {
@@ -81,10 +85,14 @@ pub struct JavaClientPlatform {
     /// The client's brand or modpack information, Optional.
     pub brand: Mutex<Option<String>>,
     pub player: Mutex<Option<Arc<Player>>>,
+    /// The bytes and packets sent to and received from the client.
+    pub statistics: Arc<PacketStatistics>,
     /// A collection of tasks associated with this client. The tasks await completion when removing the client.
     tasks: TaskTracker,
     /// An notifier that is triggered when this client is closed.
     close_interrupt: Arc<Notify>,
+    /// An notifier that is triggered when this client enters the play state.
+    login_finished: Notify,
     /// A queue of serialized packets to send to the network
     outgoing_packet_queue_send: Sender<Bytes>,
     /// A queue of serialized packets to send to the network
@@ -93,6 +101,35 @@ pub struct JavaClientPlatform {
     network_writer: Arc<Mutex<TCPNetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
     /// The packet decoder for incoming packets.
     network_reader: Mutex<TCPNetworkDecoder<BufReader<OwnedReadHalf>>>,
+    /// The suggestion request waiting for the running one, see [`SuggestionQueue`].
+    suggestions: Arc<Mutex<SuggestionQueue>>,
+}
+
+/// Only one command suggestion task runs per client. Requests arriving meanwhile replace each
+/// other, since the client only shows the answer to its latest request anyway.
+#[derive(Default)]
+struct SuggestionQueue {
+    running: bool,
+    next: Option<SCommandSuggestion>,
+}
+
+impl SuggestionQueue {
+    /// Returns the request if it should be answered now, otherwise queues it.
+    fn offer(&mut self, request: SCommandSuggestion) -> Option<SCommandSuggestion> {
+        if self.running {
+            self.next = Some(request);
+            return None;
+        }
+        self.running = true;
+        Some(request)
+    }
+
+    /// Called when a request was answered, returns the one to answer next.
+    fn finish(&mut self) -> Option<SCommandSuggestion> {
+        let next = self.next.take();
+        self.running = next.is_some();
+        next
+    }
 }
 
 impl JavaClientPlatform {
@@ -100,6 +137,7 @@ impl JavaClientPlatform {
     pub fn new(tcp_stream: TcpStream, address: SocketAddr, id: u64) -> Self {
         let (read, write) = tcp_stream.into_split();
         let (send, recv) = tokio::sync::mpsc::channel(128);
+        let statistics = Arc::new(PacketStatistics::default());
         Self {
             id,
             protocol_version: AtomicI32::new(0),
@@ -110,14 +148,21 @@ impl JavaClientPlatform {
             connection_state: AtomicCell::new(ConnectionState::HandShake),
             closed: Arc::new(AtomicBool::new(false)),
             close_interrupt: Arc::new(Notify::new()),
+            login_finished: Notify::new(),
             tasks: TaskTracker::new(),
             outgoing_packet_queue_send: send,
             outgoing_packet_queue_recv: Some(recv),
 
-            network_writer: Arc::new(Mutex::new(TCPNetworkEncoder::new(BufWriter::new(write)))),
-            network_reader: Mutex::new(TCPNetworkDecoder::new(BufReader::new(read))),
+            network_writer: Arc::new(Mutex::new(
+                TCPNetworkEncoder::new(BufWriter::new(write)).with_statistics(statistics.clone()),
+            )),
+            network_reader: Mutex::new(
+                TCPNetworkDecoder::new(BufReader::new(read)).with_statistics(statistics.clone()),
+            ),
             brand: Mutex::new(None),
             player: Mutex::new(None),
+            statistics,
+            suggestions: Arc::new(Mutex::new(SuggestionQueue::default())),
         }
     }
     pub async fn set_encryption(
@@ -133,10 +178,6 @@ impl JavaClientPlatform {
     }
 
     pub async fn set_compression(&self, compression: CompressionInfo) {
-        if compression.level > 9 {
-            log::error!("Invalid compression level! Clients will not be able to read this!");
-        }
-
         self.network_reader
             .lock()
             .await
@@ -145,7 +186,8 @@ impl JavaClientPlatform {
         self.network_writer
             .lock()
             .await
-            .set_compression((compression.threshold as usize, compression.level));
+            .set_compression((compression.threshold as usize, compression.level))
+            .expect("The compression level is validated when loading the config");
     }
 
     /// Processes all packets received from the connected client in a loop.
@@ -164,7 +206,15 @@ impl JavaClientPlatform {
     pub async fn process_packets(self: &Arc<Self>, server: &Arc<Server>) {
         loop {
             let packet = self.get_packet().await;
-            let Some(packet) = packet else { break };
+            let Some(mut packet) = packet else { break };
+
+            if PACKET_HOOKS
+                .intercept(self, PacketDirection::Serverbound, &mut packet)
+                .await
+                == PacketAction::Cancel
+            {
+                continue;
+            }
 
             if let Err(error) = self.handle_packet(server, &packet).await {
                 let text = format!("Error while reading incoming packet {error}");
@@ -200,14 +250,60 @@ impl JavaClientPlatform {
         }
     }
 
+    /// Answers the suggestion request, and then the ones queued while it ran.
+    fn spawn_suggestion_task(
+        &self,
+        player: &Arc<Player>,
+        server: &Arc<Server>,
+        packet: SCommandSuggestion,
+    ) {
+        let suggestions = self.suggestions.clone();
+        let player = player.clone();
+        let server = server.clone();
+        self.spawn_task(async move {
+            let mut request = Some(packet);
+            while let Some(packet) = request {
+                Self::handle_command_suggestion(&player, packet, &server).await;
+                request = suggestions.lock().await.finish();
+            }
+        });
+    }
+
     pub async fn enqueue_packet<P>(&self, packet: &P)
     where
         P: ClientPacket,
     {
+        if let Some(buf) = self.serialize_packet(packet).await {
+            self.enqueue_packet_data(buf.into()).await;
+        }
+    }
+
+    /// Writes the id and body of a packet, first passing it through the clientbound packet hooks.
+    /// Returns `None` if a hook cancelled the packet.
+    async fn serialize_packet<P: ClientPacket>(&self, packet: &P) -> Option<Vec<u8>> {
+        let mut buf = Vec::new();
+        let state = self.connection_state.load();
+        if !PACKET_HOOKS.is_hooked(state, PacketDirection::Clientbound, P::PACKET_ID) {
+            Self::write_packet(packet, &mut buf).unwrap();
+            return Some(buf);
+        }
+
+        packet.write_packet_data(&mut buf).unwrap();
+        let mut raw = RawPacket {
+            id: P::PACKET_ID,
+            payload: buf.into(),
+        };
+        if PACKET_HOOKS
+            .intercept(self, PacketDirection::Clientbound, &mut raw)
+            .await
+            == PacketAction::Cancel
+        {
+            return None;
+        }
         let mut buf = Vec::new();
-        let writer = &mut buf;
-        Self::write_packet(packet, writer).unwrap();
-        self.enqueue_packet_data(buf.into()).await;
+        buf.write_var_int(&VarInt(raw.id)).unwrap();
+        buf.extend_from_slice(&raw.payload);
+        Some(buf)
     }
 
     /// Queues a clientbound packet to be sent to the connected client. Queued chunks are sent
@@ -229,6 +325,24 @@ impl JavaClientPlatform {
         }
     }
 
+    /// Holds the login permit until the client is in the play state, closing the
+    /// connection if that takes longer than the timeout.
+    pub async fn await_login(&self, permit: LoginPermit, timeout: Duration) {
+        tokio::select! {
+            () = self.login_finished.notified() => {},
+            () = self.await_close_interrupt() => {},
+            () = tokio::time::sleep(timeout) => {
+                log::info!(
+                    "Dropping connection {}: did not log in within {} seconds",
+                    self.id,
+                    timeout.as_secs()
+                );
+                self.close();
+            }
+        }
+        drop(permit);
+    }
+
     pub async fn await_close_interrupt(&self) {
         self.close_interrupt.notified().await;
     }
@@ -261,18 +375,14 @@ impl JavaClientPlatform {
         }
     }
 
+    /// Disconnects the client, sending the reason in the packet of the current connection phase.
     pub async fn kick(&self, reason: TextComponent) {
         match self.connection_state.load() {
             ConnectionState::Login => {
-                // TextComponent implements Serialize and writes in bytes instead of String, that's the reasib we only use content
-                self.send_packet_now(&CLoginDisconnect::new(
-                    &serde_json::to_string(&reason.0).unwrap_or_else(|_| String::new()),
-                ))
-                .await;
+                self.send_packet_now(&CLoginDisconnect::new(&reason)).await;
             }
             ConnectionState::Config => {
-                self.send_packet_now(&CConfigDisconnect::new(&reason.get_text()))
-                    .await;
+                self.send_packet_now(&CConfigDisconnect::new(&reason)).await;
             }
             ConnectionState::Play => self.send_packet_now(&CPlayDisconnect::new(&reason)).await,
             _ => {}
@@ -282,10 +392,9 @@ impl JavaClientPlatform {
     }
 
     pub async fn send_packet_now<P: ClientPacket>(&self, packet: &P) {
-        let mut packet_buf = Vec::new();
-        let writer = &mut packet_buf;
-        Self::write_packet(packet, writer).unwrap();
-        self.send_packet_now_data(packet_buf).await;
+        if let Some(packet_buf) = self.serialize_packet(packet).await {
+            self.send_packet_now_data(packet_buf).await;
+        }
     }
 
     pub async fn send_packet_now_data(&self, packet: Vec<u8>) {
@@ -346,7 +455,7 @@ impl JavaClientPlatform {
     ) -> Result<(), ReadingError> {
         match self.connection_state.load() {
             pumpkin_protocol::ConnectionState::HandShake => {
-                self.handle_handshake_packet(packet).await
+                self.handle_handshake_packet(server, packet).await
             }
             pumpkin_protocol::ConnectionState::Status => {
                 self.handle_status_packet(server, packet).await
@@ -384,12 +493,17 @@ impl JavaClientPlatform {
         }
     }
 
-    async fn handle_handshake_packet(&self, packet: &RawPacket) -> Result<(), ReadingError> {
+    async fn handle_handshake_packet(
+        self: &Arc<Self>,
+        server: &Server,
+        packet: &RawPacket,
+    ) -> Result<(), ReadingError> {
         log::debug!("Handling handshake group");
         let payload = &packet.payload[..];
         match packet.id {
             0 => {
-                self.handle_handshake(SHandShake::read(payload)?).await;
+                self.handle_handshake(server, SHandShake::read(payload)?)
+                    .await;
             }
             _ => {
                 log::error!(
@@ -478,6 +592,7 @@ impl JavaClientPlatform {
         self.close_interrupt.notify_waiters();
         self.closed
             .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.statistics.reset();
     }
 
     async fn handle_login_packet(
@@ -577,6 +692,9 @@ impl JavaClientPlatform {
                 self.handle_chat_command(player, server, &(SChatCommand::read(payload)?))
                     .await;
             }
+            SChatAck::PACKET_ID => {
+                self.handle_chat_ack(player, SChatAck::read(payload)?).await;
+            }
             SChatMessage::PACKET_ID => {
                 self.handle_chat_message(player, SChatMessage::read(payload)?)
                     .await;
@@ -586,7 +704,7 @@ impl JavaClientPlatform {
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(player, SClientCommand::read(payload)?)
+                self.handle_client_status(player, server, SClientCommand::read(payload)?)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
@@ -672,8 +790,12 @@ impl JavaClientPlatform {
                     .await;
             }
             SCommandSuggestion::PACKET_ID => {
-                self.handle_command_suggestion(player, SCommandSuggestion::read(payload)?, server)
-                    .await;
+                // Suggestions may look up players or registries, so don't hold up the packet loop
+                let packet = SCommandSuggestion::read(payload)?;
+                let request = self.suggestions.lock().await.offer(packet);
+                if let Some(request) = request {
+                    self.spawn_suggestion_task(player, server, request);
+                }
             }
             SPCookieResponse::PACKET_ID => {
                 self.handle_cookie_response(&SPCookieResponse::read(payload)?);
@@ -697,3 +819,29 @@ impl JavaClientPlatform {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_protocol::{codec::var_int::VarInt, java::server::play::SCommandSuggestion};
+
+    use super::SuggestionQueue;
+
+    fn request(id: i32) -> SCommandSuggestion {
+        SCommandSuggestion {
+            id: VarInt(id),
+            command: "/give ".to_string(),
+        }
+    }
+
+    #[test]
+    fn suggestion_queue_keeps_only_the_latest_request() {
+        let mut queue = SuggestionQueue::default();
+        assert_eq!(queue.offer(request(1)).map(|request| request.id.0), Some(1));
+        assert!(queue.offer(request(2)).is_none());
+        assert!(queue.offer(request(3)).is_none());
+
+        assert_eq!(queue.finish().map(|request| request.id.0), Some(3));
+        assert!(queue.finish().is_none());
+        assert_eq!(queue.offer(request(4)).map(|request| request.id.0), Some(4));
+    }
+}
@@ -1,8 +1,9 @@
-use pumpkin_util::PermissionLvl;
+use rsa::RsaPublicKey;
 use rsa::pkcs1v15::{Signature as RsaPkcs1v15Signature, VerifyingKey};
+use rsa::pkcs8::DecodePublicKey;
 use rsa::signature::Verifier;
 use sha1::Sha1;
-use std::num::NonZeroU8;
+use sha2::Sha256;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -13,7 +14,8 @@ use crate::block::registry::BlockActionResult;
 use crate::block::{self, BlockIsReplacing};
 use crate::command::CommandSender;
 use crate::entity::EntityBase;
-use crate::entity::player::{ChatMode, ChatSession, Hand, Player};
+use crate::entity::leash;
+use crate::entity::player::{ChatMode, ChatSession, Hand, LastSeen, Player};
 use crate::entity::r#type::from_type;
 use crate::error::PumpkinError;
 use crate::net::PlayerConfig;
@@ -31,21 +33,22 @@ use pumpkin_data::block_properties::{
 use pumpkin_data::entity::{EntityType, entity_from_egg};
 use pumpkin_data::item::Item;
 use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::world::RAW;
 use pumpkin_data::{Block, BlockDirection};
 use pumpkin_inventory::InventoryError;
 use pumpkin_inventory::equipment_slot::EquipmentSlot;
 use pumpkin_inventory::player::player_inventory::PlayerInventory;
 use pumpkin_inventory::screen_handler::ScreenHandler;
 use pumpkin_macros::send_cancellable;
+use pumpkin_protocol::codec::angle::Angle;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::java::client::play::{
     Animation, CBlockUpdate, CCommandSuggestions, CEntityAnimation, CEntityPositionSync, CHeadRot,
     COpenSignEditor, CPingResponse, CPlayerInfoUpdate, CPlayerPosition, CSetSelectedSlot,
-    CSystemChatMessage, CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, InitChat,
-    PlayerAction,
+    CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot, InitChat, PlayerAction,
 };
 use pumpkin_protocol::java::server::play::{
-    Action, ActionType, CommandBlockMode, FLAG_ON_GROUND, SChangeGameMode, SChatCommand,
+    Action, ActionType, CommandBlockMode, FLAG_ON_GROUND, SChangeGameMode, SChatAck, SChatCommand,
     SChatMessage, SChunkBatch, SClientCommand, SClientInformationPlay, SCloseContainer,
     SCommandSuggestion, SConfirmTeleport, SCookieResponse as SPCookieResponse, SInteract,
     SKeepAlive, SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction,
@@ -53,6 +56,7 @@ use pumpkin_protocol::java::server::play::{
     SPlayerSession, SSetCommandBlock, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm,
     SUpdateSign, SUseItem, SUseItemOn, Status,
 };
+use pumpkin_registry::VanillaDimensionType;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_util::math::{polynomial_rolling_hash, position::BlockPos, wrap_degrees};
 use pumpkin_util::text::color::NamedColor;
@@ -66,6 +70,9 @@ use uuid::Uuid;
 /// In secure chat mode, Player will be kicked if they send a chat message with a timestamp that is older than this (in ms)
 /// Vanilla: 2 minutes
 const CHAT_MESSAGE_MAX_AGE: i64 = 1000 * 60 * 2;
+/// Player will be kicked if this many messages were sent to them without being acknowledged
+/// Vanilla: 4096
+const MAX_PENDING_MESSAGES: usize = 4096;
 
 #[derive(Debug, Error)]
 pub enum BlockPlacingError {
@@ -198,8 +205,9 @@ impl JavaClientPlatform {
         player: &Arc<Player>,
         change_game_mode: SChangeGameMode,
     ) {
-        if player.permission_lvl.load() >= PermissionLvl::Two {
-            player.set_gamemode(change_game_mode.game_mode).await;
+        if player.has_permission("minecraft:command.gamemode").await
+            && player.set_gamemode(change_game_mode.game_mode).await
+        {
             let gamemode_string = format!("{:?}", change_game_mode.game_mode).to_lowercase();
             let gamemode_string = format!("gameMode.{gamemode_string}");
             player
@@ -259,6 +267,31 @@ impl JavaClientPlatform {
         true
     }
 
+    /// Clamps the position a player moved to into the world and checks the movement with the
+    /// anti cheat. Returns `None` if the movement was rejected, the player is moved back then.
+    async fn validate_move(
+        &self,
+        player: &Arc<Player>,
+        position: Vector3<f64>,
+        on_ground: bool,
+    ) -> Option<Vector3<f64>> {
+        let position = Vector3::new(
+            Self::clamp_horizontal(position.x),
+            Self::clamp_vertical(position.y),
+            Self::clamp_horizontal(position.z),
+        );
+        let from = player.living_entity.entity.pos.load();
+        if !player
+            .anti_cheat
+            .check_move(player, from, position, on_ground)
+            .await
+        {
+            self.force_tp(player, from).await;
+            return None;
+        }
+        Some(position)
+    }
+
     pub async fn handle_position(&self, player: &Arc<Player>, packet: SPlayerPosition) {
         if !player.has_client_loaded() {
             return;
@@ -273,11 +306,12 @@ impl JavaClientPlatform {
             .await;
             return;
         }
-        let position = Vector3::new(
-            Self::clamp_horizontal(position.x),
-            Self::clamp_vertical(position.y),
-            Self::clamp_horizontal(position.z),
-        );
+        let Some(position) = self
+            .validate_move(player, position, packet.collision & FLAG_ON_GROUND != 0)
+            .await
+        else {
+            return;
+        };
 
         send_cancellable! {{
             PlayerMoveEvent {
@@ -301,7 +335,6 @@ impl JavaClientPlatform {
                 entity.on_ground.store(packet.collision & FLAG_ON_GROUND != 0, Ordering::Relaxed);
                 let world = &player.world().await;
 
-                // TODO: Warn when player moves to quickly
                 if !self.sync_position(player, world, pos, last_pos, entity.yaw.load(), entity.pitch.load(), packet.collision & FLAG_ON_GROUND != 0).await {
                     // Send the new position to all other players.
                     world
@@ -375,11 +408,12 @@ impl JavaClientPlatform {
             return;
         }
 
-        let position = Vector3::new(
-            Self::clamp_horizontal(position.x),
-            Self::clamp_vertical(position.y),
-            Self::clamp_horizontal(position.z),
-        );
+        let Some(position) = self
+            .validate_move(player, position, packet.collision & FLAG_ON_GROUND != 0)
+            .await
+        else {
+            return;
+        };
 
         send_cancellable! {{
             PlayerMoveEvent::new(
@@ -409,12 +443,10 @@ impl JavaClientPlatform {
 
                 let entity_id = entity.entity_id;
 
-                let yaw = (entity.yaw.load() * 256.0 / 360.0).rem_euclid(256.0);
-                let pitch = (entity.pitch.load() * 256.0 / 360.0).rem_euclid(256.0);
-                // let head_yaw = (entity.head_yaw * 256.0 / 360.0).floor();
+                let yaw = entity.yaw.load();
+                let pitch = entity.pitch.load();
                 let world = &entity.world.read().await;
 
-                // TODO: Warn when player moves to quickly
                 if !self
                     .sync_position(player, world, pos, last_pos, yaw, pitch, (packet.collision & FLAG_ON_GROUND) != 0)
                     .await
@@ -430,8 +462,8 @@ impl JavaClientPlatform {
                                     pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
                                     pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
                                 ),
-                                yaw as u8,
-                                pitch as u8,
+                                Angle::from_degrees(yaw),
+                                Angle::from_degrees(pitch),
                                 (packet.collision & FLAG_ON_GROUND) != 0,
                             ),
                         )
@@ -441,7 +473,7 @@ impl JavaClientPlatform {
                 world
                     .broadcast_packet_except(
                         &[player.gameprofile.id],
-                        &CHeadRot::new(entity_id.into(), yaw as u8),
+                        &CHeadRot::new(entity_id.into(), Angle::from_degrees(yaw)),
                     )
                     .await;
                 if !player.abilities.lock().await.flying {
@@ -507,17 +539,15 @@ impl JavaClientPlatform {
         );
         // Send the new position to all other players.
         let entity_id = entity.entity_id;
-        let yaw = (entity.yaw.load() * 256.0 / 360.0).rem_euclid(256.0);
-        let pitch = (entity.pitch.load() * 256.0 / 360.0).rem_euclid(256.0);
-        // let head_yaw = modulus(entity.head_yaw * 256.0 / 360.0, 256.0);
+        let yaw = Angle::from_degrees(entity.yaw.load());
+        let pitch = Angle::from_degrees(entity.pitch.load());
 
         let world = &entity.world.read().await;
-        let packet =
-            CUpdateEntityRot::new(entity_id.into(), yaw as u8, pitch as u8, rotation.ground);
+        let packet = CUpdateEntityRot::new(entity_id.into(), yaw, pitch, rotation.ground);
         world
             .broadcast_packet_except(&[player.gameprofile.id], &packet)
             .await;
-        let packet = CHeadRot::new(entity_id.into(), yaw as u8);
+        let packet = CHeadRot::new(entity_id.into(), yaw);
         world
             .broadcast_packet_except(&[player.gameprofile.id], &packet)
             .await;
@@ -578,7 +608,11 @@ impl JavaClientPlatform {
         player: &Arc<Player>,
         pick_item: SPickItemFromBlock,
     ) {
-        if !player.can_interact_with_block_at(&pick_item.pos, 1.0) {
+        if !player
+            .anti_cheat
+            .check_block_reach(player, &pick_item.pos)
+            .await
+        {
             return;
         }
 
@@ -658,36 +692,30 @@ impl JavaClientPlatform {
             return;
         }
 
+        // Sneaking is not part of this packet anymore, it is sent with the player input
         if let Ok(action) = Action::try_from(command.action.0) {
             let entity = &player.living_entity.entity;
             match action {
-                pumpkin_protocol::java::server::play::Action::StartSprinting => {
-                    if !entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(true).await;
+                Action::StartSprinting | Action::StopSprinting => {
+                    let sprinting = matches!(action, Action::StartSprinting);
+                    if entity.sprinting.load(Ordering::Relaxed) != sprinting {
+                        player.living_entity.set_sprinting(sprinting).await;
                     }
                 }
-                pumpkin_protocol::java::server::play::Action::StopSprinting => {
-                    if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(false).await;
+                Action::LeaveBed => {
+                    if player.sleeping_since.load().is_some() {
+                        player.wake_up().await;
                     }
                 }
-                pumpkin_protocol::java::server::play::Action::LeaveBed => player.wake_up().await,
-
-                pumpkin_protocol::java::server::play::Action::StartHorseJump
-                | pumpkin_protocol::java::server::play::Action::StopHorseJump
-                | pumpkin_protocol::java::server::play::Action::OpenVehicleInventory => {
-                    log::debug!("todo");
+                Action::StartHorseJump | Action::StopHorseJump | Action::OpenVehicleInventory => {
+                    // There are no rideable entities yet
+                    log::debug!("{} is not riding anything", player.gameprofile.name);
                 }
-                pumpkin_protocol::java::server::play::Action::StartFlyingElytra => {
-                    let fall_flying = entity.check_fall_flying();
-                    if entity
-                        .fall_flying
-                        .load(std::sync::atomic::Ordering::Relaxed)
-                        != fall_flying
-                    {
-                        entity.set_fall_flying(fall_flying).await;
+                Action::StartFlyingElytra => {
+                    if !player.try_start_fall_flying().await {
+                        player.stop_fall_flying().await;
                     }
-                } // TODO
+                }
             }
         } else {
             self.kick(TextComponent::text("Invalid player command"))
@@ -822,16 +850,48 @@ impl JavaClientPlatform {
                 if BASIC_CONFIG.allow_chat_reports {
                     world.broadcast_secure_player_chat(player, &chat_message, decorated_message).await;
                 } else {
-                    let no_reports_packet = &CSystemChatMessage::new(
-                        decorated_message,
-                        false,
-                    );
-                    world.broadcast_packet_all(no_reports_packet).await;
+                    // Without chat reports signatures are never looked at
+                    world
+                        .broadcast_message(
+                            decorated_message,
+                            &TextComponent::text(gameprofile.name.clone()),
+                            RAW,
+                            None,
+                        )
+                        .await;
                 }
             }
         }}
     }
 
+    pub async fn handle_chat_ack(&self, player: &Arc<Player>, ack: SChatAck) {
+        if !BASIC_CONFIG.allow_chat_reports {
+            return;
+        }
+        let result = {
+            let mut cache = player.signature_cache.lock().await;
+            if !cache.apply_offset(ack.offset.0) {
+                Err(ChatError::ChatValidationFailed)
+            } else if cache.tracked_messages() > MAX_PENDING_MESSAGES {
+                Err(ChatError::TooManyPendingChats)
+            } else {
+                Ok(())
+            }
+        };
+        if let Err(err) = result {
+            log::log!(
+                err.severity(),
+                "{} (uuid {}) {}",
+                player.gameprofile.name,
+                player.gameprofile.id,
+                err
+            );
+            if let Some(reason) = err.client_kick_reason() {
+                self.kick(TextComponent::text(reason)).await;
+            }
+        }
+    }
+
     /// Runs all vanilla checks for a valid chat message
     pub async fn validate_chat_message(
         &self,
@@ -853,12 +913,11 @@ impl JavaClientPlatform {
         // These checks are only run in secure chat mode
         if BASIC_CONFIG.allow_chat_reports {
             // Check for unsigned chat
-            if let Some(signature) = &chat_message.signature {
-                if signature.len() != 256 {
-                    return Err(ChatError::UnsignedChat); // Signature is the wrong length
-                }
-            } else {
+            let Some(signature) = &chat_message.signature else {
                 return Err(ChatError::UnsignedChat); // There is no signature
+            };
+            if signature.len() != 256 {
+                return Err(ChatError::UnsignedChat); // Signature is the wrong length
             }
 
             let now = SystemTime::now()
@@ -877,19 +936,78 @@ impl JavaClientPlatform {
                 return Err(ChatError::ExpiredPublicKey);
             }
 
+            // Move the last seen window the same way the client did before signing
+            let last_seen = player
+                .signature_cache
+                .lock()
+                .await
+                .apply_update(chat_message.message_count.0, &chat_message.acknowledged)
+                .ok_or(ChatError::ChatValidationFailed)?;
+            if player.signature_cache.lock().await.tracked_messages() > MAX_PENDING_MESSAGES {
+                return Err(ChatError::TooManyPendingChats);
+            }
+
             // Validate previous signature checksum (new in 1.21.5)
             // The client can bypass this check by sending 0
-            if chat_message.checksum != 0 {
-                let checksum =
-                    polynomial_rolling_hash(player.signature_cache.lock().await.last_seen.as_ref());
-                if checksum != chat_message.checksum {
-                    return Err(ChatError::ChatValidationFailed);
-                }
+            if chat_message.checksum != 0
+                && polynomial_rolling_hash(last_seen.as_ref()) != chat_message.checksum
+            {
+                return Err(ChatError::ChatValidationFailed);
             }
+
+            Self::verify_message_signature(player, chat_message, signature, &last_seen).await?;
+
+            // The message is now part of the sender's chain, whether or not it gets broadcast
+            player.chat_session.lock().await.messages_sent += 1;
+            player.signature_cache.lock().await.last_seen = last_seen;
         }
         Ok(())
     }
 
+    /// Verifies the signature of a chat message with the player's chat session key.
+    ///
+    /// The signed data is the same the client signs: the message link (sender, session and
+    /// index in the chain) followed by the message body and the acknowledged last seen signatures.
+    async fn verify_message_signature(
+        player: &Player,
+        chat_message: &SChatMessage,
+        signature: &[u8],
+        last_seen: &LastSeen,
+    ) -> Result<(), ChatError> {
+        let (session_id, public_key, index) = {
+            let session = player.chat_session.lock().await;
+            (
+                session.session_id,
+                session.public_key.clone(),
+                session.messages_sent,
+            )
+        };
+        let public_key =
+            RsaPublicKey::from_public_key_der(&public_key).map_err(|_| ChatError::UnsignedChat)?;
+        let signature =
+            RsaPkcs1v15Signature::try_from(signature).map_err(|_| ChatError::UnsignedChat)?;
+
+        let acknowledged = last_seen.as_ref();
+        let message = chat_message.message.as_bytes();
+        let mut signable = Vec::with_capacity(100 + message.len() + acknowledged.len() * 256);
+        signable.extend_from_slice(&1i32.to_be_bytes()); // Version
+        signable.extend_from_slice(player.gameprofile.id.as_bytes());
+        signable.extend_from_slice(session_id.as_bytes());
+        signable.extend_from_slice(&index.to_be_bytes());
+        signable.extend_from_slice(&chat_message.salt.to_be_bytes());
+        signable.extend_from_slice(&(chat_message.timestamp / 1000).to_be_bytes());
+        signable.extend_from_slice(&(message.len() as i32).to_be_bytes());
+        signable.extend_from_slice(message);
+        signable.extend_from_slice(&(acknowledged.len() as i32).to_be_bytes());
+        for sig in acknowledged {
+            signable.extend_from_slice(sig);
+        }
+
+        VerifyingKey::<Sha256>::new(public_key)
+            .verify(&signable, &signature)
+            .map_err(|_| ChatError::UnsignedChat)
+    }
+
     pub async fn handle_chat_session_update(
         &self,
         player: &Arc<Player>,
@@ -1029,15 +1147,8 @@ impl JavaClientPlatform {
 
                 *config = PlayerConfig {
                     locale: client_information.locale,
-                    // A negative view distance would be impossible and makes no sense, right? Mojang: Let's make it signed :D
-                    // client_information.view_distance was checked above to be > 0, so compiler should optimize this out.
-                    view_distance: match NonZeroU8::new(client_information.view_distance as u8) {
-                        Some(dist) => dist,
-                        None => {
-                            // Unreachable branch
-                            return;
-                        }
-                    },
+                    // Changed below, together with the watched chunks
+                    view_distance: old_view_distance,
                     chat_mode,
                     chat_colors: client_information.chat_colors,
                     skin_parts: client_information.skin_parts,
@@ -1049,7 +1160,11 @@ impl JavaClientPlatform {
             };
 
             if update_watched {
-                chunker::update_position(player).await;
+                // A negative view distance would be impossible and makes no sense, right? Mojang: Let's make it signed :D
+                // client_information.view_distance was checked above to be > 0
+                player
+                    .update_chunk_cache_radius(client_information.view_distance as u8)
+                    .await;
             }
 
             if update_settings {
@@ -1066,14 +1181,31 @@ impl JavaClientPlatform {
         }
     }
 
-    pub async fn handle_client_status(&self, player: &Arc<Player>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        &self,
+        player: &Arc<Player>,
+        server: &Server,
+        client_status: SClientCommand,
+    ) {
         match client_status.action_id.0 {
             0 => {
                 // Perform respawn
                 if player.living_entity.health.load() > 0.0 {
                     return;
                 }
-                player.world().await.respawn_player(player, false).await;
+                // Without a bed or respawn anchor players respawn at the overworld spawn
+                let (world, respawn_point) =
+                    if let Some((world, position, yaw)) = player.get_respawn_point(server).await {
+                        (world, Some((position, yaw)))
+                    } else if let Some(overworld) = server
+                        .get_world_from_dimension(VanillaDimensionType::Overworld)
+                        .await
+                    {
+                        (overworld, None)
+                    } else {
+                        (player.world().await, None)
+                    };
+                world.respawn_player(player, false, respawn_point).await;
 
                 let screen_handler = player.current_screen_handler.lock().await;
                 let mut screen_handler = screen_handler.lock().await;
@@ -1097,7 +1229,8 @@ impl JavaClientPlatform {
         }
     }
 
-    pub async fn handle_interact(&self, player: &Player, interact: SInteract) {
+    #[expect(clippy::too_many_lines)]
+    pub async fn handle_interact(&self, player: &Arc<Player>, interact: SInteract) {
         if !player.has_client_loaded() {
             return;
         }
@@ -1122,6 +1255,9 @@ impl JavaClientPlatform {
                 }
 
                 // TODO: set as camera entity when spectator
+                if player.is_spectator() {
+                    return;
+                }
 
                 let world = &entity.world.read().await;
                 let player_victim = world.get_player_by_id(entity_id.0).await;
@@ -1135,7 +1271,16 @@ impl JavaClientPlatform {
                     return;
                 }
                 if let Some(player_victim) = player_victim {
-                    if player_victim.living_entity.health.load() <= 0.0 {
+                    if !player
+                        .anti_cheat
+                        .check_entity_reach(player, &player_victim.living_entity.entity)
+                        .await
+                    {
+                        return;
+                    }
+                    if player_victim.living_entity.health.load() <= 0.0
+                        || player_victim.is_spectator()
+                    {
                         // You can trigger this from a non-modded / innocent client,
                         // so we shouldn't kick the player.
                         return;
@@ -1154,6 +1299,13 @@ impl JavaClientPlatform {
                     }
                     player.attack(player_victim).await;
                 } else if let Some(entity_victim) = world.get_entity_by_id(entity_id.0).await {
+                    if !player
+                        .anti_cheat
+                        .check_entity_reach(player, entity_victim.get_entity())
+                        .await
+                    {
+                        return;
+                    }
                     player.attack(entity_victim).await;
                 } else {
                     log::error!(
@@ -1168,7 +1320,37 @@ impl JavaClientPlatform {
                     .await;
                 }
             }
-            ActionType::Interact | ActionType::InteractAt => {
+            // `InteractAt` is only used for precise spots, like on armor stands
+            ActionType::Interact => {
+                if player.is_spectator() {
+                    return;
+                }
+                let Ok(hand) = Hand::try_from(interact.hand.map_or(0, |hand| hand.0)) else {
+                    self.kick(TextComponent::text("InvalidHand")).await;
+                    return;
+                };
+                let world = entity.world.read().await.clone();
+                let Some(target) = world.get_entity_by_id(interact.entity_id.0).await else {
+                    return;
+                };
+                if !player
+                    .anti_cheat
+                    .check_entity_reach(player, target.get_entity())
+                    .await
+                {
+                    return;
+                }
+                let item_in_hand = if hand == Hand::Left {
+                    player.inventory().held_item()
+                } else {
+                    player.inventory().off_hand_item().await
+                };
+                if leash::interact_with_lead(player, target.as_ref(), &item_in_hand).await {
+                    return;
+                }
+                target.on_interact(player, hand).await;
+            }
+            ActionType::InteractAt => {
                 log::debug!("todo");
             }
         }
@@ -1187,12 +1369,11 @@ impl JavaClientPlatform {
         match Status::try_from(player_action.status.0) {
             Ok(status) => match status {
                 Status::StartedDigging => {
-                    if !player.can_interact_with_block_at(&player_action.position, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            player.gameprofile.name,
-                            player_action.position
-                        );
+                    if !player
+                        .anti_cheat
+                        .check_block_reach(player, &player_action.position)
+                        .await
+                    {
                         return;
                     }
                     let position = player_action.position;
@@ -1255,6 +1436,7 @@ impl JavaClientPlatform {
                                 .block_registry
                                 .broken(world, block, player, &position, server, broken_state)
                                 .await;
+                            player.damage_tool_for_block(broken_state).await;
                         } else {
                             player
                                 .mining
@@ -1270,12 +1452,11 @@ impl JavaClientPlatform {
                     self.update_sequence(player, player_action.sequence.0);
                 }
                 Status::CancelledDigging => {
-                    if !player.can_interact_with_block_at(&player_action.position, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            player.gameprofile.name,
-                            player_action.position
-                        );
+                    if !player
+                        .anti_cheat
+                        .check_block_reach(player, &player_action.position)
+                        .await
+                    {
                         return;
                     }
                     player
@@ -1291,12 +1472,7 @@ impl JavaClientPlatform {
                 Status::FinishedDigging => {
                     // TODO: do validation
                     let location = player_action.position;
-                    if !player.can_interact_with_block_at(&location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            player.gameprofile.name,
-                            player_action.position
-                        );
+                    if !player.anti_cheat.check_block_reach(player, &location).await {
                         return;
                     }
 
@@ -1329,6 +1505,7 @@ impl JavaClientPlatform {
                         .block_registry
                         .broken(world, block, player, &location, server, state)
                         .await;
+                    player.damage_tool_for_block(state).await;
 
                     self.update_sequence(player, player_action.sequence.0);
                 }
@@ -1339,7 +1516,9 @@ impl JavaClientPlatform {
                     player.drop_held_item(true).await;
                 }
                 Status::ShootArrowOrFinishEating => {
-                    log::debug!("todo");
+                    // Also sent when the player lowers their shield
+                    player.blocking_hand.store(None);
+                    player.item_use.release(player).await;
                 }
                 Status::SwapItem => {
                     player.swap_item().await;
@@ -1402,7 +1581,7 @@ impl JavaClientPlatform {
     #[allow(clippy::too_many_lines)]
     pub async fn handle_use_item_on(
         &self,
-        player: &Player,
+        player: &Arc<Player>,
         use_item_on: SUseItemOn,
         server: &Arc<Server>,
     ) -> Result<(), Box<dyn PumpkinError>> {
@@ -1416,8 +1595,7 @@ impl JavaClientPlatform {
 
         let mut should_try_decrement = false;
 
-        if !player.can_interact_with_block_at(&position, 1.0) {
-            // TODO: maybe log?
+        if !player.anti_cheat.check_block_reach(player, &position).await {
             return Err(BlockPlacingError::BlockOutOfReach.into());
         }
 
@@ -1565,16 +1743,11 @@ impl JavaClientPlatform {
             return;
         }
 
-        let inventory = player.inventory();
         let Ok(hand) = Hand::try_from(use_item.hand.0) else {
             self.kick(TextComponent::text("InvalidHand")).await;
             return;
         };
-        let item_in_hand = if hand == Hand::Left {
-            inventory.held_item()
-        } else {
-            inventory.off_hand_item().await
-        };
+        let item_in_hand = player.item_in_hand(hand).await;
 
         let hit_result = player
             .world()
@@ -1616,8 +1789,18 @@ impl JavaClientPlatform {
                 let held = item_in_hand.lock().await;
                 let item = held.item;
                 drop(held);
-                server.item_registry.on_use(item, player).await;
                 self.update_sequence(player, use_item.sequence.0);
+                if player.item_cooldowns.is_on_cooldown(player, item).await {
+                    break 'after;
+                }
+                if item == &Item::SHIELD {
+                    player.blocking_hand.store(Some(hand));
+                }
+                // Food and bows are used over time and finish in the player's tick
+                if !player.item_use.start(player, hand, item).await {
+                    server.item_registry.on_use(item, player).await;
+                    player.item_cooldowns.apply(player, item).await;
+                }
             }
         }}
     }
@@ -1630,7 +1813,9 @@ impl JavaClientPlatform {
         }
         let inv = player.inventory();
         inv.set_selected_slot(slot as u8);
-        let stack = *inv.held_item().lock().await;
+        player.blocking_hand.store(None);
+        player.item_use.cancel(player).await;
+        let stack = inv.held_item().lock().await.clone();
         let equipment = &[(EquipmentSlot::MAIN_HAND, stack)];
         player.living_entity.send_equipment_changes(equipment).await;
     }
@@ -1654,7 +1839,7 @@ impl JavaClientPlatform {
             player_screen_handler
                 .get_slot(packet.slot as usize)
                 .await
-                .set_stack(item_stack)
+                .set_stack(item_stack.clone())
                 .await;
             player_screen_handler.set_received_stack(packet.slot as usize, item_stack);
             player_screen_handler.send_content_updates().await;
@@ -1684,7 +1869,6 @@ impl JavaClientPlatform {
     }
 
     pub async fn handle_command_suggestion(
-        &self,
         player: &Arc<Player>,
         packet: SCommandSuggestion,
         server: &Arc<Server>,
@@ -1709,7 +1893,7 @@ impl JavaClientPlatform {
             suggestions.into(),
         );
 
-        self.enqueue_packet(&response).await;
+        player.client.enqueue_packet(&response).await;
     }
 
     pub fn handle_cookie_response(&self, packet: &SPCookieResponse) {
@@ -1903,6 +2087,9 @@ impl JavaClientPlatform {
         // Check if there is a player in the way of the block being placed
         let shapes = get_state_by_state_id(new_state).get_block_collision_shapes();
         for player in world.get_nearby_players(location.0.to_f64(), 3.0).await {
+            if player.1.is_spectator() {
+                continue;
+            }
             let player_box = player.1.living_entity.entity.bounding_box.load();
             for shape in &shapes {
                 if shape.at_pos(final_block_pos).intersects(&player_box) {
@@ -1,5 +1,8 @@
+use std::sync::atomic::Ordering;
+
 use pumpkin_protocol::{
-    java::client::status::CPingResponse, java::server::status::SStatusPingRequest,
+    java::client::status::{CPingResponse, CStatusResponse},
+    java::server::status::SStatusPingRequest,
 };
 
 use crate::{net::java::JavaClientPlatform, server::Server};
@@ -7,9 +10,10 @@ use crate::{net::java::JavaClientPlatform, server::Server};
 impl JavaClientPlatform {
     pub async fn handle_status_request(&self, server: &Server) {
         log::debug!("Handling status request");
-        let status = server.get_status();
-        self.send_packet_now(&status.lock().await.get_status())
-            .await;
+        let protocol = self.protocol_version.load(Ordering::Relaxed);
+        let mut status = server.get_status().lock().await;
+        let json = status.get_status_json(protocol);
+        self.send_packet_now(&CStatusResponse::new(&json)).await;
     }
 
     pub async fn handle_ping_request(&self, ping_request: SStatusPingRequest) {
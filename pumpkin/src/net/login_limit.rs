@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// Counts the connections per IP which did not reach the play state yet.
+#[derive(Default)]
+pub struct LoginLimiter {
+    in_progress: Arc<Mutex<HashMap<IpAddr, u32>>>,
+}
+
+impl LoginLimiter {
+    /// Registers a new connection from the IP.
+    ///
+    /// Returns `None` if the IP already has `max` logins in progress.
+    #[must_use]
+    pub fn try_acquire(&self, ip: IpAddr, max: u32) -> Option<LoginPermit> {
+        let mut in_progress = self.in_progress.lock().unwrap();
+        let count = in_progress.entry(ip).or_insert(0);
+        if *count >= max {
+            return None;
+        }
+        *count += 1;
+        Some(LoginPermit {
+            ip,
+            in_progress: self.in_progress.clone(),
+        })
+    }
+}
+
+/// A login in progress, which is released when dropped.
+///
+/// Connections drop their permit once they enter the play state or disconnect.
+pub struct LoginPermit {
+    ip: IpAddr,
+    in_progress: Arc<Mutex<HashMap<IpAddr, u32>>>,
+}
+
+impl Drop for LoginPermit {
+    fn drop(&mut self) {
+        let mut in_progress = self.in_progress.lock().unwrap();
+        if let Some(count) = in_progress.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                in_progress.remove(&self.ip);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::LoginLimiter;
+
+    #[test]
+    fn limits_logins_per_ip() {
+        let limiter = LoginLimiter::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+        let first = limiter.try_acquire(ip, 2).unwrap();
+        let _second = limiter.try_acquire(ip, 2).unwrap();
+        assert!(limiter.try_acquire(ip, 2).is_none());
+        // Other IPs have their own limit
+        assert!(limiter.try_acquire(other_ip, 2).is_some());
+
+        // Finishing a login makes room for the next one
+        drop(first);
+        assert!(limiter.try_acquire(ip, 2).is_some());
+    }
+
+    #[test]
+    fn released_ips_are_forgotten() {
+        let limiter = LoginLimiter::default();
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        drop(limiter.try_acquire(ip, 1).unwrap());
+        assert!(limiter.in_progress.lock().unwrap().is_empty());
+    }
+}
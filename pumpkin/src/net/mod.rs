@@ -7,18 +7,17 @@ use std::{
 use crate::{
     data::{
         banned_ip_data::BANNED_IP_LIST, banned_player_data::BANNED_PLAYER_LIST,
-        op_data::OPERATOR_CONFIG, whitelist_data::WHITELIST_CONFIG,
+        whitelist_data::WHITELIST_CONFIG,
     },
     entity::player::{ChatMode, Hand},
     net::{bedrock::BedrockClientPlatform, java::JavaClientPlatform},
     server::Server,
 };
 
+use md5::{Digest, Md5};
 use pumpkin_protocol::{ClientPacket, Property};
 use pumpkin_util::{ProfileAction, text::TextComponent};
 use serde::Deserialize;
-use sha1::Digest;
-use sha2::Sha256;
 use simplelog::FormatItem;
 use tokio::task::JoinHandle;
 
@@ -28,6 +27,7 @@ pub mod authentication;
 pub mod bedrock;
 pub mod java;
 pub mod lan_broadcast;
+pub mod login_limit;
 mod proxy;
 pub mod query;
 pub mod rcon;
@@ -41,8 +41,14 @@ pub struct GameProfile {
     pub profile_actions: Option<Vec<ProfileAction>>,
 }
 
-pub fn offline_uuid(username: &str) -> Result<Uuid, uuid::Error> {
-    Uuid::from_slice(&Sha256::digest(username)[..16])
+impl GameProfile {
+    /// Derives the UUID vanilla assigns to a player when the server is in offline mode,
+    /// a name-based (version 3) UUID from the MD5 of `OfflinePlayer:<name>`.
+    #[must_use]
+    pub fn offline_uuid(name: &str) -> Uuid {
+        let hash = Md5::digest(format!("OfflinePlayer:{name}"));
+        uuid::Builder::from_md5_bytes(hash.into()).into_uuid()
+    }
 }
 
 /// Represents a player's configuration settings.
@@ -197,10 +203,9 @@ pub async fn can_not_join(
     drop(banned_players);
 
     if server.white_list.load(Ordering::Relaxed) {
-        let ops = OPERATOR_CONFIG.read().await;
         let whitelist = WHITELIST_CONFIG.read().await;
 
-        if ops.get_entry(&profile.id).is_none() && !whitelist.is_whitelisted(profile) {
+        if !whitelist.is_allowed(profile).await {
             return Some(TextComponent::translate(
                 "multiplayer.disconnect.not_whitelisted",
                 &[],
@@ -239,3 +244,18 @@ pub enum EncryptionError {
 fn is_valid_player_name(name: &str) -> bool {
     name.len() <= 16 && name.chars().all(|c| c > 32u8 as char && c < 127u8 as char)
 }
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::GameProfile;
+
+    #[test]
+    fn offline_uuid_matches_vanilla() {
+        assert_eq!(
+            GameProfile::offline_uuid("Player"),
+            Uuid::parse_str("a01e3843-e521-3998-958a-f459800e4d11").unwrap()
+        );
+    }
+}
@@ -4,7 +4,7 @@ use pumpkin_protocol::Property;
 use thiserror::Error;
 use tokio::sync::Mutex;
 
-use crate::net::{GameProfile, offline_uuid};
+use crate::net::GameProfile;
 
 #[derive(Error, Debug)]
 pub enum BungeeCordError {
@@ -14,8 +14,6 @@ pub enum BungeeCordError {
     FailedParseUUID,
     #[error("Failed to parse properties")]
     FailedParseProperties,
-    #[error("Failed to make offline UUID")]
-    FailedMakeOfflineUUID,
 }
 
 /// Attempts to login a player via `BungeeCord`.
@@ -48,7 +46,7 @@ pub async fn bungeecord_login(
     // The UUID of the player; only given if `ip_forward` on bungee is true.
     let id = match data.get(2) {
         Some(uuid) => uuid.parse().map_err(|_| BungeeCordError::FailedParseUUID)?,
-        None => offline_uuid(name.as_str()).map_err(|_| BungeeCordError::FailedMakeOfflineUUID)?,
+        None => GameProfile::offline_uuid(&name),
     };
 
     // Read properties and get textures.
@@ -1,19 +1,27 @@
-use std::{fs, path::Path, path::PathBuf, sync::Arc};
+use std::{fs, future::Future, path::Path, path::PathBuf, sync::Arc};
 
 use crate::command::client_suggestions;
+use crate::data::permission_data::save_permissions;
+use pumpkin_protocol::ConnectionState;
+use pumpkin_registry::VanillaDimensionType;
 use pumpkin_util::{
     PermissionLvl,
+    math::position::BlockPos,
     permission::{Permission, PermissionManager},
 };
-use tokio::sync::RwLock;
+use tokio::{sync::RwLock, task::JoinHandle};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 
 use crate::{
     entity::player::Player,
-    plugin::{EventHandler, HandlerMap, PluginManager, TypedEventHandler},
+    plugin::{CommandRegistry, EventHandler, HandlerMap, PluginManager, TypedEventHandler},
     server::Server,
 };
 
-use super::{Event, EventPriority, PluginMetadata};
+use super::{
+    Event, EventPriority, PluginMetadata,
+    packet::{PACKET_HOOKS, PacketDirection, PacketHook},
+};
 
 /// The `Context` struct represents the context of a plugin, containing metadata,
 /// a server reference, and event handlers.
@@ -22,12 +30,32 @@ use super::{Event, EventPriority, PluginMetadata};
 /// - `metadata`: Metadata of the plugin.
 /// - `server`: A reference to the server on which the plugin operates.
 /// - `handlers`: A map of event handlers, protected by a read-write lock for safe access across threads.
+/// - `tasks`: The tasks spawned by the plugin, cancelled and awaited when the plugin is unloaded.
+/// - `commands`: The commands registered by each plugin, unregistered when the plugin is unloaded.
 pub struct Context {
     metadata: PluginMetadata<'static>,
     pub server: Arc<Server>,
     pub handlers: Arc<RwLock<HandlerMap>>,
     pub plugin_manager: Arc<RwLock<PluginManager>>,
     pub permission_manager: Arc<RwLock<PermissionManager>>,
+    tasks: PluginTasks,
+    commands: Arc<RwLock<CommandRegistry>>,
+}
+
+/// Tracks the background tasks of a single plugin, so they can be cancelled on unload.
+#[derive(Clone, Default)]
+pub struct PluginTasks {
+    tracker: TaskTracker,
+    cancel_token: CancellationToken,
+}
+
+impl PluginTasks {
+    /// Cancels all tasks and waits for them to finish.
+    pub async fn shutdown(&self) {
+        self.cancel_token.cancel();
+        self.tracker.close();
+        self.tracker.wait().await;
+    }
 }
 impl Context {
     /// Creates a new instance of `Context`.
@@ -36,6 +64,7 @@ impl Context {
     /// - `metadata`: The metadata of the plugin.
     /// - `server`: A reference to the server.
     /// - `handlers`: A collection containing the event handlers.
+    /// - `commands`: The commands registered by each plugin.
     ///
     /// # Returns
     /// A new instance of `Context`.
@@ -46,6 +75,8 @@ impl Context {
         handlers: Arc<RwLock<HandlerMap>>,
         plugin_manager: Arc<RwLock<PluginManager>>,
         permission_manager: Arc<RwLock<PermissionManager>>,
+        tasks: PluginTasks,
+        commands: Arc<RwLock<CommandRegistry>>,
     ) -> Self {
         Self {
             metadata,
@@ -53,9 +84,29 @@ impl Context {
             handlers,
             plugin_manager,
             permission_manager,
+            tasks,
+            commands,
         }
     }
 
+    /// Spawns a background task owned by this plugin.
+    ///
+    /// The task is cancelled (dropped at its next `.await`) when the plugin gets unloaded or
+    /// reloaded, so plugins don't leave work running against a server that forgot about them.
+    pub fn spawn_task<F>(&self, task: F) -> JoinHandle<Option<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let cancel_token = self.tasks.cancel_token.clone();
+        self.tasks.tracker.spawn(async move {
+            tokio::select! {
+                () = cancel_token.cancelled() => None,
+                output = task => Some(output),
+            }
+        })
+    }
+
     /// Retrieves the data folder path for the plugin, creating it if it does not exist.
     ///
     /// # Returns
@@ -80,6 +131,23 @@ impl Context {
         self.server.get_player_by_name(&player_name).await
     }
 
+    /// Asynchronously retrieves the biome at a block position.
+    ///
+    /// # Arguments
+    /// - `dimension`: The dimension of the world to look in.
+    /// - `position`: The position of the block.
+    ///
+    /// # Returns
+    /// The resource location of the biome, like `minecraft:plains`, or `None` if the server has no world for the dimension.
+    pub async fn get_biome_at(
+        &self,
+        dimension: VanillaDimensionType,
+        position: &BlockPos,
+    ) -> Option<&'static str> {
+        let world = self.server.get_world_from_dimension(dimension).await?;
+        Some(world.get_biome_at(position).await)
+    }
+
     /// Asynchronously registers a command with the server.
     ///
     /// # Arguments
@@ -97,10 +165,17 @@ impl Context {
             format!("{plugin_name}:{permission_node}")
         };
 
+        let name = tree.names[0].clone();
         {
             let mut dispatcher_lock = self.server.command_dispatcher.write().await;
             dispatcher_lock.register(tree, full_permission_node.as_str());
         };
+        self.commands
+            .write()
+            .await
+            .entry(plugin_name.to_string())
+            .or_default()
+            .push(name);
 
         for world in self.server.worlds.read().await.iter() {
             for player in world.players.read().await.values() {
@@ -117,8 +192,11 @@ impl Context {
     pub async fn unregister_command(&self, name: &str) {
         {
             let mut dispatcher_lock = self.server.command_dispatcher.write().await;
-            dispatcher_lock.unregister(name);
+            dispatcher_lock.unregister_command(name);
         };
+        if let Some(commands) = self.commands.write().await.get_mut(self.metadata.name) {
+            commands.retain(|command| command != name);
+        }
 
         for world in self.server.worlds.read().await.iter() {
             for player in world.players.read().await.values() {
@@ -145,6 +223,28 @@ impl Context {
         registry.register_permission(permission)
     }
 
+    /// Assigns a permission to a player, which is kept across restarts.
+    pub async fn set_player_permission(&self, player_uuid: uuid::Uuid, node: &str, value: bool) {
+        let attachment = self
+            .permission_manager
+            .write()
+            .await
+            .get_attachment(player_uuid);
+        attachment.write().await.set_permission(node, value);
+        save_permissions(&self.permission_manager).await;
+    }
+
+    /// Removes a permission assigned to a player, falling back to the permission's default.
+    pub async fn unset_player_permission(&self, player_uuid: uuid::Uuid, node: &str) {
+        let attachment = self
+            .permission_manager
+            .write()
+            .await
+            .get_attachment(player_uuid);
+        attachment.write().await.unset_permission(node);
+        save_permissions(&self.permission_manager).await;
+    }
+
     /// Check if a player has a permission
     pub async fn player_has_permission(&self, player_uuid: &uuid::Uuid, permission: &str) -> bool {
         let permission_manager = self.permission_manager.read().await;
@@ -194,6 +294,24 @@ impl Context {
         handlers_vec.push(Box::new(typed_handler));
     }
 
+    /// Registers a hook for the raw packets with the given id on Java connections.
+    /// Hooks are removed when the plugin is unloaded.
+    ///
+    /// # Arguments
+    /// - `state`: The connection state the packet id belongs to.
+    /// - `direction`: Whether to hook packets sent by the client or by the server.
+    /// - `id`: The id of the packet to hook.
+    /// - `hook`: The hook, see [`PacketHook`] for when it runs.
+    pub fn register_packet_hook(
+        &self,
+        state: ConnectionState,
+        direction: PacketDirection,
+        id: i32,
+        hook: Arc<dyn PacketHook>,
+    ) {
+        PACKET_HOOKS.register(self.metadata.name, state, direction, id, hook);
+    }
+
     /// Registers a custom plugin loader that can load additional plugin types.
     ///
     /// This method allows plugins to extend the server with support for loading
@@ -1,4 +1,5 @@
 use pumpkin_macros::{Event, cancellable};
+use pumpkin_registry::VanillaDimensionType;
 use pumpkin_util::math::vector3::Vector3;
 use std::sync::Arc;
 
@@ -8,7 +9,8 @@ use super::PlayerEvent;
 
 /// An event that occurs when a player gets teleported to another world.
 ///
-/// This event contains information about the player changing worlds.
+/// This event contains information about the player changing worlds. It is fired before the
+/// player is moved, cancelling it keeps the player in the previous world.
 #[cancellable]
 #[derive(Event, Clone)]
 pub struct PlayerChangeWorldEvent {
@@ -62,6 +64,18 @@ impl PlayerChangeWorldEvent {
             cancelled: false,
         }
     }
+
+    /// The dimension the player is leaving.
+    #[must_use]
+    pub fn previous_dimension(&self) -> VanillaDimensionType {
+        self.previous_world.dimension_type
+    }
+
+    /// The dimension the player is entering.
+    #[must_use]
+    pub fn new_dimension(&self) -> VanillaDimensionType {
+        self.new_world.dimension_type
+    }
 }
 
 impl PlayerEvent for PlayerChangeWorldEvent {
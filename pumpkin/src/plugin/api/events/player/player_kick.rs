@@ -0,0 +1,73 @@
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::text::TextComponent;
+use std::sync::Arc;
+
+use crate::entity::player::Player;
+
+use super::PlayerEvent;
+
+/// Why a player is being kicked from the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KickCause {
+    /// An operator kicked the player, e.g. with `/kick`, `/ban` or by enforcing the whitelist.
+    Admin,
+    /// A plugin kicked the player.
+    Plugin,
+    /// The anti cheat caught the player cheating.
+    AntiCheat,
+    /// A plugin cancelled the player's login.
+    LoginCancelled,
+    /// The server or the player's world is shutting down, no event is fired for this.
+    Shutdown,
+}
+
+impl KickCause {
+    /// Whether plugins may veto a kick with this cause by cancelling the [`PlayerKickEvent`].
+    #[must_use]
+    pub const fn is_cancellable(self) -> bool {
+        matches!(self, Self::Admin | Self::Plugin)
+    }
+}
+
+/// An event that occurs before a player is kicked from the server.
+///
+/// Plugins can change the kick reason. Cancelling the event keeps the player on the server,
+/// but only for kicks whose [`KickCause::is_cancellable`].
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct PlayerKickEvent {
+    /// The player who is being kicked.
+    pub player: Arc<Player>,
+
+    /// The reason shown to the player.
+    pub reason: TextComponent,
+
+    /// Why the player is being kicked.
+    pub cause: KickCause,
+}
+
+impl PlayerKickEvent {
+    /// Creates a new instance of `PlayerKickEvent`.
+    ///
+    /// # Arguments
+    /// - `player`: A reference to the player being kicked.
+    /// - `reason`: The reason shown to the player.
+    /// - `cause`: Why the player is being kicked.
+    ///
+    /// # Returns
+    /// A new instance of `PlayerKickEvent`.
+    pub fn new(player: Arc<Player>, reason: TextComponent, cause: KickCause) -> Self {
+        Self {
+            player,
+            reason,
+            cause,
+            cancelled: false,
+        }
+    }
+}
+
+impl PlayerEvent for PlayerKickEvent {
+    fn get_player(&self) -> &Arc<Player> {
+        &self.player
+    }
+}
@@ -0,0 +1,51 @@
+use pumpkin_macros::{Event, cancellable};
+use pumpkin_util::text::TextComponent;
+
+/// An event that occurs when a title is about to be shown to every player on the server.
+///
+/// This event contains the title, the optional subtitle and the animation times.
+#[cancellable]
+#[derive(Event, Clone)]
+pub struct ServerTitleEvent {
+    /// The title being shown.
+    pub title: TextComponent,
+    /// The subtitle shown below the title, if any.
+    pub subtitle: Option<TextComponent>,
+    /// The ticks the title takes to fade in.
+    pub fade_in: i32,
+    /// The ticks the title stays on screen.
+    pub stay: i32,
+    /// The ticks the title takes to fade out.
+    pub fade_out: i32,
+}
+
+impl ServerTitleEvent {
+    /// Creates a new instance of `ServerTitleEvent`.
+    ///
+    /// # Arguments
+    /// - `title`: The title being shown.
+    /// - `subtitle`: The subtitle shown below the title, if any.
+    /// - `fade_in`: The ticks the title takes to fade in.
+    /// - `stay`: The ticks the title stays on screen.
+    /// - `fade_out`: The ticks the title takes to fade out.
+    ///
+    /// # Returns
+    /// A new instance of `ServerTitleEvent`.
+    #[must_use]
+    pub fn new(
+        title: TextComponent,
+        subtitle: Option<TextComponent>,
+        fade_in: i32,
+        stay: i32,
+        fade_out: i32,
+    ) -> Self {
+        Self {
+            title,
+            subtitle,
+            fade_in,
+            stay,
+            fade_out,
+            cancelled: false,
+        }
+    }
+}
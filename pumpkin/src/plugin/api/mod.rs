@@ -1,5 +1,6 @@
 pub mod context;
 pub mod events;
+pub mod packet;
 
 use async_trait::async_trait;
 pub use context::*;
@@ -11,7 +12,14 @@ pub use events::*;
 /// version, authors, and a description. It is generic over a lifetime `'s` to allow
 /// for string slices that are valid for the lifetime of the plugin metadata.
 #[derive(Debug, Clone)]
+#[repr(C)]
 pub struct PluginMetadata<'s> {
+    /// The version of the plugin API the plugin was built against, see
+    /// [`MAX_PLUGIN_API_VERSION`](crate::plugin::MAX_PLUGIN_API_VERSION).
+    ///
+    /// This is the first field so plugins built before it existed fail the version check instead
+    /// of being read with a mismatched layout.
+    pub api_version: &'s str,
     /// The name of the plugin.
     pub name: &'s str,
     /// The version of the plugin.
@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, LazyLock, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use pumpkin_protocol::{ConnectionState, RawPacket};
+
+use crate::net::java::JavaClientPlatform;
+
+/// The packet hooks of all plugins.
+pub static PACKET_HOOKS: LazyLock<PacketHooks> = LazyLock::new(PacketHooks::default);
+
+/// The direction a packet travels in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketDirection {
+    /// Sent by the client to the server.
+    Serverbound,
+    /// Sent by the server to the client.
+    Clientbound,
+}
+
+/// What should happen with a packet after a hook looked at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketAction {
+    /// Hand the packet to the next hook, then handle or send it as usual.
+    Pass,
+    /// Drop the packet, no further hooks see it.
+    Cancel,
+}
+
+/// A hook observing the raw packets of a specific id on Java connections.
+///
+/// Hooks are awaited inline: serverbound hooks run on the connection's packet task before the
+/// packet is handled, clientbound hooks run on the task sending the packet, before its body is
+/// framed, compressed and encrypted. A slow hook therefore stalls that connection, so move
+/// longer work into a task. Hooks can capture the server from their plugin's [`super::Context`]
+/// to touch it. Packets which are queued already serialized, like cached chunks, skip hooks.
+#[async_trait]
+pub trait PacketHook: Send + Sync {
+    /// Inspects the packet, which may be modified in place.
+    ///
+    /// # Arguments
+    /// - `client`: The connection the packet is sent to or received from.
+    /// - `packet`: The packet id and its body, without the length prefix.
+    ///
+    /// # Returns
+    /// Whether to pass or cancel the packet.
+    async fn intercept(&self, client: &JavaClientPlatform, packet: &mut RawPacket) -> PacketAction;
+}
+
+type HookKey = (ConnectionState, PacketDirection, i32);
+
+struct RegisteredHook {
+    plugin: String,
+    hook: Arc<dyn PacketHook>,
+}
+
+/// The packet hooks registered by plugins, by connection state, direction and packet id.
+#[derive(Default)]
+pub struct PacketHooks {
+    /// Set while any hook is registered, so connections without hooks skip the lookup.
+    active: AtomicBool,
+    hooks: RwLock<HashMap<HookKey, Vec<RegisteredHook>>>,
+}
+
+impl PacketHooks {
+    pub fn register(
+        &self,
+        plugin: &str,
+        state: ConnectionState,
+        direction: PacketDirection,
+        id: i32,
+        hook: Arc<dyn PacketHook>,
+    ) {
+        let mut hooks = self.hooks.write().unwrap();
+        hooks
+            .entry((state, direction, id))
+            .or_default()
+            .push(RegisteredHook {
+                plugin: plugin.to_string(),
+                hook,
+            });
+        self.active.store(true, Ordering::Relaxed);
+    }
+
+    /// Removes all hooks registered by a plugin.
+    pub fn unregister_plugin(&self, plugin: &str) {
+        let mut hooks = self.hooks.write().unwrap();
+        hooks.retain(|_, registered| {
+            registered.retain(|hook| hook.plugin != plugin);
+            !registered.is_empty()
+        });
+        self.active.store(!hooks.is_empty(), Ordering::Relaxed);
+    }
+
+    /// Whether any hook wants to see packets with this id.
+    pub fn is_hooked(&self, state: ConnectionState, direction: PacketDirection, id: i32) -> bool {
+        self.active.load(Ordering::Relaxed)
+            && self
+                .hooks
+                .read()
+                .unwrap()
+                .contains_key(&(state, direction, id))
+    }
+
+    /// Runs the hooks registered for the packet in registration order, until one cancels it.
+    pub async fn intercept(
+        &self,
+        client: &JavaClientPlatform,
+        direction: PacketDirection,
+        packet: &mut RawPacket,
+    ) -> PacketAction {
+        if !self.active.load(Ordering::Relaxed) {
+            return PacketAction::Pass;
+        }
+        let key = (client.connection_state.load(), direction, packet.id);
+        // Don't hold the lock while the hooks run, they may register other hooks
+        let hooks: Vec<_> = match self.hooks.read().unwrap().get(&key) {
+            Some(registered) => registered.iter().map(|hook| hook.hook.clone()).collect(),
+            None => return PacketAction::Pass,
+        };
+        for hook in hooks {
+            if hook.intercept(client, packet).await == PacketAction::Cancel {
+                return PacketAction::Cancel;
+            }
+        }
+        PacketAction::Pass
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use pumpkin_protocol::{ConnectionState, RawPacket};
+
+    use super::{PacketAction, PacketDirection, PacketHook, PacketHooks};
+    use crate::net::java::JavaClientPlatform;
+
+    struct CancelHook;
+
+    #[async_trait]
+    impl PacketHook for CancelHook {
+        async fn intercept(
+            &self,
+            _client: &JavaClientPlatform,
+            _packet: &mut RawPacket,
+        ) -> PacketAction {
+            PacketAction::Cancel
+        }
+    }
+
+    #[test]
+    fn hooks_are_removed_with_their_plugin() {
+        let hooks = PacketHooks::default();
+        let state = ConnectionState::Play;
+        hooks.register(
+            "a",
+            state,
+            PacketDirection::Serverbound,
+            5,
+            Arc::new(CancelHook),
+        );
+        hooks.register(
+            "b",
+            state,
+            PacketDirection::Clientbound,
+            5,
+            Arc::new(CancelHook),
+        );
+
+        assert!(hooks.is_hooked(state, PacketDirection::Serverbound, 5));
+        assert!(!hooks.is_hooked(state, PacketDirection::Serverbound, 6));
+        assert!(!hooks.is_hooked(ConnectionState::Config, PacketDirection::Serverbound, 5));
+
+        hooks.unregister_plugin("a");
+        assert!(!hooks.is_hooked(state, PacketDirection::Serverbound, 5));
+        assert!(hooks.is_hooked(state, PacketDirection::Clientbound, 5));
+
+        hooks.unregister_plugin("b");
+        assert!(!hooks.is_hooked(state, PacketDirection::Clientbound, 5));
+    }
+}
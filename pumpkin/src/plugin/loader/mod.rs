@@ -1,4 +1,7 @@
-use crate::plugin::api::{Plugin, PluginMetadata};
+use crate::plugin::{
+    MAX_PLUGIN_API_VERSION, MIN_PLUGIN_API_VERSION,
+    api::{Plugin, PluginMetadata},
+};
 use async_trait::async_trait;
 use std::{any::Any, path::Path};
 use thiserror::Error;
@@ -50,4 +53,69 @@ pub enum LoaderError {
 
     #[error("Invalid loader data")]
     InvalidLoaderData,
+
+    #[error(
+        "Plugin was built for plugin API {0}, but this server supports {MIN_PLUGIN_API_VERSION} to {MAX_PLUGIN_API_VERSION}. \
+        Update the plugin's `pumpkin` dependency to a release in that range and rebuild it, \
+        fixing any API changes the compiler reports"
+    )]
+    IncompatibleApiVersion(String),
+}
+
+/// Parses the `major.minor.patch` of a semver version, ignoring any pre-release or build metadata.
+fn parse_api_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version
+        .split_once('+')
+        .map_or(version, |(version, _)| version);
+    let version = version
+        .split_once('-')
+        .map_or(version, |(version, _)| version);
+    let mut parts = version.split('.').map(str::parse::<u64>);
+    let (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some((major, minor, patch))
+}
+
+/// Checks that a plugin built against the plugin API `api_version` can be loaded by this server.
+///
+/// The major version has to match the server's, and the version has to lie between
+/// [`MIN_PLUGIN_API_VERSION`] and [`MAX_PLUGIN_API_VERSION`].
+pub fn check_api_version(api_version: &str) -> Result<(), LoaderError> {
+    let (Some(min), Some(max)) = (
+        parse_api_version(MIN_PLUGIN_API_VERSION),
+        parse_api_version(MAX_PLUGIN_API_VERSION),
+    ) else {
+        unreachable!("The plugin API version bounds are valid semver");
+    };
+    match parse_api_version(api_version) {
+        Some(version) if version.0 == max.0 && (min..=max).contains(&version) => Ok(()),
+        _ => Err(LoaderError::IncompatibleApiVersion(api_version.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_api_version, parse_api_version};
+    use crate::plugin::{MAX_PLUGIN_API_VERSION, MIN_PLUGIN_API_VERSION};
+
+    #[test]
+    fn parses_semver() {
+        assert_eq!(parse_api_version("1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_api_version("0.1.0-dev+1.21.7"), Some((0, 1, 0)));
+        assert_eq!(parse_api_version("1.2"), None);
+        assert_eq!(parse_api_version("1.2.3.4"), None);
+        assert_eq!(parse_api_version("my_plugin"), None);
+    }
+
+    #[test]
+    fn checks_api_version() {
+        assert!(check_api_version(MIN_PLUGIN_API_VERSION).is_ok());
+        assert!(check_api_version(MAX_PLUGIN_API_VERSION).is_ok());
+        assert!(check_api_version("999.0.0").is_err());
+        assert!(check_api_version("0.0.1").is_err());
+        assert!(check_api_version("").is_err());
+    }
 }
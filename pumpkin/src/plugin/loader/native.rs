@@ -2,7 +2,9 @@ use std::any::Any;
 
 use libloading::Library;
 
-use super::{LoaderError, Path, Plugin, PluginLoader, PluginMetadata, async_trait};
+use super::{
+    LoaderError, Path, Plugin, PluginLoader, PluginMetadata, async_trait, check_api_version,
+};
 
 #[derive(Debug)]
 pub struct NativePluginLoader;
@@ -31,6 +33,8 @@ impl PluginLoader for NativePluginLoader {
                 .get::<*const PluginMetadata>(b"METADATA")
                 .map_err(|_| LoaderError::MetadataMissing)?
         };
+        // Nothing else of the plugin is touched until we know it was built against this API
+        check_api_version(metadata.api_version)?;
 
         let plugin = unsafe {
             library
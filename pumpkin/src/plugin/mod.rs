@@ -13,8 +13,14 @@ use tokio::sync::RwLock;
 pub mod api;
 pub mod loader;
 
-use crate::{PERMISSION_MANAGER, server::Server};
+/// The oldest plugin API version plugins can be built against and still be loaded.
+pub const MIN_PLUGIN_API_VERSION: &str = "0.1.0";
+/// The plugin API version of this server, which plugins built against it report.
+pub const MAX_PLUGIN_API_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+use crate::{PERMISSION_MANAGER, command::client_suggestions, server::Server};
 pub use api::*;
+use packet::PACKET_HOOKS;
 
 /// A trait for handling events dynamically.
 ///
@@ -128,12 +134,16 @@ where
 /// and the value is a vector of dynamic event handlers.
 type HandlerMap = HashMap<&'static str, Vec<Box<dyn DynEventHandler>>>;
 
+/// A type alias for a map of plugin names to the names of the commands they registered.
+type CommandRegistry = HashMap<String, Vec<String>>;
+
 /// Core plugin management system
 pub struct PluginManager {
     plugins: Vec<LoadedPlugin>,
     loaders: Vec<Arc<dyn PluginLoader>>,
     server: Option<Arc<Server>>,
     handlers: Arc<RwLock<HandlerMap>>,
+    commands: Arc<RwLock<CommandRegistry>>,
     unloaded_files: HashSet<PathBuf>,
     // Self-reference for sharing with contexts
     self_ref: Option<Arc<RwLock<PluginManager>>>,
@@ -148,6 +158,7 @@ struct LoadedPlugin {
     instance: Box<dyn Plugin>,
     loader: Arc<dyn PluginLoader>,
     loader_data: Box<dyn Any + Send + Sync>,
+    tasks: PluginTasks,
     is_active: bool,
 }
 
@@ -177,6 +188,7 @@ impl Default for PluginManager {
             loaders: vec![Arc::new(NativePluginLoader)],
             server: None,
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            commands: Arc::new(RwLock::new(HashMap::new())),
             unloaded_files: HashSet::new(),
             self_ref: None,
         }
@@ -208,6 +220,13 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Drops all registered event handlers.
+    ///
+    /// Used after unloading every plugin, so reloaded plugins don't end up registered twice.
+    pub async fn clear_handlers(&self) {
+        self.handlers.write().await.clear();
+    }
+
     /// Add a new plugin loader implementation
     pub async fn add_loader(&mut self, loader: Arc<dyn PluginLoader>) {
         self.loaders.push(loader);
@@ -318,18 +337,24 @@ impl PluginManager {
             .as_ref()
             .ok_or(ManagerError::ServerNotInitialized)?;
 
+        let tasks = PluginTasks::default();
         let context = Context::new(
             metadata.clone(),
             Arc::clone(server),
             Arc::clone(&self.handlers),
             Arc::clone(self_ref),
             Arc::clone(&PERMISSION_MANAGER),
+            tasks.clone(),
+            Arc::clone(&self.commands),
         );
 
         if let Err(e) = instance.on_load(&context).await {
             let data = loader_data;
             let loader = loader.clone();
             let _ = instance.on_unload(&context).await;
+            tasks.shutdown().await;
+            self.unregister_commands(server, metadata.name).await;
+            PACKET_HOOKS.unregister_plugin(metadata.name);
             tokio::spawn(async move {
                 loader.unload(data).await.ok();
             });
@@ -343,6 +368,7 @@ impl PluginManager {
             instance,
             loader: loader.clone(),
             loader_data,
+            tasks,
             is_active: true,
         })
     }
@@ -403,9 +429,21 @@ impl PluginManager {
             Arc::clone(&self.handlers),
             Arc::clone(self_ref),
             Arc::clone(&PERMISSION_MANAGER),
+            plugin.tasks.clone(),
+            Arc::clone(&self.commands),
         );
 
         plugin.instance.on_unload(&context).await.ok();
+        plugin.tasks.shutdown().await;
+        self.unregister_commands(server, plugin.metadata.name).await;
+        PACKET_HOOKS.unregister_plugin(plugin.metadata.name);
+        PERMISSION_MANAGER
+            .read()
+            .await
+            .registry
+            .write()
+            .await
+            .unregister_namespace(plugin.metadata.name);
 
         if plugin.loader.can_unload() {
             plugin.loader.unload(plugin.loader_data).await?;
@@ -417,6 +455,28 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Removes the commands a plugin registered from the dispatcher and resends the command
+    /// tree to all players, so their tab-completion doesn't suggest them anymore.
+    async fn unregister_commands(&self, server: &Server, plugin_name: &str) {
+        let Some(names) = self.commands.write().await.remove(plugin_name) else {
+            return;
+        };
+
+        let mut dispatcher = server.command_dispatcher.write().await;
+        let mut removed = false;
+        for name in names {
+            removed |= dispatcher.unregister_command(&name);
+        }
+        if !removed {
+            return;
+        }
+
+        let dispatcher = dispatcher.downgrade();
+        for player in server.get_all_players().await {
+            client_suggestions::send_c_commands_packet(&player, &dispatcher).await;
+        }
+    }
+
     /// Register an event handler
     pub async fn register<E, H>(&self, handler: Arc<H>, priority: EventPriority, blocking: bool)
     where
@@ -1,25 +1,48 @@
 use super::CURRENT_MC_VERSION;
 use crate::entity::player::Player;
+use crate::net::java::handshake::accepted_protocols;
 use base64::{Engine as _, engine::general_purpose};
 use core::error;
-use pumpkin_config::{BASIC_CONFIG, BasicConfiguration};
+use pumpkin_config::{BASIC_CONFIG, BasicConfiguration, StatusConfig, advanced_config};
 use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
 use pumpkin_protocol::{
-    Players, StatusResponse, Version,
-    codec::var_int::VarInt,
-    java::client::{config::CPluginMessage, status::CStatusResponse},
+    Players, Sample, StatusResponse, Version, codec::var_int::VarInt,
+    java::client::config::CPluginMessage,
 };
-use std::{fs::File, io::Read, path::Path};
+use std::{borrow::Cow, fs::File, io::Read, path::Path};
+use uuid::Uuid;
 
-const DEFAULT_ICON: &[u8] = include_bytes!("../../../assets/default_icon.png");
+/// The size in pixels the client expects the server icon to have
+const ICON_SIZE: u32 = 64;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// The maximum number of entries in the player sample, like vanilla
+const MAX_SAMPLE_SIZE: usize = 12;
 
 fn load_icon_from_file<P: AsRef<Path>>(path: P) -> Result<String, Box<dyn error::Error>> {
     let mut icon_file = File::open(path)?;
     let mut buf = Vec::new();
     icon_file.read_to_end(&mut buf)?;
+    let (width, height) = png_dimensions(&buf).ok_or("not a PNG image")?;
+    if width != ICON_SIZE || height != ICON_SIZE {
+        return Err(
+            format!("must be {ICON_SIZE}x{ICON_SIZE} pixels, but is {width}x{height}").into(),
+        );
+    }
     Ok(load_icon_from_bytes(&buf))
 }
 
+/// Reads the width and height from the header chunk of a PNG image.
+fn png_dimensions(png_data: &[u8]) -> Option<(u32, u32)> {
+    // The signature is followed by the length and the type of the IHDR chunk
+    let header = png_data.strip_prefix(PNG_SIGNATURE)?.get(8..16)?;
+    if png_data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(header[..4].try_into().ok()?);
+    let height = u32::from_be_bytes(header[4..].try_into().ok()?);
+    Some((width, height))
+}
+
 fn load_icon_from_bytes(png_data: &[u8]) -> String {
     assert!(!png_data.is_empty(), "PNG data is empty");
     let mut result = "data:image/png;base64,".to_owned();
@@ -27,8 +50,42 @@ fn load_icon_from_bytes(png_data: &[u8]) -> String {
     result
 }
 
+/// Turns `&` followed by a formatting code into a `§` color code, e.g. `&6Pumpkin` into `§6Pumpkin`.
+fn translate_color_codes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '&'
+            && chars
+                .peek()
+                .is_some_and(|code| "0123456789abcdefklmnor".contains(code.to_ascii_lowercase()))
+        {
+            result.push('§');
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn build_motd(config: &BasicConfiguration, status_config: &StatusConfig) -> String {
+    if status_config.motd.is_empty() {
+        return config.motd.clone();
+    }
+    status_config
+        .motd
+        .iter()
+        .map(|line| translate_color_codes(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub struct CachedStatus {
     status_response: StatusResponse,
+    /// The number of players on the server, including the ones which disabled server listing
+    online: u32,
+    /// The players which allow being shown in the player sample
+    listed_players: Vec<(String, Uuid)>,
     // We cache the json response here so we don't parse it every time someone makes a status request.
     // Keep in mind that we must parse this again when the StatusResponse changes, which usually happen when a player joins or leaves.
     status_response_json: String,
@@ -70,91 +127,163 @@ impl CachedStatus {
         Self {
             status_response,
             status_response_json,
+            online: 0,
+            listed_players: Vec::new(),
         }
     }
 
-    pub fn get_status(&self) -> CStatusResponse<'_> {
-        CStatusResponse::new(&self.status_response_json)
+    /// The status JSON for a client with the `protocol` version from its handshake.
+    ///
+    /// Accepted clients get their own protocol back, so every accepted version shows up as
+    /// compatible. The others are told whether they or the server are outdated.
+    pub fn get_status_json(&mut self, protocol: i32) -> Cow<'_, str> {
+        let Some(version) = Self::version_for(protocol) else {
+            return Cow::Borrowed(&self.status_response_json);
+        };
+        let cached = self.status_response.version.replace(version);
+        let json = serde_json::to_string(&self.status_response)
+            .expect("Failed to parse status response into JSON");
+        self.status_response.version = cached;
+        Cow::Owned(json)
     }
 
-    // TODO: Player samples
-    pub fn add_player(&mut self, _player: &Player) {
-        let status_response = &mut self.status_response;
-        if let Some(players) = &mut status_response.players {
-            // TODO
-            // if player
-            //     .client
-            //     .added_to_server_listing
-            //     .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-            //     .is_ok()
-            // {
-            players.online += 1;
-            // }
+    /// The version shown to a client, or `None` if it's the cached one.
+    fn version_for(protocol: i32) -> Option<Version> {
+        let status_config = &advanced_config().networking.status;
+        if status_config.protocol.is_some() || protocol == CURRENT_MC_PROTOCOL as i32 {
+            return None;
+        }
+        let accepted = accepted_protocols();
+        if accepted.contains(&protocol) {
+            return Some(Version {
+                name: status_config
+                    .version_name
+                    .clone()
+                    .unwrap_or_else(|| CURRENT_MC_VERSION.into()),
+                protocol: protocol as u32,
+            });
         }
+        let name = status_config.version_name.clone().unwrap_or_else(|| {
+            if protocol < *accepted.start() {
+                format!("Outdated client! Please use {CURRENT_MC_VERSION}")
+            } else {
+                format!("Outdated server! Still on {CURRENT_MC_VERSION}")
+            }
+        });
+        Some(Version {
+            name,
+            protocol: CURRENT_MC_PROTOCOL,
+        })
+    }
 
-        self.status_response_json = serde_json::to_string(&status_response)
-            .expect("Failed to parse status response into JSON");
+    /// Counts a player joining the server, `listed` players also show up in the player sample.
+    pub fn add_player(&mut self, player: &Player, listed: bool) {
+        self.online += 1;
+        if listed {
+            self.listed_players
+                .push((player.gameprofile.name.clone(), player.gameprofile.id));
+        }
+        self.update_players();
+    }
+
+    pub fn remove_player(&mut self, player: &Player) {
+        self.online = self.online.saturating_sub(1);
+        self.listed_players
+            .retain(|(_, id)| *id != player.gameprofile.id);
+        self.update_players();
     }
 
-    pub fn remove_player(&mut self, _player: &Player) {
-        let status_response = &mut self.status_response;
-        if let Some(players) = &mut status_response.players {
-            // TODO
-            // if player
-            //     .client
-            //     .added_to_server_listing
-            //     .compare_exchange(true, false, Ordering::Acquire, Ordering::Relaxed)
-            //     .is_ok()
-            // {
-            players.online -= 1;
-            // }
+    fn update_players(&mut self) {
+        if let Some(players) = &mut self.status_response.players {
+            let status_config = &advanced_config().networking.status;
+            players.online = self.online + status_config.extra_online_players;
+            players.sample = Self::build_sample(status_config, &self.listed_players);
         }
 
-        self.status_response_json = serde_json::to_string(&status_response)
+        self.status_response_json = serde_json::to_string(&self.status_response)
             .expect("Failed to parse status response into JSON");
     }
 
-    pub fn build_response(config: &BasicConfiguration) -> StatusResponse {
-        let favicon = if config.use_favicon {
-            let icon_path = &config.favicon_path;
-            log::debug!("Attempting to load server favicon from '{icon_path}'");
-
-            match load_icon_from_file(icon_path) {
-                Ok(icon) => Some(icon),
-                Err(e) => {
-                    let error_message = e.downcast_ref::<std::io::Error>().map_or_else(
-                        || format!("other error: {e}; using default."),
-                        |io_err| {
-                            if io_err.kind() == std::io::ErrorKind::NotFound {
-                                "not found; using default.".to_string()
-                            } else {
-                                format!("I/O error: {io_err}; using default.")
-                            }
-                        },
-                    );
-                    log::warn!("Failed to load favicon from '{icon_path}': {error_message}");
-
-                    // Attempt to load default icon
-                    Some(load_icon_from_bytes(DEFAULT_ICON))
-                }
-            }
-        } else {
+    fn build_sample(
+        status_config: &StatusConfig,
+        listed_players: &[(String, Uuid)],
+    ) -> Vec<Sample> {
+        let online_players = listed_players
+            .iter()
+            .filter(|_| status_config.show_online_players)
+            .map(|(name, id)| Sample {
+                name: name.clone(),
+                id: id.to_string(),
+            });
+        let extra_lines = status_config.sample.iter().map(|line| Sample {
+            name: translate_color_codes(line),
+            id: Uuid::nil().to_string(),
+        });
+        online_players
+            .chain(extra_lines)
+            .take(MAX_SAMPLE_SIZE)
+            .collect()
+    }
+
+    /// Loads the server icon of the given configuration from disk again, e.g. after it was
+    /// replaced or the configuration was reloaded.
+    pub fn reload_icon(&mut self, config: &BasicConfiguration) {
+        self.status_response.favicon = Self::load_icon(config);
+        self.status_response_json = serde_json::to_string(&self.status_response)
+            .expect("Failed to parse status response into JSON");
+    }
+
+    /// Shows the MOTD of the given configuration from now on, e.g. after a reload.
+    pub fn reload_motd(&mut self, config: &BasicConfiguration) {
+        self.status_response.description = build_motd(config, &advanced_config().networking.status);
+        self.status_response_json = serde_json::to_string(&self.status_response)
+            .expect("Failed to parse status response into JSON");
+    }
+
+    fn load_icon(config: &BasicConfiguration) -> Option<String> {
+        if !config.use_favicon {
             log::info!("Favicon usage is disabled.");
-            None
-        };
+            return None;
+        }
 
+        let icon_path = &config.server_icon_path;
+        log::debug!("Attempting to load server icon from '{icon_path}'");
+        match load_icon_from_file(icon_path) {
+            Ok(icon) => Some(icon),
+            Err(e) => {
+                let error_message = e.downcast_ref::<std::io::Error>().map_or_else(
+                    || e.to_string(),
+                    |io_err| {
+                        if io_err.kind() == std::io::ErrorKind::NotFound {
+                            "not found".to_string()
+                        } else {
+                            format!("I/O error: {io_err}")
+                        }
+                    },
+                );
+                log::warn!("Failed to load server icon from '{icon_path}': {error_message}");
+                None
+            }
+        }
+    }
+
+    pub fn build_response(config: &BasicConfiguration) -> StatusResponse {
+        let status_config = &advanced_config().networking.status;
         StatusResponse {
             version: Some(Version {
-                name: CURRENT_MC_VERSION.into(),
-                protocol: CURRENT_MC_PROTOCOL,
+                name: status_config
+                    .version_name
+                    .clone()
+                    .unwrap_or_else(|| CURRENT_MC_VERSION.into()),
+                protocol: status_config.protocol.unwrap_or(CURRENT_MC_PROTOCOL),
             }),
-            players: Some(Players {
+            players: (!status_config.hide_players).then(|| Players {
                 max: config.max_players,
-                online: 0,
-                sample: vec![],
+                online: status_config.extra_online_players,
+                sample: Self::build_sample(status_config, &[]),
             }),
-            description: config.motd.clone(),
-            favicon,
+            description: build_motd(config, status_config),
+            favicon: Self::load_icon(config),
             // This should stay true even when reports are disabled.
             // It prevents the annoying popup when joining the server.
             enforce_secure_chat: true,
@@ -167,3 +296,43 @@ impl Default for CachedStatus {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::BasicConfiguration;
+
+    use super::{CachedStatus, png_dimensions, translate_color_codes};
+
+    #[test]
+    fn translates_color_codes() {
+        assert_eq!(
+            translate_color_codes("&6Pumpkin &lServer"),
+            "§6Pumpkin §lServer"
+        );
+        assert_eq!(translate_color_codes("Fish & Chips&"), "Fish & Chips&");
+    }
+
+    #[test]
+    fn reads_png_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&64u32.to_be_bytes());
+        png.extend_from_slice(&32u32.to_be_bytes());
+        assert_eq!(png_dimensions(&png), Some((64, 32)));
+        assert_eq!(png_dimensions(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn reloads_motd() {
+        let mut status = CachedStatus::new();
+        let config = BasicConfiguration {
+            motd: "Reloaded".to_string(),
+            ..Default::default()
+        };
+        status.reload_motd(&config);
+        assert!(
+            status
+                .get_status_json(0)
+                .contains("\"description\":\"Reloaded\"")
+        );
+    }
+}
@@ -1,28 +1,49 @@
 use crate::block::registry::BlockRegistry;
-use crate::command::commands::default_dispatcher;
+use crate::command::CommandSender;
+use crate::command::client_suggestions;
 use crate::command::commands::defaultgamemode::DefaultGamemode;
+use crate::command::commands::{builtin_dispatcher, default_dispatcher};
+use crate::data::LoadJSONConfiguration;
+use crate::data::permission_data::{load_permissions, save_permissions};
 use crate::data::player_server_data::ServerPlayerData;
-use crate::entity::NBTStorage;
+use crate::data::whitelist_data::{WHITELIST_CONFIG, WhitelistConfig};
+use crate::entity::{EntityBase, NBTStorage};
 use crate::item::registry::ItemRegistry;
+use crate::net::login_limit::LoginLimiter;
 use crate::net::{ClientPlatform, EncryptionError, GameProfile, PlayerConfig};
+use crate::plugin::player::player_kick::KickCause;
 use crate::plugin::player::player_login::PlayerLoginEvent;
 use crate::plugin::server::server_broadcast::ServerBroadcastEvent;
+use crate::plugin::server::server_title::ServerTitleEvent;
 use crate::server::tick_rate_manager::ServerTickRateManager;
+use crate::server::tps::TpsAverages;
+use crate::world::chunker;
 use crate::world::custom_bossbar::CustomBossbars;
-use crate::{command::dispatcher::CommandDispatcher, entity::player::Player, world::World};
+use crate::world::map::{MapState, MapStore};
+use crate::world::scoreboard::Scoreboard;
+use crate::{
+    PERMISSION_MANAGER, PLUGIN_MANAGER, command::dispatcher::CommandDispatcher,
+    entity::player::Player, world::World,
+};
 use connection_cache::{CachedBranding, CachedStatus};
 use key_store::KeyStore;
-use pumpkin_config::{BASIC_CONFIG, advanced_config};
+use pumpkin_config::{
+    AdvancedConfiguration, BASIC_CONFIG, BasicConfiguration, advanced_config, changed_settings,
+};
 
 use pumpkin_macros::send_cancellable;
 use pumpkin_protocol::java::client::login::CEncryptionRequest;
-use pumpkin_protocol::java::client::play::CChangeDifficulty;
+use pumpkin_protocol::java::client::play::{
+    CChangeDifficulty, CSetTitleAnimationTimes, CSubtitle, CTitleText,
+};
 use pumpkin_protocol::{ClientPacket, java::client::config::CPluginMessage};
 use pumpkin_registry::{Registry, VanillaDimensionType};
 use pumpkin_util::Difficulty;
 use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::text::TextComponent;
 use pumpkin_world::dimension::Dimension;
+use pumpkin_world::generation::settings::GenerationSettings;
+use pumpkin_world::level::Level;
 use pumpkin_world::lock::LevelLocker;
 use pumpkin_world::lock::anvil::AnvilLevelLocker;
 use pumpkin_world::world_info::anvil::{
@@ -31,9 +52,11 @@ use pumpkin_world::world_info::anvil::{
 use pumpkin_world::world_info::{LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter};
 use rand::seq::IndexedRandom;
 use rsa::RsaPublicKey;
-use std::fs;
+use std::collections::HashMap;
 use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32};
+use std::{env, fs};
 use std::{
     future::Future,
     sync::{Arc, atomic::Ordering},
@@ -48,10 +71,16 @@ mod key_store;
 pub mod seasonal_events;
 pub mod tick_rate_manager;
 pub mod ticker;
+pub mod tps;
+pub mod watchdog;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.7";
 pub const CURRENT_BEDROCK_MC_VERSION: &str = "1.21.93";
 
+/// The world each online player is in, by their UUID. Shared with the players, which update it
+/// when they change worlds.
+pub type WorldLookup = Arc<RwLock<HashMap<uuid::Uuid, Arc<World>>>>;
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -68,8 +97,10 @@ pub struct Server {
     pub item_registry: Arc<ItemRegistry>,
     /// Manages multiple worlds within the server.
     pub worlds: RwLock<Vec<Arc<World>>>,
+    /// The world each online player is in, so it doesn't have to be searched for.
+    world_lookup: WorldLookup,
     /// All the dimensions that exist on the server.
-    pub dimensions: Vec<VanillaDimensionType>,
+    pub dimensions: RwLock<Vec<VanillaDimensionType>>,
     /// Caches game registries for efficient access.
     pub cached_registry: Vec<Registry>,
     /// Assigns unique IDs to containers.
@@ -83,14 +114,29 @@ pub struct Server {
     pub defaultgamemode: Mutex<DefaultGamemode>,
     /// Manages player data storage
     pub player_data_storage: ServerPlayerData,
+    /// The filled maps of the server
+    pub maps: MapStore,
+    /// The objectives, scores and teams, shared by all worlds
+    pub scoreboard: Mutex<Scoreboard>,
     // Whether the server whitelist is on or off
     pub white_list: AtomicBool,
+    /// Counts the logins in progress per IP
+    pub login_limiter: LoginLimiter,
+    /// The senders of `/reload`, the reload itself happens before the next tick
+    reload_requests: Mutex<Vec<CommandSender>>,
+    /// The configuration applied by the last `/reload`, `None` until the first one, when the
+    /// configuration from startup applies
+    reloaded_config: Mutex<Option<(BasicConfiguration, AdvancedConfiguration)>>,
+    /// Whether an autosave is running in the background
+    autosaving: AtomicBool,
     /// Manages the server's tick rate, freezing, and sprinting
     pub tick_rate_manager: Arc<ServerTickRateManager>,
     /// Stores the duration of the last 100 ticks for performance analysis
     pub tick_times_nanos: Mutex<[i64; 100]>,
     /// Aggregated tick times for efficient rolling average calculation
     pub aggregated_tick_times_nanos: AtomicI64,
+    /// Moving averages of the ticks per second over 1, 5 and 15 minutes
+    tps_averages: TpsAverages,
     /// Total number of ticks processed by the server
     pub tick_count: AtomicI32,
     /// Random unique Server ID used by Bedrock Edition
@@ -109,35 +155,14 @@ impl Server {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub async fn new() -> Self {
+        load_permissions(&PERMISSION_MANAGER).await;
         // First register the default commands. After that, plugins can put in their own.
         let command_dispatcher = RwLock::new(default_dispatcher().await);
         let world_path = BASIC_CONFIG.get_world_path();
 
         let block_registry = super::block::default_registry();
 
-        let level_info = AnvilLevelInfo.read_world_info(&world_path);
-        if let Err(error) = &level_info {
-            match error {
-                // If it doesn't exist, just make a new one
-                WorldInfoError::InfoNotFound => (),
-                WorldInfoError::UnsupportedVersion(version) => {
-                    log::error!("Failed to load world info!, {version}");
-                    log::error!("{error}");
-                    panic!("Unsupported world data! See the logs for more info.");
-                }
-                e => {
-                    panic!("World Error {e}");
-                }
-            }
-        } else {
-            let dat_path = world_path.join(LEVEL_DAT_FILE_NAME);
-            if dat_path.exists() {
-                let backup_path = world_path.join(LEVEL_DAT_BACKUP_FILE_NAME);
-                fs::copy(dat_path, backup_path).unwrap();
-            }
-        }
-
-        let level_info = level_info.unwrap_or_default(); // TODO: Improve error handling
+        let level_info = Self::load_level_info(&world_path);
         let seed = level_info.world_gen_settings.seed;
         log::info!("Loading Overworld: {seed}");
         let overworld = World::load(
@@ -171,12 +196,13 @@ impl Server {
             cached_registry: Registry::get_synced(),
             container_id: 0.into(),
             worlds: RwLock::new(vec![Arc::new(overworld), Arc::new(nether), Arc::new(end)]),
-            dimensions: vec![
+            world_lookup: Arc::new(RwLock::new(HashMap::new())),
+            dimensions: RwLock::new(vec![
                 VanillaDimensionType::Overworld,
                 VanillaDimensionType::OverworldCaves,
                 VanillaDimensionType::TheNether,
                 VanillaDimensionType::TheEnd,
-            ],
+            ]),
             command_dispatcher,
             block_registry,
             item_registry: super::item::items::default_registry(),
@@ -191,10 +217,17 @@ impl Server {
                 format!("{world_name}/playerdata"),
                 Duration::from_secs(advanced_config().player_data.save_player_cron_interval),
             ),
+            maps: MapStore::new(format!("{world_name}/data")),
+            scoreboard: Mutex::new(Scoreboard::load(&world_path.join("data"))),
             white_list: AtomicBool::new(BASIC_CONFIG.white_list),
+            login_limiter: LoginLimiter::default(),
+            reload_requests: Mutex::new(Vec::new()),
+            reloaded_config: Mutex::new(None),
+            autosaving: AtomicBool::new(false),
             tick_rate_manager: Arc::new(ServerTickRateManager::default()),
             tick_times_nanos: Mutex::new([0; 100]),
             aggregated_tick_times_nanos: AtomicI64::new(0),
+            tps_averages: TpsAverages::new(BASIC_CONFIG.tps),
             tick_count: AtomicI32::new(0),
             tasks: TaskTracker::new(),
             server_guid: rand::random(),
@@ -227,16 +260,124 @@ impl Server {
         self.tasks.spawn(task)
     }
 
-    pub async fn get_world_from_dimension(&self, dimension: VanillaDimensionType) -> Arc<World> {
-        // TODO: this is really bad
-        let world_guard = self.worlds.read().await;
-        let world = match dimension {
-            VanillaDimensionType::Overworld => world_guard.first(),
-            VanillaDimensionType::OverworldCaves => todo!(),
-            VanillaDimensionType::TheEnd => world_guard.get(2),
-            VanillaDimensionType::TheNether => world_guard.get(1),
+    /// Returns the world of the given dimension, or `None` if the server has no world for it.
+    pub async fn get_world_from_dimension(
+        &self,
+        dimension: VanillaDimensionType,
+    ) -> Option<Arc<World>> {
+        self.worlds
+            .read()
+            .await
+            .iter()
+            .find(|world| world.dimension_type == dimension)
+            .cloned()
+    }
+
+    /// Creates a world for the dimension, generated with the given settings, and adds it to the
+    /// server. Plugins can call this from `on_enable`.
+    ///
+    /// If the dimension already has a world, that world is returned instead.
+    /// Only vanilla dimension types can be registered, custom resource locations would require
+    /// extending [`VanillaDimensionType`].
+    pub async fn register_dimension(
+        &self,
+        dimension: VanillaDimensionType,
+        settings: GenerationSettings,
+    ) -> Arc<World> {
+        let mut worlds = self.worlds.write().await;
+        if let Some(world) = worlds
+            .iter()
+            .find(|world| world.dimension_type == dimension)
+        {
+            log::warn!(
+                "Dimension {} is already registered",
+                dimension.resource_location()
+            );
+            return world.clone();
+        }
+
+        // Worlds live until the server stops, so leaking the settings is fine
+        let settings: &'static GenerationSettings = Box::leak(Box::new(settings));
+        let level_info = self.level_info.read().await.clone();
+        let (generator, folder) = match dimension {
+            VanillaDimensionType::Overworld => (Dimension::Overworld, PathBuf::new()),
+            VanillaDimensionType::OverworldCaves => (
+                Dimension::Overworld,
+                ["dimensions", "minecraft", "overworld_caves"]
+                    .iter()
+                    .collect(),
+            ),
+            VanillaDimensionType::TheNether => (Dimension::Nether, PathBuf::from("DIM-1")),
+            VanillaDimensionType::TheEnd => (Dimension::End, PathBuf::from("DIM1")),
+        };
+        log::info!(
+            "Loading {}: {}",
+            dimension.resource_location(),
+            level_info.world_gen_settings.seed
+        );
+        let level = Level::with_generation_settings(
+            BASIC_CONFIG.get_world_path().join(folder),
+            self.block_registry.clone(),
+            level_info.world_gen_settings.seed,
+            generator,
+            settings,
+        );
+        let world = Arc::new(World::load(
+            level,
+            level_info,
+            dimension,
+            self.block_registry.clone(),
+        ));
+        worlds.push(world.clone());
+        drop(worlds);
+
+        let mut dimensions = self.dimensions.write().await;
+        if !dimensions.contains(&dimension) {
+            dimensions.push(dimension);
+        }
+        world
+    }
+
+    /// Removes the dimension's world from the server, saving and shutting it down. Plugins can
+    /// call this from `on_disable`.
+    ///
+    /// Players in the world are moved to the spawn of the first remaining world.
+    pub async fn unregister_dimension(&self, dimension: VanillaDimensionType) {
+        let mut worlds = self.worlds.write().await;
+        let Some(index) = worlds
+            .iter()
+            .position(|world| world.dimension_type == dimension)
+        else {
+            log::warn!(
+                "Dimension {} is not registered",
+                dimension.resource_location()
+            );
+            return;
         };
-        world.cloned().unwrap()
+        let world = worlds.remove(index);
+        let fallback = worlds.first().cloned();
+        drop(worlds);
+        self.dimensions.write().await.retain(|d| *d != dimension);
+
+        let players: Vec<_> = world.players.read().await.values().cloned().collect();
+        for player in players {
+            match &fallback {
+                Some(fallback) => {
+                    player
+                        .teleport_world(fallback.clone(), None, None, None)
+                        .await;
+                }
+                None => {
+                    player
+                        .kick(
+                            KickCause::Shutdown,
+                            TextComponent::translate("multiplayer.disconnect.server_shutdown", []),
+                        )
+                        .await;
+                }
+            }
+        }
+        world.shutdown().await;
     }
 
     #[allow(clippy::if_then_some_else_none)]
@@ -275,13 +416,15 @@ impl Server {
 
         let (world, nbt) = if let Ok(Some(data)) = self.player_data_storage.load_data(&profile.id) {
             if let Some(dimension_key) = data.get_string("Dimension") {
-                if let Some(dimension) =
-                    VanillaDimensionType::from_resource_location_string(dimension_key)
+                let world = match VanillaDimensionType::from_resource_location_string(dimension_key)
                 {
-                    let world = self.get_world_from_dimension(dimension).await;
+                    Some(dimension) => self.get_world_from_dimension(dimension).await,
+                    None => None,
+                };
+                if let Some(world) = world {
                     (world, Some(data))
                 } else {
-                    log::warn!("Invalid dimension key in player data: {dimension_key}");
+                    log::warn!("Invalid or unloaded dimension in player data: {dimension_key}");
                     let default_world_guard = self.worlds.read().await;
                     let default_world = default_world_guard
                         .first()
@@ -311,11 +454,13 @@ impl Server {
             config.clone().unwrap_or_default(),
             world.clone(),
             gamemode,
+            self.world_lookup.clone(),
         )
         .await;
 
         if let Some(mut nbt_data) = nbt {
-            player.read_nbt(&mut nbt_data).await;
+            // `EntityBase::read_nbt` only covers the entity part, the saved player data needs the full load
+            NBTStorage::read_nbt(&mut player, &mut nbt_data).await;
         }
 
         // Wrap in Arc after data is loaded
@@ -328,13 +473,13 @@ impl Server {
                 if world
                     .add_player(player.gameprofile.id, player.clone())
                     .await.is_ok() {
-                    // TODO: Config if we want increase online
-                    if let Some(config) = config {
-                        // TODO: Config so we can also just ignore this hehe
-                        if config.server_listing {
-                            self.listing.lock().await.add_player(&player);
-                        }
-                    }
+                    self.world_lookup
+                        .write()
+                        .await
+                        .insert(player.gameprofile.id, world.clone());
+                    // Players who disabled server listing still count as online, but stay out of the sample
+                    let listed = config.as_ref().is_some_and(|config| config.server_listing);
+                    self.listing.lock().await.add_player(&player, listed);
 
                     // Send tick rate information to the new player
                     if let ClientPlatform::Java(_) = &player.client {
@@ -350,15 +495,22 @@ impl Server {
             }
 
             'cancelled: {
-                player.kick(event.kick_message).await;
+                player.kick(KickCause::LoginCancelled, event.kick_message).await;
                 None
             }
         }}
     }
 
     pub async fn remove_player(&self, player: &Player) {
-        // TODO: Config if we want decrease online
+        self.world_lookup
+            .write()
+            .await
+            .remove(&player.gameprofile.id);
         self.listing.lock().await.remove_player(player);
+        self.bossbars
+            .lock()
+            .await
+            .remove_viewer(&player.gameprofile.id);
     }
 
     pub async fn shutdown(&self) {
@@ -371,14 +523,112 @@ impl Server {
         for world in self.worlds.read().await.iter() {
             world.shutdown().await;
         }
+        if let Err(err) = self.maps.save().await {
+            log::error!("Failed to save maps: {err}");
+        }
+        if let Err(err) = self.scoreboard.lock().await.save() {
+            log::error!("Failed to save the scoreboard: {err}");
+        }
         // then lets save the world info
-        if let Err(err) = self.world_info_writer.write_world_info(
-            &*self.level_info.read().await,
-            &BASIC_CONFIG.get_world_path(),
-        ) {
+        self.write_level_info().await;
+        save_permissions(&PERMISSION_MANAGER).await;
+        log::info!("Completed worlds");
+    }
+
+    /// Reads `level.dat` and backs it up, or creates new level info if there is none. The extra
+    /// data packs of the config are enabled here, so they are part of it from the start.
+    fn load_level_info(world_path: &Path) -> LevelData {
+        let level_info = AnvilLevelInfo.read_world_info(world_path);
+        if let Err(error) = &level_info {
+            match error {
+                // If it doesn't exist, just make a new one
+                WorldInfoError::InfoNotFound => (),
+                WorldInfoError::UnsupportedVersion(version) => {
+                    log::error!("Failed to load world info!, {version}");
+                    log::error!("{error}");
+                    panic!("Unsupported world data! See the logs for more info.");
+                }
+                e => {
+                    panic!("World Error {e}");
+                }
+            }
+        } else {
+            let dat_path = world_path.join(LEVEL_DAT_FILE_NAME);
+            if dat_path.exists() {
+                let backup_path = world_path.join(LEVEL_DAT_BACKUP_FILE_NAME);
+                fs::copy(dat_path, backup_path).unwrap();
+            }
+        }
+
+        let mut level_info = level_info.unwrap_or_default(); // TODO: Improve error handling
+        level_info
+            .data_packs
+            .enable(&advanced_config().world.extra_data_packs);
+        log::info!(
+            "Enabled data packs: {}",
+            level_info.data_packs.enabled.join(", ")
+        );
+        level_info
+    }
+
+    async fn write_level_info(&self) {
+        let mut level_info = self.level_info.read().await.clone();
+        // Like vanilla, `level.dat` stores the weather of the overworld
+        if let Some(overworld) = self.worlds.read().await.first() {
+            overworld
+                .weather
+                .lock()
+                .await
+                .write_to_level_data(&mut level_info);
+        }
+        if let Err(err) = self
+            .world_info_writer
+            .write_world_info(&level_info, &BASIC_CONFIG.get_world_path())
+        {
             log::error!("Failed to save level.dat: {err}");
         }
-        log::info!("Completed worlds");
+    }
+
+    /// Saves the worlds, players, maps and `level.dat` while the server keeps running.
+    ///
+    /// With `flush`, the chunks are written all at once instead of in batches.
+    pub async fn save_all(&self, flush: bool) {
+        log::debug!("Saving the game");
+        if let Err(err) = self.player_data_storage.save_all_players(self).await {
+            log::error!("Failed to save player data: {err}");
+        }
+        for world in self.worlds.read().await.iter() {
+            world.level.save(flush).await;
+            world.save_nether_portals().await;
+        }
+        if let Err(err) = self.maps.save().await {
+            log::error!("Failed to save maps: {err}");
+        }
+        if let Err(err) = self.scoreboard.lock().await.save() {
+            log::error!("Failed to save the scoreboard: {err}");
+        }
+        self.write_level_info().await;
+        save_permissions(&PERMISSION_MANAGER).await;
+        log::debug!("Saved the game");
+    }
+
+    /// Starts an autosave in the background every `autosave_interval` ticks, unless the last one
+    /// is still running.
+    fn maybe_autosave(self: &Arc<Self>) {
+        let interval = advanced_config().world.autosave_interval;
+        let tick = self.tick_count.load(Ordering::Relaxed);
+        if interval == 0 || tick == 0 || !(tick as u32).is_multiple_of(interval) {
+            return;
+        }
+        if self.autosaving.swap(true, Ordering::Relaxed) {
+            log::debug!("Skipping autosave, the last one is still running");
+            return;
+        }
+        let server = self.clone();
+        self.spawn_task(async move {
+            server.save_all(false).await;
+            server.autosaving.store(false, Ordering::Relaxed);
+        });
     }
 
     /// Broadcasts a packet to all players in all worlds.
@@ -420,6 +670,44 @@ impl Server {
         }}
     }
 
+    /// Shows a title to every player in every world.
+    ///
+    /// The animation times are sent first, then the subtitle if there is one and finally the title,
+    /// which makes the client display both.
+    ///
+    /// # Arguments
+    ///
+    /// * `title`: The title to show.
+    /// * `subtitle`: The subtitle shown below the title, if any.
+    /// * `fade_in`: The ticks the title takes to fade in.
+    /// * `stay`: The ticks the title stays on screen.
+    /// * `fade_out`: The ticks the title takes to fade out.
+    pub async fn broadcast_title(
+        &self,
+        title: TextComponent,
+        subtitle: Option<TextComponent>,
+        fade_in: i32,
+        stay: i32,
+        fade_out: i32,
+    ) {
+        send_cancellable! {{
+            ServerTitleEvent::new(title, subtitle, fade_in, stay, fade_out);
+
+            'after: {
+                self.broadcast_packet_all(&CSetTitleAnimationTimes::new(
+                    event.fade_in,
+                    event.stay,
+                    event.fade_out,
+                ))
+                .await;
+                if let Some(subtitle) = &event.subtitle {
+                    self.broadcast_packet_all(&CSubtitle::new(subtitle)).await;
+                }
+                self.broadcast_packet_all(&CTitleText::new(&event.title)).await;
+            }
+        }}
+    }
+
     /// Sets the difficulty of the server.
     ///
     /// This function updates the difficulty level of the server and broadcasts the change to all players.
@@ -507,6 +795,22 @@ impl Server {
         players
     }
 
+    /// Returns all entities from all worlds, including players.
+    pub async fn get_all_entities(&self) -> Vec<Arc<dyn EntityBase>> {
+        let mut entities = Vec::<Arc<dyn EntityBase>>::new();
+
+        for world in self.worlds.read().await.iter() {
+            for player in world.players.read().await.values() {
+                entities.push(player.clone());
+            }
+            for entity in world.entities.read().await.values() {
+                entities.push(entity.clone());
+            }
+        }
+
+        entities
+    }
+
     /// Returns a random player from any of the worlds, or `None` if all worlds are empty.
     pub async fn get_random_player(&self) -> Option<Arc<Player>> {
         let players = self.get_all_players().await;
@@ -527,12 +831,15 @@ impl Server {
     ///
     /// An `Option<Arc<Player>>` containing the player if found, or `None` if not found.
     pub async fn get_player_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<Player>> {
-        for world in self.worlds.read().await.iter() {
-            if let Some(player) = world.get_player_by_uuid(id).await {
-                return Some(player);
-            }
-        }
-        None
+        self.world_for_player(id)
+            .await?
+            .get_player_by_uuid(id)
+            .await
+    }
+
+    /// Returns the world the online player with the given UUID is in, without searching every world.
+    pub async fn world_for_player(&self, id: uuid::Uuid) -> Option<Arc<World>> {
+        self.world_lookup.read().await.get(&id).cloned()
     }
 
     /// Counts the total number of players across all worlds.
@@ -562,6 +869,13 @@ impl Server {
         false
     }
 
+    /// Creates a new empty map centered at the origin of the overworld.
+    pub async fn create_map(&self, scale: i8) -> Arc<Mutex<MapState>> {
+        self.maps
+            .create(scale, 0, 0, "minecraft:overworld".to_string())
+            .await
+    }
+
     /// Generates a new container id.
     pub fn new_container_id(&self) -> u32 {
         self.container_id.fetch_add(1, Ordering::SeqCst)
@@ -592,9 +906,132 @@ impl Server {
         self.key_store.get_digest(secret)
     }
 
+    /// Schedules a [`Server::reload`] before the next tick, `sender` is told once it is done.
+    ///
+    /// Commands are executed while the command dispatcher is locked, so they can't reload plugins
+    /// (which register commands) directly.
+    pub async fn request_reload(&self, sender: CommandSender) {
+        self.reload_requests.lock().await.push(sender);
+    }
+
+    /// Reloads the configuration files, the whitelist, the server icon and all plugins without restarting.
+    ///
+    /// Settings which can't be changed while the server is running are kept and reported.
+    pub async fn reload(&self) {
+        log::info!("Reloading configuration and plugins...");
+        self.reload_config().await;
+
+        *WHITELIST_CONFIG.write().await = WhitelistConfig::load();
+        self.reload_icon().await;
+
+        let mut plugin_manager = PLUGIN_MANAGER.write().await;
+        if let Err(err) = plugin_manager.unload_all_plugins().await {
+            log::error!("Failed to unload plugins: {err}");
+        }
+        plugin_manager.clear_handlers().await;
+        // Drop the commands of the unloaded plugins, the reloaded ones register theirs again
+        *self.command_dispatcher.write().await = builtin_dispatcher();
+        if let Err(err) = plugin_manager.load_plugins().await {
+            log::error!("Failed to load plugins: {err}");
+        }
+        drop(plugin_manager);
+
+        let dispatcher = self.command_dispatcher.read().await;
+        for player in self.get_all_players().await {
+            client_suggestions::send_c_commands_packet(&player, &dispatcher).await;
+        }
+        log::info!("Reload complete");
+    }
+
+    /// Loads the server icon from disk again, with the icon settings of the last reloaded
+    /// configuration.
+    pub async fn reload_icon(&self) {
+        let reloaded_config = self.reloaded_config.lock().await;
+        let basic = reloaded_config
+            .as_ref()
+            .map_or(&*BASIC_CONFIG, |(basic, _)| basic);
+        self.listing.lock().await.reload_icon(basic);
+    }
+
+    async fn reload_config(&self) {
+        let exec_dir = match env::current_dir() {
+            Ok(dir) => dir,
+            Err(err) => {
+                log::error!("Failed to reload configuration: {err}");
+                return;
+            }
+        };
+        let (basic, advanced) = match (
+            BasicConfiguration::reload(&exec_dir),
+            AdvancedConfiguration::reload(&exec_dir),
+        ) {
+            (Ok(basic), Ok(advanced)) => (basic, advanced),
+            (Err(err), _) | (_, Err(err)) => {
+                log::error!("Failed to reload configuration, keeping the current one: {err}");
+                return;
+            }
+        };
+
+        let mut reloaded_config = self.reloaded_config.lock().await;
+        let (applied_basic, applied_advanced) = reloaded_config
+            .as_ref()
+            .map_or((&*BASIC_CONFIG, advanced_config()), |(basic, advanced)| {
+                (basic, advanced)
+            });
+        let changed_basic = changed_settings(applied_basic, &basic);
+        let changed_advanced = changed_settings(applied_advanced, &advanced);
+        let changed_logging = changed_settings(&applied_advanced.logging, &advanced.logging);
+
+        for setting in changed_basic {
+            match setting.as_str() {
+                "white_list" => self.white_list.store(basic.white_list, Ordering::Relaxed),
+                "view_distance" => {
+                    if !(2..=32).contains(&basic.view_distance.get()) {
+                        log::warn!("Ignoring view_distance, it must be between 2 and 32");
+                        continue;
+                    }
+                    chunker::set_max_view_distance(basic.view_distance);
+                    for player in self.get_all_players().await {
+                        chunker::update_view_distance(&player).await;
+                    }
+                }
+                "default_gamemode" => {
+                    self.defaultgamemode.lock().await.gamemode = basic.default_gamemode;
+                }
+                "motd" => self.listing.lock().await.reload_motd(&basic),
+                // The icon is loaded again once the configuration is reloaded
+                "use_favicon" | "server_icon_path" => {}
+                _ => log::warn!("Changing '{setting}' requires a restart"),
+            }
+        }
+        for setting in changed_advanced {
+            if setting != "logging" {
+                log::warn!("Changing '{setting}' requires a restart");
+            }
+        }
+        for setting in changed_logging {
+            if setting == "level" {
+                log::set_max_level(advanced.logging.level_filter());
+            } else {
+                log::warn!("Changing 'logging.{setting}' requires a restart");
+            }
+        }
+        *reloaded_config = Some((basic, advanced));
+    }
+
     /// Main server tick method. This now handles both player/network ticking (which always runs)
     /// and world/game logic ticking (which is affected by freeze state).
     pub async fn tick(self: &Arc<Self>) {
+        let reload_requests = std::mem::take(&mut *self.reload_requests.lock().await);
+        if !reload_requests.is_empty() {
+            self.reload().await;
+            for sender in reload_requests {
+                sender
+                    .send_message(TextComponent::translate("commands.reload.success", []))
+                    .await;
+            }
+        }
+
         // Always run player and network ticking, even when game is frozen
         self.tick_players_and_network().await;
 
@@ -617,6 +1054,14 @@ impl Server {
         for player in players_to_tick {
             player.tick(self).await;
         }
+
+        // Chunks players left are unloaded even while the game is frozen
+        let tick = self.tick_count.load(Ordering::Relaxed);
+        if tick % 20 == 0 {
+            for world in self.worlds.read().await.iter() {
+                world.level.unload_unwatched_chunks(i64::from(tick)).await;
+            }
+        }
     }
     /// Ticks the game logic for all worlds. This is the part that is affected by `/tick freeze`.
     pub async fn tick_worlds(self: &Arc<Self>) {
@@ -638,6 +1083,8 @@ impl Server {
         if let Err(e) = self.player_data_storage.tick(self).await {
             log::error!("Error ticking player data: {e}");
         }
+        self.maps.tick(&self.get_all_players().await).await;
+        self.maybe_autosave();
     }
 
     /// Updates the tick time statistics with the duration of the last tick.
@@ -652,6 +1099,15 @@ impl Server {
 
         self.aggregated_tick_times_nanos
             .fetch_add(tick_duration_nanos - old_time, Ordering::Relaxed);
+        self.tps_averages.update(
+            tick_duration_nanos,
+            self.tick_rate_manager.nanoseconds_per_tick(),
+        );
+    }
+
+    /// Gets the ticks per second averaged over the last 1, 5 and 15 minutes.
+    pub fn get_tps(&self) -> (f32, f32, f32) {
+        self.tps_averages.get()
     }
 
     /// Gets the rolling average tick time over the last 100 ticks, in nanoseconds.
@@ -1,14 +1,19 @@
-use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32, Ordering};
 use std::time::Instant;
 
 use crossbeam::atomic::AtomicCell;
-use pumpkin_config::BASIC_CONFIG;
+use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_protocol::java::client::play::{CSystemChatMessage, CTickingState, CTickingStep};
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 
 use crate::entity::player::Player;
 use crate::server::Server;
 const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+/// How many ticks the average tick time is taken over. The tick rate is only lowered once a full
+/// window passed since the last change, as the average lags behind a single slow tick
+const ADAPT_WINDOW_TICKS: u32 = 100;
+/// How many ticks in a row have to be fast before the tick rate is raised again
+const FAST_TICKS_UNTIL_RAISE: u32 = 10;
 
 #[derive(Debug)]
 pub struct ServerTickRateManager {
@@ -24,6 +29,14 @@ pub struct ServerTickRateManager {
     sprint_time_spend: AtomicI64,
     scheduled_current_sprint_ticks: AtomicI64,
     previous_is_frozen: AtomicBool,
+
+    // Adaptive tick rate
+    performance_adaptive_tick_rate: AtomicBool,
+    min_tick_rate: AtomicU32,
+    /// The tick rate which was set manually, which is restored once the server keeps up again
+    max_tick_rate: AtomicCell<f32>,
+    ticks_since_adapted: AtomicU32,
+    fast_ticks: AtomicU32,
 }
 
 impl Default for ServerTickRateManager {
@@ -40,6 +53,13 @@ impl Default for ServerTickRateManager {
             sprint_time_spend: AtomicI64::new(0),
             scheduled_current_sprint_ticks: AtomicI64::new(0),
             previous_is_frozen: AtomicBool::new(false),
+            performance_adaptive_tick_rate: AtomicBool::new(
+                advanced_config().performance.adaptive_tick_rate,
+            ),
+            min_tick_rate: AtomicU32::new(advanced_config().performance.min_tick_rate.max(1)),
+            max_tick_rate: AtomicCell::new(tickrate),
+            ticks_since_adapted: AtomicU32::new(0),
+            fast_ticks: AtomicU32::new(0),
         }
     }
 }
@@ -81,6 +101,13 @@ impl ServerTickRateManager {
     }
 
     pub async fn set_tick_rate(&self, server: &Server, rate: f32) {
+        self.max_tick_rate.store(rate.max(1.0));
+        self.ticks_since_adapted.store(0, Ordering::Relaxed);
+        self.fast_ticks.store(0, Ordering::Relaxed);
+        self.store_tick_rate(server, rate).await;
+    }
+
+    async fn store_tick_rate(&self, server: &Server, rate: f32) {
         self.tickrate.store(rate.max(1.0));
         self.nanoseconds_per_tick.store(
             (NANOSECONDS_PER_SECOND as f64 / f64::from(self.tickrate.load())) as i64,
@@ -90,6 +117,74 @@ impl ServerTickRateManager {
         self.update_state_to_clients(server).await;
     }
 
+    pub fn set_performance_adaptive_tick_rate(&self, enabled: bool) {
+        self.performance_adaptive_tick_rate
+            .store(enabled, Ordering::Relaxed);
+        self.ticks_since_adapted.store(0, Ordering::Relaxed);
+        self.fast_ticks.store(0, Ordering::Relaxed);
+    }
+
+    pub fn set_min_tick_rate(&self, min_tick_rate: u32) {
+        self.min_tick_rate
+            .store(min_tick_rate.max(1), Ordering::Relaxed);
+    }
+
+    /// Lowers the tick rate by one while ticks take more than 120% of their time, down to the
+    /// minimum tick rate, at most once per averaging window. Once ticks take less than 80% of
+    /// their time for 10 ticks in a row, the tick rate is raised by one again until it is back at
+    /// the manually set one.
+    pub async fn adapt_tick_rate(&self, server: &Server) {
+        if !self.performance_adaptive_tick_rate.load(Ordering::Relaxed)
+            || self.is_frozen()
+            || self.is_sprinting()
+        {
+            return;
+        }
+        let ticks_since_adapted = self.ticks_since_adapted.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let average_tick_time = server.get_average_tick_time_nanos() as f64;
+        let target_tick_time = self.nanoseconds_per_tick() as f64;
+        let fast_ticks = if average_tick_time < target_tick_time * 0.8 {
+            self.fast_ticks.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.fast_ticks.store(0, Ordering::Relaxed);
+            0
+        };
+        let rate = self.tickrate();
+        let min_tick_rate = self.min_tick_rate.load(Ordering::Relaxed) as f32;
+        let max_tick_rate = self.max_tick_rate.load();
+
+        // A manually set rate below the minimum is left alone instead of being raised to it
+        let (new_rate, change) = if average_tick_time > target_tick_time * 1.2
+            && ticks_since_adapted >= ADAPT_WINDOW_TICKS
+            && rate > min_tick_rate
+        {
+            ((rate - 1.0).max(min_tick_rate), "lowered")
+        } else if fast_ticks >= FAST_TICKS_UNTIL_RAISE && rate < max_tick_rate {
+            ((rate + 1.0).min(max_tick_rate), "raised")
+        } else {
+            return;
+        };
+        self.ticks_since_adapted.store(0, Ordering::Relaxed);
+        self.fast_ticks.store(0, Ordering::Relaxed);
+        self.store_tick_rate(server, new_rate).await;
+        Self::notify_ops(server, change, new_rate).await;
+    }
+
+    async fn notify_ops(server: &Server, change: &str, rate: f32) {
+        log::info!("Tick rate automatically {change} to {rate}");
+        let message = TextComponent::text(format!(
+            "[Server: Tick rate automatically {change} to {rate}]"
+        ))
+        .italic()
+        .color_named(NamedColor::Gray);
+        for player in server.get_all_players().await {
+            if player.has_permission("pumpkin:notify.tick_rate").await {
+                player.send_system_message(&message).await;
+            }
+        }
+    }
+
     pub async fn set_frozen(&self, server: &Server, frozen: bool) {
         self.is_frozen.store(frozen, Ordering::Relaxed);
         self.update_state_to_clients(server).await;
@@ -1,10 +1,13 @@
 use crate::{SHOULD_STOP, server::Server};
+use pumpkin_config::advanced_config;
 use std::{
     sync::{Arc, atomic::Ordering},
     time::{Duration, Instant},
 };
 use tokio::time::sleep;
 
+use super::watchdog::Watchdog;
+
 pub struct Ticker {
     last_tick: Instant,
 }
@@ -25,8 +28,12 @@ impl Ticker {
 
     /// IMPORTANT: Run this in a new thread/tokio task.
     pub async fn run(&mut self, server: &Arc<Server>) {
+        let watchdog = Watchdog::start(&advanced_config().performance.watchdog);
         while !SHOULD_STOP.load(Ordering::Relaxed) {
             let tick_start_time = Instant::now();
+            if let Some(watchdog) = &watchdog {
+                watchdog.tick_started();
+            }
             let manager = &server.tick_rate_manager;
 
             manager.tick();
@@ -49,8 +56,13 @@ impl Ticker {
             }
 
             // Record the total time this tick took
-            let tick_duration_nanos = tick_start_time.elapsed().as_nanos() as i64;
+            let tick_duration = tick_start_time.elapsed();
+            if let Some(watchdog) = &watchdog {
+                watchdog.tick_finished(tick_duration);
+            }
+            let tick_duration_nanos = tick_duration.as_nanos() as i64;
             server.update_tick_times(tick_duration_nanos).await;
+            manager.adapt_tick_rate(server).await;
 
             // Sleep logic remains the same
             let now = Instant::now();
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The periods of the averages in nanoseconds, one, five and fifteen minutes like the Unix load
+/// average.
+const PERIODS_NANOS: [f64; 3] = [60.0e9, 300.0e9, 900.0e9];
+
+/// Exponential moving averages of the ticks per second over 1, 5 and 15 minutes.
+///
+/// The tick durations are averaged and only turned into ticks per second when read, as averaging
+/// the rates of single ticks would overweight the fast ones. Each tick is weighted by how long it
+/// took, so the periods stay the same however slow the server is.
+///
+/// Only the ticker updates them, so plain loads and stores are enough to stay lock-free.
+pub struct TpsAverages {
+    /// The average tick durations in nanoseconds, stored as `f64` bits
+    averages: [AtomicU64; 3],
+}
+
+impl TpsAverages {
+    /// Starts every average at `tick_rate`, so they don't have to ramp up after startup.
+    #[must_use]
+    pub fn new(tick_rate: f32) -> Self {
+        let initial = (1.0e9 / f64::from(tick_rate)).to_bits();
+        Self {
+            averages: [
+                AtomicU64::new(initial),
+                AtomicU64::new(initial),
+                AtomicU64::new(initial),
+            ],
+        }
+    }
+
+    /// Adds a tick which took `tick_duration_nanos`, a tick is never shorter than `nanos_per_tick`
+    /// since the ticker sleeps for the rest of it.
+    pub fn update(&self, tick_duration_nanos: i64, nanos_per_tick: i64) {
+        let duration = tick_duration_nanos.max(nanos_per_tick).max(1) as f64;
+        for (average, period) in self.averages.iter().zip(PERIODS_NANOS) {
+            let alpha = 1.0 - (-duration / period).exp();
+            let previous = f64::from_bits(average.load(Ordering::Relaxed));
+            let updated = alpha.mul_add(duration - previous, previous);
+            average.store(updated.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the averages over 1, 5 and 15 minutes.
+    pub fn get(&self) -> (f32, f32, f32) {
+        let [one, five, fifteen] = self
+            .averages
+            .each_ref()
+            .map(|average| (1.0e9 / f64::from_bits(average.load(Ordering::Relaxed))) as f32);
+        (one, five, fifteen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TpsAverages;
+
+    const NANOS_PER_TICK: i64 = 50_000_000;
+
+    #[test]
+    fn stays_at_the_tick_rate_when_ticks_are_fast() {
+        let tps = TpsAverages::new(20.0);
+        for _ in 0..1000 {
+            tps.update(10_000_000, NANOS_PER_TICK);
+        }
+        let (one, five, fifteen) = tps.get();
+        assert!((one - 20.0).abs() < 0.001);
+        assert!((five - 20.0).abs() < 0.001);
+        assert!((fifteen - 20.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn shorter_periods_react_faster_to_lag() {
+        let tps = TpsAverages::new(20.0);
+        // 1200 ticks taking twice as long as they should, which is two minutes at 10 TPS
+        for _ in 0..1200 {
+            tps.update(2 * NANOS_PER_TICK, NANOS_PER_TICK);
+        }
+        let (one, five, fifteen) = tps.get();
+        assert!(one < five && five < fifteen && fifteen < 20.0);
+        // After two periods, the average duration moved about 86% of the way to 100 ms
+        assert!((one - 10.73).abs() < 0.05);
+        assert!((five - 15.04).abs() < 0.05);
+    }
+
+    #[test]
+    fn periods_are_measured_in_time() {
+        // The same two minutes, in a fifth of the ticks
+        let tps = TpsAverages::new(20.0);
+        for _ in 0..240 {
+            tps.update(10 * NANOS_PER_TICK, NANOS_PER_TICK);
+        }
+        let (one, _, _) = tps.get();
+        // The average duration is 500 - 450 * e^-2 = 439.1 ms
+        assert!((one - 2.277).abs() < 0.01);
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use pumpkin_config::WatchdogConfig;
+use tokio::runtime::Handle;
+
+use crate::{SHOULD_STOP, stop_server};
+
+/// Marks that no tick is running right now
+const NOT_TICKING: u64 = u64::MAX;
+
+/// Watches the tick loop from its own thread and reports ticks which take too long.
+///
+/// It never waits on the async runtime, so it still reports when the runtime itself is stuck.
+pub struct Watchdog {
+    epoch: Instant,
+    /// When the running tick started, in milliseconds since `epoch`
+    tick_start_ms: AtomicU64,
+    /// How many times the ticks have stalled in a row
+    stalls: AtomicU32,
+    threshold: Duration,
+}
+
+impl Watchdog {
+    /// Starts watching on a new thread, returns `None` if the watchdog is disabled.
+    pub fn start(config: &WatchdogConfig) -> Option<Arc<Self>> {
+        if !config.enabled || config.stall_threshold_ms == 0 {
+            return None;
+        }
+
+        let watchdog = Arc::new(Self {
+            epoch: Instant::now(),
+            tick_start_ms: AtomicU64::new(NOT_TICKING),
+            stalls: AtomicU32::new(0),
+            threshold: Duration::from_millis(config.stall_threshold_ms),
+        });
+
+        let runtime = config.dump_runtime_state.then(Handle::current);
+        let stop_after_stalls = config.stop_after_stalls;
+        let thread_watchdog = watchdog.clone();
+        let result = std::thread::Builder::new()
+            .name("Tick Watchdog".to_string())
+            .spawn(move || thread_watchdog.watch(runtime.as_ref(), stop_after_stalls));
+        if let Err(err) = result {
+            log::error!("Failed to start the tick watchdog: {err}");
+            return None;
+        }
+
+        Some(watchdog)
+    }
+
+    pub fn tick_started(&self) {
+        self.tick_start_ms
+            .store(self.epoch.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn tick_finished(&self, duration: Duration) {
+        self.tick_start_ms.store(NOT_TICKING, Ordering::Relaxed);
+        if duration < self.threshold {
+            self.stalls.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn watch(&self, runtime: Option<&Handle>, stop_after_stalls: u32) {
+        let check_interval = (self.threshold / 4).min(Duration::from_secs(1));
+        // The tick which was reported last and how often, so every threshold passed is reported once
+        let mut reported = (NOT_TICKING, 0);
+
+        while !SHOULD_STOP.load(Ordering::Relaxed) {
+            std::thread::sleep(check_interval);
+
+            let tick_start_ms = self.tick_start_ms.load(Ordering::Relaxed);
+            if tick_start_ms == NOT_TICKING {
+                continue;
+            }
+            let running = self
+                .epoch
+                .elapsed()
+                .saturating_sub(Duration::from_millis(tick_start_ms));
+            if reported.0 != tick_start_ms {
+                reported = (tick_start_ms, 0);
+            }
+            if running < self.threshold * (reported.1 + 1) {
+                continue;
+            }
+            reported.1 += 1;
+
+            let stalls = self.stalls.fetch_add(1, Ordering::Relaxed) + 1;
+            log::warn!(
+                "The server tick has been running for {running:.2?}, longer than the watchdog threshold of {:?} ({stalls} stalls in a row)",
+                self.threshold
+            );
+            if let Some(runtime) = runtime {
+                Self::dump_runtime_state(runtime);
+            }
+
+            if stop_after_stalls > 0 && stalls >= stop_after_stalls {
+                log::error!(
+                    "The server ticks stalled {stalls} times in a row, saving and stopping"
+                );
+                stop_server();
+                break;
+            }
+        }
+    }
+
+    fn dump_runtime_state(runtime: &Handle) {
+        let metrics = runtime.metrics();
+        log::warn!(
+            "Runtime state: {} workers, {} alive tasks, {} tasks in the global queue",
+            metrics.num_workers(),
+            metrics.num_alive_tasks(),
+            metrics.global_queue_depth()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use pumpkin_protocol::java::client::play::{
     CInitializeWorldBorder, CSetBorderCenter, CSetBorderLerpSize, CSetBorderSize,
     CSetBorderWarningDelay, CSetBorderWarningDistance,
@@ -12,7 +14,10 @@ pub struct Worldborder {
     pub center_z: f64,
     pub old_diameter: f64,
     pub new_diameter: f64,
+    /// How long moving from `old_diameter` to `new_diameter` takes in milliseconds
     pub speed: i64,
+    /// When the border started moving to `new_diameter`
+    pub lerp_start: Instant,
     pub portal_teleport_boundary: i32,
     pub warning_blocks: i32,
     pub warning_time: i32,
@@ -36,6 +41,7 @@ impl Worldborder {
             old_diameter: diameter,
             new_diameter: diameter,
             speed,
+            lerp_start: Instant::now(),
             portal_teleport_boundary: 29_999_984,
             warning_blocks,
             warning_time,
@@ -44,14 +50,66 @@ impl Worldborder {
         }
     }
 
+    /// How many milliseconds are left until the border reaches `new_diameter`.
+    #[must_use]
+    pub fn remaining_time(&self) -> i64 {
+        let elapsed = i64::try_from(self.lerp_start.elapsed().as_millis()).unwrap_or(i64::MAX);
+        (self.speed - elapsed).max(0)
+    }
+
+    /// The current diameter, linearly interpolated while the border is moving.
+    #[must_use]
+    pub fn diameter(&self) -> f64 {
+        let remaining = self.remaining_time();
+        if remaining == 0 {
+            return self.new_diameter;
+        }
+        let progress = 1.0 - remaining as f64 / self.speed as f64;
+        (self.new_diameter - self.old_diameter).mul_add(progress, self.old_diameter)
+    }
+
+    /// The distance of a position to the nearest edge of the border, negative when outside of it.
+    #[must_use]
+    pub fn distance_to_border(&self, x: f64, z: f64) -> f64 {
+        let radius = self.diameter() / 2.0;
+        let max = f64::from(self.portal_teleport_boundary);
+        let min_x = (self.center_x - radius).clamp(-max, max);
+        let max_x = (self.center_x + radius).clamp(-max, max);
+        let min_z = (self.center_z - radius).clamp(-max, max);
+        let max_z = (self.center_z + radius).clamp(-max, max);
+        (x - min_x).min(max_x - x).min(z - min_z).min(max_z - z)
+    }
+
+    /// The damage an entity at this position takes each tick, growing with its distance past the buffer.
+    #[must_use]
+    pub fn damage_at(&self, x: f64, z: f64) -> Option<f32> {
+        let distance = self.distance_to_border(x, z) + f64::from(self.buffer);
+        if distance >= 0.0 || self.damage_per_block <= 0.0 {
+            return None;
+        }
+        Some(
+            (-distance * f64::from(self.damage_per_block))
+                .floor()
+                .max(1.0) as f32,
+        )
+    }
+
+    /// Settles the border once it has finished moving.
+    pub fn tick(&mut self) {
+        if self.speed > 0 && self.remaining_time() == 0 {
+            self.old_diameter = self.new_diameter;
+            self.speed = 0;
+        }
+    }
+
     pub async fn init_client(&self, client: &ClientPlatform) {
         client
             .enqueue_packet(&CInitializeWorldBorder::new(
                 self.center_x,
                 self.center_z,
-                self.old_diameter,
+                self.diameter(),
                 self.new_diameter,
-                self.speed.into(),
+                self.remaining_time().into(),
                 self.portal_teleport_boundary.into(),
                 self.warning_blocks.into(),
                 self.warning_time.into(),
@@ -69,8 +127,11 @@ impl Worldborder {
     }
 
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
+        // A border that is still moving starts the new movement from where it is now
+        self.old_diameter = self.diameter();
         self.new_diameter = diameter;
+        self.speed = speed.unwrap_or(0);
+        self.lerp_start = Instant::now();
 
         match speed {
             Some(speed) => {
@@ -91,7 +152,7 @@ impl Worldborder {
     }
 
     pub async fn add_diameter(&mut self, world: &World, offset: f64, speed: Option<i64>) {
-        self.set_diameter(world, self.new_diameter + offset, speed)
+        self.set_diameter(world, self.diameter() + offset, speed)
             .await;
     }
 
@@ -111,3 +172,47 @@ impl Worldborder {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::Worldborder;
+
+    #[test]
+    fn damage_scales_with_distance() {
+        let mut border = Worldborder::new(0.0, 0.0, 10.0, 0, 0, 0);
+        border.damage_per_block = 0.2;
+        border.buffer = 5.0;
+
+        // Inside of the border and inside of the buffer
+        assert_eq!(border.damage_at(0.0, 0.0), None);
+        assert_eq!(border.damage_at(10.0, 0.0), None);
+        // Always at least one damage
+        assert_eq!(border.damage_at(12.0, 0.0), Some(1.0));
+        assert_eq!(border.damage_at(0.0, -20.0), Some(2.0));
+        assert_eq!(border.damage_at(-55.0, 0.0), Some(9.0));
+
+        border.damage_per_block = 0.0;
+        assert_eq!(border.damage_at(-55.0, 0.0), None);
+    }
+
+    #[test]
+    fn diameter_lerps() {
+        let mut border = Worldborder::new(0.0, 0.0, 10.0, 0, 0, 0);
+        border.new_diameter = 20.0;
+        border.speed = 10_000;
+        border.lerp_start = Instant::now().checked_sub(Duration::from_secs(5)).unwrap();
+
+        assert!((border.diameter() - 15.0).abs() < 0.1);
+        assert!((border.distance_to_border(0.0, 0.0) - 7.5).abs() < 0.1);
+        assert!(border.remaining_time() <= 5000);
+
+        border.lerp_start = Instant::now().checked_sub(Duration::from_secs(11)).unwrap();
+        assert_eq!(border.remaining_time(), 0);
+        assert!((border.diameter() - 20.0).abs() < f64::EPSILON);
+        border.tick();
+        assert_eq!(border.speed, 0);
+        assert!((border.old_diameter - 20.0).abs() < f64::EPSILON);
+    }
+}
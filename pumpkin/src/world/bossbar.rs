@@ -1,4 +1,7 @@
+use std::collections::HashSet;
+
 use crate::entity::player::Player;
+use crate::world::World;
 use pumpkin_protocol::java::client::play::{BosseventAction, CBossEvent};
 use pumpkin_util::text::TextComponent;
 use uuid::Uuid;
@@ -23,6 +26,7 @@ pub enum BossbarDivisions {
     Notches20,
 }
 
+/// The bits of [`Bossbar::flags`]
 #[derive(Clone)]
 pub enum BossbarFlags {
     NoFlags,
@@ -38,7 +42,8 @@ pub struct Bossbar {
     pub health: f32,
     pub color: BossbarColor,
     pub division: BossbarDivisions,
-    pub flags: BossbarFlags,
+    /// The [`BossbarFlags`] combined
+    pub flags: u8,
 }
 
 impl Bossbar {
@@ -52,7 +57,86 @@ impl Bossbar {
             health: 0.0,
             color: BossbarColor::White,
             division: BossbarDivisions::NoDivision,
-            flags: BossbarFlags::NoFlags,
+            flags: BossbarFlags::NoFlags as u8,
+        }
+    }
+}
+
+/// A bossbar belonging to a world, like the one of a boss mob.
+pub struct WorldBossbar {
+    pub bossbar: Bossbar,
+    /// The players currently shown the bar
+    pub viewers: HashSet<Uuid>,
+}
+
+impl WorldBossbar {
+    #[must_use]
+    pub fn new(bossbar: Bossbar) -> Self {
+        Self {
+            bossbar,
+            viewers: HashSet::new(),
+        }
+    }
+
+    /// Sets how full the bar is, from `0.0` to `1.0`.
+    pub async fn set_health(&mut self, world: &World, health: f32) {
+        let health = health.clamp(0.0, 1.0);
+        self.bossbar.health = health;
+        self.send_to_viewers(world, BosseventAction::UpdateHealth(health))
+            .await;
+    }
+
+    pub async fn set_title(&mut self, world: &World, title: TextComponent) {
+        if self.bossbar.title == title {
+            return;
+        }
+        self.bossbar.title = title.clone();
+        self.send_to_viewers(world, BosseventAction::UpdateTile(title))
+            .await;
+    }
+
+    pub async fn set_color(&mut self, world: &World, color: BossbarColor) {
+        if self.bossbar.color == color {
+            return;
+        }
+        self.bossbar.color = color;
+        self.send_style(world).await;
+    }
+
+    pub async fn set_division(&mut self, world: &World, division: BossbarDivisions) {
+        if self.bossbar.division == division {
+            return;
+        }
+        self.bossbar.division = division;
+        self.send_style(world).await;
+    }
+
+    /// Sets the [`BossbarFlags`], combined into one byte.
+    pub async fn set_flags(&mut self, world: &World, flags: u8) {
+        if self.bossbar.flags == flags {
+            return;
+        }
+        self.bossbar.flags = flags;
+        self.send_to_viewers(world, BosseventAction::UpdateFlags(flags))
+            .await;
+    }
+
+    async fn send_style(&self, world: &World) {
+        let action = BosseventAction::UpdateStyle {
+            color: (self.bossbar.color.clone() as u8).into(),
+            dividers: (self.bossbar.division.clone() as u8).into(),
+        };
+        self.send_to_viewers(world, action).await;
+    }
+
+    /// Only sends the changed part of the bar instead of adding it again.
+    async fn send_to_viewers(&self, world: &World, action: BosseventAction) {
+        let packet = CBossEvent::new(&self.bossbar.uuid, action);
+        let players = world.players.read().await;
+        for uuid in &self.viewers {
+            if let Some(player) = players.get(uuid) {
+                player.client.enqueue_packet(&packet).await;
+            }
         }
     }
 }
@@ -67,7 +151,7 @@ impl Player {
             health: bossbar.health,
             color: (bossbar.color as u8).into(),
             division: (bossbar.division as u8).into(),
-            flags: bossbar.flags as u8,
+            flags: bossbar.flags,
         };
 
         let packet = CBossEvent::new(&bossbar.uuid, boss_action);
@@ -109,8 +193,8 @@ impl Player {
         self.client.enqueue_packet(&packet).await;
     }
 
-    pub async fn update_bossbar_flags(&self, uuid: &Uuid, flags: BossbarFlags) {
-        let boss_action = BosseventAction::UpdateFlags(flags as u8);
+    pub async fn update_bossbar_flags(&self, uuid: &Uuid, flags: u8) {
+        let boss_action = BosseventAction::UpdateFlags(flags);
 
         let packet = CBossEvent::new(uuid, boss_action);
         self.client.enqueue_packet(&packet).await;
@@ -1,18 +1,37 @@
-use std::{num::NonZeroU8, sync::Arc};
+use std::{
+    num::NonZeroU8,
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU8, Ordering},
+    },
+};
 
 use pumpkin_config::BASIC_CONFIG;
-use pumpkin_protocol::java::client::play::{CCenterChunk, CUnloadChunk};
+use pumpkin_protocol::java::client::play::{CCenterChunk, CSetChunkCacheRadius, CUnloadChunk};
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
 
 use crate::entity::player::Player;
 
+/// The maximum view distance of the server, this can change at runtime (e.g. on `/reload`)
+static MAX_VIEW_DISTANCE: LazyLock<AtomicU8> =
+    LazyLock::new(|| AtomicU8::new(BASIC_CONFIG.view_distance.get()));
+
+#[must_use]
+pub fn get_max_view_distance() -> NonZeroU8 {
+    NonZeroU8::new(MAX_VIEW_DISTANCE.load(Ordering::Relaxed)).unwrap_or(BASIC_CONFIG.view_distance)
+}
+
+pub fn set_max_view_distance(view_distance: NonZeroU8) {
+    MAX_VIEW_DISTANCE.store(view_distance.get(), Ordering::Relaxed);
+}
+
 pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
     player
         .config
         .read()
         .await
         .view_distance
-        .clamp(NonZeroU8::new(2).unwrap(), BASIC_CONFIG.view_distance)
+        .clamp(NonZeroU8::new(2).unwrap(), get_max_view_distance())
 }
 
 pub async fn player_join(player: &Arc<Player>) {
@@ -36,6 +55,22 @@ pub async fn player_join(player: &Arc<Player>) {
     update_position(player).await;
 }
 
+/// Sends the player its current view distance and loads or unloads the chunks which came into or
+/// left its range.
+pub async fn update_view_distance(player: &Arc<Player>) {
+    let view_distance = get_view_distance(player).await;
+    if player.watched_section.load().view_distance == view_distance {
+        return;
+    }
+    player
+        .client
+        .enqueue_packet(&CSetChunkCacheRadius {
+            view_distance: view_distance.get().into(),
+        })
+        .await;
+    update_position(player).await;
+}
+
 pub async fn update_position(player: &Arc<Player>) {
     let entity = &player.living_entity.entity;
 
@@ -71,7 +106,7 @@ pub async fn update_position(player: &Arc<Player>) {
         // ensure what we unload when the player disconnects is correct.
         let level = &entity.world.read().await.level;
         level.mark_chunks_as_newly_watched(&loading_chunks).await;
-        let chunks_to_clean = level.mark_chunks_as_not_watched(&unloading_chunks).await;
+        level.mark_chunks_as_not_watched(&unloading_chunks).await;
 
         {
             // After marking the chunks as watched, remove chunks that we are already in the process
@@ -82,14 +117,13 @@ pub async fn update_position(player: &Arc<Player>) {
 
         player.watched_section.store(new_cylindrical);
 
-        if !chunks_to_clean.is_empty() {
-            level.clean_chunks(&chunks_to_clean).await;
-            for chunk in unloading_chunks {
-                player
-                    .client
-                    .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
-                    .await;
-            }
+        // Chunks without watchers are unloaded by the world after a delay, the client forgets
+        // them right away even if other players still watch them
+        for chunk in unloading_chunks {
+            player
+                .client
+                .enqueue_packet(&CUnloadChunk::new(chunk.x, chunk.y))
+                .await;
         }
 
         if !loading_chunks.is_empty() {
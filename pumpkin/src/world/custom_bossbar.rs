@@ -3,7 +3,7 @@ use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::bossbar::{Bossbar, BossbarColor, BossbarDivisions};
 use pumpkin_util::text::TextComponent;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use thiserror::Error;
 use uuid::Uuid;
@@ -25,6 +25,8 @@ pub struct CustomBossbar {
     pub value: u32,
     pub visible: bool,
     pub player: Vec<Uuid>,
+    /// The online players currently shown the bar
+    pub viewers: HashSet<Uuid>,
 }
 
 impl CustomBossbar {
@@ -38,8 +40,19 @@ impl CustomBossbar {
             value: 0,
             visible: true,
             player: vec![],
+            viewers: HashSet::new(),
         }
     }
+
+    async fn online_viewers(&self, server: &Server) -> Vec<Arc<Player>> {
+        let mut players = Vec::with_capacity(self.viewers.len());
+        for uuid in &self.viewers {
+            if let Some(player) = server.get_player_by_uuid(*uuid).await {
+                players.push(player);
+            }
+        }
+        players
+    }
 }
 
 pub struct CustomBossbars {
@@ -74,6 +87,25 @@ impl CustomBossbars {
         None
     }
 
+    /// Shows a joining player every visible bar they were added to.
+    pub async fn add_viewer(&mut self, player: &Player) {
+        let uuid = player.gameprofile.id;
+        for bossbar in self.custom_bossbars.values_mut() {
+            if bossbar.visible && bossbar.player.contains(&uuid) {
+                player.send_bossbar(&bossbar.bossbar_data).await;
+                bossbar.viewers.insert(uuid);
+            }
+        }
+    }
+
+    /// Stops tracking a disconnected player as viewer. They stay added to the bars,
+    /// so they see them again when rejoining.
+    pub fn remove_viewer(&mut self, uuid: &Uuid) {
+        for bossbar in self.custom_bossbars.values_mut() {
+            bossbar.viewers.remove(uuid);
+        }
+    }
+
     pub fn create_bossbar(&mut self, namespace: String, bossbar_data: Bossbar) {
         self.custom_bossbars.insert(
             namespace.clone(),
@@ -113,17 +145,8 @@ impl CustomBossbars {
         if let Some(bossbar) = bossbar {
             self.custom_bossbars.remove(&resource_location);
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-
-            let online_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
-
-            if bossbar.visible {
-                for player in online_players {
-                    player.remove_bossbar(bossbar.bossbar_data.uuid).await;
-                }
+            for player in bossbar.online_viewers(server).await {
+                player.remove_bossbar(bossbar.bossbar_data.uuid).await;
             }
 
             return Ok(());
@@ -170,12 +193,7 @@ impl CustomBossbars {
                 return Ok(());
             }
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-            let matching_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
-            for player in matching_players {
+            for player in bossbar.online_viewers(server).await {
                 player
                     .update_bossbar_health(&bossbar.bossbar_data.uuid, bossbar.bossbar_data.health)
                     .await;
@@ -206,18 +224,20 @@ impl CustomBossbars {
 
             bossbar.visible = new_visibility;
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-            let online_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
+            if bossbar.visible {
+                for uuid in &bossbar.player {
+                    let Some(player) = server.get_player_by_uuid(*uuid).await else {
+                        continue;
+                    };
 
-            for player in online_players {
-                if bossbar.visible {
                     player.send_bossbar(&bossbar.bossbar_data).await;
-                } else {
+                    bossbar.viewers.insert(*uuid);
+                }
+            } else {
+                for player in bossbar.online_viewers(server).await {
                     player.remove_bossbar(bossbar.bossbar_data.uuid).await;
                 }
+                bossbar.viewers.clear();
             }
 
             return Ok(());
@@ -245,12 +265,7 @@ impl CustomBossbars {
                 return Ok(());
             }
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-            let matching_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
-            for player in matching_players {
+            for player in bossbar.online_viewers(server).await {
                 player
                     .update_bossbar_title(
                         &bossbar.bossbar_data.uuid,
@@ -284,12 +299,7 @@ impl CustomBossbars {
                 return Ok(());
             }
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-            let matching_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
-            for player in matching_players {
+            for player in bossbar.online_viewers(server).await {
                 player
                     .update_bossbar_style(
                         &bossbar.bossbar_data.uuid,
@@ -324,12 +334,7 @@ impl CustomBossbars {
                 return Ok(());
             }
 
-            let players: Vec<Arc<Player>> = server.get_all_players().await;
-            let matching_players: Vec<&Arc<Player>> = players
-                .iter()
-                .filter(|player| bossbar.player.contains(&player.gameprofile.id))
-                .collect();
-            for player in matching_players {
+            for player in bossbar.online_viewers(server).await {
                 player
                     .update_bossbar_style(
                         &bossbar.bossbar_data.uuid,
@@ -346,6 +351,36 @@ impl CustomBossbars {
         ))
     }
 
+    pub async fn update_flags(
+        &mut self,
+        server: &Server,
+        resource_location: &str,
+        new_flags: u8,
+    ) -> Result<(), BossbarUpdateError> {
+        let Some(bossbar) = self.custom_bossbars.get_mut(resource_location) else {
+            return Err(BossbarUpdateError::InvalidResourceLocation(
+                resource_location.to_string(),
+            ));
+        };
+        if bossbar.bossbar_data.flags == new_flags {
+            return Err(BossbarUpdateError::NoChanges("flags", None));
+        }
+
+        bossbar.bossbar_data.flags = new_flags;
+
+        if !bossbar.visible {
+            return Ok(());
+        }
+
+        for player in bossbar.online_viewers(server).await {
+            player
+                .update_bossbar_flags(&bossbar.bossbar_data.uuid, new_flags)
+                .await;
+        }
+
+        Ok(())
+    }
+
     pub async fn update_players(
         &mut self,
         server: &Server,
@@ -372,14 +407,15 @@ impl CustomBossbars {
                 return Err(BossbarUpdateError::NoChanges("players", None));
             }
 
-            if bossbar.visible {
-                for uuid in removed_players {
-                    let Some(player) = server.get_player_by_uuid(uuid).await else {
-                        continue;
-                    };
-
-                    player.remove_bossbar(bossbar.bossbar_data.uuid).await;
+            for uuid in removed_players {
+                if !bossbar.viewers.remove(&uuid) {
+                    continue;
                 }
+                let Some(player) = server.get_player_by_uuid(uuid).await else {
+                    continue;
+                };
+
+                player.remove_bossbar(bossbar.bossbar_data.uuid).await;
             }
 
             bossbar.player = new_players;
@@ -394,6 +430,7 @@ impl CustomBossbars {
                 };
 
                 player.send_bossbar(&bossbar.bossbar_data).await;
+                bossbar.viewers.insert(uuid);
             }
 
             return Ok(());
@@ -1,15 +1,22 @@
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 use pumpkin_data::block_properties::get_state_by_state_id;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::particle::Particle;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::math::boundingbox::BoundingBox;
 use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
 use crate::{
     block::{drop_loot, loot::LootContextParameters, pumpkin_block::ExplodeArgs},
+    entity::{EntityBase, damage::DamageSource, player::Player},
     server::Server,
 };
 
-use super::{BlockFlags, World};
+use super::{BlockFlags, ParticleData, ParticleEffect, World};
 
 pub struct Explosion {
     power: f32,
@@ -71,15 +78,134 @@ impl Explosion {
         set.into_iter().collect()
     }
 
+    /// The fraction of points sampled across the bounding box that have a clear line to the center.
+    ///
+    /// Vanilla: net.minecraft.world.level.ServerExplosion#getSeenPercent
+    pub async fn calculate_exposure(
+        world: &Arc<World>,
+        entity_aabb: &BoundingBox,
+        center: Vector3<f64>,
+    ) -> f64 {
+        const SAMPLES: u8 = 5;
+        let size = entity_aabb.max.sub(&entity_aabb.min);
+        let step = |i: u8| f64::from(i) / f64::from(SAMPLES - 1);
+
+        let mut seen = 0u32;
+        for x in 0..SAMPLES {
+            for y in 0..SAMPLES {
+                for z in 0..SAMPLES {
+                    let point = entity_aabb.min.add_raw(
+                        size.x * step(x),
+                        size.y * step(y),
+                        size.z * step(z),
+                    );
+                    let hit = world
+                        .raycast(point, center, async |pos, world| {
+                            !world.get_block_state(pos).await.collision_shapes.is_empty()
+                        })
+                        .await;
+                    if hit.is_none() {
+                        seen += 1;
+                    }
+                }
+            }
+        }
+        f64::from(seen) / f64::from(SAMPLES).powi(3)
+    }
+
+    /// Damages and knocks back every entity in reach, falling off with distance and cover.
+    async fn damage_entities(&self, world: &Arc<World>) {
+        let radius = f64::from(self.power) * 2.0;
+        let aabb = BoundingBox::new(
+            self.pos.sub_raw(radius + 1.0, radius + 1.0, radius + 1.0),
+            self.pos.add_raw(radius + 1.0, radius + 1.0, radius + 1.0),
+        );
+
+        for entity in world.get_entities_at_box(&aabb).await {
+            self.damage_entity(world, entity.as_ref(), None).await;
+        }
+        for player in world.get_players_at_box(&aabb).await {
+            self.damage_entity(world, player.as_ref(), Some(&player))
+                .await;
+        }
+    }
+
+    async fn damage_entity(
+        &self,
+        world: &Arc<World>,
+        entity_base: &dyn EntityBase,
+        player: Option<&Player>,
+    ) {
+        let entity = entity_base.get_entity();
+        // Spectators are not affected by explosions
+        if entity.no_clip.load(Ordering::Relaxed) {
+            return;
+        }
+        let radius = f64::from(self.power) * 2.0;
+        let pos = entity.pos.load();
+        let distance = pos.squared_distance_to_vec(self.pos).sqrt() / radius;
+        if distance > 1.0 {
+            return;
+        }
+
+        let target_y = if entity.entity_type == EntityType::TNT {
+            pos.y
+        } else {
+            pos.y + f64::from(entity.standing_eye_height)
+        };
+        let direction = Vector3::new(pos.x, target_y, pos.z).sub(&self.pos);
+        if direction.length() == 0.0 {
+            return;
+        }
+        let direction = direction.normalize();
+
+        let exposure = Self::calculate_exposure(world, &entity.bounding_box.load(), self.pos).await;
+        let impact = (1.0 - distance) * exposure;
+        let damage = (f64::midpoint(impact * impact, impact) * 7.0 * radius + 1.0) as f32;
+
+        let blocked = match player {
+            Some(player) => {
+                player.is_blocking_with_shield().await && Self::is_facing(player, self.pos)
+            }
+            None => false,
+        };
+        if blocked {
+            // The shield takes the hit, only the knockback goes through
+            world
+                .play_sound(Sound::ItemShieldBlock, SoundCategory::Players, &pos)
+                .await;
+        } else {
+            entity_base
+                .damage_with_source(damage, DamageSource::at(DamageType::EXPLOSION, self.pos))
+                .await;
+        }
+
+        entity
+            .set_velocity(entity.velocity.load().add(&(direction * impact)))
+            .await;
+    }
+
+    /// Whether the source is in front of the player, where a raised shield covers them.
+    fn is_facing(player: &Player, source: Vector3<f64>) -> bool {
+        let entity = &player.living_entity.entity;
+        let look = Vector3::rotation_vector(0.0, f64::from(entity.yaw.load()));
+        let to_player = entity.pos.load().sub(&source);
+        let to_player = Vector3::new(to_player.x, 0.0, to_player.z).normalize();
+        to_player.x * look.x + to_player.z * look.z < 0.0
+    }
+
     pub async fn explode(&self, server: &Server, world: &Arc<World>) {
         let blocks = self.get_blocks_to_destroy(world).await;
-        // TODO: Entity damage, fire
+        self.damage_entities(world).await;
+        // TODO: fire
+        let mut particles = Vec::new();
         for pos in blocks {
             let block_state = world.get_block_state(&pos).await;
 
             if block_state.is_air() {
                 continue;
             }
+            particles.push(Self::block_particle(&pos));
 
             let block = world.get_block(&pos).await;
             let pumpkin_block = server.block_registry.get_pumpkin_block(block);
@@ -90,6 +216,7 @@ impl Explosion {
                 let params = LootContextParameters {
                     block_state: Some(get_state_by_state_id(block_state.id)),
                     explosion_radius: Some(self.power),
+                    ..Default::default()
                 };
                 drop_loot(world, block, &pos, false, params).await;
             }
@@ -103,5 +230,18 @@ impl Explosion {
                     .await;
             }
         }
+        world.add_particle_burst(particles).await;
+    }
+
+    /// The puff of smoke shown where a block got blown up
+    fn block_particle(pos: &BlockPos) -> ParticleEffect {
+        (
+            pos.to_centered_f64(),
+            Vector3::new(0.25, 0.25, 0.25),
+            0.05,
+            2,
+            Particle::Poof,
+            ParticleData::None,
+        )
     }
 }
@@ -0,0 +1,488 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicI32, Ordering},
+    },
+};
+
+use pumpkin_data::item::Item;
+use pumpkin_nbt::{compound::NbtCompound, nbt_compress, tag::NbtTag};
+use pumpkin_protocol::java::client::play::{CMapData, MapColorPatch, MapDecoration};
+use pumpkin_world::chunk::format::anvil::WORLD_DATA_VERSION;
+use pumpkin_world::item::ItemStack;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use crate::entity::player::Player;
+
+/// Maps are always 128x128 pixels, the scale only changes how many blocks a pixel covers.
+pub const MAP_SIZE: usize = 128;
+pub const MAX_MAP_SCALE: i8 = 4;
+
+/// The server side state of a filled map.
+///
+/// Changes are tracked, so only the changed region gets sent to the players holding the map.
+pub struct MapState {
+    pub id: i32,
+    pub scale: i8,
+    pub center_x: i32,
+    pub center_z: i32,
+    pub dimension: String,
+    locked: bool,
+    colors: Box<[u8]>,
+    decorations: Vec<MapDecoration>,
+    /// Changed rectangle since the last update as `(min_x, min_z, max_x, max_z)`
+    dirty_region: Option<(usize, usize, usize, usize)>,
+    /// Whether the decorations or flags changed since the last update
+    meta_dirty: bool,
+    /// Whether there are changes which aren't saved yet
+    unsaved: bool,
+    /// Players which already received the whole map
+    viewers: HashSet<Uuid>,
+}
+
+impl MapState {
+    #[must_use]
+    pub fn new(id: i32, scale: i8, center_x: i32, center_z: i32, dimension: String) -> Self {
+        Self {
+            id,
+            scale: scale.clamp(0, MAX_MAP_SCALE),
+            center_x,
+            center_z,
+            dimension,
+            locked: false,
+            colors: vec![0; MAP_SIZE * MAP_SIZE].into_boxed_slice(),
+            decorations: Vec::new(),
+            dirty_region: None,
+            meta_dirty: false,
+            unsaved: true,
+            viewers: HashSet::new(),
+        }
+    }
+
+    /// Creates a filled map item showing this map.
+    #[must_use]
+    pub fn to_item_stack(&self) -> ItemStack {
+        let mut stack = ItemStack::new(1, &Item::FILLED_MAP);
+        stack.map_id = Some(self.id);
+        stack
+    }
+
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Locked maps don't get updated by exploring anymore, like maps locked in a cartography table.
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+        self.meta_dirty = true;
+        self.unsaved = true;
+    }
+
+    #[must_use]
+    pub fn get_pixel(&self, x: usize, z: usize) -> u8 {
+        self.colors[z * MAP_SIZE + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, z: usize, color: u8) {
+        self.set_pixels(x, z, 1, &[color]);
+    }
+
+    /// Sets a rectangle of pixels, `colors` is row-major and `width` pixels wide.
+    ///
+    /// Pixels outside of the map are ignored.
+    pub fn set_pixels(&mut self, start_x: usize, start_z: usize, width: usize, colors: &[u8]) {
+        if width == 0 {
+            return;
+        }
+        let mut changed: Option<(usize, usize, usize, usize)> = None;
+        for (i, &color) in colors.iter().enumerate() {
+            let (x, z) = (start_x + i % width, start_z + i / width);
+            if x >= MAP_SIZE || z >= MAP_SIZE {
+                continue;
+            }
+            let pixel = &mut self.colors[z * MAP_SIZE + x];
+            if *pixel == color {
+                continue;
+            }
+            *pixel = color;
+            changed = Some(match changed {
+                Some((min_x, min_z, max_x, max_z)) => {
+                    (min_x.min(x), min_z.min(z), max_x.max(x), max_z.max(z))
+                }
+                None => (x, z, x, z),
+            });
+        }
+        if let Some((min_x, min_z, max_x, max_z)) = changed {
+            self.dirty_region = Some(match self.dirty_region {
+                Some((old_min_x, old_min_z, old_max_x, old_max_z)) => (
+                    old_min_x.min(min_x),
+                    old_min_z.min(min_z),
+                    old_max_x.max(max_x),
+                    old_max_z.max(max_z),
+                ),
+                None => (min_x, min_z, max_x, max_z),
+            });
+            self.unsaved = true;
+        }
+    }
+
+    #[must_use]
+    pub fn decorations(&self) -> &[MapDecoration] {
+        &self.decorations
+    }
+
+    /// Replaces the markers shown on the map.
+    pub fn set_decorations(&mut self, decorations: Vec<MapDecoration>) {
+        self.decorations = decorations;
+        self.meta_dirty = true;
+    }
+
+    fn full_packet(&self) -> CMapData<'_> {
+        CMapData::new(
+            self.id.into(),
+            self.scale,
+            self.locked,
+            Some(&self.decorations),
+            Some(MapColorPatch {
+                width: MAP_SIZE as u8,
+                height: MAP_SIZE as u8,
+                start_x: 0,
+                start_z: 0,
+                colors: &self.colors,
+            }),
+        )
+    }
+
+    /// Forgets the viewers which stopped holding a map and returns whether anything has to be
+    /// sent: the map changed, or someone new is holding it.
+    fn needs_update(&mut self, holders: &[Uuid]) -> bool {
+        // Players which aren't holding the map anymore miss updates, so they need the whole map again
+        self.viewers.retain(|uuid| holders.contains(uuid));
+        self.dirty_region.is_some()
+            || self.meta_dirty
+            || holders.iter().any(|holder| !self.viewers.contains(holder))
+    }
+
+    /// Sends the whole map to new holders and the changes to everyone else holding it.
+    async fn send_updates(&mut self, holders: &[&Arc<Player>]) {
+        let holder_ids: Vec<_> = holders.iter().map(|holder| holder.gameprofile.id).collect();
+        if !self.needs_update(&holder_ids) {
+            return;
+        }
+
+        let mut delta_colors = Vec::new();
+        let patch = self
+            .dirty_region
+            .take()
+            .map(|(min_x, min_z, max_x, max_z)| {
+                // Only send the changed rectangle instead of the whole 128x128 buffer
+                let (width, height) = (max_x - min_x + 1, max_z - min_z + 1);
+                delta_colors.reserve(width * height);
+                for z in min_z..=max_z {
+                    let row = z * MAP_SIZE;
+                    delta_colors.extend_from_slice(&self.colors[row + min_x..=row + max_x]);
+                }
+                (width as u8, height as u8, min_x as u8, min_z as u8)
+            });
+        let delta = (patch.is_some() || self.meta_dirty).then(|| {
+            CMapData::new(
+                self.id.into(),
+                self.scale,
+                self.locked,
+                self.meta_dirty.then_some(self.decorations.as_slice()),
+                patch.map(|(width, height, start_x, start_z)| MapColorPatch {
+                    width,
+                    height,
+                    start_x,
+                    start_z,
+                    colors: &delta_colors,
+                }),
+            )
+        });
+        self.meta_dirty = false;
+
+        for holder in holders {
+            if self.viewers.contains(&holder.gameprofile.id) {
+                if let Some(delta) = &delta {
+                    holder.client.enqueue_packet(delta).await;
+                }
+            } else {
+                holder.client.enqueue_packet(&self.full_packet()).await;
+            }
+        }
+        self.viewers
+            .extend(holders.iter().map(|holder| holder.gameprofile.id));
+    }
+
+    fn write_nbt(&self) -> NbtCompound {
+        let mut data = NbtCompound::new();
+        data.put_byte("scale", self.scale);
+        data.put_string("dimension", self.dimension.clone());
+        data.put_int("xCenter", self.center_x);
+        data.put_int("zCenter", self.center_z);
+        data.put_bool("locked", self.locked);
+        data.put_bool("trackingPosition", false);
+        data.put_bool("unlimitedTracking", false);
+        data.put("colors", NbtTag::ByteArray(self.colors.clone()));
+        data.put_list("banners", Vec::new());
+        data.put_list("frames", Vec::new());
+
+        let mut nbt = NbtCompound::new();
+        nbt.put_component("data", data);
+        nbt.put_int("DataVersion", WORLD_DATA_VERSION);
+        nbt
+    }
+
+    fn read_nbt(id: i32, nbt: &NbtCompound) -> Option<Self> {
+        let data = nbt.get_compound("data")?;
+        let mut map = Self::new(
+            id,
+            data.get_byte("scale").unwrap_or(0),
+            data.get_int("xCenter").unwrap_or(0),
+            data.get_int("zCenter").unwrap_or(0),
+            data.get_string("dimension")
+                .cloned()
+                .unwrap_or_else(|| "minecraft:overworld".to_string()),
+        );
+        map.locked = data.get_bool("locked").unwrap_or(false);
+        if let Some(colors) = data.get("colors").and_then(NbtTag::extract_byte_array) {
+            if colors.len() == map.colors.len() {
+                map.colors = colors;
+            }
+        }
+        map.unsaved = false;
+        Some(map)
+    }
+}
+
+/// The maps shown by the items in both hands, a map held twice counts once.
+fn held_maps(main_hand: Option<i32>, off_hand: Option<i32>) -> impl Iterator<Item = i32> {
+    main_hand
+        .into_iter()
+        .chain(off_hand.filter(|id| main_hand != Some(*id)))
+}
+
+/// All maps of the server, stored in the `data` folder of the world like vanilla.
+pub struct MapStore {
+    data_path: PathBuf,
+    maps: RwLock<HashMap<i32, Arc<Mutex<MapState>>>>,
+    next_id: AtomicI32,
+}
+
+impl MapStore {
+    #[must_use]
+    pub fn new(data_path: impl Into<PathBuf>) -> Self {
+        let data_path = data_path.into();
+        let next_id = File::open(data_path.join("idcounts.dat"))
+            .ok()
+            .and_then(|file| nbt_compress::read_gzip_compound_tag(file).ok())
+            .and_then(|nbt| nbt.get_compound("data")?.get_int("map"))
+            .map_or(0, |last_id| last_id + 1);
+        Self {
+            data_path,
+            maps: RwLock::new(HashMap::new()),
+            next_id: AtomicI32::new(next_id),
+        }
+    }
+
+    fn map_path(&self, id: i32) -> PathBuf {
+        self.data_path.join(format!("map_{id}.dat"))
+    }
+
+    /// Creates a new empty map.
+    pub async fn create(
+        &self,
+        scale: i8,
+        center_x: i32,
+        center_z: i32,
+        dimension: String,
+    ) -> Arc<Mutex<MapState>> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let map = Arc::new(Mutex::new(MapState::new(
+            id, scale, center_x, center_z, dimension,
+        )));
+        self.maps.write().await.insert(id, map.clone());
+        map
+    }
+
+    /// Gets a map, loading it from disk if needed.
+    pub async fn get(&self, id: i32) -> Option<Arc<Mutex<MapState>>> {
+        if let Some(map) = self.maps.read().await.get(&id) {
+            return Some(map.clone());
+        }
+        let nbt = nbt_compress::read_gzip_compound_tag(File::open(self.map_path(id)).ok()?)
+            .map_err(|err| log::warn!("Failed to read map {id}: {err}"))
+            .ok()?;
+        let map = Arc::new(Mutex::new(MapState::read_nbt(id, &nbt)?));
+        Some(self.maps.write().await.entry(id).or_insert(map).clone())
+    }
+
+    /// Sends the changed maps to the players holding them, and the whole maps to players which
+    /// just started holding one.
+    pub async fn tick(&self, players: &[Arc<Player>]) {
+        let mut holders: HashMap<i32, Vec<&Arc<Player>>> = HashMap::new();
+        for player in players {
+            let main_hand = player.inventory.held_item().lock().await.map_id;
+            let off_hand = player.inventory.off_hand_item().await.lock().await.map_id;
+            for map_id in held_maps(main_hand, off_hand) {
+                holders.entry(map_id).or_default().push(player);
+            }
+        }
+        for (id, map) in self.maps.read().await.iter() {
+            let holders = holders.get(id).map_or(&[][..], Vec::as_slice);
+            map.lock().await.send_updates(holders).await;
+        }
+    }
+
+    /// Unloads the saved maps which nobody is looking at or using, they are read again from disk
+    /// when needed.
+    async fn prune(&self) {
+        let mut maps = self.maps.write().await;
+        maps.retain(|_, map| {
+            // Someone else still uses the map
+            if Arc::strong_count(map) > 1 {
+                return true;
+            }
+            !map.try_lock()
+                .is_ok_and(|map| !map.unsaved && map.viewers.is_empty())
+        });
+    }
+
+    /// Saves all changed maps and the id counter, then unloads the unused ones.
+    pub async fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.data_path)?;
+        for (id, map) in self.maps.read().await.iter() {
+            let mut map = map.lock().await;
+            if !map.unsaved {
+                continue;
+            }
+            nbt_compress::write_gzip_compound_tag(
+                &map.write_nbt(),
+                File::create(self.map_path(*id))?,
+            )
+            .map_err(io::Error::other)?;
+            map.unsaved = false;
+        }
+        self.prune().await;
+
+        let next_id = self.next_id.load(Ordering::Relaxed);
+        if next_id > 0 {
+            let mut data = NbtCompound::new();
+            data.put_int("map", next_id - 1);
+            let mut nbt = NbtCompound::new();
+            nbt.put_component("data", data);
+            nbt.put_int("DataVersion", WORLD_DATA_VERSION);
+            nbt_compress::write_gzip_compound_tag(
+                &nbt,
+                File::create(self.data_path.join("idcounts.dat"))?,
+            )
+            .map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::{MapState, MapStore, held_maps};
+
+    #[tokio::test]
+    async fn maps_are_saved_and_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MapStore::new(dir.path());
+        let map = store
+            .create(2, 64, -128, "minecraft:the_nether".to_string())
+            .await;
+        let id = {
+            let mut map = map.lock().await;
+            map.set_pixels(10, 20, 2, &[1, 2, 3, 4]);
+            map.set_locked(true);
+            map.id
+        };
+        drop(map);
+        store.save().await.unwrap();
+
+        let store = MapStore::new(dir.path());
+        let map = store.get(id).await.unwrap();
+        let map = map.lock().await;
+        assert_eq!((map.scale, map.center_x, map.center_z), (2, 64, -128));
+        assert_eq!(map.dimension, "minecraft:the_nether");
+        assert!(map.is_locked());
+        assert_eq!(map.get_pixel(11, 21), 4);
+        assert_eq!(map.get_pixel(0, 0), 0);
+        drop(map);
+        // The id counter is saved too
+        let next = store
+            .create(0, 0, 0, "minecraft:overworld".to_string())
+            .await;
+        assert_eq!(next.lock().await.id, id + 1);
+    }
+
+    #[tokio::test]
+    async fn unused_maps_are_unloaded_after_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MapStore::new(dir.path());
+        let unused = store
+            .create(0, 0, 0, "minecraft:overworld".to_string())
+            .await;
+        let unused_id = unused.lock().await.id;
+        drop(unused);
+        let viewed = store
+            .create(0, 0, 0, "minecraft:overworld".to_string())
+            .await;
+        let viewed_id = {
+            let mut viewed = viewed.lock().await;
+            viewed.viewers.insert(Uuid::new_v4());
+            viewed.id
+        };
+        drop(viewed);
+        let in_use = store
+            .create(0, 0, 0, "minecraft:overworld".to_string())
+            .await;
+
+        store.save().await.unwrap();
+        let loaded = store.maps.read().await;
+        assert!(!loaded.contains_key(&unused_id));
+        assert!(loaded.contains_key(&viewed_id));
+        assert!(loaded.contains_key(&in_use.lock().await.id));
+        drop(loaded);
+        // Unloaded maps are read from disk again
+        assert!(store.get(unused_id).await.is_some());
+    }
+
+    #[test]
+    fn players_only_hold_the_maps_in_their_hands() {
+        assert_eq!(held_maps(Some(1), Some(2)).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(held_maps(Some(3), Some(3)).collect::<Vec<_>>(), [3]);
+        assert_eq!(held_maps(None, None).count(), 0);
+        let map = MapState::new(5, 0, 0, 0, "minecraft:overworld".to_string());
+        assert_eq!(map.to_item_stack().map_id, Some(5));
+    }
+
+    #[test]
+    fn only_changed_maps_need_updates() {
+        let holder = Uuid::new_v4();
+        let mut map = MapState::new(0, 0, 0, 0, "minecraft:overworld".to_string());
+        // New holders get the whole map
+        assert!(map.needs_update(&[holder]));
+        map.viewers.insert(holder);
+        map.dirty_region = None;
+        assert!(!map.needs_update(&[holder]));
+
+        map.set_pixel(1, 1, 5);
+        assert!(map.needs_update(&[holder]));
+        map.dirty_region = None;
+
+        // Putting the map away and taking it out again sends the whole map again
+        assert!(!map.needs_update(&[]));
+        assert!(map.needs_update(&[holder]));
+    }
+}
@@ -1,10 +1,13 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap, HashSet},
+    num::NonZeroI32,
     sync::{Arc, atomic::Ordering},
 };
 
 pub mod chunker;
 pub mod explosion;
+pub mod map;
+pub mod particle;
 pub mod portal;
 pub mod time;
 
@@ -16,7 +19,7 @@ use crate::{
         registry::BlockRegistry,
     },
     command::client_suggestions,
-    entity::{Entity, EntityBase, EntityId, player::Player, r#type::from_type},
+    entity::{Entity, EntityBase, EntityId, leash::LeashIndex, player::Player, r#type::from_type},
     error::PumpkinError,
     net::ClientPlatform,
     plugin::{
@@ -32,9 +35,11 @@ use crate::{
 };
 use async_trait::async_trait;
 use border::Worldborder;
+use bossbar::{Bossbar, WorldBossbar};
 use bytes::BufMut;
 use explosion::Explosion;
-use pumpkin_config::BasicConfiguration;
+use particle::ParticleData;
+use pumpkin_config::{BASIC_CONFIG, BasicConfiguration, advanced_config};
 use pumpkin_data::BlockDirection;
 use pumpkin_data::entity::EffectType;
 use pumpkin_data::fluid::{Falling, FluidProperties};
@@ -84,8 +89,8 @@ use pumpkin_protocol::{
 use pumpkin_protocol::{
     codec::var_int::VarInt,
     java::client::play::{
-        CBlockUpdate, CDisguisedChatMessage, CExplosion, CRespawn, CSetBlockDestroyStage,
-        CWorldEvent,
+        CBlockUpdate, CBundleDelimiter, CDisguisedChatMessage, CExplosion, CParticle, CRespawn,
+        CSetBlockDestroyStage, CUpdateLight, CWorldEvent,
     },
 };
 use pumpkin_registry::VanillaDimensionType;
@@ -93,33 +98,45 @@ use pumpkin_util::math::{position::chunk_section_from_pos, vector2::Vector2};
 use pumpkin_util::resource_location::ResourceLocation;
 use pumpkin_util::text::{TextComponent, color::NamedColor};
 use pumpkin_util::{
-    Difficulty,
+    Difficulty, HeightMap,
     math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
 };
 use pumpkin_world::{
-    BlockStateId, GENERATION_SETTINGS, GeneratorSetting, biome, block::entities::BlockEntity,
-    chunk::io::Dirtiable, item::ItemStack, world::SimpleWorld,
+    BlockStateId, biome, block::entities::BlockEntity, chunk::io::Dirtiable,
+    generation::settings::GenerationSettings, item::ItemStack, world::SimpleWorld,
+};
+use pumpkin_world::{
+    chunk::{
+        ChunkData,
+        anti_xray::{ChunkNeighbors, ChunkObfuscator},
+        light::recalculate_block_light,
+        palette::BlockPalette,
+    },
+    world::BlockAccessor,
+};
+use pumpkin_world::{
+    chunk::{TickPriority, structure::StructureStart},
+    level::{Level, SyncChunk},
 };
-use pumpkin_world::{chunk::ChunkData, world::BlockAccessor};
-use pumpkin_world::{chunk::TickPriority, level::Level};
 use pumpkin_world::{
     entity::entity_data_flags::{DATA_PLAYER_MAIN_HAND, DATA_PLAYER_MODE_CUSTOMISATION},
     world::GetBlockError,
 };
 use pumpkin_world::{world::BlockFlags, world_info::LevelData};
 use rand::{Rng, rng};
-use scoreboard::Scoreboard;
 use serde::Serialize;
 use time::LevelTime;
 use tokio::sync::Mutex;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 pub mod border;
 pub mod bossbar;
 pub mod custom_bossbar;
 pub mod scoreboard;
+pub mod spread;
 pub mod weather;
 
+use portal::nether::NetherPortal;
 use uuid::Uuid;
 use weather::Weather;
 
@@ -139,6 +156,38 @@ impl PumpkinError for GetBlockError {
     }
 }
 
+/// The most effects sent by a single [`World::add_particle_burst`]
+pub const MAX_PARTICLE_BURST: usize = 256;
+
+/// A particle effect of a burst as `(position, offset, max_speed, particle_count, particle, data)`,
+/// `data` holds the options particles like `dust` or `block` need
+pub type ParticleEffect = (Vector3<f64>, Vector3<f32>, f32, i32, Particle, ParticleData);
+
+/// Groups the indices of block changes by chunk, keeping their order inside each chunk. Chunks
+/// are sorted so batches always lock them in the same order.
+fn changes_by_chunk(changes: &[(BlockPos, BlockStateId)]) -> Vec<(Vector2<i32>, Vec<usize>)> {
+    let mut by_chunk: HashMap<Vector2<i32>, Vec<usize>> = HashMap::new();
+    for (index, (position, _)) in changes.iter().enumerate() {
+        let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
+        by_chunk.entry(chunk_coordinate).or_default().push(index);
+    }
+    let mut by_chunk: Vec<_> = by_chunk.into_iter().collect();
+    by_chunk.sort_unstable_by_key(|(chunk, _)| (chunk.x, chunk.y));
+    by_chunk
+}
+
+/// The effects of a burst which a player at `viewer` can see.
+fn particle_effects_in_range(
+    effects: &[ParticleEffect],
+    viewer: Vector3<f64>,
+    max_distance: f64,
+) -> impl Iterator<Item = &ParticleEffect> {
+    let max_distance_squared = max_distance * max_distance;
+    effects.iter().filter(move |(position, ..)| {
+        viewer.squared_distance_to_vec(*position) <= max_distance_squared
+    })
+}
+
 /// Represents a Minecraft world, containing entities, players, and the underlying level data.
 ///
 /// Each dimension (Overworld, Nether, End) typically has its own `World`.
@@ -157,8 +206,8 @@ pub struct World {
     /// A map of active entities within the world, keyed by their unique UUID.
     /// This does not include players.
     pub entities: Arc<RwLock<HashMap<uuid::Uuid, Arc<dyn EntityBase>>>>,
-    /// The world's scoreboard, used for tracking scores, objectives, and display information.
-    pub scoreboard: Mutex<Scoreboard>,
+    /// The same entities as `entities`, keyed by their entity id. Always locked after `entities`.
+    entities_by_id: RwLock<HashMap<EntityId, Arc<dyn EntityBase>>>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
     pub worldborder: Mutex<Worldborder>,
     /// The world's time, including counting ticks for weather, time cycles, and statistics.
@@ -168,11 +217,23 @@ pub struct World {
     pub sea_level: i32,
     /// The world's weather, including rain and thunder levels.
     pub weather: Mutex<Weather>,
+    /// The lower corners of the nether portals in this world, so entities travelling here can find them.
+    pub nether_portals: Mutex<HashSet<BlockPos>>,
+    /// The leashed entities and leash knots of this world.
+    pub leashes: Mutex<LeashIndex>,
+    /// The bossbars belonging to this world, keyed by their UUID.
+    pub bossbars: Mutex<HashMap<Uuid, WorldBossbar>>,
     /// Block Behaviour
     pub block_registry: Arc<BlockRegistry>,
     synced_block_event_queue: Mutex<Vec<BlockEvent>>,
     /// A map of unsent block changes, keyed by block position.
     unsent_block_changes: Mutex<HashMap<BlockPos, u16>>,
+    /// The light sections changed since the last update, keyed by chunk position.
+    unsent_light_changes: Mutex<HashMap<Vector2<i32>, BTreeSet<usize>>>,
+    /// Hides blocks like ores in the chunks sent to players, if anti-xray is enabled.
+    pub anti_xray: Option<ChunkObfuscator>,
+    /// The structures starting in each chunk searched by `/locate`, so chunks are only read once.
+    structure_starts: RwLock<HashMap<Vector2<i32>, Vec<StructureStart>>>,
 }
 
 impl World {
@@ -183,34 +244,30 @@ impl World {
         dimension_type: VanillaDimensionType,
         block_registry: Arc<BlockRegistry>,
     ) -> Self {
-        // TODO
-        let generation_settings = match dimension_type {
-            VanillaDimensionType::Overworld => GENERATION_SETTINGS
-                .get(&GeneratorSetting::Overworld)
-                .unwrap(),
-            VanillaDimensionType::OverworldCaves => todo!(),
-            VanillaDimensionType::TheEnd => {
-                GENERATION_SETTINGS.get(&GeneratorSetting::End).unwrap()
-            }
-            VanillaDimensionType::TheNether => {
-                GENERATION_SETTINGS.get(&GeneratorSetting::Nether).unwrap()
-            }
-        };
+        let sea_level = level.generation_settings().sea_level;
+        let level_data_path = level.level_folder().root_folder.join("data");
+        let weather = Weather::load(dimension_type, &level_info);
 
         Self {
             level: Arc::new(level),
             level_info: Arc::new(RwLock::new(level_info)),
             players: Arc::new(RwLock::new(HashMap::new())),
             entities: Arc::new(RwLock::new(HashMap::new())),
-            scoreboard: Mutex::new(Scoreboard::new()),
+            entities_by_id: RwLock::new(HashMap::new()),
             worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 30_000_000.0, 0, 0, 0)),
             level_time: Mutex::new(LevelTime::new()),
             dimension_type,
-            weather: Mutex::new(Weather::new()),
+            weather: Mutex::new(weather),
+            nether_portals: Mutex::new(NetherPortal::load_index(&level_data_path)),
+            leashes: Mutex::new(LeashIndex::default()),
+            bossbars: Mutex::new(HashMap::new()),
             block_registry,
-            sea_level: generation_settings.sea_level,
+            sea_level,
             synced_block_event_queue: Mutex::new(Vec::new()),
             unsent_block_changes: Mutex::new(HashMap::new()),
+            unsent_light_changes: Mutex::new(HashMap::new()),
+            anti_xray: Self::create_chunk_obfuscator(dimension_type),
+            structure_starts: RwLock::new(HashMap::new()),
         }
     }
 
@@ -218,9 +275,18 @@ impl World {
         for (uuid, entity) in self.entities.read().await.iter() {
             self.save_entity(uuid, entity).await;
         }
+        self.save_nether_portals().await;
         self.level.shutdown().await;
     }
 
+    /// Saves the nether portals of this world, so entities can find them after a restart.
+    pub async fn save_nether_portals(&self) {
+        let data_path = self.level.level_folder().root_folder.join("data");
+        if let Err(err) = NetherPortal::save_index(&data_path, &*self.nether_portals.lock().await) {
+            log::error!("Failed to save the nether portals: {err}");
+        }
+    }
+
     async fn save_entity(&self, uuid: &uuid::Uuid, entity: &Arc<dyn EntityBase>) {
         // First lets see if the entity was saved on an other chunk, and if the current chunk does not match we remove it
         // Otherwise we just update the nbt data
@@ -357,7 +423,16 @@ impl World {
         chat_message: &SChatMessage,
         decorated_message: &TextComponent,
     ) {
-        let messages_sent: i32 = sender.chat_session.lock().await.messages_sent;
+        // Only signed messages can be sent as player chat, unsigned ones must use `broadcast_message`
+        let Some(signature) = &chat_message.signature else {
+            log::warn!(
+                "Tried to broadcast an unsigned chat message from {}",
+                sender.gameprofile.name
+            );
+            return;
+        };
+        // The message was added to the sender's chain when it was validated
+        let message_index: i32 = sender.chat_session.lock().await.messages_sent - 1;
         let sender_last_seen = {
             let cache = sender.signature_cache.lock().await;
             cache.last_seen.clone()
@@ -368,8 +443,8 @@ impl World {
             let packet = &CPlayerChatMessage::new(
                 VarInt(messages_received),
                 sender.gameprofile.id,
-                VarInt(messages_sent),
-                chat_message.signature.clone(),
+                VarInt(message_index),
+                Some(signature.clone()),
                 chat_message.message.clone(),
                 chat_message.timestamp,
                 chat_message.salt,
@@ -382,21 +457,15 @@ impl World {
             );
             recipient.client.enqueue_packet(packet).await;
 
+            // The recipient's cache has to be updated after the packet was built,
+            // as the last seen signatures are indexed against the cache the client has right now
             recipient
                 .signature_cache
                 .lock()
                 .await
-                .add_seen_signature(&chat_message.signature.clone().unwrap()); // Unwrap is safe because we check for None in validate_chat_message
-
-            let recipient_signature_cache = &mut recipient.signature_cache.lock().await;
-            if recipient.gameprofile.id != sender.gameprofile.id {
-                // Sender may update recipient on signatures recipient hasn't seen
-                recipient_signature_cache.cache_signatures(sender_last_seen.as_ref());
-            }
+                .add_message(sender_last_seen.as_ref(), signature);
             recipient.chat_session.lock().await.messages_received += 1;
         }
-
-        sender.chat_session.lock().await.messages_sent += 1;
     }
 
     /// Broadcasts a packet to all connected players within the world, excluding the specified players.
@@ -429,20 +498,110 @@ impl World {
         max_speed: f32,
         particle_count: i32,
         particle: Particle,
+    ) {
+        self.spawn_particle_with_data(
+            position,
+            offset,
+            max_speed,
+            particle_count,
+            particle,
+            ParticleData::None,
+            false,
+        )
+        .await;
+    }
+
+    /// Sends a particle with options to every player close enough to see it.
+    ///
+    /// See [`Player::spawn_particle`] for what forcing it does.
+    #[expect(clippy::too_many_arguments)]
+    pub async fn spawn_particle_with_data(
+        &self,
+        position: Vector3<f64>,
+        offset: Vector3<f32>,
+        max_speed: f32,
+        particle_count: i32,
+        particle: Particle,
+        data: ParticleData,
+        force: bool,
     ) {
         let players = self.players.read().await;
         for player in players.values() {
             player
-                .spawn_particle(position, offset, max_speed, particle_count, particle)
+                .spawn_particle(
+                    position,
+                    offset,
+                    max_speed,
+                    particle_count,
+                    particle,
+                    data,
+                    force,
+                )
                 .await;
         }
     }
 
+    /// Sends many particle effects at once, e.g. for explosions or fireworks, instead of calling
+    /// [`World::spawn_particle`] in a loop.
+    ///
+    /// Each player only gets the effects within the configured `particle_burst_distance` of them,
+    /// and only the first [`MAX_PARTICLE_BURST`] effects are sent. The effects are bundled so the
+    /// client shows them all in the same frame. Each effect carries its own [`ParticleData`], so
+    /// bursts can also show particles like `dust` or `block` which need options.
+    pub async fn add_particle_burst(&self, effects: Vec<ParticleEffect>) {
+        let effects = &effects[..effects.len().min(MAX_PARTICLE_BURST)];
+        if effects.is_empty() {
+            return;
+        }
+        let max_distance = advanced_config().world.particle_burst_distance;
+
+        for player in self.players.read().await.values() {
+            let mut in_range =
+                particle_effects_in_range(effects, player.position(), max_distance).peekable();
+            if in_range.peek().is_none() {
+                continue;
+            }
+            player.client.enqueue_packet(&CBundleDelimiter).await;
+            for &(position, offset, max_speed, particle_count, particle, data) in in_range {
+                player
+                    .client
+                    .enqueue_packet(&CParticle::new(
+                        false,
+                        false,
+                        position,
+                        offset,
+                        max_speed,
+                        particle_count,
+                        VarInt(particle as i32),
+                        &data.to_bytes(),
+                    ))
+                    .await;
+            }
+            player.client.enqueue_packet(&CBundleDelimiter).await;
+        }
+    }
+
     pub async fn play_sound(&self, sound: Sound, category: SoundCategory, position: &Vector3<f64>) {
         self.play_sound_raw(sound as u16, category, position, 1.0, 1.0)
             .await;
     }
 
+    /// Plays a sound from the eyes of an entity, with the pitch varying slightly like vanilla.
+    pub async fn play_sound_at_entity(
+        &self,
+        entity: &Entity,
+        sound: Sound,
+        category: SoundCategory,
+        volume: f32,
+        pitch: f32,
+    ) {
+        let pitch = pitch * rng().random_range(0.95..1.05);
+        let mut position = entity.pos.load();
+        position.y += f64::from(entity.standing_eye_height);
+        self.play_sound_raw(sound as u16, category, &position, volume, pitch)
+            .await;
+    }
+
     pub async fn play_sound_expect(
         &self,
         player: &Player,
@@ -520,15 +679,20 @@ impl World {
         self.flush_synced_block_events().await;
 
         // world ticks
+        let daylight_cycle = self.level_info.read().await.game_rules.do_daylight_cycle;
         let mut level_time = self.level_time.lock().await;
-        level_time.tick_time();
+        level_time.tick_time(daylight_cycle);
         let mut weather = self.weather.lock().await;
         weather.tick_weather(self).await;
+        self.worldborder.lock().await.tick();
 
         if self.should_skip_night().await {
-            let time = level_time.time_of_day + 24000;
-            level_time.set_time(time - time % 24000);
-            level_time.send_time(self).await;
+            // Players still wake up when the daylight cycle is frozen, but the time stays
+            if daylight_cycle {
+                let time = level_time.time_of_day + 24000;
+                level_time.set_time(time - time % 24000);
+                level_time.send_time(self).await;
+            }
 
             for player in self.players.read().await.values() {
                 player.wake_up().await;
@@ -543,9 +707,18 @@ impl World {
         drop(level_time);
         drop(weather);
 
+        // Only chunks within the simulation distance of a player get ticked
+        let player_chunks: Vec<_> = self
+            .players
+            .read()
+            .await
+            .values()
+            .map(|player| player.living_entity.entity.chunk_pos.load())
+            .collect();
+
         let chunk_start = tokio::time::Instant::now();
         log::debug!("Ticking chunks");
-        self.tick_chunks().await;
+        self.tick_chunks(&player_chunks).await;
         let elapsed = chunk_start.elapsed();
 
         let players_to_tick: Vec<_> = self.players.read().await.values().cloned().collect();
@@ -561,16 +734,21 @@ impl World {
         log::debug!("Ticking entities");
         // Entity ticks
         for entity in entities_to_tick {
+            let chunk_pos = entity.get_entity().chunk_pos.load();
+            if !Self::is_simulated(&player_chunks, chunk_pos) {
+                continue;
+            }
             entity.tick(entity.clone(), server).await;
             for player in self.players.read().await.values() {
-                if player
-                    .living_entity
-                    .entity
-                    .bounding_box
-                    .load()
-                    // This is vanilla, but TODO: change this when is in a vehicle
-                    .expand(1.0, 0.5, 1.0)
-                    .intersects(&entity.get_entity().bounding_box.load())
+                if !player.living_entity.entity.no_clip.load(Ordering::Relaxed)
+                    && player
+                        .living_entity
+                        .entity
+                        .bounding_box
+                        .load()
+                        // This is vanilla, but TODO: change this when is in a vehicle
+                        .expand(1.0, 0.5, 1.0)
+                        .intersects(&entity.get_entity().bounding_box.load())
                 {
                     entity.on_player_collision(player).await;
                     break;
@@ -598,26 +776,93 @@ impl World {
 
         // TODO: only send packet to players who have the chunks loaded
         // TODO: Send light updates to update the wire directly next to a broken block
-        for chunk_section in block_state_updates_by_chunk_section.values() {
-            if chunk_section.is_empty() {
+        for (chunk_section, updates) in block_state_updates_by_chunk_section {
+            if updates.is_empty() {
                 continue;
             }
-            if chunk_section.len() == 1 {
-                let (block_pos, block_state_id) = chunk_section[0];
+            if updates.len() == 1 {
+                let (block_pos, block_state_id) = updates[0];
                 self.broadcast_packet_all(&CBlockUpdate::new(
                     block_pos,
                     i32::from(block_state_id).into(),
                 ))
                 .await;
             } else {
-                self.broadcast_packet_all(&CMultiBlockUpdate::new(chunk_section.clone()))
+                self.broadcast_packet_all(&CMultiBlockUpdate::new(chunk_section, updates))
                     .await;
             }
         }
+
+        self.resend_changed_chunks().await;
+
+        let light_changes: Vec<_> = self.unsent_light_changes.lock().await.drain().collect();
+        for (chunk_coordinate, sections) in light_changes {
+            let chunk = self.level.get_chunk(chunk_coordinate).await;
+            let block_light = {
+                let chunk = chunk.read().await;
+                sections
+                    .into_iter()
+                    .map(|index| (index, chunk.light_engine.block_light[index].clone()))
+                    .collect()
+            };
+            self.broadcast_packet_all(&CUpdateLight::new(
+                chunk_coordinate.x.into(),
+                chunk_coordinate.y.into(),
+                block_light,
+            ))
+            .await;
+        }
     }
 
-    pub async fn tick_chunks(self: &Arc<Self>) {
-        let tick_data = self.level.get_tick_data().await;
+    /// Queues the chunks again which got feature blocks of a neighbour set after they were loaded,
+    /// for every player that can see them.
+    async fn resend_changed_chunks(&self) {
+        let changed_chunks = self.level.take_changed_chunks().await;
+        if changed_chunks.is_empty() {
+            return;
+        }
+        let players = self.players.read().await;
+        for position in changed_chunks {
+            let Some(chunk) = self
+                .level
+                .try_get_chunk(position)
+                .map(|chunk| chunk.clone())
+            else {
+                continue;
+            };
+            for player in players.values() {
+                if !player
+                    .watched_section
+                    .load()
+                    .is_within_distance(position.x, position.y)
+                {
+                    continue;
+                }
+                let mut chunk_manager = player.chunk_manager.lock().await;
+                // Chunks still waiting to be sent already contain the blocks
+                if !chunk_manager.is_chunk_pending(&position) {
+                    chunk_manager.push_chunk(position, chunk.clone());
+                }
+            }
+        }
+    }
+
+    /// Whether a chunk is within the simulation distance of one of the given player chunks.
+    ///
+    /// Like vanilla the distance is measured in chunks along each axis.
+    fn is_simulated(player_chunks: &[Vector2<i32>], chunk: Vector2<i32>) -> bool {
+        let distance = NonZeroI32::from(BASIC_CONFIG.simulation_distance).get();
+        player_chunks.iter().any(|player_chunk| {
+            (player_chunk.x - chunk.x).abs() <= distance
+                && (player_chunk.y - chunk.y).abs() <= distance
+        })
+    }
+
+    pub async fn tick_chunks(self: &Arc<Self>, player_chunks: &[Vector2<i32>]) {
+        let tick_data = self
+            .level
+            .get_tick_data(|chunk| Self::is_simulated(player_chunks, *chunk))
+            .await;
         for scheduled_tick in tick_data.block_ticks {
             let block = self.get_block(&scheduled_tick.block_pos).await;
             if let Some(pumpkin_block) = self.block_registry.get_pumpkin_block(block) {
@@ -659,25 +904,17 @@ impl World {
         }
     }
 
+    /// The generation settings of this world's dimension, e.g. for its height limits.
+    #[must_use]
+    pub fn generation_settings(&self) -> &'static GenerationSettings {
+        self.level.generation_settings()
+    }
+
     /// Gets the y position of the first non air block from the top down
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        // TODO: this is bad
-        let generation_settings = match self.dimension_type {
-            VanillaDimensionType::Overworld => GENERATION_SETTINGS
-                .get(&GeneratorSetting::Overworld)
-                .unwrap(),
-            VanillaDimensionType::OverworldCaves => todo!(),
-            VanillaDimensionType::TheEnd => {
-                GENERATION_SETTINGS.get(&GeneratorSetting::End).unwrap()
-            }
-            VanillaDimensionType::TheNether => {
-                GENERATION_SETTINGS.get(&GeneratorSetting::Nether).unwrap()
-            }
-        };
-        for y in (i32::from(generation_settings.shape.min_y)
-            ..=i32::from(generation_settings.shape.height))
-            .rev()
-        {
+        let shape = &self.generation_settings().shape;
+        let min_y = i32::from(shape.min_y);
+        for y in (min_y..min_y + i32::from(shape.height)).rev() {
             let pos = BlockPos(Vector3::new(position.x, y, position.y));
             let block = self.get_block_state(&pos).await;
             if block.is_air() {
@@ -685,7 +922,148 @@ impl World {
             }
             return y;
         }
-        i32::from(generation_settings.shape.height)
+        min_y - 1
+    }
+
+    /// Gets the y position of the first free block above the column's highest block in the
+    /// heightmap. Reads the chunk's stored heightmap, scanning the column if it has none.
+    pub async fn get_top_y(&self, position: Vector2<i32>, heightmap: HeightMap) -> i32 {
+        let (chunk_coordinate, relative) =
+            BlockPos(Vector3::new(position.x, 0, position.y)).chunk_and_chunk_relative_position();
+        let chunk = self.level.get_chunk(chunk_coordinate).await;
+        let top_y =
+            chunk
+                .read()
+                .await
+                .get_top_y(heightmap, relative.x as usize, relative.z as usize);
+        match top_y {
+            Some(top_y) => top_y,
+            None => self.get_top_block(position).await + 1,
+        }
+    }
+
+    /// The position and rotation a joining player spawns with: where they left off, or on top
+    /// of the world spawn when they join for the first time.
+    async fn get_join_position(&self, player: &Player) -> (Vector3<f64>, f32, f32) {
+        if player.has_played_before.load(Ordering::Relaxed) {
+            let position = player.position();
+            let yaw = player.living_entity.entity.yaw.load(); //info.spawn_angle;
+            let pitch = player.living_entity.entity.pitch.load();
+
+            (position, yaw, pitch)
+        } else {
+            let info = &self.level_info.read().await;
+            let spawn_position = Vector2::new(info.spawn_x, info.spawn_z);
+            let pos_y = self
+                .get_top_y(spawn_position, HeightMap::MotionBlocking)
+                .await;
+
+            let position = Vector3::new(
+                f64::from(info.spawn_x),
+                f64::from(pos_y),
+                f64::from(info.spawn_z),
+            );
+            let yaw = info.spawn_angle;
+            let pitch = 0.0;
+
+            (position, yaw, pitch)
+        }
+    }
+
+    fn create_chunk_obfuscator(dimension_type: VanillaDimensionType) -> Option<ChunkObfuscator> {
+        let config = &advanced_config().anti_xray;
+        if !config.enabled {
+            return None;
+        }
+        let get_block = |name: &String| {
+            let block = Block::from_registry_key(name.strip_prefix("minecraft:").unwrap_or(name));
+            if block.is_none() {
+                log::warn!("Unknown anti-xray block {name}");
+            }
+            block
+        };
+        let (filler, deep_filler) = match dimension_type {
+            VanillaDimensionType::Overworld | VanillaDimensionType::OverworldCaves => {
+                (&config.filler_block, &config.deepslate_filler_block)
+            }
+            VanillaDimensionType::TheNether => {
+                (&config.nether_filler_block, &config.nether_filler_block)
+            }
+            VanillaDimensionType::TheEnd => (&config.end_filler_block, &config.end_filler_block),
+        };
+        Some(ChunkObfuscator::new(
+            config.hidden_blocks.iter().filter_map(get_block),
+            get_block(filler)?,
+            get_block(deep_filler)?,
+        ))
+    }
+
+    /// Locks the chunk to send it to a client. `send` gets the chunk and, if anti-xray hides
+    /// blocks in it, the block states to send instead of its own.
+    ///
+    /// Anti-xray also reads the loaded neighbouring chunks. They are locked together with the
+    /// chunk in the same order `set_block_state_batch` locks chunks, so sending chunks can't
+    /// deadlock with block changes.
+    pub async fn with_chunk_to_send(
+        &self,
+        position: Vector2<i32>,
+        chunk: &SyncChunk,
+        send: impl AsyncFnOnce(&ChunkData, Option<&[BlockPalette]>),
+    ) {
+        let Some(anti_xray) = self.anti_xray.as_ref() else {
+            send(&*chunk.read().await, None).await;
+            return;
+        };
+        // The chunk itself, then its west, east, north and south neighbours
+        let offsets = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1)];
+        let chunks: Vec<Option<SyncChunk>> = offsets
+            .iter()
+            .map(|&(x, z)| {
+                if (x, z) == (0, 0) {
+                    return Some(chunk.clone());
+                }
+                self.level
+                    .try_get_chunk(Vector2::new(position.x + x, position.y + z))
+                    .map(|chunk| chunk.value().clone())
+            })
+            .collect();
+
+        let mut lock_order: Vec<usize> = (0..offsets.len()).collect();
+        lock_order.sort_unstable_by_key(|&i| offsets[i]);
+        let mut guards: [Option<RwLockReadGuard<ChunkData>>; 5] = Default::default();
+        for i in lock_order {
+            if let Some(chunk) = &chunks[i] {
+                guards[i] = Some(chunk.read().await);
+            }
+        }
+        let sections = |index: usize| guards[index].as_ref().map(|chunk| &chunk.section);
+        let Some(center) = guards[0].as_deref() else {
+            unreachable!("The chunk itself is always locked");
+        };
+        let obfuscated = anti_xray.obfuscate(
+            &center.section,
+            ChunkNeighbors {
+                west: sections(1),
+                east: sections(2),
+                north: sections(3),
+                south: sections(4),
+            },
+        );
+        send(center, obfuscated.as_deref()).await;
+    }
+
+    /// Sends the real state of hidden blocks next to a position which just got exposed.
+    async fn reveal_hidden_neighbors(&self, anti_xray: &ChunkObfuscator, position: &BlockPos) {
+        for direction in BlockDirection::all() {
+            let neighbor_pos = position.offset(direction.to_offset());
+            let state_id = self.get_block_state_id(&neighbor_pos).await;
+            if anti_xray.is_hidden(state_id) {
+                self.unsent_block_changes
+                    .lock()
+                    .await
+                    .insert(neighbor_pos, state_id);
+            }
+        }
     }
 
     #[allow(clippy::too_many_lines)]
@@ -695,13 +1073,26 @@ impl World {
         player: Arc<Player>,
         server: &Server,
     ) {
+        let (position, yaw, pitch) = self.get_join_position(&player).await;
+        let entity = &player.living_entity.entity;
+        entity.set_pos(position);
+        entity.set_rotation(yaw, pitch);
+
+        let (world_age, daytime) = {
+            let level_time = self.level_time.lock().await;
+            (level_time.world_age, level_time.query_daytime())
+        };
         let level_info = server.level_info.read().await;
         let weather = self.weather.lock().await;
         let level_settings = LevelSettings {
             seed: self.level.seed.0,
             spawn_biome_type: 0,
             custom_biome_name: String::with_capacity(0),
-            dimension: VarInt(0),
+            dimension: VarInt(match self.dimension_type {
+                VanillaDimensionType::Overworld | VanillaDimensionType::OverworldCaves => 0,
+                VanillaDimensionType::TheNether => 1,
+                VanillaDimensionType::TheEnd => 2,
+            }),
             generator_type: VarInt(1),
             world_gamemode: VarInt(server.defaultgamemode.lock().await.gamemode as i32),
             hardcore: base_config.hardcore,
@@ -715,7 +1106,12 @@ impl World {
             editor_world_type: VarInt(0),
             is_created_in_editor: false,
             is_exported_from_editor: false,
-            day_cycle_stop_time: VarInt(-1),
+            // The time stays frozen on the client when the daylight cycle is disabled
+            day_cycle_stop_time: VarInt(if level_info.game_rules.do_daylight_cycle {
+                -1
+            } else {
+                daytime as i32
+            }),
             education_edition_offer: VarInt(0),
             has_education_features_enabled: false,
             education_product_id: String::with_capacity(0),
@@ -771,17 +1167,22 @@ impl World {
                         entity_id: VarLong(i64::from(player.entity_id())),
                         runtime_entity_id: VarULong(player.entity_id() as u64),
                         player_gamemode: VarInt(player.gamemode.load() as i32),
-                        position: Vector3::new(0.0, 100.0, 0.0),
-                        yaw: 0.0,
-                        pitch: 0.0,
+                        // Bedrock player positions are at eye height
+                        position: Vector3::new(
+                            position.x as f32,
+                            position.y as f32 + entity.standing_eye_height,
+                            position.z as f32,
+                        ),
+                        yaw,
+                        pitch,
                         level_settings,
                         level_id: String::with_capacity(0),
-                        level_name: "level".to_string(),
+                        level_name: level_info.level_name.clone(),
                         premium_world_template_id: String::with_capacity(0),
                         is_trial: false,
                         rewind_history_size: VarInt(40),
                         server_authoritative_block_breaking: false,
-                        current_level_time: 0,
+                        current_level_time: world_age as u64,
                         enchantment_seed: VarInt(0),
                         block_properties_size: VarUInt(0),
                         // TODO Make this unique
@@ -810,6 +1211,18 @@ impl World {
         }
     }
 
+    /// The mood sound of the nether biomes, the other dimensions only have cave sounds.
+    fn biome_mood_sound(biome: &str) -> Option<Sound> {
+        match biome {
+            "minecraft:basalt_deltas" => Some(Sound::AmbientBasaltDeltasMood),
+            "minecraft:crimson_forest" => Some(Sound::AmbientCrimsonForestMood),
+            "minecraft:nether_wastes" => Some(Sound::AmbientNetherWastesMood),
+            "minecraft:soul_sand_valley" => Some(Sound::AmbientSoulSandValleyMood),
+            "minecraft:warped_forest" => Some(Sound::AmbientWarpedForestMood),
+            _ => None,
+        }
+    }
+
     #[expect(clippy::too_many_lines)]
     pub async fn spawn_java_player(
         &self,
@@ -819,6 +1232,8 @@ impl World {
     ) {
         let dimensions: Vec<ResourceLocation> = server
             .dimensions
+            .read()
+            .await
             .iter()
             .map(VanillaDimensionType::resource_location)
             .collect();
@@ -840,8 +1255,8 @@ impl World {
                 base_config.hardcore,
                 &dimensions,
                 base_config.max_players.try_into().unwrap(),
-                base_config.view_distance.get().into(), //  TODO: view distance
-                base_config.simulation_distance.get().into(), // TODO: sim view dinstance
+                chunker::get_max_view_distance().get().into(), //  TODO: view distance
+                base_config.simulation_distance.get().into(),  // TODO: sim view dinstance
                 false,
                 true,
                 false,
@@ -853,7 +1268,7 @@ impl World {
                     .previous_gamemode
                     .load()
                     .map_or(-1, |gamemode| gamemode as i8),
-                false,
+                self.level.is_debug(),
                 false,
                 None,
                 VarInt(player.get_entity().portal_cooldown.load(Ordering::Relaxed) as i32),
@@ -885,27 +1300,7 @@ impl World {
         chunker::player_join(&player).await;
 
         // Teleport
-        let (position, yaw, pitch) = if player.has_played_before.load(Ordering::Relaxed) {
-            let position = player.position();
-            let yaw = player.living_entity.entity.yaw.load(); //info.spawn_angle;
-            let pitch = player.living_entity.entity.pitch.load();
-
-            (position, yaw, pitch)
-        } else {
-            let info = &self.level_info.read().await;
-            let spawn_position = Vector2::new(info.spawn_x, info.spawn_z);
-            let pos_y = self.get_top_block(spawn_position).await + 1; // +1 to spawn on top of the block
-
-            let position = Vector3::new(
-                f64::from(info.spawn_x),
-                f64::from(pos_y),
-                f64::from(info.spawn_z),
-            );
-            let yaw = info.spawn_angle;
-            let pitch = 0.0;
-
-            (position, yaw, pitch)
-        };
+        let (position, yaw, pitch) = self.get_join_position(&player).await;
 
         let velocity = player.living_entity.entity.velocity.load();
 
@@ -1088,6 +1483,22 @@ impl World {
         // Sends initial time
         player.send_time(self).await;
 
+        // Greet the player with the mood sound of the biome they join in
+        let biome = self.get_biome_at(&position.to_block_pos()).await;
+        if let Some(sound) = Self::biome_mood_sound(biome) {
+            let seed = rng().random::<f64>();
+            player
+                .play_sound(
+                    sound as u16,
+                    SoundCategory::Ambient,
+                    &position,
+                    1.0,
+                    1.0,
+                    seed,
+                )
+                .await;
+        }
+
         // Send initial weather state
         let weather = self.weather.lock().await;
         if weather.raining {
@@ -1113,11 +1524,8 @@ impl World {
                 .await;
         }
 
-        // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
-        //     for bossbar in bossbars {
-        //         player.send_bossbar(bossbar).await;
-        //     }
-        // }
+        server.bossbars.lock().await.add_viewer(&player).await;
+        server.scoreboard.lock().await.send_to(&player).await;
 
         player.has_played_before.store(true, Ordering::Relaxed);
         player
@@ -1125,6 +1533,7 @@ impl World {
             .await;
 
         player.send_active_effects().await;
+        player.living_entity.send_attributes().await;
         self.send_player_equipment(&player).await;
     }
 
@@ -1133,18 +1542,18 @@ impl World {
 
         equipment_list.push((
             EquipmentSlot::MAIN_HAND.discriminant(),
-            *from.inventory.held_item().lock().await,
+            from.inventory.held_item().lock().await.clone(),
         ));
 
         for (slot, item_arc_mutex) in &from.inventory.entity_equipment.lock().await.equipment {
             let item_guard = item_arc_mutex.lock().await;
-            let item_stack = *item_guard;
+            let item_stack = item_guard.clone();
             equipment_list.push((slot.discriminant(), item_stack));
         }
 
         let equipment: Vec<(i8, ItemStackSerializer)> = equipment_list
             .iter()
-            .map(|(slot, stack)| (*slot, ItemStackSerializer::from(*stack)))
+            .map(|(slot, stack)| (*slot, ItemStackSerializer::from(stack.clone())))
             .collect();
         self.broadcast_packet_except(
             &[from.get_entity().entity_uuid],
@@ -1224,7 +1633,14 @@ impl World {
         }
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
+    /// Respawns the player in this world, at `respawn_point` or else at the world spawn. Players
+    /// coming from another world are moved into this one.
+    pub async fn respawn_player(
+        self: &Arc<Self>,
+        player: &Arc<Player>,
+        alive: bool,
+        respawn_point: Option<(Vector3<f64>, f32)>,
+    ) {
         let last_pos = player.living_entity.last_pos.load();
         let death_dimension = player.world().await.dimension_type.resource_location();
         let death_location = BlockPos(Vector3::new(
@@ -1235,7 +1651,7 @@ impl World {
 
         let data_kept = u8::from(alive);
 
-        // TODO: switch world in player entity to new world
+        player.move_to_world(self).await;
 
         player
             .client
@@ -1244,8 +1660,11 @@ impl World {
                 self.dimension_type.resource_location(),
                 biome::hash_seed(self.level.seed.0), // seed
                 player.gamemode.load() as u8,
-                player.gamemode.load() as i8,
-                false,
+                player
+                    .previous_gamemode
+                    .load()
+                    .map_or(-1, |gamemode| gamemode as i8),
+                self.level.is_debug(),
                 false,
                 Some((death_dimension, death_location)),
                 VarInt(player.get_entity().portal_cooldown.load(Ordering::Relaxed) as i32),
@@ -1268,15 +1687,18 @@ impl World {
 
         // Teleport
         let pitch = 0.0;
-        let (position, yaw) = if let Some(respawn) = player.get_respawn_point().await {
+        let (position, yaw) = if let Some(respawn) = respawn_point {
             respawn
         } else {
             let top = self
-                .get_top_block(Vector2::new(info.spawn_x, info.spawn_z))
+                .get_top_y(
+                    Vector2::new(info.spawn_x, info.spawn_z),
+                    HeightMap::MotionBlocking,
+                )
                 .await;
 
             (
-                Vector3::new(info.spawn_x.into(), (top + 1).into(), info.spawn_z.into()),
+                Vector3::new(info.spawn_x.into(), top.into(), info.spawn_z.into()),
                 info.spawn_angle,
             )
         };
@@ -1286,6 +1708,7 @@ impl World {
         player.living_entity.last_pos.store(position);
 
         // TODO: difficulty, exp bar, status effect
+        player.living_entity.send_attributes().await;
 
         self.send_world_info(player, position, yaw, pitch).await;
     }
@@ -1306,7 +1729,7 @@ impl World {
             .count();
 
         // TODO: sleep ratio
-        sleeping_player_count == player_count
+        player_count > 0 && sleeping_player_count == player_count
     }
 
     // NOTE: This function doesn't actually await on anything, it just spawns two tokio tasks
@@ -1448,6 +1871,7 @@ impl World {
                     // Remove all the entities from the world
                     let entity_chunk = chunk.read().await;
                     let mut entities = world.entities.write().await;
+                    let mut entities_by_id = world.entities_by_id.write().await;
                     for (uuid, entity_nbt) in &entity_chunk.data {
                         let Some(id) = entity_nbt.get_string("id") else {
                             log::warn!("Entity has no ID");
@@ -1465,7 +1889,10 @@ impl World {
                         entity.read_nbt(entity_nbt).await;
                         let base_entity = entity.get_entity();
 
-                        entities.remove(&base_entity.entity_uuid);
+                        if let Some(removed) = entities.remove(&base_entity.entity_uuid) {
+                            entities_by_id.remove(&removed.get_entity().entity_id);
+                        }
+                        world.leashes.lock().await.remove(base_entity.entity_uuid);
                         ids.push(VarInt(base_entity.entity_id));
 
                         world.save_entity(uuid, &entity).await;
@@ -1484,6 +1911,8 @@ impl World {
                 let entity_chunk = chunk.read().await;
                 // Add all new Entities to the world
                 let mut current_entities = world.entities.write().await;
+                let mut entities_by_id = world.entities_by_id.write().await;
+                let mut leashed = Vec::new();
 
                 for (uuid, entity_nbt) in &entity_chunk.data {
                     let Some(id) = entity_nbt.get_string("id") else {
@@ -1505,8 +1934,21 @@ impl World {
                         .enqueue_packet(&base_entity.create_spawn_packet())
                         .await;
                     entity.init_data_tracker().await;
+                    world.leashes.lock().await.track(base_entity);
+                    if base_entity.is_leashed() {
+                        leashed.push(entity.clone());
+                    }
+                    entities_by_id.insert(base_entity.entity_id, entity.clone());
                     current_entities.insert(base_entity.entity_uuid, entity);
                 }
+                // The holders have to be in the world before the leads can be shown
+                drop(entities_by_id);
+                drop(current_entities);
+                for entity in leashed {
+                    if let Some(packet) = entity.get_entity().leash_link_packet().await {
+                        player.client.enqueue_packet(&packet).await;
+                    }
+                }
             }
 
             #[cfg(debug_assertions)]
@@ -1526,12 +1968,7 @@ impl World {
 
     /// Gets an entity by an entity id
     pub async fn get_entity_by_id(&self, id: EntityId) -> Option<Arc<dyn EntityBase>> {
-        for entity in self.entities.read().await.values() {
-            if entity.get_entity().entity_id == id {
-                return Some(entity.clone());
-            }
-        }
-        None
+        self.entities_by_id.read().await.get(&id).cloned()
     }
 
     /// Gets a `Player` by a username
@@ -1552,11 +1989,15 @@ impl World {
             .cloned()
             .collect()
     }
+
+    /// Gets the players colliding with the box, spectators don't collide
     pub async fn get_players_at_box(&self, aabb: &BoundingBox) -> Vec<Arc<Player>> {
         let players_guard = self.players.read().await;
         players_guard
             .values()
-            .filter(|player| player.get_entity().bounding_box.load().intersects(aabb))
+            .filter(|player| {
+                !player.is_spectator() && player.get_entity().bounding_box.load().intersects(aabb)
+            })
             .cloned()
             .collect()
     }
@@ -1628,10 +2069,12 @@ impl World {
             .collect()
     }
 
+    /// Gets the closest player within `radius` that can be targeted, so no spectators
     pub async fn get_closest_player(&self, pos: Vector3<f64>, radius: f64) -> Option<Arc<Player>> {
         let players = self.get_nearby_players(pos, radius).await;
         players
             .iter()
+            .filter(|(_, player)| !player.is_spectator())
             .min_by(|a, b| {
                 a.1.living_entity
                     .entity
@@ -1717,6 +2160,13 @@ impl World {
         self.broadcast_packet_all(&CRemoveEntities::new(&[player.entity_id().into()]))
             .await;
 
+        // The bars of this world should not follow the player into another one
+        for (bossbar_id, bossbar) in self.bossbars.lock().await.iter_mut() {
+            if bossbar.viewers.remove(&uuid) {
+                player.remove_bossbar(*bossbar_id).await;
+            }
+        }
+
         if fire_event {
             let msg_comp = TextComponent::translate(
                 "multiplayer.player.left",
@@ -1737,6 +2187,55 @@ impl World {
         }
     }
 
+    /// Registers a bossbar in this world. It is not shown to anyone until players are added to it.
+    pub async fn add_bossbar(&self, bossbar: Bossbar) {
+        self.bossbars
+            .lock()
+            .await
+            .insert(bossbar.uuid, WorldBossbar::new(bossbar));
+    }
+
+    /// Removes a bossbar from this world, hiding it from everyone still seeing it.
+    pub async fn remove_bossbar(&self, bossbar_id: Uuid) {
+        let Some(bossbar) = self.bossbars.lock().await.remove(&bossbar_id) else {
+            return;
+        };
+        let players = self.players.read().await;
+        for uuid in &bossbar.viewers {
+            if let Some(player) = players.get(uuid) {
+                player.remove_bossbar(bossbar_id).await;
+            }
+        }
+    }
+
+    /// Shows the bossbar to the player. Returns `false` if there is no such bossbar or the player
+    /// already sees it.
+    pub async fn add_player_to_bossbar(&self, bossbar_id: Uuid, player: &Player) -> bool {
+        let mut bossbars = self.bossbars.lock().await;
+        let Some(bossbar) = bossbars.get_mut(&bossbar_id) else {
+            return false;
+        };
+        if !bossbar.viewers.insert(player.gameprofile.id) {
+            return false;
+        }
+        player.send_bossbar(&bossbar.bossbar).await;
+        true
+    }
+
+    /// Hides the bossbar from the player. Returns `false` if there is no such bossbar or the player
+    /// did not see it.
+    pub async fn remove_player_from_bossbar(&self, bossbar_id: Uuid, player: &Player) -> bool {
+        let mut bossbars = self.bossbars.lock().await;
+        let Some(bossbar) = bossbars.get_mut(&bossbar_id) else {
+            return false;
+        };
+        if !bossbar.viewers.remove(&player.gameprofile.id) {
+            return false;
+        }
+        player.remove_bossbar(bossbar_id).await;
+        true
+    }
+
     /// Adds an entity to the world.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
         let base_entity = entity.get_entity();
@@ -1756,11 +2255,24 @@ impl World {
         chunk.mark_dirty(true);
 
         let mut current_entities = self.entities.write().await;
+        self.entities_by_id
+            .write()
+            .await
+            .insert(base_entity.entity_id, entity.clone());
+        self.leashes.lock().await.track(base_entity);
         current_entities.insert(base_entity.entity_uuid, entity);
     }
 
     pub async fn remove_entity(&self, entity: &Entity) {
-        self.entities.write().await.remove(&entity.entity_uuid);
+        let mut entities = self.entities.write().await;
+        if let Some(removed) = entities.remove(&entity.entity_uuid) {
+            self.entities_by_id
+                .write()
+                .await
+                .remove(&removed.get_entity().entity_id);
+        }
+        drop(entities);
+        self.leashes.lock().await.remove(entity.entity_uuid);
         self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
             .await;
 
@@ -1775,36 +2287,250 @@ impl World {
         .await;
     }
 
+    /// Places a configured feature, like a tree, an ore vein or a flower patch, at the position.
+    ///
+    /// Returns whether the feature was placed.
+    pub async fn place_feature(
+        self: &Arc<Self>,
+        feature_id: ResourceLocation,
+        pos: BlockPos,
+        rng: &mut impl Rng,
+    ) -> bool {
+        let seed = rng.random();
+        let Some(changes) = self
+            .level
+            .place_feature(&feature_id.to_string(), pos, seed)
+            .await
+        else {
+            return false;
+        };
+        for (pos, state_id) in changes {
+            self.set_block_state(&pos, state_id, BlockFlags::NOTIFY_LISTENERS)
+                .await;
+        }
+        true
+    }
+
+    /// Finds the start of the nearest structure of the type, like a village, within
+    /// `search_radius` chunks around the center.
+    ///
+    /// Only chunks which were generated are searched, since the structures are read from the
+    /// saved chunks.
+    pub async fn find_nearest_structure(
+        &self,
+        center: BlockPos,
+        structure_type: &ResourceLocation,
+        search_radius: i32,
+    ) -> Option<BlockPos> {
+        let (center_chunk, _) = center.chunk_and_chunk_relative_position();
+        let structure_type = structure_type.to_string();
+        let mut nearest: Option<(BlockPos, i64)> = None;
+
+        for radius in 0..=search_radius {
+            // A start in this ring is at least this far away, so we can't find a closer one
+            if let Some((_, distance)) = nearest {
+                let min_distance = i64::from((radius - 1) * 16);
+                if min_distance * min_distance > distance {
+                    break;
+                }
+            }
+
+            let ring = Self::chunk_ring(center_chunk, radius);
+            for start in self.structure_starts_in(&ring).await {
+                if start.id != structure_type {
+                    continue;
+                }
+                let pos = start.locate_pos();
+                let dx = i64::from(pos.0.x - center.0.x);
+                let dz = i64::from(pos.0.z - center.0.z);
+                let distance = dx * dx + dz * dz;
+                if nearest.is_none_or(|(_, nearest)| distance < nearest) {
+                    nearest = Some((pos, distance));
+                }
+            }
+        }
+        nearest.map(|(pos, _)| pos)
+    }
+
+    /// The chunks exactly `radius` chunks away from the center.
+    fn chunk_ring(center: Vector2<i32>, radius: i32) -> Vec<Vector2<i32>> {
+        if radius == 0 {
+            return vec![center];
+        }
+        let mut ring = Vec::with_capacity(8 * radius as usize);
+        for offset in -radius..=radius {
+            ring.push(Vector2::new(center.x + offset, center.y - radius));
+            ring.push(Vector2::new(center.x + offset, center.y + radius));
+        }
+        for offset in 1 - radius..radius {
+            ring.push(Vector2::new(center.x - radius, center.y + offset));
+            ring.push(Vector2::new(center.x + radius, center.y + offset));
+        }
+        ring
+    }
+
+    /// The structures starting in the chunks, read from the chunks which aren't cached yet.
+    async fn structure_starts_in(&self, chunks: &[Vector2<i32>]) -> Vec<StructureStart> {
+        let mut starts = Vec::new();
+        let mut uncached = Vec::new();
+        {
+            let cache = self.structure_starts.read().await;
+            for chunk in chunks {
+                match cache.get(chunk) {
+                    Some(chunk_starts) => starts.extend_from_slice(chunk_starts),
+                    None => uncached.push(*chunk),
+                }
+            }
+        }
+        if uncached.is_empty() {
+            return starts;
+        }
+
+        // Chunks which were never generated aren't cached, they may get structures later
+        let saved_chunks = self.level.read_saved_chunks(&uncached).await;
+        let mut cache = self.structure_starts.write().await;
+        for chunk in saved_chunks {
+            let chunk = chunk.read().await;
+            let chunk_starts = chunk
+                .structures
+                .as_ref()
+                .map(|structures| StructureStart::from_nbt(structures, chunk.position))
+                .unwrap_or_default();
+            starts.extend_from_slice(&chunk_starts);
+            cache.insert(chunk.position, chunk_starts);
+        }
+        starts
+    }
+
     /// Sets a block and returns the old block id
-    #[expect(clippy::too_many_lines)]
     pub async fn set_block_state(
         self: &Arc<Self>,
         position: &BlockPos,
         block_state_id: BlockStateId,
         flags: BlockFlags,
     ) -> BlockStateId {
-        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let (chunk_coordinate, _) = position.chunk_and_chunk_relative_position();
         let chunk = self.level.get_chunk(chunk_coordinate).await;
         let Ok(mut chunk) =
             tokio::time::timeout(std::time::Duration::from_secs(1), chunk.write()).await
         else {
             panic!("Timed out while waiting to acquire chunk write lock")
         };
-        let Some(replaced_block_state_id) = chunk.section.get_block_absolute_y(
+        let replaced = self
+            .write_block_state(&mut chunk, position, block_state_id)
+            .await;
+        drop(chunk);
+
+        match replaced {
+            Some(replaced_block_state_id) => {
+                self.on_block_state_changed(
+                    position,
+                    replaced_block_state_id,
+                    block_state_id,
+                    flags,
+                )
+                .await;
+                replaced_block_state_id
+            }
+            None => block_state_id,
+        }
+    }
+
+    /// Sets many blocks at once and returns the old block ids in the same order.
+    ///
+    /// All touched chunks are locked before the first block is set, so other tasks see either
+    /// none or all of the changes. Neighbors are only updated after all blocks are set, so block
+    /// updates never see a half built structure.
+    pub async fn set_block_state_batch<I>(
+        self: &Arc<Self>,
+        changes: I,
+        flags: BlockFlags,
+    ) -> Vec<BlockStateId>
+    where
+        I: IntoIterator<Item = (BlockPos, BlockStateId)>,
+    {
+        let changes: Vec<_> = changes.into_iter().collect();
+        let changes_by_chunk = changes_by_chunk(&changes);
+
+        let mut chunks = Vec::with_capacity(changes_by_chunk.len());
+        for (chunk_coordinate, _) in &changes_by_chunk {
+            chunks.push(self.level.get_chunk(*chunk_coordinate).await);
+        }
+        // Chunks are locked in the sorted order of `changes_by_chunk`, so two batches can't
+        // deadlock each other
+        let mut guards = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            let Ok(guard) =
+                tokio::time::timeout(std::time::Duration::from_secs(1), chunk.write()).await
+            else {
+                panic!("Timed out while waiting to acquire chunk write lock")
+            };
+            guards.push(guard);
+        }
+
+        // The old block id of each change, and whether it changed
+        let mut replaced = vec![(Block::AIR.default_state.id, false); changes.len()];
+        for ((_, indices), chunk) in changes_by_chunk.into_iter().zip(guards.iter_mut()) {
+            for index in indices {
+                let (position, block_state_id) = &changes[index];
+                replaced[index] = if let Some(replaced_block_state_id) = self
+                    .write_block_state(chunk, position, *block_state_id)
+                    .await
+                {
+                    (replaced_block_state_id, true)
+                } else {
+                    // Either the block did not change or it is outside of the world, where only
+                    // air is read
+                    let (_, relative) = position.chunk_and_chunk_relative_position();
+                    let current = chunk
+                        .section
+                        .get_block_absolute_y(relative.x as usize, relative.y, relative.z as usize)
+                        .unwrap_or(Block::AIR.default_state.id);
+                    (current, false)
+                };
+            }
+        }
+        drop(guards);
+
+        let mut replaced_ids = Vec::with_capacity(changes.len());
+        for ((position, block_state_id), (replaced_block_state_id, changed)) in
+            changes.iter().zip(replaced)
+        {
+            if changed {
+                self.on_block_state_changed(
+                    position,
+                    replaced_block_state_id,
+                    *block_state_id,
+                    flags,
+                )
+                .await;
+            }
+            replaced_ids.push(replaced_block_state_id);
+        }
+        replaced_ids
+    }
+
+    /// Writes the block into the locked chunk, returns the old block id if it changed.
+    async fn write_block_state(
+        &self,
+        chunk: &mut ChunkData,
+        position: &BlockPos,
+        block_state_id: BlockStateId,
+    ) -> Option<BlockStateId> {
+        let (_, relative) = position.chunk_and_chunk_relative_position();
+        let replaced_block_state_id = chunk.section.get_block_absolute_y(
             relative.x as usize,
             relative.y,
             relative.z as usize,
-        ) else {
-            return block_state_id;
-        };
+        )?;
 
         if replaced_block_state_id == block_state_id {
-            return block_state_id;
+            return None;
         }
 
         chunk.mark_dirty(true);
 
-        chunk.section.set_block_absolute_y(
+        chunk.set_block_absolute_y(
             relative.x as usize,
             relative.y,
             relative.z as usize,
@@ -1814,7 +2540,83 @@ impl World {
             .lock()
             .await
             .insert(*position, block_state_id);
-        drop(chunk);
+
+        Some(replaced_block_state_id)
+    }
+
+    /// Recalculates the block light around a block which changed its light emission or opacity.
+    ///
+    /// Light can spread into the neighbouring chunks, so all loaded chunks around the block are
+    /// locked, in the same order `set_block_state_batch` locks them.
+    async fn update_block_light(&self, position: &BlockPos) {
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let coordinates: Vec<Vector2<i32>> = (-1..=1)
+            .flat_map(|z| {
+                (-1..=1).map(move |x| Vector2::new(chunk_coordinate.x + x, chunk_coordinate.y + z))
+            })
+            .collect();
+        let chunks: Vec<_> = coordinates
+            .iter()
+            .map(|coordinate| {
+                self.level
+                    .try_get_chunk(*coordinate)
+                    .map(|chunk| chunk.clone())
+            })
+            .collect();
+
+        let mut lock_order: Vec<usize> = (0..coordinates.len()).collect();
+        lock_order.sort_unstable_by_key(|&i| (coordinates[i].x, coordinates[i].y));
+        let mut guards: [Option<RwLockWriteGuard<ChunkData>>; 9] = Default::default();
+        for i in lock_order {
+            if let Some(chunk) = &chunks[i] {
+                guards[i] = Some(chunk.write().await);
+            }
+        }
+
+        let changed_sections = recalculate_block_light(
+            guards.each_mut().map(|guard| guard.as_deref_mut()),
+            relative.x as usize,
+            relative.y,
+            relative.z as usize,
+        );
+
+        let mut unsent_light_changes = self.unsent_light_changes.lock().await;
+        for ((guard, coordinate), sections) in
+            guards.iter_mut().zip(coordinates).zip(changed_sections)
+        {
+            let Some(chunk) = guard else {
+                continue;
+            };
+            if sections.is_empty() {
+                continue;
+            }
+            chunk.mark_dirty(true);
+            unsent_light_changes
+                .entry(coordinate)
+                .or_default()
+                .extend(sections);
+        }
+    }
+
+    /// Runs the block callbacks and neighbor updates once a block was changed.
+    async fn on_block_state_changed(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        replaced_block_state_id: BlockStateId,
+        block_state_id: BlockStateId,
+        flags: BlockFlags,
+    ) {
+        let old_state = get_state_by_state_id(replaced_block_state_id);
+        let new_state = get_state_by_state_id(block_state_id);
+        if old_state.luminance != new_state.luminance || old_state.opacity != new_state.opacity {
+            self.update_block_light(position).await;
+        }
+
+        if let Some(anti_xray) = &self.anti_xray {
+            if ChunkObfuscator::is_open(block_state_id) {
+                self.reveal_hidden_neighbors(anti_xray, position).await;
+            }
+        }
 
         let old_block = Block::from_state_id(replaced_block_state_id);
         let new_block = Block::from_state_id(block_state_id);
@@ -1904,8 +2706,6 @@ impl World {
                     .await;
             }
         }
-
-        replaced_block_state_id
     }
 
     pub async fn schedule_block_tick(
@@ -1985,7 +2785,7 @@ impl World {
                     broken_state_id.into(),
                     false,
                 );
-                match cause {
+                match &cause {
                     Some(player) => {
                         self.broadcast_packet_except(&[player.gameprofile.id], &particles_packet)
                             .await;
@@ -1995,8 +2795,13 @@ impl World {
             }
 
             if !flags.contains(BlockFlags::SKIP_DROPS) {
+                let tool = match &cause {
+                    Some(player) => Some(player.inventory().held_item().lock().await.clone()),
+                    None => None,
+                };
                 let params = LootContextParameters {
                     block_state: Some(get_state_by_state_id(broken_state_id)),
+                    tool,
                     ..Default::default()
                 };
                 block::drop_loot(self, broken_block, position, true, params).await;
@@ -2065,6 +2870,12 @@ impl World {
             .unwrap_or(&Fluid::EMPTY)
     }
 
+    /// Gets the resource location of the biome at the given block, like `minecraft:plains`.
+    /// Biomes are stored per 4x4x4 cell, so neighbouring blocks share a biome.
+    pub async fn get_biome_at(&self, position: &BlockPos) -> &'static str {
+        self.level.get_biome(position).await.resource_location
+    }
+
     pub async fn get_block_state_id(&self, position: &BlockPos) -> BlockStateId {
         self.level.get_block_state(position).await.0
     }
@@ -2193,7 +3004,7 @@ impl World {
             .level
             .get_chunk(block_pos.chunk_and_chunk_relative_position().0)
             .await;
-        let chunk: tokio::sync::RwLockReadGuard<ChunkData> = chunk.read().await;
+        let chunk: RwLockReadGuard<ChunkData> = chunk.read().await;
 
         chunk.block_entities.get(block_pos).cloned()
     }
@@ -2232,7 +3043,7 @@ impl World {
         chunk.mark_dirty(true);
     }
 
-    fn intersects_aabb_with_direction(
+    pub(crate) fn intersects_aabb_with_direction(
         from: Vector3<f64>,
         to: Vector3<f64>,
         min: Vector3<f64>,
@@ -2480,3 +3291,57 @@ impl BlockAccessor for World {
         get_block_and_state_by_state_id(id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_data::particle::Particle;
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+
+    use super::{ParticleData, ParticleEffect, changes_by_chunk, particle_effects_in_range};
+
+    #[test]
+    fn particle_bursts_only_reach_close_players() {
+        let effect = |x: f64| -> ParticleEffect {
+            (
+                Vector3::new(x, 64.0, 0.0),
+                Vector3::new(0.0, 0.0, 0.0),
+                0.0,
+                1,
+                Particle::Poof,
+                ParticleData::None,
+            )
+        };
+        let effects = [effect(0.0), effect(32.0), effect(64.0), effect(65.0)];
+        let viewer = Vector3::new(0.0, 64.0, 0.0);
+
+        let in_range: Vec<f64> = particle_effects_in_range(&effects, viewer, 64.0)
+            .map(|(position, ..)| position.x)
+            .collect();
+        assert_eq!(in_range, [0.0, 32.0, 64.0]);
+        assert_eq!(particle_effects_in_range(&effects, viewer, 0.0).count(), 1);
+    }
+
+    #[test]
+    fn batch_changes_are_grouped_by_chunk_in_order() {
+        let change = |x: i32, z: i32, id: u16| (BlockPos(Vector3::new(x, 64, z)), id);
+        let changes = [
+            change(20, 0, 1),
+            change(0, 0, 2),
+            change(-1, 0, 3),
+            change(21, 5, 4),
+            change(15, 15, 5),
+            change(-16, -1, 6),
+        ];
+
+        let grouped = changes_by_chunk(&changes);
+        assert_eq!(
+            grouped,
+            [
+                (Vector2::new(-1, -1), vec![5]),
+                (Vector2::new(-1, 0), vec![2]),
+                (Vector2::new(0, 0), vec![1, 4]),
+                (Vector2::new(1, 0), vec![0, 3]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use pumpkin_data::{Block, block_properties::get_block, particle::Particle};
+use pumpkin_protocol::codec::var_int::VarInt;
+
+/// The extra options some particles need, like the color of dust or the block of block particles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParticleData {
+    None,
+    /// `dust`, the color is packed as RGB
+    Dust {
+        color: i32,
+        scale: f32,
+    },
+    /// `dust_color_transition`, the colors are packed as RGB
+    DustColorTransition {
+        from: i32,
+        to: i32,
+        scale: f32,
+    },
+    /// `block`, `block_marker`, `falling_dust`, `dust_pillar` and `block_crumble`
+    BlockState(u16),
+    /// `entity_effect` and `tinted_leaves`, the color is packed as ARGB
+    Color(i32),
+    /// `effect` and `instant_effect`, the color is packed as RGB
+    Spell {
+        color: i32,
+        power: f32,
+    },
+    /// `sculk_charge`
+    SculkCharge {
+        roll: f32,
+    },
+    /// `shriek`
+    Shriek {
+        delay: i32,
+    },
+}
+
+impl ParticleData {
+    /// Parses the options written after a particle name in commands, e.g. the
+    /// `{color:[1.0,0.0,0.0],scale:1.0}` of `dust{color:[1.0,0.0,0.0],scale:1.0}`.
+    ///
+    /// Returns `None` when options the particle requires are missing or invalid.
+    #[must_use]
+    pub fn parse(particle: Particle, options: Option<&str>) -> Option<Self> {
+        let options = match options {
+            Some(options) => parse_compound(options)?,
+            None => HashMap::new(),
+        };
+        let float = |key: &str| options.get(key).and_then(|value| parse_float(value));
+
+        Some(match particle {
+            Particle::Dust => Self::Dust {
+                color: parse_color(options.get("color")?, false)?,
+                scale: float("scale")?,
+            },
+            Particle::DustColorTransition => Self::DustColorTransition {
+                from: parse_color(options.get("from_color")?, false)?,
+                to: parse_color(options.get("to_color")?, false)?,
+                scale: float("scale")?,
+            },
+            Particle::Block
+            | Particle::BlockMarker
+            | Particle::FallingDust
+            | Particle::DustPillar
+            | Particle::BlockCrumble => {
+                Self::BlockState(parse_block_state(options.get("block_state")?)?)
+            }
+            Particle::EntityEffect | Particle::TintedLeaves => {
+                Self::Color(parse_color(options.get("color")?, true)?)
+            }
+            Particle::Effect | Particle::InstantEffect => Self::Spell {
+                color: match options.get("color") {
+                    Some(color) => parse_color(color, false)?,
+                    None => 0xFF_FF_FF,
+                },
+                power: match options.get("power") {
+                    Some(power) => parse_float(power)?,
+                    None => 1.0,
+                },
+            },
+            Particle::SculkCharge => Self::SculkCharge {
+                roll: float("roll")?,
+            },
+            Particle::Shriek => Self::Shriek {
+                delay: parse_float(options.get("delay")?)? as i32,
+            },
+            // Their options hold item stacks and position sources, which we can not parse yet
+            Particle::Item | Particle::Vibration | Particle::Trail => return None,
+            _ if options.is_empty() => Self::None,
+            _ => return None,
+        })
+    }
+
+    /// Serializes the options the way they follow the particle id in the particle packet.
+    #[must_use]
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::None => {}
+            Self::Dust { color, scale } => {
+                buf.extend_from_slice(&color.to_be_bytes());
+                buf.extend_from_slice(&scale.to_be_bytes());
+            }
+            Self::DustColorTransition { from, to, scale } => {
+                buf.extend_from_slice(&from.to_be_bytes());
+                buf.extend_from_slice(&to.to_be_bytes());
+                buf.extend_from_slice(&scale.to_be_bytes());
+            }
+            Self::BlockState(state_id) => {
+                VarInt(i32::from(state_id)).encode(&mut buf).unwrap();
+            }
+            Self::Color(color) => buf.extend_from_slice(&color.to_be_bytes()),
+            Self::Spell { color, power } => {
+                buf.extend_from_slice(&color.to_be_bytes());
+                buf.extend_from_slice(&power.to_be_bytes());
+            }
+            Self::SculkCharge { roll } => buf.extend_from_slice(&roll.to_be_bytes()),
+            Self::Shriek { delay } => VarInt(delay).encode(&mut buf).unwrap(),
+        }
+        buf
+    }
+}
+
+/// Splits `{key:value,...}` into its entries, keeping nested lists and compounds as they are.
+fn parse_compound(input: &str) -> Option<HashMap<&str, &str>> {
+    let inner = input.strip_prefix('{')?.strip_suffix('}')?;
+    split_top_level(inner)
+        .into_iter()
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (key, value) = entry.split_once(':')?;
+            Some((unquote(key.trim()), value.trim()))
+        })
+        .collect()
+}
+
+/// Splits on the commas that are not inside a nested list, compound or string.
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                parts.push(input[start..i].trim());
+                start = i + 1;
+            }
+            // Anything inside a string, or not a separator
+            _ => {}
+        }
+    }
+    parts.push(input[start..].trim());
+    parts
+}
+
+fn unquote(input: &str) -> &str {
+    input
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| input.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(input)
+}
+
+fn parse_float(input: &str) -> Option<f32> {
+    input.trim_end_matches(['f', 'F', 'd', 'D']).parse().ok()
+}
+
+/// Colors are either a packed integer or a list of floats between 0 and 1.
+fn parse_color(input: &str, with_alpha: bool) -> Option<i32> {
+    if let Ok(color) = input.parse() {
+        return Some(color);
+    }
+    let components = input.strip_prefix('[')?.strip_suffix(']')?;
+    let components = split_top_level(components)
+        .into_iter()
+        .map(parse_float)
+        .collect::<Option<Vec<_>>>()?;
+    if components.len() != if with_alpha { 4 } else { 3 } {
+        return None;
+    }
+    // A list color is RGBA, while the packed color is ARGB
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as i32;
+    let rgb = components[..3]
+        .iter()
+        .fold(0, |color, &value| (color << 8) | channel(value));
+    Some(if with_alpha {
+        (channel(components[3]) << 24) | rgb
+    } else {
+        rgb
+    })
+}
+
+/// Block states are either `"minecraft:oak_log[axis=x]"` or `{Name:"minecraft:oak_log",Properties:{axis:"x"}}`.
+fn parse_block_state(input: &str) -> Option<u16> {
+    let (block, properties) = if let Some(compound) = parse_compound(input) {
+        let block = get_block(unquote(compound.get("Name")?))?;
+        let properties = match compound.get("Properties") {
+            Some(properties) => parse_compound(properties)?
+                .into_iter()
+                .map(|(key, value)| (key, unquote(value)))
+                .collect(),
+            None => HashMap::new(),
+        };
+        (block, properties)
+    } else {
+        let input = unquote(input);
+        match input.split_once('[') {
+            Some((name, properties)) => {
+                let properties = properties
+                    .strip_suffix(']')?
+                    .split(',')
+                    .filter(|property| !property.is_empty())
+                    .map(|property| property.split_once('='))
+                    .collect::<Option<HashMap<_, _>>>()?;
+                (get_block(name)?, properties)
+            }
+            None => (get_block(input)?, HashMap::new()),
+        }
+    };
+    block_state_from_properties(block, properties)
+}
+
+fn block_state_from_properties(block: &Block, properties: HashMap<&str, &str>) -> Option<u16> {
+    if properties.is_empty() {
+        return Some(block.default_state.id);
+    }
+    Some(block.from_properties(properties)?.to_state_id(block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_encodes_dust() {
+        let data = ParticleData::parse(Particle::Dust, Some("{color:[1.0,0.0,0.5],scale:2f}"));
+        assert_eq!(
+            data,
+            Some(ParticleData::Dust {
+                color: 0xFF_00_80,
+                scale: 2.0
+            })
+        );
+        let bytes = data.unwrap().to_bytes();
+        assert_eq!(&bytes[..4], &0xFF_00_80i32.to_be_bytes());
+        assert_eq!(&bytes[4..], &2.0f32.to_be_bytes());
+    }
+
+    #[test]
+    fn parses_options_with_spaces() {
+        assert_eq!(
+            ParticleData::parse(Particle::Dust, Some("{color:[1.0, 0.0, 0.0], scale:1.0}")),
+            Some(ParticleData::Dust {
+                color: 0xFF_00_00,
+                scale: 1.0
+            })
+        );
+    }
+
+    #[test]
+    fn parses_block_states() {
+        let stone = Block::STONE.default_state.id;
+        for options in [
+            r#"{block_state:"minecraft:stone"}"#,
+            "{block_state:stone}",
+            r#"{block_state:{Name:"minecraft:stone"}}"#,
+        ] {
+            assert_eq!(
+                ParticleData::parse(Particle::Block, Some(options)),
+                Some(ParticleData::BlockState(stone))
+            );
+        }
+        assert_eq!(ParticleData::parse(Particle::Block, None), None);
+    }
+
+    #[test]
+    fn rejects_options_on_plain_particles() {
+        assert_eq!(
+            ParticleData::parse(Particle::Flame, None),
+            Some(ParticleData::None)
+        );
+        assert_eq!(
+            ParticleData::parse(Particle::Flame, Some("{scale:1}")),
+            None
+        );
+    }
+}
@@ -1,15 +1,25 @@
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io,
+    path::Path,
+    sync::Arc,
+};
 
 use pumpkin_data::{
     Block, BlockDirection, BlockState,
     block_properties::{BlockProperties, HorizontalAxis, NetherPortalLikeProperties},
     tag::Tagable,
 };
-use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::world::BlockFlags;
+use pumpkin_nbt::{compound::NbtCompound, nbt_compress, tag::NbtTag};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{chunk::format::anvil::WORLD_DATA_VERSION, world::BlockFlags};
 
 use crate::world::World;
 
+/// The file the nether portals of a world are stored in, inside its `data` folder.
+const PORTALS_FILE_NAME: &str = "nether_portals.dat";
+
 pub struct NetherPortal {
     axis: HorizontalAxis,
     found_portal_blocks: u32,
@@ -29,6 +39,11 @@ impl NetherPortal {
 
     const FRAME_BLOCK: Block = Block::OBSIDIAN;
 
+    /// How far from the destination a new portal may be built, vanilla
+    const CREATE_RADIUS: i32 = 16;
+    /// The most spots checked for room for a new portal, as each check reads a few dozen blocks
+    const MAX_SPOTS_CHECKED: usize = 4096;
+
     #[must_use]
     pub fn is_valid(&self) -> bool {
         self.width >= Self::MIN_WIDTH
@@ -42,7 +57,59 @@ impl NetherPortal {
         self.is_valid() && self.found_portal_blocks == self.width * self.height
     }
 
+    /// Loads the lower corners of the portals known in a world from its `data` folder.
+    #[must_use]
+    pub fn load_index(data_path: &Path) -> HashSet<BlockPos> {
+        let Ok(file) = File::open(data_path.join(PORTALS_FILE_NAME)) else {
+            return HashSet::new();
+        };
+        let nbt = match nbt_compress::read_gzip_compound_tag(file) {
+            Ok(nbt) => nbt,
+            Err(err) => {
+                log::warn!("Failed to read the nether portals: {err}");
+                return HashSet::new();
+            }
+        };
+        nbt.get_compound("data")
+            .and_then(|data| data.get_int_array("portals"))
+            .map(|portals| {
+                portals
+                    .chunks_exact(3)
+                    .map(|pos| BlockPos(Vector3::new(pos[0], pos[1], pos[2])))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Saves the lower corners of the portals known in a world into its `data` folder.
+    pub fn save_index(data_path: &Path, portals: &HashSet<BlockPos>) -> io::Result<()> {
+        fs::create_dir_all(data_path)?;
+        let mut sorted: Vec<_> = portals.iter().map(|pos| pos.0).collect();
+        sorted.sort_unstable_by_key(|pos| (pos.x, pos.y, pos.z));
+        let positions = sorted
+            .into_iter()
+            .flat_map(|pos| [pos.x, pos.y, pos.z])
+            .collect();
+
+        let mut data = NbtCompound::new();
+        data.put("portals", NbtTag::IntArray(positions));
+        let mut nbt = NbtCompound::new();
+        nbt.put_component("data", data);
+        nbt.put_int("DataVersion", WORLD_DATA_VERSION);
+        nbt_compress::write_gzip_compound_tag(
+            &nbt,
+            File::create(data_path.join(PORTALS_FILE_NAME))?,
+        )
+        .map_err(io::Error::other)
+    }
+
+    /// Lights the portal and remembers it, so entities travelling to this world can find it.
     pub async fn create(&self, world: &Arc<World>) {
+        self.fill(world).await;
+        world.nether_portals.lock().await.insert(self.lower_conor);
+    }
+
+    async fn fill(&self, world: &Arc<World>) {
         let mut props = NetherPortalLikeProperties::default(&Block::NETHER_PORTAL);
         props.axis = self.axis;
         let state = props.to_state_id(&Block::NETHER_PORTAL);
@@ -64,6 +131,205 @@ impl NetherPortal {
         }
     }
 
+    /// Finds the portal closest to `pos` within `search_radius` blocks horizontally, or builds a
+    /// new one near it if there is none. Returns the position of the portal's lower corner.
+    ///
+    /// The world's portals are locked again before building, so entities arriving at the same
+    /// time find the portal built for the first one instead of each building their own.
+    pub async fn find_or_create(world: &Arc<World>, pos: BlockPos, search_radius: i32) -> BlockPos {
+        let mut portals = world.nether_portals.lock().await;
+        if let Some(corner) = Self::find_closest(world, &mut portals, pos, search_radius).await {
+            return corner;
+        }
+        // Looking for space reads a lot of blocks, so other entities may travel meanwhile
+        drop(portals);
+
+        let settings = world.generation_settings();
+        let min_y = i32::from(settings.shape.min_y);
+        let height = i32::from(settings.shape.height).min(world.dimension_type.logical_height());
+        let max_y = min_y + height - 1;
+        let space = Self::find_space(world, pos, min_y, max_y).await;
+
+        let mut portals = world.nether_portals.lock().await;
+        if let Some(corner) = Self::find_closest(world, &mut portals, pos, search_radius).await {
+            return corner;
+        }
+        let portal = if let Some(portal) = space {
+            portal
+        } else {
+            // Nowhere to stand, so we carve out a room with a floor to build the portal in
+            let lower_corner = BlockPos(Vector3::new(
+                pos.0.x,
+                pos.0
+                    .y
+                    .clamp(min_y + 1, max_y - Self::MIN_HEIGHT as i32 - 1),
+                pos.0.z,
+            ));
+            let portal = Self::new_with_size(lower_corner, HorizontalAxis::X);
+            portal.clear_surroundings(world).await;
+            portal
+        };
+        portal.build_frame(world).await;
+        portal.fill(world).await;
+        portals.insert(portal.lower_conor);
+        portal.lower_conor
+    }
+
+    /// The lower corner of the portal closest to `pos` within `search_radius` blocks
+    /// horizontally. Portals which were broken are forgotten.
+    async fn find_closest(
+        world: &World,
+        portals: &mut HashSet<BlockPos>,
+        pos: BlockPos,
+        search_radius: i32,
+    ) -> Option<BlockPos> {
+        let mut closest: Option<(BlockPos, i32)> = None;
+        let mut broken = Vec::new();
+        for corner in portals.iter() {
+            if (corner.0.x - pos.0.x).abs() > search_radius
+                || (corner.0.z - pos.0.z).abs() > search_radius
+            {
+                continue;
+            }
+            if world.get_block(corner).await != &Block::NETHER_PORTAL {
+                broken.push(*corner);
+                continue;
+            }
+            let distance = corner.0.squared_distance_to_vec(pos.0);
+            if closest.is_none_or(|(_, closest_distance)| distance < closest_distance) {
+                closest = Some((*corner, distance));
+            }
+        }
+        for corner in broken {
+            portals.remove(&corner);
+        }
+        closest.map(|(corner, _)| corner)
+    }
+
+    /// Looks for a spot with solid ground and room for a portal frame within `CREATE_RADIUS`
+    /// blocks of `pos`, preferring the ones closest to it. At most `MAX_SPOTS_CHECKED` spots are
+    /// checked.
+    async fn find_space(world: &World, pos: BlockPos, min_y: i32, max_y: i32) -> Option<Self> {
+        let lowest = min_y + 1;
+        let highest = max_y - Self::MIN_HEIGHT as i32;
+        if lowest > highest {
+            return None;
+        }
+        let center_y = pos.0.y.clamp(lowest, highest);
+        let radius = Self::CREATE_RADIUS;
+        let mut offsets: Vec<Vector3<i32>> = BlockPos::iterate(
+            BlockPos(Vector3::new(-radius, -radius, -radius)),
+            BlockPos(Vector3::new(radius, radius, radius)),
+        )
+        .map(|offset| offset.0)
+        .filter(|offset| (lowest..=highest).contains(&(center_y + offset.y)))
+        .collect();
+        offsets.sort_by_key(Vector3::length_squared);
+
+        for offset in offsets.into_iter().take(Self::MAX_SPOTS_CHECKED) {
+            let lower_corner = BlockPos(Vector3::new(
+                pos.0.x + offset.x,
+                center_y + offset.y,
+                pos.0.z + offset.z,
+            ));
+            for axis in [HorizontalAxis::X, HorizontalAxis::Z] {
+                let portal = Self::new_with_size(lower_corner, axis);
+                if portal.has_space(world).await {
+                    return Some(portal);
+                }
+            }
+        }
+        None
+    }
+
+    /// A new portal of the smallest size, with its frame not built yet.
+    fn new_with_size(lower_corner: BlockPos, axis: HorizontalAxis) -> Self {
+        Self {
+            axis,
+            found_portal_blocks: 0,
+            negative_direction: if axis == HorizontalAxis::X {
+                BlockDirection::West
+            } else {
+                BlockDirection::South
+            },
+            lower_conor: lower_corner,
+            width: Self::MIN_WIDTH,
+            height: Self::MIN_HEIGHT,
+        }
+    }
+
+    /// The position `up` blocks above and `along` blocks next to the lower corner, where
+    /// `-1` and `width` along are the sides of the frame.
+    fn frame_pos(&self, along: i32, up: i32) -> BlockPos {
+        self.lower_conor
+            .offset_dir(BlockDirection::Up.to_offset(), up)
+            .offset_dir(self.negative_direction.to_offset(), along)
+    }
+
+    async fn has_space(&self, world: &World) -> bool {
+        for along in -1..=self.width as i32 {
+            if !world
+                .get_block_state(&self.frame_pos(along, -1))
+                .await
+                .is_solid()
+            {
+                return false;
+            }
+            for up in 0..=self.height as i32 {
+                if !world
+                    .get_block_state(&self.frame_pos(along, up))
+                    .await
+                    .is_air()
+                {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    async fn clear_surroundings(&self, world: &Arc<World>) {
+        let side = self.negative_direction.rotate_clockwise().to_offset();
+        for along in -1..=self.width as i32 {
+            for across in -1..=1 {
+                let floor = self.frame_pos(along, -1).offset_dir(side, across);
+                world
+                    .set_block_state(
+                        &floor,
+                        Self::FRAME_BLOCK.default_state.id,
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+                for up in 0..=self.height as i32 {
+                    let pos = self.frame_pos(along, up).offset_dir(side, across);
+                    world
+                        .set_block_state(&pos, Block::AIR.default_state.id, BlockFlags::NOTIFY_ALL)
+                        .await;
+                }
+            }
+        }
+    }
+
+    async fn build_frame(&self, world: &Arc<World>) {
+        for along in -1..=self.width as i32 {
+            for up in -1..=self.height as i32 {
+                let is_frame = along == -1
+                    || along == self.width as i32
+                    || up == -1
+                    || up == self.height as i32;
+                if is_frame {
+                    world
+                        .set_block_state(
+                            &self.frame_pos(along, up),
+                            Self::FRAME_BLOCK.default_state.id,
+                            BlockFlags::NOTIFY_ALL,
+                        )
+                        .await;
+                }
+            }
+        }
+    }
+
     pub async fn get_new_portal(
         world: &World,
         pos: &BlockPos,
@@ -246,3 +512,27 @@ impl NetherPortal {
             || block == &Block::NETHER_PORTAL
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::NetherPortal;
+
+    #[test]
+    fn portal_index_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(NetherPortal::load_index(dir.path()).is_empty());
+
+        let portals: HashSet<_> = [
+            BlockPos(Vector3::new(12, 64, -300)),
+            BlockPos(Vector3::new(-1_000_000, -50, 7)),
+        ]
+        .into_iter()
+        .collect();
+        NetherPortal::save_index(dir.path(), &portals).unwrap();
+        assert_eq!(NetherPortal::load_index(dir.path()), portals);
+    }
+}
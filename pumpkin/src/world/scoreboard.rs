@@ -1,30 +1,303 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
 
 use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_nbt::{compound::NbtCompound, nbt_compress, tag::NbtTag};
 use pumpkin_protocol::{
     NumberFormat,
     codec::var_int::VarInt,
-    java::client::play::{CDisplayObjective, CUpdateObjectives, CUpdateScore, RenderType},
+    java::client::play::{
+        CDisplayObjective, CResetScore, CUpdateObjectives, CUpdateScore, CUpdateTeams,
+        CollisionRule, Mode, NameTagVisibility, RenderType, TeamMethod, TeamParameters,
+    },
 };
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+use pumpkin_world::chunk::format::anvil::WORLD_DATA_VERSION;
+
+use crate::{entity::player::Player, server::Server};
+
+/// The file the scoreboard is stored in, inside the `data` folder of the world like vanilla.
+pub const SCOREBOARD_FILE_NAME: &str = "scoreboard.dat";
+
+/// All display slots, ordered by their protocol id.
+pub const DISPLAY_SLOTS: [ScoreboardDisplaySlot; 19] = [
+    ScoreboardDisplaySlot::List,
+    ScoreboardDisplaySlot::Sidebar,
+    ScoreboardDisplaySlot::BelowName,
+    ScoreboardDisplaySlot::TeamBlack,
+    ScoreboardDisplaySlot::TeamDarkBlue,
+    ScoreboardDisplaySlot::TeamDarkGreen,
+    ScoreboardDisplaySlot::TeamDarkAqua,
+    ScoreboardDisplaySlot::TeamDarkRed,
+    ScoreboardDisplaySlot::TeamDarkPurple,
+    ScoreboardDisplaySlot::TeamGold,
+    ScoreboardDisplaySlot::TeamGray,
+    ScoreboardDisplaySlot::TeamDarkGray,
+    ScoreboardDisplaySlot::TeamBlue,
+    ScoreboardDisplaySlot::TeamGreen,
+    ScoreboardDisplaySlot::TeamAqua,
+    ScoreboardDisplaySlot::TeamRed,
+    ScoreboardDisplaySlot::TeamLightPurple,
+    ScoreboardDisplaySlot::TeamYellow,
+    ScoreboardDisplaySlot::TeamWhite,
+];
+
+/// The named colors, ordered by their id.
+pub const NAMED_COLORS: [NamedColor; 16] = [
+    NamedColor::Black,
+    NamedColor::DarkBlue,
+    NamedColor::DarkGreen,
+    NamedColor::DarkAqua,
+    NamedColor::DarkRed,
+    NamedColor::DarkPurple,
+    NamedColor::Gold,
+    NamedColor::Gray,
+    NamedColor::DarkGray,
+    NamedColor::Blue,
+    NamedColor::Green,
+    NamedColor::Aqua,
+    NamedColor::Red,
+    NamedColor::LightPurple,
+    NamedColor::Yellow,
+    NamedColor::White,
+];
+
+const COLOR_NAMES: [&str; 16] = [
+    "black",
+    "dark_blue",
+    "dark_green",
+    "dark_aqua",
+    "dark_red",
+    "dark_purple",
+    "gold",
+    "gray",
+    "dark_gray",
+    "blue",
+    "green",
+    "aqua",
+    "red",
+    "light_purple",
+    "yellow",
+    "white",
+];
 
-use super::World;
+/// The color id the client uses for teams without a color
+const RESET_COLOR_ID: i32 = 21;
+
+#[must_use]
+pub const fn color_name(color: NamedColor) -> &'static str {
+    COLOR_NAMES[color as usize]
+}
+
+/// The name of a display slot as used by commands and in `scoreboard.dat`.
+#[must_use]
+pub fn display_slot_name(slot: ScoreboardDisplaySlot) -> String {
+    match slot {
+        ScoreboardDisplaySlot::List => "list".to_string(),
+        ScoreboardDisplaySlot::Sidebar => "sidebar".to_string(),
+        ScoreboardDisplaySlot::BelowName => "below_name".to_string(),
+        team_slot => format!(
+            "sidebar.team.{}",
+            COLOR_NAMES[team_slot as usize - ScoreboardDisplaySlot::TeamBlack as usize]
+        ),
+    }
+}
 
+#[must_use]
+pub fn display_slot_from_name(name: &str) -> Option<ScoreboardDisplaySlot> {
+    DISPLAY_SLOTS
+        .into_iter()
+        .find(|slot| display_slot_name(*slot) == name)
+}
+
+/// The sidebar slot which is only shown to members of teams with the given color.
+#[must_use]
+pub const fn team_sidebar_slot(color: NamedColor) -> ScoreboardDisplaySlot {
+    DISPLAY_SLOTS[ScoreboardDisplaySlot::TeamBlack as usize + color as usize]
+}
+
+struct Objective {
+    display_name: TextComponent,
+    criteria: String,
+    render_type: RenderType,
+    number_format: Option<NumberFormat>,
+}
+
+pub struct Team {
+    pub display_name: TextComponent,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+    pub color: Option<NamedColor>,
+    pub allow_friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: NameTagVisibility,
+    pub collision_rule: CollisionRule,
+    members: BTreeSet<String>,
+}
+
+impl Team {
+    #[must_use]
+    pub fn new(display_name: TextComponent) -> Self {
+        Self {
+            display_name,
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            color: None,
+            allow_friendly_fire: true,
+            see_friendly_invisibles: true,
+            name_tag_visibility: NameTagVisibility::Always,
+            collision_rule: CollisionRule::Always,
+            members: BTreeSet::new(),
+        }
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = &String> {
+        self.members.iter()
+    }
+
+    fn parameters(&self) -> TeamParameters<'_> {
+        TeamParameters {
+            display_name: &self.display_name,
+            allow_friendly_fire: self.allow_friendly_fire,
+            see_friendly_invisibles: self.see_friendly_invisibles,
+            name_tag_visibility: self.name_tag_visibility,
+            collision_rule: self.collision_rule,
+            color: self.color.map_or(RESET_COLOR_ID, |color| color as i32),
+            prefix: &self.prefix,
+            suffix: &self.suffix,
+        }
+    }
+}
+
+/// The objectives, scores and teams of the server, shared by all of its worlds like vanilla.
+///
+/// Scores are kept by the name of their holder, so they stay when a player reconnects.
 #[derive(Default)]
 pub struct Scoreboard {
-    objectives: HashMap<String, ScoreboardObjective<'static>>,
-    //  teams: HashMap<String, Team>,
+    objectives: HashMap<String, Objective>,
+    /// Scores by holder and objective name
+    scores: HashMap<String, HashMap<String, i32>>,
+    display_slots: HashMap<ScoreboardDisplaySlot, String>,
+    teams: HashMap<String, Team>,
+    /// Where the scoreboard gets saved, `None` if it is never saved
+    path: Option<PathBuf>,
+    /// Whether there are changes which aren't saved yet
+    unsaved: bool,
 }
 
 impl Scoreboard {
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            objectives: HashMap::new(),
+        Self::default()
+    }
+
+    /// Loads the scoreboard from the `data` folder of a world, or creates an empty one.
+    #[must_use]
+    pub fn load(data_path: &Path) -> Self {
+        let path = data_path.join(SCOREBOARD_FILE_NAME);
+        let mut scoreboard = File::open(&path)
+            .ok()
+            .and_then(|file| {
+                nbt_compress::read_gzip_compound_tag(file)
+                    .map_err(|err| log::warn!("Failed to read the scoreboard: {err}"))
+                    .ok()
+            })
+            .and_then(|nbt| Some(Self::read_nbt(nbt.get_compound("data")?)))
+            .unwrap_or_default();
+        scoreboard.path = Some(path);
+        scoreboard
+    }
+
+    /// Saves the scoreboard if it changed since it was loaded.
+    pub fn save(&mut self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !self.unsaved {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
         }
+        let mut nbt = NbtCompound::new();
+        nbt.put_component("data", self.write_nbt());
+        nbt.put_int("DataVersion", WORLD_DATA_VERSION);
+        nbt_compress::write_gzip_compound_tag(&nbt, File::create(path)?)
+            .map_err(io::Error::other)?;
+        self.unsaved = false;
+        Ok(())
     }
 
-    pub async fn add_objective(&mut self, world: &World, objective: ScoreboardObjective<'_>) {
+    /// Sends the whole scoreboard to a player which just joined.
+    pub async fn send_to(&self, player: &Player) {
+        for (name, objective) in &self.objectives {
+            player
+                .client
+                .enqueue_packet(&CUpdateObjectives::new(
+                    name.clone(),
+                    Mode::Add,
+                    objective.display_name.clone(),
+                    objective.render_type,
+                    objective.number_format.clone(),
+                ))
+                .await;
+        }
+        for (slot, objective_name) in &self.display_slots {
+            player
+                .client
+                .enqueue_packet(&CDisplayObjective::new(*slot, objective_name.clone()))
+                .await;
+        }
+        for (holder, scores) in &self.scores {
+            for (objective_name, value) in scores {
+                player
+                    .client
+                    .enqueue_packet(&CUpdateScore::new(
+                        holder.clone(),
+                        objective_name.clone(),
+                        VarInt(*value),
+                        None,
+                        None,
+                    ))
+                    .await;
+            }
+        }
+        for (name, team) in &self.teams {
+            let members: Vec<String> = team.members.iter().cloned().collect();
+            player
+                .client
+                .enqueue_packet(&CUpdateTeams::new(
+                    name,
+                    TeamMethod::Add(team.parameters(), &members),
+                ))
+                .await;
+        }
+    }
+
+    #[must_use]
+    pub fn has_objective(&self, name: &str) -> bool {
+        self.objectives.contains_key(name)
+    }
+
+    /// The names and display names of all objectives.
+    pub fn objectives(&self) -> impl Iterator<Item = (&String, &TextComponent)> {
+        self.objectives
+            .iter()
+            .map(|(name, objective)| (name, &objective.display_name))
+    }
+
+    #[must_use]
+    pub fn objective_display_name(&self, name: &str) -> Option<&TextComponent> {
+        self.objectives
+            .get(name)
+            .map(|objective| &objective.display_name)
+    }
+
+    pub async fn add_objective(&mut self, server: &Server, objective: ScoreboardObjective<'_>) {
         if self.objectives.contains_key(objective.name) {
             // Maybe make this an error?
             log::warn!(
@@ -33,32 +306,121 @@ impl Scoreboard {
             );
             return;
         }
-        world
+        server
             .broadcast_packet_all(&CUpdateObjectives::new(
                 objective.name.to_string(),
-                pumpkin_protocol::java::client::play::Mode::Add,
+                Mode::Add,
+                objective.display_name.clone(),
+                objective.render_type,
+                objective.number_format.clone(),
+            ))
+            .await;
+        self.objectives.insert(
+            objective.name.to_string(),
+            Objective {
+                display_name: objective.display_name,
+                criteria: objective.criteria.to_string(),
+                render_type: objective.render_type,
+                number_format: objective.number_format,
+            },
+        );
+        self.unsaved = true;
+    }
+
+    /// Removes an objective together with its scores and display slots.
+    pub async fn remove_objective(&mut self, server: &Server, name: &str) -> bool {
+        let Some(objective) = self.objectives.remove(name) else {
+            return false;
+        };
+        server
+            .broadcast_packet_all(&CUpdateObjectives::new(
+                name.to_string(),
+                Mode::Remove,
                 objective.display_name,
                 objective.render_type,
-                objective.number_format,
+                None,
             ))
             .await;
-        world
+        self.display_slots
+            .retain(|_, objective_name| objective_name != name);
+        for scores in self.scores.values_mut() {
+            scores.remove(name);
+        }
+        self.scores.retain(|_, scores| !scores.is_empty());
+        self.unsaved = true;
+        true
+    }
+
+    /// Changes how an objective is shown, returns `false` if there is no such objective.
+    pub async fn update_objective(
+        &mut self,
+        server: &Server,
+        name: &str,
+        display_name: Option<TextComponent>,
+        render_type: Option<RenderType>,
+    ) -> bool {
+        let Some(objective) = self.objectives.get_mut(name) else {
+            return false;
+        };
+        if let Some(display_name) = display_name {
+            objective.display_name = display_name;
+        }
+        if let Some(render_type) = render_type {
+            objective.render_type = render_type;
+        }
+        server
+            .broadcast_packet_all(&CUpdateObjectives::new(
+                name.to_string(),
+                Mode::Update,
+                objective.display_name.clone(),
+                objective.render_type,
+                objective.number_format.clone(),
+            ))
+            .await;
+        self.unsaved = true;
+        true
+    }
+
+    #[must_use]
+    pub fn display_slot(&self, slot: ScoreboardDisplaySlot) -> Option<&String> {
+        self.display_slots.get(&slot)
+    }
+
+    /// Shows an objective in a display slot, or clears the slot.
+    pub async fn set_display_slot(
+        &mut self,
+        server: &Server,
+        slot: ScoreboardDisplaySlot,
+        objective_name: Option<&str>,
+    ) {
+        match objective_name {
+            Some(name) => self.display_slots.insert(slot, name.to_string()),
+            None => self.display_slots.remove(&slot),
+        };
+        server
             .broadcast_packet_all(&CDisplayObjective::new(
-                ScoreboardDisplaySlot::Sidebar,
-                objective.name.to_string(),
+                slot,
+                objective_name.unwrap_or_default().to_string(),
             ))
             .await;
+        self.unsaved = true;
     }
 
-    pub async fn update_score(&self, world: &World, score: ScoreboardScore<'_>) {
-        if self.objectives.contains_key(score.objective_name) {
+    /// Sets a score, returns `false` if the objective does not exist.
+    pub async fn update_score(&mut self, server: &Server, score: ScoreboardScore<'_>) -> bool {
+        if !self.objectives.contains_key(score.objective_name) {
             log::warn!(
                 "Tried to place a score into an objective which does not exist: {}",
                 &score.objective_name
             );
-            return;
+            return false;
         }
-        world
+        self.scores
+            .entry(score.entity_name.to_string())
+            .or_default()
+            .insert(score.objective_name.to_string(), score.value.0);
+        self.unsaved = true;
+        server
             .broadcast_packet_all(&CUpdateScore::new(
                 score.entity_name.to_string(),
                 score.objective_name.to_string(),
@@ -67,19 +429,410 @@ impl Scoreboard {
                 score.number_format,
             ))
             .await;
+        true
+    }
+
+    #[must_use]
+    pub fn get_score(&self, holder: &str, objective_name: &str) -> Option<i32> {
+        self.scores.get(holder)?.get(objective_name).copied()
     }
 
-    // pub fn add_team(&mut self, name: String) {
-    //     if self.teams.contains_key(&name) {
-    //         // Maybe make this an error ?
-    //         log::warn!("Tried to create Team which does already exist, {}", name);
-    //     }
-    // }
+    /// All scores of a holder by objective name.
+    #[must_use]
+    pub fn holder_scores(&self, holder: &str) -> Option<&HashMap<String, i32>> {
+        self.scores.get(holder)
+    }
+
+    /// Everyone who has at least one score.
+    pub fn holders(&self) -> impl Iterator<Item = &String> {
+        self.scores.keys()
+    }
+
+    /// Removes one or, without an objective, all scores of a holder.
+    pub async fn reset_score(
+        &mut self,
+        server: &Server,
+        holder: &str,
+        objective_name: Option<&str>,
+    ) -> bool {
+        let Some(scores) = self.scores.get_mut(holder) else {
+            return false;
+        };
+        let removed = if let Some(objective_name) = objective_name {
+            scores.remove(objective_name).is_some()
+        } else {
+            scores.clear();
+            true
+        };
+        if scores.is_empty() {
+            self.scores.remove(holder);
+        }
+        if removed {
+            server
+                .broadcast_packet_all(&CResetScore::new(
+                    holder.to_string(),
+                    objective_name.map(str::to_string),
+                ))
+                .await;
+            self.unsaved = true;
+        }
+        removed
+    }
+
+    #[must_use]
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    pub fn teams(&self) -> impl Iterator<Item = (&String, &Team)> {
+        self.teams.iter()
+    }
+
+    /// The name of the team a holder is in.
+    #[must_use]
+    pub fn team_of(&self, member: &str) -> Option<&String> {
+        self.teams
+            .iter()
+            .find(|(_, team)| team.members.contains(member))
+            .map(|(name, _)| name)
+    }
+
+    pub async fn add_team(&mut self, server: &Server, name: &str, team: Team) {
+        if self.teams.contains_key(name) {
+            // Maybe make this an error ?
+            log::warn!("Tried to create Team which does already exist, {name}");
+            return;
+        }
+        let members: Vec<String> = team.members.iter().cloned().collect();
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name,
+                TeamMethod::Add(team.parameters(), &members),
+            ))
+            .await;
+        self.teams.insert(name.to_string(), team);
+        self.unsaved = true;
+    }
+
+    pub async fn remove_team(&mut self, server: &Server, name: &str) -> bool {
+        if self.teams.remove(name).is_none() {
+            return false;
+        }
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(name, TeamMethod::Remove))
+            .await;
+        self.unsaved = true;
+        true
+    }
+
+    /// Changes the options of a team, returns `false` if there is no such team.
+    pub async fn update_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        update: impl FnOnce(&mut Team),
+    ) -> bool {
+        let Some(team) = self.teams.get_mut(name) else {
+            return false;
+        };
+        update(team);
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name,
+                TeamMethod::Update(team.parameters()),
+            ))
+            .await;
+        self.unsaved = true;
+        true
+    }
+
+    /// Moves holders into a team, taking them out of their previous team.
+    pub async fn join_team(&mut self, server: &Server, name: &str, members: &[String]) -> bool {
+        if !self.teams.contains_key(name) {
+            return false;
+        }
+        self.leave_team(server, members).await;
+        let Some(team) = self.teams.get_mut(name) else {
+            return false;
+        };
+        team.members.extend(members.iter().cloned());
+        server
+            .broadcast_packet_all(&CUpdateTeams::new(name, TeamMethod::AddEntities(members)))
+            .await;
+        self.unsaved = true;
+        true
+    }
+
+    /// Takes holders out of their teams, returns how many were in a team.
+    pub async fn leave_team(&mut self, server: &Server, members: &[String]) -> usize {
+        let mut count = 0;
+        for (name, team) in &mut self.teams {
+            let left: Vec<String> = members
+                .iter()
+                .filter(|member| team.members.remove(*member))
+                .cloned()
+                .collect();
+            if left.is_empty() {
+                continue;
+            }
+            count += left.len();
+            server
+                .broadcast_packet_all(&CUpdateTeams::new(name, TeamMethod::RemoveEntities(&left)))
+                .await;
+        }
+        if count > 0 {
+            self.unsaved = true;
+        }
+        count
+    }
+
+    fn write_nbt(&self) -> NbtCompound {
+        let objectives = self
+            .objectives
+            .iter()
+            .map(|(name, objective)| {
+                let mut nbt = NbtCompound::new();
+                nbt.put_string("Name", name.clone());
+                nbt.put_string("CriteriaName", objective.criteria.clone());
+                nbt.put_string("DisplayName", component_to_json(&objective.display_name));
+                nbt.put_string(
+                    "RenderType",
+                    match objective.render_type {
+                        RenderType::Integer => "integer",
+                        RenderType::Hearts => "hearts",
+                    }
+                    .to_string(),
+                );
+                nbt.put_bool("display_auto_update", false);
+                NbtTag::Compound(nbt)
+            })
+            .collect();
+
+        let scores = self
+            .scores
+            .iter()
+            .flat_map(|(holder, scores)| {
+                scores.iter().map(move |(objective_name, value)| {
+                    let mut nbt = NbtCompound::new();
+                    nbt.put_string("Name", holder.clone());
+                    nbt.put_string("Objective", objective_name.clone());
+                    nbt.put_int("Score", *value);
+                    nbt.put_bool("Locked", true);
+                    NbtTag::Compound(nbt)
+                })
+            })
+            .collect();
+
+        let teams = self
+            .teams
+            .iter()
+            .map(|(name, team)| {
+                let mut nbt = NbtCompound::new();
+                nbt.put_string("Name", name.clone());
+                nbt.put_string("DisplayName", component_to_json(&team.display_name));
+                nbt.put_string("MemberNamePrefix", component_to_json(&team.prefix));
+                nbt.put_string("MemberNameSuffix", component_to_json(&team.suffix));
+                if let Some(color) = team.color {
+                    nbt.put_string("TeamColor", color_name(color).to_string());
+                }
+                nbt.put_bool("AllowFriendlyFire", team.allow_friendly_fire);
+                nbt.put_bool("SeeFriendlyInvisibles", team.see_friendly_invisibles);
+                nbt.put_string(
+                    "NameTagVisibility",
+                    name_tag_visibility_name(team.name_tag_visibility).to_string(),
+                );
+                nbt.put_string(
+                    "CollisionRule",
+                    collision_rule_name(team.collision_rule).to_string(),
+                );
+                nbt.put_list(
+                    "Players",
+                    team.members
+                        .iter()
+                        .map(|member| NbtTag::String(member.clone()))
+                        .collect(),
+                );
+                NbtTag::Compound(nbt)
+            })
+            .collect();
+
+        let mut display_slots = NbtCompound::new();
+        for (slot, objective_name) in &self.display_slots {
+            display_slots.put_string(&display_slot_name(*slot), objective_name.clone());
+        }
+
+        let mut data = NbtCompound::new();
+        data.put_list("Objectives", objectives);
+        data.put_list("PlayerScores", scores);
+        data.put_list("Teams", teams);
+        data.put_component("DisplaySlots", display_slots);
+        data
+    }
+
+    fn read_nbt(data: &NbtCompound) -> Self {
+        let mut scoreboard = Self::new();
+
+        for nbt in compounds(data, "Objectives") {
+            let Some(name) = nbt.get_string("Name") else {
+                continue;
+            };
+            scoreboard.objectives.insert(
+                name.clone(),
+                Objective {
+                    display_name: component_from_nbt(nbt, "DisplayName")
+                        .unwrap_or_else(|| TextComponent::text(name.clone())),
+                    criteria: nbt
+                        .get_string("CriteriaName")
+                        .cloned()
+                        .unwrap_or_else(|| "dummy".to_string()),
+                    render_type: match nbt.get_string("RenderType").map(String::as_str) {
+                        Some("hearts") => RenderType::Hearts,
+                        _ => RenderType::Integer,
+                    },
+                    number_format: None,
+                },
+            );
+        }
+
+        for nbt in compounds(data, "PlayerScores") {
+            let (Some(holder), Some(objective_name), Some(value)) = (
+                nbt.get_string("Name"),
+                nbt.get_string("Objective"),
+                nbt.get_int("Score"),
+            ) else {
+                continue;
+            };
+            if scoreboard.objectives.contains_key(objective_name) {
+                scoreboard
+                    .scores
+                    .entry(holder.clone())
+                    .or_default()
+                    .insert(objective_name.clone(), value);
+            }
+        }
+
+        for nbt in compounds(data, "Teams") {
+            let Some(name) = nbt.get_string("Name") else {
+                continue;
+            };
+            let mut team = Team::new(
+                component_from_nbt(nbt, "DisplayName")
+                    .unwrap_or_else(|| TextComponent::text(name.clone())),
+            );
+            if let Some(prefix) = component_from_nbt(nbt, "MemberNamePrefix") {
+                team.prefix = prefix;
+            }
+            if let Some(suffix) = component_from_nbt(nbt, "MemberNameSuffix") {
+                team.suffix = suffix;
+            }
+            team.color = nbt
+                .get_string("TeamColor")
+                .and_then(|color| NamedColor::try_from(color.as_str()).ok());
+            team.allow_friendly_fire = nbt.get_bool("AllowFriendlyFire").unwrap_or(true);
+            team.see_friendly_invisibles = nbt.get_bool("SeeFriendlyInvisibles").unwrap_or(true);
+            if let Some(visibility) = nbt
+                .get_string("NameTagVisibility")
+                .and_then(|visibility| name_tag_visibility_from_name(visibility))
+            {
+                team.name_tag_visibility = visibility;
+            }
+            if let Some(rule) = nbt
+                .get_string("CollisionRule")
+                .and_then(|rule| collision_rule_from_name(rule))
+            {
+                team.collision_rule = rule;
+            }
+            team.members = nbt
+                .get_list("Players")
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|member| member.extract_string().cloned())
+                .collect();
+            scoreboard.teams.insert(name.clone(), team);
+        }
+
+        if let Some(display_slots) = data.get_compound("DisplaySlots") {
+            for (slot_name, objective_name) in &display_slots.child_tags {
+                let (Some(slot), Some(objective_name)) = (
+                    display_slot_from_name(slot_name),
+                    objective_name.extract_string(),
+                ) else {
+                    continue;
+                };
+                if scoreboard.objectives.contains_key(objective_name) {
+                    scoreboard
+                        .display_slots
+                        .insert(slot, objective_name.clone());
+                }
+            }
+        }
+
+        scoreboard
+    }
+}
+
+fn compounds<'a>(data: &'a NbtCompound, name: &str) -> impl Iterator<Item = &'a NbtCompound> {
+    data.get_list(name)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(NbtTag::extract_compound)
+}
+
+fn component_to_json(component: &TextComponent) -> String {
+    serde_json::to_string(component).unwrap_or_default()
+}
+
+fn component_from_nbt(nbt: &NbtCompound, name: &str) -> Option<TextComponent> {
+    serde_json::from_str(nbt.get_string(name)?).ok()
+}
+
+#[must_use]
+pub const fn name_tag_visibility_name(visibility: NameTagVisibility) -> &'static str {
+    match visibility {
+        NameTagVisibility::Always => "always",
+        NameTagVisibility::Never => "never",
+        NameTagVisibility::HideForOtherTeams => "hideForOtherTeams",
+        NameTagVisibility::HideForOwnTeam => "hideForOwnTeam",
+    }
+}
+
+fn name_tag_visibility_from_name(name: &str) -> Option<NameTagVisibility> {
+    [
+        NameTagVisibility::Always,
+        NameTagVisibility::Never,
+        NameTagVisibility::HideForOtherTeams,
+        NameTagVisibility::HideForOwnTeam,
+    ]
+    .into_iter()
+    .find(|visibility| name_tag_visibility_name(*visibility) == name)
+}
+
+#[must_use]
+pub const fn collision_rule_name(rule: CollisionRule) -> &'static str {
+    match rule {
+        CollisionRule::Always => "always",
+        CollisionRule::Never => "never",
+        CollisionRule::PushOtherTeams => "pushOtherTeams",
+        CollisionRule::PushOwnTeam => "pushOwnTeam",
+    }
+}
+
+fn collision_rule_from_name(name: &str) -> Option<CollisionRule> {
+    [
+        CollisionRule::Always,
+        CollisionRule::Never,
+        CollisionRule::PushOtherTeams,
+        CollisionRule::PushOwnTeam,
+    ]
+    .into_iter()
+    .find(|rule| collision_rule_name(*rule) == name)
 }
 
 pub struct ScoreboardObjective<'a> {
     name: &'a str,
     display_name: TextComponent,
+    /// What changes the scores, only `dummy` objectives are supported right now
+    criteria: &'a str,
     render_type: RenderType,
     number_format: Option<NumberFormat>,
 }
@@ -89,12 +842,14 @@ impl<'a> ScoreboardObjective<'a> {
     pub const fn new(
         name: &'a str,
         display_name: TextComponent,
+        criteria: &'a str,
         render_type: RenderType,
         number_format: Option<NumberFormat>,
     ) -> Self {
         Self {
             name,
             display_name,
+            criteria,
             render_type,
             number_format,
         }
@@ -127,3 +882,49 @@ impl<'a> ScoreboardScore<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+    use pumpkin_protocol::java::client::play::RenderType;
+    use pumpkin_util::text::TextComponent;
+
+    use super::{Objective, Scoreboard, Team};
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut scoreboard = Scoreboard::load(dir.path());
+        scoreboard.objectives.insert(
+            "kills".to_string(),
+            Objective {
+                display_name: TextComponent::text("Kills"),
+                criteria: "playerKillCount".to_string(),
+                render_type: RenderType::Integer,
+                number_format: None,
+            },
+        );
+        scoreboard
+            .scores
+            .entry("Steve".to_string())
+            .or_default()
+            .insert("kills".to_string(), 7);
+        scoreboard
+            .display_slots
+            .insert(ScoreboardDisplaySlot::Sidebar, "kills".to_string());
+        let mut team = Team::new(TextComponent::text("Red"));
+        team.members.insert("Steve".to_string());
+        scoreboard.teams.insert("red".to_string(), team);
+        scoreboard.unsaved = true;
+        scoreboard.save().unwrap();
+
+        let loaded = Scoreboard::load(dir.path());
+        assert!(loaded.has_objective("kills"));
+        assert_eq!(loaded.get_score("Steve", "kills"), Some(7));
+        assert_eq!(
+            loaded.display_slot(ScoreboardDisplaySlot::Sidebar),
+            Some(&"kills".to_string())
+        );
+        assert_eq!(loaded.team_of("Steve"), Some(&"red".to_string()));
+    }
+}
@@ -0,0 +1,212 @@
+use std::f64::consts::TAU;
+use std::sync::Arc;
+
+use pumpkin_util::HeightMap;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::math::vector3::Vector3;
+use rand::{Rng, rng};
+use thiserror::Error;
+
+use super::World;
+use crate::entity::EntityBase;
+
+/// How often the entities are pushed apart before giving up.
+const MAX_ITERATIONS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SpreadError {
+    #[error("Could not separate all entities")]
+    NoSafeLocation,
+}
+
+/// A random position in the circle of `max_range` around `center`.
+fn random_position(rng: &mut impl Rng, center: Vector2<f64>, max_range: f64) -> Vector2<f64> {
+    let angle = rng.random::<f64>() * TAU;
+    // The square root keeps the positions evenly distributed over the area
+    let distance = max_range * rng.random::<f64>().sqrt();
+    Vector2::new(
+        center.x + angle.cos() * distance,
+        center.y + angle.sin() * distance,
+    )
+}
+
+/// Moves `position` back onto the edge of the circle if it left it.
+fn clamp_to_range(position: Vector2<f64>, center: Vector2<f64>, max_range: f64) -> Vector2<f64> {
+    let offset = position.sub(&center);
+    if offset.length_squared() <= max_range * max_range {
+        return position;
+    }
+    center.add(&(offset.normalize() * max_range))
+}
+
+/// Pushes every position away from the ones closer than `spread_distance` by up to `step` blocks.
+///
+/// Returns whether any two positions were too close to each other.
+fn separate(
+    positions: &mut [Vector2<f64>],
+    center: Vector2<f64>,
+    spread_distance: f64,
+    max_range: f64,
+    step: f64,
+    rng: &mut impl Rng,
+) -> bool {
+    let mut overlapping = false;
+    for i in 0..positions.len() {
+        let mut push = Vector2::new(0.0, 0.0);
+        let mut neighbours = 0;
+        for j in 0..positions.len() {
+            if i == j {
+                continue;
+            }
+            let away = positions[i].sub(&positions[j]);
+            let distance = away.length();
+            if distance >= spread_distance {
+                continue;
+            }
+            neighbours += 1;
+            push = if distance == 0.0 {
+                // Two entities on the same spot have no direction to move apart in
+                let angle = rng.random::<f64>() * TAU;
+                push.add(&Vector2::new(angle.cos(), angle.sin()))
+            } else {
+                push.add(&(away * (1.0 / distance)))
+            };
+        }
+        if neighbours == 0 {
+            continue;
+        }
+        overlapping = true;
+        if push.length_squared() > 0.0 {
+            positions[i] = clamp_to_range(
+                positions[i].add(&(push.normalize() * step)),
+                center,
+                max_range,
+            );
+        }
+    }
+    overlapping
+}
+
+impl World {
+    /// Spreads the entities randomly within `max_range` blocks of `center`, keeping them at least
+    /// `spread_distance` blocks apart and off of liquids, then teleports them on top of the
+    /// ground at their positions.
+    pub async fn spread_entities(
+        self: &Arc<Self>,
+        center: Vector2<f64>,
+        spread_distance: f64,
+        max_range: f64,
+        entities: Vec<Arc<dyn EntityBase>>,
+    ) -> Result<(), SpreadError> {
+        let mut positions: Vec<_> = {
+            let mut rng = rng();
+            (0..entities.len())
+                .map(|_| random_position(&mut rng, center, max_range))
+                .collect()
+        };
+
+        for iteration in 0..MAX_ITERATIONS {
+            // The steps get smaller over time, so the entities settle instead of jumping around
+            let temperature = 1.0 - iteration as f64 / MAX_ITERATIONS as f64;
+            let step = (spread_distance * temperature).max(1.0);
+            let overlapping = separate(
+                &mut positions,
+                center,
+                spread_distance,
+                max_range,
+                step,
+                &mut rng(),
+            );
+
+            let mut unsafe_positions = Vec::new();
+            for (i, position) in positions.iter().enumerate() {
+                if !self.is_safe_spread_position(*position).await {
+                    unsafe_positions.push(i);
+                }
+            }
+            if !overlapping && unsafe_positions.is_empty() {
+                for (entity, position) in entities.into_iter().zip(positions) {
+                    let block_x = position.x.floor();
+                    let block_z = position.y.floor();
+                    let y = self
+                        .get_top_y(
+                            Vector2::new(block_x as i32, block_z as i32),
+                            HeightMap::MotionBlocking,
+                        )
+                        .await;
+                    entity
+                        .teleport(
+                            Some(Vector3::new(block_x + 0.5, f64::from(y), block_z + 0.5)),
+                            None,
+                            None,
+                            self.clone(),
+                        )
+                        .await;
+                }
+                return Ok(());
+            }
+
+            let mut rng = rng();
+            for i in unsafe_positions {
+                positions[i] = random_position(&mut rng, center, max_range);
+            }
+        }
+        Err(SpreadError::NoSafeLocation)
+    }
+
+    /// Whether an entity can stand on top of the column, i.e. it isn't on a liquid.
+    async fn is_safe_spread_position(&self, position: Vector2<f64>) -> bool {
+        let column = Vector2::new(position.x.floor() as i32, position.y.floor() as i32);
+        let top_y = self.get_top_y(column, HeightMap::MotionBlocking).await;
+        let ground = BlockPos(Vector3::new(column.x, top_y - 1, column.y));
+        !self.get_block_state(&ground).await.is_liquid()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::{MAX_ITERATIONS, random_position, separate};
+
+    #[test]
+    fn separates_entities_within_range() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let center = Vector2::new(100.0, -50.0);
+        let (spread_distance, max_range) = (4.0, 20.0);
+        let mut positions: Vec<_> = (0..10)
+            .map(|_| random_position(&mut rng, center, 2.0))
+            .collect();
+
+        let separated = (0..MAX_ITERATIONS).any(|_| {
+            !separate(
+                &mut positions,
+                center,
+                spread_distance,
+                max_range,
+                1.0,
+                &mut rng,
+            )
+        });
+        assert!(separated);
+
+        for (i, a) in positions.iter().enumerate() {
+            assert!(a.sub(&center).length() <= max_range + 1e-9);
+            for b in &positions[i + 1..] {
+                assert!(a.sub(b).length() >= spread_distance);
+            }
+        }
+    }
+
+    #[test]
+    fn entities_on_the_same_spot_are_separated() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let center = Vector2::new(0.0, 0.0);
+        let mut positions = vec![center, center];
+        assert!(separate(&mut positions, center, 2.0, 10.0, 2.0, &mut rng));
+        assert!(positions[0].sub(&positions[1]).length() > 0.0);
+    }
+}
@@ -24,15 +24,24 @@ impl LevelTime {
         }
     }
 
-    pub fn tick_time(&mut self) {
+    /// Advances the world age, the time of day only moves on while the daylight cycle runs.
+    pub fn tick_time(&mut self, daylight_cycle: bool) {
         self.world_age += 1;
-        self.time_of_day += 1;
+        if daylight_cycle {
+            self.time_of_day += 1;
+        }
         self.rain_time += 1;
     }
 
     pub async fn send_time(&self, world: &World) {
+        // Clients only advance the time themselves while the daylight cycle runs
+        let daylight_cycle = world.level_info.read().await.game_rules.do_daylight_cycle;
         world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
+            .broadcast_packet_all(&CUpdateTime::new(
+                self.world_age,
+                self.time_of_day,
+                daylight_cycle,
+            ))
             .await;
     }
 
@@ -59,3 +68,29 @@ impl LevelTime {
         self.time_of_day / 24000
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LevelTime;
+
+    #[test]
+    fn frozen_daylight_cycle_keeps_the_time_of_day() {
+        let mut time = LevelTime::new();
+        time.set_time(6000);
+        time.tick_time(false);
+        assert_eq!(time.world_age, 1);
+        assert_eq!(time.time_of_day, 6000);
+
+        time.tick_time(true);
+        assert_eq!(time.world_age, 2);
+        assert_eq!(time.time_of_day, 6001);
+    }
+
+    #[test]
+    fn queries_are_relative_to_the_day() {
+        let mut time = LevelTime::new();
+        time.set_time(3 * 24000 + 1000);
+        assert_eq!(time.query_daytime(), 1000);
+        assert_eq!(time.query_day(), 3);
+    }
+}
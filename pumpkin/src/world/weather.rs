@@ -1,5 +1,7 @@
 use super::World;
 use pumpkin_protocol::java::client::play::{CGameEvent, GameEvent};
+use pumpkin_registry::VanillaDimensionType;
+use pumpkin_world::world_info::LevelData;
 use rand::Rng;
 
 // Weather timing constants
@@ -52,6 +54,45 @@ impl Weather {
         }
     }
 
+    /// Restores the weather saved in `level.dat`, already fully raining or thundering if it was.
+    #[must_use]
+    pub fn read_from_level_data(level: &LevelData) -> Self {
+        let rain_level = if level.raining { 1.0 } else { 0.0 };
+        let thunder_level = if level.thundering { 1.0 } else { 0.0 };
+        Self {
+            clear_weather_time: level.clear_weather_time,
+            raining: level.raining,
+            rain_time: level.rain_time,
+            thundering: level.thundering,
+            thunder_time: level.thunder_time,
+            rain_level,
+            old_rain_level: rain_level,
+            thunder_level,
+            old_thunder_level: thunder_level,
+            weather_cycle_enabled: true,
+        }
+    }
+
+    /// The weather a dimension starts with. Like in vanilla, `level.dat` only holds the weather of
+    /// the overworld, the other dimensions start clear.
+    #[must_use]
+    pub fn load(dimension_type: VanillaDimensionType, level: &LevelData) -> Self {
+        if dimension_type == VanillaDimensionType::Overworld {
+            Self::read_from_level_data(level)
+        } else {
+            Self::new()
+        }
+    }
+
+    /// Stores the weather in `level.dat`, so it persists across restarts.
+    pub fn write_to_level_data(&self, level: &mut LevelData) {
+        level.clear_weather_time = self.clear_weather_time;
+        level.raining = self.raining;
+        level.rain_time = self.rain_time;
+        level.thundering = self.thundering;
+        level.thunder_time = self.thunder_time;
+    }
+
     pub async fn set_weather_parameters(
         &mut self,
         world: &World,
@@ -179,3 +220,57 @@ impl Clone for Weather {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_registry::VanillaDimensionType;
+    use pumpkin_world::world_info::{
+        LevelData, WorldInfoReader, WorldInfoWriter, anvil::AnvilLevelInfo,
+    };
+    use tempfile::tempdir;
+
+    use super::Weather;
+
+    #[test]
+    fn only_the_overworld_loads_the_saved_weather() {
+        let level = LevelData {
+            raining: true,
+            thundering: true,
+            ..Default::default()
+        };
+        assert!(Weather::load(VanillaDimensionType::Overworld, &level).raining);
+        for dimension in [
+            VanillaDimensionType::TheNether,
+            VanillaDimensionType::TheEnd,
+        ] {
+            let weather = Weather::load(dimension, &level);
+            assert!(!weather.raining && !weather.thundering);
+        }
+    }
+
+    #[test]
+    fn weather_survives_restart() {
+        let mut weather = Weather::new();
+        weather.raining = true;
+        weather.rain_time = 1234;
+        weather.thundering = true;
+        weather.thunder_time = 567;
+        weather.clear_weather_time = 0;
+
+        let temp_dir = tempdir().unwrap();
+        let mut level = LevelData::default();
+        weather.write_to_level_data(&mut level);
+        AnvilLevelInfo
+            .write_world_info(&level, temp_dir.path())
+            .unwrap();
+
+        let level = AnvilLevelInfo.read_world_info(temp_dir.path()).unwrap();
+        let loaded = Weather::read_from_level_data(&level);
+        assert_eq!(loaded.clear_weather_time, weather.clear_weather_time);
+        assert_eq!(loaded.raining, weather.raining);
+        assert_eq!(loaded.rain_time, weather.rain_time);
+        assert_eq!(loaded.thundering, weather.thundering);
+        assert_eq!(loaded.thunder_time, weather.thunder_time);
+        assert!((loaded.rain_level - 1.0).abs() < f32::EPSILON);
+    }
+}